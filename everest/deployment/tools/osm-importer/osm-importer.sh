@@ -10,14 +10,25 @@ PGUSER=${PGUSER:-postgres}
 PGPASSWORD=${PGPASSWORD:-password}
 PGDATABASE=${PGDATABASE:-ev_db}
 REGION=${REGION:-tunisia}
+# Comma-separated Geofabrik regions, e.g. africa/tunisia,africa/algeria
+REGIONS=${REGIONS:-$REGION}
 DATADIR=${DATADIR:-/osm/data}
-PBF=${PBF:-$DATADIR/${REGION}-latest.osm.pbf}
 STYLE=${STYLE:-/usr/local/bin/custom.style}
 HOST=download.geofabrik.de
 
-echo "📌 Starting OSM import for region: $REGION"
+IFS=',' read -r -a REGION_LIST <<< "$REGIONS"
+PBFS=()
+for region in "${REGION_LIST[@]}"; do
+  PBFS+=("$DATADIR/$(basename "$region")-latest.osm.pbf")
+done
+# A single extract can be given directly
+if [[ -n "$PBF" && ${#REGION_LIST[@]} -eq 1 ]]; then
+  PBFS=("$PBF")
+fi
+
+echo "📌 Starting OSM import for regions: $REGIONS"
 echo "Data directory: $DATADIR"
-echo "PBF file: $PBF"
+echo "PBF files: ${PBFS[*]}"
 
 # ----------------------------
 # Wait for PostGIS to be ready
@@ -36,18 +47,23 @@ psql -h "$PGHOST" -p "$PGPORT" -U "$PGUSER" -d "$PGDATABASE" -c "CREATE EXTENSIO
 # ----------------------------
 # Download PBF if missing
 # ----------------------------
-if [[ ! -f "$PBF" ]]; then
-  echo "🌐 Downloading $REGION OSM PBF from Geofabrik..."
-  mkdir -p "$DATADIR"
-  curl -L -o "$PBF" "https://${HOST}/${REGION}-latest.osm.pbf"
-else
-  echo "✅ Found local PBF at $PBF"
-fi
+for i in "${!REGION_LIST[@]}"; do
+  region=${REGION_LIST[$i]}
+  pbf=${PBFS[$i]}
+  if [[ ! -f "$pbf" ]]; then
+    echo "🌐 Downloading $region OSM PBF from Geofabrik..."
+    mkdir -p "$DATADIR"
+    curl -L -o "$pbf" "https://${HOST}/${region}-latest.osm.pbf"
+  else
+    echo "✅ Found local PBF at $pbf"
+  fi
+done
 
 # ----------------------------
 # Check if database is already imported
 # ----------------------------
-if psql -h "$PGHOST" -p "$PGPORT" -U "$PGUSER" -d "$PGDATABASE" -c "SELECT 1 FROM osm2pgsql_properties LIMIT 1;" > /dev/null 2>&1; then
+# Replication follows a single extract, several regions are reimported
+if [[ ${#PBFS[@]} -eq 1 ]] && psql -h "$PGHOST" -p "$PGPORT" -U "$PGUSER" -d "$PGDATABASE" -c "SELECT 1 FROM osm2pgsql_properties LIMIT 1;" > /dev/null 2>&1; then
   echo "🔄 Updating existing database with replication..."
   osm2pgsql-replication update \
     -v \
@@ -69,15 +85,19 @@ else
     --database "$PGDATABASE" \
     --user "$PGUSER" \
     --port "$PGPORT" \
-    "$PBF"  
+    "${PBFS[@]}"
 
-  echo "✅ Import complete. Initializing replication..."
-  osm2pgsql-replication init \
-    --host "$PGHOST" \
-    --database "$PGDATABASE" \
-    --user "$PGUSER" \
-    --port "$PGPORT" \
-    --osm-file "$PBF"
+  if [[ ${#PBFS[@]} -eq 1 ]]; then
+    echo "✅ Import complete. Initializing replication..."
+    osm2pgsql-replication init \
+      --host "$PGHOST" \
+      --database "$PGDATABASE" \
+      --user "$PGUSER" \
+      --port "$PGPORT" \
+      --osm-file "${PBFS[0]}"
+  else
+    echo "✅ Import complete. Replication is not set up for several regions."
+  fi
 fi
 
 echo "🎉 OSM import finished successfully."
\ No newline at end of file
@@ -0,0 +1,41 @@
+------------------------------------------------------------
+-- Tariffs
+------------------------------------------------------------
+
+CREATE TABLE tariffs (
+    tariff_id VARCHAR(32) PRIMARY KEY,
+    network_id VARCHAR(32) NOT NULL REFERENCES networks(network_id),
+    name VARCHAR(255) NOT NULL,
+    currency CHAR(3) NOT NULL DEFAULT 'TND',
+    vat_percent DECIMAL(5,2) NOT NULL DEFAULT 0 CHECK (vat_percent >= 0),
+    min_price DECIMAL(10,3) CHECK (min_price >= 0),
+    max_price DECIMAL(10,3) CHECK (max_price >= 0),
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by VARCHAR(36),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_by VARCHAR(36),
+    updated_at TIMESTAMPTZ,
+    CONSTRAINT tariff_price_caps CHECK (min_price IS NULL OR max_price IS NULL OR min_price <= max_price)
+);
+
+-- Prices are excluding VAT.
+--   ENERGY       price per kWh, step_size in Wh
+--   TIME         price per hour of charging, step_size in seconds
+--   PARKING_TIME price per hour of idle time, step_size in seconds
+--   FLAT         price per session, step_size unused
+CREATE TABLE tariff_components (
+    id SERIAL PRIMARY KEY,
+    tariff_id VARCHAR(32) NOT NULL REFERENCES tariffs(tariff_id) ON DELETE CASCADE,
+    component_type VARCHAR(20) NOT NULL CHECK (component_type IN ('ENERGY', 'TIME', 'PARKING_TIME', 'FLAT')),
+    price DECIMAL(10,4) NOT NULL CHECK (price >= 0),
+    step_size INTEGER NOT NULL DEFAULT 1 CHECK (step_size >= 1),
+    CONSTRAINT unique_tariff_component UNIQUE (tariff_id, component_type)
+);
+
+------------------------------------------------------------
+-- Indexes
+------------------------------------------------------------
+
+-- A network publishes at most one active tariff at a time
+CREATE UNIQUE INDEX idx_tariffs_active_network ON tariffs (network_id) WHERE is_active;
+CREATE INDEX idx_tariff_components_tariff_id ON tariff_components (tariff_id);
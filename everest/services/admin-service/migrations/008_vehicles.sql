@@ -0,0 +1,122 @@
+------------------------------------------------------------
+-- Vehicle Catalogue
+------------------------------------------------------------
+
+CREATE TABLE vehicle_models (
+    id SERIAL PRIMARY KEY,
+    make VARCHAR(100) NOT NULL,
+    model VARCHAR(100) NOT NULL,
+    battery_kwh DECIMAL(6,2) NOT NULL CHECK (battery_kwh > 0),
+    connector_types TEXT[] NOT NULL,
+    max_ac_kw DECIMAL(6,2) CHECK (max_ac_kw > 0),
+    max_dc_kw DECIMAL(6,2) CHECK (max_dc_kw > 0),
+    CONSTRAINT unique_vehicle_model UNIQUE (make, model)
+);
+
+------------------------------------------------------------
+-- User Vehicles
+------------------------------------------------------------
+
+CREATE TABLE user_vehicles (
+    vehicle_id VARCHAR(32) PRIMARY KEY,
+    user_id VARCHAR(36) NOT NULL,
+    model_id INTEGER REFERENCES vehicle_models(id),
+    nickname VARCHAR(100),
+    make VARCHAR(100) NOT NULL,
+    model VARCHAR(100) NOT NULL,
+    battery_kwh DECIMAL(6,2) NOT NULL CHECK (battery_kwh > 0),
+    connector_types TEXT[] NOT NULL CHECK (cardinality(connector_types) > 0),
+    max_ac_kw DECIMAL(6,2) CHECK (max_ac_kw > 0),
+    max_dc_kw DECIMAL(6,2) CHECK (max_dc_kw > 0),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_by VARCHAR(36),
+    updated_by VARCHAR(36)
+);
+
+CREATE INDEX idx_user_vehicles_user_id ON user_vehicles (user_id);
+
+------------------------------------------------------------
+-- Reference data
+------------------------------------------------------------
+
+INSERT INTO vehicle_models (make, model, battery_kwh, connector_types, max_ac_kw, max_dc_kw) VALUES
+('Tesla', 'Model 3 Standard Range', 57.5, ARRAY['type2', 'ccs'], 11.0, 170.0),
+('Tesla', 'Model Y Long Range', 75.0, ARRAY['type2', 'ccs'], 11.0, 250.0),
+('Renault', 'Zoe R135', 52.0, ARRAY['type2', 'ccs'], 22.0, 50.0),
+('Nissan', 'Leaf', 40.0, ARRAY['type2', 'chademo'], 6.6, 50.0),
+('Nissan', 'Leaf e+', 59.0, ARRAY['type2', 'chademo'], 6.6, 100.0),
+('Mitsubishi', 'Outlander PHEV', 13.8, ARRAY['type1', 'chademo'], 3.7, 22.0),
+('Volkswagen', 'ID.3 Pro', 58.0, ARRAY['type2', 'ccs'], 11.0, 120.0),
+('Volkswagen', 'ID.4 Pro', 77.0, ARRAY['type2', 'ccs'], 11.0, 135.0),
+('Hyundai', 'Kona Electric 64 kWh', 64.0, ARRAY['type2', 'ccs'], 11.0, 77.0),
+('Hyundai', 'Ioniq 5 Long Range', 77.4, ARRAY['type2', 'ccs'], 11.0, 235.0),
+('Kia', 'EV6 Long Range', 77.4, ARRAY['type2', 'ccs'], 11.0, 235.0),
+('Peugeot', 'e-208', 50.0, ARRAY['type2', 'ccs'], 11.0, 100.0),
+('MG', 'MG4 Standard', 51.0, ARRAY['type2', 'ccs'], 6.6, 117.0),
+('BYD', 'Atto 3', 60.5, ARRAY['type2', 'ccs'], 7.0, 88.0),
+('Dacia', 'Spring', 26.8, ARRAY['type2', 'ccs'], 7.0, 30.0);
+
+-- ============================
+-- Find nearby stations compatible with a vehicle
+-- ============================
+-- Only stations with at least one connector of a supported type are returned.
+-- effective_power_kw is the best power the vehicle can draw at the station:
+-- min(connector power_kw, vehicle max for the connector's current type).
+CREATE OR REPLACE FUNCTION find_nearby_compatible_stations(
+    p_latitude FLOAT,
+    p_longitude FLOAT,
+    p_radius_meters INTEGER,
+    p_limit INTEGER,
+    p_connector_types TEXT[],
+    p_max_ac_kw FLOAT DEFAULT NULL,
+    p_max_dc_kw FLOAT DEFAULT NULL
+) RETURNS TABLE(
+    station_id VARCHAR(32),
+    name VARCHAR,
+    address TEXT,
+    distance_meters FLOAT,
+    has_available_connectors BOOLEAN,
+    total_available_connectors BIGINT,
+    max_power_kw FLOAT,
+    power_tier TEXT,
+    operator TEXT,
+    latitude FLOAT,
+    longitude FLOAT,
+    effective_power_kw FLOAT
+) AS $$
+BEGIN
+    RETURN QUERY
+    SELECT
+        gs.station_id,
+        gs.name,
+        gs.address,
+        ST_Distance(gs.location, ST_Point(p_longitude, p_latitude)::GEOGRAPHY) AS distance_meters,
+        gs.has_available_connectors,
+        gs.total_available_connectors,
+        gs.max_power_kw::FLOAT,
+        gs.power_tier,
+        gs.operator,
+        ST_Y(gs.location::GEOMETRY)::FLOAT AS latitude,
+        ST_X(gs.location::GEOMETRY)::FLOAT AS longitude,
+        compat.effective_power_kw
+    FROM mv_stations_geo gs
+    CROSS JOIN LATERAL (
+        SELECT
+            COUNT(*) AS compatible_connectors,
+            MAX(
+                CASE
+                    WHEN c->>'power_kw' IS NULL THEN NULL
+                    WHEN c->>'current_type_name' = 'DC' THEN LEAST((c->>'power_kw')::FLOAT, p_max_dc_kw)
+                    ELSE LEAST((c->>'power_kw')::FLOAT, p_max_ac_kw)
+                END
+            ) AS effective_power_kw
+        FROM jsonb_array_elements(COALESCE(gs.connectors, '[]'::jsonb)) c
+        WHERE c->>'type_name' = ANY(p_connector_types)
+    ) compat
+    WHERE ST_DWithin(gs.location, ST_Point(p_longitude, p_latitude)::GEOGRAPHY, p_radius_meters)
+    AND compat.compatible_connectors > 0
+    ORDER BY ST_Distance(gs.location, ST_Point(p_longitude, p_latitude)::GEOGRAPHY)
+    LIMIT p_limit;
+END;
+$$ LANGUAGE plpgsql;
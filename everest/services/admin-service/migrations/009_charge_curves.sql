@@ -0,0 +1,68 @@
+------------------------------------------------------------
+-- Vehicle Charging Characteristics
+------------------------------------------------------------
+-- charge_curve is the DC charging curve as a JSON array of
+-- {"soc_percent": <0-100>, "power_kw": <kW>} points sorted by soc_percent.
+-- ac_phases is the number of phases the on-board charger can use (1 or 3).
+
+ALTER TABLE vehicle_models
+    ADD COLUMN ac_phases SMALLINT NOT NULL DEFAULT 3 CHECK (ac_phases IN (1, 3)),
+    ADD COLUMN charge_curve JSONB CHECK (jsonb_typeof(charge_curve) = 'array');
+
+ALTER TABLE user_vehicles
+    ADD COLUMN ac_phases SMALLINT NOT NULL DEFAULT 3 CHECK (ac_phases IN (1, 3)),
+    ADD COLUMN charge_curve JSONB CHECK (jsonb_typeof(charge_curve) = 'array');
+
+------------------------------------------------------------
+-- Reference data
+------------------------------------------------------------
+
+UPDATE vehicle_models SET ac_phases = 1 WHERE (make, model) IN (
+    ('Nissan', 'Leaf'),
+    ('Nissan', 'Leaf e+'),
+    ('Mitsubishi', 'Outlander PHEV'),
+    ('MG', 'MG4 Standard'),
+    ('BYD', 'Atto 3'),
+    ('Dacia', 'Spring')
+);
+
+UPDATE vehicle_models SET charge_curve = '[
+    {"soc_percent": 0, "power_kw": 100}, {"soc_percent": 10, "power_kw": 170},
+    {"soc_percent": 40, "power_kw": 120}, {"soc_percent": 70, "power_kw": 60},
+    {"soc_percent": 90, "power_kw": 25}, {"soc_percent": 100, "power_kw": 8}
+]' WHERE make = 'Tesla' AND model = 'Model 3 Standard Range';
+
+UPDATE vehicle_models SET charge_curve = '[
+    {"soc_percent": 0, "power_kw": 150}, {"soc_percent": 10, "power_kw": 250},
+    {"soc_percent": 30, "power_kw": 160}, {"soc_percent": 60, "power_kw": 90},
+    {"soc_percent": 80, "power_kw": 45}, {"soc_percent": 100, "power_kw": 10}
+]' WHERE make = 'Tesla' AND model = 'Model Y Long Range';
+
+UPDATE vehicle_models SET charge_curve = '[
+    {"soc_percent": 0, "power_kw": 46}, {"soc_percent": 40, "power_kw": 46},
+    {"soc_percent": 60, "power_kw": 35}, {"soc_percent": 80, "power_kw": 22},
+    {"soc_percent": 100, "power_kw": 6}
+]' WHERE make = 'Renault' AND model = 'Zoe R135';
+
+UPDATE vehicle_models SET charge_curve = '[
+    {"soc_percent": 0, "power_kw": 45}, {"soc_percent": 55, "power_kw": 42},
+    {"soc_percent": 80, "power_kw": 22}, {"soc_percent": 100, "power_kw": 5}
+]' WHERE make = 'Nissan' AND model = 'Leaf';
+
+UPDATE vehicle_models SET charge_curve = '[
+    {"soc_percent": 0, "power_kw": 235}, {"soc_percent": 50, "power_kw": 220},
+    {"soc_percent": 80, "power_kw": 150}, {"soc_percent": 90, "power_kw": 60},
+    {"soc_percent": 100, "power_kw": 10}
+]' WHERE (make, model) IN (('Hyundai', 'Ioniq 5 Long Range'), ('Kia', 'EV6 Long Range'));
+
+UPDATE vehicle_models SET charge_curve = '[
+    {"soc_percent": 0, "power_kw": 115}, {"soc_percent": 35, "power_kw": 120},
+    {"soc_percent": 60, "power_kw": 80}, {"soc_percent": 80, "power_kw": 50},
+    {"soc_percent": 100, "power_kw": 10}
+]' WHERE make = 'Volkswagen' AND model = 'ID.3 Pro';
+
+-- Vehicles created before this migration inherit the catalogue values
+UPDATE user_vehicles uv
+SET ac_phases = vm.ac_phases, charge_curve = vm.charge_curve
+FROM vehicle_models vm
+WHERE uv.model_id = vm.id;
@@ -0,0 +1,13 @@
+------------------------------------------------------------
+-- One review per user per station
+------------------------------------------------------------
+
+-- Keep only the most recent review when a user reviewed a station several times
+DELETE FROM user_reviews r
+USING user_reviews newer
+WHERE r.user_id = newer.user_id
+  AND r.station_id = newer.station_id
+  AND (r.updated_at, r.review_id) < (newer.updated_at, newer.review_id);
+
+ALTER TABLE user_reviews
+    ADD CONSTRAINT unique_user_station_review UNIQUE (user_id, station_id);
@@ -0,0 +1,115 @@
+------------------------------------------------------------
+-- Review Moderation
+------------------------------------------------------------
+
+ALTER TABLE user_reviews
+    ADD COLUMN moderation_status VARCHAR(20) NOT NULL DEFAULT 'visible'
+        CHECK (moderation_status IN ('visible', 'pending', 'hidden')),
+    ADD COLUMN moderation_reason TEXT,
+    ADD COLUMN moderated_by VARCHAR(36),
+    ADD COLUMN moderated_at TIMESTAMPTZ;
+
+CREATE INDEX idx_user_reviews_moderation_status ON user_reviews (moderation_status);
+
+CREATE TABLE review_flags (
+    flag_id VARCHAR(32) PRIMARY KEY,
+    review_id VARCHAR(32) NOT NULL REFERENCES user_reviews(review_id) ON DELETE CASCADE,
+    user_id VARCHAR(36) NOT NULL,
+    reason VARCHAR(30) NOT NULL
+        CHECK (reason IN ('spam', 'offensive', 'off_topic', 'false_information', 'other')),
+    comment TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- Set when an admin acts on the review
+    resolved_at TIMESTAMPTZ,
+    resolved_by VARCHAR(36),
+    CONSTRAINT unique_review_flag UNIQUE (review_id, user_id)
+);
+
+CREATE INDEX idx_review_flags_open ON review_flags (review_id) WHERE resolved_at IS NULL;
+
+-- ============================
+-- Stations + Reviews View (visible reviews only)
+-- ============================
+DROP MATERIALIZED VIEW IF EXISTS mv_stations_reviews;
+
+CREATE MATERIALIZED VIEW mv_stations_reviews AS
+SELECT
+    s.station_id,
+    s.name AS station_name,
+    s.address,
+    s.location,
+    ST_X(s.location::geometry) AS longitude,
+    ST_Y(s.location::geometry) AS latitude,
+
+    -- Connector summary
+    EXISTS (
+        SELECT 1
+        FROM connectors c
+        WHERE c.station_id = s.station_id AND c.count_available > 0
+    ) AS has_available_connectors,
+
+    (
+        SELECT jsonb_agg(
+            jsonb_build_object(
+                'connector_id', c.connector_id,
+                'type_id', c.connector_type_id,
+                'type_name', ct.name,
+                'status_id', c.status_id,
+                'status_name', cs.name,
+                'current_type_id', c.current_type_id,
+                'current_type_name', cur.name,
+                'power_kw', c.power_kw,
+                'voltage', c.voltage,
+                'amperage', c.amperage,
+                'available', c.count_available,
+                'total', c.count_total
+            ) ORDER BY c.power_kw DESC NULLS LAST
+        )
+        FROM connectors c
+        LEFT JOIN connector_types ct ON c.connector_type_id = ct.id
+        LEFT JOIN connector_statuses cs ON c.status_id = cs.id
+        LEFT JOIN current_types cur ON c.current_type_id = cur.id
+        WHERE c.station_id = s.station_id
+    ) AS connectors,
+
+    -- Reviews summary
+    (
+        SELECT AVG(r.rating)
+        FROM user_reviews r
+        WHERE r.station_id = s.station_id AND r.moderation_status = 'visible'
+    ) AS avg_rating,
+
+    (
+        SELECT COUNT(*)
+        FROM user_reviews r
+        WHERE r.station_id = s.station_id AND r.moderation_status = 'visible'
+    ) AS total_reviews,
+
+    -- Detailed reviews as JSON
+    (
+        SELECT jsonb_agg(
+            jsonb_build_object(
+                'review_id', r.review_id,
+                'user_id', r.user_id,
+                'rating', r.rating,
+                'review_text', r.review_text,
+                'created_at', r.created_at,
+                'updated_at', r.updated_at
+            ) ORDER BY r.created_at DESC
+        )
+        FROM user_reviews r
+        WHERE r.station_id = s.station_id AND r.moderation_status = 'visible'
+    ) AS reviews,
+
+    s.tags,
+    s.created_at,
+    s.updated_at
+
+FROM stations s
+WHERE s.location IS NOT NULL
+WITH DATA;
+
+CREATE UNIQUE INDEX idx_mv_stations_reviews_id ON mv_stations_reviews (station_id);
+CREATE INDEX idx_mv_stations_reviews_location_gist ON mv_stations_reviews USING GIST (location);
+CREATE INDEX idx_mv_stations_reviews_rating ON mv_stations_reviews (avg_rating);
+CREATE INDEX idx_mv_stations_reviews_available ON mv_stations_reviews (has_available_connectors);
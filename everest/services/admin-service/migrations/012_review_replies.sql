@@ -0,0 +1,32 @@
+------------------------------------------------------------
+-- Operator Replies
+------------------------------------------------------------
+-- One public reply per review, written on behalf of the station's network.
+
+CREATE TABLE review_replies (
+    reply_id VARCHAR(32) PRIMARY KEY,
+    review_id VARCHAR(32) NOT NULL UNIQUE REFERENCES user_reviews(review_id) ON DELETE CASCADE,
+    network_id VARCHAR(32) NOT NULL REFERENCES networks(network_id),
+    author_id VARCHAR(36) NOT NULL,
+    reply_text TEXT NOT NULL CHECK (length(trim(reply_text)) > 0),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_by VARCHAR(36),
+    updated_by VARCHAR(36)
+);
+
+------------------------------------------------------------
+-- User Notifications
+------------------------------------------------------------
+
+CREATE TABLE user_notifications (
+    notification_id VARCHAR(32) PRIMARY KEY,
+    user_id VARCHAR(36) NOT NULL,
+    kind VARCHAR(30) NOT NULL CHECK (kind IN ('review_reply', 'review_reply_updated')),
+    message TEXT NOT NULL,
+    review_id VARCHAR(32) REFERENCES user_reviews(review_id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    read_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_user_notifications_user ON user_notifications (user_id, created_at DESC);
@@ -0,0 +1,14 @@
+------------------------------------------------------------
+-- Review Helpfulness Votes
+------------------------------------------------------------
+
+CREATE TABLE review_votes (
+    review_id VARCHAR(32) NOT NULL REFERENCES user_reviews(review_id) ON DELETE CASCADE,
+    user_id VARCHAR(36) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (review_id, user_id)
+);
+
+CREATE INDEX idx_user_reviews_station_visible
+    ON user_reviews (station_id, created_at DESC)
+    WHERE moderation_status = 'visible';
@@ -0,0 +1,31 @@
+------------------------------------------------------------
+-- Connector Issue Reports
+------------------------------------------------------------
+-- Reported by drivers, handled as tickets by the network operating the
+-- station. Lifecycle: open -> acknowledged -> resolved.
+
+CREATE TABLE issue_reports (
+    issue_id VARCHAR(32) PRIMARY KEY,
+    station_id VARCHAR(32) NOT NULL REFERENCES stations(station_id) ON DELETE CASCADE,
+    connector_id VARCHAR(32) NOT NULL REFERENCES connectors(connector_id) ON DELETE CASCADE,
+    network_id VARCHAR(32) REFERENCES networks(network_id),
+    reporter_id VARCHAR(36) NOT NULL,
+    category VARCHAR(20) NOT NULL CHECK (category IN (
+        'wont_start', 'damaged_cable', 'blocked_by_ice', 'payment_failed', 'slow_charging', 'other'
+    )),
+    description TEXT,
+    photo_url TEXT,
+    status VARCHAR(20) NOT NULL DEFAULT 'open' CHECK (status IN ('open', 'acknowledged', 'resolved')),
+    resolution_note TEXT,
+    acknowledged_by VARCHAR(36),
+    acknowledged_at TIMESTAMPTZ,
+    resolved_by VARCHAR(36),
+    resolved_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_by VARCHAR(36)
+);
+
+CREATE INDEX idx_issue_reports_network_status ON issue_reports (network_id, status, created_at DESC);
+CREATE INDEX idx_issue_reports_connector ON issue_reports (connector_id);
+CREATE INDEX idx_issue_reports_reporter ON issue_reports (reporter_id, created_at DESC);
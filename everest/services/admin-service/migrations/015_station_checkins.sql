@@ -0,0 +1,20 @@
+------------------------------------------------------------
+-- Driver Check-ins
+------------------------------------------------------------
+-- What drivers found when they got to a station. Used to estimate real
+-- availability where there is no live telemetry.
+
+CREATE TABLE station_checkins (
+    checkin_id VARCHAR(32) PRIMARY KEY,
+    station_id VARCHAR(32) NOT NULL REFERENCES stations(station_id) ON DELETE CASCADE,
+    connector_id VARCHAR(32) REFERENCES connectors(connector_id) ON DELETE SET NULL,
+    user_id VARCHAR(36) NOT NULL,
+    outcome VARCHAR(20) NOT NULL CHECK (outcome IN ('charged_ok', 'all_occupied', 'not_working')),
+    comment TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_station_checkins_station ON station_checkins (station_id, created_at DESC);
+CREATE INDEX idx_station_checkins_success ON station_checkins (station_id, created_at DESC)
+    WHERE outcome = 'charged_ok';
+CREATE INDEX idx_station_checkins_user ON station_checkins (user_id, station_id, created_at DESC);
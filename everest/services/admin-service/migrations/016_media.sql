@@ -0,0 +1,23 @@
+------------------------------------------------------------
+-- Station and Review Photos
+------------------------------------------------------------
+-- Files live in object storage (local directory or S3-compatible bucket);
+-- only their keys are stored here. Review photos also carry the reviewed
+-- station so that a station's gallery can include them.
+
+CREATE TABLE media (
+    media_id VARCHAR(32) PRIMARY KEY,
+    station_id VARCHAR(32) NOT NULL REFERENCES stations(station_id) ON DELETE CASCADE,
+    review_id VARCHAR(32) REFERENCES user_reviews(review_id) ON DELETE CASCADE,
+    uploaded_by VARCHAR(36) NOT NULL,
+    content_type VARCHAR(50) NOT NULL,
+    width INT NOT NULL CHECK (width > 0),
+    height INT NOT NULL CHECK (height > 0),
+    size_bytes BIGINT NOT NULL CHECK (size_bytes > 0),
+    storage_key TEXT NOT NULL UNIQUE,
+    thumbnail_key TEXT NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_media_station ON media (station_id, created_at DESC);
+CREATE INDEX idx_media_review ON media (review_id) WHERE review_id IS NOT NULL;
@@ -0,0 +1,95 @@
+------------------------------------------------------------
+-- Station Amenities
+------------------------------------------------------------
+-- Points of interest around a station, read from the planet_osm_point and
+-- planet_osm_polygon tables loaded by the osm-importer (osm2pgsql, default
+-- Web Mercator projection, --hstore). PL/pgSQL so that the function can be
+-- created before the first import has run.
+
+CREATE OR REPLACE FUNCTION osm_amenity_category(
+    p_amenity TEXT,
+    p_shop TEXT,
+    p_tourism TEXT
+) RETURNS TEXT AS $$
+    SELECT CASE
+        WHEN p_amenity = 'cafe' THEN 'cafe'
+        WHEN p_amenity IN ('restaurant', 'fast_food', 'food_court') THEN 'restaurant'
+        WHEN p_amenity = 'toilets' THEN 'toilets'
+        WHEN p_shop IN ('supermarket', 'convenience') THEN 'supermarket'
+        WHEN p_tourism IN ('hotel', 'motel', 'guest_house', 'hostel') THEN 'hotel'
+    END;
+$$ LANGUAGE sql IMMUTABLE;
+
+-- Amenities within p_radius_meters of the station, closest first. Polygons
+-- (e.g. a supermarket building) are located at a point on their surface.
+CREATE OR REPLACE FUNCTION find_station_amenities(
+    p_station_id VARCHAR(32),
+    p_radius_meters INTEGER DEFAULT 500
+) RETURNS TABLE(
+    osm_id BIGINT,
+    osm_type TEXT,
+    category TEXT,
+    kind TEXT,
+    name TEXT,
+    opening_hours TEXT,
+    distance_meters FLOAT,
+    latitude FLOAT,
+    longitude FLOAT
+) AS $$
+#variable_conflict use_column
+DECLARE
+    v_location GEOGRAPHY;
+    v_origin GEOMETRY;
+    v_search_radius FLOAT;
+BEGIN
+    SELECT s.location INTO v_location FROM stations s WHERE s.station_id = p_station_id;
+    IF v_location IS NULL THEN
+        RETURN;
+    END IF;
+
+    -- Web Mercator stretches distances by 1 / cos(latitude); search the
+    -- stretched radius on the GIST index, then filter on the true distance
+    v_origin := ST_Transform(v_location::GEOMETRY, 3857);
+    v_search_radius := p_radius_meters / COS(RADIANS(ST_Y(v_location::GEOMETRY)));
+
+    RETURN QUERY
+    WITH candidates AS (
+        SELECT
+            p.osm_id,
+            'node'::TEXT AS osm_type,
+            osm_amenity_category(p.amenity, p.shop, p.tourism) AS category,
+            COALESCE(p.amenity, p.shop, p.tourism) AS kind,
+            p.name,
+            p.tags -> 'opening_hours' AS opening_hours,
+            ST_Transform(p.way, 4326) AS geom
+        FROM planet_osm_point p
+        WHERE ST_DWithin(p.way, v_origin, v_search_radius)
+          AND osm_amenity_category(p.amenity, p.shop, p.tourism) IS NOT NULL
+        UNION ALL
+        SELECT
+            ABS(p.osm_id),
+            CASE WHEN p.osm_id < 0 THEN 'relation' ELSE 'way' END,
+            osm_amenity_category(p.amenity, p.shop, p.tourism),
+            COALESCE(p.amenity, p.shop, p.tourism),
+            p.name,
+            p.tags -> 'opening_hours',
+            ST_Transform(ST_PointOnSurface(p.way), 4326)
+        FROM planet_osm_polygon p
+        WHERE ST_DWithin(p.way, v_origin, v_search_radius)
+          AND osm_amenity_category(p.amenity, p.shop, p.tourism) IS NOT NULL
+    )
+    SELECT
+        c.osm_id,
+        c.osm_type,
+        c.category,
+        c.kind,
+        c.name,
+        c.opening_hours,
+        ST_Distance(c.geom::GEOGRAPHY, v_location)::FLOAT,
+        ST_Y(c.geom)::FLOAT,
+        ST_X(c.geom)::FLOAT
+    FROM candidates c
+    WHERE ST_DWithin(c.geom::GEOGRAPHY, v_location, p_radius_meters)
+    ORDER BY ST_Distance(c.geom::GEOGRAPHY, v_location);
+END;
+$$ LANGUAGE plpgsql STABLE;
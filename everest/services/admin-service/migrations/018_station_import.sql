@@ -0,0 +1,20 @@
+------------------------------------------------------------
+-- Bulk Station Import
+------------------------------------------------------------
+-- Partner sites that are not in OpenStreetMap still need an osm_id, which is
+-- NOT NULL and UNIQUE. They draw negative ids from this sequence so they can
+-- never collide with a real OSM node picked up by the sync.
+
+CREATE SEQUENCE IF NOT EXISTS imported_station_osm_id_seq
+    INCREMENT BY -1
+    MINVALUE -9223372036854775808
+    MAXVALUE -1
+    START WITH -1;
+
+-- The partner's own identifier for the site, so that re-importing the same
+-- file is reported instead of creating the stations twice.
+ALTER TABLE stations ADD COLUMN IF NOT EXISTS external_ref VARCHAR(64);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_stations_network_external_ref
+    ON stations (network_id, external_ref)
+    WHERE external_ref IS NOT NULL;
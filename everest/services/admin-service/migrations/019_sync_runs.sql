@@ -0,0 +1,288 @@
+------------------------------------------------------------
+-- OSM Sync Runs
+------------------------------------------------------------
+-- One row per execution of sync_osm_charging_stations(), whether triggered
+-- by an admin or by the admin-service schedule. At most one run can be in
+-- progress at a time.
+
+CREATE TABLE IF NOT EXISTS sync_runs (
+    run_id VARCHAR(32) PRIMARY KEY,
+    trigger_type VARCHAR(20) NOT NULL CHECK (trigger_type IN ('manual', 'scheduled')),
+    status VARCHAR(20) NOT NULL DEFAULT 'running'
+        CHECK (status IN ('running', 'succeeded', 'failed')),
+    triggered_by VARCHAR(36),
+    started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    finished_at TIMESTAMPTZ,
+    inserted_count INTEGER,
+    updated_count INTEGER,
+    deactivated_count INTEGER,
+    error TEXT
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_sync_runs_single_running
+    ON sync_runs ((status))
+    WHERE status = 'running';
+
+CREATE INDEX IF NOT EXISTS idx_sync_runs_started_at ON sync_runs (started_at DESC);
+
+-- OSM stations that disappeared from the extract are kept, with their
+-- reviews and history, but marked as deactivated.
+ALTER TABLE stations ADD COLUMN IF NOT EXISTS deactivated_at TIMESTAMPTZ;
+
+-- ============================
+-- Helpers
+-- ============================
+
+-- Same shape as the ids generated by the services: PREFIX-<16 chars>
+CREATE OR REPLACE FUNCTION generate_prefixed_id(p_prefix TEXT) RETURNS TEXT AS $$
+    SELECT p_prefix || '-' || SUBSTR(REPLACE(gen_random_uuid()::TEXT, '-', ''), 1, 16);
+$$ LANGUAGE sql VOLATILE;
+
+CREATE OR REPLACE FUNCTION refresh_charging_station_views() RETURNS VOID AS $$
+BEGIN
+    REFRESH MATERIALIZED VIEW mv_stations_geo;
+    REFRESH MATERIALIZED VIEW mv_stations_summary;
+    REFRESH MATERIALIZED VIEW mv_connector_type_stats;
+END;
+$$ LANGUAGE plpgsql;
+
+-- ============================
+-- Connectors from OSM tags
+-- ============================
+-- Unchanged except for the connector ids, which used to be 'CHR' || uuid and
+-- did not fit in connectors.connector_id.
+
+CREATE OR REPLACE FUNCTION extract_connectors_from_osm_tags(
+    p_station_id VARCHAR(32),
+    p_tags HSTORE,
+    p_user_id VARCHAR(36)
+) RETURNS INTEGER AS $$
+DECLARE
+    v_connector_count INTEGER := 0;
+    v_count_total INTEGER;
+    v_power_kw DECIMAL(5,2);
+BEGIN
+    -- Clear existing connectors for this station
+    DELETE FROM connectors WHERE station_id = p_station_id;
+
+    -- Type2 AC
+    IF p_tags ? 'socket:type2' AND p_tags->'socket:type2' ~ '^\d+$' THEN
+        v_count_total := (p_tags->'socket:type2')::INTEGER;
+        v_power_kw := NULLIF(p_tags->'socket:type2:output', '')::DECIMAL;
+        INSERT INTO connectors (
+            connector_id, station_id, connector_type_id, status_id, current_type_id,
+            power_kw, count_total, count_available, created_by, created_at
+        ) VALUES (
+            generate_prefixed_id('CON'),
+            p_station_id,
+            (SELECT id FROM connector_types WHERE name = 'type2'),
+            1,  -- Available
+            (SELECT id FROM current_types WHERE name = 'AC'),
+            COALESCE(v_power_kw, 22.0),
+            v_count_total,
+            v_count_total,
+            p_user_id,
+            NOW()
+        );
+        v_connector_count := v_connector_count + 1;
+    END IF;
+
+    -- CCS DC
+    IF p_tags ? 'socket:ccs' AND p_tags->'socket:ccs' ~ '^\d+$' THEN
+        v_count_total := (p_tags->'socket:ccs')::INTEGER;
+        v_power_kw := NULLIF(p_tags->'socket:ccs:output', '')::DECIMAL;
+        INSERT INTO connectors (
+            connector_id, station_id, connector_type_id, status_id, current_type_id,
+            power_kw, count_total, count_available, created_by, created_at
+        ) VALUES (
+            generate_prefixed_id('CON'),
+            p_station_id,
+            (SELECT id FROM connector_types WHERE name = 'ccs'),
+            1,
+            (SELECT id FROM current_types WHERE name = 'DC'),
+            COALESCE(v_power_kw, 50.0),
+            v_count_total,
+            v_count_total,
+            p_user_id,
+            NOW()
+        );
+        v_connector_count := v_connector_count + 1;
+    END IF;
+
+    -- CHAdeMO DC
+    IF p_tags ? 'socket:chademo' AND p_tags->'socket:chademo' ~ '^\d+$' THEN
+        v_count_total := (p_tags->'socket:chademo')::INTEGER;
+        v_power_kw := NULLIF(p_tags->'socket:chademo:output', '')::DECIMAL;
+        INSERT INTO connectors (
+            connector_id, station_id, connector_type_id, status_id, current_type_id,
+            power_kw, count_total, count_available, created_by, created_at
+        ) VALUES (
+            generate_prefixed_id('CON'),
+            p_station_id,
+            (SELECT id FROM connector_types WHERE name = 'chademo'),
+            1,
+            (SELECT id FROM current_types WHERE name = 'DC'),
+            COALESCE(v_power_kw, 50.0),
+            v_count_total,
+            v_count_total,
+            p_user_id,
+            NOW()
+        );
+        v_connector_count := v_connector_count + 1;
+    END IF;
+
+    RETURN v_connector_count;
+END;
+$$ LANGUAGE plpgsql;
+
+-- ============================
+-- Main OSM sync function
+-- ============================
+-- Connectors are rebuilt for exactly the stations this run inserted or
+-- updated (previously: any station touched in the last 5 minutes). OSM
+-- stations missing from the staging table are deactivated, and come back
+-- when they reappear. Partner-imported stations (external_ref) are left
+-- alone. Refreshing the materialized views is up to the caller.
+
+DROP FUNCTION IF EXISTS sync_osm_charging_stations(VARCHAR);
+
+CREATE FUNCTION sync_osm_charging_stations(
+    p_user_id VARCHAR(36) DEFAULT NULL
+) RETURNS TABLE(
+    updated_count INTEGER,
+    inserted_count INTEGER,
+    deactivated_count INTEGER
+) AS $$
+DECLARE
+    v_user_id VARCHAR(36) := COALESCE(p_user_id, 'USR000000000000000000000000000000');
+    v_updated_ids VARCHAR(32)[];
+    v_inserted_ids VARCHAR(32)[];
+    v_deactivated_count INTEGER := 0;
+    v_station_id VARCHAR(32);
+    v_tags HSTORE;
+BEGIN
+    -- An empty extract would deactivate every OSM station
+    IF NOT EXISTS (SELECT 1 FROM osm_charging_stations_temp) THEN
+        RAISE EXCEPTION 'osm_charging_stations_temp is empty, run the OSM import first';
+    END IF;
+
+    -- Update existing stations
+    WITH updated AS (
+        UPDATE stations s
+        SET
+            name = COALESCE(osm.name, s.name),
+            address = COALESCE(osm.address, s.address),
+            tags = s.tags || hstore(array[
+                ['operator', osm.operator],
+                ['opening_hours', osm.opening_hours],
+                ['capacity', osm.capacity::text],
+                ['fee', osm.fee],
+                ['parking_fee', osm.parking_fee],
+                ['access', osm.access],
+                ['socket:type2', osm.socket_type2::text],
+                ['socket:ccs', osm.socket_ccs::text],
+                ['socket:chademo', osm.socket_chademo::text],
+                ['socket:type2:output', osm.socket_type2_output::text],
+                ['socket:ccs:output', osm.socket_ccs_output::text],
+                ['socket:chademo:output', osm.socket_chademo_output::text]
+            ]) || osm.tags,
+            deactivated_at = NULL,
+            updated_by = v_user_id,
+            updated_at = NOW()
+        FROM osm_charging_stations_temp osm
+        WHERE s.osm_id = osm.osm_id
+        AND (
+            s.deactivated_at IS NOT NULL OR
+            s.name IS DISTINCT FROM osm.name OR
+            s.address IS DISTINCT FROM osm.address OR
+            s.tags IS DISTINCT FROM (
+                hstore(array[
+                    ['operator', osm.operator],
+                    ['opening_hours', osm.opening_hours],
+                    ['capacity', osm.capacity::text],
+                    ['fee', osm.fee],
+                    ['parking_fee', osm.parking_fee],
+                    ['access', osm.access],
+                    ['socket:type2', osm.socket_type2::text],
+                    ['socket:ccs', osm.socket_ccs::text],
+                    ['socket:chademo', osm.socket_chademo::text],
+                    ['socket:type2:output', osm.socket_type2_output::text],
+                    ['socket:ccs:output', osm.socket_ccs_output::text],
+                    ['socket:chademo:output', osm.socket_chademo_output::text]
+                ]) || osm.tags
+            )
+        )
+        RETURNING s.station_id
+    )
+    SELECT COALESCE(array_agg(station_id), '{}') INTO v_updated_ids FROM updated;
+
+    -- Insert new stations
+    WITH inserted AS (
+        INSERT INTO stations (
+            station_id, osm_id, name, address, location, tags, created_by, created_at
+        )
+        SELECT
+            generate_prefixed_id('STA'),
+            osm.osm_id,
+            osm.name,
+            osm.address,
+            osm.geom::GEOGRAPHY,
+            hstore(array[
+                ['operator', osm.operator],
+                ['opening_hours', osm.opening_hours],
+                ['capacity', osm.capacity::text],
+                ['fee', osm.fee],
+                ['parking_fee', osm.parking_fee],
+                ['access', osm.access],
+                ['socket:type2', osm.socket_type2::text],
+                ['socket:ccs', osm.socket_ccs::text],
+                ['socket:chademo', osm.socket_chademo::text],
+                ['socket:type2:output', osm.socket_type2_output::text],
+                ['socket:ccs:output', osm.socket_ccs_output::text],
+                ['socket:chademo:output', osm.socket_chademo_output::text]
+            ]) || osm.tags,
+            v_user_id,
+            NOW()
+        FROM osm_charging_stations_temp osm
+        WHERE NOT EXISTS (
+            SELECT 1 FROM stations WHERE osm_id = osm.osm_id
+        )
+        RETURNING station_id
+    )
+    SELECT COALESCE(array_agg(station_id), '{}') INTO v_inserted_ids FROM inserted;
+
+    -- Rebuild connectors of the stations inserted or updated above
+    FOR v_station_id, v_tags IN
+        SELECT s.station_id, s.tags
+        FROM stations s
+        WHERE s.station_id = ANY(v_updated_ids || v_inserted_ids)
+    LOOP
+        PERFORM extract_connectors_from_osm_tags(v_station_id, v_tags, v_user_id);
+    END LOOP;
+
+    -- Deactivate OSM stations that are no longer in the extract
+    WITH deactivated AS (
+        UPDATE stations s
+        SET
+            deactivated_at = NOW(),
+            updated_by = v_user_id,
+            updated_at = NOW()
+        WHERE s.osm_id > 0
+          AND s.external_ref IS NULL
+          AND s.deactivated_at IS NULL
+          AND NOT EXISTS (
+              SELECT 1 FROM osm_charging_stations_temp osm WHERE osm.osm_id = s.osm_id
+          )
+        RETURNING s.station_id
+    )
+    SELECT COUNT(*) INTO v_deactivated_count FROM deactivated;
+
+    RETURN QUERY SELECT
+        CARDINALITY(v_updated_ids),
+        CARDINALITY(v_inserted_ids),
+        v_deactivated_count;
+
+EXCEPTION WHEN OTHERS THEN
+    RAISE EXCEPTION 'OSM sync failed: %', SQLERRM;
+END;
+$$ LANGUAGE plpgsql;
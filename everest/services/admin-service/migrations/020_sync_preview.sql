@@ -0,0 +1,209 @@
+------------------------------------------------------------
+-- OSM Sync Preview
+------------------------------------------------------------
+-- The preview in admin-service and the sync itself must agree on what a
+-- staged row turns into, so the tag merge and the connector mapping live in
+-- functions both use. A sync can skip osm_ids that an admin rejected after
+-- reviewing the preview.
+
+ALTER TABLE sync_runs ADD COLUMN IF NOT EXISTS skipped_osm_ids BIGINT[] NOT NULL DEFAULT '{}';
+
+-- Tags a staged row contributes to its station
+CREATE OR REPLACE FUNCTION osm_station_tags(osm osm_charging_stations_temp) RETURNS HSTORE AS $$
+    SELECT hstore(array[
+        ['operator', osm.operator],
+        ['opening_hours', osm.opening_hours],
+        ['capacity', osm.capacity::text],
+        ['fee', osm.fee],
+        ['parking_fee', osm.parking_fee],
+        ['access', osm.access],
+        ['socket:type2', osm.socket_type2::text],
+        ['socket:ccs', osm.socket_ccs::text],
+        ['socket:chademo', osm.socket_chademo::text],
+        ['socket:type2:output', osm.socket_type2_output::text],
+        ['socket:ccs:output', osm.socket_ccs_output::text],
+        ['socket:chademo:output', osm.socket_chademo_output::text]
+    ]) || COALESCE(osm.tags, ''::HSTORE);
+$$ LANGUAGE sql IMMUTABLE;
+
+-- Staged rows carry NULL for the fields OSM does not set. Those keys are
+-- written, but a station whose only difference is such a key is unchanged.
+CREATE OR REPLACE FUNCTION hstore_strip_nulls(p_tags HSTORE) RETURNS HSTORE AS $$
+    SELECT p_tags - ARRAY(SELECT key FROM each(p_tags) WHERE value IS NULL);
+$$ LANGUAGE sql IMMUTABLE;
+
+-- Connectors described by a station's tags. Outputs that are not a plain
+-- number fall back to the default power instead of failing the sync.
+CREATE OR REPLACE FUNCTION osm_tag_connectors(p_tags HSTORE) RETURNS TABLE(
+    connector_type TEXT,
+    current_type TEXT,
+    power_kw DECIMAL(5,2),
+    count_total INTEGER
+) AS $$
+    SELECT
+        s.connector_type,
+        s.current_type,
+        CASE
+            WHEN p_tags -> (s.tag || ':output') ~ '^\d{1,3}(\.\d+)?$'
+                THEN (p_tags -> (s.tag || ':output'))::DECIMAL(5,2)
+            ELSE s.default_power_kw
+        END,
+        (p_tags -> s.tag)::INTEGER
+    FROM (VALUES
+        ('type2', 'AC', 'socket:type2', 22.0),
+        ('ccs', 'DC', 'socket:ccs', 50.0),
+        ('chademo', 'DC', 'socket:chademo', 50.0)
+    ) AS s(connector_type, current_type, tag, default_power_kw)
+    WHERE p_tags -> s.tag ~ '^[1-9]\d{0,3}$';
+$$ LANGUAGE sql IMMUTABLE;
+
+CREATE OR REPLACE FUNCTION extract_connectors_from_osm_tags(
+    p_station_id VARCHAR(32),
+    p_tags HSTORE,
+    p_user_id VARCHAR(36)
+) RETURNS INTEGER AS $$
+DECLARE
+    v_connector_count INTEGER := 0;
+BEGIN
+    -- Clear existing connectors for this station
+    DELETE FROM connectors WHERE station_id = p_station_id;
+
+    INSERT INTO connectors (
+        connector_id, station_id, connector_type_id, status_id, current_type_id,
+        power_kw, count_total, count_available, created_by, created_at
+    )
+    SELECT
+        generate_prefixed_id('CON'),
+        p_station_id,
+        ct.id,
+        1,  -- Available
+        cur.id,
+        c.power_kw,
+        c.count_total,
+        c.count_total,
+        p_user_id,
+        NOW()
+    FROM osm_tag_connectors(p_tags) c
+    JOIN connector_types ct ON ct.name = c.connector_type
+    JOIN current_types cur ON cur.name = c.current_type;
+
+    GET DIAGNOSTICS v_connector_count = ROW_COUNT;
+    RETURN v_connector_count;
+END;
+$$ LANGUAGE plpgsql;
+
+-- ============================
+-- Main OSM sync function
+-- ============================
+-- Now also moves stations whose OSM location changed, only counts a station
+-- as updated when the merged values actually differ, and names unnamed OSM
+-- stations after their operator.
+
+DROP FUNCTION IF EXISTS sync_osm_charging_stations(VARCHAR);
+
+CREATE FUNCTION sync_osm_charging_stations(
+    p_user_id VARCHAR(36) DEFAULT NULL,
+    p_skip_osm_ids BIGINT[] DEFAULT '{}'
+) RETURNS TABLE(
+    updated_count INTEGER,
+    inserted_count INTEGER,
+    deactivated_count INTEGER
+) AS $$
+DECLARE
+    v_user_id VARCHAR(36) := COALESCE(p_user_id, 'USR000000000000000000000000000000');
+    v_updated_ids VARCHAR(32)[];
+    v_inserted_ids VARCHAR(32)[];
+    v_deactivated_count INTEGER := 0;
+    v_station_id VARCHAR(32);
+    v_tags HSTORE;
+BEGIN
+    -- An empty extract would deactivate every OSM station
+    IF NOT EXISTS (SELECT 1 FROM osm_charging_stations_temp) THEN
+        RAISE EXCEPTION 'osm_charging_stations_temp is empty, run the OSM import first';
+    END IF;
+
+    -- Update existing stations
+    WITH updated AS (
+        UPDATE stations s
+        SET
+            name = COALESCE(osm.name, s.name),
+            address = COALESCE(osm.address, s.address),
+            location = COALESCE(osm.geom::GEOGRAPHY, s.location),
+            tags = COALESCE(s.tags, ''::HSTORE) || osm_station_tags(osm),
+            deactivated_at = NULL,
+            updated_by = v_user_id,
+            updated_at = NOW()
+        FROM osm_charging_stations_temp osm
+        WHERE s.osm_id = osm.osm_id
+        AND NOT (osm.osm_id = ANY(p_skip_osm_ids))
+        AND (
+            s.deactivated_at IS NOT NULL OR
+            s.name IS DISTINCT FROM COALESCE(osm.name, s.name) OR
+            s.address IS DISTINCT FROM COALESCE(osm.address, s.address) OR
+            NOT ST_Equals(s.location::GEOMETRY, COALESCE(osm.geom, s.location::GEOMETRY)) OR
+            hstore_strip_nulls(COALESCE(s.tags, ''::HSTORE))
+                IS DISTINCT FROM hstore_strip_nulls(COALESCE(s.tags, ''::HSTORE) || osm_station_tags(osm))
+        )
+        RETURNING s.station_id
+    )
+    SELECT COALESCE(array_agg(station_id), '{}') INTO v_updated_ids FROM updated;
+
+    -- Insert new stations
+    WITH inserted AS (
+        INSERT INTO stations (
+            station_id, osm_id, name, address, location, tags, created_by, created_at
+        )
+        SELECT
+            generate_prefixed_id('STA'),
+            osm.osm_id,
+            COALESCE(osm.name, osm.operator, 'Charging station'),
+            osm.address,
+            osm.geom::GEOGRAPHY,
+            osm_station_tags(osm),
+            v_user_id,
+            NOW()
+        FROM osm_charging_stations_temp osm
+        WHERE NOT EXISTS (
+            SELECT 1 FROM stations WHERE osm_id = osm.osm_id
+        )
+        AND NOT (osm.osm_id = ANY(p_skip_osm_ids))
+        RETURNING station_id
+    )
+    SELECT COALESCE(array_agg(station_id), '{}') INTO v_inserted_ids FROM inserted;
+
+    -- Rebuild connectors of the stations inserted or updated above
+    FOR v_station_id, v_tags IN
+        SELECT s.station_id, s.tags
+        FROM stations s
+        WHERE s.station_id = ANY(v_updated_ids || v_inserted_ids)
+    LOOP
+        PERFORM extract_connectors_from_osm_tags(v_station_id, v_tags, v_user_id);
+    END LOOP;
+
+    -- Deactivate OSM stations that are no longer in the extract
+    WITH deactivated AS (
+        UPDATE stations s
+        SET
+            deactivated_at = NOW(),
+            updated_by = v_user_id,
+            updated_at = NOW()
+        WHERE s.osm_id > 0
+          AND s.external_ref IS NULL
+          AND s.deactivated_at IS NULL
+          AND NOT (s.osm_id = ANY(p_skip_osm_ids))
+          AND NOT EXISTS (
+              SELECT 1 FROM osm_charging_stations_temp osm WHERE osm.osm_id = s.osm_id
+          )
+        RETURNING s.station_id
+    )
+    SELECT COUNT(*) INTO v_deactivated_count FROM deactivated;
+
+    RETURN QUERY SELECT
+        CARDINALITY(v_updated_ids),
+        CARDINALITY(v_inserted_ids),
+        v_deactivated_count;
+
+EXCEPTION WHEN OTHERS THEN
+    RAISE EXCEPTION 'OSM sync failed: %', SQLERRM;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,380 @@
+------------------------------------------------------------
+-- Field Provenance
+------------------------------------------------------------
+-- Where each station field and each connector came from, using the
+-- data_sources names (osm, user_submitted, commercial). The OSM sync only
+-- overwrites station fields that came from OSM and are not locked, and only
+-- touches connectors it created itself. Everything it leaves alone while
+-- OSM says otherwise is recorded as a conflict of the run.
+
+-- A station field without a row here came from OSM and is not locked
+CREATE TABLE IF NOT EXISTS station_field_sources (
+    station_id VARCHAR(32) NOT NULL REFERENCES stations(station_id) ON DELETE CASCADE,
+    field VARCHAR(20) NOT NULL CHECK (field IN ('name', 'address', 'location', 'tags')),
+    data_source VARCHAR(50) NOT NULL REFERENCES data_sources(name),
+    locked BOOLEAN NOT NULL DEFAULT FALSE,
+    updated_by VARCHAR(36),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (station_id, field)
+);
+
+ALTER TABLE connectors ADD COLUMN IF NOT EXISTS data_source VARCHAR(50) NOT NULL
+    DEFAULT 'user_submitted' REFERENCES data_sources(name);
+
+-- Connectors created by the sync under the system user, and partner imports
+UPDATE connectors SET data_source = 'osm'
+WHERE created_by = 'USR000000000000000000000000000000';
+
+UPDATE connectors c SET data_source = 'commercial'
+FROM stations s
+WHERE s.station_id = c.station_id AND s.external_ref IS NOT NULL;
+
+INSERT INTO station_field_sources (station_id, field, data_source, updated_by)
+SELECT s.station_id, f.field, 'commercial', s.created_by
+FROM stations s
+CROSS JOIN (VALUES ('name'), ('address'), ('location'), ('tags')) AS f(field)
+WHERE s.external_ref IS NOT NULL
+ON CONFLICT DO NOTHING;
+
+CREATE TABLE IF NOT EXISTS sync_conflicts (
+    id BIGSERIAL PRIMARY KEY,
+    run_id VARCHAR(32) NOT NULL REFERENCES sync_runs(run_id) ON DELETE CASCADE,
+    station_id VARCHAR(32) NOT NULL REFERENCES stations(station_id) ON DELETE CASCADE,
+    osm_id BIGINT NOT NULL,
+    -- name, address, location, tags or connector:<type>/<current>
+    field VARCHAR(64) NOT NULL,
+    data_source VARCHAR(50) NOT NULL,
+    locked BOOLEAN NOT NULL,
+    current_value TEXT,
+    osm_value TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_sync_conflicts_run_id ON sync_conflicts (run_id);
+
+ALTER TABLE sync_runs ADD COLUMN IF NOT EXISTS conflict_count INTEGER;
+
+-- ============================
+-- Helpers
+-- ============================
+
+CREATE OR REPLACE FUNCTION osm_may_overwrite(
+    p_station_id VARCHAR(32),
+    p_field TEXT
+) RETURNS BOOLEAN AS $$
+    SELECT NOT EXISTS (
+        SELECT 1
+        FROM station_field_sources f
+        WHERE f.station_id = p_station_id
+          AND f.field = p_field
+          AND (f.locked OR f.data_source <> 'osm')
+    );
+$$ LANGUAGE sql STABLE;
+
+-- What each staged row would write to its station (station_id NULL for a
+-- new station), protected fields keeping their current value
+CREATE OR REPLACE FUNCTION osm_staged_station_values() RETURNS TABLE(
+    osm_id BIGINT,
+    station_id VARCHAR(32),
+    name VARCHAR(255),
+    address TEXT,
+    location GEOGRAPHY,
+    tags HSTORE
+) AS $$
+    SELECT
+        osm.osm_id,
+        s.station_id,
+        CASE
+            WHEN s.station_id IS NULL THEN COALESCE(osm.name, osm.operator, 'Charging station')
+            WHEN osm_may_overwrite(s.station_id, 'name') THEN COALESCE(osm.name, s.name)
+            ELSE s.name
+        END,
+        CASE
+            WHEN osm_may_overwrite(s.station_id, 'address') THEN COALESCE(osm.address, s.address)
+            ELSE s.address
+        END,
+        CASE
+            WHEN osm_may_overwrite(s.station_id, 'location')
+                THEN COALESCE(osm.geom::GEOGRAPHY, s.location)
+            ELSE s.location
+        END,
+        CASE
+            WHEN osm_may_overwrite(s.station_id, 'tags')
+                THEN COALESCE(s.tags, ''::HSTORE) || osm_station_tags(osm)
+            ELSE s.tags
+        END
+    FROM osm_charging_stations_temp osm
+    LEFT JOIN stations s ON s.osm_id = osm.osm_id;
+$$ LANGUAGE sql STABLE;
+
+-- Staged values the sync will not apply because the field or connector is
+-- protected
+CREATE OR REPLACE FUNCTION osm_sync_conflicts(
+    p_skip_osm_ids BIGINT[] DEFAULT '{}'
+) RETURNS TABLE(
+    station_id VARCHAR(32),
+    osm_id BIGINT,
+    field TEXT,
+    data_source VARCHAR(50),
+    locked BOOLEAN,
+    current_value TEXT,
+    osm_value TEXT
+) AS $$
+    SELECT s.station_id, s.osm_id, f.field::TEXT, f.data_source, f.locked, c.current_value, c.osm_value
+    FROM osm_charging_stations_temp osm
+    JOIN stations s ON s.osm_id = osm.osm_id
+    JOIN station_field_sources f
+        ON f.station_id = s.station_id AND (f.locked OR f.data_source <> 'osm')
+    CROSS JOIN LATERAL (
+        SELECT s.name::TEXT, osm.name::TEXT
+        WHERE f.field = 'name' AND osm.name IS NOT NULL AND osm.name IS DISTINCT FROM s.name
+        UNION ALL
+        SELECT s.address, osm.address
+        WHERE f.field = 'address' AND osm.address IS NOT NULL
+          AND osm.address IS DISTINCT FROM s.address
+        UNION ALL
+        SELECT
+            ST_Y(s.location::GEOMETRY) || ',' || ST_X(s.location::GEOMETRY),
+            ST_Y(osm.geom) || ',' || ST_X(osm.geom)
+        WHERE f.field = 'location' AND osm.geom IS NOT NULL
+          AND NOT ST_Equals(s.location::GEOMETRY, osm.geom)
+        UNION ALL
+        SELECT slice(COALESCE(s.tags, ''::HSTORE), akeys(d.tags))::TEXT, d.tags::TEXT
+        FROM (
+            SELECT hstore_strip_nulls(osm_station_tags(osm))
+                - hstore_strip_nulls(COALESCE(s.tags, ''::HSTORE)) AS tags
+        ) d
+        WHERE f.field = 'tags' AND array_length(akeys(d.tags), 1) > 0
+    ) AS c(current_value, osm_value)
+    WHERE NOT (s.osm_id = ANY(p_skip_osm_ids))
+
+    UNION ALL
+
+    SELECT
+        s.station_id,
+        s.osm_id,
+        'connector:' || w.connector_type || '/' || w.current_type,
+        c.data_source,
+        FALSE,
+        COALESCE(c.count_total::TEXT, '?') || ' x ' || COALESCE(c.power_kw::TEXT, '?') || ' kW',
+        w.count_total || ' x ' || w.power_kw || ' kW'
+    FROM osm_staged_station_values() v
+    JOIN stations s ON s.station_id = v.station_id
+    CROSS JOIN LATERAL osm_tag_connectors(v.tags) w
+    JOIN connector_types ct ON ct.name = w.connector_type
+    JOIN current_types cur ON cur.name = w.current_type
+    JOIN connectors c
+        ON c.station_id = s.station_id
+       AND c.connector_type_id = ct.id
+       AND c.current_type_id = cur.id
+    WHERE c.data_source <> 'osm'
+      AND (c.power_kw IS DISTINCT FROM w.power_kw OR c.count_total IS DISTINCT FROM w.count_total)
+      AND NOT (s.osm_id = ANY(p_skip_osm_ids))
+
+    ORDER BY 2, 3;
+$$ LANGUAGE sql STABLE;
+
+-- ============================
+-- Connectors from OSM tags
+-- ============================
+-- Updates the station's OSM connectors in place so that their live status
+-- and availability survive, removes the ones OSM no longer lists, and adds
+-- new ones unless an operator-managed connector of that type exists.
+
+CREATE OR REPLACE FUNCTION extract_connectors_from_osm_tags(
+    p_station_id VARCHAR(32),
+    p_tags HSTORE,
+    p_user_id VARCHAR(36)
+) RETURNS INTEGER AS $$
+DECLARE
+    v_changed_count INTEGER := 0;
+    v_inserted_count INTEGER := 0;
+BEGIN
+    DELETE FROM connectors c
+    WHERE c.station_id = p_station_id
+      AND c.data_source = 'osm'
+      AND NOT EXISTS (
+          SELECT 1
+          FROM osm_tag_connectors(p_tags) w
+          JOIN connector_types ct ON ct.name = w.connector_type
+          JOIN current_types cur ON cur.name = w.current_type
+          WHERE ct.id = c.connector_type_id AND cur.id = c.current_type_id
+      );
+
+    UPDATE connectors c
+    SET
+        power_kw = w.power_kw,
+        count_total = w.count_total,
+        count_available = LEAST(c.count_available, w.count_total),
+        updated_by = p_user_id,
+        updated_at = NOW()
+    FROM osm_tag_connectors(p_tags) w
+    JOIN connector_types ct ON ct.name = w.connector_type
+    JOIN current_types cur ON cur.name = w.current_type
+    WHERE c.station_id = p_station_id
+      AND c.data_source = 'osm'
+      AND c.connector_type_id = ct.id
+      AND c.current_type_id = cur.id
+      AND (c.power_kw IS DISTINCT FROM w.power_kw OR c.count_total IS DISTINCT FROM w.count_total);
+    GET DIAGNOSTICS v_changed_count = ROW_COUNT;
+
+    INSERT INTO connectors (
+        connector_id, station_id, connector_type_id, status_id, current_type_id,
+        power_kw, count_total, count_available, data_source, created_by, created_at
+    )
+    SELECT
+        generate_prefixed_id('CON'),
+        p_station_id,
+        ct.id,
+        1,  -- Available
+        cur.id,
+        w.power_kw,
+        w.count_total,
+        w.count_total,
+        'osm',
+        p_user_id,
+        NOW()
+    FROM osm_tag_connectors(p_tags) w
+    JOIN connector_types ct ON ct.name = w.connector_type
+    JOIN current_types cur ON cur.name = w.current_type
+    WHERE NOT EXISTS (
+        SELECT 1
+        FROM connectors c
+        WHERE c.station_id = p_station_id
+          AND c.connector_type_id = ct.id
+          AND c.current_type_id = cur.id
+    );
+    GET DIAGNOSTICS v_inserted_count = ROW_COUNT;
+
+    RETURN v_changed_count + v_inserted_count;
+END;
+$$ LANGUAGE plpgsql;
+
+-- ============================
+-- Main OSM sync function
+-- ============================
+
+DROP FUNCTION IF EXISTS sync_osm_charging_stations(VARCHAR, BIGINT[]);
+
+CREATE FUNCTION sync_osm_charging_stations(
+    p_user_id VARCHAR(36) DEFAULT NULL,
+    p_skip_osm_ids BIGINT[] DEFAULT '{}',
+    p_run_id VARCHAR(32) DEFAULT NULL
+) RETURNS TABLE(
+    updated_count INTEGER,
+    inserted_count INTEGER,
+    deactivated_count INTEGER,
+    conflict_count INTEGER
+) AS $$
+DECLARE
+    v_user_id VARCHAR(36) := COALESCE(p_user_id, 'USR000000000000000000000000000000');
+    v_updated_ids VARCHAR(32)[];
+    v_inserted_ids VARCHAR(32)[];
+    v_deactivated_count INTEGER := 0;
+    v_conflict_count INTEGER := 0;
+    v_station_id VARCHAR(32);
+    v_tags HSTORE;
+BEGIN
+    -- An empty extract would deactivate every OSM station
+    IF NOT EXISTS (SELECT 1 FROM osm_charging_stations_temp) THEN
+        RAISE EXCEPTION 'osm_charging_stations_temp is empty, run the OSM import first';
+    END IF;
+
+    -- Record what the sync leaves alone, before anything changes
+    IF p_run_id IS NOT NULL THEN
+        INSERT INTO sync_conflicts (
+            run_id, station_id, osm_id, field, data_source, locked, current_value, osm_value
+        )
+        SELECT p_run_id, c.station_id, c.osm_id, c.field, c.data_source, c.locked,
+               c.current_value, c.osm_value
+        FROM osm_sync_conflicts(p_skip_osm_ids) c;
+        GET DIAGNOSTICS v_conflict_count = ROW_COUNT;
+    ELSE
+        SELECT COUNT(*) INTO v_conflict_count FROM osm_sync_conflicts(p_skip_osm_ids);
+    END IF;
+
+    -- Update existing stations
+    WITH updated AS (
+        UPDATE stations s
+        SET
+            name = v.name,
+            address = v.address,
+            location = v.location,
+            tags = v.tags,
+            deactivated_at = NULL,
+            updated_by = v_user_id,
+            updated_at = NOW()
+        FROM osm_staged_station_values() v
+        WHERE s.station_id = v.station_id
+        AND NOT (v.osm_id = ANY(p_skip_osm_ids))
+        AND (
+            s.deactivated_at IS NOT NULL OR
+            s.name IS DISTINCT FROM v.name OR
+            s.address IS DISTINCT FROM v.address OR
+            NOT ST_Equals(s.location::GEOMETRY, v.location::GEOMETRY) OR
+            hstore_strip_nulls(COALESCE(s.tags, ''::HSTORE))
+                IS DISTINCT FROM hstore_strip_nulls(COALESCE(v.tags, ''::HSTORE))
+        )
+        RETURNING s.station_id
+    )
+    SELECT COALESCE(array_agg(station_id), '{}') INTO v_updated_ids FROM updated;
+
+    -- Insert new stations
+    WITH inserted AS (
+        INSERT INTO stations (
+            station_id, osm_id, name, address, location, tags, created_by, created_at
+        )
+        SELECT
+            generate_prefixed_id('STA'),
+            v.osm_id,
+            v.name,
+            v.address,
+            v.location,
+            v.tags,
+            v_user_id,
+            NOW()
+        FROM osm_staged_station_values() v
+        WHERE v.station_id IS NULL
+          AND v.location IS NOT NULL
+          AND NOT (v.osm_id = ANY(p_skip_osm_ids))
+        RETURNING station_id
+    )
+    SELECT COALESCE(array_agg(station_id), '{}') INTO v_inserted_ids FROM inserted;
+
+    -- Bring the OSM connectors of those stations in line with their tags
+    FOR v_station_id, v_tags IN
+        SELECT s.station_id, s.tags
+        FROM stations s
+        WHERE s.station_id = ANY(v_updated_ids || v_inserted_ids)
+    LOOP
+        PERFORM extract_connectors_from_osm_tags(v_station_id, v_tags, v_user_id);
+    END LOOP;
+
+    -- Deactivate OSM stations that are no longer in the extract
+    WITH deactivated AS (
+        UPDATE stations s
+        SET
+            deactivated_at = NOW(),
+            updated_by = v_user_id,
+            updated_at = NOW()
+        WHERE s.osm_id > 0
+          AND s.external_ref IS NULL
+          AND s.deactivated_at IS NULL
+          AND NOT (s.osm_id = ANY(p_skip_osm_ids))
+          AND NOT EXISTS (
+              SELECT 1 FROM osm_charging_stations_temp osm WHERE osm.osm_id = s.osm_id
+          )
+        RETURNING s.station_id
+    )
+    SELECT COUNT(*) INTO v_deactivated_count FROM deactivated;
+
+    RETURN QUERY SELECT
+        CARDINALITY(v_updated_ids),
+        CARDINALITY(v_inserted_ids),
+        v_deactivated_count,
+        v_conflict_count;
+
+EXCEPTION WHEN OTHERS THEN
+    RAISE EXCEPTION 'OSM sync failed: %', SQLERRM;
+END;
+$$ LANGUAGE plpgsql;
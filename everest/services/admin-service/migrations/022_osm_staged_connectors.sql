@@ -0,0 +1,328 @@
+------------------------------------------------------------
+-- OSM Connectors Parsed in admin-service
+------------------------------------------------------------
+-- The connector mapping moves out of plpgsql: admin-service parses the full
+-- socket:* tag family (every socket type, :output, :voltage, :current) of
+-- the staged stations and writes the result to osm_staged_connectors before
+-- previewing or running a sync. The sync, its conflicts and the preview all
+-- read the connectors from there.
+
+CREATE TABLE IF NOT EXISTS osm_staged_connectors (
+    osm_id BIGINT NOT NULL,
+    connector_type VARCHAR(50) NOT NULL,
+    current_type VARCHAR(10) NOT NULL,
+    power_kw DECIMAL(5,2),
+    voltage INT,
+    amperage INT,
+    count_total INT NOT NULL,
+    PRIMARY KEY (osm_id, connector_type, current_type)
+);
+
+-- Connector as shown in a conflict, e.g. 2 x 22.00 kW 400 V 32 A
+CREATE OR REPLACE FUNCTION osm_connector_label(
+    p_count_total INT,
+    p_power_kw DECIMAL,
+    p_voltage INT,
+    p_amperage INT
+) RETURNS TEXT AS $$
+    SELECT COALESCE(p_count_total::TEXT, '?') || ' x ' || COALESCE(p_power_kw::TEXT, '?') || ' kW'
+        || COALESCE(' ' || p_voltage || ' V', '')
+        || COALESCE(' ' || p_amperage || ' A', '');
+$$ LANGUAGE sql IMMUTABLE;
+
+-- Staged values the sync will not apply because the field or connector is
+-- protected
+CREATE OR REPLACE FUNCTION osm_sync_conflicts(
+    p_skip_osm_ids BIGINT[] DEFAULT '{}'
+) RETURNS TABLE(
+    station_id VARCHAR(32),
+    osm_id BIGINT,
+    field TEXT,
+    data_source VARCHAR(50),
+    locked BOOLEAN,
+    current_value TEXT,
+    osm_value TEXT
+) AS $$
+    SELECT s.station_id, s.osm_id, f.field::TEXT, f.data_source, f.locked, c.current_value, c.osm_value
+    FROM osm_charging_stations_temp osm
+    JOIN stations s ON s.osm_id = osm.osm_id
+    JOIN station_field_sources f
+        ON f.station_id = s.station_id AND (f.locked OR f.data_source <> 'osm')
+    CROSS JOIN LATERAL (
+        SELECT s.name::TEXT, osm.name::TEXT
+        WHERE f.field = 'name' AND osm.name IS NOT NULL AND osm.name IS DISTINCT FROM s.name
+        UNION ALL
+        SELECT s.address, osm.address
+        WHERE f.field = 'address' AND osm.address IS NOT NULL
+          AND osm.address IS DISTINCT FROM s.address
+        UNION ALL
+        SELECT
+            ST_Y(s.location::GEOMETRY) || ',' || ST_X(s.location::GEOMETRY),
+            ST_Y(osm.geom) || ',' || ST_X(osm.geom)
+        WHERE f.field = 'location' AND osm.geom IS NOT NULL
+          AND NOT ST_Equals(s.location::GEOMETRY, osm.geom)
+        UNION ALL
+        SELECT slice(COALESCE(s.tags, ''::HSTORE), akeys(d.tags))::TEXT, d.tags::TEXT
+        FROM (
+            SELECT hstore_strip_nulls(osm_station_tags(osm))
+                - hstore_strip_nulls(COALESCE(s.tags, ''::HSTORE)) AS tags
+        ) d
+        WHERE f.field = 'tags' AND array_length(akeys(d.tags), 1) > 0
+    ) AS c(current_value, osm_value)
+    WHERE NOT (s.osm_id = ANY(p_skip_osm_ids))
+
+    UNION ALL
+
+    SELECT
+        s.station_id,
+        s.osm_id,
+        'connector:' || w.connector_type || '/' || w.current_type,
+        c.data_source,
+        FALSE,
+        osm_connector_label(c.count_total, c.power_kw, c.voltage, c.amperage),
+        osm_connector_label(w.count_total, w.power_kw, w.voltage, w.amperage)
+    FROM osm_staged_connectors w
+    JOIN stations s ON s.osm_id = w.osm_id
+    JOIN connector_types ct ON ct.name = w.connector_type
+    JOIN current_types cur ON cur.name = w.current_type
+    JOIN connectors c
+        ON c.station_id = s.station_id
+       AND c.connector_type_id = ct.id
+       AND c.current_type_id = cur.id
+    WHERE c.data_source <> 'osm'
+      AND (
+          c.power_kw IS DISTINCT FROM w.power_kw OR
+          c.count_total IS DISTINCT FROM w.count_total OR
+          c.voltage IS DISTINCT FROM w.voltage OR
+          c.amperage IS DISTINCT FROM w.amperage
+      )
+      AND NOT (s.osm_id = ANY(p_skip_osm_ids))
+
+    ORDER BY 2, 3;
+$$ LANGUAGE sql STABLE;
+
+-- ============================
+-- Connectors from the staged rows
+-- ============================
+-- Same rules as before: OSM connectors are updated in place so their live
+-- status and availability survive, the ones OSM no longer lists go, and new
+-- ones are added unless an operator-managed connector of that type exists.
+
+CREATE OR REPLACE FUNCTION apply_staged_osm_connectors(
+    p_station_id VARCHAR(32),
+    p_osm_id BIGINT,
+    p_user_id VARCHAR(36)
+) RETURNS INTEGER AS $$
+DECLARE
+    v_changed_count INTEGER := 0;
+    v_inserted_count INTEGER := 0;
+BEGIN
+    DELETE FROM connectors c
+    WHERE c.station_id = p_station_id
+      AND c.data_source = 'osm'
+      AND NOT EXISTS (
+          SELECT 1
+          FROM osm_staged_connectors w
+          JOIN connector_types ct ON ct.name = w.connector_type
+          JOIN current_types cur ON cur.name = w.current_type
+          WHERE w.osm_id = p_osm_id
+            AND ct.id = c.connector_type_id
+            AND cur.id = c.current_type_id
+      );
+
+    UPDATE connectors c
+    SET
+        power_kw = w.power_kw,
+        voltage = w.voltage,
+        amperage = w.amperage,
+        count_total = w.count_total,
+        count_available = LEAST(c.count_available, w.count_total),
+        updated_by = p_user_id,
+        updated_at = NOW()
+    FROM osm_staged_connectors w
+    JOIN connector_types ct ON ct.name = w.connector_type
+    JOIN current_types cur ON cur.name = w.current_type
+    WHERE w.osm_id = p_osm_id
+      AND c.station_id = p_station_id
+      AND c.data_source = 'osm'
+      AND c.connector_type_id = ct.id
+      AND c.current_type_id = cur.id
+      AND (
+          c.power_kw IS DISTINCT FROM w.power_kw OR
+          c.count_total IS DISTINCT FROM w.count_total OR
+          c.voltage IS DISTINCT FROM w.voltage OR
+          c.amperage IS DISTINCT FROM w.amperage
+      );
+    GET DIAGNOSTICS v_changed_count = ROW_COUNT;
+
+    INSERT INTO connectors (
+        connector_id, station_id, connector_type_id, status_id, current_type_id,
+        power_kw, voltage, amperage, count_total, count_available, data_source,
+        created_by, created_at
+    )
+    SELECT
+        generate_prefixed_id('CON'),
+        p_station_id,
+        ct.id,
+        1,  -- Available
+        cur.id,
+        w.power_kw,
+        w.voltage,
+        w.amperage,
+        w.count_total,
+        w.count_total,
+        'osm',
+        p_user_id,
+        NOW()
+    FROM osm_staged_connectors w
+    JOIN connector_types ct ON ct.name = w.connector_type
+    JOIN current_types cur ON cur.name = w.current_type
+    WHERE w.osm_id = p_osm_id
+      AND NOT EXISTS (
+          SELECT 1
+          FROM connectors c
+          WHERE c.station_id = p_station_id
+            AND c.connector_type_id = ct.id
+            AND c.current_type_id = cur.id
+      );
+    GET DIAGNOSTICS v_inserted_count = ROW_COUNT;
+
+    RETURN v_changed_count + v_inserted_count;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP FUNCTION IF EXISTS extract_connectors_from_osm_tags(VARCHAR, HSTORE, VARCHAR);
+DROP FUNCTION IF EXISTS osm_tag_connectors(HSTORE);
+
+-- ============================
+-- Main OSM sync function
+-- ============================
+-- Unchanged apart from reading the connectors from osm_staged_connectors.
+
+CREATE OR REPLACE FUNCTION sync_osm_charging_stations(
+    p_user_id VARCHAR(36) DEFAULT NULL,
+    p_skip_osm_ids BIGINT[] DEFAULT '{}',
+    p_run_id VARCHAR(32) DEFAULT NULL
+) RETURNS TABLE(
+    updated_count INTEGER,
+    inserted_count INTEGER,
+    deactivated_count INTEGER,
+    conflict_count INTEGER
+) AS $$
+DECLARE
+    v_user_id VARCHAR(36) := COALESCE(p_user_id, 'USR000000000000000000000000000000');
+    v_updated_ids VARCHAR(32)[];
+    v_inserted_ids VARCHAR(32)[];
+    v_deactivated_count INTEGER := 0;
+    v_conflict_count INTEGER := 0;
+    v_station_id VARCHAR(32);
+    v_osm_id BIGINT;
+BEGIN
+    -- An empty extract would deactivate every OSM station
+    IF NOT EXISTS (SELECT 1 FROM osm_charging_stations_temp) THEN
+        RAISE EXCEPTION 'osm_charging_stations_temp is empty, run the OSM import first';
+    END IF;
+
+    -- Without them every OSM connector would be removed
+    IF NOT EXISTS (SELECT 1 FROM osm_staged_connectors) THEN
+        RAISE EXCEPTION 'osm_staged_connectors is empty, stage the connectors parsed from the tags first';
+    END IF;
+
+    -- Record what the sync leaves alone, before anything changes
+    IF p_run_id IS NOT NULL THEN
+        INSERT INTO sync_conflicts (
+            run_id, station_id, osm_id, field, data_source, locked, current_value, osm_value
+        )
+        SELECT p_run_id, c.station_id, c.osm_id, c.field, c.data_source, c.locked,
+               c.current_value, c.osm_value
+        FROM osm_sync_conflicts(p_skip_osm_ids) c;
+        GET DIAGNOSTICS v_conflict_count = ROW_COUNT;
+    ELSE
+        SELECT COUNT(*) INTO v_conflict_count FROM osm_sync_conflicts(p_skip_osm_ids);
+    END IF;
+
+    -- Update existing stations
+    WITH updated AS (
+        UPDATE stations s
+        SET
+            name = v.name,
+            address = v.address,
+            location = v.location,
+            tags = v.tags,
+            deactivated_at = NULL,
+            updated_by = v_user_id,
+            updated_at = NOW()
+        FROM osm_staged_station_values() v
+        WHERE s.station_id = v.station_id
+        AND NOT (v.osm_id = ANY(p_skip_osm_ids))
+        AND (
+            s.deactivated_at IS NOT NULL OR
+            s.name IS DISTINCT FROM v.name OR
+            s.address IS DISTINCT FROM v.address OR
+            NOT ST_Equals(s.location::GEOMETRY, v.location::GEOMETRY) OR
+            hstore_strip_nulls(COALESCE(s.tags, ''::HSTORE))
+                IS DISTINCT FROM hstore_strip_nulls(COALESCE(v.tags, ''::HSTORE))
+        )
+        RETURNING s.station_id
+    )
+    SELECT COALESCE(array_agg(station_id), '{}') INTO v_updated_ids FROM updated;
+
+    -- Insert new stations
+    WITH inserted AS (
+        INSERT INTO stations (
+            station_id, osm_id, name, address, location, tags, created_by, created_at
+        )
+        SELECT
+            generate_prefixed_id('STA'),
+            v.osm_id,
+            v.name,
+            v.address,
+            v.location,
+            v.tags,
+            v_user_id,
+            NOW()
+        FROM osm_staged_station_values() v
+        WHERE v.station_id IS NULL
+          AND v.location IS NOT NULL
+          AND NOT (v.osm_id = ANY(p_skip_osm_ids))
+        RETURNING station_id
+    )
+    SELECT COALESCE(array_agg(station_id), '{}') INTO v_inserted_ids FROM inserted;
+
+    -- Bring the OSM connectors of those stations in line with their tags
+    FOR v_station_id, v_osm_id IN
+        SELECT s.station_id, s.osm_id
+        FROM stations s
+        WHERE s.station_id = ANY(v_updated_ids || v_inserted_ids)
+    LOOP
+        PERFORM apply_staged_osm_connectors(v_station_id, v_osm_id, v_user_id);
+    END LOOP;
+
+    -- Deactivate OSM stations that are no longer in the extract
+    WITH deactivated AS (
+        UPDATE stations s
+        SET
+            deactivated_at = NOW(),
+            updated_by = v_user_id,
+            updated_at = NOW()
+        WHERE s.osm_id > 0
+          AND s.external_ref IS NULL
+          AND s.deactivated_at IS NULL
+          AND NOT (s.osm_id = ANY(p_skip_osm_ids))
+          AND NOT EXISTS (
+              SELECT 1 FROM osm_charging_stations_temp osm WHERE osm.osm_id = s.osm_id
+          )
+        RETURNING s.station_id
+    )
+    SELECT COUNT(*) INTO v_deactivated_count FROM deactivated;
+
+    RETURN QUERY SELECT
+        CARDINALITY(v_updated_ids),
+        CARDINALITY(v_inserted_ids),
+        v_deactivated_count,
+        v_conflict_count;
+
+EXCEPTION WHEN OTHERS THEN
+    RAISE EXCEPTION 'OSM sync failed: %', SQLERRM;
+END;
+$$ LANGUAGE plpgsql;
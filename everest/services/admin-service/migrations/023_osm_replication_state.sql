@@ -0,0 +1,49 @@
+------------------------------------------------------------
+-- OSM Replication State
+------------------------------------------------------------
+-- osm-import can keep osm_charging_stations_temp current by applying the
+-- osmChange diffs of the replication servers instead of reimporting the
+-- extract. The sequence number of the last applied diff is kept here, so
+-- that diffs are applied once and in order. A full import resets it to the
+-- sequence number of the extract, or clears it when the extract has none.
+
+CREATE TABLE IF NOT EXISTS osm_replication_state (
+    -- Single row table
+    id BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (id),
+    sequence_number BIGINT,
+    applied_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- ============================
+-- Deleted OSM stations
+-- ============================
+-- Stations whose OSM node or way was deleted, or lost its
+-- amenity=charging_station tag, in an applied diff. Same rules as the
+-- deactivation at the end of sync_osm_charging_stations: partner stations
+-- are left alone and the station comes back if OSM brings it back.
+
+CREATE OR REPLACE FUNCTION deactivate_deleted_osm_stations(
+    p_osm_ids BIGINT[],
+    p_user_id VARCHAR(36) DEFAULT NULL
+) RETURNS INTEGER AS $$
+DECLARE
+    v_user_id VARCHAR(36) := COALESCE(p_user_id, 'USR000000000000000000000000000000');
+    v_deactivated_count INTEGER := 0;
+BEGIN
+    WITH deactivated AS (
+        UPDATE stations s
+        SET
+            deactivated_at = NOW(),
+            updated_by = v_user_id,
+            updated_at = NOW()
+        WHERE s.osm_id = ANY(p_osm_ids)
+          AND s.osm_id > 0
+          AND s.external_ref IS NULL
+          AND s.deactivated_at IS NULL
+        RETURNING s.station_id
+    )
+    SELECT COUNT(*) INTO v_deactivated_count FROM deactivated;
+
+    RETURN v_deactivated_count;
+END;
+$$ LANGUAGE plpgsql;
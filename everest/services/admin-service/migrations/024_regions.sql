@@ -0,0 +1,377 @@
+------------------------------------------------------------
+-- Regions
+------------------------------------------------------------
+-- Stations belong to the region whose boundary contains them, so that
+-- neighbouring countries share the database without mixing their datasets:
+-- sync runs, statistics and searches can be limited to one region.
+-- Boundaries are either the administrative boundaries loaded by the
+-- osm-importer (planet_osm_polygon, Web Mercator) or configured polygons.
+
+CREATE TABLE IF NOT EXISTS regions (
+    -- Short name used in filters, e.g. tunisia
+    region_id VARCHAR(50) PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    country_code CHAR(2),
+    boundary_source VARCHAR(20) NOT NULL CHECK (boundary_source IN ('osm', 'configured')),
+    boundary GEOMETRY(MultiPolygon, 4326) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_regions_boundary ON regions USING GIST (boundary);
+
+ALTER TABLE stations ADD COLUMN IF NOT EXISTS region_id VARCHAR(50)
+    REFERENCES regions(region_id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_stations_region_id ON stations (region_id);
+
+-- NULL for runs over every region. Not a foreign key, so that the history
+-- of a deleted region keeps its name.
+ALTER TABLE sync_runs ADD COLUMN IF NOT EXISTS region_id VARCHAR(50);
+
+-- ============================
+-- Region of a station
+-- ============================
+
+-- The smallest region containing the location, for nested regions
+CREATE OR REPLACE FUNCTION region_at(p_location GEOGRAPHY) RETURNS VARCHAR(50) AS $$
+    SELECT r.region_id
+    FROM regions r
+    WHERE ST_Covers(r.boundary, p_location::GEOMETRY)
+    ORDER BY ST_Area(r.boundary)
+    LIMIT 1;
+$$ LANGUAGE sql STABLE;
+
+-- Every write path (admin, partner import, OSM sync) sets the region
+CREATE OR REPLACE FUNCTION set_station_region() RETURNS TRIGGER AS $$
+BEGIN
+    NEW.region_id := region_at(NEW.location);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trg_stations_region ON stations;
+CREATE TRIGGER trg_stations_region
+    BEFORE INSERT OR UPDATE OF location ON stations
+    FOR EACH ROW EXECUTE FUNCTION set_station_region();
+
+-- After regions are added or removed. Returns the number of stations whose
+-- region changed.
+CREATE OR REPLACE FUNCTION assign_station_regions() RETURNS INTEGER AS $$
+DECLARE
+    v_count INTEGER;
+BEGIN
+    UPDATE stations s
+    SET region_id = region_at(s.location)
+    WHERE s.region_id IS DISTINCT FROM region_at(s.location);
+    GET DIAGNOSTICS v_count = ROW_COUNT;
+
+    RETURN v_count;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION in_region(
+    p_station_id VARCHAR(32),
+    p_region_id VARCHAR(50)
+) RETURNS BOOLEAN AS $$
+    SELECT p_region_id IS NULL OR EXISTS (
+        SELECT 1 FROM stations s
+        WHERE s.station_id = p_station_id AND s.region_id = p_region_id
+    );
+$$ LANGUAGE sql STABLE;
+
+-- ============================
+-- Boundaries from OSM
+-- ============================
+-- The administrative boundary with the ISO 3166-1 (country) or ISO 3166-2
+-- (subdivision) code, NULL when the import has none. PL/pgSQL so that the
+-- function can be created before the first import has run.
+
+CREATE OR REPLACE FUNCTION osm_admin_boundary(p_iso_code TEXT) RETURNS GEOMETRY AS $$
+DECLARE
+    v_boundary GEOMETRY;
+BEGIN
+    SELECT ST_Multi(ST_CollectionExtract(ST_MakeValid(ST_Union(ST_Transform(p.way, 4326))), 3))
+    INTO v_boundary
+    FROM planet_osm_polygon p
+    WHERE p.boundary = 'administrative'
+      AND (
+          p.tags -> 'ISO3166-1' = p_iso_code OR
+          p.tags -> 'ISO3166-1:alpha2' = p_iso_code OR
+          p.tags -> 'ISO3166-2' = p_iso_code
+      );
+
+    RETURN v_boundary;
+END;
+$$ LANGUAGE plpgsql STABLE;
+
+-- ============================
+-- Regional OSM sync
+-- ============================
+-- Staged rows outside the region and stations of other regions (or of none)
+-- are added to the skipped osm_ids, so the sync neither updates, inserts nor
+-- deactivates them.
+
+CREATE OR REPLACE FUNCTION osm_ids_outside_region(p_region_id VARCHAR(50)) RETURNS BIGINT[] AS $$
+    SELECT COALESCE(array_agg(DISTINCT x.osm_id), '{}')
+    FROM (
+        SELECT osm.osm_id
+        FROM osm_charging_stations_temp osm
+        JOIN regions r ON r.region_id = p_region_id
+        WHERE osm.geom IS NULL OR NOT ST_Covers(r.boundary, osm.geom)
+        UNION
+        SELECT s.osm_id
+        FROM stations s
+        WHERE s.osm_id > 0 AND s.region_id IS DISTINCT FROM p_region_id
+    ) x;
+$$ LANGUAGE sql STABLE;
+
+DROP FUNCTION IF EXISTS sync_osm_charging_stations(VARCHAR, BIGINT[], VARCHAR);
+
+CREATE FUNCTION sync_osm_charging_stations(
+    p_user_id VARCHAR(36) DEFAULT NULL,
+    p_skip_osm_ids BIGINT[] DEFAULT '{}',
+    p_run_id VARCHAR(32) DEFAULT NULL,
+    p_region_id VARCHAR(50) DEFAULT NULL
+) RETURNS TABLE(
+    updated_count INTEGER,
+    inserted_count INTEGER,
+    deactivated_count INTEGER,
+    conflict_count INTEGER
+) AS $$
+DECLARE
+    v_user_id VARCHAR(36) := COALESCE(p_user_id, 'USR000000000000000000000000000000');
+    v_skip_osm_ids BIGINT[] := p_skip_osm_ids;
+    v_updated_ids VARCHAR(32)[];
+    v_inserted_ids VARCHAR(32)[];
+    v_deactivated_count INTEGER := 0;
+    v_conflict_count INTEGER := 0;
+    v_station_id VARCHAR(32);
+    v_osm_id BIGINT;
+BEGIN
+    -- An empty extract would deactivate every OSM station
+    IF NOT EXISTS (SELECT 1 FROM osm_charging_stations_temp) THEN
+        RAISE EXCEPTION 'osm_charging_stations_temp is empty, run the OSM import first';
+    END IF;
+
+    -- Without them every OSM connector would be removed
+    IF NOT EXISTS (SELECT 1 FROM osm_staged_connectors) THEN
+        RAISE EXCEPTION 'osm_staged_connectors is empty, stage the connectors parsed from the tags first';
+    END IF;
+
+    -- A regional run leaves everything outside the region alone
+    IF p_region_id IS NOT NULL THEN
+        IF NOT EXISTS (SELECT 1 FROM regions r WHERE r.region_id = p_region_id) THEN
+            RAISE EXCEPTION 'Unknown region %', p_region_id;
+        END IF;
+        v_skip_osm_ids := p_skip_osm_ids || osm_ids_outside_region(p_region_id);
+    END IF;
+
+    -- Record what the sync leaves alone, before anything changes
+    IF p_run_id IS NOT NULL THEN
+        INSERT INTO sync_conflicts (
+            run_id, station_id, osm_id, field, data_source, locked, current_value, osm_value
+        )
+        SELECT p_run_id, c.station_id, c.osm_id, c.field, c.data_source, c.locked,
+               c.current_value, c.osm_value
+        FROM osm_sync_conflicts(v_skip_osm_ids) c;
+        GET DIAGNOSTICS v_conflict_count = ROW_COUNT;
+    ELSE
+        SELECT COUNT(*) INTO v_conflict_count FROM osm_sync_conflicts(v_skip_osm_ids);
+    END IF;
+
+    -- Update existing stations
+    WITH updated AS (
+        UPDATE stations s
+        SET
+            name = v.name,
+            address = v.address,
+            location = v.location,
+            tags = v.tags,
+            deactivated_at = NULL,
+            updated_by = v_user_id,
+            updated_at = NOW()
+        FROM osm_staged_station_values() v
+        WHERE s.station_id = v.station_id
+        AND NOT (v.osm_id = ANY(v_skip_osm_ids))
+        AND (
+            s.deactivated_at IS NOT NULL OR
+            s.name IS DISTINCT FROM v.name OR
+            s.address IS DISTINCT FROM v.address OR
+            NOT ST_Equals(s.location::GEOMETRY, v.location::GEOMETRY) OR
+            hstore_strip_nulls(COALESCE(s.tags, ''::HSTORE))
+                IS DISTINCT FROM hstore_strip_nulls(COALESCE(v.tags, ''::HSTORE))
+        )
+        RETURNING s.station_id
+    )
+    SELECT COALESCE(array_agg(station_id), '{}') INTO v_updated_ids FROM updated;
+
+    -- Insert new stations
+    WITH inserted AS (
+        INSERT INTO stations (
+            station_id, osm_id, name, address, location, tags, created_by, created_at
+        )
+        SELECT
+            generate_prefixed_id('STA'),
+            v.osm_id,
+            v.name,
+            v.address,
+            v.location,
+            v.tags,
+            v_user_id,
+            NOW()
+        FROM osm_staged_station_values() v
+        WHERE v.station_id IS NULL
+          AND v.location IS NOT NULL
+          AND NOT (v.osm_id = ANY(v_skip_osm_ids))
+        RETURNING station_id
+    )
+    SELECT COALESCE(array_agg(station_id), '{}') INTO v_inserted_ids FROM inserted;
+
+    -- Bring the OSM connectors of those stations in line with their tags
+    FOR v_station_id, v_osm_id IN
+        SELECT s.station_id, s.osm_id
+        FROM stations s
+        WHERE s.station_id = ANY(v_updated_ids || v_inserted_ids)
+    LOOP
+        PERFORM apply_staged_osm_connectors(v_station_id, v_osm_id, v_user_id);
+    END LOOP;
+
+    -- Deactivate OSM stations that are no longer in the extract
+    WITH deactivated AS (
+        UPDATE stations s
+        SET
+            deactivated_at = NOW(),
+            updated_by = v_user_id,
+            updated_at = NOW()
+        WHERE s.osm_id > 0
+          AND s.external_ref IS NULL
+          AND s.deactivated_at IS NULL
+          AND NOT (s.osm_id = ANY(v_skip_osm_ids))
+          AND NOT EXISTS (
+              SELECT 1 FROM osm_charging_stations_temp osm WHERE osm.osm_id = s.osm_id
+          )
+        RETURNING s.station_id
+    )
+    SELECT COUNT(*) INTO v_deactivated_count FROM deactivated;
+
+    RETURN QUERY SELECT
+        CARDINALITY(v_updated_ids),
+        CARDINALITY(v_inserted_ids),
+        v_deactivated_count,
+        v_conflict_count;
+
+EXCEPTION WHEN OTHERS THEN
+    RAISE EXCEPTION 'OSM sync failed: %', SQLERRM;
+END;
+$$ LANGUAGE plpgsql;
+
+-- ============================
+-- Nearby search by region
+-- ============================
+
+DROP FUNCTION IF EXISTS find_nearby_stations(FLOAT, FLOAT, INTEGER, INTEGER);
+
+CREATE FUNCTION find_nearby_stations(
+    p_latitude FLOAT,
+    p_longitude FLOAT,
+    p_radius_meters INTEGER DEFAULT 5000,
+    p_limit INTEGER DEFAULT 50,
+    p_region_id VARCHAR(50) DEFAULT NULL
+) RETURNS TABLE(
+    station_id VARCHAR(32),
+    name VARCHAR,
+    address TEXT,
+    distance_meters FLOAT,
+    has_available_connectors BOOLEAN,
+    total_available_connectors BIGINT,
+    max_power_kw FLOAT,
+    power_tier TEXT,
+    operator TEXT,
+    latitude FLOAT,
+    longitude FLOAT
+) AS $$
+BEGIN
+    RETURN QUERY
+    SELECT 
+        gs.station_id,
+        gs.name,
+        gs.address,
+        ST_Distance(gs.location, ST_Point(p_longitude, p_latitude)::GEOGRAPHY) AS distance_meters,
+        gs.has_available_connectors,
+        gs.total_available_connectors,
+        gs.max_power_kw::FLOAT,
+        gs.power_tier,
+        gs.operator,
+        ST_Y(gs.location::GEOMETRY)::FLOAT AS latitude,
+        ST_X(gs.location::GEOMETRY)::FLOAT AS longitude
+    FROM mv_stations_geo gs
+    WHERE ST_DWithin(gs.location, ST_Point(p_longitude, p_latitude)::GEOGRAPHY, p_radius_meters)
+    AND in_region(gs.station_id, p_region_id)
+    ORDER BY ST_Distance(gs.location, ST_Point(p_longitude, p_latitude)::GEOGRAPHY)
+    LIMIT p_limit;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP FUNCTION IF EXISTS find_nearby_compatible_stations(
+    FLOAT, FLOAT, INTEGER, INTEGER, TEXT[], FLOAT, FLOAT
+);
+
+CREATE FUNCTION find_nearby_compatible_stations(
+    p_latitude FLOAT,
+    p_longitude FLOAT,
+    p_radius_meters INTEGER,
+    p_limit INTEGER,
+    p_connector_types TEXT[],
+    p_max_ac_kw FLOAT DEFAULT NULL,
+    p_max_dc_kw FLOAT DEFAULT NULL,
+    p_region_id VARCHAR(50) DEFAULT NULL
+) RETURNS TABLE(
+    station_id VARCHAR(32),
+    name VARCHAR,
+    address TEXT,
+    distance_meters FLOAT,
+    has_available_connectors BOOLEAN,
+    total_available_connectors BIGINT,
+    max_power_kw FLOAT,
+    power_tier TEXT,
+    operator TEXT,
+    latitude FLOAT,
+    longitude FLOAT,
+    effective_power_kw FLOAT
+) AS $$
+BEGIN
+    RETURN QUERY
+    SELECT
+        gs.station_id,
+        gs.name,
+        gs.address,
+        ST_Distance(gs.location, ST_Point(p_longitude, p_latitude)::GEOGRAPHY) AS distance_meters,
+        gs.has_available_connectors,
+        gs.total_available_connectors,
+        gs.max_power_kw::FLOAT,
+        gs.power_tier,
+        gs.operator,
+        ST_Y(gs.location::GEOMETRY)::FLOAT AS latitude,
+        ST_X(gs.location::GEOMETRY)::FLOAT AS longitude,
+        compat.effective_power_kw
+    FROM mv_stations_geo gs
+    CROSS JOIN LATERAL (
+        SELECT
+            COUNT(*) AS compatible_connectors,
+            MAX(
+                CASE
+                    WHEN c->>'power_kw' IS NULL THEN NULL
+                    WHEN c->>'current_type_name' = 'DC' THEN LEAST((c->>'power_kw')::FLOAT, p_max_dc_kw)
+                    ELSE LEAST((c->>'power_kw')::FLOAT, p_max_ac_kw)
+                END
+            ) AS effective_power_kw
+        FROM jsonb_array_elements(COALESCE(gs.connectors, '[]'::jsonb)) c
+        WHERE c->>'type_name' = ANY(p_connector_types)
+    ) compat
+    WHERE ST_DWithin(gs.location, ST_Point(p_longitude, p_latitude)::GEOGRAPHY, p_radius_meters)
+    AND compat.compatible_connectors > 0
+    AND in_region(gs.station_id, p_region_id)
+    ORDER BY ST_Distance(gs.location, ST_Point(p_longitude, p_latitude)::GEOGRAPHY)
+    LIMIT p_limit;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,274 @@
+------------------------------------------------------------
+-- Duplicate Stations
+------------------------------------------------------------
+-- OSM and partner imports bring in the same site twice, a few metres apart
+-- and under slightly different names. Candidate pairs are scored on
+-- distance, name similarity and connectors for an admin to review, who
+-- either merges the pair into one station or dismisses it.
+
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+-- Pairs an admin reviewed and kept apart, station_id_a < station_id_b
+CREATE TABLE IF NOT EXISTS station_duplicate_dismissals (
+    station_id_a VARCHAR(32) NOT NULL REFERENCES stations(station_id) ON DELETE CASCADE,
+    station_id_b VARCHAR(32) NOT NULL REFERENCES stations(station_id) ON DELETE CASCADE,
+    dismissed_by VARCHAR(36),
+    dismissed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (station_id_a, station_id_b),
+    CHECK (station_id_a < station_id_b)
+);
+
+-- History of the merges. The merged station is gone, so what it was is
+-- kept here as a snapshot.
+CREATE TABLE IF NOT EXISTS station_merges (
+    merge_id VARCHAR(32) PRIMARY KEY,
+    surviving_station_id VARCHAR(32) NOT NULL REFERENCES stations(station_id) ON DELETE CASCADE,
+    merged_station_id VARCHAR(32) NOT NULL,
+    merged_osm_id BIGINT NOT NULL,
+    -- name, address, latitude, longitude, tags, network_id, external_ref
+    merged_station JSONB NOT NULL,
+    moved_connectors INTEGER NOT NULL,
+    -- Connectors of the merged station the surviving one already had
+    dropped_connectors INTEGER NOT NULL,
+    moved_reviews INTEGER NOT NULL,
+    -- Older reviews of users who reviewed both stations
+    dropped_reviews INTEGER NOT NULL,
+    moved_checkins INTEGER NOT NULL,
+    moved_media INTEGER NOT NULL,
+    moved_issue_reports INTEGER NOT NULL,
+    merged_by VARCHAR(36),
+    merged_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_station_merges_surviving ON station_merges (surviving_station_id);
+CREATE INDEX IF NOT EXISTS idx_stations_name_trgm ON stations USING GIN (name gin_trgm_ops);
+
+-- ============================
+-- Candidate pairs
+-- ============================
+-- Score between 0 and 1: closeness within the distance (40%), trigram
+-- similarity of the names (40%) and overlap of the connector types (20%,
+-- half of it when either station has no connectors). Pairs of two OSM
+-- stations are left out: the duplicate is in OSM and is fixed there.
+
+CREATE OR REPLACE FUNCTION find_duplicate_station_candidates(
+    p_max_distance_meters FLOAT DEFAULT 50,
+    p_min_score FLOAT DEFAULT 0.5,
+    p_region_id VARCHAR(50) DEFAULT NULL
+) RETURNS TABLE(
+    station_id_a VARCHAR(32),
+    station_id_b VARCHAR(32),
+    distance_meters FLOAT,
+    name_similarity FLOAT,
+    -- NULL when either station has no connectors
+    connector_overlap FLOAT,
+    score FLOAT
+) AS $$
+    WITH pairs AS (
+        SELECT
+            a.station_id AS station_id_a,
+            b.station_id AS station_id_b,
+            ST_Distance(a.location, b.location) AS distance_meters,
+            similarity(lower(a.name), lower(b.name))::FLOAT AS name_similarity,
+            -- Shared connector types over the types of either station
+            (
+                SELECT CASE WHEN bool_or(t.in_a) AND bool_or(t.in_b) THEN
+                    (COUNT(*) FILTER (WHERE t.in_a AND t.in_b))::FLOAT / COUNT(*)
+                END
+                FROM (
+                    SELECT
+                        c.connector_type_id,
+                        bool_or(c.station_id = a.station_id) AS in_a,
+                        bool_or(c.station_id = b.station_id) AS in_b
+                    FROM connectors c
+                    WHERE c.station_id IN (a.station_id, b.station_id)
+                    GROUP BY c.connector_type_id
+                ) t
+            ) AS connector_overlap
+        FROM stations a
+        JOIN stations b
+            ON a.station_id < b.station_id
+           AND ST_DWithin(a.location, b.location, p_max_distance_meters)
+        WHERE a.deactivated_at IS NULL
+          AND b.deactivated_at IS NULL
+          AND NOT (a.osm_id > 0 AND b.osm_id > 0)
+          AND (p_region_id IS NULL OR a.region_id = p_region_id OR b.region_id = p_region_id)
+          AND NOT EXISTS (
+              SELECT 1 FROM station_duplicate_dismissals d
+              WHERE d.station_id_a = a.station_id AND d.station_id_b = b.station_id
+          )
+    ),
+    scored AS (
+        SELECT
+            p.*,
+            0.4 * (1 - LEAST(p.distance_meters / NULLIF(p_max_distance_meters, 0), 1))
+                + 0.4 * p.name_similarity
+                + 0.2 * COALESCE(p.connector_overlap, 0.5) AS score
+        FROM pairs p
+    )
+    SELECT s.station_id_a, s.station_id_b, s.distance_meters, s.name_similarity,
+           s.connector_overlap, s.score
+    FROM scored s
+    WHERE s.score >= p_min_score
+    ORDER BY s.score DESC, s.distance_meters;
+$$ LANGUAGE sql STABLE;
+
+-- ============================
+-- Merge
+-- ============================
+-- Moves everything attached to the merged station onto the surviving one,
+-- records the merge and deletes the merged station:
+--   connectors    moved, except types the surviving station already has;
+--                 check-ins and issue reports on those follow the
+--                 surviving connector
+--   reviews       moved; a user who reviewed both keeps the newer review
+--   check-ins, media, issue reports, operator accounts   moved
+-- A surviving station that is not from OSM takes over the OSM id of the
+-- merged one, so the sync updates it instead of bringing the merged station
+-- back. Its current values are then marked user_submitted to keep the sync
+-- from overwriting them. Likewise a surviving station without a partner
+-- reference takes over the one of a merged station of the same (or no)
+-- network, so that a reimport of the partner file finds it. Refreshing the
+-- materialized views is up to the caller.
+
+CREATE OR REPLACE FUNCTION merge_stations(
+    p_surviving_station_id VARCHAR(32),
+    p_merged_station_id VARCHAR(32),
+    p_merge_id VARCHAR(32),
+    p_user_id VARCHAR(36) DEFAULT NULL
+) RETURNS VOID AS $$
+DECLARE
+    v_surviving stations%ROWTYPE;
+    v_merged stations%ROWTYPE;
+    v_takes_external_ref BOOLEAN;
+    v_moved_connectors INTEGER;
+    v_dropped_connectors INTEGER;
+    v_moved_reviews INTEGER;
+    v_dropped_reviews INTEGER;
+    v_moved_checkins INTEGER;
+    v_moved_media INTEGER;
+    v_moved_issue_reports INTEGER;
+BEGIN
+    IF p_surviving_station_id = p_merged_station_id THEN
+        RAISE EXCEPTION 'A station cannot be merged into itself';
+    END IF;
+
+    -- Both rows are locked until the merge commits
+    SELECT * INTO v_surviving FROM stations WHERE station_id = p_surviving_station_id FOR UPDATE;
+    IF NOT FOUND THEN
+        RAISE EXCEPTION 'Station % not found', p_surviving_station_id;
+    END IF;
+    SELECT * INTO v_merged FROM stations WHERE station_id = p_merged_station_id FOR UPDATE;
+    IF NOT FOUND THEN
+        RAISE EXCEPTION 'Station % not found', p_merged_station_id;
+    END IF;
+    IF v_surviving.osm_id > 0 AND v_merged.osm_id > 0 THEN
+        RAISE EXCEPTION 'Both stations come from OSM, fix the duplicate in OpenStreetMap';
+    END IF;
+
+    -- Connectors the surviving station already has
+    UPDATE station_checkins ch
+    SET connector_id = cs.connector_id
+    FROM connectors cm
+    JOIN connectors cs
+        ON cs.station_id = p_surviving_station_id
+       AND cs.connector_type_id = cm.connector_type_id
+       AND cs.current_type_id = cm.current_type_id
+    WHERE cm.station_id = p_merged_station_id AND ch.connector_id = cm.connector_id;
+
+    UPDATE issue_reports ir
+    SET connector_id = cs.connector_id
+    FROM connectors cm
+    JOIN connectors cs
+        ON cs.station_id = p_surviving_station_id
+       AND cs.connector_type_id = cm.connector_type_id
+       AND cs.current_type_id = cm.current_type_id
+    WHERE cm.station_id = p_merged_station_id AND ir.connector_id = cm.connector_id;
+
+    DELETE FROM connectors cm
+    USING connectors cs
+    WHERE cm.station_id = p_merged_station_id
+      AND cs.station_id = p_surviving_station_id
+      AND cs.connector_type_id = cm.connector_type_id
+      AND cs.current_type_id = cm.current_type_id;
+    GET DIAGNOSTICS v_dropped_connectors = ROW_COUNT;
+
+    UPDATE connectors
+    SET station_id = p_surviving_station_id, updated_by = p_user_id, updated_at = NOW()
+    WHERE station_id = p_merged_station_id;
+    GET DIAGNOSTICS v_moved_connectors = ROW_COUNT;
+
+    -- One review per user and station
+    DELETE FROM user_reviews r
+    USING user_reviews o
+    WHERE r.user_id = o.user_id
+      AND r.station_id IN (p_surviving_station_id, p_merged_station_id)
+      AND o.station_id IN (p_surviving_station_id, p_merged_station_id)
+      AND r.station_id <> o.station_id
+      AND (COALESCE(r.updated_at, r.created_at), r.review_id)
+          < (COALESCE(o.updated_at, o.created_at), o.review_id);
+    GET DIAGNOSTICS v_dropped_reviews = ROW_COUNT;
+
+    UPDATE user_reviews SET station_id = p_surviving_station_id
+    WHERE station_id = p_merged_station_id;
+    GET DIAGNOSTICS v_moved_reviews = ROW_COUNT;
+
+    UPDATE station_checkins SET station_id = p_surviving_station_id
+    WHERE station_id = p_merged_station_id;
+    GET DIAGNOSTICS v_moved_checkins = ROW_COUNT;
+
+    UPDATE media SET station_id = p_surviving_station_id
+    WHERE station_id = p_merged_station_id;
+    GET DIAGNOSTICS v_moved_media = ROW_COUNT;
+
+    UPDATE issue_reports SET station_id = p_surviving_station_id
+    WHERE station_id = p_merged_station_id;
+    GET DIAGNOSTICS v_moved_issue_reports = ROW_COUNT;
+
+    UPDATE users SET station_id = p_surviving_station_id, updated_at = NOW()
+    WHERE station_id = p_merged_station_id;
+
+    INSERT INTO station_merges (
+        merge_id, surviving_station_id, merged_station_id, merged_osm_id, merged_station,
+        moved_connectors, dropped_connectors, moved_reviews, dropped_reviews,
+        moved_checkins, moved_media, moved_issue_reports, merged_by
+    ) VALUES (
+        p_merge_id, p_surviving_station_id, p_merged_station_id, v_merged.osm_id,
+        jsonb_build_object(
+            'name', v_merged.name,
+            'address', v_merged.address,
+            'latitude', ST_Y(v_merged.location::GEOMETRY),
+            'longitude', ST_X(v_merged.location::GEOMETRY),
+            'tags', hstore_to_jsonb(v_merged.tags),
+            'network_id', v_merged.network_id,
+            'external_ref', v_merged.external_ref
+        ),
+        v_moved_connectors, v_dropped_connectors, v_moved_reviews, v_dropped_reviews,
+        v_moved_checkins, v_moved_media, v_moved_issue_reports, p_user_id
+    );
+
+    -- Provenance, sync conflicts and dismissals of the merged station go
+    -- with it
+    DELETE FROM stations WHERE station_id = p_merged_station_id;
+
+    IF v_merged.osm_id > 0 THEN
+        INSERT INTO station_field_sources (station_id, field, data_source, updated_by)
+        SELECT p_surviving_station_id, f.field, 'user_submitted', p_user_id
+        FROM (VALUES ('name'), ('address'), ('location'), ('tags')) AS f(field)
+        ON CONFLICT DO NOTHING;
+    END IF;
+
+    v_takes_external_ref := v_surviving.external_ref IS NULL
+        AND v_merged.external_ref IS NOT NULL
+        AND (v_surviving.network_id IS NULL OR v_surviving.network_id = v_merged.network_id);
+
+    UPDATE stations
+    SET
+        osm_id = CASE WHEN v_merged.osm_id > 0 THEN v_merged.osm_id ELSE osm_id END,
+        external_ref = CASE WHEN v_takes_external_ref THEN v_merged.external_ref ELSE external_ref END,
+        network_id = CASE WHEN v_takes_external_ref THEN v_merged.network_id ELSE network_id END,
+        updated_by = p_user_id,
+        updated_at = NOW()
+    WHERE station_id = p_surviving_station_id;
+END;
+$$ LANGUAGE plpgsql;
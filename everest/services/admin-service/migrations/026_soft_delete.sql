@@ -0,0 +1,610 @@
+------------------------------------------------------------
+-- Soft Deletion
+------------------------------------------------------------
+-- Deleting a station, connector or network from the admin API used to
+-- remove the row: connectors went with their station, and stations with
+-- reviews could not be deleted at all. The rows are now marked deleted and
+-- hidden from every query and view, can be restored by an admin, and are
+-- removed for good by purge_deleted_records() once the retention period is
+-- over.
+--
+-- A station's connectors are deleted along with it, at the same time, and
+-- come back with it. Deactivated stations (gone from OSM) are hidden from
+-- the views too.
+
+ALTER TABLE stations ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+ALTER TABLE stations ADD COLUMN IF NOT EXISTS deleted_by VARCHAR(36);
+ALTER TABLE connectors ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+ALTER TABLE connectors ADD COLUMN IF NOT EXISTS deleted_by VARCHAR(36);
+ALTER TABLE networks ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+ALTER TABLE networks ADD COLUMN IF NOT EXISTS deleted_by VARCHAR(36);
+
+CREATE INDEX IF NOT EXISTS idx_stations_deleted_at ON stations (deleted_at)
+    WHERE deleted_at IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_connectors_deleted_at ON connectors (deleted_at)
+    WHERE deleted_at IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_networks_deleted_at ON networks (deleted_at)
+    WHERE deleted_at IS NOT NULL;
+
+-- A deleted connector no longer takes up its type on the station
+ALTER TABLE connectors DROP CONSTRAINT IF EXISTS unique_connector;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_connectors_unique_live
+    ON connectors (station_id, connector_type_id, current_type_id)
+    WHERE deleted_at IS NULL;
+
+-- ============================
+-- Views without deleted rows
+-- ============================
+-- Same definitions as in 003_mviews.sql and 011_review_moderation.sql, with
+-- deleted and deactivated stations and deleted connectors left out.
+
+DROP MATERIALIZED VIEW IF EXISTS mv_stations_geo;
+DROP MATERIALIZED VIEW IF EXISTS mv_stations_summary;
+DROP MATERIALIZED VIEW IF EXISTS mv_connector_type_stats;
+DROP MATERIALIZED VIEW IF EXISTS mv_stations_reviews;
+
+CREATE MATERIALIZED VIEW mv_stations_geo AS
+SELECT
+    cs.station_id,
+    cs.osm_id,
+    cs.name,
+    cs.address,
+    cs.location,
+
+    -- Extract coordinates for easy access
+    ST_X(cs.location::geometry) AS longitude,
+    ST_Y(cs.location::geometry) AS latitude,
+
+    -- Quick availability summary
+    EXISTS (
+        SELECT 1 FROM connectors sc
+        WHERE sc.station_id = cs.station_id AND sc.deleted_at IS NULL AND sc.count_available > 0
+    ) AS has_available_connectors,
+
+    -- Connector summary as JSON
+    (
+        SELECT jsonb_agg(
+            jsonb_build_object(
+                'connector_id', sc.connector_id,
+                'type_id', sc.connector_type_id,
+                'type_name', ct.name,
+                'status_id', sc.status_id,
+                'status_name', cs2.name,
+                'current_type_id', sc.current_type_id,
+                'current_type_name', cur.name,
+                'power_kw', sc.power_kw,
+                'voltage', sc.voltage,
+                'amperage', sc.amperage,
+                'available', sc.count_available,
+                'total', sc.count_total
+            ) ORDER BY sc.power_kw DESC NULLS LAST
+        )
+        FROM connectors sc
+        LEFT JOIN connector_types ct ON sc.connector_type_id = ct.id
+        LEFT JOIN connector_statuses cs2 ON sc.status_id = cs2.id
+        LEFT JOIN current_types cur ON sc.current_type_id = cur.id
+        WHERE sc.station_id = cs.station_id AND sc.deleted_at IS NULL
+    ) AS connectors,
+
+    -- Power statistics
+    (
+        SELECT MAX(power_kw)
+        FROM connectors sc
+        WHERE sc.station_id = cs.station_id AND sc.deleted_at IS NULL
+    ) AS max_power_kw,
+
+    (
+        SELECT MIN(power_kw)
+        FROM connectors sc
+        WHERE sc.station_id = cs.station_id AND sc.deleted_at IS NULL AND sc.power_kw > 0
+    ) AS min_power_kw,
+
+    -- Connector counts
+    (
+        SELECT COALESCE(SUM(count_available), 0)
+        FROM connectors sc
+        WHERE sc.station_id = cs.station_id AND sc.deleted_at IS NULL
+    ) AS total_available_connectors,
+
+    (
+        SELECT COUNT(*)
+        FROM connectors sc
+        WHERE sc.station_id = cs.station_id AND sc.deleted_at IS NULL
+    ) AS total_connectors,
+
+    -- Array of available connector type IDs and names
+    (
+        SELECT ARRAY_AGG(DISTINCT sc.connector_type_id)
+        FROM connectors sc
+        WHERE sc.station_id = cs.station_id AND sc.deleted_at IS NULL AND sc.count_available > 0
+    ) AS available_connector_type_ids,
+
+    (
+        SELECT ARRAY_AGG(DISTINCT ct.name)
+        FROM connectors sc
+        LEFT JOIN connector_types ct ON sc.connector_type_id = ct.id
+        WHERE sc.station_id = cs.station_id AND sc.deleted_at IS NULL AND sc.count_available > 0
+    ) AS available_connector_names,
+
+    -- Power tier classification
+    CASE
+        WHEN (SELECT MAX(power_kw) FROM connectors WHERE station_id = cs.station_id AND deleted_at IS NULL) >= 150 THEN 'ultra_fast'
+        WHEN (SELECT MAX(power_kw) FROM connectors WHERE station_id = cs.station_id AND deleted_at IS NULL) >= 50 THEN 'fast'
+        WHEN (SELECT MAX(power_kw) FROM connectors WHERE station_id = cs.station_id AND deleted_at IS NULL) >= 22 THEN 'medium'
+        ELSE 'slow'
+    END AS power_tier,
+
+    -- Station metadata from tags
+    cs.tags->'operator' AS operator,
+    cs.tags->'opening_hours' AS opening_hours,
+    cs.tags->'capacity' AS capacity,
+    cs.tags->'fee' AS fee,
+    cs.tags->'parking_fee' AS parking_fee,
+    cs.tags->'access' AS access,
+
+    -- Timestamps
+    cs.created_at,
+    cs.updated_at
+
+FROM stations cs
+WHERE cs.location IS NOT NULL
+  AND cs.deleted_at IS NULL
+  AND cs.deactivated_at IS NULL
+WITH DATA;
+
+CREATE MATERIALIZED VIEW mv_stations_summary AS
+SELECT
+    cs.station_id,
+    cs.osm_id,
+    cs.name,
+    cs.address,
+    cs.location,
+    cs.tags,
+    cs.created_at,
+    cs.updated_at,
+
+    -- Connector statistics
+    COUNT(sc.connector_id) AS total_connectors,
+    SUM(sc.count_available) AS available_connectors,
+    SUM(sc.count_total) AS total_connector_slots,
+    MAX(sc.power_kw) AS max_power_kw,
+    MIN(sc.power_kw) AS min_power_kw,
+    AVG(sc.power_kw) AS avg_power_kw,
+
+    -- Connector type breakdown
+    COUNT(DISTINCT sc.connector_type_id) AS unique_connector_types,
+
+    -- Array of available connector types
+    ARRAY_AGG(DISTINCT ct.name) FILTER (WHERE ct.name IS NOT NULL) AS connector_type_names,
+
+    -- Current status summary
+    EXISTS (
+        SELECT 1 FROM connectors sc2
+        WHERE sc2.station_id = cs.station_id AND sc2.deleted_at IS NULL AND sc2.count_available > 0
+    ) AS has_available_connectors,
+
+    -- Power capacity tiers
+    CASE
+        WHEN MAX(sc.power_kw) >= 150 THEN 'ultra_fast'
+        WHEN MAX(sc.power_kw) >= 50 THEN 'fast'
+        WHEN MAX(sc.power_kw) >= 22 THEN 'medium'
+        ELSE 'slow'
+    END AS power_tier
+
+FROM stations cs
+LEFT JOIN connectors sc ON cs.station_id = sc.station_id AND sc.deleted_at IS NULL
+LEFT JOIN connector_types ct ON sc.connector_type_id = ct.id
+WHERE cs.deleted_at IS NULL
+  AND cs.deactivated_at IS NULL
+GROUP BY cs.station_id, cs.osm_id, cs.name, cs.address, cs.location, cs.tags, cs.created_at, cs.updated_at
+WITH DATA;
+
+CREATE MATERIALIZED VIEW mv_connector_type_stats AS
+SELECT
+    cs.station_id,
+    cs.name AS station_name,
+    ct.name AS connector_type,
+    COUNT(sc.connector_id) AS connector_count,
+    SUM(sc.count_available) AS available_count,
+    SUM(sc.count_total) AS total_slots,
+    AVG(sc.power_kw) AS avg_power,
+    MIN(sc.power_kw) AS min_power,
+    MAX(sc.power_kw) AS max_power,
+    cs.location
+
+FROM stations cs
+JOIN connectors sc ON cs.station_id = sc.station_id AND sc.deleted_at IS NULL
+JOIN connector_types ct ON sc.connector_type_id = ct.id
+WHERE cs.deleted_at IS NULL
+  AND cs.deactivated_at IS NULL
+GROUP BY cs.station_id, cs.name, ct.name, cs.location
+WITH DATA;
+
+CREATE MATERIALIZED VIEW mv_stations_reviews AS
+SELECT
+    s.station_id,
+    s.name AS station_name,
+    s.address,
+    s.location,
+    ST_X(s.location::geometry) AS longitude,
+    ST_Y(s.location::geometry) AS latitude,
+
+    -- Connector summary
+    EXISTS (
+        SELECT 1
+        FROM connectors c
+        WHERE c.station_id = s.station_id AND c.deleted_at IS NULL AND c.count_available > 0
+    ) AS has_available_connectors,
+
+    (
+        SELECT jsonb_agg(
+            jsonb_build_object(
+                'connector_id', c.connector_id,
+                'type_id', c.connector_type_id,
+                'type_name', ct.name,
+                'status_id', c.status_id,
+                'status_name', cs.name,
+                'current_type_id', c.current_type_id,
+                'current_type_name', cur.name,
+                'power_kw', c.power_kw,
+                'voltage', c.voltage,
+                'amperage', c.amperage,
+                'available', c.count_available,
+                'total', c.count_total
+            ) ORDER BY c.power_kw DESC NULLS LAST
+        )
+        FROM connectors c
+        LEFT JOIN connector_types ct ON c.connector_type_id = ct.id
+        LEFT JOIN connector_statuses cs ON c.status_id = cs.id
+        LEFT JOIN current_types cur ON c.current_type_id = cur.id
+        WHERE c.station_id = s.station_id AND c.deleted_at IS NULL
+    ) AS connectors,
+
+    -- Reviews summary
+    (
+        SELECT AVG(r.rating)
+        FROM user_reviews r
+        WHERE r.station_id = s.station_id AND r.moderation_status = 'visible'
+    ) AS avg_rating,
+
+    (
+        SELECT COUNT(*)
+        FROM user_reviews r
+        WHERE r.station_id = s.station_id AND r.moderation_status = 'visible'
+    ) AS total_reviews,
+
+    -- Detailed reviews as JSON
+    (
+        SELECT jsonb_agg(
+            jsonb_build_object(
+                'review_id', r.review_id,
+                'user_id', r.user_id,
+                'rating', r.rating,
+                'review_text', r.review_text,
+                'created_at', r.created_at,
+                'updated_at', r.updated_at
+            ) ORDER BY r.created_at DESC
+        )
+        FROM user_reviews r
+        WHERE r.station_id = s.station_id AND r.moderation_status = 'visible'
+    ) AS reviews,
+
+    s.tags,
+    s.created_at,
+    s.updated_at
+
+FROM stations s
+WHERE s.location IS NOT NULL
+  AND s.deleted_at IS NULL
+  AND s.deactivated_at IS NULL
+WITH DATA;
+
+CREATE UNIQUE INDEX idx_mv_geo_station_id ON mv_stations_geo (station_id);
+CREATE INDEX idx_mv_geo_location_gist ON mv_stations_geo USING GIST (location);
+CREATE INDEX idx_mv_geo_coords ON mv_stations_geo (longitude, latitude);
+CREATE INDEX idx_mv_geo_available ON mv_stations_geo (has_available_connectors);
+CREATE INDEX idx_mv_geo_max_power ON mv_stations_geo (max_power_kw);
+CREATE INDEX idx_mv_geo_power_tier ON mv_stations_geo (power_tier);
+CREATE INDEX idx_mv_geo_operator ON mv_stations_geo (operator);
+CREATE INDEX idx_mv_geo_connector_types ON mv_stations_geo USING GIN (available_connector_type_ids);
+
+CREATE UNIQUE INDEX idx_mv_summary_station_id ON mv_stations_summary (station_id);
+CREATE INDEX idx_mv_summary_location ON mv_stations_summary USING GIST (location);
+CREATE INDEX idx_mv_summary_power_tier ON mv_stations_summary (power_tier);
+
+CREATE INDEX idx_mv_connector_stats_station ON mv_connector_type_stats (station_id);
+CREATE INDEX idx_mv_connector_stats_type ON mv_connector_type_stats (connector_type);
+
+CREATE UNIQUE INDEX idx_mv_stations_reviews_id ON mv_stations_reviews (station_id);
+CREATE INDEX idx_mv_stations_reviews_location_gist ON mv_stations_reviews USING GIST (location);
+CREATE INDEX idx_mv_stations_reviews_rating ON mv_stations_reviews (avg_rating);
+CREATE INDEX idx_mv_stations_reviews_available ON mv_stations_reviews (has_available_connectors);
+
+-- ============================
+-- Duplicate candidates
+-- ============================
+-- As in 025_station_merges.sql, without deleted stations and connectors
+
+CREATE OR REPLACE FUNCTION find_duplicate_station_candidates(
+    p_max_distance_meters FLOAT DEFAULT 50,
+    p_min_score FLOAT DEFAULT 0.5,
+    p_region_id VARCHAR(50) DEFAULT NULL
+) RETURNS TABLE(
+    station_id_a VARCHAR(32),
+    station_id_b VARCHAR(32),
+    distance_meters FLOAT,
+    name_similarity FLOAT,
+    -- NULL when either station has no connectors
+    connector_overlap FLOAT,
+    score FLOAT
+) AS $$
+    WITH pairs AS (
+        SELECT
+            a.station_id AS station_id_a,
+            b.station_id AS station_id_b,
+            ST_Distance(a.location, b.location) AS distance_meters,
+            similarity(lower(a.name), lower(b.name))::FLOAT AS name_similarity,
+            -- Shared connector types over the types of either station
+            (
+                SELECT CASE WHEN bool_or(t.in_a) AND bool_or(t.in_b) THEN
+                    (COUNT(*) FILTER (WHERE t.in_a AND t.in_b))::FLOAT / COUNT(*)
+                END
+                FROM (
+                    SELECT
+                        c.connector_type_id,
+                        bool_or(c.station_id = a.station_id) AS in_a,
+                        bool_or(c.station_id = b.station_id) AS in_b
+                    FROM connectors c
+                    WHERE c.station_id IN (a.station_id, b.station_id)
+                      AND c.deleted_at IS NULL
+                    GROUP BY c.connector_type_id
+                ) t
+            ) AS connector_overlap
+        FROM stations a
+        JOIN stations b
+            ON a.station_id < b.station_id
+           AND ST_DWithin(a.location, b.location, p_max_distance_meters)
+        WHERE a.deactivated_at IS NULL
+          AND b.deactivated_at IS NULL
+          AND a.deleted_at IS NULL
+          AND b.deleted_at IS NULL
+          AND NOT (a.osm_id > 0 AND b.osm_id > 0)
+          AND (p_region_id IS NULL OR a.region_id = p_region_id OR b.region_id = p_region_id)
+          AND NOT EXISTS (
+              SELECT 1 FROM station_duplicate_dismissals d
+              WHERE d.station_id_a = a.station_id AND d.station_id_b = b.station_id
+          )
+    ),
+    scored AS (
+        SELECT
+            p.*,
+            0.4 * (1 - LEAST(p.distance_meters / NULLIF(p_max_distance_meters, 0), 1))
+                + 0.4 * p.name_similarity
+                + 0.2 * COALESCE(p.connector_overlap, 0.5) AS score
+        FROM pairs p
+    )
+    SELECT s.station_id_a, s.station_id_b, s.distance_meters, s.name_similarity,
+           s.connector_overlap, s.score
+    FROM scored s
+    WHERE s.score >= p_min_score
+    ORDER BY s.score DESC, s.distance_meters;
+$$ LANGUAGE sql STABLE;
+
+-- ============================
+-- Merge
+-- ============================
+-- As in 025_station_merges.sql, except that deleted stations cannot be
+-- merged, and that only live connectors of the surviving station count as
+-- already there. Deleted connectors of the merged station are dropped.
+
+CREATE OR REPLACE FUNCTION merge_stations(
+    p_surviving_station_id VARCHAR(32),
+    p_merged_station_id VARCHAR(32),
+    p_merge_id VARCHAR(32),
+    p_user_id VARCHAR(36) DEFAULT NULL
+) RETURNS VOID AS $$
+DECLARE
+    v_surviving stations%ROWTYPE;
+    v_merged stations%ROWTYPE;
+    v_takes_external_ref BOOLEAN;
+    v_moved_connectors INTEGER;
+    v_dropped_connectors INTEGER;
+    v_dropped_deleted_connectors INTEGER;
+    v_moved_reviews INTEGER;
+    v_dropped_reviews INTEGER;
+    v_moved_checkins INTEGER;
+    v_moved_media INTEGER;
+    v_moved_issue_reports INTEGER;
+BEGIN
+    IF p_surviving_station_id = p_merged_station_id THEN
+        RAISE EXCEPTION 'A station cannot be merged into itself';
+    END IF;
+
+    -- Both rows are locked until the merge commits
+    SELECT * INTO v_surviving FROM stations
+    WHERE station_id = p_surviving_station_id AND deleted_at IS NULL FOR UPDATE;
+    IF NOT FOUND THEN
+        RAISE EXCEPTION 'Station % not found', p_surviving_station_id;
+    END IF;
+    SELECT * INTO v_merged FROM stations
+    WHERE station_id = p_merged_station_id AND deleted_at IS NULL FOR UPDATE;
+    IF NOT FOUND THEN
+        RAISE EXCEPTION 'Station % not found', p_merged_station_id;
+    END IF;
+    IF v_surviving.osm_id > 0 AND v_merged.osm_id > 0 THEN
+        RAISE EXCEPTION 'Both stations come from OSM, fix the duplicate in OpenStreetMap';
+    END IF;
+
+    -- Deleted connectors are not moved
+    DELETE FROM connectors
+    WHERE station_id = p_merged_station_id AND deleted_at IS NOT NULL;
+    GET DIAGNOSTICS v_dropped_deleted_connectors = ROW_COUNT;
+
+    -- Connectors the surviving station already has
+    UPDATE station_checkins ch
+    SET connector_id = cs.connector_id
+    FROM connectors cm
+    JOIN connectors cs
+        ON cs.station_id = p_surviving_station_id
+       AND cs.deleted_at IS NULL
+       AND cs.connector_type_id = cm.connector_type_id
+       AND cs.current_type_id = cm.current_type_id
+    WHERE cm.station_id = p_merged_station_id AND ch.connector_id = cm.connector_id;
+
+    UPDATE issue_reports ir
+    SET connector_id = cs.connector_id
+    FROM connectors cm
+    JOIN connectors cs
+        ON cs.station_id = p_surviving_station_id
+       AND cs.deleted_at IS NULL
+       AND cs.connector_type_id = cm.connector_type_id
+       AND cs.current_type_id = cm.current_type_id
+    WHERE cm.station_id = p_merged_station_id AND ir.connector_id = cm.connector_id;
+
+    DELETE FROM connectors cm
+    USING connectors cs
+    WHERE cm.station_id = p_merged_station_id
+      AND cs.station_id = p_surviving_station_id
+      AND cs.deleted_at IS NULL
+      AND cs.connector_type_id = cm.connector_type_id
+      AND cs.current_type_id = cm.current_type_id;
+    GET DIAGNOSTICS v_dropped_connectors = ROW_COUNT;
+    v_dropped_connectors := v_dropped_connectors + v_dropped_deleted_connectors;
+
+    UPDATE connectors
+    SET station_id = p_surviving_station_id, updated_by = p_user_id, updated_at = NOW()
+    WHERE station_id = p_merged_station_id;
+    GET DIAGNOSTICS v_moved_connectors = ROW_COUNT;
+
+    -- One review per user and station
+    DELETE FROM user_reviews r
+    USING user_reviews o
+    WHERE r.user_id = o.user_id
+      AND r.station_id IN (p_surviving_station_id, p_merged_station_id)
+      AND o.station_id IN (p_surviving_station_id, p_merged_station_id)
+      AND r.station_id <> o.station_id
+      AND (COALESCE(r.updated_at, r.created_at), r.review_id)
+          < (COALESCE(o.updated_at, o.created_at), o.review_id);
+    GET DIAGNOSTICS v_dropped_reviews = ROW_COUNT;
+
+    UPDATE user_reviews SET station_id = p_surviving_station_id
+    WHERE station_id = p_merged_station_id;
+    GET DIAGNOSTICS v_moved_reviews = ROW_COUNT;
+
+    UPDATE station_checkins SET station_id = p_surviving_station_id
+    WHERE station_id = p_merged_station_id;
+    GET DIAGNOSTICS v_moved_checkins = ROW_COUNT;
+
+    UPDATE media SET station_id = p_surviving_station_id
+    WHERE station_id = p_merged_station_id;
+    GET DIAGNOSTICS v_moved_media = ROW_COUNT;
+
+    UPDATE issue_reports SET station_id = p_surviving_station_id
+    WHERE station_id = p_merged_station_id;
+    GET DIAGNOSTICS v_moved_issue_reports = ROW_COUNT;
+
+    UPDATE users SET station_id = p_surviving_station_id, updated_at = NOW()
+    WHERE station_id = p_merged_station_id;
+
+    INSERT INTO station_merges (
+        merge_id, surviving_station_id, merged_station_id, merged_osm_id, merged_station,
+        moved_connectors, dropped_connectors, moved_reviews, dropped_reviews,
+        moved_checkins, moved_media, moved_issue_reports, merged_by
+    ) VALUES (
+        p_merge_id, p_surviving_station_id, p_merged_station_id, v_merged.osm_id,
+        jsonb_build_object(
+            'name', v_merged.name,
+            'address', v_merged.address,
+            'latitude', ST_Y(v_merged.location::GEOMETRY),
+            'longitude', ST_X(v_merged.location::GEOMETRY),
+            'tags', hstore_to_jsonb(v_merged.tags),
+            'network_id', v_merged.network_id,
+            'external_ref', v_merged.external_ref
+        ),
+        v_moved_connectors, v_dropped_connectors, v_moved_reviews, v_dropped_reviews,
+        v_moved_checkins, v_moved_media, v_moved_issue_reports, p_user_id
+    );
+
+    -- Provenance, sync conflicts and dismissals of the merged station go
+    -- with it
+    DELETE FROM stations WHERE station_id = p_merged_station_id;
+
+    IF v_merged.osm_id > 0 THEN
+        INSERT INTO station_field_sources (station_id, field, data_source, updated_by)
+        SELECT p_surviving_station_id, f.field, 'user_submitted', p_user_id
+        FROM (VALUES ('name'), ('address'), ('location'), ('tags')) AS f(field)
+        ON CONFLICT DO NOTHING;
+    END IF;
+
+    v_takes_external_ref := v_surviving.external_ref IS NULL
+        AND v_merged.external_ref IS NOT NULL
+        AND (v_surviving.network_id IS NULL OR v_surviving.network_id = v_merged.network_id);
+
+    UPDATE stations
+    SET
+        osm_id = CASE WHEN v_merged.osm_id > 0 THEN v_merged.osm_id ELSE osm_id END,
+        external_ref = CASE WHEN v_takes_external_ref THEN v_merged.external_ref ELSE external_ref END,
+        network_id = CASE WHEN v_takes_external_ref THEN v_merged.network_id ELSE network_id END,
+        updated_by = p_user_id,
+        updated_at = NOW()
+    WHERE station_id = p_surviving_station_id;
+END;
+$$ LANGUAGE plpgsql;
+
+-- ============================
+-- Purge
+-- ============================
+-- Removes the records deleted before p_deleted_before, with what still
+-- points at them:
+--   stations      reviews (with their replies, flags and votes) deleted,
+--                 operator accounts detached; connectors, check-ins, media,
+--                 issue reports and provenance go by cascade. Stored media
+--                 files are not removed.
+--   connectors    issue reports go by cascade, check-ins are detached
+--   networks      tariffs and the network's review replies deleted,
+--                 stations, operator accounts and issue reports detached
+-- The views leave deleted records out already, so they need no refresh.
+
+CREATE OR REPLACE FUNCTION purge_deleted_records(
+    p_deleted_before TIMESTAMPTZ
+) RETURNS TABLE(
+    purged_stations INTEGER,
+    purged_connectors INTEGER,
+    purged_networks INTEGER
+) AS $$
+DECLARE
+    v_station_ids VARCHAR(32)[];
+    v_network_ids VARCHAR(32)[];
+BEGIN
+    SELECT COALESCE(ARRAY_AGG(station_id), '{}') INTO v_station_ids
+    FROM stations
+    WHERE deleted_at < p_deleted_before;
+
+    -- Connectors of purged stations are counted with them
+    DELETE FROM connectors
+    WHERE deleted_at < p_deleted_before
+      AND NOT station_id = ANY(v_station_ids);
+    GET DIAGNOSTICS purged_connectors = ROW_COUNT;
+
+    DELETE FROM user_reviews WHERE station_id = ANY(v_station_ids);
+    UPDATE users SET station_id = NULL, updated_at = NOW()
+    WHERE station_id = ANY(v_station_ids);
+    DELETE FROM stations WHERE station_id = ANY(v_station_ids);
+    GET DIAGNOSTICS purged_stations = ROW_COUNT;
+
+    SELECT COALESCE(ARRAY_AGG(network_id), '{}') INTO v_network_ids
+    FROM networks
+    WHERE deleted_at < p_deleted_before;
+
+    UPDATE stations SET network_id = NULL WHERE network_id = ANY(v_network_ids);
+    UPDATE users SET network_id = NULL, updated_at = NOW()
+    WHERE network_id = ANY(v_network_ids);
+    UPDATE issue_reports SET network_id = NULL WHERE network_id = ANY(v_network_ids);
+    DELETE FROM review_replies WHERE network_id = ANY(v_network_ids);
+    DELETE FROM tariffs WHERE network_id = ANY(v_network_ids);
+    DELETE FROM networks WHERE network_id = ANY(v_network_ids);
+    GET DIAGNOSTICS purged_networks = ROW_COUNT;
+
+    RETURN NEXT;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,97 @@
+------------------------------------------------------------
+-- Network Verification
+------------------------------------------------------------
+-- A partner submits the company details of its network and uploads
+-- supporting documents; an admin approves or rejects the request.
+-- Lifecycle: pending -> approved | rejected, approved -> revoked.
+-- networks.is_verified follows the outcome and is no longer edited directly.
+
+CREATE TABLE network_verification_requests (
+    request_id VARCHAR(32) PRIMARY KEY,
+    network_id VARCHAR(32) NOT NULL REFERENCES networks(network_id) ON DELETE CASCADE,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'approved', 'rejected', 'revoked')),
+    company_name VARCHAR(255) NOT NULL,
+    registration_number VARCHAR(100) NOT NULL,
+    vat_number VARCHAR(100),
+    registered_address TEXT NOT NULL,
+    contact_name VARCHAR(255) NOT NULL,
+    contact_email VARCHAR(255) NOT NULL,
+    contact_phone VARCHAR(50),
+    submitted_by VARCHAR(36) NOT NULL,
+    submitted_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    reviewed_by VARCHAR(36),
+    reviewed_at TIMESTAMPTZ,
+    review_comment TEXT
+);
+
+-- At most one request under review per network
+CREATE UNIQUE INDEX idx_network_verification_requests_pending
+    ON network_verification_requests (network_id) WHERE status = 'pending';
+CREATE INDEX idx_network_verification_requests_status
+    ON network_verification_requests (status, submitted_at);
+CREATE INDEX idx_network_verification_requests_network
+    ON network_verification_requests (network_id, submitted_at DESC);
+
+-- Files are kept in object storage and never served publicly
+CREATE TABLE network_verification_documents (
+    document_id VARCHAR(32) PRIMARY KEY,
+    request_id VARCHAR(32) NOT NULL
+        REFERENCES network_verification_requests(request_id) ON DELETE CASCADE,
+    file_name VARCHAR(255) NOT NULL,
+    content_type VARCHAR(100) NOT NULL,
+    size_bytes BIGINT NOT NULL,
+    storage_key TEXT NOT NULL,
+    uploaded_by VARCHAR(36) NOT NULL,
+    uploaded_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_network_verification_documents_request
+    ON network_verification_documents (request_id, uploaded_at);
+
+-- Every change of a network's verification state. request_id is NULL when
+-- an admin revokes a network verified before requests existed.
+CREATE TABLE network_verification_events (
+    event_id BIGSERIAL PRIMARY KEY,
+    network_id VARCHAR(32) NOT NULL REFERENCES networks(network_id) ON DELETE CASCADE,
+    request_id VARCHAR(32)
+        REFERENCES network_verification_requests(request_id) ON DELETE CASCADE,
+    from_status VARCHAR(20),
+    to_status VARCHAR(20) NOT NULL,
+    actor_id VARCHAR(36) NOT NULL,
+    comment TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_network_verification_events_network
+    ON network_verification_events (network_id, created_at);
+CREATE INDEX idx_network_verification_events_request
+    ON network_verification_events (request_id, created_at);
+
+-- Partners hear back about their request
+ALTER TABLE user_notifications DROP CONSTRAINT IF EXISTS user_notifications_kind_check;
+ALTER TABLE user_notifications ADD CONSTRAINT user_notifications_kind_check CHECK (kind IN (
+    'review_reply', 'review_reply_updated',
+    'network_verification_approved', 'network_verification_rejected',
+    'network_verification_revoked'
+));
+
+-- Only verified networks publish tariffs. Revoking a verification
+-- deactivates the network's tariffs.
+CREATE OR REPLACE FUNCTION check_tariff_network_verified() RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.is_active AND NOT EXISTS (
+        SELECT 1 FROM networks
+        WHERE network_id = NEW.network_id AND is_verified AND deleted_at IS NULL
+    ) THEN
+        RAISE EXCEPTION 'Network % is not verified and cannot publish tariffs', NEW.network_id
+            USING ERRCODE = 'check_violation';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trg_tariffs_verified_network ON tariffs;
+CREATE TRIGGER trg_tariffs_verified_network
+    BEFORE INSERT OR UPDATE OF is_active, network_id ON tariffs
+    FOR EACH ROW EXECUTE FUNCTION check_tariff_network_verified();
@@ -0,0 +1,42 @@
+------------------------------------------------------------
+-- OCPI 2.2.1 parties
+------------------------------------------------------------
+-- eMSPs roaming with our network as CPO. An admin creates a party and
+-- hands its token A over; the party registers with it on our credentials
+-- endpoint, which exchanges it for token B (ours to call them) and
+-- token C (theirs to call us).
+-- Lifecycle: pending -> registered -> unregistered.
+
+CREATE TABLE ocpi_parties (
+    party_uid VARCHAR(32) PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'registered', 'unregistered')),
+    -- Set from the party's credentials once registered
+    country_code CHAR(2),
+    party_id VARCHAR(3),
+    role VARCHAR(10),
+    token_a VARCHAR(64) UNIQUE,
+    token_b TEXT,
+    token_c VARCHAR(64) UNIQUE,
+    versions_url TEXT,
+    -- The party's Locations receiver; NULL when it takes no pushes
+    locations_url TEXT,
+    -- Locations are pushed in (last_updated, location id) order; the last
+    -- one the party accepted
+    locations_pushed_until TIMESTAMPTZ,
+    locations_pushed_until_id VARCHAR(32),
+    last_push_error TEXT,
+    last_push_at TIMESTAMPTZ,
+    created_by VARCHAR(36),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    registered_at TIMESTAMPTZ,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT ocpi_party_tokens CHECK (
+        (status = 'pending' AND token_a IS NOT NULL AND token_c IS NULL)
+        OR (status = 'registered' AND token_a IS NULL AND token_b IS NOT NULL AND token_c IS NOT NULL)
+        OR (status = 'unregistered' AND token_a IS NULL AND token_c IS NULL)
+    )
+);
+
+CREATE INDEX idx_ocpi_parties_status ON ocpi_parties (status);
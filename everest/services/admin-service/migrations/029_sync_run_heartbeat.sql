@@ -0,0 +1,10 @@
+------------------------------------------------------------
+-- OSM sync run heartbeat
+------------------------------------------------------------
+-- The admin-service executing a run refreshes heartbeat_at while the run
+-- is in progress. A running run whose heartbeat stopped belongs to a
+-- process that died, and is failed by whichever replica notices it first;
+-- runs of live replicas are left alone.
+
+ALTER TABLE sync_runs
+    ADD COLUMN IF NOT EXISTS heartbeat_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
@@ -0,0 +1,15 @@
+------------------------------------------------------------
+-- Tariffs of unverified networks
+------------------------------------------------------------
+-- The trigger of 027 only checks tariffs as they are written, so tariffs
+-- that were already active when it was added kept publishing for networks
+-- that are not verified. Deactivate them once, the same way revoking a
+-- verification does.
+
+UPDATE tariffs t
+SET is_active = FALSE, updated_at = NOW()
+WHERE t.is_active
+  AND NOT EXISTS (
+      SELECT 1 FROM networks n
+      WHERE n.network_id = t.network_id AND n.is_verified AND n.deleted_at IS NULL
+  );
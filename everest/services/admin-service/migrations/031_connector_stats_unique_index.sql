@@ -0,0 +1,9 @@
+------------------------------------------------------------
+-- Concurrent refresh of mv_connector_type_stats
+------------------------------------------------------------
+-- REFRESH MATERIALIZED VIEW CONCURRENTLY needs a unique index. The view has
+-- one row per station and connector type; the station name and location
+-- it groups by follow from the station.
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_mv_connector_stats_station_type
+    ON mv_connector_type_stats (station_id, connector_type);
@@ -0,0 +1,26 @@
+------------------------------------------------------------
+-- Review dedupe with nullable timestamps
+------------------------------------------------------------
+-- 010 keeps the newest review by (updated_at, review_id). Both timestamps
+-- are nullable, and a NULL makes that comparison NULL, so such duplicates
+-- were never compared. Repeat the dedupe on the coalesced timestamps and
+-- make sure the constraint of 010 is in place.
+
+DELETE FROM user_reviews r
+USING user_reviews newer
+WHERE r.user_id = newer.user_id
+  AND r.station_id = newer.station_id
+  AND (COALESCE(r.updated_at, r.created_at, '-infinity'), r.review_id)
+      < (COALESCE(newer.updated_at, newer.created_at, '-infinity'), newer.review_id);
+
+DO $$
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1 FROM pg_constraint
+        WHERE conname = 'unique_user_station_review'
+          AND conrelid = 'user_reviews'::regclass
+    ) THEN
+        ALTER TABLE user_reviews
+            ADD CONSTRAINT unique_user_station_review UNIQUE (user_id, station_id);
+    END IF;
+END $$;
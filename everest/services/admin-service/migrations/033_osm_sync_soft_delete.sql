@@ -0,0 +1,457 @@
+------------------------------------------------------------
+-- OSM Sync and Soft Delete
+------------------------------------------------------------
+-- The sync functions predate soft delete: a sync still updated deleted
+-- stations and recreated connectors on them, and once a deleted OSM station
+-- was purged the next sync imported it again. They now leave deleted
+-- stations and connectors alone, and purging an OSM station keeps its
+-- osm_id as a tombstone so that it is not imported again.
+
+CREATE TABLE IF NOT EXISTS purged_osm_stations (
+    osm_id BIGINT PRIMARY KEY,
+    -- The purged station, for reference only
+    station_id VARCHAR(32) NOT NULL,
+    purged_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- ============================
+-- Staged values
+-- ============================
+-- Staged rows of deleted or purged stations write nothing
+
+CREATE OR REPLACE FUNCTION osm_staged_station_values() RETURNS TABLE(
+    osm_id BIGINT,
+    station_id VARCHAR(32),
+    name VARCHAR(255),
+    address TEXT,
+    location GEOGRAPHY,
+    tags HSTORE
+) AS $$
+    SELECT
+        osm.osm_id,
+        s.station_id,
+        CASE
+            WHEN s.station_id IS NULL THEN COALESCE(osm.name, osm.operator, 'Charging station')
+            WHEN osm_may_overwrite(s.station_id, 'name') THEN COALESCE(osm.name, s.name)
+            ELSE s.name
+        END,
+        CASE
+            WHEN osm_may_overwrite(s.station_id, 'address') THEN COALESCE(osm.address, s.address)
+            ELSE s.address
+        END,
+        CASE
+            WHEN osm_may_overwrite(s.station_id, 'location')
+                THEN COALESCE(osm.geom::GEOGRAPHY, s.location)
+            ELSE s.location
+        END,
+        CASE
+            WHEN osm_may_overwrite(s.station_id, 'tags')
+                THEN COALESCE(s.tags, ''::HSTORE) || osm_station_tags(osm)
+            ELSE s.tags
+        END
+    FROM osm_charging_stations_temp osm
+    LEFT JOIN stations s ON s.osm_id = osm.osm_id
+    WHERE s.deleted_at IS NULL
+      AND NOT EXISTS (SELECT 1 FROM purged_osm_stations p WHERE p.osm_id = osm.osm_id);
+$$ LANGUAGE sql STABLE;
+
+CREATE OR REPLACE FUNCTION osm_sync_conflicts(
+    p_skip_osm_ids BIGINT[] DEFAULT '{}'
+) RETURNS TABLE(
+    station_id VARCHAR(32),
+    osm_id BIGINT,
+    field TEXT,
+    data_source VARCHAR(50),
+    locked BOOLEAN,
+    current_value TEXT,
+    osm_value TEXT
+) AS $$
+    SELECT s.station_id, s.osm_id, f.field::TEXT, f.data_source, f.locked, c.current_value, c.osm_value
+    FROM osm_charging_stations_temp osm
+    JOIN stations s ON s.osm_id = osm.osm_id
+    JOIN station_field_sources f
+        ON f.station_id = s.station_id AND (f.locked OR f.data_source <> 'osm')
+    CROSS JOIN LATERAL (
+        SELECT s.name::TEXT, osm.name::TEXT
+        WHERE f.field = 'name' AND osm.name IS NOT NULL AND osm.name IS DISTINCT FROM s.name
+        UNION ALL
+        SELECT s.address, osm.address
+        WHERE f.field = 'address' AND osm.address IS NOT NULL
+          AND osm.address IS DISTINCT FROM s.address
+        UNION ALL
+        SELECT
+            ST_Y(s.location::GEOMETRY) || ',' || ST_X(s.location::GEOMETRY),
+            ST_Y(osm.geom) || ',' || ST_X(osm.geom)
+        WHERE f.field = 'location' AND osm.geom IS NOT NULL
+          AND NOT ST_Equals(s.location::GEOMETRY, osm.geom)
+        UNION ALL
+        SELECT slice(COALESCE(s.tags, ''::HSTORE), akeys(d.tags))::TEXT, d.tags::TEXT
+        FROM (
+            SELECT hstore_strip_nulls(osm_station_tags(osm))
+                - hstore_strip_nulls(COALESCE(s.tags, ''::HSTORE)) AS tags
+        ) d
+        WHERE f.field = 'tags' AND array_length(akeys(d.tags), 1) > 0
+    ) AS c(current_value, osm_value)
+    WHERE s.deleted_at IS NULL
+      AND NOT (s.osm_id = ANY(p_skip_osm_ids))
+
+    UNION ALL
+
+    SELECT
+        s.station_id,
+        s.osm_id,
+        'connector:' || w.connector_type || '/' || w.current_type,
+        c.data_source,
+        FALSE,
+        osm_connector_label(c.count_total, c.power_kw, c.voltage, c.amperage),
+        osm_connector_label(w.count_total, w.power_kw, w.voltage, w.amperage)
+    FROM osm_staged_connectors w
+    JOIN stations s ON s.osm_id = w.osm_id
+    JOIN connector_types ct ON ct.name = w.connector_type
+    JOIN current_types cur ON cur.name = w.current_type
+    JOIN connectors c
+        ON c.station_id = s.station_id
+       AND c.connector_type_id = ct.id
+       AND c.current_type_id = cur.id
+    WHERE c.data_source <> 'osm'
+      AND s.deleted_at IS NULL
+      AND c.deleted_at IS NULL
+      AND (
+          c.power_kw IS DISTINCT FROM w.power_kw OR
+          c.count_total IS DISTINCT FROM w.count_total OR
+          c.voltage IS DISTINCT FROM w.voltage OR
+          c.amperage IS DISTINCT FROM w.amperage
+      )
+      AND NOT (s.osm_id = ANY(p_skip_osm_ids))
+
+    ORDER BY 2, 3;
+$$ LANGUAGE sql STABLE;
+
+-- ============================
+-- Connectors from the staged rows
+-- ============================
+-- A deleted station keeps its connectors as they were, and deleted
+-- connectors are neither updated nor recreated
+
+CREATE OR REPLACE FUNCTION apply_staged_osm_connectors(
+    p_station_id VARCHAR(32),
+    p_osm_id BIGINT,
+    p_user_id VARCHAR(36)
+) RETURNS INTEGER AS $$
+DECLARE
+    v_changed_count INTEGER := 0;
+    v_inserted_count INTEGER := 0;
+BEGIN
+    IF EXISTS (
+        SELECT 1 FROM stations s
+        WHERE s.station_id = p_station_id AND s.deleted_at IS NOT NULL
+    ) THEN
+        RETURN 0;
+    END IF;
+
+    DELETE FROM connectors c
+    WHERE c.station_id = p_station_id
+      AND c.data_source = 'osm'
+      AND c.deleted_at IS NULL
+      AND NOT EXISTS (
+          SELECT 1
+          FROM osm_staged_connectors w
+          JOIN connector_types ct ON ct.name = w.connector_type
+          JOIN current_types cur ON cur.name = w.current_type
+          WHERE w.osm_id = p_osm_id
+            AND ct.id = c.connector_type_id
+            AND cur.id = c.current_type_id
+      );
+
+    UPDATE connectors c
+    SET
+        power_kw = w.power_kw,
+        voltage = w.voltage,
+        amperage = w.amperage,
+        count_total = w.count_total,
+        count_available = LEAST(c.count_available, w.count_total),
+        updated_by = p_user_id,
+        updated_at = NOW()
+    FROM osm_staged_connectors w
+    JOIN connector_types ct ON ct.name = w.connector_type
+    JOIN current_types cur ON cur.name = w.current_type
+    WHERE w.osm_id = p_osm_id
+      AND c.station_id = p_station_id
+      AND c.data_source = 'osm'
+      AND c.deleted_at IS NULL
+      AND c.connector_type_id = ct.id
+      AND c.current_type_id = cur.id
+      AND (
+          c.power_kw IS DISTINCT FROM w.power_kw OR
+          c.count_total IS DISTINCT FROM w.count_total OR
+          c.voltage IS DISTINCT FROM w.voltage OR
+          c.amperage IS DISTINCT FROM w.amperage
+      );
+    GET DIAGNOSTICS v_changed_count = ROW_COUNT;
+
+    INSERT INTO connectors (
+        connector_id, station_id, connector_type_id, status_id, current_type_id,
+        power_kw, voltage, amperage, count_total, count_available, data_source,
+        created_by, created_at
+    )
+    SELECT
+        generate_prefixed_id('CON'),
+        p_station_id,
+        ct.id,
+        1,  -- Available
+        cur.id,
+        w.power_kw,
+        w.voltage,
+        w.amperage,
+        w.count_total,
+        w.count_total,
+        'osm',
+        p_user_id,
+        NOW()
+    FROM osm_staged_connectors w
+    JOIN connector_types ct ON ct.name = w.connector_type
+    JOIN current_types cur ON cur.name = w.current_type
+    WHERE w.osm_id = p_osm_id
+      AND NOT EXISTS (
+          SELECT 1
+          FROM connectors c
+          WHERE c.station_id = p_station_id
+            AND c.connector_type_id = ct.id
+            AND c.current_type_id = cur.id
+      );
+    GET DIAGNOSTICS v_inserted_count = ROW_COUNT;
+
+    RETURN v_changed_count + v_inserted_count;
+END;
+$$ LANGUAGE plpgsql;
+
+-- ============================
+-- Deactivation
+-- ============================
+
+CREATE OR REPLACE FUNCTION deactivate_deleted_osm_stations(
+    p_osm_ids BIGINT[],
+    p_user_id VARCHAR(36) DEFAULT NULL
+) RETURNS INTEGER AS $$
+DECLARE
+    v_user_id VARCHAR(36) := COALESCE(p_user_id, 'USR000000000000000000000000000000');
+    v_deactivated_count INTEGER := 0;
+BEGIN
+    WITH deactivated AS (
+        UPDATE stations s
+        SET
+            deactivated_at = NOW(),
+            updated_by = v_user_id,
+            updated_at = NOW()
+        WHERE s.osm_id = ANY(p_osm_ids)
+          AND s.osm_id > 0
+          AND s.external_ref IS NULL
+          AND s.deactivated_at IS NULL
+          AND s.deleted_at IS NULL
+        RETURNING s.station_id
+    )
+    SELECT COUNT(*) INTO v_deactivated_count FROM deactivated;
+
+    RETURN v_deactivated_count;
+END;
+$$ LANGUAGE plpgsql;
+
+-- ============================
+-- Main OSM sync function
+-- ============================
+-- Unchanged apart from leaving deleted stations alone.
+
+CREATE OR REPLACE FUNCTION sync_osm_charging_stations(
+    p_user_id VARCHAR(36) DEFAULT NULL,
+    p_skip_osm_ids BIGINT[] DEFAULT '{}',
+    p_run_id VARCHAR(32) DEFAULT NULL,
+    p_region_id VARCHAR(50) DEFAULT NULL
+) RETURNS TABLE(
+    updated_count INTEGER,
+    inserted_count INTEGER,
+    deactivated_count INTEGER,
+    conflict_count INTEGER
+) AS $$
+DECLARE
+    v_user_id VARCHAR(36) := COALESCE(p_user_id, 'USR000000000000000000000000000000');
+    v_skip_osm_ids BIGINT[] := p_skip_osm_ids;
+    v_updated_ids VARCHAR(32)[];
+    v_inserted_ids VARCHAR(32)[];
+    v_deactivated_count INTEGER := 0;
+    v_conflict_count INTEGER := 0;
+    v_station_id VARCHAR(32);
+    v_osm_id BIGINT;
+BEGIN
+    -- An empty extract would deactivate every OSM station
+    IF NOT EXISTS (SELECT 1 FROM osm_charging_stations_temp) THEN
+        RAISE EXCEPTION 'osm_charging_stations_temp is empty, run the OSM import first';
+    END IF;
+
+    -- Without them every OSM connector would be removed
+    IF NOT EXISTS (SELECT 1 FROM osm_staged_connectors) THEN
+        RAISE EXCEPTION 'osm_staged_connectors is empty, stage the connectors parsed from the tags first';
+    END IF;
+
+    -- A regional run leaves everything outside the region alone
+    IF p_region_id IS NOT NULL THEN
+        IF NOT EXISTS (SELECT 1 FROM regions r WHERE r.region_id = p_region_id) THEN
+            RAISE EXCEPTION 'Unknown region %', p_region_id;
+        END IF;
+        v_skip_osm_ids := p_skip_osm_ids || osm_ids_outside_region(p_region_id);
+    END IF;
+
+    -- Record what the sync leaves alone, before anything changes
+    IF p_run_id IS NOT NULL THEN
+        INSERT INTO sync_conflicts (
+            run_id, station_id, osm_id, field, data_source, locked, current_value, osm_value
+        )
+        SELECT p_run_id, c.station_id, c.osm_id, c.field, c.data_source, c.locked,
+               c.current_value, c.osm_value
+        FROM osm_sync_conflicts(v_skip_osm_ids) c;
+        GET DIAGNOSTICS v_conflict_count = ROW_COUNT;
+    ELSE
+        SELECT COUNT(*) INTO v_conflict_count FROM osm_sync_conflicts(v_skip_osm_ids);
+    END IF;
+
+    -- Update existing stations
+    WITH updated AS (
+        UPDATE stations s
+        SET
+            name = v.name,
+            address = v.address,
+            location = v.location,
+            tags = v.tags,
+            deactivated_at = NULL,
+            updated_by = v_user_id,
+            updated_at = NOW()
+        FROM osm_staged_station_values() v
+        WHERE s.station_id = v.station_id
+        AND s.deleted_at IS NULL
+        AND NOT (v.osm_id = ANY(v_skip_osm_ids))
+        AND (
+            s.deactivated_at IS NOT NULL OR
+            s.name IS DISTINCT FROM v.name OR
+            s.address IS DISTINCT FROM v.address OR
+            NOT ST_Equals(s.location::GEOMETRY, v.location::GEOMETRY) OR
+            hstore_strip_nulls(COALESCE(s.tags, ''::HSTORE))
+                IS DISTINCT FROM hstore_strip_nulls(COALESCE(v.tags, ''::HSTORE))
+        )
+        RETURNING s.station_id
+    )
+    SELECT COALESCE(array_agg(station_id), '{}') INTO v_updated_ids FROM updated;
+
+    -- Insert new stations
+    WITH inserted AS (
+        INSERT INTO stations (
+            station_id, osm_id, name, address, location, tags, created_by, created_at
+        )
+        SELECT
+            generate_prefixed_id('STA'),
+            v.osm_id,
+            v.name,
+            v.address,
+            v.location,
+            v.tags,
+            v_user_id,
+            NOW()
+        FROM osm_staged_station_values() v
+        WHERE v.station_id IS NULL
+          AND v.location IS NOT NULL
+          AND NOT (v.osm_id = ANY(v_skip_osm_ids))
+        RETURNING station_id
+    )
+    SELECT COALESCE(array_agg(station_id), '{}') INTO v_inserted_ids FROM inserted;
+
+    -- Bring the OSM connectors of those stations in line with their tags
+    FOR v_station_id, v_osm_id IN
+        SELECT s.station_id, s.osm_id
+        FROM stations s
+        WHERE s.station_id = ANY(v_updated_ids || v_inserted_ids)
+    LOOP
+        PERFORM apply_staged_osm_connectors(v_station_id, v_osm_id, v_user_id);
+    END LOOP;
+
+    -- Deactivate OSM stations that are no longer in the extract
+    WITH deactivated AS (
+        UPDATE stations s
+        SET
+            deactivated_at = NOW(),
+            updated_by = v_user_id,
+            updated_at = NOW()
+        WHERE s.osm_id > 0
+          AND s.external_ref IS NULL
+          AND s.deactivated_at IS NULL
+          AND s.deleted_at IS NULL
+          AND NOT (s.osm_id = ANY(v_skip_osm_ids))
+          AND NOT EXISTS (
+              SELECT 1 FROM osm_charging_stations_temp osm WHERE osm.osm_id = s.osm_id
+          )
+        RETURNING s.station_id
+    )
+    SELECT COUNT(*) INTO v_deactivated_count FROM deactivated;
+
+    RETURN QUERY SELECT
+        CARDINALITY(v_updated_ids),
+        CARDINALITY(v_inserted_ids),
+        v_deactivated_count,
+        v_conflict_count;
+
+EXCEPTION WHEN OTHERS THEN
+    RAISE EXCEPTION 'OSM sync failed: %', SQLERRM;
+END;
+$$ LANGUAGE plpgsql;
+
+-- ============================
+-- Purge
+-- ============================
+-- Unchanged apart from recording the osm_id of purged OSM stations.
+
+CREATE OR REPLACE FUNCTION purge_deleted_records(
+    p_deleted_before TIMESTAMPTZ
+) RETURNS TABLE(
+    purged_stations INTEGER,
+    purged_connectors INTEGER,
+    purged_networks INTEGER
+) AS $$
+DECLARE
+    v_station_ids VARCHAR(32)[];
+    v_network_ids VARCHAR(32)[];
+BEGIN
+    SELECT COALESCE(ARRAY_AGG(station_id), '{}') INTO v_station_ids
+    FROM stations
+    WHERE deleted_at < p_deleted_before;
+
+    -- Connectors of purged stations are counted with them
+    DELETE FROM connectors
+    WHERE deleted_at < p_deleted_before
+      AND NOT station_id = ANY(v_station_ids);
+    GET DIAGNOSTICS purged_connectors = ROW_COUNT;
+
+    INSERT INTO purged_osm_stations (osm_id, station_id)
+    SELECT osm_id, station_id
+    FROM stations
+    WHERE station_id = ANY(v_station_ids) AND osm_id > 0
+    ON CONFLICT (osm_id) DO NOTHING;
+
+    DELETE FROM user_reviews WHERE station_id = ANY(v_station_ids);
+    UPDATE users SET station_id = NULL, updated_at = NOW()
+    WHERE station_id = ANY(v_station_ids);
+    DELETE FROM stations WHERE station_id = ANY(v_station_ids);
+    GET DIAGNOSTICS purged_stations = ROW_COUNT;
+
+    SELECT COALESCE(ARRAY_AGG(network_id), '{}') INTO v_network_ids
+    FROM networks
+    WHERE deleted_at < p_deleted_before;
+
+    UPDATE stations SET network_id = NULL WHERE network_id = ANY(v_network_ids);
+    UPDATE users SET network_id = NULL, updated_at = NOW()
+    WHERE network_id = ANY(v_network_ids);
+    UPDATE issue_reports SET network_id = NULL WHERE network_id = ANY(v_network_ids);
+    DELETE FROM review_replies WHERE network_id = ANY(v_network_ids);
+    DELETE FROM tariffs WHERE network_id = ANY(v_network_ids);
+    DELETE FROM networks WHERE network_id = ANY(v_network_ids);
+    GET DIAGNOSTICS purged_networks = ROW_COUNT;
+
+    RETURN NEXT;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,12 @@
+------------------------------------------------------------
+-- Issue Report Photos from the Media Store
+------------------------------------------------------------
+-- The optional photo of an issue was any URL the driver sent. It is now a
+-- station photo uploaded by the driver through the media store, so that it
+-- goes through the same content checks, resizing and EXIF stripping.
+-- Existing URLs were never fetched or checked and are dropped.
+
+ALTER TABLE issue_reports
+    ADD COLUMN photo_media_id VARCHAR(32) REFERENCES media(media_id) ON DELETE SET NULL;
+
+ALTER TABLE issue_reports DROP COLUMN photo_url;
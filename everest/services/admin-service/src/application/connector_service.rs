@@ -2,6 +2,7 @@ use crate::core::constants::*;
 use crate::core::errors::{AppError, AppResult};
 use crate::core::utils::generate_id;
 use crate::domain::entities::Connector;
+use crate::domain::provenance::DataSource;
 use crate::domain::repositories::ConnectorRepository;
 use crate::domain::services::ConnectorService;
 use crate::domain::value_objects::{CreateConnectorData, UpdateConnectorData};
@@ -45,6 +46,7 @@ impl ConnectorService for ConnectorServiceImpl {
             amperage: data.amperage,
             count_available: data.count_available,
             count_total: data.count_total,
+            data_source: DataSource::UserSubmitted.as_str().to_string(),
             created_by: None,
             created_at: Utc::now(),
             updated_by: None,
@@ -82,6 +84,14 @@ impl ConnectorService for ConnectorServiceImpl {
         data: UpdateConnectorData,
     ) -> AppResult<Connector> {
         let mut connector = self.get_connector(connector_id).await?;
+        // Status and availability change all the time; anything else makes
+        // the connector operator-managed so the OSM sync stops touching it
+        let spec_changed = data.connector_type_id.is_some()
+            || data.current_type_id.is_some()
+            || data.power_kw.is_some()
+            || data.voltage.is_some()
+            || data.amperage.is_some()
+            || data.count_total.is_some();
 
         if let Some(type_id) = data.connector_type_id {
             connector.connector_type_id = type_id;
@@ -115,12 +125,28 @@ impl ConnectorService for ConnectorServiceImpl {
             ));
         }
 
+        if spec_changed {
+            connector.data_source = DataSource::UserSubmitted.as_str().to_string();
+        }
         connector.updated_at = Some(Utc::now());
         self.connector_repo.update(&connector).await
     }
 
-    async fn delete_connector(&self, connector_id: &str) -> AppResult<()> {
+    async fn delete_connector(&self, connector_id: &str, deleted_by: &str) -> AppResult<()> {
         let _ = self.get_connector(connector_id).await?;
-        self.connector_repo.delete(connector_id).await
+        self.connector_repo.delete(connector_id, deleted_by).await
+    }
+
+    async fn restore_connector(
+        &self,
+        connector_id: &str,
+        restored_by: &str,
+    ) -> AppResult<Connector> {
+        self.connector_repo
+            .restore(connector_id, restored_by)
+            .await?
+            .ok_or(AppError::NotFound(
+                "Deleted connector not found".to_string(),
+            ))
     }
 }
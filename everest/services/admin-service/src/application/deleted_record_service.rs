@@ -0,0 +1,70 @@
+use crate::core::errors::AppResult;
+use crate::domain::deleted::{DeletedRecordType, PurgeCounts};
+use crate::domain::entities::DeletedRecord;
+use crate::domain::repositories::DeletedRecordRepository;
+use crate::domain::services::DeletedRecordService;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use std::time::Duration;
+
+pub struct DeletedRecordServiceImpl {
+    deleted_repo: Arc<dyn DeletedRecordRepository>,
+    retention: chrono::Duration,
+}
+
+impl DeletedRecordServiceImpl {
+    pub fn new(deleted_repo: Arc<dyn DeletedRecordRepository>, retention_days: u32) -> Self {
+        Self {
+            deleted_repo,
+            retention: chrono::Duration::days(retention_days.into()),
+        }
+    }
+
+    /// Purges the expired records at startup and every `interval` after
+    pub fn schedule(self: Arc<Self>, interval: Duration) {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                ticker.tick().await;
+                match self.purge_expired().await {
+                    Ok(counts) if counts == PurgeCounts::default() => {}
+                    Ok(counts) => tracing::info!(
+                        "Purged {} stations, {} connectors and {} networks deleted over {} days ago",
+                        counts.purged_stations,
+                        counts.purged_connectors,
+                        counts.purged_networks,
+                        self.retention.num_days()
+                    ),
+                    Err(e) => tracing::error!("Failed to purge deleted records: {}", e),
+                }
+            }
+        });
+    }
+}
+
+#[async_trait]
+impl DeletedRecordService for DeletedRecordServiceImpl {
+    async fn list_deleted(
+        &self,
+        record_type: Option<DeletedRecordType>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<(Vec<DeletedRecord>, i64)> {
+        let records = self
+            .deleted_repo
+            .find_all(record_type, limit, offset)
+            .await?;
+        let total = self.deleted_repo.count(record_type).await?;
+        Ok((records, total))
+    }
+
+    fn purge_after(&self, deleted_at: DateTime<Utc>) -> DateTime<Utc> {
+        deleted_at + self.retention
+    }
+
+    async fn purge_expired(&self) -> AppResult<PurgeCounts> {
+        self.deleted_repo.purge(Utc::now() - self.retention).await
+    }
+}
@@ -42,6 +42,8 @@ pub struct ConnectorResponse {
     pub amperage: Option<i32>,
     pub count_available: i32,
     pub count_total: i32,
+    /// osm, user_submitted or commercial
+    pub data_source: String,
     pub created_at: String,
     pub updated_at: Option<String>,
 }
@@ -59,6 +61,7 @@ impl From<Connector> for ConnectorResponse {
             amperage: connector.amperage,
             count_available: connector.count_available,
             count_total: connector.count_total,
+            data_source: connector.data_source,
             created_at: connector.created_at.to_rfc3339(),
             updated_at: connector.updated_at.map(|dt| dt.to_rfc3339()),
         }
@@ -0,0 +1,58 @@
+use crate::domain::deleted::PurgeCounts;
+use crate::domain::entities::DeletedRecord;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DeletedRecordResponse {
+    /// station, connector or network
+    pub record_type: String,
+    pub record_id: String,
+    /// Name of the station or network; for a connector, its type
+    pub name: Option<String>,
+    /// Station of a connector
+    pub station_id: Option<String>,
+    pub deleted_at: String,
+    pub deleted_by: Option<String>,
+    /// When the purge removes the record for good
+    pub purge_after: String,
+}
+
+impl DeletedRecordResponse {
+    pub fn new(record: DeletedRecord, purge_after: DateTime<Utc>) -> Self {
+        Self {
+            record_type: record.record_type,
+            record_id: record.record_id,
+            name: record.name,
+            station_id: record.station_id,
+            deleted_at: record.deleted_at.to_rfc3339(),
+            deleted_by: record.deleted_by,
+            purge_after: purge_after.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DeletedRecordListResponse {
+    pub records: Vec<DeletedRecordResponse>,
+    pub total: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PurgeResponse {
+    /// Connectors deleted along with their station are counted with it
+    pub purged_stations: i32,
+    pub purged_connectors: i32,
+    pub purged_networks: i32,
+}
+
+impl From<PurgeCounts> for PurgeResponse {
+    fn from(counts: PurgeCounts) -> Self {
+        Self {
+            purged_stations: counts.purged_stations,
+            purged_connectors: counts.purged_connectors,
+            purged_networks: counts.purged_networks,
+        }
+    }
+}
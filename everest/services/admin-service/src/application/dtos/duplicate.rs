@@ -0,0 +1,118 @@
+use crate::domain::entities::{DuplicateCandidate, DuplicateStation, StationMerge};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DuplicateStationResponse {
+    pub station_id: String,
+    /// Negative for stations that are not from OSM
+    pub osm_id: i64,
+    pub name: String,
+    pub address: Option<String>,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub network_id: Option<String>,
+    /// Partner reference of imported stations
+    pub external_ref: Option<String>,
+    pub connector_types: Vec<String>,
+}
+
+impl From<DuplicateStation> for DuplicateStationResponse {
+    fn from(station: DuplicateStation) -> Self {
+        Self {
+            station_id: station.station_id,
+            osm_id: station.osm_id,
+            name: station.name,
+            address: station.address,
+            latitude: station.latitude,
+            longitude: station.longitude,
+            network_id: station.network_id,
+            external_ref: station.external_ref,
+            connector_types: station.connector_types,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DuplicateCandidateResponse {
+    pub station_a: DuplicateStationResponse,
+    pub station_b: DuplicateStationResponse,
+    pub distance_meters: f64,
+    /// Trigram similarity of the names, 0 to 1
+    pub name_similarity: f64,
+    /// Shared connector types over all of their types, null when either
+    /// station has no connectors
+    pub connector_overlap: Option<f64>,
+    /// 0 to 1, higher is more likely a duplicate
+    pub score: f64,
+}
+
+impl From<DuplicateCandidate> for DuplicateCandidateResponse {
+    fn from(candidate: DuplicateCandidate) -> Self {
+        Self {
+            station_a: candidate.station_a.into(),
+            station_b: candidate.station_b.into(),
+            distance_meters: candidate.distance_meters,
+            name_similarity: candidate.name_similarity,
+            connector_overlap: candidate.connector_overlap,
+            score: candidate.score,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct DismissDuplicateRequest {
+    pub station_id_a: String,
+    pub station_id_b: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct MergeStationsRequest {
+    /// Station that is kept
+    pub surviving_station_id: String,
+    /// Station that is deleted once everything is moved
+    pub merged_station_id: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StationMergeResponse {
+    pub merge_id: String,
+    pub surviving_station_id: String,
+    pub merged_station_id: String,
+    pub merged_osm_id: i64,
+    /// What the merged station was: name, address, latitude, longitude,
+    /// tags, network_id and external_ref
+    pub merged_station: serde_json::Value,
+    pub moved_connectors: i32,
+    /// Connectors of a type the surviving station already had
+    pub dropped_connectors: i32,
+    pub moved_reviews: i32,
+    /// Older reviews of users who had reviewed both stations
+    pub dropped_reviews: i32,
+    pub moved_checkins: i32,
+    pub moved_media: i32,
+    pub moved_issue_reports: i32,
+    pub merged_by: Option<String>,
+    pub merged_at: String,
+}
+
+impl From<StationMerge> for StationMergeResponse {
+    fn from(merge: StationMerge) -> Self {
+        Self {
+            merge_id: merge.merge_id,
+            surviving_station_id: merge.surviving_station_id,
+            merged_station_id: merge.merged_station_id,
+            merged_osm_id: merge.merged_osm_id,
+            merged_station: merge.merged_station,
+            moved_connectors: merge.moved_connectors,
+            dropped_connectors: merge.dropped_connectors,
+            moved_reviews: merge.moved_reviews,
+            dropped_reviews: merge.dropped_reviews,
+            moved_checkins: merge.moved_checkins,
+            moved_media: merge.moved_media,
+            moved_issue_reports: merge.moved_issue_reports,
+            merged_by: merge.merged_by,
+            merged_at: merge.merged_at.to_rfc3339(),
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use crate::domain::import::{ImportReport, RowReport};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImportRowResponse {
+    /// CSV line number, or 1-based feature index for GeoJSON
+    pub row: usize,
+    pub station_ref: Option<String>,
+    pub name: Option<String>,
+    /// valid or invalid
+    pub status: String,
+    pub errors: Vec<String>,
+}
+
+impl From<RowReport> for ImportRowResponse {
+    fn from(report: RowReport) -> Self {
+        Self {
+            row: report.row,
+            status: if report.is_valid() {
+                "valid"
+            } else {
+                "invalid"
+            }
+            .to_string(),
+            station_ref: report.station_ref,
+            name: report.name,
+            errors: report.errors,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImportReportResponse {
+    pub format: String,
+    pub dry_run: bool,
+    /// True once the stations have been created
+    pub committed: bool,
+    pub total_rows: usize,
+    pub valid_rows: usize,
+    pub invalid_rows: usize,
+    /// Stations and connectors the valid rows describe
+    pub station_count: usize,
+    pub connector_count: usize,
+    pub station_ids: Vec<String>,
+    pub rows: Vec<ImportRowResponse>,
+}
+
+impl From<ImportReport> for ImportReportResponse {
+    fn from(report: ImportReport) -> Self {
+        let valid_rows = report.rows.iter().filter(|r| r.is_valid()).count();
+        Self {
+            format: report.format.as_str().to_string(),
+            dry_run: report.dry_run,
+            committed: report.committed,
+            total_rows: report.rows.len(),
+            valid_rows,
+            invalid_rows: report.rows.len() - valid_rows,
+            station_count: report.station_count,
+            connector_count: report.connector_count,
+            station_ids: report.station_ids,
+            rows: report
+                .rows
+                .into_iter()
+                .map(ImportRowResponse::from)
+                .collect(),
+        }
+    }
+}
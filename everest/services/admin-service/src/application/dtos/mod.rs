@@ -1,4 +1,12 @@
 pub mod connector;
+pub mod deleted;
+pub mod duplicate;
 pub mod health;
+pub mod import;
 pub mod network;
+pub mod ocpi;
+pub mod region;
+pub mod review;
 pub mod station;
+pub mod sync;
+pub mod verification;
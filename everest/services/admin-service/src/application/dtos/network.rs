@@ -19,7 +19,6 @@ pub struct UpdateNetworkRequest {
     pub network_type: Option<String>,
     pub support_phone: Option<String>,
     pub support_email: Option<String>,
-    pub is_verified: Option<bool>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -29,6 +28,7 @@ pub struct NetworkResponse {
     pub network_type: String,
     pub support_phone: Option<String>,
     pub support_email: Option<String>,
+    /// Set by approving or revoking a verification request
     pub is_verified: bool,
     pub created_at: String,
     pub updated_at: String,
@@ -0,0 +1,65 @@
+use crate::domain::entities::OcpiParty;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateOcpiPartyRequest {
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OcpiPartyResponse {
+    pub party_uid: String,
+    pub name: String,
+    /// pending, registered or unregistered
+    pub status: String,
+    pub country_code: Option<String>,
+    pub party_id: Option<String>,
+    pub role: Option<String>,
+    /// Token A to hand over to the party; only while pending
+    pub registration_token: Option<String>,
+    pub versions_url: Option<String>,
+    /// The party's Locations receiver, if it takes pushes
+    pub locations_url: Option<String>,
+    /// last_updated of the last location the party accepted
+    pub locations_pushed_until: Option<String>,
+    pub last_push_at: Option<String>,
+    pub last_push_error: Option<String>,
+    pub created_at: String,
+    pub registered_at: Option<String>,
+}
+
+impl From<OcpiParty> for OcpiPartyResponse {
+    fn from(party: OcpiParty) -> Self {
+        Self {
+            party_uid: party.party_uid,
+            name: party.name,
+            status: party.status,
+            country_code: party.country_code,
+            party_id: party.party_id,
+            role: party.role,
+            registration_token: party.token_a,
+            versions_url: party.versions_url,
+            locations_url: party.locations_url,
+            locations_pushed_until: party.locations_pushed_until.map(|t| t.to_rfc3339()),
+            last_push_at: party.last_push_at.map(|t| t.to_rfc3339()),
+            last_push_error: party.last_push_error,
+            created_at: party.created_at.to_rfc3339(),
+            registered_at: party.registered_at.map(|t| t.to_rfc3339()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OcpiPartyCreatedResponse {
+    #[serde(flatten)]
+    pub party: OcpiPartyResponse,
+    /// Where the party starts the credentials handshake with its token A
+    pub our_versions_url: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OcpiPushResponse {
+    /// Locations the parties accepted; failures are recorded on the party
+    pub pushed: usize,
+}
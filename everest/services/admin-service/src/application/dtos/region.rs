@@ -0,0 +1,76 @@
+use crate::domain::entities::{Region, RegionStatistics};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateRegionRequest {
+    /// Lowercase letters, digits and dashes, e.g. tunisia
+    pub region_id: String,
+    pub name: String,
+    /// ISO 3166-1 alpha-2
+    pub country_code: Option<String>,
+    /// GeoJSON Polygon or MultiPolygon geometry, in WGS 84
+    pub boundary: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateOsmRegionRequest {
+    /// Lowercase letters, digits and dashes, e.g. tunisia
+    pub region_id: String,
+    pub name: String,
+    /// ISO 3166-1 (TN) or ISO 3166-2 (TN-11) code of an administrative
+    /// boundary in the OSM import
+    pub iso_code: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RegionResponse {
+    pub region_id: String,
+    pub name: String,
+    pub country_code: Option<String>,
+    /// osm or configured
+    pub boundary_source: String,
+    pub created_at: String,
+}
+
+impl From<Region> for RegionResponse {
+    fn from(region: Region) -> Self {
+        Self {
+            region_id: region.region_id,
+            name: region.name,
+            country_code: region.country_code,
+            boundary_source: region.boundary_source,
+            created_at: region.created_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RegionStatisticsResponse {
+    pub region_id: String,
+    pub name: String,
+    pub station_count: i64,
+    /// Stations not deactivated by the OSM sync
+    pub active_station_count: i64,
+    pub osm_station_count: i64,
+    /// Connectors of the active stations
+    pub connector_count: i64,
+    pub available_connector_count: i64,
+    /// Last successful OSM sync covering the region
+    pub last_synced_at: Option<String>,
+}
+
+impl From<RegionStatistics> for RegionStatisticsResponse {
+    fn from(stats: RegionStatistics) -> Self {
+        Self {
+            region_id: stats.region_id,
+            name: stats.name,
+            station_count: stats.station_count,
+            active_station_count: stats.active_station_count,
+            osm_station_count: stats.osm_station_count,
+            connector_count: stats.connector_count,
+            available_connector_count: stats.available_connector_count,
+            last_synced_at: stats.last_synced_at.map(|t| t.to_rfc3339()),
+        }
+    }
+}
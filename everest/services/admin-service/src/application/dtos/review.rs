@@ -0,0 +1,68 @@
+use crate::domain::entities::{ModerationQueueEntry, UserReview};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ModerationActionRequest {
+    /// Why the review is hidden or restored; required
+    #[validate(length(max = 2000))]
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReviewResponse {
+    pub review_id: String,
+    pub user_id: String,
+    pub station_id: String,
+    pub rating: i32,
+    pub review_text: Option<String>,
+    /// visible, pending or hidden
+    pub moderation_status: String,
+    pub moderation_reason: Option<String>,
+    pub moderated_by: Option<String>,
+    pub moderated_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<UserReview> for ReviewResponse {
+    fn from(review: UserReview) -> Self {
+        Self {
+            review_id: review.review_id,
+            user_id: review.user_id,
+            station_id: review.station_id,
+            rating: review.rating,
+            review_text: review.review_text,
+            moderation_status: review.moderation_status,
+            moderation_reason: review.moderation_reason,
+            moderated_by: review.moderated_by,
+            moderated_at: review.moderated_at.map(|t| t.to_rfc3339()),
+            created_at: review.created_at.to_rfc3339(),
+            updated_at: review.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ModerationQueueItemResponse {
+    pub review: ReviewResponse,
+    pub open_flags: i64,
+    pub flag_reasons: Vec<String>,
+}
+
+impl From<ModerationQueueEntry> for ModerationQueueItemResponse {
+    fn from(entry: ModerationQueueEntry) -> Self {
+        Self {
+            review: ReviewResponse::from(entry.review),
+            open_flags: entry.open_flags,
+            flag_reasons: entry.flag_reasons,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ModerationQueueResponse {
+    pub reviews: Vec<ModerationQueueItemResponse>,
+    pub total: i64,
+}
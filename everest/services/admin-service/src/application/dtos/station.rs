@@ -1,4 +1,4 @@
-use crate::domain::entities::Station;
+use crate::domain::entities::{Station, StationFieldSource};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use utoipa::ToSchema;
@@ -39,10 +39,44 @@ pub struct StationResponse {
     pub longitude: f64,
     pub tags: Option<serde_json::Value>, // Correctly matches Entity
     pub network_id: Option<String>,
+    /// Region containing the station, if any
+    pub region_id: Option<String>,
     pub created_at: String,
     pub updated_at: Option<String>,
 }
 
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FieldSourceResponse {
+    /// name, address, location or tags
+    pub field: String,
+    /// osm, user_submitted or commercial
+    pub data_source: String,
+    /// A locked field is never overwritten by the OSM sync
+    pub locked: bool,
+    pub updated_by: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+impl From<StationFieldSource> for FieldSourceResponse {
+    fn from(source: StationFieldSource) -> Self {
+        Self {
+            field: source.field,
+            data_source: source.data_source,
+            locked: source.locked,
+            updated_by: source.updated_by,
+            updated_at: source.updated_at.map(|dt| dt.to_rfc3339()),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateFieldSourceRequest {
+    /// osm, user_submitted or commercial; setting osm hands the field back
+    /// to the sync
+    pub data_source: Option<String>,
+    pub locked: Option<bool>,
+}
+
 impl From<Station> for StationResponse {
     fn from(station: Station) -> Self {
         Self {
@@ -54,6 +88,7 @@ impl From<Station> for StationResponse {
             longitude: station.longitude,
             tags: station.tags, // This now works because both are Option<serde_json::Value>
             network_id: station.network_id,
+            region_id: station.region_id,
             created_at: station.created_at.to_rfc3339(),
             updated_at: station.updated_at.map(|dt| dt.to_rfc3339()),
         }
@@ -0,0 +1,333 @@
+use crate::domain::entities::{OsmRemovedStation, SyncConflict, SyncRun};
+use crate::domain::sync::{
+    ConnectorChange, ConnectorSpec, FieldChange, NewStationPreview, StationChangePreview,
+    SyncPreview, TagChange,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SyncRunResponse {
+    pub run_id: String,
+    /// manual or scheduled
+    pub trigger_type: String,
+    /// running, succeeded or failed
+    pub status: String,
+    pub triggered_by: Option<String>,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+    pub inserted_count: Option<i32>,
+    pub updated_count: Option<i32>,
+    pub deactivated_count: Option<i32>,
+    pub error: Option<String>,
+    /// Staged changes the admin chose not to apply
+    pub skipped_osm_ids: Vec<i64>,
+    /// Staged values left unapplied because the field is protected
+    pub conflict_count: Option<i32>,
+    /// Region the run was limited to, if any
+    pub region_id: Option<String>,
+}
+
+impl From<SyncRun> for SyncRunResponse {
+    fn from(run: SyncRun) -> Self {
+        Self {
+            run_id: run.run_id,
+            trigger_type: run.trigger_type,
+            status: run.status,
+            triggered_by: run.triggered_by,
+            started_at: run.started_at.to_rfc3339(),
+            finished_at: run.finished_at.map(|t| t.to_rfc3339()),
+            inserted_count: run.inserted_count,
+            updated_count: run.updated_count,
+            deactivated_count: run.deactivated_count,
+            error: run.error,
+            skipped_osm_ids: run.skipped_osm_ids,
+            conflict_count: run.conflict_count,
+            region_id: run.region_id,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SyncRunListResponse {
+    pub runs: Vec<SyncRunResponse>,
+    pub total: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SyncConflictResponse {
+    pub station_id: String,
+    pub osm_id: i64,
+    /// name, address, location, tags or connector:<type>/<current>
+    pub field: String,
+    /// Source of the value the sync kept
+    pub data_source: String,
+    pub locked: bool,
+    pub current_value: Option<String>,
+    pub osm_value: Option<String>,
+}
+
+impl From<SyncConflict> for SyncConflictResponse {
+    fn from(conflict: SyncConflict) -> Self {
+        Self {
+            station_id: conflict.station_id,
+            osm_id: conflict.osm_id,
+            field: conflict.field,
+            data_source: conflict.data_source,
+            locked: conflict.locked,
+            current_value: conflict.current_value,
+            osm_value: conflict.osm_value,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct StartSyncRequest {
+    /// osm_ids from the preview whose changes must not be applied
+    #[serde(default)]
+    pub skip_osm_ids: Vec<i64>,
+    /// Only sync the stations of this region
+    pub region_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FieldChangeResponse {
+    /// name, address or location (`lat,lon`)
+    pub field: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+impl From<FieldChange> for FieldChangeResponse {
+    fn from(change: FieldChange) -> Self {
+        Self {
+            field: change.field.to_string(),
+            old: change.old,
+            new: change.new,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TagChangeResponse {
+    pub key: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+impl From<TagChange> for TagChangeResponse {
+    fn from(change: TagChange) -> Self {
+        Self {
+            key: change.key,
+            old: change.old,
+            new: change.new,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ConnectorSpecResponse {
+    pub power_kw: Option<f64>,
+    pub voltage: Option<i32>,
+    pub amperage: Option<i32>,
+    pub count_total: Option<i32>,
+}
+
+impl From<ConnectorSpec> for ConnectorSpecResponse {
+    fn from(spec: ConnectorSpec) -> Self {
+        Self {
+            power_kw: spec.power_kw,
+            voltage: spec.voltage,
+            amperage: spec.amperage,
+            count_total: spec.count_total,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ConnectorChangeResponse {
+    pub connector_type: String,
+    pub current_type: String,
+    /// added, removed or changed
+    pub change: String,
+    pub old: Option<ConnectorSpecResponse>,
+    pub new: Option<ConnectorSpecResponse>,
+}
+
+impl From<ConnectorChange> for ConnectorChangeResponse {
+    fn from(change: ConnectorChange) -> Self {
+        Self {
+            change: match (&change.old, &change.new) {
+                (None, _) => "added",
+                (_, None) => "removed",
+                _ => "changed",
+            }
+            .to_string(),
+            connector_type: change.connector_type,
+            current_type: change.current_type,
+            old: change.old.map(ConnectorSpecResponse::from),
+            new: change.new.map(ConnectorSpecResponse::from),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NewConnectorResponse {
+    pub connector_type: String,
+    pub current_type: String,
+    pub power_kw: Option<f64>,
+    pub voltage: Option<i32>,
+    pub amperage: Option<i32>,
+    pub count_total: Option<i32>,
+}
+
+impl From<ConnectorSpec> for NewConnectorResponse {
+    fn from(spec: ConnectorSpec) -> Self {
+        Self {
+            connector_type: spec.connector_type,
+            current_type: spec.current_type,
+            power_kw: spec.power_kw,
+            voltage: spec.voltage,
+            amperage: spec.amperage,
+            count_total: spec.count_total,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NewStationPreviewResponse {
+    pub osm_id: i64,
+    pub name: String,
+    pub address: Option<String>,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub connectors: Vec<NewConnectorResponse>,
+}
+
+impl From<NewStationPreview> for NewStationPreviewResponse {
+    fn from(station: NewStationPreview) -> Self {
+        Self {
+            osm_id: station.osm_id,
+            name: station.name,
+            address: station.address,
+            latitude: station.latitude,
+            longitude: station.longitude,
+            connectors: station
+                .connectors
+                .into_iter()
+                .map(NewConnectorResponse::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StationChangePreviewResponse {
+    pub station_id: String,
+    pub osm_id: i64,
+    pub name: String,
+    pub reactivated: bool,
+    pub fields: Vec<FieldChangeResponse>,
+    pub moved_meters: Option<f64>,
+    pub tags: Vec<TagChangeResponse>,
+    pub connectors: Vec<ConnectorChangeResponse>,
+}
+
+impl From<StationChangePreview> for StationChangePreviewResponse {
+    fn from(change: StationChangePreview) -> Self {
+        Self {
+            station_id: change.station_id,
+            osm_id: change.osm_id,
+            name: change.name,
+            reactivated: change.reactivated,
+            fields: change
+                .fields
+                .into_iter()
+                .map(FieldChangeResponse::from)
+                .collect(),
+            moved_meters: change.moved_meters,
+            tags: change
+                .tags
+                .into_iter()
+                .map(TagChangeResponse::from)
+                .collect(),
+            connectors: change
+                .connectors
+                .into_iter()
+                .map(ConnectorChangeResponse::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RemovedStationPreviewResponse {
+    pub station_id: String,
+    pub osm_id: i64,
+    pub name: String,
+    pub address: Option<String>,
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl From<OsmRemovedStation> for RemovedStationPreviewResponse {
+    fn from(station: OsmRemovedStation) -> Self {
+        Self {
+            station_id: station.station_id,
+            osm_id: station.osm_id,
+            name: station.name,
+            address: station.address,
+            latitude: station.latitude,
+            longitude: station.longitude,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SyncPreviewResponse {
+    pub new_count: usize,
+    pub changed_count: usize,
+    pub removed_count: usize,
+    pub unchanged_count: usize,
+    pub conflict_count: usize,
+    /// Staged stations the sync would create
+    pub new_stations: Vec<NewStationPreviewResponse>,
+    /// Existing stations the sync would update
+    pub changed_stations: Vec<StationChangePreviewResponse>,
+    /// OSM stations missing from the extract, which the sync would deactivate
+    pub removed_stations: Vec<RemovedStationPreviewResponse>,
+    /// Staged values the sync would leave unapplied
+    pub conflicts: Vec<SyncConflictResponse>,
+}
+
+impl From<SyncPreview> for SyncPreviewResponse {
+    fn from(preview: SyncPreview) -> Self {
+        Self {
+            new_count: preview.new_stations.len(),
+            changed_count: preview.changed_stations.len(),
+            removed_count: preview.removed_stations.len(),
+            unchanged_count: preview.unchanged_count,
+            conflict_count: preview.conflicts.len(),
+            new_stations: preview
+                .new_stations
+                .into_iter()
+                .map(NewStationPreviewResponse::from)
+                .collect(),
+            changed_stations: preview
+                .changed_stations
+                .into_iter()
+                .map(StationChangePreviewResponse::from)
+                .collect(),
+            removed_stations: preview
+                .removed_stations
+                .into_iter()
+                .map(RemovedStationPreviewResponse::from)
+                .collect(),
+            conflicts: preview
+                .conflicts
+                .into_iter()
+                .map(SyncConflictResponse::from)
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use crate::domain::entities::{
+    NetworkVerificationDetail, NetworkVerificationRequest, VerificationDocument, VerificationEvent,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct VerificationDecisionRequest {
+    /// Shown to the partner; required to reject or revoke
+    #[validate(length(max = 2000))]
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct VerificationRequestResponse {
+    pub request_id: String,
+    pub network_id: String,
+    /// pending, approved, rejected or revoked
+    pub status: String,
+    pub company_name: String,
+    pub registration_number: String,
+    pub vat_number: Option<String>,
+    pub registered_address: String,
+    pub contact_name: String,
+    pub contact_email: String,
+    pub contact_phone: Option<String>,
+    pub submitted_by: String,
+    pub submitted_at: String,
+    pub reviewed_by: Option<String>,
+    pub reviewed_at: Option<String>,
+    pub review_comment: Option<String>,
+}
+
+impl From<NetworkVerificationRequest> for VerificationRequestResponse {
+    fn from(request: NetworkVerificationRequest) -> Self {
+        Self {
+            request_id: request.request_id,
+            network_id: request.network_id,
+            status: request.status,
+            company_name: request.company_name,
+            registration_number: request.registration_number,
+            vat_number: request.vat_number,
+            registered_address: request.registered_address,
+            contact_name: request.contact_name,
+            contact_email: request.contact_email,
+            contact_phone: request.contact_phone,
+            submitted_by: request.submitted_by,
+            submitted_at: request.submitted_at.to_rfc3339(),
+            reviewed_by: request.reviewed_by,
+            reviewed_at: request.reviewed_at.map(|t| t.to_rfc3339()),
+            review_comment: request.review_comment,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct VerificationRequestListResponse {
+    pub requests: Vec<VerificationRequestResponse>,
+    pub total: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct VerificationDocumentResponse {
+    pub document_id: String,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    /// Download path on locate-service, which stores the files; it accepts
+    /// the same admin token
+    pub url: String,
+    pub uploaded_by: String,
+    pub uploaded_at: String,
+}
+
+impl From<VerificationDocument> for VerificationDocumentResponse {
+    fn from(document: VerificationDocument) -> Self {
+        Self {
+            url: format!(
+                "/api/network-verifications/{}/documents/{}",
+                document.request_id, document.document_id
+            ),
+            document_id: document.document_id,
+            file_name: document.file_name,
+            content_type: document.content_type,
+            size_bytes: document.size_bytes,
+            uploaded_by: document.uploaded_by,
+            uploaded_at: document.uploaded_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct VerificationEventResponse {
+    pub network_id: String,
+    pub request_id: Option<String>,
+    pub from_status: Option<String>,
+    pub to_status: String,
+    pub actor_id: String,
+    pub comment: Option<String>,
+    pub created_at: String,
+}
+
+impl From<VerificationEvent> for VerificationEventResponse {
+    fn from(event: VerificationEvent) -> Self {
+        Self {
+            network_id: event.network_id,
+            request_id: event.request_id,
+            from_status: event.from_status,
+            to_status: event.to_status,
+            actor_id: event.actor_id,
+            comment: event.comment,
+            created_at: event.created_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct VerificationRequestDetailResponse {
+    #[serde(flatten)]
+    pub request: VerificationRequestResponse,
+    pub documents: Vec<VerificationDocumentResponse>,
+    /// Oldest first
+    pub events: Vec<VerificationEventResponse>,
+}
+
+impl From<NetworkVerificationDetail> for VerificationRequestDetailResponse {
+    fn from(detail: NetworkVerificationDetail) -> Self {
+        Self {
+            request: VerificationRequestResponse::from(detail.request),
+            documents: detail
+                .documents
+                .into_iter()
+                .map(VerificationDocumentResponse::from)
+                .collect(),
+            events: detail
+                .events
+                .into_iter()
+                .map(VerificationEventResponse::from)
+                .collect(),
+        }
+    }
+}
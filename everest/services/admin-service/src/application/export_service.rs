@@ -0,0 +1,81 @@
+use crate::core::constants::EXPORT_CHUNK_BYTES;
+use crate::core::errors::AppResult;
+use crate::domain::entities::StationExportRow;
+use crate::domain::export::{ExportEncoder, ExportFilter, ExportFormat};
+use crate::domain::repositories::ExportRepository;
+use crate::domain::services::StationExportService;
+use futures_util::StreamExt;
+use futures_util::stream::{self, BoxStream};
+use std::sync::Arc;
+
+pub struct StationExportServiceImpl {
+    export_repo: Arc<dyn ExportRepository>,
+}
+
+impl StationExportServiceImpl {
+    pub fn new(export_repo: Arc<dyn ExportRepository>) -> Self {
+        Self { export_repo }
+    }
+}
+
+struct ExportState {
+    rows: BoxStream<'static, AppResult<StationExportRow>>,
+    encoder: ExportEncoder,
+    started: bool,
+}
+
+impl StationExportService for StationExportServiceImpl {
+    fn export_stations(
+        &self,
+        format: ExportFormat,
+        filter: ExportFilter,
+    ) -> BoxStream<'static, AppResult<String>> {
+        let state = ExportState {
+            rows: self.export_repo.stream_stations(filter),
+            encoder: ExportEncoder::new(format),
+            started: false,
+        };
+
+        // Buffers encoded rows into chunks of about EXPORT_CHUNK_BYTES
+        stream::unfold(Some(state), |state| async move {
+            let mut state = state?;
+            let mut chunk = String::new();
+            if !state.started {
+                state.started = true;
+                match state.encoder.header() {
+                    Ok(header) => chunk.push_str(&header),
+                    Err(e) => return Some((Err(e), None)),
+                }
+            }
+
+            loop {
+                match state.rows.next().await {
+                    Some(Ok(row)) => match state.encoder.push(row) {
+                        Ok(Some(text)) => {
+                            chunk.push_str(&text);
+                            if chunk.len() >= EXPORT_CHUNK_BYTES {
+                                return Some((Ok(chunk), Some(state)));
+                            }
+                        }
+                        Ok(None) => {}
+                        Err(e) => return Some((Err(e), None)),
+                    },
+                    Some(Err(e)) => {
+                        tracing::error!("Station export failed: {}", e);
+                        return Some((Err(e), None));
+                    }
+                    None => {
+                        return match state.encoder.finish() {
+                            Ok(text) => {
+                                chunk.push_str(&text);
+                                Some((Ok(chunk), None))
+                            }
+                            Err(e) => Some((Err(e), None)),
+                        };
+                    }
+                }
+            }
+        })
+        .boxed()
+    }
+}
@@ -3,7 +3,6 @@ use crate::core::database::check_database_health;
 use crate::core::errors::AppResult;
 use chrono::Utc;
 use sqlx::PgPool;
-use std::sync::Arc;
 
 pub struct HealthService {
     db_pool: PgPool,
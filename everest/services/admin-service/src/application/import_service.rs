@@ -0,0 +1,116 @@
+use crate::core::constants::MAX_IMPORT_ROWS;
+use crate::core::errors::{AppError, AppResult};
+use crate::domain::import::{ImportFormat, ImportLookups, ImportReport, SourceRow, plan_import};
+use crate::domain::repositories::ImportRepository;
+use crate::domain::services::StationImportService;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+pub struct StationImportServiceImpl {
+    import_repo: Arc<dyn ImportRepository>,
+}
+
+impl StationImportServiceImpl {
+    pub fn new(import_repo: Arc<dyn ImportRepository>) -> Self {
+        Self { import_repo }
+    }
+
+    /// Loads the reference data the rows mention
+    async fn load_lookups(&self, rows: &[SourceRow]) -> AppResult<ImportLookups> {
+        let mut network_ids: Vec<String> =
+            rows.iter().filter_map(|r| r.network_id.clone()).collect();
+        network_ids.sort_unstable();
+        network_ids.dedup();
+        let osm_ids: Vec<i64> = rows.iter().filter_map(|r| r.osm_id).collect();
+        let external_refs: Vec<String> =
+            rows.iter().filter_map(|r| r.station_ref.clone()).collect();
+
+        Ok(ImportLookups {
+            connector_types: self
+                .import_repo
+                .find_connector_types()
+                .await?
+                .into_iter()
+                .map(|t| (t.name.to_lowercase(), t.id))
+                .collect(),
+            current_types: self
+                .import_repo
+                .find_current_types()
+                .await?
+                .into_iter()
+                .map(|t| (t.name.to_uppercase(), t.id))
+                .collect(),
+            networks: self
+                .import_repo
+                .find_existing_networks(&network_ids)
+                .await?
+                .into_iter()
+                .collect(),
+            existing_osm_ids: self
+                .import_repo
+                .find_existing_osm_ids(&osm_ids)
+                .await?
+                .into_iter()
+                .collect(),
+            existing_refs: self
+                .import_repo
+                .find_existing_refs(&external_refs)
+                .await?
+                .into_iter()
+                .collect(),
+        })
+    }
+}
+
+#[async_trait]
+impl StationImportService for StationImportServiceImpl {
+    async fn import_stations(
+        &self,
+        format: ImportFormat,
+        text: &str,
+        dry_run: bool,
+        imported_by: &str,
+    ) -> AppResult<ImportReport> {
+        let rows = format.parse(text)?;
+        if rows.is_empty() {
+            return Err(AppError::ValidationError(
+                "The file contains no stations".to_string(),
+            ));
+        }
+        if rows.len() > MAX_IMPORT_ROWS {
+            return Err(AppError::ValidationError(format!(
+                "At most {} rows can be imported at once",
+                MAX_IMPORT_ROWS
+            )));
+        }
+
+        let lookups = self.load_lookups(&rows).await?;
+        let plan = plan_import(rows, &lookups);
+
+        let committed = !dry_run && plan.is_valid();
+        if committed {
+            self.import_repo
+                .import_stations(&plan.stations, imported_by)
+                .await?;
+            tracing::info!(
+                "Imported {} stations and {} connectors",
+                plan.stations.len(),
+                plan.connector_count()
+            );
+        }
+
+        Ok(ImportReport {
+            format,
+            dry_run,
+            committed,
+            station_count: plan.stations.len(),
+            connector_count: plan.connector_count(),
+            station_ids: if committed {
+                plan.stations.iter().map(|s| s.station_id.clone()).collect()
+            } else {
+                Vec::new()
+            },
+            rows: plan.rows,
+        })
+    }
+}
@@ -1,5 +1,15 @@
 pub mod connector_service;
+pub mod deleted_record_service;
 pub mod dtos;
+pub mod export_service;
 pub mod health_service;
+pub mod import_service;
 pub mod network_service;
+pub mod network_verification_service;
+pub mod ocpi_service;
+pub mod osm_import_service;
+pub mod region_service;
+pub mod review_moderation_service;
+pub mod station_merge_service;
 pub mod station_service;
+pub mod sync_service;
@@ -82,16 +82,20 @@ impl NetworkService for NetworkServiceImpl {
         if let Some(email) = data.support_email {
             network.support_email = Some(email);
         }
-        if let Some(verified) = data.is_verified {
-            network.is_verified = verified;
-        }
 
         network.updated_at = Utc::now();
         self.network_repo.update(&network).await
     }
 
-    async fn delete_network(&self, network_id: &str) -> AppResult<()> {
+    async fn delete_network(&self, network_id: &str, deleted_by: &str) -> AppResult<()> {
         let _ = self.get_network(network_id).await?;
-        self.network_repo.delete(network_id).await
+        self.network_repo.delete(network_id, deleted_by).await
+    }
+
+    async fn restore_network(&self, network_id: &str, restored_by: &str) -> AppResult<Network> {
+        self.network_repo
+            .restore(network_id, restored_by)
+            .await?
+            .ok_or(AppError::NotFound("Deleted network not found".to_string()))
     }
 }
@@ -0,0 +1,371 @@
+use crate::core::errors::{AppError, AppResult};
+use crate::domain::entities::{
+    NetworkVerificationDetail, NetworkVerificationRequest, VerificationEvent,
+};
+use crate::domain::repositories::NetworkVerificationRepository;
+use crate::domain::services::NetworkVerificationService;
+use crate::domain::verification::{VerificationNotification, VerificationStatus};
+use async_trait::async_trait;
+use std::str::FromStr;
+use std::sync::Arc;
+
+pub struct NetworkVerificationServiceImpl {
+    verification_repo: Arc<dyn NetworkVerificationRepository>,
+}
+
+impl NetworkVerificationServiceImpl {
+    pub fn new(verification_repo: Arc<dyn NetworkVerificationRepository>) -> Self {
+        Self { verification_repo }
+    }
+
+    async fn find_request(&self, request_id: &str) -> AppResult<NetworkVerificationRequest> {
+        self.verification_repo
+            .find_by_id(request_id)
+            .await?
+            .ok_or(AppError::NotFound(format!(
+                "Verification request with id {} not found",
+                request_id
+            )))
+    }
+
+    /// Moves the request to `next` and tells the partner who submitted it
+    async fn decide(
+        &self,
+        request: NetworkVerificationRequest,
+        next: VerificationStatus,
+        admin_id: &str,
+        comment: Option<String>,
+    ) -> AppResult<NetworkVerificationRequest> {
+        let request_id = request.request_id.as_str();
+        let current = VerificationStatus::from_str(&request.status)?;
+        if !current.can_transition_to(next) {
+            return Err(AppError::Conflict(format!(
+                "Verification request {} is {} and cannot move to {}",
+                request_id,
+                current.as_str(),
+                next.as_str()
+            )));
+        }
+
+        let comment = comment.filter(|c| !c.trim().is_empty());
+        let notification = VerificationNotification::for_decision(next, comment.as_deref()).ok_or(
+            AppError::InternalError(format!("No notification for a move to {}", next.as_str())),
+        )?;
+
+        self.verification_repo
+            .transition(
+                request_id,
+                current,
+                next,
+                admin_id,
+                comment.as_deref(),
+                &notification,
+            )
+            .await?
+            .ok_or(AppError::Conflict(format!(
+                "Verification request {} was changed meanwhile",
+                request_id
+            )))
+    }
+}
+
+fn require_comment(comment: String) -> AppResult<String> {
+    if comment.trim().is_empty() {
+        return Err(AppError::ValidationError(
+            "A comment is required".to_string(),
+        ));
+    }
+    Ok(comment)
+}
+
+#[async_trait]
+impl NetworkVerificationService for NetworkVerificationServiceImpl {
+    async fn list_requests(
+        &self,
+        network_id: Option<&str>,
+        status: Option<VerificationStatus>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<(Vec<NetworkVerificationRequest>, i64)> {
+        let status = status.as_ref().map(VerificationStatus::as_str);
+        let requests = self
+            .verification_repo
+            .find_all(network_id, status, limit, offset)
+            .await?;
+        let total = self.verification_repo.count(network_id, status).await?;
+        Ok((requests, total))
+    }
+
+    async fn get_request(&self, request_id: &str) -> AppResult<NetworkVerificationDetail> {
+        let request = self.find_request(request_id).await?;
+        let documents = self.verification_repo.find_documents(request_id).await?;
+        let events = self
+            .verification_repo
+            .find_events_by_request(request_id)
+            .await?;
+
+        Ok(NetworkVerificationDetail {
+            request,
+            documents,
+            events,
+        })
+    }
+
+    async fn approve_request(
+        &self,
+        request_id: &str,
+        admin_id: &str,
+        comment: Option<String>,
+    ) -> AppResult<NetworkVerificationRequest> {
+        let request = self.find_request(request_id).await?;
+        if self.verification_repo.count_documents(request_id).await? == 0 {
+            return Err(AppError::ValidationError(format!(
+                "Verification request {} has no documents",
+                request_id
+            )));
+        }
+
+        self.decide(request, VerificationStatus::Approved, admin_id, comment)
+            .await
+    }
+
+    async fn reject_request(
+        &self,
+        request_id: &str,
+        admin_id: &str,
+        comment: String,
+    ) -> AppResult<NetworkVerificationRequest> {
+        let comment = require_comment(comment)?;
+        let request = self.find_request(request_id).await?;
+        self.decide(
+            request,
+            VerificationStatus::Rejected,
+            admin_id,
+            Some(comment),
+        )
+        .await
+    }
+
+    async fn revoke_verification(
+        &self,
+        network_id: &str,
+        admin_id: &str,
+        comment: String,
+    ) -> AppResult<()> {
+        let comment = require_comment(comment)?;
+
+        if let Some(approved) = self.verification_repo.find_approved(network_id).await? {
+            self.decide(
+                approved,
+                VerificationStatus::Revoked,
+                admin_id,
+                Some(comment),
+            )
+            .await?;
+            return Ok(());
+        }
+
+        // Networks verified before requests existed have nothing to revoke
+        match self
+            .verification_repo
+            .find_network_verified(network_id)
+            .await?
+        {
+            None => Err(AppError::NotFound(format!(
+                "Network with id {} not found",
+                network_id
+            ))),
+            Some(false) => Err(AppError::Conflict(format!(
+                "Network {} is not verified",
+                network_id
+            ))),
+            Some(true) => {
+                if !self
+                    .verification_repo
+                    .revoke_network(network_id, admin_id, Some(&comment))
+                    .await?
+                {
+                    return Err(AppError::Conflict(format!(
+                        "Network {} is not verified",
+                        network_id
+                    )));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    async fn list_network_events(&self, network_id: &str) -> AppResult<Vec<VerificationEvent>> {
+        if self
+            .verification_repo
+            .find_network_verified(network_id)
+            .await?
+            .is_none()
+        {
+            return Err(AppError::NotFound(format!(
+                "Network with id {} not found",
+                network_id
+            )));
+        }
+
+        self.verification_repo
+            .find_events_by_network(network_id)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repositories::MockNetworkVerificationRepository;
+    use chrono::Utc;
+    use mockall::predicate::eq;
+
+    fn request(status: VerificationStatus) -> NetworkVerificationRequest {
+        NetworkVerificationRequest {
+            request_id: "NVR-1".to_string(),
+            network_id: "NET-1".to_string(),
+            status: status.as_str().to_string(),
+            company_name: "Volt SARL".to_string(),
+            registration_number: "B123".to_string(),
+            vat_number: None,
+            registered_address: "1 Rue de Marseille, Tunis".to_string(),
+            contact_name: "Amel".to_string(),
+            contact_email: "amel@example.com".to_string(),
+            contact_phone: None,
+            submitted_by: "partner-1".to_string(),
+            submitted_at: Utc::now(),
+            reviewed_by: None,
+            reviewed_at: None,
+            review_comment: None,
+        }
+    }
+
+    fn service(repo: MockNetworkVerificationRepository) -> NetworkVerificationServiceImpl {
+        NetworkVerificationServiceImpl::new(Arc::new(repo))
+    }
+
+    #[tokio::test]
+    async fn approves_a_pending_request_with_documents_and_notifies_the_partner() {
+        let mut repo = MockNetworkVerificationRepository::new();
+        repo.expect_find_by_id()
+            .returning(|_| Ok(Some(request(VerificationStatus::Pending))));
+        repo.expect_count_documents().returning(|_| Ok(1));
+        repo.expect_transition()
+            .withf(|id, from, to, admin, comment, notification| {
+                id == "NVR-1"
+                    && *from == VerificationStatus::Pending
+                    && *to == VerificationStatus::Approved
+                    && admin == "admin-1"
+                    && comment.is_none()
+                    && notification.kind == "network_verification_approved"
+            })
+            .times(1)
+            .returning(|_, _, _, _, _, _| Ok(Some(request(VerificationStatus::Approved))));
+
+        let approved = service(repo)
+            .approve_request("NVR-1", "admin-1", Some("  ".to_string()))
+            .await
+            .unwrap();
+
+        assert_eq!(approved.status, "approved");
+    }
+
+    #[tokio::test]
+    async fn does_not_approve_a_request_without_documents() {
+        let mut repo = MockNetworkVerificationRepository::new();
+        repo.expect_find_by_id()
+            .returning(|_| Ok(Some(request(VerificationStatus::Pending))));
+        repo.expect_count_documents().returning(|_| Ok(0));
+        repo.expect_transition().never();
+
+        let result = service(repo)
+            .approve_request("NVR-1", "admin-1", None)
+            .await;
+
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn rejecting_needs_a_comment() {
+        let mut repo = MockNetworkVerificationRepository::new();
+        repo.expect_find_by_id().never();
+
+        let result = service(repo)
+            .reject_request("NVR-1", "admin-1", " ".to_string())
+            .await;
+
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn does_not_decide_twice() {
+        let mut repo = MockNetworkVerificationRepository::new();
+        repo.expect_find_by_id()
+            .returning(|_| Ok(Some(request(VerificationStatus::Rejected))));
+        repo.expect_transition().never();
+
+        let result = service(repo)
+            .reject_request("NVR-1", "admin-1", "Unreadable extract".to_string())
+            .await;
+
+        assert!(matches!(result, Err(AppError::Conflict(_))));
+    }
+
+    #[tokio::test]
+    async fn revokes_the_approved_request_of_the_network() {
+        let mut repo = MockNetworkVerificationRepository::new();
+        repo.expect_find_approved()
+            .with(eq("NET-1"))
+            .returning(|_| Ok(Some(request(VerificationStatus::Approved))));
+        repo.expect_transition()
+            .withf(|_, from, to, _, comment, notification| {
+                *from == VerificationStatus::Approved
+                    && *to == VerificationStatus::Revoked
+                    && *comment == Some("Company dissolved")
+                    && notification.message.ends_with("Company dissolved")
+            })
+            .times(1)
+            .returning(|_, _, _, _, _, _| Ok(Some(request(VerificationStatus::Revoked))));
+        repo.expect_revoke_network().never();
+
+        service(repo)
+            .revoke_verification("NET-1", "admin-1", "Company dissolved".to_string())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn revokes_a_network_verified_before_requests_existed() {
+        let mut repo = MockNetworkVerificationRepository::new();
+        repo.expect_find_approved().returning(|_| Ok(None));
+        repo.expect_find_network_verified()
+            .returning(|_| Ok(Some(true)));
+        repo.expect_revoke_network()
+            .withf(|network, admin, comment| {
+                network == "NET-1" && admin == "admin-1" && *comment == Some("Company dissolved")
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(true));
+
+        service(repo)
+            .revoke_verification("NET-1", "admin-1", "Company dissolved".to_string())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn does_not_revoke_an_unverified_network() {
+        let mut repo = MockNetworkVerificationRepository::new();
+        repo.expect_find_approved().returning(|_| Ok(None));
+        repo.expect_find_network_verified()
+            .returning(|_| Ok(Some(false)));
+        repo.expect_revoke_network().never();
+
+        let result = service(repo)
+            .revoke_verification("NET-1", "admin-1", "Company dissolved".to_string())
+            .await;
+
+        assert!(matches!(result, Err(AppError::Conflict(_))));
+    }
+}
@@ -0,0 +1,376 @@
+use crate::core::constants::{
+    OCPI_PARTY_ID_PREFIX, OCPI_PUSH_BATCH, OCPI_PUSH_SETTLE_SECONDS, OCPI_TOKEN_LENGTH,
+    OCPI_VERSION,
+};
+use crate::core::errors::{AppError, AppResult};
+use crate::core::utils::generate_id;
+use crate::domain::entities::OcpiParty;
+use crate::domain::ocpi::{
+    self, Connector, Credentials, Evse, Location, OcpiIdentity, OcpiPartyApi, OcpiPartyStatus,
+    OcpiToken, Tariff,
+};
+use crate::domain::repositories::{OcpiPartyRepository, OcpiRepository};
+use crate::domain::services::OcpiService;
+use crate::domain::value_objects::OcpiRegistrationData;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use std::time::Duration;
+
+pub struct OcpiServiceImpl {
+    ocpi_repo: Arc<dyn OcpiRepository>,
+    party_repo: Arc<dyn OcpiPartyRepository>,
+    party_api: Arc<dyn OcpiPartyApi>,
+    identity: OcpiIdentity,
+}
+
+impl OcpiServiceImpl {
+    pub fn new(
+        ocpi_repo: Arc<dyn OcpiRepository>,
+        party_repo: Arc<dyn OcpiPartyRepository>,
+        party_api: Arc<dyn OcpiPartyApi>,
+        identity: OcpiIdentity,
+    ) -> Self {
+        Self {
+            ocpi_repo,
+            party_repo,
+            party_api,
+            identity,
+        }
+    }
+
+    /// Pushes the location updates at startup and every `interval` after
+    pub fn schedule(self: Arc<Self>, interval: Duration) {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                ticker.tick().await;
+                match self.push_locations().await {
+                    Ok(0) => {}
+                    Ok(pushed) => tracing::info!("Pushed {} location updates over OCPI", pushed),
+                    Err(e) => tracing::error!("Failed to push locations over OCPI: {}", e),
+                }
+            }
+        });
+    }
+
+    /// Reads the party's credentials and endpoints. Credentials listing
+    /// several roles register as the eMSP one.
+    async fn fetch_registration(
+        &self,
+        credentials: &Credentials,
+        token_c: String,
+    ) -> AppResult<OcpiRegistrationData> {
+        let role = credentials
+            .roles
+            .iter()
+            .find(|r| r.role == "EMSP")
+            .or_else(|| credentials.roles.first())
+            .ok_or_else(|| AppError::ValidationError("Credentials list no role".to_string()))?;
+        if role.country_code.len() != 2 || role.party_id.is_empty() || role.party_id.len() > 3 {
+            return Err(AppError::ValidationError(
+                "Credentials role needs a 2 letter country code and a party id of up to 3 characters"
+                    .to_string(),
+            ));
+        }
+
+        let versions = self
+            .party_api
+            .versions(&credentials.url, &credentials.token)
+            .await?;
+        let version = versions
+            .iter()
+            .find(|v| v.version == OCPI_VERSION)
+            .ok_or_else(|| {
+                AppError::InternalError(format!("Party does not support OCPI {}", OCPI_VERSION))
+            })?;
+        let details = self
+            .party_api
+            .version_details(&version.url, &credentials.token)
+            .await?;
+        let locations_url = details
+            .endpoints
+            .iter()
+            .find(|e| e.identifier == "locations" && e.role == "RECEIVER")
+            .map(|e| e.url.clone());
+
+        Ok(OcpiRegistrationData {
+            country_code: role.country_code.to_uppercase(),
+            party_id: role.party_id.to_uppercase(),
+            role: role.role.clone(),
+            token_b: credentials.token.clone(),
+            token_c,
+            versions_url: credentials.url.clone(),
+            locations_url,
+        })
+    }
+
+    async fn store_registration(
+        &self,
+        party: &OcpiParty,
+        credentials: Credentials,
+    ) -> AppResult<Credentials> {
+        let token_c = nanoid::nanoid!(OCPI_TOKEN_LENGTH);
+        let registration = self
+            .fetch_registration(&credentials, token_c.clone())
+            .await?;
+        self.party_repo
+            .register(&party.party_uid, &registration)
+            .await?
+            .ok_or_else(|| AppError::Unauthorized("OCPI party was unregistered".to_string()))?;
+
+        Ok(self.identity.credentials(&token_c))
+    }
+
+    /// Pushes the locations past the party's cursor, then records how far
+    /// it got. Returns how many were pushed along with the outcome.
+    async fn push_to(&self, party: &OcpiParty) -> (usize, AppResult<()>) {
+        let mut cursor = party
+            .locations_pushed_until
+            .zip(party.locations_pushed_until_id.clone());
+        let mut pushed = 0;
+        let result = self.push_batches(party, &mut cursor, &mut pushed).await;
+
+        let error = result.as_ref().err().map(|e| e.to_string());
+        if let Err(e) = self
+            .party_repo
+            .record_push(&party.party_uid, cursor, error.as_deref())
+            .await
+        {
+            return (pushed, Err(e));
+        }
+
+        (pushed, result)
+    }
+
+    async fn push_batches(
+        &self,
+        party: &OcpiParty,
+        cursor: &mut Option<(DateTime<Utc>, String)>,
+        pushed: &mut usize,
+    ) -> AppResult<()> {
+        // A party pushed to from scratch never saw the removed locations
+        let from_scratch = cursor.is_none();
+        loop {
+            let stations = self
+                .ocpi_repo
+                .find_locations_after(cursor.clone(), OCPI_PUSH_SETTLE_SECONDS, OCPI_PUSH_BATCH)
+                .await?;
+            if stations.is_empty() {
+                return Ok(());
+            }
+
+            for station in stations {
+                if !(from_scratch && station.removed) {
+                    let location = ocpi::location(&self.identity, &station);
+                    self.party_api.put_location(party, &location).await?;
+                    *pushed += 1;
+                }
+                *cursor = Some((station.last_updated, station.station_id));
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl OcpiService for OcpiServiceImpl {
+    async fn create_party(&self, name: &str, created_by: &str) -> AppResult<OcpiParty> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(AppError::ValidationError(
+                "Party name cannot be empty".to_string(),
+            ));
+        }
+
+        let party = OcpiParty {
+            party_uid: generate_id(OCPI_PARTY_ID_PREFIX),
+            name: name.to_string(),
+            status: OcpiPartyStatus::Pending.as_str().to_string(),
+            country_code: None,
+            party_id: None,
+            role: None,
+            token_a: Some(nanoid::nanoid!(OCPI_TOKEN_LENGTH)),
+            token_b: None,
+            token_c: None,
+            versions_url: None,
+            locations_url: None,
+            locations_pushed_until: None,
+            locations_pushed_until_id: None,
+            last_push_error: None,
+            last_push_at: None,
+            created_by: Some(created_by.to_string()),
+            created_at: Utc::now(),
+            registered_at: None,
+            updated_at: Utc::now(),
+        };
+
+        self.party_repo.create(&party).await
+    }
+
+    async fn list_parties(&self) -> AppResult<Vec<OcpiParty>> {
+        self.party_repo.find_all().await
+    }
+
+    async fn unregister_party(&self, party_uid: &str) -> AppResult<OcpiParty> {
+        if let Some(party) = self.party_repo.unregister(party_uid).await? {
+            return Ok(party);
+        }
+
+        match self.party_repo.find_by_id(party_uid).await? {
+            Some(_) => Err(AppError::Conflict(
+                "OCPI party is already unregistered".to_string(),
+            )),
+            None => Err(AppError::NotFound("OCPI party not found".to_string())),
+        }
+    }
+
+    async fn authenticate(&self, token: &str) -> AppResult<(OcpiParty, OcpiToken)> {
+        let party = self
+            .party_repo
+            .find_by_token(token)
+            .await?
+            .ok_or_else(|| AppError::Unauthorized("Unknown OCPI token".to_string()))?;
+        let kind = if party.token_a.as_deref() == Some(token) {
+            OcpiToken::Registration
+        } else {
+            OcpiToken::Access
+        };
+
+        Ok((party, kind))
+    }
+
+    fn identity(&self) -> &OcpiIdentity {
+        &self.identity
+    }
+
+    async fn register(
+        &self,
+        party: &OcpiParty,
+        credentials: Credentials,
+    ) -> AppResult<Credentials> {
+        if party.status != OcpiPartyStatus::Pending.as_str() {
+            return Err(AppError::Conflict(
+                "Already registered, update the credentials with PUT".to_string(),
+            ));
+        }
+        self.store_registration(party, credentials).await
+    }
+
+    async fn update_registration(
+        &self,
+        party: &OcpiParty,
+        credentials: Credentials,
+    ) -> AppResult<Credentials> {
+        if party.status != OcpiPartyStatus::Registered.as_str() {
+            return Err(AppError::Conflict(
+                "Not registered yet, register with POST".to_string(),
+            ));
+        }
+        self.store_registration(party, credentials).await
+    }
+
+    async fn unregister(&self, party: &OcpiParty) -> AppResult<()> {
+        if party.status != OcpiPartyStatus::Registered.as_str() {
+            return Err(AppError::Conflict("Not registered".to_string()));
+        }
+        self.party_repo.unregister(&party.party_uid).await?;
+        Ok(())
+    }
+
+    async fn list_locations(
+        &self,
+        date_from: Option<DateTime<Utc>>,
+        date_to: Option<DateTime<Utc>>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<(Vec<Location>, i64)> {
+        let stations = self
+            .ocpi_repo
+            .find_locations(date_from, date_to, limit, offset)
+            .await?;
+        let total = self.ocpi_repo.count_locations(date_from, date_to).await?;
+
+        Ok((
+            stations
+                .iter()
+                .map(|station| ocpi::location(&self.identity, station))
+                .collect(),
+            total,
+        ))
+    }
+
+    async fn get_location(&self, location_id: &str) -> AppResult<Location> {
+        let station = self
+            .ocpi_repo
+            .find_location(location_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Unknown location {}", location_id)))?;
+
+        Ok(ocpi::location(&self.identity, &station))
+    }
+
+    async fn get_evse(&self, location_id: &str, evse_uid: &str) -> AppResult<Evse> {
+        self.get_location(location_id)
+            .await?
+            .evses
+            .into_iter()
+            .find(|evse| evse.uid == evse_uid)
+            .ok_or_else(|| AppError::NotFound(format!("Unknown EVSE {}", evse_uid)))
+    }
+
+    async fn get_connector(
+        &self,
+        location_id: &str,
+        evse_uid: &str,
+        connector_id: &str,
+    ) -> AppResult<Connector> {
+        self.get_evse(location_id, evse_uid)
+            .await?
+            .connectors
+            .into_iter()
+            .find(|connector| connector.id == connector_id)
+            .ok_or_else(|| AppError::NotFound(format!("Unknown connector {}", connector_id)))
+    }
+
+    async fn list_tariffs(
+        &self,
+        date_from: Option<DateTime<Utc>>,
+        date_to: Option<DateTime<Utc>>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<(Vec<Tariff>, i64)> {
+        let tariffs = self
+            .ocpi_repo
+            .find_tariffs(date_from, date_to, limit, offset)
+            .await?;
+        let total = self.ocpi_repo.count_tariffs(date_from, date_to).await?;
+
+        Ok((
+            tariffs
+                .iter()
+                .map(|tariff| ocpi::tariff(&self.identity, tariff))
+                .collect(),
+            total,
+        ))
+    }
+
+    async fn push_locations(&self) -> AppResult<usize> {
+        let parties = self.party_repo.find_registered().await?;
+
+        let mut pushed = 0;
+        for party in parties.iter().filter(|p| p.locations_url.is_some()) {
+            let (count, result) = self.push_to(party).await;
+            pushed += count;
+            if let Err(e) = result {
+                tracing::warn!(
+                    "Pushing locations to OCPI party {} stopped: {}",
+                    party.party_uid,
+                    e
+                );
+            }
+        }
+
+        Ok(pushed)
+    }
+}
@@ -0,0 +1,282 @@
+use crate::core::errors::{AppError, AppResult};
+use crate::domain::osm_import::{
+    OsmDiffReport, OsmImportReport, OsmStagingChanges, StagedOsmStation, WAY_OSM_ID_OFFSET,
+    is_charging_station, staged_station, way_centroid,
+};
+use crate::domain::repositories::OsmStagingRepository;
+use crate::domain::services::OsmImportService;
+use crate::infrastructure::osm_change::{ChangeAction, ChangedElement, OsmChange, read_changes};
+use crate::infrastructure::osm_pbf::{OsmElement, for_each_element};
+use async_trait::async_trait;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+pub struct OsmImportServiceImpl {
+    staging_repo: Arc<dyn OsmStagingRepository>,
+}
+
+impl OsmImportServiceImpl {
+    pub fn new(staging_repo: Arc<dyn OsmStagingRepository>) -> Self {
+        Self { staging_repo }
+    }
+}
+
+/// A charging station way, kept until its nodes are located
+struct StationWay {
+    id: i64,
+    node_ids: Vec<i64>,
+    tags: Vec<(String, String)>,
+}
+
+/// Charging station nodes and ways of the file as staging rows. Ways need a
+/// second pass over the file for the coordinates of their nodes.
+pub fn read_charging_stations(path: &Path) -> AppResult<(Vec<StagedOsmStation>, OsmImportReport)> {
+    let mut report = OsmImportReport::default();
+    let mut stations = Vec::new();
+    let mut ways: Vec<StationWay> = Vec::new();
+
+    let sequence_number = for_each_element(path, |element| match element {
+        OsmElement::Node {
+            id,
+            longitude,
+            latitude,
+            tags,
+        } if is_charging_station(&tags) => {
+            report.station_nodes += 1;
+            stations.push(staged_station(id, longitude, latitude, &tags));
+        }
+        OsmElement::Way { id, node_ids, tags } if is_charging_station(&tags) => {
+            report.station_ways += 1;
+            let tags = tags
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+            ways.push(StationWay { id, node_ids, tags });
+        }
+        _ => {}
+    })?;
+
+    if !ways.is_empty() {
+        let wanted: HashSet<i64> = ways.iter().flat_map(|w| &w.node_ids).copied().collect();
+        let mut locations: HashMap<i64, (f64, f64)> = HashMap::with_capacity(wanted.len());
+        for_each_element(path, |element| {
+            if let OsmElement::Node {
+                id,
+                longitude,
+                latitude,
+                ..
+            } = element
+                && wanted.contains(&id)
+            {
+                locations.insert(id, (longitude, latitude));
+            }
+        })?;
+
+        for way in ways {
+            let points: Vec<(f64, f64)> = way
+                .node_ids
+                .iter()
+                .filter_map(|node_id| locations.get(node_id).copied())
+                .collect();
+            let Some((longitude, latitude)) = way_centroid(&points) else {
+                report.skipped_ways += 1;
+                continue;
+            };
+            let tags: Vec<(&str, &str)> = way
+                .tags
+                .iter()
+                .map(|(k, v)| (k.as_str(), v.as_str()))
+                .collect();
+            stations.push(staged_station(
+                WAY_OSM_ID_OFFSET + way.id,
+                longitude,
+                latitude,
+                &tags,
+            ));
+        }
+    }
+
+    report.staged = stations.len();
+    report.sequence_number = sequence_number;
+    Ok((stations, report))
+}
+
+/// The charging stations of several extracts, e.g. of neighbouring
+/// countries. Stations near a border may be in both extracts and are staged
+/// once. Extracts from different replication streams cannot share one
+/// sequence, so merged extracts have none.
+pub fn read_extracts(paths: &[PathBuf]) -> AppResult<(Vec<StagedOsmStation>, OsmImportReport)> {
+    if let [path] = paths {
+        return read_charging_stations(path);
+    }
+
+    let mut report = OsmImportReport::default();
+    let mut stations = Vec::new();
+    let mut seen: HashSet<i64> = HashSet::new();
+    for path in paths {
+        let (extract_stations, extract_report) = read_charging_stations(path)?;
+        report.station_nodes += extract_report.station_nodes;
+        report.station_ways += extract_report.station_ways;
+        report.skipped_ways += extract_report.skipped_ways;
+        stations.extend(
+            extract_stations
+                .into_iter()
+                .filter(|station| seen.insert(station.osm_id)),
+        );
+    }
+
+    report.staged = stations.len();
+    Ok((stations, report))
+}
+
+/// What the changes of a diff, applied in order, leave for each charging
+/// station element. `staged_locations` are the staged locations of the
+/// changed ways, used when the diff lacks some of their nodes.
+///
+/// Only the way itself being changed updates its staged location: nodes that
+/// move without the way changing are not followed.
+fn staging_changes(
+    changes: &[OsmChange],
+    staged_locations: &HashMap<i64, (f64, f64)>,
+    report: &mut OsmDiffReport,
+) -> OsmStagingChanges {
+    let node_locations: HashMap<i64, (f64, f64)> = changes
+        .iter()
+        .filter(|change| change.action != ChangeAction::Delete)
+        .filter_map(|change| match &change.element {
+            ChangedElement::Node {
+                id,
+                location: Some(location),
+                ..
+            } => Some((*id, *location)),
+            _ => None,
+        })
+        .collect();
+
+    // The last change of an element wins; None removes it
+    let mut outcome: BTreeMap<i64, Option<StagedOsmStation>> = BTreeMap::new();
+    for change in changes {
+        let (osm_id, tags) = match &change.element {
+            ChangedElement::Node { id, tags, .. } => (*id, tags),
+            ChangedElement::Way { id, tags, .. } => (WAY_OSM_ID_OFFSET + id, tags),
+        };
+        let tags: Vec<(&str, &str)> = tags.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+        if change.action == ChangeAction::Delete || !is_charging_station(&tags) {
+            outcome.insert(osm_id, None);
+            continue;
+        }
+
+        let location = match &change.element {
+            ChangedElement::Node { location, .. } => *location,
+            ChangedElement::Way { node_ids, .. } => node_ids
+                .iter()
+                .map(|node_id| node_locations.get(node_id).copied())
+                .collect::<Option<Vec<_>>>()
+                .and_then(|points| way_centroid(&points))
+                .or_else(|| staged_locations.get(&osm_id).copied()),
+        };
+        match location {
+            Some((longitude, latitude)) => {
+                outcome.insert(
+                    osm_id,
+                    Some(staged_station(osm_id, longitude, latitude, &tags)),
+                );
+            }
+            None => report.skipped_ways += 1,
+        }
+    }
+
+    let mut staging = OsmStagingChanges::default();
+    for (osm_id, station) in outcome {
+        match station {
+            Some(station) => staging.upserts.push(station),
+            None => staging.removed_osm_ids.push(osm_id),
+        }
+    }
+    report.upserted = staging.upserts.len();
+    staging
+}
+
+#[async_trait]
+impl OsmImportService for OsmImportServiceImpl {
+    async fn import_pbf(&self, paths: &[PathBuf]) -> AppResult<OsmImportReport> {
+        if paths.is_empty() {
+            return Err(AppError::ValidationError(
+                "No extract to import".to_string(),
+            ));
+        }
+        let paths = paths.to_vec();
+        let (stations, report) = tokio::task::spawn_blocking(move || read_extracts(&paths))
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))??;
+
+        if stations.is_empty() {
+            return Err(AppError::ValidationError(
+                "The extracts have no amenity=charging_station nodes or ways".to_string(),
+            ));
+        }
+
+        self.staging_repo
+            .replace_staged_stations(&stations, report.sequence_number)
+            .await?;
+        Ok(report)
+    }
+
+    async fn apply_diff(&self, path: &Path, sequence_number: i64) -> AppResult<OsmDiffReport> {
+        if !self.staging_repo.has_staged_stations().await? {
+            return Err(AppError::ValidationError(
+                "Nothing is staged, import a full extract before applying diffs".to_string(),
+            ));
+        }
+        match self.staging_repo.find_replication_sequence().await? {
+            Some(last) if sequence_number <= last => {
+                return Err(AppError::Conflict(format!(
+                    "Diff {} is already applied, the last applied sequence is {}",
+                    sequence_number, last
+                )));
+            }
+            Some(last) if sequence_number > last + 1 => {
+                return Err(AppError::ValidationError(format!(
+                    "Diff {} does not follow the last applied sequence {}, apply {} first",
+                    sequence_number,
+                    last,
+                    last + 1
+                )));
+            }
+            _ => {}
+        }
+
+        let path: PathBuf = path.to_path_buf();
+        let changes = tokio::task::spawn_blocking(move || read_changes(&path))
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))??;
+
+        let way_ids: Vec<i64> = changes
+            .iter()
+            .filter_map(|change| match change.element {
+                ChangedElement::Way { id, .. } => Some(WAY_OSM_ID_OFFSET + id),
+                ChangedElement::Node { .. } => None,
+            })
+            .collect();
+        let staged_locations: HashMap<i64, (f64, f64)> = self
+            .staging_repo
+            .find_staged_locations(&way_ids)
+            .await?
+            .into_iter()
+            .map(|(osm_id, longitude, latitude)| (osm_id, (longitude, latitude)))
+            .collect();
+
+        let mut report = OsmDiffReport {
+            sequence_number,
+            ..Default::default()
+        };
+        let staging = staging_changes(&changes, &staged_locations, &mut report);
+        (report.removed, report.deactivated) = self
+            .staging_repo
+            .apply_staged_changes(&staging, sequence_number)
+            .await?;
+
+        Ok(report)
+    }
+}
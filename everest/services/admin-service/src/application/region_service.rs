@@ -0,0 +1,158 @@
+use crate::core::errors::{AppError, AppResult};
+use crate::domain::entities::{Region, RegionStatistics};
+use crate::domain::repositories::RegionRepository;
+use crate::domain::services::RegionService;
+use crate::domain::value_objects::{CreateRegionData, RegionBoundary};
+use async_trait::async_trait;
+use chrono::Utc;
+use std::sync::Arc;
+
+pub struct RegionServiceImpl {
+    region_repo: Arc<dyn RegionRepository>,
+}
+
+impl RegionServiceImpl {
+    pub fn new(region_repo: Arc<dyn RegionRepository>) -> Self {
+        Self { region_repo }
+    }
+}
+
+/// Region ids are used in query strings: lowercase letters, digits and dashes
+fn validate_region_id(region_id: &str) -> AppResult<()> {
+    let valid = (2..=50).contains(&region_id.len())
+        && region_id
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if !valid {
+        return Err(AppError::ValidationError(
+            "Region id must be 2 to 50 lowercase letters, digits or dashes".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_country_code(country_code: &str) -> AppResult<()> {
+    if country_code.len() != 2 || !country_code.chars().all(|c| c.is_ascii_uppercase()) {
+        return Err(AppError::ValidationError(format!(
+            "Invalid ISO 3166-1 country code: {}",
+            country_code
+        )));
+    }
+    Ok(())
+}
+
+fn validate_boundary(boundary: &RegionBoundary) -> AppResult<()> {
+    match boundary {
+        // TN for a country, TN-11 for a subdivision
+        RegionBoundary::OsmIsoCode(iso_code) => {
+            let (country, subdivision) = match iso_code.split_once('-') {
+                Some((country, subdivision)) => (country, Some(subdivision)),
+                None => (iso_code.as_str(), None),
+            };
+            validate_country_code(country)?;
+            if subdivision.is_some_and(|s| {
+                s.is_empty() || s.len() > 3 || !s.chars().all(|c| c.is_ascii_alphanumeric())
+            }) {
+                return Err(AppError::ValidationError(format!(
+                    "Invalid ISO 3166-2 code: {}",
+                    iso_code
+                )));
+            }
+        }
+        RegionBoundary::GeoJson(geojson) => {
+            let value: serde_json::Value = serde_json::from_str(geojson).map_err(|e| {
+                AppError::ValidationError(format!("Boundary is not valid GeoJSON: {}", e))
+            })?;
+            let geometry_type = value.get("type").and_then(|t| t.as_str());
+            if !matches!(geometry_type, Some("Polygon" | "MultiPolygon")) {
+                return Err(AppError::ValidationError(
+                    "Boundary must be a GeoJSON Polygon or MultiPolygon geometry".to_string(),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl RegionService for RegionServiceImpl {
+    async fn create_region(&self, data: CreateRegionData) -> AppResult<Region> {
+        validate_region_id(&data.region_id)?;
+        if data.name.trim().is_empty() {
+            return Err(AppError::ValidationError(
+                "Region name is required".to_string(),
+            ));
+        }
+        validate_boundary(&data.boundary)?;
+
+        // An OSM boundary is in the country of its ISO code
+        let country_code = match (&data.country_code, &data.boundary) {
+            (Some(country_code), _) => Some(country_code.to_uppercase()),
+            (None, RegionBoundary::OsmIsoCode(iso_code)) => {
+                iso_code.split('-').next().map(str::to_string)
+            }
+            (None, RegionBoundary::GeoJson(_)) => None,
+        };
+        if let Some(country_code) = &country_code {
+            validate_country_code(country_code)?;
+        }
+
+        if self
+            .region_repo
+            .find_by_id(&data.region_id)
+            .await?
+            .is_some()
+        {
+            return Err(AppError::Conflict(format!(
+                "Region {} already exists",
+                data.region_id
+            )));
+        }
+
+        let region = Region {
+            region_id: data.region_id,
+            name: data.name.trim().to_string(),
+            country_code,
+            boundary_source: match data.boundary {
+                RegionBoundary::OsmIsoCode(_) => "osm",
+                RegionBoundary::GeoJson(_) => "configured",
+            }
+            .to_string(),
+            created_at: Utc::now(),
+        };
+
+        let region = self.region_repo.create(&region, &data.boundary).await?;
+        let moved = self.region_repo.assign_station_regions().await?;
+        tracing::info!(
+            "Region {} created, {} stations moved",
+            region.region_id,
+            moved
+        );
+
+        Ok(region)
+    }
+
+    async fn get_region(&self, region_id: &str) -> AppResult<Region> {
+        self.region_repo
+            .find_by_id(region_id)
+            .await?
+            .ok_or(AppError::NotFound("Region not found".to_string()))
+    }
+
+    async fn list_regions(&self) -> AppResult<Vec<Region>> {
+        self.region_repo.find_all().await
+    }
+
+    async fn delete_region(&self, region_id: &str) -> AppResult<()> {
+        let _ = self.get_region(region_id).await?;
+        self.region_repo.delete(region_id).await?;
+        let moved = self.region_repo.assign_station_regions().await?;
+        tracing::info!("Region {} deleted, {} stations moved", region_id, moved);
+
+        Ok(())
+    }
+
+    async fn region_statistics(&self) -> AppResult<Vec<RegionStatistics>> {
+        self.region_repo.statistics().await
+    }
+}
@@ -0,0 +1,171 @@
+use crate::core::errors::{AppError, AppResult};
+use crate::domain::entities::{ModerationQueueEntry, UserReview};
+use crate::domain::moderation::ModerationStatus;
+use crate::domain::repositories::ReviewModerationRepository;
+use crate::domain::services::ReviewModerationService;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+pub struct ReviewModerationServiceImpl {
+    moderation_repo: Arc<dyn ReviewModerationRepository>,
+}
+
+impl ReviewModerationServiceImpl {
+    pub fn new(moderation_repo: Arc<dyn ReviewModerationRepository>) -> Self {
+        Self { moderation_repo }
+    }
+}
+
+#[async_trait]
+impl ReviewModerationService for ReviewModerationServiceImpl {
+    async fn list_queue(
+        &self,
+        status: Option<ModerationStatus>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<(Vec<ModerationQueueEntry>, i64)> {
+        let status = status.as_ref().map(ModerationStatus::as_str);
+        let entries = self
+            .moderation_repo
+            .find_queue(status, limit, offset)
+            .await?;
+        let total = self.moderation_repo.count_queue(status).await?;
+        Ok((entries, total))
+    }
+
+    async fn moderate_review(
+        &self,
+        review_id: &str,
+        status: ModerationStatus,
+        reason: String,
+        admin_id: &str,
+    ) -> AppResult<UserReview> {
+        if reason.trim().is_empty() {
+            return Err(AppError::ValidationError(
+                "A reason is required for moderation actions".to_string(),
+            ));
+        }
+
+        self.moderation_repo
+            .moderate(review_id, status, reason.trim(), admin_id)
+            .await?
+            .ok_or(AppError::NotFound(format!(
+                "Review with id {} not found",
+                review_id
+            )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repositories::MockReviewModerationRepository;
+    use chrono::Utc;
+
+    fn review(status: ModerationStatus) -> UserReview {
+        UserReview {
+            review_id: "REV-1".to_string(),
+            user_id: "user-1".to_string(),
+            station_id: "STA-1".to_string(),
+            rating: 1,
+            review_text: Some("Never works".to_string()),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            moderation_status: status.as_str().to_string(),
+            moderation_reason: None,
+            moderated_by: None,
+            moderated_at: None,
+        }
+    }
+
+    fn service(repo: MockReviewModerationRepository) -> ReviewModerationServiceImpl {
+        ReviewModerationServiceImpl::new(Arc::new(repo))
+    }
+
+    #[tokio::test]
+    async fn hides_a_review_with_the_admin_and_reason() {
+        let mut repo = MockReviewModerationRepository::new();
+        repo.expect_moderate()
+            .withf(|id, status, reason, admin| {
+                id == "REV-1"
+                    && *status == ModerationStatus::Hidden
+                    && reason == "Insults the operator"
+                    && admin == "admin-1"
+            })
+            .times(1)
+            .returning(|_, _, _, _| Ok(Some(review(ModerationStatus::Hidden))));
+
+        let hidden = service(repo)
+            .moderate_review(
+                "REV-1",
+                ModerationStatus::Hidden,
+                " Insults the operator ".to_string(),
+                "admin-1",
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(hidden.moderation_status, "hidden");
+    }
+
+    #[tokio::test]
+    async fn moderating_needs_a_reason() {
+        let mut repo = MockReviewModerationRepository::new();
+        repo.expect_moderate().never();
+
+        let result = service(repo)
+            .moderate_review(
+                "REV-1",
+                ModerationStatus::Visible,
+                " ".to_string(),
+                "admin-1",
+            )
+            .await;
+
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn does_not_moderate_a_missing_review() {
+        let mut repo = MockReviewModerationRepository::new();
+        repo.expect_moderate().returning(|_, _, _, _| Ok(None));
+
+        let result = service(repo)
+            .moderate_review(
+                "REV-9",
+                ModerationStatus::Hidden,
+                "Spam".to_string(),
+                "admin-1",
+            )
+            .await;
+
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn lists_the_queue_with_its_total() {
+        let mut repo = MockReviewModerationRepository::new();
+        repo.expect_find_queue()
+            .withf(|status, limit, offset| {
+                *status == Some("pending") && *limit == 20 && *offset == 0
+            })
+            .returning(|_, _, _| {
+                Ok(vec![ModerationQueueEntry {
+                    review: review(ModerationStatus::Pending),
+                    open_flags: 3,
+                    flag_reasons: vec!["spam".to_string()],
+                }])
+            });
+        repo.expect_count_queue()
+            .withf(|status| *status == Some("pending"))
+            .returning(|_| Ok(41));
+
+        let (entries, total) = service(repo)
+            .list_queue(Some(ModerationStatus::Pending), 20, 0)
+            .await
+            .unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(total, 41);
+    }
+}
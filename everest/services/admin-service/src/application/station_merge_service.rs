@@ -0,0 +1,132 @@
+use crate::core::constants::*;
+use crate::core::errors::{AppError, AppResult};
+use crate::core::utils::generate_id;
+use crate::domain::entities::{DuplicateCandidate, Station, StationMerge};
+use crate::domain::repositories::{StationMergeRepository, StationRepository};
+use crate::domain::services::StationMergeService;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+pub struct StationMergeServiceImpl {
+    merge_repo: Arc<dyn StationMergeRepository>,
+    station_repo: Arc<dyn StationRepository>,
+}
+
+impl StationMergeServiceImpl {
+    pub fn new(
+        merge_repo: Arc<dyn StationMergeRepository>,
+        station_repo: Arc<dyn StationRepository>,
+    ) -> Self {
+        Self {
+            merge_repo,
+            station_repo,
+        }
+    }
+
+    async fn get_station(&self, station_id: &str) -> AppResult<Station> {
+        self.station_repo
+            .find_by_id(station_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Station {} not found", station_id)))
+    }
+
+    async fn get_pair(
+        &self,
+        station_id_a: &str,
+        station_id_b: &str,
+    ) -> AppResult<(Station, Station)> {
+        if station_id_a == station_id_b {
+            return Err(AppError::ValidationError(
+                "A duplicate pair needs two different stations".to_string(),
+            ));
+        }
+        let a = self.get_station(station_id_a).await?;
+        let b = self.get_station(station_id_b).await?;
+        Ok((a, b))
+    }
+}
+
+#[async_trait]
+impl StationMergeService for StationMergeServiceImpl {
+    async fn list_candidates(
+        &self,
+        max_distance_meters: Option<f64>,
+        min_score: Option<f64>,
+        region_id: Option<&str>,
+        limit: i64,
+    ) -> AppResult<Vec<DuplicateCandidate>> {
+        let max_distance = max_distance_meters.unwrap_or(DEFAULT_DUPLICATE_DISTANCE_METERS);
+        if !(max_distance > 0.0 && max_distance <= MAX_DUPLICATE_DISTANCE_METERS) {
+            return Err(AppError::ValidationError(format!(
+                "Distance must be between 0 and {} meters",
+                MAX_DUPLICATE_DISTANCE_METERS
+            )));
+        }
+        let min_score = min_score.unwrap_or(DEFAULT_DUPLICATE_MIN_SCORE);
+        if !(0.0..=1.0).contains(&min_score) {
+            return Err(AppError::ValidationError(
+                "Minimum score must be between 0 and 1".to_string(),
+            ));
+        }
+
+        self.merge_repo
+            .find_candidates(max_distance, min_score, region_id, limit)
+            .await
+    }
+
+    async fn dismiss_candidate(
+        &self,
+        station_id_a: &str,
+        station_id_b: &str,
+        dismissed_by: Option<&str>,
+    ) -> AppResult<()> {
+        let _ = self.get_pair(station_id_a, station_id_b).await?;
+        self.merge_repo
+            .dismiss(station_id_a, station_id_b, dismissed_by)
+            .await
+    }
+
+    async fn merge_stations(
+        &self,
+        surviving_station_id: &str,
+        merged_station_id: &str,
+        merged_by: Option<&str>,
+    ) -> AppResult<StationMerge> {
+        let (surviving, merged) = self
+            .get_pair(surviving_station_id, merged_station_id)
+            .await?;
+        // The sync would bring the merged station back with its OSM id
+        if surviving.osm_id > 0 && merged.osm_id > 0 {
+            return Err(AppError::ValidationError(
+                "Both stations come from OSM, fix the duplicate in OpenStreetMap instead"
+                    .to_string(),
+            ));
+        }
+
+        let merge = self
+            .merge_repo
+            .merge(
+                &generate_id(STATION_MERGE_ID_PREFIX),
+                surviving_station_id,
+                merged_station_id,
+                merged_by,
+            )
+            .await?;
+        tracing::info!(
+            "Station {} merged into {}",
+            merged_station_id,
+            surviving_station_id
+        );
+
+        Ok(merge)
+    }
+
+    async fn list_merges(
+        &self,
+        station_id: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<StationMerge>> {
+        self.merge_repo.find_merges(station_id, limit, offset).await
+    }
+}
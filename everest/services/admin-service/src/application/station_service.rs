@@ -1,8 +1,9 @@
 use crate::core::constants::*;
 use crate::core::errors::{AppError, AppResult};
 use crate::core::utils::generate_id;
-use crate::domain::entities::Station;
-use crate::domain::repositories::StationRepository;
+use crate::domain::entities::{Station, StationFieldSource};
+use crate::domain::provenance::{DataSource, StationField};
+use crate::domain::repositories::{ProvenanceRepository, StationRepository};
 use crate::domain::services::StationService;
 use crate::domain::value_objects::{CreateStationData, UpdateStationData};
 use async_trait::async_trait;
@@ -11,11 +12,18 @@ use std::sync::Arc;
 
 pub struct StationServiceImpl {
     station_repo: Arc<dyn StationRepository>,
+    provenance_repo: Arc<dyn ProvenanceRepository>,
 }
 
 impl StationServiceImpl {
-    pub fn new(station_repo: Arc<dyn StationRepository>) -> Self {
-        Self { station_repo }
+    pub fn new(
+        station_repo: Arc<dyn StationRepository>,
+        provenance_repo: Arc<dyn ProvenanceRepository>,
+    ) -> Self {
+        Self {
+            station_repo,
+            provenance_repo,
+        }
     }
 }
 
@@ -43,6 +51,7 @@ impl StationService for StationServiceImpl {
             longitude: data.longitude,
             tags: data.tags,
             network_id: data.network_id,
+            region_id: None,
             created_by: None,
             created_at: Utc::now(),
             updated_by: None,
@@ -62,17 +71,17 @@ impl StationService for StationServiceImpl {
     async fn list_stations(
         &self,
         network_id: Option<String>,
+        region_id: Option<String>,
         limit: i64,
         offset: i64,
     ) -> AppResult<(Vec<Station>, i64)> {
-        let stations = if let Some(net_id) = network_id {
-            self.station_repo
-                .find_by_network(&net_id, limit, offset)
-                .await?
-        } else {
-            self.station_repo.find_all(limit, offset).await?
-        };
-        let total = self.station_repo.count().await?;
+        let network_id = network_id.as_deref();
+        let region_id = region_id.as_deref();
+        let stations = self
+            .station_repo
+            .find_all(network_id, region_id, limit, offset)
+            .await?;
+        let total = self.station_repo.count(network_id, region_id).await?;
         Ok((stations, total))
     }
 
@@ -80,17 +89,25 @@ impl StationService for StationServiceImpl {
         &self,
         station_id: &str,
         data: UpdateStationData,
+        updated_by: &str,
     ) -> AppResult<Station> {
         let mut station = self.get_station(station_id).await?;
+        let mut changed = Vec::new();
 
         if let Some(name) = data.name {
+            if name != station.name {
+                changed.push(StationField::Name);
+            }
             station.name = name;
         }
         if let Some(address) = data.address {
+            if station.address.as_deref() != Some(address.as_str()) {
+                changed.push(StationField::Address);
+            }
             station.address = Some(address);
         }
         if let Some(lat) = data.latitude {
-            if lat < -90.0 || lat > 90.0 {
+            if !(-90.0..=90.0).contains(&lat) {
                 return Err(AppError::ValidationError(
                     "Latitude must be between -90 and 90".to_string(),
                 ));
@@ -98,26 +115,108 @@ impl StationService for StationServiceImpl {
             station.latitude = lat;
         }
         if let Some(lon) = data.longitude {
-            if lon < -180.0 || lon > 180.0 {
+            if !(-180.0..=180.0).contains(&lon) {
                 return Err(AppError::ValidationError(
                     "Longitude must be between -180 and 180".to_string(),
                 ));
             }
             station.longitude = lon;
         }
+        if data.latitude.is_some() || data.longitude.is_some() {
+            changed.push(StationField::Location);
+        }
         if let Some(tags) = data.tags {
+            if station.tags.as_ref() != Some(&tags) {
+                changed.push(StationField::Tags);
+            }
             station.tags = Some(tags);
         }
         if let Some(network_id) = data.network_id {
             station.network_id = Some(network_id);
         }
 
+        station.updated_by = Some(updated_by.to_string());
         station.updated_at = Some(Utc::now());
-        self.station_repo.update(&station).await
+        let station = self.station_repo.update(&station).await?;
+
+        if !changed.is_empty() {
+            self.provenance_repo
+                .set_sources(
+                    station_id,
+                    &changed,
+                    DataSource::UserSubmitted,
+                    None,
+                    Some(updated_by),
+                )
+                .await?;
+        }
+
+        Ok(station)
     }
 
-    async fn delete_station(&self, station_id: &str) -> AppResult<()> {
+    async fn delete_station(&self, station_id: &str, deleted_by: &str) -> AppResult<()> {
         let _ = self.get_station(station_id).await?;
-        self.station_repo.delete(station_id).await
+        self.station_repo.delete(station_id, deleted_by).await
+    }
+
+    async fn restore_station(&self, station_id: &str, restored_by: &str) -> AppResult<Station> {
+        self.station_repo
+            .restore(station_id, restored_by)
+            .await?
+            .ok_or(AppError::NotFound("Deleted station not found".to_string()))
+    }
+
+    async fn get_station_provenance(&self, station_id: &str) -> AppResult<Vec<StationFieldSource>> {
+        let _ = self.get_station(station_id).await?;
+        let recorded = self.provenance_repo.find_by_station(station_id).await?;
+
+        Ok(StationField::ALL
+            .iter()
+            .map(|field| {
+                recorded
+                    .iter()
+                    .find(|source| source.field == field.as_str())
+                    .cloned()
+                    .unwrap_or_else(|| StationFieldSource {
+                        station_id: station_id.to_string(),
+                        field: field.as_str().to_string(),
+                        data_source: DataSource::Osm.as_str().to_string(),
+                        locked: false,
+                        updated_by: None,
+                        updated_at: None,
+                    })
+            })
+            .collect())
+    }
+
+    async fn update_field_provenance(
+        &self,
+        station_id: &str,
+        field: StationField,
+        source: Option<DataSource>,
+        locked: Option<bool>,
+        updated_by: &str,
+    ) -> AppResult<Vec<StationFieldSource>> {
+        if source.is_none() && locked.is_none() {
+            return Err(AppError::ValidationError(
+                "Either data_source or locked must be set".to_string(),
+            ));
+        }
+
+        let current = self.get_station_provenance(station_id).await?;
+        let source = match source {
+            Some(source) => source,
+            None => current
+                .iter()
+                .find(|s| s.field == field.as_str())
+                .map(|s| s.data_source.parse())
+                .transpose()?
+                .unwrap_or(DataSource::Osm),
+        };
+
+        self.provenance_repo
+            .set_sources(station_id, &[field], source, locked, Some(updated_by))
+            .await?;
+        self.get_station_provenance(station_id).await
     }
 }
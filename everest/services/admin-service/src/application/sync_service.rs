@@ -0,0 +1,273 @@
+use crate::core::constants::{SYNC_HEARTBEAT_SECONDS, SYNC_RUN_ID_PREFIX, SYNC_STALE_SECONDS};
+use crate::core::errors::{AppError, AppResult};
+use crate::core::utils::generate_id;
+use crate::domain::entities::{OsmStationConnector, SyncConflict, SyncRun};
+use crate::domain::osm_tags::connectors_from_tags;
+use crate::domain::repositories::{RegionRepository, SyncRunRepository};
+use crate::domain::services::OsmSyncService;
+use crate::domain::sync::{SyncPreview, SyncStatus, SyncTrigger, build_preview, tag_map};
+use async_trait::async_trait;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+pub struct OsmSyncServiceImpl {
+    sync_repo: Arc<dyn SyncRunRepository>,
+    region_repo: Arc<dyn RegionRepository>,
+}
+
+impl OsmSyncServiceImpl {
+    pub fn new(
+        sync_repo: Arc<dyn SyncRunRepository>,
+        region_repo: Arc<dyn RegionRepository>,
+    ) -> Self {
+        Self {
+            sync_repo,
+            region_repo,
+        }
+    }
+
+    async fn ensure_region_exists(&self, region_id: &str) -> AppResult<()> {
+        match self.region_repo.find_by_id(region_id).await? {
+            Some(_) => Ok(()),
+            None => Err(AppError::NotFound(format!(
+                "Region {} not found",
+                region_id
+            ))),
+        }
+    }
+
+    /// Fails runs left behind by a process that died, so a new one can
+    /// start. Runs other replicas are executing keep their heartbeat fresh.
+    pub async fn recover_interrupted_runs(&self) -> AppResult<()> {
+        let count = self
+            .sync_repo
+            .fail_interrupted_runs(SYNC_STALE_SECONDS)
+            .await?;
+        if count > 0 {
+            tracing::warn!("Marked {} interrupted OSM sync runs as failed", count);
+        }
+        Ok(())
+    }
+
+    /// Starts a scheduled sync every `interval`, skipping a tick while a run
+    /// is still in progress
+    pub fn schedule(self: Arc<Self>, interval: Duration) {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            // The first tick completes immediately
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                match self
+                    .start_sync(SyncTrigger::Scheduled, None, Vec::new(), None)
+                    .await
+                {
+                    Ok(run) => tracing::info!("Scheduled OSM sync {} started", run.run_id),
+                    Err(AppError::Conflict(_)) => {
+                        tracing::info!("Skipping scheduled OSM sync, a run is in progress")
+                    }
+                    Err(e) => tracing::error!("Failed to start scheduled OSM sync: {}", e),
+                }
+            }
+        });
+    }
+}
+
+/// Parses the connectors out of the staged tags
+async fn tagged_connectors(
+    sync_repo: &dyn SyncRunRepository,
+) -> AppResult<Vec<OsmStationConnector>> {
+    let staged = sync_repo.find_staged_tags().await?;
+    Ok(staged
+        .iter()
+        .flat_map(|row| {
+            connectors_from_tags(&tag_map(&row.tags))
+                .into_iter()
+                .map(|connector| connector.for_station(row.osm_id))
+        })
+        .collect())
+}
+
+/// Fills `osm_staged_connectors`, which the sync reads the connectors from
+async fn stage_tagged_connectors(sync_repo: &dyn SyncRunRepository) -> AppResult<()> {
+    let connectors = tagged_connectors(sync_repo).await?;
+    sync_repo.stage_connectors(&connectors).await
+}
+
+/// Runs the sync, refreshes the views and records the outcome
+async fn execute_run(
+    sync_repo: Arc<dyn SyncRunRepository>,
+    run_id: String,
+    user_id: Option<String>,
+    skipped_osm_ids: Vec<i64>,
+    region_id: Option<String>,
+) {
+    let heartbeat = {
+        let (sync_repo, run_id) = (sync_repo.clone(), run_id.clone());
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(Duration::from_secs(SYNC_HEARTBEAT_SECONDS));
+            loop {
+                ticker.tick().await;
+                if let Err(e) = sync_repo.touch_run(&run_id).await {
+                    tracing::warn!(
+                        "Failed to record the heartbeat of OSM sync {}: {}",
+                        run_id,
+                        e
+                    );
+                }
+            }
+        })
+    };
+
+    let synced = match stage_tagged_connectors(sync_repo.as_ref()).await {
+        Ok(()) => {
+            sync_repo
+                .sync_osm_stations(
+                    &run_id,
+                    user_id.as_deref(),
+                    &skipped_osm_ids,
+                    region_id.as_deref(),
+                )
+                .await
+        }
+        Err(e) => Err(e),
+    };
+    let outcome = match synced {
+        Ok(counts) => match sync_repo.refresh_station_views().await {
+            Ok(()) => Ok(counts),
+            Err(e) => Err((Some(counts), e)),
+        },
+        Err(e) => Err((None, e)),
+    };
+
+    let recorded = match outcome {
+        Ok(counts) => {
+            tracing::info!(
+                "OSM sync {} inserted {}, updated {}, deactivated {} stations, kept {} conflicting values",
+                run_id,
+                counts.inserted_count,
+                counts.updated_count,
+                counts.deactivated_count,
+                counts.conflict_count
+            );
+            sync_repo
+                .finish_run(&run_id, SyncStatus::Succeeded, Some(counts), None)
+                .await
+        }
+        Err((counts, e)) => {
+            tracing::error!("OSM sync {} failed: {}", run_id, e);
+            sync_repo
+                .finish_run(&run_id, SyncStatus::Failed, counts, Some(&e.to_string()))
+                .await
+        }
+    };
+    heartbeat.abort();
+    if let Err(e) = recorded {
+        tracing::error!("Failed to record the outcome of OSM sync {}: {}", run_id, e);
+    }
+}
+
+#[async_trait]
+impl OsmSyncService for OsmSyncServiceImpl {
+    async fn preview_sync(&self, region_id: Option<&str>) -> AppResult<SyncPreview> {
+        let mut staged = self.sync_repo.find_staged_stations().await?;
+        if staged.is_empty() {
+            return Err(AppError::ValidationError(
+                "osm_charging_stations_temp is empty, run the OSM import first".to_string(),
+            ));
+        }
+        // Parsed in memory: staging them would change what a running sync reads
+        let tagged = tagged_connectors(self.sync_repo.as_ref()).await?;
+        let mut staged_connectors = self.sync_repo.filter_uncovered_connectors(&tagged).await?;
+        let mut matched_connectors = self.sync_repo.find_matched_connectors().await?;
+        let mut missing = self.sync_repo.find_missing_stations().await?;
+        let mut conflicts = self.sync_repo.find_pending_conflicts().await?;
+
+        // The same osm_ids the regional sync adds to the skipped ones
+        if let Some(region_id) = region_id {
+            self.ensure_region_exists(region_id).await?;
+            let outside: HashSet<i64> = self
+                .sync_repo
+                .find_osm_ids_outside_region(region_id)
+                .await?
+                .into_iter()
+                .collect();
+            staged.retain(|s| !outside.contains(&s.osm_id));
+            staged_connectors.retain(|c| !outside.contains(&c.osm_id));
+            matched_connectors.retain(|c| !outside.contains(&c.osm_id));
+            missing.retain(|s| !outside.contains(&s.osm_id));
+            conflicts.retain(|c| !outside.contains(&c.osm_id));
+        }
+
+        let mut preview = build_preview(staged, staged_connectors, matched_connectors, missing);
+        preview.conflicts = conflicts;
+        Ok(preview)
+    }
+
+    async fn start_sync(
+        &self,
+        trigger: SyncTrigger,
+        triggered_by: Option<&str>,
+        mut skipped_osm_ids: Vec<i64>,
+        region_id: Option<String>,
+    ) -> AppResult<SyncRun> {
+        skipped_osm_ids.sort_unstable();
+        skipped_osm_ids.dedup();
+        if let Some(region_id) = &region_id {
+            self.ensure_region_exists(region_id).await?;
+        }
+        // A replica that died mid-run would otherwise block syncs until a restart
+        self.recover_interrupted_runs().await?;
+
+        let run_id = generate_id(SYNC_RUN_ID_PREFIX);
+        let run = self
+            .sync_repo
+            .start_run(
+                &run_id,
+                trigger,
+                triggered_by,
+                &skipped_osm_ids,
+                region_id.as_deref(),
+            )
+            .await?;
+
+        tokio::spawn(execute_run(
+            self.sync_repo.clone(),
+            run_id,
+            triggered_by.map(str::to_string),
+            skipped_osm_ids,
+            region_id,
+        ));
+
+        Ok(run)
+    }
+
+    async fn get_run(&self, run_id: &str) -> AppResult<SyncRun> {
+        self.sync_repo
+            .find_by_id(run_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Sync run {} not found", run_id)))
+    }
+
+    async fn get_run_conflicts(&self, run_id: &str) -> AppResult<Vec<SyncConflict>> {
+        let _ = self.get_run(run_id).await?;
+        self.sync_repo.find_conflicts(run_id).await
+    }
+
+    async fn list_runs(
+        &self,
+        status: Option<SyncStatus>,
+        region_id: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<(Vec<SyncRun>, i64)> {
+        let runs = self
+            .sync_repo
+            .find_all(status, region_id, limit, offset)
+            .await?;
+        let total = self.sync_repo.count(status, region_id).await?;
+        Ok((runs, total))
+    }
+}
@@ -0,0 +1,231 @@
+//! A minimal OCPI 2.2.1 eMSP to try the CPO interface against locally.
+//!
+//! Usage:
+//!   ocpi-mock-emsp <cpo versions url> <token A> [listen address]
+//!
+//! Create a party with `POST /api/ocpi/parties` for the token A. The mock
+//! listens on 127.0.0.1:4000 by default, registers with the CPO, pulls its
+//! locations and tariffs, then logs the locations the CPO pushes until
+//! stopped.
+
+use actix_web::{App, HttpRequest, HttpResponse, HttpServer, get, put, web};
+use admin_service::core::logging;
+use admin_service::domain::ocpi::{
+    BusinessDetails, Credentials, CredentialsRole, Endpoint, Location, OcpiResponse, Version,
+    VersionDetails,
+};
+use anyhow::{Context, bail};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::env;
+
+const USAGE: &str = "usage: ocpi-mock-emsp <cpo versions url> <token A> [listen address]";
+const VERSION: &str = "2.2.1";
+
+struct MockState {
+    base_url: String,
+    /// Token B, which the CPO calls us with
+    token: String,
+}
+
+fn authorized(req: &HttpRequest, state: &MockState) -> bool {
+    let expected = format!("Token {}", BASE64.encode(&state.token));
+    req.headers()
+        .get("Authorization")
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v == expected)
+}
+
+fn respond<T: Serialize>(req: &HttpRequest, state: &MockState, data: Option<T>) -> HttpResponse {
+    if !authorized(req, state) {
+        return HttpResponse::Unauthorized()
+            .json(OcpiResponse::<()>::error(2000, "Unknown token".to_string()));
+    }
+    HttpResponse::Ok().json(OcpiResponse::success(data))
+}
+
+#[get("/ocpi/versions")]
+async fn get_versions(req: HttpRequest, state: web::Data<MockState>) -> HttpResponse {
+    let data = vec![Version {
+        version: VERSION.to_string(),
+        url: format!("{}/ocpi/{}", state.base_url, VERSION),
+    }];
+    respond(&req, &state, Some(data))
+}
+
+#[get("/ocpi/2.2.1")]
+async fn get_version_details(req: HttpRequest, state: web::Data<MockState>) -> HttpResponse {
+    let data = VersionDetails {
+        version: VERSION.to_string(),
+        endpoints: vec![Endpoint {
+            identifier: "locations".to_string(),
+            role: "RECEIVER".to_string(),
+            url: format!("{}/ocpi/{}/locations", state.base_url, VERSION),
+        }],
+    };
+    respond(&req, &state, Some(data))
+}
+
+#[put("/ocpi/2.2.1/locations/{country_code}/{party_id}/{location_id}")]
+async fn receive_location(
+    req: HttpRequest,
+    path: web::Path<(String, String, String)>,
+    body: web::Json<Location>,
+    state: web::Data<MockState>,
+) -> HttpResponse {
+    let (country_code, party_id, location_id) = path.into_inner();
+    if authorized(&req, &state) {
+        tracing::info!(
+            "Pushed {}*{} location {}: {} (publish {}, {} EVSEs, last updated {})",
+            country_code,
+            party_id,
+            location_id,
+            body.name,
+            body.publish,
+            body.evses.len(),
+            body.last_updated
+        );
+    }
+    respond::<()>(&req, &state, None)
+}
+
+/// Calls the CPO and unwraps the OCPI envelope
+async fn call<T: DeserializeOwned>(
+    request: reqwest::RequestBuilder,
+    token: &str,
+) -> anyhow::Result<(T, reqwest::header::HeaderMap)> {
+    let response = request
+        .header("Authorization", format!("Token {}", BASE64.encode(token)))
+        .header("X-Request-ID", nanoid::nanoid!())
+        .header("X-Correlation-ID", nanoid::nanoid!())
+        .send()
+        .await?;
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body: OcpiResponse<T> = response.json().await.context("invalid OCPI response")?;
+    match body.data {
+        Some(data) if status.is_success() && body.status_code == 1000 => Ok((data, headers)),
+        _ => bail!(
+            "HTTP {}, status {}: {}",
+            status,
+            body.status_code,
+            body.status_message.unwrap_or_default()
+        ),
+    }
+}
+
+/// Follows the Link headers and returns how many items were listed
+async fn pull_all(http: &reqwest::Client, url: String, token: &str) -> anyhow::Result<usize> {
+    let mut next = Some(url);
+    let mut count = 0;
+    while let Some(url) = next.take() {
+        let (items, headers) = call::<Vec<serde_json::Value>>(http.get(&url), token).await?;
+        count += items.len();
+        next = headers
+            .get("Link")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix('<'))
+            .and_then(|v| v.split_once('>'))
+            .map(|(url, _)| url.to_string());
+    }
+    Ok(count)
+}
+
+async fn register(
+    http: &reqwest::Client,
+    cpo_versions_url: &str,
+    token_a: &str,
+    ours: Credentials,
+) -> anyhow::Result<()> {
+    let (versions, _) = call::<Vec<Version>>(http.get(cpo_versions_url), token_a).await?;
+    let version = versions
+        .into_iter()
+        .find(|v| v.version == VERSION)
+        .context("CPO does not support OCPI 2.2.1")?;
+    let (details, _) = call::<VersionDetails>(http.get(&version.url), token_a).await?;
+    let endpoint = |identifier: &str| {
+        details
+            .endpoints
+            .iter()
+            .find(|e| e.identifier == identifier)
+            .map(|e| e.url.clone())
+            .with_context(|| format!("CPO has no {} endpoint", identifier))
+    };
+
+    let (theirs, _) =
+        call::<Credentials>(http.post(endpoint("credentials")?).json(&ours), token_a).await?;
+    let cpo = theirs
+        .roles
+        .first()
+        .context("CPO credentials list no role")?;
+    tracing::info!(
+        "Registered with {} ({}*{}), token C {}",
+        cpo.business_details.name,
+        cpo.country_code,
+        cpo.party_id,
+        theirs.token
+    );
+
+    let locations = pull_all(http, endpoint("locations")?, &theirs.token).await?;
+    let tariffs = pull_all(http, endpoint("tariffs")?, &theirs.token).await?;
+    tracing::info!("Pulled {} locations and {} tariffs", locations, tariffs);
+
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    logging::init_logging(&env::var("RUST_LOG").unwrap_or_else(|_| "info".to_string()));
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (cpo_versions_url, token_a, listen) = match args.as_slice() {
+        [url, token] => (url.clone(), token.clone(), "127.0.0.1:4000".to_string()),
+        [url, token, listen] => (url.clone(), token.clone(), listen.clone()),
+        _ => bail!(USAGE),
+    };
+
+    let state = web::Data::new(MockState {
+        base_url: format!("http://{}", listen),
+        token: nanoid::nanoid!(32),
+    });
+    let ours = Credentials {
+        token: state.token.clone(),
+        url: format!("{}/ocpi/versions", state.base_url),
+        roles: vec![CredentialsRole {
+            role: "EMSP".to_string(),
+            business_details: BusinessDetails {
+                name: "Mock eMSP".to_string(),
+                website: None,
+            },
+            party_id: "MSP".to_string(),
+            country_code: "TN".to_string(),
+        }],
+    };
+
+    let server_state = state.clone();
+    let server = HttpServer::new(move || {
+        App::new()
+            .app_data(server_state.clone())
+            .service(get_versions)
+            .service(get_version_details)
+            .service(receive_location)
+    })
+    .bind(&listen)
+    .with_context(|| format!("cannot listen on {}", listen))?
+    .run();
+    let handle = server.handle();
+    let server = tokio::spawn(server);
+    tracing::info!("Mock eMSP listening on {}", listen);
+
+    let http = reqwest::Client::new();
+    if let Err(e) = register(&http, &cpo_versions_url, &token_a, ours).await {
+        handle.stop(true).await;
+        return Err(e.context("registration failed"));
+    }
+
+    tracing::info!("Waiting for location pushes, Ctrl-C to stop");
+    server.await??;
+    Ok(())
+}
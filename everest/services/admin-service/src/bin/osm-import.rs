@@ -0,0 +1,106 @@
+//! Fills `osm_charging_stations_temp` from local `.osm.pbf` files, or
+//! applies an osmChange diff to it, for the OSM sync to apply.
+//!
+//! Usage:
+//!   osm-import [--dry-run] <file.osm.pbf>...
+//!   osm-import --diff [--sequence <n>] <file.osc[.gz]>
+//!
+//! Several extracts, e.g. one per country, are staged together; diffs only
+//! apply to a single extract. With `--dry-run` the extracts are only read
+//! and the counts reported, without touching the database. The sequence number of a diff defaults to the one
+//! in its replication server path, `.../000/004/321.osc.gz` being 4321.
+//! Stations of deleted OSM elements are deactivated right away; the other
+//! changes reach the stations with the next sync.
+
+use admin_service::application::osm_import_service::{OsmImportServiceImpl, read_extracts};
+use admin_service::core::database::create_pool;
+use admin_service::core::logging;
+use admin_service::domain::osm_import::{OsmDiffReport, OsmImportReport, sequence_from_path};
+use admin_service::domain::services::OsmImportService;
+use admin_service::infrastructure::repositories::osm_staging_repo::PgOsmStagingRepository;
+use anyhow::{Context, bail};
+use std::env;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+const USAGE: &str = "usage: osm-import [--dry-run] <file.osm.pbf>...\n       \
+                     osm-import --diff [--sequence <n>] <file.osc[.gz]>";
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    dotenvy::dotenv().ok();
+    logging::init_logging(&env::var("RUST_LOG").unwrap_or_else(|_| "info".to_string()));
+
+    let mut dry_run = false;
+    let mut diff = false;
+    let mut sequence_number: Option<i64> = None;
+    let mut paths: Vec<PathBuf> = Vec::new();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--dry-run" => dry_run = true,
+            "--diff" => diff = true,
+            "--sequence" => {
+                let value = args.next().context(USAGE)?;
+                sequence_number = Some(value.parse().context("--sequence must be a number")?);
+            }
+            _ if !arg.starts_with("--") => paths.push(PathBuf::from(arg)),
+            _ => bail!(USAGE),
+        }
+    }
+    if paths.is_empty() {
+        bail!(USAGE);
+    }
+    if diff && dry_run {
+        bail!("--dry-run only applies to full imports");
+    }
+    if sequence_number.is_some() && !diff {
+        bail!("--sequence only applies to diffs");
+    }
+    if diff && paths.len() > 1 {
+        bail!("--diff takes a single osmChange file");
+    }
+
+    if dry_run {
+        print_report(&read_extracts(&paths)?.1, true);
+        return Ok(());
+    }
+
+    let database_url = env::var("DATABASE_URL").context("DATABASE_URL must be set")?;
+    let pool = create_pool(&database_url).await?;
+    let service = OsmImportServiceImpl::new(Arc::new(PgOsmStagingRepository::new(pool)));
+
+    if diff {
+        let path = &paths[0];
+        let sequence_number = sequence_number
+            .or_else(|| sequence_from_path(path))
+            .context("no sequence number in the path of the diff, pass --sequence")?;
+        print_diff_report(&service.apply_diff(path, sequence_number).await?);
+    } else {
+        print_report(&service.import_pbf(&paths).await?, false);
+    }
+    Ok(())
+}
+
+fn print_report(report: &OsmImportReport, dry_run: bool) {
+    println!("Charging station nodes: {}", report.station_nodes);
+    println!("Charging station ways:  {}", report.station_ways);
+    println!("Ways without nodes:     {}", report.skipped_ways);
+    if dry_run {
+        println!("Would stage:            {}", report.staged);
+    } else {
+        println!("Staged:                 {}", report.staged);
+    }
+    match report.sequence_number {
+        Some(sequence_number) => println!("Sequence number:        {}", sequence_number),
+        None => println!("Sequence number:        none, diffs start from any sequence"),
+    }
+}
+
+fn print_diff_report(report: &OsmDiffReport) {
+    println!("Sequence number:        {}", report.sequence_number);
+    println!("Created or modified:    {}", report.upserted);
+    println!("Deleted or untagged:    {}", report.removed);
+    println!("Ways without location:  {}", report.skipped_ways);
+    println!("Deactivated stations:   {}", report.deactivated);
+}
@@ -3,7 +3,7 @@
 // =============================================================================
 use crate::core::errors::{AppError, AppResult};
 use actix_web::HttpRequest;
-use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use jsonwebtoken::{Algorithm, DecodingKey, Validation, decode, decode_header};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -20,7 +20,7 @@ pub struct TokenClaims {
     pub email: Option<String>,
     pub preferred_username: Option<String>,
     pub iss: Option<String>,
-    pub aud: Option<serde_json::Value>,  // Can be string or array
+    pub aud: Option<serde_json::Value>, // Can be string or array
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -32,12 +32,12 @@ pub struct RealmAccess {
 impl TokenClaims {
     pub fn get_roles(&self) -> Vec<String> {
         // Try realm_access.roles first (Keycloak default)
-        if let Some(ref realm) = self.realm_access {
-            if !realm.roles.is_empty() {
-                return realm.roles.clone();
-            }
+        if let Some(ref realm) = self.realm_access
+            && !realm.roles.is_empty()
+        {
+            return realm.roles.clone();
         }
-        
+
         // Fallback to direct roles array
         self.roles.clone()
     }
@@ -125,10 +125,10 @@ impl JwtValidator {
         // Validate token - RELAXED VALIDATION
         let mut validation = Validation::new(Algorithm::RS256);
         validation.set_issuer(&[&self.jwt_issuer]);
-        
+
         // Don't validate audience - Keycloak tokens can have various audiences
         validation.validate_aud = false;
-        
+
         // Allow some clock skew (60 seconds)
         validation.leeway = 60;
 
@@ -141,7 +141,7 @@ impl JwtValidator {
         let claims = self.validate_token(token).await?;
 
         let roles = claims.get_roles();
-        
+
         if !roles.contains(&"admin".to_string()) {
             return Err(AppError::Forbidden(format!(
                 "Admin role required. Found roles: {:?}",
@@ -171,4 +171,4 @@ pub async fn require_admin_auth(
 ) -> AppResult<TokenClaims> {
     let token = extract_bearer_token(req)?;
     validator.validate_admin(&token).await
-}
\ No newline at end of file
+}
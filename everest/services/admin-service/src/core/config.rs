@@ -8,6 +8,23 @@ pub struct Config {
     pub log_level: String,
     pub jwt_issuer: String,
     pub jwks_url: String,
+    /// Unset or 0 disables the scheduled OSM sync
+    pub osm_sync_interval_minutes: u64,
+    /// Days deleted stations, connectors and networks are kept for restore
+    pub deleted_retention_days: u32,
+    /// 0 disables the scheduled purge of deleted records
+    pub deleted_purge_interval_minutes: u64,
+    /// Public URL of this service, the OCPI endpoints being under `/ocpi`
+    pub ocpi_base_url: String,
+    /// ISO 3166-1 alpha-2 country code and party id we roam as
+    pub ocpi_country_code: String,
+    pub ocpi_party_id: String,
+    pub ocpi_business_name: String,
+    /// ISO 3166-1 alpha-3 country code and time zone of the locations
+    pub ocpi_location_country: String,
+    pub ocpi_time_zone: String,
+    /// 0 disables pushing location updates to the registered OCPI parties
+    pub ocpi_push_interval_minutes: u64,
 }
 
 impl Config {
@@ -24,6 +41,34 @@ impl Config {
             log_level: env::var("RUST_LOG").unwrap_or_else(|_| "info".to_string()),
             jwt_issuer: env::var("JWT_ISSUER").expect("JWT_ISSUER must be set"),
             jwks_url: env::var("JWKS_URL").expect("JWKS_URL must be set"),
+            osm_sync_interval_minutes: env::var("OSM_SYNC_INTERVAL_MINUTES")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse()
+                .expect("OSM_SYNC_INTERVAL_MINUTES must be a number"),
+            deleted_retention_days: env::var("DELETED_RETENTION_DAYS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .expect("DELETED_RETENTION_DAYS must be a number"),
+            deleted_purge_interval_minutes: env::var("DELETED_PURGE_INTERVAL_MINUTES")
+                .unwrap_or_else(|_| "1440".to_string())
+                .parse()
+                .expect("DELETED_PURGE_INTERVAL_MINUTES must be a number"),
+            ocpi_base_url: env::var("OCPI_BASE_URL")
+                .unwrap_or_else(|_| "http://localhost:3200".to_string())
+                .trim_end_matches('/')
+                .to_string(),
+            ocpi_country_code: env::var("OCPI_COUNTRY_CODE").unwrap_or_else(|_| "TN".to_string()),
+            ocpi_party_id: env::var("OCPI_PARTY_ID").unwrap_or_else(|_| "EVR".to_string()),
+            ocpi_business_name: env::var("OCPI_BUSINESS_NAME")
+                .unwrap_or_else(|_| "Everest".to_string()),
+            ocpi_location_country: env::var("OCPI_LOCATION_COUNTRY")
+                .unwrap_or_else(|_| "TUN".to_string()),
+            ocpi_time_zone: env::var("OCPI_TIME_ZONE")
+                .unwrap_or_else(|_| "Africa/Tunis".to_string()),
+            ocpi_push_interval_minutes: env::var("OCPI_PUSH_INTERVAL_MINUTES")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse()
+                .expect("OCPI_PUSH_INTERVAL_MINUTES must be a number"),
         }
     }
 
@@ -1,4 +1,45 @@
 pub const NETWORK_ID_PREFIX: &str = "NET";
 pub const STATION_ID_PREFIX: &str = "STA";
 pub const CONNECTOR_ID_PREFIX: &str = "CON";
+pub const SYNC_RUN_ID_PREFIX: &str = "SYN";
+pub const STATION_MERGE_ID_PREFIX: &str = "MRG";
+pub const OCPI_PARTY_ID_PREFIX: &str = "OCP";
+/// Shared with locate-service, which owns `user_notifications`
+pub const NOTIFICATION_ID_PREFIX: &str = "NTF";
 pub const NANOID_LENGTH: usize = 16;
+
+pub const MAX_IMPORT_BYTES: usize = 10 * 1024 * 1024;
+pub const MAX_IMPORT_ROWS: usize = 5000;
+/// Exported rows are sent in chunks of about this size
+pub const EXPORT_CHUNK_BYTES: usize = 16 * 1024;
+/// Rows fetched ahead of the client before the query waits for it
+pub const EXPORT_PREFETCH_ROWS: usize = 256;
+
+pub const MAX_SYNC_RUNS_PAGE: i64 = 100;
+/// How often a replica executing a sync run records that it is alive
+pub const SYNC_HEARTBEAT_SECONDS: u64 = 30;
+/// Running runs without a heartbeat for this long are failed as interrupted
+pub const SYNC_STALE_SECONDS: i64 = 120;
+/// Duplicate candidates are at most this far apart by default
+pub const DEFAULT_DUPLICATE_DISTANCE_METERS: f64 = 50.0;
+pub const MAX_DUPLICATE_DISTANCE_METERS: f64 = 500.0;
+/// Candidates scoring below this are not listed by default
+pub const DEFAULT_DUPLICATE_MIN_SCORE: f64 = 0.5;
+pub const MAX_DUPLICATES_PAGE: i64 = 100;
+pub const MAX_DELETED_RECORDS_PAGE: i64 = 100;
+pub const MAX_VERIFICATION_REQUESTS_PAGE: i64 = 100;
+pub const MAX_MODERATION_QUEUE_PAGE: i64 = 100;
+/// Rows per INSERT when filling osm_charging_stations_temp
+pub const OSM_STAGING_BATCH_ROWS: usize = 1000;
+
+pub const OCPI_VERSION: &str = "2.2.1";
+pub const OCPI_TOKEN_LENGTH: usize = 32;
+pub const MAX_OCPI_PAGE: i64 = 100;
+/// Locations read per query while pushing to a party
+pub const OCPI_PUSH_BATCH: i64 = 100;
+/// Locations are pushed once their last update is this old. last_updated
+/// is the start time of the writing transaction, so a transaction that
+/// commits after the push cursor moved past it is picked up by a later push
+/// as long as it ran for less than this.
+pub const OCPI_PUSH_SETTLE_SECONDS: i64 = 120;
+pub const OCPI_REQUEST_TIMEOUT_SECONDS: u64 = 30;
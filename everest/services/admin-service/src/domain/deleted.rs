@@ -0,0 +1,45 @@
+use crate::core::errors::AppError;
+use std::str::FromStr;
+
+/// Kinds of soft-deleted records
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeletedRecordType {
+    Station,
+    Connector,
+    Network,
+}
+
+impl DeletedRecordType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Station => "station",
+            Self::Connector => "connector",
+            Self::Network => "network",
+        }
+    }
+}
+
+impl FromStr for DeletedRecordType {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "station" => Ok(Self::Station),
+            "connector" => Ok(Self::Connector),
+            "network" => Ok(Self::Network),
+            other => Err(AppError::ValidationError(format!(
+                "Unknown record type: {}, expected station, connector or network",
+                other
+            ))),
+        }
+    }
+}
+
+/// What `purge_deleted_records()` removed. Connectors deleted along with
+/// their station are counted with the station.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, sqlx::FromRow)]
+pub struct PurgeCounts {
+    pub purged_stations: i32,
+    pub purged_connectors: i32,
+    pub purged_networks: i32,
+}
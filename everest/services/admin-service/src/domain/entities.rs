@@ -27,6 +27,8 @@ pub struct Station {
     /// and resolve type mismatches between DTOs and Repository.
     pub tags: Option<serde_json::Value>,
     pub network_id: Option<String>,
+    /// Set by the database from the region boundaries
+    pub region_id: Option<String>,
     pub created_by: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_by: Option<String>,
@@ -45,12 +47,135 @@ pub struct Connector {
     pub amperage: Option<i32>,
     pub count_available: i32,
     pub count_total: i32,
+    /// osm for connectors maintained by the OSM sync; anything else is
+    /// operator-managed and left alone by it
+    pub data_source: String,
     pub created_by: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_by: Option<String>,
     pub updated_at: Option<DateTime<Utc>>,
 }
 
+/// Where a station field came from. Fields without a row came from OSM.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct StationFieldSource {
+    pub station_id: String,
+    pub field: String,
+    pub data_source: String,
+    /// The OSM sync never overwrites a locked field
+    pub locked: bool,
+    pub updated_by: Option<String>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// One connector of a station with its lookup names, or a station without
+/// connectors, as exported to partners
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct StationExportRow {
+    pub station_id: String,
+    pub external_ref: Option<String>,
+    pub osm_id: i64,
+    pub name: String,
+    pub address: Option<String>,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub network_id: Option<String>,
+    pub network_name: Option<String>,
+    pub connector_id: Option<String>,
+    pub connector_type: Option<String>,
+    pub current_type: Option<String>,
+    pub status: Option<String>,
+    pub power_kw: Option<f64>,
+    pub voltage: Option<i32>,
+    pub amperage: Option<i32>,
+    pub count_total: Option<i32>,
+    pub count_available: Option<i32>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SyncRun {
+    pub run_id: String,
+    pub trigger_type: String,
+    pub status: String,
+    pub triggered_by: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub inserted_count: Option<i32>,
+    pub updated_count: Option<i32>,
+    pub deactivated_count: Option<i32>,
+    pub error: Option<String>,
+    pub skipped_osm_ids: Vec<i64>,
+    pub conflict_count: Option<i32>,
+    pub region_id: Option<String>,
+}
+
+/// A staged OSM value the sync does not apply because the station field or
+/// connector is protected
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SyncConflict {
+    pub station_id: String,
+    pub osm_id: i64,
+    /// name, address, location, tags or connector:<type>/<current>
+    pub field: String,
+    pub data_source: String,
+    pub locked: bool,
+    pub current_value: Option<String>,
+    pub osm_value: Option<String>,
+}
+
+/// A row of `osm_charging_stations_temp` next to the station it would
+/// update, with the values the sync would write. Tags are hstore key/value
+/// pairs flattened into one array.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct OsmStagedStation {
+    pub osm_id: i64,
+    pub station_id: Option<String>,
+    pub deactivated: bool,
+    pub current_name: Option<String>,
+    pub current_address: Option<String>,
+    pub current_latitude: Option<f64>,
+    pub current_longitude: Option<f64>,
+    pub current_tags: Vec<Option<String>>,
+    pub name: String,
+    pub address: Option<String>,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub tags: Vec<Option<String>>,
+}
+
+/// A connector of an OSM station, either as stored or as the staged tags
+/// describe it
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct OsmStationConnector {
+    pub osm_id: i64,
+    pub connector_type: String,
+    pub current_type: String,
+    pub power_kw: Option<f64>,
+    pub voltage: Option<i32>,
+    pub amperage: Option<i32>,
+    pub count_total: Option<i32>,
+}
+
+/// The tags a staged row would leave on its station, as `hstore_to_array`
+/// key/value pairs
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct OsmStagedTags {
+    pub osm_id: i64,
+    pub tags: Vec<Option<String>>,
+}
+
+/// An OSM station missing from the extract, which the sync would deactivate
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct OsmRemovedStation {
+    pub station_id: String,
+    pub osm_id: i64,
+    pub name: String,
+    pub address: Option<String>,
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
 // Lookup tables
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct ConnectorType {
@@ -72,3 +197,261 @@ pub struct CurrentType {
     pub name: String,
     pub description: Option<String>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Region {
+    pub region_id: String,
+    pub name: String,
+    pub country_code: Option<String>,
+    /// osm or configured
+    pub boundary_source: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RegionStatistics {
+    pub region_id: String,
+    pub name: String,
+    pub station_count: i64,
+    /// Stations not deactivated by the OSM sync
+    pub active_station_count: i64,
+    pub osm_station_count: i64,
+    pub connector_count: i64,
+    pub available_connector_count: i64,
+    /// Last successful sync covering the region, regional or not
+    pub last_synced_at: Option<DateTime<Utc>>,
+}
+
+/// One side of a duplicate candidate pair
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateStation {
+    pub station_id: String,
+    pub osm_id: i64,
+    pub name: String,
+    pub address: Option<String>,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub network_id: Option<String>,
+    pub external_ref: Option<String>,
+    /// Connector types of the station, e.g. Type 2
+    pub connector_types: Vec<String>,
+}
+
+/// Two stations that are probably the same site
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateCandidate {
+    pub station_a: DuplicateStation,
+    pub station_b: DuplicateStation,
+    pub distance_meters: f64,
+    /// Trigram similarity of the names, 0 to 1
+    pub name_similarity: f64,
+    /// Shared connector types over all of their types, None when either
+    /// station has no connectors
+    pub connector_overlap: Option<f64>,
+    /// 0 to 1, higher is more likely a duplicate
+    pub score: f64,
+}
+
+/// A merge from the history, with a snapshot of the merged station
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct StationMerge {
+    pub merge_id: String,
+    pub surviving_station_id: String,
+    pub merged_station_id: String,
+    pub merged_osm_id: i64,
+    pub merged_station: serde_json::Value,
+    pub moved_connectors: i32,
+    pub dropped_connectors: i32,
+    pub moved_reviews: i32,
+    pub dropped_reviews: i32,
+    pub moved_checkins: i32,
+    pub moved_media: i32,
+    pub moved_issue_reports: i32,
+    pub merged_by: Option<String>,
+    pub merged_at: DateTime<Utc>,
+}
+
+/// A soft-deleted station, connector or network, waiting to be restored or
+/// purged
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct DeletedRecord {
+    /// station, connector or network
+    pub record_type: String,
+    pub record_id: String,
+    /// Name of the station or network; for a connector, its type
+    pub name: Option<String>,
+    /// Station of a connector
+    pub station_id: Option<String>,
+    pub deleted_at: DateTime<Utc>,
+    pub deleted_by: Option<String>,
+}
+
+/// An OCPI party roaming with our network
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct OcpiParty {
+    pub party_uid: String,
+    pub name: String,
+    /// pending, registered or unregistered
+    pub status: String,
+    pub country_code: Option<String>,
+    pub party_id: Option<String>,
+    pub role: Option<String>,
+    /// Registration token handed over to the party
+    pub token_a: Option<String>,
+    /// The party's token for our calls to it
+    pub token_b: Option<String>,
+    /// Our token for the party's calls to us
+    pub token_c: Option<String>,
+    pub versions_url: Option<String>,
+    pub locations_url: Option<String>,
+    pub locations_pushed_until: Option<DateTime<Utc>>,
+    pub locations_pushed_until_id: Option<String>,
+    pub last_push_error: Option<String>,
+    pub last_push_at: Option<DateTime<Utc>>,
+    pub created_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub registered_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A station of an operator network, as published over OCPI
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct OcpiLocationRow {
+    pub station_id: String,
+    pub name: String,
+    pub address: Option<String>,
+    pub city: Option<String>,
+    pub postal_code: Option<String>,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub operator_name: String,
+    /// Active tariff of the network
+    pub tariff_id: Option<String>,
+    /// Deleted or deactivated, or its network is not verified any more
+    pub removed: bool,
+    /// Latest change of the station, its connectors, network or tariff
+    pub last_updated: DateTime<Utc>,
+    #[sqlx(skip)]
+    pub connectors: Vec<OcpiConnectorRow>,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct OcpiConnectorRow {
+    pub station_id: String,
+    pub connector_id: String,
+    /// connector_types, current_types and connector_statuses names
+    pub connector_type: String,
+    pub current_type: String,
+    pub status: String,
+    pub power_kw: Option<f64>,
+    pub voltage: Option<i32>,
+    pub amperage: Option<i32>,
+    pub count_available: i32,
+    pub count_total: i32,
+    pub removed: bool,
+    pub last_updated: DateTime<Utc>,
+}
+
+/// An active tariff of a verified network, as published over OCPI
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct OcpiTariffRow {
+    pub tariff_id: String,
+    pub currency: String,
+    pub vat_percent: f64,
+    /// Excluding VAT
+    pub min_price: Option<f64>,
+    pub max_price: Option<f64>,
+    pub last_updated: DateTime<Utc>,
+    #[sqlx(skip)]
+    pub components: Vec<OcpiTariffComponentRow>,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct OcpiTariffComponentRow {
+    pub tariff_id: String,
+    /// ENERGY, TIME, PARKING_TIME or FLAT
+    pub component_type: String,
+    /// Excluding VAT
+    pub price: f64,
+    pub step_size: i32,
+}
+
+/// Company details a partner submitted through locate-service to get its
+/// network verified
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct NetworkVerificationRequest {
+    pub request_id: String,
+    pub network_id: String,
+    pub status: String,
+    pub company_name: String,
+    pub registration_number: String,
+    pub vat_number: Option<String>,
+    pub registered_address: String,
+    pub contact_name: String,
+    pub contact_email: String,
+    pub contact_phone: Option<String>,
+    pub submitted_by: String,
+    pub submitted_at: DateTime<Utc>,
+    pub reviewed_by: Option<String>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub review_comment: Option<String>,
+}
+
+/// A supporting document of a verification request. The file itself stays
+/// in the object storage of locate-service.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct VerificationDocument {
+    pub document_id: String,
+    pub request_id: String,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub storage_key: String,
+    pub uploaded_by: String,
+    pub uploaded_at: DateTime<Utc>,
+}
+
+/// A change of a network's verification state
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct VerificationEvent {
+    pub event_id: i64,
+    pub network_id: String,
+    pub request_id: Option<String>,
+    /// None when the request was submitted
+    pub from_status: Option<String>,
+    pub to_status: String,
+    pub actor_id: String,
+    pub comment: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NetworkVerificationDetail {
+    pub request: NetworkVerificationRequest,
+    pub documents: Vec<VerificationDocument>,
+    pub events: Vec<VerificationEvent>,
+}
+
+/// A review users wrote through locate-service, as moderated here
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct UserReview {
+    pub review_id: String,
+    pub user_id: String,
+    pub station_id: String,
+    pub rating: i32,
+    pub review_text: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub moderation_status: String,
+    pub moderation_reason: Option<String>,
+    pub moderated_by: Option<String>,
+    pub moderated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ModerationQueueEntry {
+    #[sqlx(flatten)]
+    pub review: UserReview,
+    pub open_flags: i64,
+    pub flag_reasons: Vec<String>,
+}
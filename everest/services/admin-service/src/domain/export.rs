@@ -0,0 +1,266 @@
+use super::entities::StationExportRow;
+use crate::core::errors::{AppError, AppResult};
+use serde_json::{Value, json};
+use std::str::FromStr;
+
+const CSV_HEADER: [&str; 19] = [
+    "station_id",
+    "station_ref",
+    "osm_id",
+    "name",
+    "address",
+    "latitude",
+    "longitude",
+    "network_id",
+    "network_name",
+    "connector_id",
+    "connector_type",
+    "current_type",
+    "status",
+    "power_kw",
+    "voltage",
+    "amperage",
+    "count_total",
+    "count_available",
+    "updated_at",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    GeoJson,
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::GeoJson => "application/geo+json",
+        }
+    }
+
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::GeoJson => "geojson",
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Self::Csv),
+            "geojson" => Ok(Self::GeoJson),
+            other => Err(AppError::ValidationError(format!(
+                "Unknown export format: {}, expected csv or geojson",
+                other
+            ))),
+        }
+    }
+}
+
+/// `min_lon,min_lat,max_lon,max_lat` in WGS84
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub min_lon: f64,
+    pub min_lat: f64,
+    pub max_lon: f64,
+    pub max_lat: f64,
+}
+
+impl FromStr for BoundingBox {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            AppError::ValidationError(
+                "bbox must be min_lon,min_lat,max_lon,max_lat in degrees".to_string(),
+            )
+        };
+        let values = s
+            .split(',')
+            .map(|v| v.trim().parse::<f64>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| invalid())?;
+        let [min_lon, min_lat, max_lon, max_lat] = values[..] else {
+            return Err(invalid());
+        };
+        if !(-180.0..=180.0).contains(&min_lon)
+            || !(-180.0..=180.0).contains(&max_lon)
+            || !(-90.0..=90.0).contains(&min_lat)
+            || !(-90.0..=90.0).contains(&max_lat)
+            || min_lon >= max_lon
+            || min_lat >= max_lat
+        {
+            return Err(invalid());
+        }
+        Ok(Self {
+            min_lon,
+            min_lat,
+            max_lon,
+            max_lat,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ExportFilter {
+    pub network_id: Option<String>,
+    pub bbox: Option<BoundingBox>,
+}
+
+/// Turns rows ordered by station into CSV lines (one per connector) or
+/// GeoJSON features (one per station with a `connectors` array) as they
+/// arrive, holding at most one station in memory.
+pub struct ExportEncoder {
+    format: ExportFormat,
+    pending: Option<Value>,
+    features_written: usize,
+}
+
+impl ExportEncoder {
+    pub fn new(format: ExportFormat) -> Self {
+        Self {
+            format,
+            pending: None,
+            features_written: 0,
+        }
+    }
+
+    pub fn header(&self) -> AppResult<String> {
+        match self.format {
+            ExportFormat::Csv => csv_line(CSV_HEADER.iter().map(|h| h.to_string())),
+            ExportFormat::GeoJson => Ok(r#"{"type":"FeatureCollection","features":["#.to_string()),
+        }
+    }
+
+    /// Text to send for this row, if any is complete yet
+    pub fn push(&mut self, row: StationExportRow) -> AppResult<Option<String>> {
+        match self.format {
+            ExportFormat::Csv => csv_line(csv_fields(row)).map(Some),
+            ExportFormat::GeoJson => {
+                let same_station = self.pending.as_ref().is_some_and(|feature| {
+                    feature["properties"]["station_id"].as_str() == Some(row.station_id.as_str())
+                });
+                if same_station {
+                    if let Some(connector) = connector_json(&row)
+                        && let Some(connectors) = self
+                            .pending
+                            .as_mut()
+                            .and_then(|f| f["properties"]["connectors"].as_array_mut())
+                    {
+                        connectors.push(connector);
+                    }
+                    return Ok(None);
+                }
+                let finished = self.take_feature()?;
+                self.pending = Some(feature_json(&row));
+                Ok(finished)
+            }
+        }
+    }
+
+    /// Whatever is left once the last row has been pushed
+    pub fn finish(&mut self) -> AppResult<String> {
+        match self.format {
+            ExportFormat::Csv => Ok(String::new()),
+            ExportFormat::GeoJson => {
+                let mut text = self.take_feature()?.unwrap_or_default();
+                text.push_str("]}");
+                Ok(text)
+            }
+        }
+    }
+
+    fn take_feature(&mut self) -> AppResult<Option<String>> {
+        let Some(feature) = self.pending.take() else {
+            return Ok(None);
+        };
+        let json = serde_json::to_string(&feature)
+            .map_err(|e| AppError::InternalError(format!("Failed to encode feature: {}", e)))?;
+        let separator = if self.features_written > 0 { "," } else { "" };
+        self.features_written += 1;
+        Ok(Some(format!("{}{}", separator, json)))
+    }
+}
+
+fn csv_fields(row: StationExportRow) -> Vec<String> {
+    fn opt<T: ToString>(value: Option<T>) -> String {
+        value.map(|v| v.to_string()).unwrap_or_default()
+    }
+    vec![
+        row.station_id,
+        opt(row.external_ref),
+        row.osm_id.to_string(),
+        row.name,
+        opt(row.address),
+        row.latitude.to_string(),
+        row.longitude.to_string(),
+        opt(row.network_id),
+        opt(row.network_name),
+        opt(row.connector_id),
+        opt(row.connector_type),
+        opt(row.current_type),
+        opt(row.status),
+        opt(row.power_kw),
+        opt(row.voltage),
+        opt(row.amperage),
+        opt(row.count_total),
+        opt(row.count_available),
+        row.updated_at.to_rfc3339(),
+    ]
+}
+
+fn csv_line(fields: impl IntoIterator<Item = String>) -> AppResult<String> {
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(false)
+        .from_writer(Vec::new());
+    writer
+        .write_record(fields)
+        .map_err(|e| AppError::InternalError(format!("Failed to encode CSV: {}", e)))?;
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| AppError::InternalError(format!("Failed to encode CSV: {}", e)))?;
+    String::from_utf8(bytes)
+        .map_err(|e| AppError::InternalError(format!("Failed to encode CSV: {}", e)))
+}
+
+fn feature_json(row: &StationExportRow) -> Value {
+    json!({
+        "type": "Feature",
+        "id": row.station_id,
+        "geometry": {
+            "type": "Point",
+            "coordinates": [row.longitude, row.latitude],
+        },
+        "properties": {
+            "station_id": row.station_id,
+            "station_ref": row.external_ref,
+            "osm_id": row.osm_id,
+            "name": row.name,
+            "address": row.address,
+            "network_id": row.network_id,
+            "network_name": row.network_name,
+            "updated_at": row.updated_at.to_rfc3339(),
+            "connectors": connector_json(row).into_iter().collect::<Vec<_>>(),
+        },
+    })
+}
+
+fn connector_json(row: &StationExportRow) -> Option<Value> {
+    let connector_id = row.connector_id.as_ref()?;
+    Some(json!({
+        "connector_id": connector_id,
+        "connector_type": row.connector_type,
+        "current_type": row.current_type,
+        "status": row.status,
+        "power_kw": row.power_kw,
+        "voltage": row.voltage,
+        "amperage": row.amperage,
+        "count_total": row.count_total,
+        "count_available": row.count_available,
+    }))
+}
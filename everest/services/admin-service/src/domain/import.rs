@@ -0,0 +1,1010 @@
+use crate::core::constants::{CONNECTOR_ID_PREFIX, STATION_ID_PREFIX};
+use crate::core::errors::{AppError, AppResult};
+use crate::core::utils::generate_id;
+use geojson::{Feature, GeoJson, Value as Geometry};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+/// Largest value a `DECIMAL(5,2)` power column holds
+const MAX_POWER_KW: f64 = 999.99;
+const MAX_NAME_LENGTH: usize = 255;
+const MAX_EXTERNAL_REF_LENGTH: usize = 64;
+
+const CSV_COLUMNS: [&str; 14] = [
+    "station_ref",
+    "osm_id",
+    "name",
+    "address",
+    "latitude",
+    "longitude",
+    "network_id",
+    "connector_type",
+    "current_type",
+    "power_kw",
+    "voltage",
+    "amperage",
+    "count_total",
+    "count_available",
+];
+const CSV_REQUIRED_COLUMNS: [&str; 3] = ["name", "latitude", "longitude"];
+const CSV_CONNECTOR_COLUMNS: [&str; 7] = [
+    "connector_type",
+    "current_type",
+    "power_kw",
+    "voltage",
+    "amperage",
+    "count_total",
+    "count_available",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    Csv,
+    GeoJson,
+}
+
+impl ImportFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::GeoJson => "geojson",
+        }
+    }
+
+    /// Guesses the format from the request's Content-Type
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        match content_type {
+            "text/csv" => Some(Self::Csv),
+            "application/geo+json" | "application/json" => Some(Self::GeoJson),
+            _ => None,
+        }
+    }
+
+    /// One row per CSV line or GeoJSON feature. Values that cannot be read
+    /// are recorded on their row; only a malformed file as a whole is an error.
+    pub fn parse(&self, text: &str) -> AppResult<Vec<SourceRow>> {
+        match self {
+            Self::Csv => parse_csv(text),
+            Self::GeoJson => parse_geojson(text),
+        }
+    }
+}
+
+impl FromStr for ImportFormat {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Self::Csv),
+            "geojson" => Ok(Self::GeoJson),
+            other => Err(AppError::ValidationError(format!(
+                "Unknown import format: {}, expected csv or geojson",
+                other
+            ))),
+        }
+    }
+}
+
+/// A station as read from one CSV line or GeoJSON feature
+#[derive(Debug, Clone, Default)]
+pub struct SourceRow {
+    /// CSV line number, or 1-based feature index for GeoJSON
+    pub row: usize,
+    /// The partner's identifier for the site; CSV lines sharing it describe
+    /// the connectors of one station
+    pub station_ref: Option<String>,
+    pub osm_id: Option<i64>,
+    pub name: Option<String>,
+    pub address: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub network_id: Option<String>,
+    pub connectors: Vec<SourceConnector>,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SourceConnector {
+    /// Prefix for error messages, e.g. `connectors[1].`
+    #[serde(skip)]
+    pub label: String,
+    pub connector_type: Option<String>,
+    pub current_type: Option<String>,
+    pub power_kw: Option<f64>,
+    pub voltage: Option<i32>,
+    pub amperage: Option<i32>,
+    pub count_total: Option<i32>,
+    pub count_available: Option<i32>,
+}
+
+/// Reference data the rows are checked against
+#[derive(Debug, Clone, Default)]
+pub struct ImportLookups {
+    /// Lowercase connector type name to id
+    pub connector_types: HashMap<String, i64>,
+    /// Uppercase current type name to id
+    pub current_types: HashMap<String, i64>,
+    pub networks: HashSet<String>,
+    pub existing_osm_ids: HashSet<i64>,
+    /// (network_id, external_ref) pairs already in the database
+    pub existing_refs: HashSet<(String, String)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PlannedStation {
+    pub station_id: String,
+    pub external_ref: Option<String>,
+    /// None draws a negative id from `imported_station_osm_id_seq`
+    pub osm_id: Option<i64>,
+    pub name: String,
+    pub address: Option<String>,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub network_id: Option<String>,
+    pub connectors: Vec<PlannedConnector>,
+    /// First source row describing the station
+    first_row: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct PlannedConnector {
+    pub connector_id: String,
+    pub connector_type_id: i64,
+    pub current_type_id: i64,
+    pub power_kw: Option<f64>,
+    pub voltage: Option<i32>,
+    pub amperage: Option<i32>,
+    pub count_total: i32,
+    pub count_available: i32,
+    row: usize,
+    label: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct RowReport {
+    pub row: usize,
+    pub station_ref: Option<String>,
+    pub name: Option<String>,
+    pub errors: Vec<String>,
+}
+
+impl RowReport {
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportPlan {
+    pub stations: Vec<PlannedStation>,
+    pub rows: Vec<RowReport>,
+}
+
+impl ImportPlan {
+    pub fn is_valid(&self) -> bool {
+        self.rows.iter().all(RowReport::is_valid)
+    }
+
+    pub fn connector_count(&self) -> usize {
+        self.stations.iter().map(|s| s.connectors.len()).sum()
+    }
+}
+
+/// Outcome of an import, dry run or not
+#[derive(Debug, Clone)]
+pub struct ImportReport {
+    pub format: ImportFormat,
+    pub dry_run: bool,
+    pub committed: bool,
+    pub station_count: usize,
+    pub connector_count: usize,
+    pub rows: Vec<RowReport>,
+    /// Ids of the created stations, once committed
+    pub station_ids: Vec<String>,
+}
+
+/// Validates every row against `lookups` and groups them into stations.
+/// Rows sharing a network and `station_ref` must agree on the station's
+/// details and add one connector each.
+pub fn plan_import(rows: Vec<SourceRow>, lookups: &ImportLookups) -> ImportPlan {
+    let mut stations: Vec<PlannedStation> = Vec::new();
+    let mut by_ref: HashMap<(Option<String>, String), usize> = HashMap::new();
+    let mut osm_ids: HashMap<i64, usize> = HashMap::new();
+    let mut reports = Vec::with_capacity(rows.len());
+
+    for source in rows {
+        let mut errors = source.errors.clone();
+        let name = check_name(source.name.as_deref(), &mut errors);
+        let coordinates = check_coordinates(source.latitude, source.longitude, &mut errors);
+
+        if let Some(network_id) = &source.network_id
+            && !lookups.networks.contains(network_id)
+        {
+            errors.push(format!("network_id: unknown network {}", network_id));
+        }
+        if let Some(station_ref) = &source.station_ref {
+            if station_ref.len() > MAX_EXTERNAL_REF_LENGTH {
+                errors.push(format!(
+                    "station_ref: at most {} characters",
+                    MAX_EXTERNAL_REF_LENGTH
+                ));
+            }
+            if let Some(network_id) = &source.network_id
+                && lookups
+                    .existing_refs
+                    .contains(&(network_id.clone(), station_ref.clone()))
+            {
+                errors.push(format!(
+                    "station_ref: {} was already imported for network {}",
+                    station_ref, network_id
+                ));
+            }
+        }
+
+        let connectors: Vec<PlannedConnector> = source
+            .connectors
+            .iter()
+            .filter_map(|c| check_connector(c, source.row, lookups, &mut errors))
+            .collect();
+
+        let group = source
+            .station_ref
+            .clone()
+            .map(|r| (source.network_id.clone(), r));
+        let existing = group.as_ref().and_then(|g| by_ref.get(g).copied());
+
+        match (existing, name, coordinates) {
+            (Some(index), _, _) => {
+                let station = &mut stations[index];
+                if source.name.as_deref().map(str::trim) != Some(station.name.as_str())
+                    || coordinates != Some((station.latitude, station.longitude))
+                    || source.address.is_some() && source.address != station.address
+                    || source.osm_id.is_some() && source.osm_id != station.osm_id
+                {
+                    errors.push(format!(
+                        "station_ref: details differ from row {} describing the same station",
+                        station.first_row
+                    ));
+                }
+                for connector in connectors {
+                    add_connector(station, connector, &mut errors);
+                }
+            }
+            (None, Some(name), Some((latitude, longitude))) => {
+                if let Some(osm_id) = source.osm_id {
+                    if lookups.existing_osm_ids.contains(&osm_id) {
+                        errors.push(format!("osm_id: {} is already used by a station", osm_id));
+                    } else if let Some(row) = osm_ids.get(&osm_id) {
+                        errors.push(format!("osm_id: {} is also used on row {}", osm_id, row));
+                    } else {
+                        osm_ids.insert(osm_id, source.row);
+                    }
+                }
+
+                let mut station = PlannedStation {
+                    station_id: generate_id(STATION_ID_PREFIX),
+                    external_ref: source.station_ref.clone(),
+                    osm_id: source.osm_id,
+                    name,
+                    address: source.address.clone(),
+                    latitude,
+                    longitude,
+                    network_id: source.network_id.clone(),
+                    connectors: Vec::new(),
+                    first_row: source.row,
+                };
+                for connector in connectors {
+                    add_connector(&mut station, connector, &mut errors);
+                }
+                if let Some(group) = group {
+                    by_ref.insert(group, stations.len());
+                }
+                stations.push(station);
+            }
+            // The row's errors already say what is missing
+            (None, _, _) => {}
+        }
+
+        reports.push(RowReport {
+            row: source.row,
+            station_ref: source.station_ref,
+            name: source.name,
+            errors,
+        });
+    }
+
+    ImportPlan {
+        stations,
+        rows: reports,
+    }
+}
+
+fn check_name(name: Option<&str>, errors: &mut Vec<String>) -> Option<String> {
+    match name.map(str::trim) {
+        None | Some("") => {
+            errors.push("name: required".to_string());
+            None
+        }
+        Some(name) if name.chars().count() > MAX_NAME_LENGTH => {
+            errors.push(format!("name: at most {} characters", MAX_NAME_LENGTH));
+            None
+        }
+        Some(name) => Some(name.to_string()),
+    }
+}
+
+fn check_coordinates(
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    errors: &mut Vec<String>,
+) -> Option<(f64, f64)> {
+    let latitude = match latitude {
+        Some(lat) if (-90.0..=90.0).contains(&lat) => Some(lat),
+        Some(_) => {
+            errors.push("latitude: must be between -90 and 90".to_string());
+            None
+        }
+        // Already reported when the value was not a number
+        None if has_error(errors, "latitude") => None,
+        None => {
+            errors.push("latitude: required".to_string());
+            None
+        }
+    };
+    let longitude = match longitude {
+        Some(lon) if (-180.0..=180.0).contains(&lon) => Some(lon),
+        Some(_) => {
+            errors.push("longitude: must be between -180 and 180".to_string());
+            None
+        }
+        None if has_error(errors, "longitude") => None,
+        None => {
+            errors.push("longitude: required".to_string());
+            None
+        }
+    };
+    Some((latitude?, longitude?))
+}
+
+fn has_error(errors: &[String], field: &str) -> bool {
+    errors.iter().any(|e| e.starts_with(&format!("{}:", field)))
+}
+
+fn check_connector(
+    source: &SourceConnector,
+    row: usize,
+    lookups: &ImportLookups,
+    errors: &mut Vec<String>,
+) -> Option<PlannedConnector> {
+    let label = &source.label;
+    let before = errors.len();
+
+    let connector_type_id = match source.connector_type.as_deref().map(str::trim) {
+        None | Some("") => {
+            errors.push(format!("{}connector_type: required", label));
+            None
+        }
+        Some(name) => {
+            let id = lookups.connector_types.get(&name.to_lowercase()).copied();
+            if id.is_none() {
+                errors.push(format!(
+                    "{}connector_type: unknown type {}, expected one of {}",
+                    label,
+                    name,
+                    sorted_names(lookups.connector_types.keys())
+                ));
+            }
+            id
+        }
+    };
+    let current_type_id = match source.current_type.as_deref().map(str::trim) {
+        None | Some("") => {
+            errors.push(format!("{}current_type: required", label));
+            None
+        }
+        Some(name) => {
+            let id = lookups.current_types.get(&name.to_uppercase()).copied();
+            if id.is_none() {
+                errors.push(format!(
+                    "{}current_type: unknown current {}, expected one of {}",
+                    label,
+                    name,
+                    sorted_names(lookups.current_types.keys())
+                ));
+            }
+            id
+        }
+    };
+
+    let count_total = source.count_total.unwrap_or(1);
+    if count_total < 1 {
+        errors.push(format!("{}count_total: must be at least 1", label));
+    }
+    let count_available = source.count_available.unwrap_or(count_total);
+    if !(0..=count_total).contains(&count_available) {
+        errors.push(format!(
+            "{}count_available: must be between 0 and count_total",
+            label
+        ));
+    }
+    if let Some(power_kw) = source.power_kw
+        && !(power_kw > 0.0 && power_kw <= MAX_POWER_KW)
+    {
+        errors.push(format!(
+            "{}power_kw: must be above 0 and at most {}",
+            label, MAX_POWER_KW
+        ));
+    }
+    if source.voltage.is_some_and(|v| v <= 0) {
+        errors.push(format!("{}voltage: must be positive", label));
+    }
+    if source.amperage.is_some_and(|a| a <= 0) {
+        errors.push(format!("{}amperage: must be positive", label));
+    }
+
+    if errors.len() > before {
+        return None;
+    }
+    Some(PlannedConnector {
+        connector_id: generate_id(CONNECTOR_ID_PREFIX),
+        connector_type_id: connector_type_id?,
+        current_type_id: current_type_id?,
+        power_kw: source.power_kw,
+        voltage: source.voltage,
+        amperage: source.amperage,
+        count_total,
+        count_available,
+        row,
+        label: label.clone(),
+    })
+}
+
+/// A station has at most one connector per connector type and current type
+fn add_connector(
+    station: &mut PlannedStation,
+    connector: PlannedConnector,
+    errors: &mut Vec<String>,
+) {
+    if let Some(other) = station.connectors.iter().find(|c| {
+        c.connector_type_id == connector.connector_type_id
+            && c.current_type_id == connector.current_type_id
+    }) {
+        errors.push(format!(
+            "{}connector_type: the station already has this connector type and current (row {}); use count_total instead",
+            connector.label, other.row
+        ));
+        return;
+    }
+    station.connectors.push(connector);
+}
+
+fn sorted_names<'a>(names: impl Iterator<Item = &'a String>) -> String {
+    let mut names: Vec<&str> = names.map(String::as_str).collect();
+    names.sort_unstable();
+    names.join(", ")
+}
+
+fn parse_csv(text: &str) -> AppResult<Vec<SourceRow>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(text.as_bytes());
+
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|e| AppError::ValidationError(format!("Invalid CSV header: {}", e)))?
+        .iter()
+        .map(|h| h.to_lowercase())
+        .collect();
+    if let Some(unknown) = headers.iter().find(|h| !CSV_COLUMNS.contains(&h.as_str())) {
+        return Err(AppError::ValidationError(format!(
+            "Unknown CSV column: {}, expected {}",
+            unknown,
+            CSV_COLUMNS.join(", ")
+        )));
+    }
+    if let Some(missing) = CSV_REQUIRED_COLUMNS
+        .iter()
+        .find(|c| !headers.iter().any(|h| h == *c))
+    {
+        return Err(AppError::ValidationError(format!(
+            "Missing CSV column: {}",
+            missing
+        )));
+    }
+
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record =
+            record.map_err(|e| AppError::ValidationError(format!("Invalid CSV: {}", e)))?;
+        let line = record
+            .position()
+            .map_or(rows.len() + 2, |p| p.line() as usize);
+        let values: HashMap<&str, &str> = headers
+            .iter()
+            .map(String::as_str)
+            .zip(record.iter())
+            .filter(|(_, v)| !v.is_empty())
+            .collect();
+
+        let mut errors = Vec::new();
+        let mut row = SourceRow {
+            row: line,
+            station_ref: values.get("station_ref").map(|v| v.to_string()),
+            osm_id: csv_number(&values, "osm_id", &mut errors),
+            name: values.get("name").map(|v| v.to_string()),
+            address: values.get("address").map(|v| v.to_string()),
+            latitude: csv_number(&values, "latitude", &mut errors),
+            longitude: csv_number(&values, "longitude", &mut errors),
+            network_id: values.get("network_id").map(|v| v.to_string()),
+            ..Default::default()
+        };
+        if CSV_CONNECTOR_COLUMNS.iter().any(|c| values.contains_key(c)) {
+            row.connectors.push(SourceConnector {
+                label: String::new(),
+                connector_type: values.get("connector_type").map(|v| v.to_string()),
+                current_type: values.get("current_type").map(|v| v.to_string()),
+                power_kw: csv_number(&values, "power_kw", &mut errors),
+                voltage: csv_number(&values, "voltage", &mut errors),
+                amperage: csv_number(&values, "amperage", &mut errors),
+                count_total: csv_number(&values, "count_total", &mut errors),
+                count_available: csv_number(&values, "count_available", &mut errors),
+            });
+        }
+        row.errors = errors;
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+fn csv_number<T: FromStr>(
+    values: &HashMap<&str, &str>,
+    column: &str,
+    errors: &mut Vec<String>,
+) -> Option<T> {
+    let value = values.get(column)?;
+    match value.parse() {
+        Ok(number) => Some(number),
+        Err(_) => {
+            errors.push(format!("{}: {} is not a valid number", column, value));
+            None
+        }
+    }
+}
+
+fn parse_geojson(text: &str) -> AppResult<Vec<SourceRow>> {
+    let geojson = GeoJson::from_str(text)
+        .map_err(|e| AppError::ValidationError(format!("Invalid GeoJSON: {}", e)))?;
+    let features = match geojson {
+        GeoJson::FeatureCollection(collection) => collection.features,
+        GeoJson::Feature(feature) => vec![feature],
+        GeoJson::Geometry(_) => {
+            return Err(AppError::ValidationError(
+                "Expected a GeoJSON FeatureCollection or Feature".to_string(),
+            ));
+        }
+    };
+
+    Ok(features
+        .into_iter()
+        .enumerate()
+        .map(|(index, feature)| feature_row(index + 1, feature))
+        .collect())
+}
+
+fn feature_row(row: usize, feature: Feature) -> SourceRow {
+    let mut errors = Vec::new();
+    let (longitude, latitude) = match feature.geometry.as_ref().map(|g| &g.value) {
+        Some(Geometry::Point(position)) if position.len() >= 2 => {
+            (Some(position[0]), Some(position[1]))
+        }
+        Some(_) => {
+            errors.push("geometry: must be a Point".to_string());
+            (None, None)
+        }
+        None => {
+            errors.push("geometry: required".to_string());
+            (None, None)
+        }
+    };
+
+    let properties = feature.properties.clone().unwrap_or_default();
+    let feature_id = feature.id.as_ref().map(|id| match id {
+        geojson::feature::Id::String(s) => s.clone(),
+        geojson::feature::Id::Number(n) => n.to_string(),
+    });
+
+    let connectors = match properties.get("connectors") {
+        None | Some(Value::Null) => Vec::new(),
+        Some(Value::Array(items)) => items
+            .iter()
+            .enumerate()
+            .filter_map(
+                |(i, item)| match serde_json::from_value::<SourceConnector>(item.clone()) {
+                    Ok(mut connector) => {
+                        connector.label = format!("connectors[{}].", i);
+                        Some(connector)
+                    }
+                    Err(e) => {
+                        errors.push(format!("connectors[{}]: {}", i, e));
+                        None
+                    }
+                },
+            )
+            .collect(),
+        Some(_) => {
+            errors.push("connectors: must be an array".to_string());
+            Vec::new()
+        }
+    };
+
+    SourceRow {
+        row,
+        station_ref: property_string(&properties, "station_ref", &mut errors).or(feature_id),
+        osm_id: property_integer(&properties, "osm_id", &mut errors),
+        name: property_string(&properties, "name", &mut errors),
+        address: property_string(&properties, "address", &mut errors),
+        latitude,
+        longitude,
+        network_id: property_string(&properties, "network_id", &mut errors),
+        connectors,
+        errors,
+    }
+}
+
+/// Strings are taken as they are and numbers written out, e.g. for refs
+fn property_string(
+    properties: &Map<String, Value>,
+    key: &str,
+    errors: &mut Vec<String>,
+) -> Option<String> {
+    match properties.get(key)? {
+        Value::Null => None,
+        Value::String(s) if s.trim().is_empty() => None,
+        Value::String(s) => Some(s.trim().to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => {
+            errors.push(format!("{}: must be a string", key));
+            None
+        }
+    }
+}
+
+fn property_integer(
+    properties: &Map<String, Value>,
+    key: &str,
+    errors: &mut Vec<String>,
+) -> Option<i64> {
+    let number = property_number(properties, key, errors)?;
+    if number.fract() != 0.0 {
+        errors.push(format!("{}: must be a whole number", key));
+        return None;
+    }
+    Some(number as i64)
+}
+
+fn property_number(
+    properties: &Map<String, Value>,
+    key: &str,
+    errors: &mut Vec<String>,
+) -> Option<f64> {
+    match properties.get(key)? {
+        Value::Null => None,
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => match s.trim().parse() {
+            Ok(n) => Some(n),
+            Err(_) => {
+                errors.push(format!("{}: {} is not a valid number", key, s));
+                None
+            }
+        },
+        _ => {
+            errors.push(format!("{}: must be a number", key));
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &str = "station_ref,osm_id,name,latitude,longitude,network_id,connector_type,current_type,power_kw,count_total,count_available";
+
+    fn lookups() -> ImportLookups {
+        ImportLookups {
+            connector_types: HashMap::from([("ccs2".to_string(), 1), ("type2".to_string(), 2)]),
+            current_types: HashMap::from([("DC".to_string(), 1), ("AC".to_string(), 2)]),
+            networks: HashSet::from(["NET-1".to_string()]),
+            existing_osm_ids: HashSet::from([42]),
+            existing_refs: HashSet::from([("NET-1".to_string(), "TUN-OLD".to_string())]),
+        }
+    }
+
+    fn plan_csv(lines: &[&str]) -> ImportPlan {
+        let text = std::iter::once(HEADER)
+            .chain(lines.iter().copied())
+            .collect::<Vec<_>>()
+            .join("\n");
+        plan_import(ImportFormat::Csv.parse(&text).unwrap(), &lookups())
+    }
+
+    fn errors(plan: &ImportPlan, row: usize) -> Vec<String> {
+        plan.rows
+            .iter()
+            .find(|r| r.row == row)
+            .map(|r| r.errors.clone())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn plans_one_station_per_station_ref() {
+        let plan = plan_csv(&[
+            "TUN-1,,Lac 2,36.84,10.27,NET-1,CCS2,DC,60,2,1",
+            "TUN-1,,Lac 2,36.84,10.27,NET-1,Type2,AC,22,,",
+            "TUN-2,1001,Menzah,36.85,10.18,NET-1,ccs2,dc,120,,",
+        ]);
+
+        assert!(plan.is_valid(), "{:?}", plan.rows);
+        assert_eq!(plan.stations.len(), 2);
+        assert_eq!(plan.connector_count(), 3);
+
+        let lac = &plan.stations[0];
+        assert!(lac.station_id.starts_with(STATION_ID_PREFIX));
+        assert_eq!(lac.external_ref.as_deref(), Some("TUN-1"));
+        assert_eq!(lac.osm_id, None);
+        assert_eq!((lac.latitude, lac.longitude), (36.84, 10.27));
+        assert_eq!(lac.connectors[0].connector_type_id, 1);
+        assert_eq!(
+            (
+                lac.connectors[0].count_total,
+                lac.connectors[0].count_available
+            ),
+            (2, 1)
+        );
+        // Without counts a line is one connector, all of it available
+        assert_eq!(
+            (
+                lac.connectors[1].count_total,
+                lac.connectors[1].count_available
+            ),
+            (1, 1)
+        );
+        assert_eq!(plan.stations[1].osm_id, Some(1001));
+        assert_eq!(
+            plan.rows.iter().map(|r| r.row).collect::<Vec<_>>(),
+            [2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_and_missing_csv_columns() {
+        let unknown = ImportFormat::Csv.parse("name,latitude,longitude,colour\nA,1,2,red");
+        let missing = ImportFormat::Csv.parse("name,latitude\nA,1");
+
+        assert!(matches!(unknown, Err(AppError::ValidationError(m)) if m.contains("colour")));
+        assert!(matches!(missing, Err(AppError::ValidationError(m)) if m.contains("longitude")));
+    }
+
+    #[test]
+    fn reports_malformed_rows_without_failing_the_file() {
+        let plan = plan_csv(&[
+            ",,,36.84,10.27,,,,,,",
+            "TUN-3,abc,Ariana,north,10.19,NET-1,,,,,",
+            "TUN-4,,Bardo,36.81,10.13,NET-9,,,,,",
+        ]);
+
+        assert!(!plan.is_valid());
+        // Only the row with a name and coordinates still yields a station
+        assert_eq!(plan.stations.len(), 1);
+        assert_eq!(errors(&plan, 2), ["name: required"]);
+        // An unreadable value is reported once, not also as missing
+        assert_eq!(
+            errors(&plan, 3),
+            [
+                "osm_id: abc is not a valid number",
+                "latitude: north is not a valid number"
+            ]
+        );
+        assert_eq!(errors(&plan, 4), ["network_id: unknown network NET-9"]);
+    }
+
+    #[test]
+    fn rejects_coordinates_out_of_range() {
+        let plan = plan_csv(&[
+            "TUN-5,,Sousse,91,10.6,,,,,,",
+            "TUN-6,,Sfax,34.7,-180.5,,,,,,",
+            "TUN-7,,Gabes,,10.1,,,,,,",
+        ]);
+
+        assert!(plan.stations.is_empty());
+        assert_eq!(errors(&plan, 2), ["latitude: must be between -90 and 90"]);
+        assert_eq!(
+            errors(&plan, 3),
+            ["longitude: must be between -180 and 180"]
+        );
+        assert_eq!(errors(&plan, 4), ["latitude: required"]);
+    }
+
+    #[test]
+    fn rejects_refs_already_imported_or_described_differently() {
+        let plan = plan_csv(&[
+            "TUN-OLD,,Lac 1,36.83,10.23,NET-1,,,,,",
+            "TUN-8,,Marsa,36.88,10.32,NET-1,CCS2,DC,50,,",
+            "TUN-8,,La Marsa,36.88,10.32,NET-1,Type2,AC,22,,",
+            "TUN-8,,Marsa,36.88,10.32,NET-1,CCS2,DC,150,,",
+        ]);
+
+        assert_eq!(
+            errors(&plan, 2),
+            ["station_ref: TUN-OLD was already imported for network NET-1"]
+        );
+        assert_eq!(
+            errors(&plan, 4),
+            ["station_ref: details differ from row 3 describing the same station"]
+        );
+        assert_eq!(
+            errors(&plan, 5),
+            [
+                "connector_type: the station already has this connector type and current (row 3); use count_total instead"
+            ]
+        );
+        // Refs are only unique within a network
+        let other = plan_csv(&[
+            "TUN-OLD,,Lac 1,36.83,10.23,,,,,,",
+            "TUN-8,,Marsa,36.88,10.32,,,,,,",
+        ]);
+        assert!(other.is_valid());
+        assert_eq!(other.stations.len(), 2);
+    }
+
+    #[test]
+    fn rejects_duplicate_osm_ids() {
+        let plan = plan_csv(&[
+            "A,42,Hammamet,36.4,10.6,,,,,,",
+            "B,1002,Nabeul,36.45,10.73,,,,,,",
+            "C,1002,Nabeul Nord,36.46,10.73,,,,,,",
+        ]);
+
+        assert_eq!(
+            errors(&plan, 2),
+            ["osm_id: 42 is already used by a station"]
+        );
+        assert!(errors(&plan, 3).is_empty());
+        assert_eq!(errors(&plan, 4), ["osm_id: 1002 is also used on row 3"]);
+    }
+
+    #[test]
+    fn rejects_bad_connector_data() {
+        let plan = plan_csv(&[
+            "A,,Kairouan,35.67,10.1,,CHAdeMO,DC,50,,",
+            "B,,Monastir,35.77,10.82,,CCS2,,0,,",
+            "C,,Mahdia,35.5,11.06,,Type2,AC,1000,2,3",
+            "D,,Tozeur,33.92,8.13,,Type2,AC,22,0,",
+        ]);
+
+        assert_eq!(
+            errors(&plan, 2),
+            ["connector_type: unknown type CHAdeMO, expected one of ccs2, type2"]
+        );
+        assert_eq!(
+            errors(&plan, 3),
+            [
+                "current_type: required",
+                "power_kw: must be above 0 and at most 999.99"
+            ]
+        );
+        assert_eq!(
+            errors(&plan, 4),
+            [
+                "count_available: must be between 0 and count_total",
+                "power_kw: must be above 0 and at most 999.99"
+            ]
+        );
+        assert_eq!(errors(&plan, 5), ["count_total: must be at least 1"]);
+        // The stations are still planned, without the rejected connectors
+        assert_eq!(plan.stations.len(), 4);
+        assert_eq!(plan.connector_count(), 0);
+    }
+
+    #[test]
+    fn reads_geojson_features() {
+        let text = r#"{
+            "type": "FeatureCollection",
+            "features": [
+                {
+                    "type": "Feature",
+                    "id": "TUN-9",
+                    "geometry": { "type": "Point", "coordinates": [10.64, 35.83] },
+                    "properties": {
+                        "name": " Sousse Centre ",
+                        "osm_id": "1003",
+                        "network_id": "NET-1",
+                        "connectors": [
+                            { "connector_type": "CCS2", "current_type": "DC", "power_kw": 60 },
+                            { "connector_type": "Type2", "current_type": "AC", "count_total": 2 }
+                        ]
+                    }
+                },
+                {
+                    "type": "Feature",
+                    "geometry": { "type": "LineString", "coordinates": [[10.0, 35.0], [10.1, 35.1]] },
+                    "properties": { "name": "Road", "osm_id": 1.5, "connectors": {} }
+                },
+                {
+                    "type": "Feature",
+                    "geometry": null,
+                    "properties": {
+                        "name": "Nowhere",
+                        "connectors": [{ "connector_type": "Type2", "power_kw": "fast" }]
+                    }
+                }
+            ]
+        }"#;
+
+        let rows = ImportFormat::GeoJson.parse(text).unwrap();
+
+        assert_eq!(rows.len(), 3);
+        let sousse = &rows[0];
+        assert_eq!(sousse.row, 1);
+        assert_eq!(sousse.station_ref.as_deref(), Some("TUN-9"));
+        assert_eq!(sousse.osm_id, Some(1003));
+        assert_eq!(sousse.name.as_deref(), Some("Sousse Centre"));
+        assert_eq!(
+            (sousse.latitude, sousse.longitude),
+            (Some(35.83), Some(10.64))
+        );
+        assert_eq!(sousse.connectors[1].label, "connectors[1].");
+        assert!(sousse.errors.is_empty());
+
+        assert_eq!(
+            rows[1].errors,
+            [
+                "geometry: must be a Point",
+                "connectors: must be an array",
+                "osm_id: must be a whole number"
+            ]
+        );
+        assert_eq!(rows[2].errors.len(), 2);
+        assert_eq!(rows[2].errors[0], "geometry: required");
+        assert!(rows[2].errors[1].starts_with("connectors[0]: "));
+
+        let plan = plan_import(rows, &lookups());
+        assert_eq!(plan.stations.len(), 1);
+        assert_eq!(plan.connector_count(), 2);
+        assert!(plan.rows[0].is_valid());
+        assert!(!plan.rows[1].is_valid());
+    }
+
+    #[test]
+    fn labels_geojson_connector_errors_with_their_index() {
+        let text = r#"{
+            "type": "Feature",
+            "geometry": { "type": "Point", "coordinates": [10.1, 36.8] },
+            "properties": {
+                "name": "Ben Arous",
+                "connectors": [
+                    { "connector_type": "CCS2", "current_type": "DC" },
+                    { "connector_type": "CCS2", "current_type": "AC", "voltage": -1 }
+                ]
+            }
+        }"#;
+
+        let plan = plan_import(ImportFormat::GeoJson.parse(text).unwrap(), &lookups());
+
+        assert_eq!(
+            plan.rows[0].errors,
+            ["connectors[1].voltage: must be positive"]
+        );
+        assert_eq!(plan.connector_count(), 1);
+    }
+
+    #[test]
+    fn rejects_a_bare_geojson_geometry() {
+        let result =
+            ImportFormat::GeoJson.parse(r#"{ "type": "Point", "coordinates": [10.1, 36.8] }"#);
+
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+}
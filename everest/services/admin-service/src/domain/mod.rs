@@ -1,4 +1,14 @@
+pub mod deleted;
 pub mod entities;
+pub mod export;
+pub mod import;
+pub mod moderation;
+pub mod ocpi;
+pub mod osm_import;
+pub mod osm_tags;
+pub mod provenance;
 pub mod repositories;
 pub mod services;
+pub mod sync;
 pub mod value_objects;
+pub mod verification;
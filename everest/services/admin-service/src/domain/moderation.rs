@@ -0,0 +1,38 @@
+use crate::core::errors::AppError;
+use std::str::FromStr;
+
+/// Moderation state of a user review. locate-service holds reviews as
+/// pending when they are flagged or match the blocklist; admins hide or
+/// restore them here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModerationStatus {
+    Visible,
+    Pending,
+    Hidden,
+}
+
+impl ModerationStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Visible => "visible",
+            Self::Pending => "pending",
+            Self::Hidden => "hidden",
+        }
+    }
+}
+
+impl FromStr for ModerationStatus {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "visible" => Ok(Self::Visible),
+            "pending" => Ok(Self::Pending),
+            "hidden" => Ok(Self::Hidden),
+            other => Err(AppError::ValidationError(format!(
+                "Unknown moderation status: {}, expected visible, pending or hidden",
+                other
+            ))),
+        }
+    }
+}
@@ -0,0 +1,516 @@
+//! OCPI 2.2.1 objects we exchange as CPO, and how stations, connectors and
+//! tariffs map onto them.
+
+use super::entities::{OcpiConnectorRow, OcpiLocationRow, OcpiParty, OcpiTariffRow};
+use crate::core::constants::OCPI_VERSION;
+use crate::core::errors::{AppError, AppResult};
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+pub const STATUS_SUCCESS: u16 = 1000;
+pub const STATUS_CLIENT_ERROR: u16 = 2000;
+pub const STATUS_INVALID_PARAMETERS: u16 = 2001;
+pub const STATUS_UNKNOWN_LOCATION: u16 = 2003;
+pub const STATUS_SERVER_ERROR: u16 = 3000;
+/// The party's API failed us while registering it
+pub const STATUS_CLIENT_API_ERROR: u16 = 3001;
+
+/// Who we roam as
+#[derive(Debug, Clone)]
+pub struct OcpiIdentity {
+    /// Public URL of the service, without trailing slash
+    pub base_url: String,
+    pub country_code: String,
+    pub party_id: String,
+    pub business_name: String,
+    /// ISO 3166-1 alpha-3 country of the locations
+    pub location_country: String,
+    pub time_zone: String,
+}
+
+impl OcpiIdentity {
+    pub fn versions_url(&self) -> String {
+        format!("{}/ocpi/versions", self.base_url)
+    }
+
+    /// Sessions and CDRs are not offered, as no charging sessions are
+    /// recorded to build them from
+    pub fn version_details(&self) -> VersionDetails {
+        let module_url =
+            |module: &str| format!("{}/ocpi/{}/{}", self.base_url, OCPI_VERSION, module);
+        VersionDetails {
+            version: OCPI_VERSION.to_string(),
+            endpoints: vec![
+                Endpoint {
+                    identifier: "credentials".to_string(),
+                    role: "SENDER".to_string(),
+                    url: module_url("credentials"),
+                },
+                Endpoint {
+                    identifier: "locations".to_string(),
+                    role: "SENDER".to_string(),
+                    url: module_url("locations"),
+                },
+                Endpoint {
+                    identifier: "tariffs".to_string(),
+                    role: "SENDER".to_string(),
+                    url: module_url("tariffs"),
+                },
+            ],
+        }
+    }
+
+    /// Our credentials, for the party to call us with `token`
+    pub fn credentials(&self, token: &str) -> Credentials {
+        Credentials {
+            token: token.to_string(),
+            url: self.versions_url(),
+            roles: vec![CredentialsRole {
+                role: "CPO".to_string(),
+                business_details: BusinessDetails {
+                    name: self.business_name.clone(),
+                    website: None,
+                },
+                party_id: self.party_id.clone(),
+                country_code: self.country_code.clone(),
+            }],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OcpiPartyStatus {
+    Pending,
+    Registered,
+    Unregistered,
+}
+
+impl OcpiPartyStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Registered => "registered",
+            Self::Unregistered => "unregistered",
+        }
+    }
+}
+
+impl FromStr for OcpiPartyStatus {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(Self::Pending),
+            "registered" => Ok(Self::Registered),
+            "unregistered" => Ok(Self::Unregistered),
+            other => Err(AppError::ValidationError(format!(
+                "Unknown OCPI party status: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Which of its tokens a party called us with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OcpiToken {
+    /// Token A, only good for registering
+    Registration,
+    /// Token C
+    Access,
+}
+
+/// Calls we make to a party
+#[async_trait]
+pub trait OcpiPartyApi: Send + Sync {
+    async fn versions(&self, url: &str, token: &str) -> AppResult<Vec<Version>>;
+    async fn version_details(&self, url: &str, token: &str) -> AppResult<VersionDetails>;
+    /// Sends a location to the party's Locations receiver
+    async fn put_location(&self, party: &OcpiParty, location: &Location) -> AppResult<()>;
+}
+
+/// OCPI timestamps are UTC, without fractional seconds here
+pub mod timestamp {
+    use chrono::{DateTime, Utc};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        value: &DateTime<Utc>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&super::format_timestamp(*value))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<DateTime<Utc>, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        super::parse_timestamp(&value).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Parses an OCPI timestamp; one without time zone designator is UTC
+pub fn parse_timestamp(value: &str) -> AppResult<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|t| t.with_timezone(&Utc))
+        .or_else(|_| {
+            NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f").map(|t| t.and_utc())
+        })
+        .map_err(|_| AppError::ValidationError(format!("Invalid OCPI timestamp: {}", value)))
+}
+
+pub fn format_timestamp(value: DateTime<Utc>) -> String {
+    value.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Envelope of every OCPI response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OcpiResponse<T> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<T>,
+    pub status_code: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_message: Option<String>,
+    #[serde(with = "timestamp")]
+    pub timestamp: DateTime<Utc>,
+}
+
+impl<T> OcpiResponse<T> {
+    pub fn success(data: Option<T>) -> Self {
+        Self {
+            data,
+            status_code: STATUS_SUCCESS,
+            status_message: Some("Success".to_string()),
+            timestamp: Utc::now(),
+        }
+    }
+
+    pub fn error(status_code: u16, message: String) -> Self {
+        Self {
+            data: None,
+            status_code,
+            status_message: Some(message),
+            timestamp: Utc::now(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Version {
+    pub version: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionDetails {
+    pub version: String,
+    pub endpoints: Vec<Endpoint>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Endpoint {
+    pub identifier: String,
+    /// SENDER or RECEIVER
+    pub role: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Credentials {
+    pub token: String,
+    /// The party's versions endpoint
+    pub url: String,
+    pub roles: Vec<CredentialsRole>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CredentialsRole {
+    /// CPO, EMSP, HUB, ...
+    pub role: String,
+    pub business_details: BusinessDetails,
+    pub party_id: String,
+    pub country_code: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BusinessDetails {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub website: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Location {
+    pub country_code: String,
+    pub party_id: String,
+    pub id: String,
+    /// False once the location is removed
+    pub publish: bool,
+    pub name: String,
+    pub address: String,
+    pub city: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub postal_code: Option<String>,
+    pub country: String,
+    pub coordinates: GeoLocation,
+    pub evses: Vec<Evse>,
+    pub operator: BusinessDetails,
+    pub time_zone: String,
+    #[serde(with = "timestamp")]
+    pub last_updated: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeoLocation {
+    pub latitude: String,
+    pub longitude: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Evse {
+    pub uid: String,
+    /// AVAILABLE, CHARGING, OUTOFORDER, RESERVED, REMOVED or UNKNOWN
+    pub status: String,
+    pub connectors: Vec<Connector>,
+    #[serde(with = "timestamp")]
+    pub last_updated: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Connector {
+    pub id: String,
+    pub standard: String,
+    /// SOCKET or CABLE
+    pub format: String,
+    /// AC_1_PHASE, AC_3_PHASE or DC
+    pub power_type: String,
+    /// Line to neutral for AC_3_PHASE
+    pub max_voltage: i32,
+    pub max_amperage: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_electric_power: Option<i32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tariff_ids: Vec<String>,
+    #[serde(with = "timestamp")]
+    pub last_updated: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tariff {
+    pub country_code: String,
+    pub party_id: String,
+    pub id: String,
+    pub currency: String,
+    pub elements: Vec<TariffElement>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_price: Option<Price>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_price: Option<Price>,
+    #[serde(with = "timestamp")]
+    pub last_updated: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TariffElement {
+    pub price_components: Vec<PriceComponent>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceComponent {
+    #[serde(rename = "type")]
+    pub component_type: String,
+    /// Excluding VAT
+    pub price: f64,
+    /// VAT percentage
+    pub vat: f64,
+    pub step_size: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Price {
+    pub excl_vat: f64,
+    pub incl_vat: f64,
+}
+
+/// OCPI connector standard of a `connector_types` name
+pub fn connector_standard(connector_type: &str) -> Option<&'static str> {
+    match connector_type {
+        "type1" => Some("IEC_62196_T1"),
+        "type2" => Some("IEC_62196_T2"),
+        "ccs" => Some("IEC_62196_T2_COMBO"),
+        "chademo" => Some("CHADEMO"),
+        "tesla" => Some("TESLA_S"),
+        "schuko" => Some("DOMESTIC_F"),
+        _ => None,
+    }
+}
+
+/// Sockets take the driver's cable; DC and type 1 chargers have their own
+pub fn connector_format(connector_type: &str, current_type: &str) -> &'static str {
+    match (connector_type, current_type) {
+        (_, "DC") | ("type1", _) | ("tesla", _) => "CABLE",
+        _ => "SOCKET",
+    }
+}
+
+/// Household and type 1 sockets are single phase, other AC ones three phase
+pub fn power_type(connector_type: &str, current_type: &str) -> &'static str {
+    match (connector_type, current_type) {
+        (_, "DC") => "DC",
+        ("schuko" | "type1", _) => "AC_1_PHASE",
+        _ => "AC_3_PHASE",
+    }
+}
+
+/// OCPI requires the voltage and amperage, which OSM often lacks: missing
+/// ones are taken from the power or the usual values of the power type.
+/// Returns the voltage (line to neutral for three phases) and amperage.
+fn voltage_and_amperage(power_type: &str, connector: &OcpiConnectorRow) -> (i32, i32) {
+    let (default_voltage, default_amperage, phases) = match power_type {
+        "AC_1_PHASE" => (230, 16, 1.0),
+        "AC_3_PHASE" => (230, 32, 3.0),
+        _ => (500, 125, 1.0),
+    };
+    // Three-phase voltages are usually recorded line to line
+    let voltage = match connector.voltage {
+        Some(v) if power_type == "AC_3_PHASE" && v > 300 => {
+            (f64::from(v) / 3f64.sqrt()).round() as i32
+        }
+        Some(v) if v > 0 => v,
+        _ => default_voltage,
+    };
+    let amperage = match (connector.amperage, connector.power_kw) {
+        (Some(a), _) if a > 0 => a,
+        (_, Some(kw)) if kw > 0.0 => (kw * 1000.0 / (phases * f64::from(voltage))).round() as i32,
+        _ => default_amperage,
+    };
+    (voltage, amperage)
+}
+
+/// Status of the `n`th (1-based) of a connector row's EVSEs
+fn evse_status(connector: &OcpiConnectorRow, n: i32) -> &'static str {
+    match connector.status.as_str() {
+        "available" | "occupied" if n <= connector.count_available => "AVAILABLE",
+        "available" | "occupied" => "CHARGING",
+        "faulty" => "OUTOFORDER",
+        "reserved" => "RESERVED",
+        _ => "UNKNOWN",
+    }
+}
+
+fn truncated(value: &str, max_chars: usize) -> String {
+    value.chars().take(max_chars).collect()
+}
+
+/// A station as OCPI location. Each connector row stands for `count_total`
+/// identical EVSEs of one connector, with uids `{connector_id}-{n}`.
+pub fn location(identity: &OcpiIdentity, station: &OcpiLocationRow) -> Location {
+    let evses = station
+        .connectors
+        .iter()
+        .filter_map(|connector| {
+            let standard = connector_standard(&connector.connector_type)?;
+            let power_type = power_type(&connector.connector_type, &connector.current_type);
+            let (max_voltage, max_amperage) = voltage_and_amperage(power_type, connector);
+            let removed = station.removed || connector.removed;
+            let last_updated = if station.removed {
+                station.last_updated
+            } else {
+                connector.last_updated
+            };
+
+            Some((1..=connector.count_total).map(move |n| {
+                Evse {
+                    uid: format!("{}-{}", connector.connector_id, n),
+                    status: if removed {
+                        "REMOVED"
+                    } else {
+                        evse_status(connector, n)
+                    }
+                    .to_string(),
+                    connectors: vec![Connector {
+                        id: "1".to_string(),
+                        standard: standard.to_string(),
+                        format: connector_format(
+                            &connector.connector_type,
+                            &connector.current_type,
+                        )
+                        .to_string(),
+                        power_type: power_type.to_string(),
+                        max_voltage,
+                        max_amperage,
+                        max_electric_power: connector
+                            .power_kw
+                            .map(|kw| (kw * 1000.0).round() as i32),
+                        tariff_ids: station.tariff_id.iter().cloned().collect(),
+                        last_updated,
+                    }],
+                    last_updated,
+                }
+            }))
+        })
+        .flatten()
+        .collect();
+
+    Location {
+        country_code: identity.country_code.clone(),
+        party_id: identity.party_id.clone(),
+        id: station.station_id.clone(),
+        publish: !station.removed,
+        name: truncated(&station.name, 255),
+        address: truncated(
+            station
+                .address
+                .as_deref()
+                .or(station.city.as_deref())
+                .unwrap_or_default(),
+            45,
+        ),
+        city: truncated(station.city.as_deref().unwrap_or_default(), 45),
+        postal_code: station.postal_code.as_deref().map(|p| truncated(p, 10)),
+        country: identity.location_country.clone(),
+        coordinates: GeoLocation {
+            latitude: format!("{:.6}", station.latitude),
+            longitude: format!("{:.6}", station.longitude),
+        },
+        evses,
+        operator: BusinessDetails {
+            name: station.operator_name.clone(),
+            website: None,
+        },
+        time_zone: identity.time_zone.clone(),
+        last_updated: station.last_updated,
+    }
+}
+
+/// A tariff as OCPI tariff; our component types and units are OCPI's
+pub fn tariff(identity: &OcpiIdentity, tariff: &OcpiTariffRow) -> Tariff {
+    let incl_vat = |excl_vat: f64| Price {
+        excl_vat,
+        incl_vat: (excl_vat * (100.0 + tariff.vat_percent) * 10.0).round() / 1000.0,
+    };
+
+    Tariff {
+        country_code: identity.country_code.clone(),
+        party_id: identity.party_id.clone(),
+        id: tariff.tariff_id.clone(),
+        currency: tariff.currency.clone(),
+        elements: vec![TariffElement {
+            price_components: tariff
+                .components
+                .iter()
+                .map(|component| PriceComponent {
+                    component_type: component.component_type.clone(),
+                    price: component.price,
+                    vat: tariff.vat_percent,
+                    step_size: component.step_size,
+                })
+                .collect(),
+        }],
+        min_price: tariff.min_price.map(incl_vat),
+        max_price: tariff.max_price.map(incl_vat),
+        last_updated: tariff.last_updated,
+    }
+}
@@ -0,0 +1,189 @@
+use super::osm_tags::{parse_count, parse_power_kw};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Ways are staged as this offset plus their id, so that a way never takes
+/// the osm_id of a node
+pub const WAY_OSM_ID_OFFSET: i64 = 1_000_000_000_000_000;
+
+/// A row of `osm_charging_stations_temp`
+#[derive(Debug, Clone)]
+pub struct StagedOsmStation {
+    pub osm_id: i64,
+    pub name: Option<String>,
+    pub address: Option<String>,
+    pub longitude: f64,
+    pub latitude: f64,
+    pub operator: Option<String>,
+    pub opening_hours: Option<String>,
+    pub capacity: Option<i32>,
+    pub fee: Option<String>,
+    pub parking_fee: Option<String>,
+    pub access: Option<String>,
+    pub socket_type2: Option<i32>,
+    pub socket_ccs: Option<i32>,
+    pub socket_chademo: Option<i32>,
+    pub socket_type2_output: Option<f64>,
+    pub socket_ccs_output: Option<f64>,
+    pub socket_chademo_output: Option<f64>,
+    /// Every tag of the element
+    pub tags: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct OsmImportReport {
+    pub station_nodes: usize,
+    pub station_ways: usize,
+    /// Ways none of whose nodes are in the file
+    pub skipped_ways: usize,
+    pub staged: usize,
+    /// Replication sequence number of the extract, from its header
+    pub sequence_number: Option<i64>,
+}
+
+/// What an osmChange diff does to `osm_charging_stations_temp`
+#[derive(Debug, Clone, Default)]
+pub struct OsmStagingChanges {
+    /// Created or modified charging stations
+    pub upserts: Vec<StagedOsmStation>,
+    /// Deleted elements and elements not tagged as a charging station. Those
+    /// that were staged are removed and their stations deactivated.
+    pub removed_osm_ids: Vec<i64>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct OsmDiffReport {
+    pub sequence_number: i64,
+    pub upserted: usize,
+    /// Staged stations the diff deleted or untagged
+    pub removed: usize,
+    /// New charging station ways whose nodes are neither in the diff nor
+    /// staged
+    pub skipped_ways: usize,
+    pub deactivated: i32,
+}
+
+pub fn is_charging_station(tags: &[(&str, &str)]) -> bool {
+    tags.iter()
+        .any(|(key, value)| *key == "amenity" && *value == "charging_station")
+}
+
+/// Sequence number of a diff named after the replication server layout,
+/// `.../000/004/321.osc.gz` being 4321
+pub fn sequence_from_path(path: &Path) -> Option<i64> {
+    let file = path.file_name()?.to_str()?;
+    let last = file
+        .strip_suffix(".osc.gz")
+        .or_else(|| file.strip_suffix(".osc"))?;
+    let parent = path.parent()?;
+    let middle = parent.file_name()?.to_str()?;
+    let first = parent.parent()?.file_name()?.to_str()?;
+
+    let mut sequence: i64 = 0;
+    for part in [first, middle, last] {
+        if part.len() != 3 || !part.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        sequence = sequence * 1000 + part.parse::<i64>().ok()?;
+    }
+    Some(sequence)
+}
+
+/// The staging row of a charging station node or way
+pub fn staged_station(
+    osm_id: i64,
+    longitude: f64,
+    latitude: f64,
+    tags: &[(&str, &str)],
+) -> StagedOsmStation {
+    let tags: BTreeMap<&str, &str> = tags.iter().copied().collect();
+    let text = |key: &str| tags.get(key).map(|v| v.to_string());
+    let first_of = |keys: &[&str]| keys.iter().find_map(|key| tags.get(key).copied());
+
+    StagedOsmStation {
+        osm_id,
+        name: text("name"),
+        address: address(&tags),
+        longitude,
+        latitude,
+        operator: text("operator"),
+        opening_hours: text("opening_hours"),
+        capacity: tags.get("capacity").and_then(|v| v.trim().parse().ok()),
+        fee: text("fee"),
+        parking_fee: text("parking:fee"),
+        access: text("access"),
+        socket_type2: tags.get("socket:type2").and_then(|v| parse_count(v)),
+        socket_ccs: first_of(&["socket:type2_combo", "socket:ccs"]).and_then(parse_count),
+        socket_chademo: tags.get("socket:chademo").and_then(|v| parse_count(v)),
+        socket_type2_output: tags
+            .get("socket:type2:output")
+            .and_then(|v| parse_power_kw(v)),
+        socket_ccs_output: first_of(&["socket:type2_combo:output", "socket:ccs:output"])
+            .and_then(parse_power_kw),
+        socket_chademo_output: tags
+            .get("socket:chademo:output")
+            .and_then(|v| parse_power_kw(v)),
+        tags: tags
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect(),
+    }
+}
+
+/// `addr:full`, or `<housenumber> <street>, <postcode> <city>` from the
+/// parts that are tagged
+fn address(tags: &BTreeMap<&str, &str>) -> Option<String> {
+    if let Some(full) = tags.get("addr:full") {
+        return Some(full.to_string());
+    }
+
+    let join = |parts: [Option<&&str>; 2]| {
+        let parts: Vec<&str> = parts.into_iter().flatten().copied().collect();
+        (!parts.is_empty()).then(|| parts.join(" "))
+    };
+    let street = join([tags.get("addr:housenumber"), tags.get("addr:street")]);
+    let place = join([tags.get("addr:postcode"), tags.get("addr:city")]);
+
+    match (street, place) {
+        (Some(street), Some(place)) => Some(format!("{}, {}", street, place)),
+        (street, place) => street.or(place),
+    }
+}
+
+/// Centroid of a way from its node coordinates (longitude, latitude): the
+/// area centroid of a closed way, the mean of the nodes otherwise
+pub fn way_centroid(points: &[(f64, f64)]) -> Option<(f64, f64)> {
+    let closed = points.len() >= 4 && points.first() == points.last();
+    if closed {
+        // Relative to the first node, as the areas of station sized ways are
+        // lost in the rounding of absolute coordinates
+        let (ox, oy) = points[0];
+        let mut area = 0.0;
+        let (mut x, mut y) = (0.0, 0.0);
+        for pair in points.windows(2) {
+            let (x0, y0) = (pair[0].0 - ox, pair[0].1 - oy);
+            let (x1, y1) = (pair[1].0 - ox, pair[1].1 - oy);
+            let cross = x0 * y1 - x1 * y0;
+            area += cross;
+            x += (x0 + x1) * cross;
+            y += (y0 + y1) * cross;
+        }
+        if area != 0.0 {
+            return Some((ox + x / (3.0 * area), oy + y / (3.0 * area)));
+        }
+    }
+
+    let distinct = if closed {
+        &points[..points.len() - 1]
+    } else {
+        points
+    };
+    if distinct.is_empty() {
+        return None;
+    }
+    let count = distinct.len() as f64;
+    let (x, y) = distinct
+        .iter()
+        .fold((0.0, 0.0), |(x, y), (px, py)| (x + px, y + py));
+    Some((x / count, y / count))
+}
@@ -0,0 +1,503 @@
+use super::entities::OsmStationConnector;
+use std::collections::BTreeMap;
+
+/// A connector described by a station's `socket:*` tags, named after the
+/// `connector_types` and `current_types` rows it maps to
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaggedConnector {
+    pub connector_type: &'static str,
+    pub current_type: &'static str,
+    pub power_kw: f64,
+    pub voltage: Option<i32>,
+    pub amperage: Option<i32>,
+    pub count_total: i32,
+}
+
+impl TaggedConnector {
+    /// The connector as staged for the station with the osm_id
+    pub fn for_station(self, osm_id: i64) -> OsmStationConnector {
+        OsmStationConnector {
+            osm_id,
+            connector_type: self.connector_type.to_string(),
+            current_type: self.current_type.to_string(),
+            power_kw: Some(self.power_kw),
+            voltage: self.voltage,
+            amperage: self.amperage,
+            count_total: Some(self.count_total),
+        }
+    }
+}
+
+/// An OSM socket key, `socket:<key>`, and what it maps to
+struct Socket {
+    key: &'static str,
+    connector_type: &'static str,
+    current_type: &'static str,
+    /// Used when neither `:output` nor `:voltage` and `:current` are tagged
+    default_power_kw: f64,
+}
+
+const SOCKETS: &[Socket] = &[
+    Socket {
+        key: "type1",
+        connector_type: "type1",
+        current_type: "AC",
+        default_power_kw: 7.4,
+    },
+    Socket {
+        key: "type1_combo",
+        connector_type: "ccs",
+        current_type: "DC",
+        default_power_kw: 50.0,
+    },
+    Socket {
+        key: "type2",
+        connector_type: "type2",
+        current_type: "AC",
+        default_power_kw: 22.0,
+    },
+    Socket {
+        key: "type2_cable",
+        connector_type: "type2",
+        current_type: "AC",
+        default_power_kw: 22.0,
+    },
+    Socket {
+        key: "type2_combo",
+        connector_type: "ccs",
+        current_type: "DC",
+        default_power_kw: 50.0,
+    },
+    // Not an OSM key, but older imports wrote it
+    Socket {
+        key: "ccs",
+        connector_type: "ccs",
+        current_type: "DC",
+        default_power_kw: 50.0,
+    },
+    Socket {
+        key: "chademo",
+        connector_type: "chademo",
+        current_type: "DC",
+        default_power_kw: 50.0,
+    },
+    Socket {
+        key: "tesla_supercharger",
+        connector_type: "tesla",
+        current_type: "DC",
+        default_power_kw: 150.0,
+    },
+    Socket {
+        key: "tesla_supercharger_ccs",
+        connector_type: "ccs",
+        current_type: "DC",
+        default_power_kw: 150.0,
+    },
+    Socket {
+        key: "tesla_destination",
+        connector_type: "tesla",
+        current_type: "AC",
+        default_power_kw: 11.0,
+    },
+    Socket {
+        key: "tesla_standard",
+        connector_type: "tesla",
+        current_type: "AC",
+        default_power_kw: 11.0,
+    },
+    Socket {
+        key: "schuko",
+        connector_type: "schuko",
+        current_type: "AC",
+        default_power_kw: 3.7,
+    },
+];
+
+/// `connectors.power_kw` is DECIMAL(5,2)
+const MAX_POWER_KW: f64 = 999.99;
+const MAX_VOLTAGE: f64 = 2000.0;
+const MAX_AMPERAGE: f64 = 1000.0;
+const MAX_SOCKET_COUNT: i32 = 1000;
+
+/// Connectors described by the tags. Sockets that map to the same connector
+/// and current type are merged: counts add up, power, voltage and current
+/// take the highest value. Unparsable `:output`, `:voltage` and `:current`
+/// values are ignored rather than failing the station.
+pub fn connectors_from_tags(tags: &BTreeMap<&str, &str>) -> Vec<TaggedConnector> {
+    let mut connectors: Vec<TaggedConnector> = Vec::new();
+
+    for socket in SOCKETS {
+        let prefix = format!("socket:{}", socket.key);
+        let Some(count) = tags.get(prefix.as_str()).and_then(|v| parse_count(v)) else {
+            continue;
+        };
+        let attribute = |name: &str| tags.get(format!("{}:{}", prefix, name).as_str()).copied();
+
+        let voltage = attribute("voltage").and_then(parse_voltage);
+        let amperage = attribute("current").and_then(parse_amperage);
+        let power_kw = attribute("output")
+            .and_then(parse_power_kw)
+            .or_else(|| power_from_electrics(socket.current_type, voltage?, amperage?))
+            .unwrap_or(socket.default_power_kw);
+
+        let existing = connectors.iter_mut().find(|c| {
+            c.connector_type == socket.connector_type && c.current_type == socket.current_type
+        });
+        match existing {
+            Some(c) => {
+                c.count_total = (c.count_total + count).min(MAX_SOCKET_COUNT);
+                c.power_kw = c.power_kw.max(power_kw);
+                c.voltage = c.voltage.max(voltage);
+                c.amperage = c.amperage.max(amperage);
+            }
+            None => connectors.push(TaggedConnector {
+                connector_type: socket.connector_type,
+                current_type: socket.current_type,
+                power_kw,
+                voltage,
+                amperage,
+                count_total: count,
+            }),
+        }
+    }
+
+    connectors
+}
+
+/// `socket:*` values: a count, `yes` for an unknown number, or counts per
+/// charge point separated by `;`
+pub fn parse_count(value: &str) -> Option<i32> {
+    let value = value.trim();
+    if value.eq_ignore_ascii_case("yes") {
+        return Some(1);
+    }
+
+    let mut total: i32 = 0;
+    for part in value.split(';') {
+        let count = part.trim().parse::<u16>().ok()?;
+        total = total.checked_add(i32::from(count))?;
+    }
+    (1..=MAX_SOCKET_COUNT).contains(&total).then_some(total)
+}
+
+/// `:output` values such as `22`, `22 kW`, `3,7kW`, `22000 W`, `2x22 kW`
+/// or `50 kW;150 kW`. Lists give the highest power.
+pub fn parse_power_kw(value: &str) -> Option<f64> {
+    let kw = quantities(value)
+        .filter_map(|(number, unit)| match unit.as_str() {
+            // Bare numbers are kW unless they only make sense as watts
+            "" if number >= 1000.0 => Some(number / 1000.0),
+            "" | "kw" | "kva" => Some(number),
+            "w" | "va" => Some(number / 1000.0),
+            "mw" => Some(number * 1000.0),
+            _ => None,
+        })
+        .filter(|kw| *kw > 0.0 && *kw <= MAX_POWER_KW)
+        .fold(None, |max: Option<f64>, kw| {
+            Some(max.map_or(kw, |m| m.max(kw)))
+        })?;
+    // A few watts round down to nothing
+    let kw = (kw * 100.0).round() / 100.0;
+    (kw > 0.0).then_some(kw)
+}
+
+/// `:voltage` values such as `400`, `230 V` or `230;400`
+fn parse_voltage(value: &str) -> Option<i32> {
+    highest(value, &["", "v"], MAX_VOLTAGE)
+}
+
+/// `:current` values such as `32`, `32 A` or `16;32`
+fn parse_amperage(value: &str) -> Option<i32> {
+    highest(value, &["", "a"], MAX_AMPERAGE)
+}
+
+fn highest(value: &str, units: &[&str], max: f64) -> Option<i32> {
+    quantities(value)
+        .filter(|(number, unit)| units.contains(&unit.as_str()) && *number > 0.0 && *number <= max)
+        .map(|(number, _)| number.round() as i32)
+        .max()
+}
+
+/// Power of a socket from its tagged voltage and current, three-phase for AC
+/// from 380 V
+fn power_from_electrics(current_type: &str, voltage: i32, amperage: i32) -> Option<f64> {
+    let watts = f64::from(voltage) * f64::from(amperage);
+    let watts = if current_type == "AC" && voltage >= 380 {
+        watts * 3f64.sqrt()
+    } else {
+        watts
+    };
+    let kw = (watts / 10.0).round() / 100.0;
+    (kw > 0.0 && kw <= MAX_POWER_KW).then_some(kw)
+}
+
+/// Every number in the value with the lowercase unit right after it.
+/// A comma between digits is a decimal separator, and a number followed by
+/// `x` is a multiplier (`2x22 kW`), which is skipped.
+fn quantities(value: &str) -> impl Iterator<Item = (f64, String)> + '_ {
+    let chars: Vec<char> = value.chars().collect();
+    let mut found = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        if !chars[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let mut number = String::new();
+        while i < chars.len() {
+            let c = chars[i];
+            let decimal_point = (c == '.' || c == ',')
+                && !number.contains('.')
+                && chars.get(i + 1).is_some_and(|n| n.is_ascii_digit());
+            if c.is_ascii_digit() {
+                number.push(c);
+            } else if decimal_point {
+                number.push('.');
+            } else {
+                break;
+            }
+            i += 1;
+        }
+
+        while i < chars.len() && chars[i] == ' ' {
+            i += 1;
+        }
+        let mut unit = String::new();
+        while i < chars.len() && chars[i].is_alphabetic() {
+            unit.extend(chars[i].to_lowercase());
+            i += 1;
+        }
+
+        if unit == "x" || unit == "×" {
+            continue;
+        }
+        if let Ok(number) = number.parse::<f64>() {
+            found.push((number, unit));
+        }
+    }
+
+    found.into_iter()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn connectors(tags: &[(&str, &str)]) -> Vec<TaggedConnector> {
+        connectors_from_tags(&tags.iter().copied().collect())
+    }
+
+    #[test]
+    fn parses_power_samples() {
+        let samples = [
+            ("22", Some(22.0)),
+            ("22 kW", Some(22.0)),
+            ("22kW", Some(22.0)),
+            ("22 KW", Some(22.0)),
+            ("3,7kW", Some(3.7)),
+            ("3.7 kW", Some(3.7)),
+            ("22000 W", Some(22.0)),
+            ("22000", Some(22.0)),
+            ("11 kVA", Some(11.0)),
+            ("2x22 kW", Some(22.0)),
+            ("2 x 22 kW", Some(22.0)),
+            ("2×22 kW", Some(22.0)),
+            ("50kW;150 kW", Some(150.0)),
+            ("50 kW; 150 kW", Some(150.0)),
+            ("0.35 MW", Some(350.0)),
+            ("7.36 kW", Some(7.36)),
+            ("7.356 kW", Some(7.36)),
+            ("yes", None),
+            ("unknown", None),
+            ("", None),
+            ("0 kW", None),
+            ("22 A", None),
+            ("2 MW", None),
+            ("1 W", None),
+        ];
+
+        for (value, expected) in samples {
+            assert_eq!(parse_power_kw(value), expected, "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn parses_count_samples() {
+        let samples = [
+            ("1", Some(1)),
+            ("2", Some(2)),
+            (" 4 ", Some(4)),
+            ("yes", Some(1)),
+            ("YES", Some(1)),
+            ("1;2", Some(3)),
+            ("2; 2; 1", Some(5)),
+            ("no", None),
+            ("0", None),
+            ("", None),
+            ("1;", None),
+            ("two", None),
+            ("-1", None),
+            ("-1;2", None),
+            ("1001", None),
+            ("99999999999", None),
+        ];
+
+        for (value, expected) in samples {
+            assert_eq!(parse_count(value), expected, "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn parses_voltage_and_amperage_samples() {
+        assert_eq!(parse_voltage("230;400"), Some(400));
+        assert_eq!(parse_voltage("230 V"), Some(230));
+        assert_eq!(parse_voltage("5000"), None);
+        assert_eq!(parse_amperage("16;32 A"), Some(32));
+        assert_eq!(parse_amperage("32 V"), None);
+    }
+
+    #[test]
+    fn maps_sockets_with_defaults() {
+        assert_eq!(
+            connectors(&[("socket:type2", "2"), ("socket:chademo", "yes")]),
+            vec![
+                TaggedConnector {
+                    connector_type: "type2",
+                    current_type: "AC",
+                    power_kw: 22.0,
+                    voltage: None,
+                    amperage: None,
+                    count_total: 2,
+                },
+                TaggedConnector {
+                    connector_type: "chademo",
+                    current_type: "DC",
+                    power_kw: 50.0,
+                    voltage: None,
+                    amperage: None,
+                    count_total: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn prefers_output_then_electrics() {
+        let tagged = connectors(&[
+            ("socket:type2", "1"),
+            ("socket:type2:output", "11 kW"),
+            ("socket:type2:voltage", "400"),
+            ("socket:type2:current", "32"),
+            ("socket:schuko", "1"),
+            ("socket:schuko:voltage", "230"),
+            ("socket:schuko:current", "16"),
+        ]);
+
+        assert_eq!(tagged[0].power_kw, 11.0);
+        assert_eq!(tagged[0].voltage, Some(400));
+        assert_eq!(tagged[0].amperage, Some(32));
+        assert_eq!(tagged[1].connector_type, "schuko");
+        assert_eq!(tagged[1].power_kw, 3.68);
+    }
+
+    #[test]
+    fn three_phase_power_from_electrics() {
+        let tagged = connectors(&[
+            ("socket:type2", "1"),
+            ("socket:type2:voltage", "400"),
+            ("socket:type2:current", "32"),
+        ]);
+        assert_eq!(tagged[0].power_kw, 22.17);
+    }
+
+    #[test]
+    fn merges_sockets_of_the_same_connector() {
+        let tagged = connectors(&[
+            ("socket:type2_combo", "2"),
+            ("socket:type2_combo:output", "50 kW"),
+            ("socket:tesla_supercharger_ccs", "4"),
+            ("socket:tesla_supercharger_ccs:output", "250 kW"),
+        ]);
+
+        assert_eq!(tagged.len(), 1);
+        assert_eq!(tagged[0].connector_type, "ccs");
+        assert_eq!(tagged[0].count_total, 6);
+        assert_eq!(tagged[0].power_kw, 250.0);
+    }
+
+    #[test]
+    fn ignores_unparsable_values() {
+        let tagged = connectors(&[
+            ("socket:type2", "many"),
+            ("socket:chademo", "1"),
+            ("socket:chademo:output", "fast"),
+            ("socket:chademo:voltage", "high"),
+        ]);
+
+        assert_eq!(tagged.len(), 1);
+        assert_eq!(tagged[0].power_kw, 50.0);
+        assert_eq!(tagged[0].voltage, None);
+    }
+
+    /// Values as found in OSM, plus whatever else
+    fn tag_value() -> impl Strategy<Value = String> {
+        prop_oneof![
+            "[0-9 .,;xX×kKwWmMvVaAyes+-]{0,24}",
+            "[0-9]{1,40}",
+            any::<String>(),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn power_stays_in_range(value in tag_value()) {
+            if let Some(kw) = parse_power_kw(&value) {
+                prop_assert!(kw > 0.0 && kw <= MAX_POWER_KW, "{} from {:?}", kw, value);
+            }
+        }
+
+        #[test]
+        fn counts_stay_capped(value in tag_value()) {
+            if let Some(count) = parse_count(&value) {
+                prop_assert!((1..=MAX_SOCKET_COUNT).contains(&count), "{} from {:?}", count, value);
+            }
+        }
+
+        #[test]
+        fn electrics_stay_in_range(value in tag_value()) {
+            if let Some(voltage) = parse_voltage(&value) {
+                prop_assert!(voltage > 0 && f64::from(voltage) <= MAX_VOLTAGE);
+            }
+            if let Some(amperage) = parse_amperage(&value) {
+                prop_assert!(amperage > 0 && f64::from(amperage) <= MAX_AMPERAGE);
+            }
+        }
+
+        #[test]
+        fn connectors_stay_in_range(
+            values in proptest::collection::vec(
+                (0..SOCKETS.len(), prop_oneof!["", ":output", ":voltage", ":current"], tag_value()),
+                0..24,
+            )
+        ) {
+            let keys: Vec<(String, String)> = values
+                .into_iter()
+                .map(|(socket, attribute, value)| {
+                    (format!("socket:{}{}", SOCKETS[socket].key, attribute), value)
+                })
+                .collect();
+            let tags: BTreeMap<&str, &str> =
+                keys.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+
+            for connector in connectors_from_tags(&tags) {
+                prop_assert!((1..=MAX_SOCKET_COUNT).contains(&connector.count_total));
+                prop_assert!(connector.power_kw > 0.0 && connector.power_kw <= MAX_POWER_KW);
+                prop_assert!(connector.voltage.is_none_or(|v| v > 0 && f64::from(v) <= MAX_VOLTAGE));
+                prop_assert!(connector.amperage.is_none_or(|a| a > 0 && f64::from(a) <= MAX_AMPERAGE));
+            }
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use crate::core::errors::AppError;
+use std::str::FromStr;
+
+/// Rows of the `data_sources` table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataSource {
+    Osm,
+    UserSubmitted,
+    Commercial,
+}
+
+impl DataSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Osm => "osm",
+            Self::UserSubmitted => "user_submitted",
+            Self::Commercial => "commercial",
+        }
+    }
+}
+
+impl FromStr for DataSource {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "osm" => Ok(Self::Osm),
+            "user_submitted" => Ok(Self::UserSubmitted),
+            "commercial" => Ok(Self::Commercial),
+            other => Err(AppError::ValidationError(format!(
+                "Unknown data source: {}, expected osm, user_submitted or commercial",
+                other
+            ))),
+        }
+    }
+}
+
+/// Station fields whose provenance is tracked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StationField {
+    Name,
+    Address,
+    Location,
+    Tags,
+}
+
+impl StationField {
+    pub const ALL: [Self; 4] = [Self::Name, Self::Address, Self::Location, Self::Tags];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::Address => "address",
+            Self::Location => "location",
+            Self::Tags => "tags",
+        }
+    }
+}
+
+impl FromStr for StationField {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "name" => Ok(Self::Name),
+            "address" => Ok(Self::Address),
+            "location" => Ok(Self::Location),
+            "tags" => Ok(Self::Tags),
+            other => Err(AppError::ValidationError(format!(
+                "Unknown station field: {}, expected name, address, location or tags",
+                other
+            ))),
+        }
+    }
+}
@@ -1,6 +1,23 @@
-use super::entities::{Connector, Network, Station};
+use super::deleted::{DeletedRecordType, PurgeCounts};
+use super::entities::{
+    Connector, ConnectorType, CurrentType, DeletedRecord, DuplicateCandidate, ModerationQueueEntry,
+    Network, NetworkVerificationRequest, OcpiLocationRow, OcpiParty, OcpiTariffRow,
+    OsmRemovedStation, OsmStagedStation, OsmStagedTags, OsmStationConnector, Region,
+    RegionStatistics, Station, StationExportRow, StationFieldSource, StationMerge, SyncConflict,
+    SyncRun, UserReview, VerificationDocument, VerificationEvent,
+};
+use super::export::ExportFilter;
+use super::import::PlannedStation;
+use super::moderation::ModerationStatus;
+use super::osm_import::{OsmStagingChanges, StagedOsmStation};
+use super::provenance::{DataSource, StationField};
+use super::sync::{SyncCounts, SyncStatus, SyncTrigger};
+use super::value_objects::{OcpiRegistrationData, RegionBoundary};
+use super::verification::{VerificationNotification, VerificationStatus};
 use crate::core::errors::AppResult;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures_util::stream::BoxStream;
 
 #[async_trait]
 pub trait NetworkRepository: Send + Sync {
@@ -8,7 +25,11 @@ pub trait NetworkRepository: Send + Sync {
     async fn find_by_id(&self, network_id: &str) -> AppResult<Option<Network>>;
     async fn find_all(&self, limit: i64, offset: i64) -> AppResult<Vec<Network>>;
     async fn update(&self, network: &Network) -> AppResult<Network>;
-    async fn delete(&self, network_id: &str) -> AppResult<()>;
+    /// Marks the network deleted, or fails with a conflict while stations
+    /// still use it
+    async fn delete(&self, network_id: &str, deleted_by: &str) -> AppResult<()>;
+    /// Brings back a deleted network, None if there is none with this id
+    async fn restore(&self, network_id: &str, restored_by: &str) -> AppResult<Option<Network>>;
     async fn count(&self) -> AppResult<i64>;
 }
 
@@ -16,16 +37,23 @@ pub trait NetworkRepository: Send + Sync {
 pub trait StationRepository: Send + Sync {
     async fn create(&self, station: &Station) -> AppResult<Station>;
     async fn find_by_id(&self, station_id: &str) -> AppResult<Option<Station>>;
-    async fn find_by_network(
+    /// Stations of the network and region, when given
+    async fn find_all(
         &self,
-        network_id: &str,
+        network_id: Option<&str>,
+        region_id: Option<&str>,
         limit: i64,
         offset: i64,
     ) -> AppResult<Vec<Station>>;
-    async fn find_all(&self, limit: i64, offset: i64) -> AppResult<Vec<Station>>;
     async fn update(&self, station: &Station) -> AppResult<Station>;
-    async fn delete(&self, station_id: &str) -> AppResult<()>;
-    async fn count(&self) -> AppResult<i64>;
+    /// Marks the station and its connectors deleted and refreshes the
+    /// station views
+    async fn delete(&self, station_id: &str, deleted_by: &str) -> AppResult<()>;
+    /// Brings back a deleted station with the connectors deleted along with
+    /// it, None if there is none with this id. Fails with a conflict while
+    /// its network is deleted.
+    async fn restore(&self, station_id: &str, restored_by: &str) -> AppResult<Option<Station>>;
+    async fn count(&self, network_id: Option<&str>, region_id: Option<&str>) -> AppResult<i64>;
 }
 
 #[async_trait]
@@ -35,6 +63,362 @@ pub trait ConnectorRepository: Send + Sync {
     async fn find_by_station(&self, station_id: &str) -> AppResult<Vec<Connector>>;
     async fn find_all(&self, limit: i64, offset: i64) -> AppResult<Vec<Connector>>;
     async fn update(&self, connector: &Connector) -> AppResult<Connector>;
-    async fn delete(&self, connector_id: &str) -> AppResult<()>;
+    /// Marks the connector deleted and refreshes the station views
+    async fn delete(&self, connector_id: &str, deleted_by: &str) -> AppResult<()>;
+    /// Brings back a deleted connector, None if there is none with this id.
+    /// Fails with a conflict while its station is deleted or has a connector
+    /// of the same type.
+    async fn restore(&self, connector_id: &str, restored_by: &str) -> AppResult<Option<Connector>>;
     async fn count(&self) -> AppResult<i64>;
 }
+
+#[async_trait]
+pub trait ImportRepository: Send + Sync {
+    async fn find_connector_types(&self) -> AppResult<Vec<ConnectorType>>;
+    async fn find_current_types(&self) -> AppResult<Vec<CurrentType>>;
+    /// The ids among `network_ids` of networks that are not deleted
+    async fn find_existing_networks(&self, network_ids: &[String]) -> AppResult<Vec<String>>;
+    /// The ids among `osm_ids` already used by a station, deleted ones
+    /// included
+    async fn find_existing_osm_ids(&self, osm_ids: &[i64]) -> AppResult<Vec<i64>>;
+    /// (network_id, external_ref) of stations whose ref is among `external_refs`
+    async fn find_existing_refs(
+        &self,
+        external_refs: &[String],
+    ) -> AppResult<Vec<(String, String)>>;
+    /// Creates the stations and their connectors in one transaction
+    async fn import_stations(&self, stations: &[PlannedStation], created_by: &str)
+    -> AppResult<()>;
+}
+
+#[async_trait]
+pub trait OsmStagingRepository: Send + Sync {
+    /// Replaces the content of `osm_charging_stations_temp` in one
+    /// transaction, and resets the replication state to `sequence_number`
+    async fn replace_staged_stations(
+        &self,
+        stations: &[StagedOsmStation],
+        sequence_number: Option<i64>,
+    ) -> AppResult<()>;
+    async fn has_staged_stations(&self) -> AppResult<bool>;
+    /// Sequence number of the last applied diff, or of the imported extract
+    async fn find_replication_sequence(&self) -> AppResult<Option<i64>>;
+    /// (osm_id, longitude, latitude) of the staged stations among `osm_ids`
+    async fn find_staged_locations(&self, osm_ids: &[i64]) -> AppResult<Vec<(i64, f64, f64)>>;
+    /// Applies the changes of a diff to the staging table, deactivates the
+    /// stations of removed OSM elements and records the sequence number, in
+    /// one transaction. Returns the number of removed staged stations and of
+    /// deactivated stations.
+    async fn apply_staged_changes(
+        &self,
+        changes: &OsmStagingChanges,
+        sequence_number: i64,
+    ) -> AppResult<(usize, i32)>;
+}
+
+#[async_trait]
+pub trait RegionRepository: Send + Sync {
+    /// A validation error when the OSM boundary is not in the import or the
+    /// GeoJSON is not a polygon
+    async fn create(&self, region: &Region, boundary: &RegionBoundary) -> AppResult<Region>;
+    async fn find_by_id(&self, region_id: &str) -> AppResult<Option<Region>>;
+    async fn find_all(&self) -> AppResult<Vec<Region>>;
+    async fn delete(&self, region_id: &str) -> AppResult<()>;
+    /// Moves the stations into the regions containing them after regions
+    /// changed; returns the number of stations that moved
+    async fn assign_station_regions(&self) -> AppResult<i32>;
+    async fn statistics(&self) -> AppResult<Vec<RegionStatistics>>;
+}
+
+#[async_trait]
+pub trait StationMergeRepository: Send + Sync {
+    /// Pairs of active stations within `max_distance_meters` scoring at
+    /// least `min_score`, best first. Dismissed pairs are left out.
+    async fn find_candidates(
+        &self,
+        max_distance_meters: f64,
+        min_score: f64,
+        region_id: Option<&str>,
+        limit: i64,
+    ) -> AppResult<Vec<DuplicateCandidate>>;
+    /// Keeps the pair out of the candidates
+    async fn dismiss(
+        &self,
+        station_id_a: &str,
+        station_id_b: &str,
+        user_id: Option<&str>,
+    ) -> AppResult<()>;
+    /// Moves everything of the merged station onto the surviving one,
+    /// deletes it and refreshes the station views
+    async fn merge(
+        &self,
+        merge_id: &str,
+        surviving_station_id: &str,
+        merged_station_id: &str,
+        user_id: Option<&str>,
+    ) -> AppResult<StationMerge>;
+    /// Most recent first; with a station, the merges into it
+    async fn find_merges(
+        &self,
+        station_id: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<StationMerge>>;
+}
+
+#[async_trait]
+pub trait DeletedRecordRepository: Send + Sync {
+    /// Most recently deleted first. Connectors deleted along with their
+    /// station are left out.
+    async fn find_all(
+        &self,
+        record_type: Option<DeletedRecordType>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<DeletedRecord>>;
+    async fn count(&self, record_type: Option<DeletedRecordType>) -> AppResult<i64>;
+    /// Removes the records deleted before `deleted_before` for good
+    async fn purge(&self, deleted_before: DateTime<Utc>) -> AppResult<PurgeCounts>;
+}
+
+pub trait ExportRepository: Send + Sync {
+    /// Matching stations joined with their connectors, ordered by station,
+    /// fetched as the stream is consumed
+    fn stream_stations(
+        &self,
+        filter: ExportFilter,
+    ) -> BoxStream<'static, AppResult<StationExportRow>>;
+}
+
+#[async_trait]
+pub trait SyncRunRepository: Send + Sync {
+    /// Records a new running run, or fails with a conflict if one is running
+    async fn start_run(
+        &self,
+        run_id: &str,
+        trigger: SyncTrigger,
+        triggered_by: Option<&str>,
+        skipped_osm_ids: &[i64],
+        region_id: Option<&str>,
+    ) -> AppResult<SyncRun>;
+    async fn finish_run(
+        &self,
+        run_id: &str,
+        status: SyncStatus,
+        counts: Option<SyncCounts>,
+        error: Option<&str>,
+    ) -> AppResult<SyncRun>;
+    /// Records that the process executing the run is still alive
+    async fn touch_run(&self, run_id: &str) -> AppResult<()>;
+    /// Marks running runs without a heartbeat for `stale_seconds` as failed,
+    /// as their process died. Runs of live processes are left alone.
+    async fn fail_interrupted_runs(&self, stale_seconds: i64) -> AppResult<u64>;
+    async fn find_by_id(&self, run_id: &str) -> AppResult<Option<SyncRun>>;
+    async fn find_all(
+        &self,
+        status: Option<SyncStatus>,
+        region_id: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<SyncRun>>;
+    async fn count(&self, status: Option<SyncStatus>, region_id: Option<&str>) -> AppResult<i64>;
+    /// Applies `osm_charging_stations_temp` to the stations, leaving the
+    /// stations with the skipped osm_ids, and those outside the region when
+    /// one is given, as they are, and records the conflicts under the run
+    async fn sync_osm_stations(
+        &self,
+        run_id: &str,
+        user_id: Option<&str>,
+        skipped_osm_ids: &[i64],
+        region_id: Option<&str>,
+    ) -> AppResult<SyncCounts>;
+    /// Staged rows outside the region and OSM stations of other regions,
+    /// which a regional sync leaves alone
+    async fn find_osm_ids_outside_region(&self, region_id: &str) -> AppResult<Vec<i64>>;
+    async fn find_conflicts(&self, run_id: &str) -> AppResult<Vec<SyncConflict>>;
+    /// Conflicts a sync would record right now
+    async fn find_pending_conflicts(&self) -> AppResult<Vec<SyncConflict>>;
+    /// Every staged row with the station it matches, by osm_id
+    async fn find_staged_stations(&self) -> AppResult<Vec<OsmStagedStation>>;
+    /// Tags of every staged row after merging them into the matching
+    /// station's tags
+    async fn find_staged_tags(&self) -> AppResult<Vec<OsmStagedTags>>;
+    /// Replaces `osm_staged_connectors`, which the sync reads the connectors
+    /// from
+    async fn stage_connectors(&self, connectors: &[OsmStationConnector]) -> AppResult<()>;
+    /// The given connectors, except those an operator connector covers and
+    /// those of unknown types. Writes nothing, for the preview.
+    async fn filter_uncovered_connectors(
+        &self,
+        connectors: &[OsmStationConnector],
+    ) -> AppResult<Vec<OsmStationConnector>>;
+    /// Current OSM connectors of the stations that have a staged row
+    async fn find_matched_connectors(&self) -> AppResult<Vec<OsmStationConnector>>;
+    /// Active OSM stations that have no staged row
+    async fn find_missing_stations(&self) -> AppResult<Vec<OsmRemovedStation>>;
+    async fn refresh_station_views(&self) -> AppResult<()>;
+}
+
+#[async_trait]
+pub trait ProvenanceRepository: Send + Sync {
+    /// Recorded sources; fields without one came from OSM
+    async fn find_by_station(&self, station_id: &str) -> AppResult<Vec<StationFieldSource>>;
+    /// Sets the source of the fields, and their lock unless `locked` is None
+    async fn set_sources(
+        &self,
+        station_id: &str,
+        fields: &[StationField],
+        source: DataSource,
+        locked: Option<bool>,
+        updated_by: Option<&str>,
+    ) -> AppResult<()>;
+}
+
+/// Locations and tariffs published over OCPI. Locations come with their
+/// connectors, in (last_updated, location id) order.
+#[async_trait]
+pub trait OcpiRepository: Send + Sync {
+    /// Locations last updated in [`date_from`, `date_to`). Without
+    /// `date_from` removed locations are left out.
+    async fn find_locations(
+        &self,
+        date_from: Option<DateTime<Utc>>,
+        date_to: Option<DateTime<Utc>>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<OcpiLocationRow>>;
+    async fn count_locations(
+        &self,
+        date_from: Option<DateTime<Utc>>,
+        date_to: Option<DateTime<Utc>>,
+    ) -> AppResult<i64>;
+    async fn find_location(&self, location_id: &str) -> AppResult<Option<OcpiLocationRow>>;
+    /// Locations past the (last_updated, location id) cursor, or the
+    /// published locations without one, that were last updated at least
+    /// `settle_seconds` ago
+    async fn find_locations_after(
+        &self,
+        cursor: Option<(DateTime<Utc>, String)>,
+        settle_seconds: i64,
+        limit: i64,
+    ) -> AppResult<Vec<OcpiLocationRow>>;
+    /// Active tariffs of verified networks last updated in
+    /// [`date_from`, `date_to`)
+    async fn find_tariffs(
+        &self,
+        date_from: Option<DateTime<Utc>>,
+        date_to: Option<DateTime<Utc>>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<OcpiTariffRow>>;
+    async fn count_tariffs(
+        &self,
+        date_from: Option<DateTime<Utc>>,
+        date_to: Option<DateTime<Utc>>,
+    ) -> AppResult<i64>;
+}
+
+#[async_trait]
+pub trait OcpiPartyRepository: Send + Sync {
+    async fn create(&self, party: &OcpiParty) -> AppResult<OcpiParty>;
+    async fn find_all(&self) -> AppResult<Vec<OcpiParty>>;
+    async fn find_by_id(&self, party_uid: &str) -> AppResult<Option<OcpiParty>>;
+    /// The pending party with this token A or the registered one with this
+    /// token C
+    async fn find_by_token(&self, token: &str) -> AppResult<Option<OcpiParty>>;
+    async fn find_registered(&self) -> AppResult<Vec<OcpiParty>>;
+    /// Stores the registration of a pending or registered party, replacing
+    /// its tokens. A new registration pushes the locations from scratch.
+    async fn register(
+        &self,
+        party_uid: &str,
+        registration: &OcpiRegistrationData,
+    ) -> AppResult<Option<OcpiParty>>;
+    /// Revokes the tokens of a pending or registered party
+    async fn unregister(&self, party_uid: &str) -> AppResult<Option<OcpiParty>>;
+    /// Moves the push cursor and records the outcome of a push
+    async fn record_push(
+        &self,
+        party_uid: &str,
+        cursor: Option<(DateTime<Utc>, String)>,
+        error: Option<&str>,
+    ) -> AppResult<()>;
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait NetworkVerificationRepository: Send + Sync {
+    async fn find_by_id(&self, request_id: &str) -> AppResult<Option<NetworkVerificationRequest>>;
+    async fn find_all<'a>(
+        &self,
+        network_id: Option<&'a str>,
+        status: Option<&'a str>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<NetworkVerificationRequest>>;
+    async fn count<'a>(
+        &self,
+        network_id: Option<&'a str>,
+        status: Option<&'a str>,
+    ) -> AppResult<i64>;
+    async fn find_approved(
+        &self,
+        network_id: &str,
+    ) -> AppResult<Option<NetworkVerificationRequest>>;
+
+    /// Moves the request from `from` to `to`, records the event, keeps
+    /// `networks.is_verified` in line and notifies the partner who
+    /// submitted the request. None when the request is no longer in `from`.
+    async fn transition<'a>(
+        &self,
+        request_id: &str,
+        from: VerificationStatus,
+        to: VerificationStatus,
+        actor_id: &str,
+        comment: Option<&'a str>,
+        notification: &VerificationNotification,
+    ) -> AppResult<Option<NetworkVerificationRequest>>;
+
+    /// Unverifies a network that has no approved request, such as one
+    /// verified before requests existed. False when it was not verified.
+    async fn revoke_network<'a>(
+        &self,
+        network_id: &str,
+        actor_id: &str,
+        comment: Option<&'a str>,
+    ) -> AppResult<bool>;
+
+    /// `is_verified` of the network, None when it does not exist
+    async fn find_network_verified(&self, network_id: &str) -> AppResult<Option<bool>>;
+
+    async fn find_documents(&self, request_id: &str) -> AppResult<Vec<VerificationDocument>>;
+    async fn count_documents(&self, request_id: &str) -> AppResult<i64>;
+
+    /// Oldest first
+    async fn find_events_by_request(&self, request_id: &str) -> AppResult<Vec<VerificationEvent>>;
+    async fn find_events_by_network(&self, network_id: &str) -> AppResult<Vec<VerificationEvent>>;
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait ReviewModerationRepository: Send + Sync {
+    /// Pending reviews plus reviews with open flags, or only reviews in
+    /// `status` when set. Most flagged first.
+    async fn find_queue<'a>(
+        &self,
+        status: Option<&'a str>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<ModerationQueueEntry>>;
+    async fn count_queue<'a>(&self, status: Option<&'a str>) -> AppResult<i64>;
+
+    /// Sets the review's status and resolves its open flags. None when the
+    /// review does not exist.
+    async fn moderate(
+        &self,
+        review_id: &str,
+        status: ModerationStatus,
+        reason: &str,
+        admin_id: &str,
+    ) -> AppResult<Option<UserReview>>;
+}
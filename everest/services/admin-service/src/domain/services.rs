@@ -1,10 +1,29 @@
-use super::entities::{Connector, Network, Station};
+use super::deleted::{DeletedRecordType, PurgeCounts};
+use super::entities::{
+    Connector, DeletedRecord, DuplicateCandidate, ModerationQueueEntry, Network,
+    NetworkVerificationDetail, NetworkVerificationRequest, OcpiParty, Region, RegionStatistics,
+    Station, StationFieldSource, StationMerge, SyncConflict, SyncRun, UserReview,
+    VerificationEvent,
+};
+use super::export::{ExportFilter, ExportFormat};
+use super::import::{ImportFormat, ImportReport};
+use super::moderation::ModerationStatus;
+use super::ocpi::{
+    Connector as OcpiConnector, Credentials, Evse, Location, OcpiIdentity, OcpiToken, Tariff,
+};
+use super::osm_import::{OsmDiffReport, OsmImportReport};
+use super::provenance::{DataSource, StationField};
+use super::sync::{SyncPreview, SyncStatus, SyncTrigger};
+use super::verification::VerificationStatus;
 use crate::core::errors::AppResult;
 use crate::domain::value_objects::{
-    CreateConnectorData, CreateNetworkData, CreateStationData, UpdateConnectorData,
-    UpdateNetworkData, UpdateStationData,
+    CreateConnectorData, CreateNetworkData, CreateRegionData, CreateStationData,
+    UpdateConnectorData, UpdateNetworkData, UpdateStationData,
 };
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures_util::stream::BoxStream;
+use std::path::{Path, PathBuf};
 
 #[async_trait]
 pub trait NetworkService: Send + Sync {
@@ -13,7 +32,9 @@ pub trait NetworkService: Send + Sync {
     async fn list_networks(&self, limit: i64, offset: i64) -> AppResult<(Vec<Network>, i64)>;
     async fn update_network(&self, network_id: &str, data: UpdateNetworkData)
     -> AppResult<Network>;
-    async fn delete_network(&self, network_id: &str) -> AppResult<()>;
+    /// Soft deletion; fails with a conflict while stations use the network
+    async fn delete_network(&self, network_id: &str, deleted_by: &str) -> AppResult<()>;
+    async fn restore_network(&self, network_id: &str, restored_by: &str) -> AppResult<Network>;
 }
 
 #[async_trait]
@@ -23,12 +44,31 @@ pub trait StationService: Send + Sync {
     async fn list_stations(
         &self,
         network_id: Option<String>,
+        region_id: Option<String>,
         limit: i64,
         offset: i64,
     ) -> AppResult<(Vec<Station>, i64)>;
-    async fn update_station(&self, station_id: &str, data: UpdateStationData)
-    -> AppResult<Station>;
-    async fn delete_station(&self, station_id: &str) -> AppResult<()>;
+    /// Fields set here become user_submitted, so the OSM sync keeps them
+    async fn update_station(
+        &self,
+        station_id: &str,
+        data: UpdateStationData,
+        updated_by: &str,
+    ) -> AppResult<Station>;
+    /// Soft deletion, connectors included
+    async fn delete_station(&self, station_id: &str, deleted_by: &str) -> AppResult<()>;
+    /// Brings back the station with the connectors deleted along with it
+    async fn restore_station(&self, station_id: &str, restored_by: &str) -> AppResult<Station>;
+    /// Source and lock of every tracked field
+    async fn get_station_provenance(&self, station_id: &str) -> AppResult<Vec<StationFieldSource>>;
+    async fn update_field_provenance(
+        &self,
+        station_id: &str,
+        field: StationField,
+        source: Option<DataSource>,
+        locked: Option<bool>,
+        updated_by: &str,
+    ) -> AppResult<Vec<StationFieldSource>>;
 }
 
 #[async_trait]
@@ -46,5 +86,242 @@ pub trait ConnectorService: Send + Sync {
         connector_id: &str,
         data: UpdateConnectorData,
     ) -> AppResult<Connector>;
-    async fn delete_connector(&self, connector_id: &str) -> AppResult<()>;
+    /// Soft deletion
+    async fn delete_connector(&self, connector_id: &str, deleted_by: &str) -> AppResult<()>;
+    async fn restore_connector(
+        &self,
+        connector_id: &str,
+        restored_by: &str,
+    ) -> AppResult<Connector>;
+}
+
+#[async_trait]
+pub trait StationImportService: Send + Sync {
+    /// Checks every row and reports on each. Unless `dry_run`, creates all
+    /// the stations and connectors at once, or nothing if any row is invalid.
+    async fn import_stations(
+        &self,
+        format: ImportFormat,
+        text: &str,
+        dry_run: bool,
+        imported_by: &str,
+    ) -> AppResult<ImportReport>;
+}
+
+#[async_trait]
+pub trait OsmImportService: Send + Sync {
+    /// Reads the charging stations of local `.osm.pbf` files, one per region,
+    /// into `osm_charging_stations_temp`, replacing what was staged before
+    async fn import_pbf(&self, paths: &[PathBuf]) -> AppResult<OsmImportReport>;
+    /// Applies a local osmChange file (`.osc` or `.osc.gz`) to the staged
+    /// stations. Diffs must be applied in sequence, each once.
+    async fn apply_diff(&self, path: &Path, sequence_number: i64) -> AppResult<OsmDiffReport>;
+}
+
+pub trait StationExportService: Send + Sync {
+    /// The encoded file, produced chunk by chunk as the client reads it
+    fn export_stations(
+        &self,
+        format: ExportFormat,
+        filter: ExportFilter,
+    ) -> BoxStream<'static, AppResult<String>>;
+}
+
+#[async_trait]
+pub trait OsmSyncService: Send + Sync {
+    /// What a sync would change right now, without changing anything
+    async fn preview_sync(&self, region_id: Option<&str>) -> AppResult<SyncPreview>;
+    /// Records a run and starts the sync in the background; a conflict if
+    /// another run is in progress. Changes to the skipped osm_ids, as listed
+    /// by the preview, are not applied. A regional run only changes the
+    /// stations of that region.
+    async fn start_sync(
+        &self,
+        trigger: SyncTrigger,
+        triggered_by: Option<&str>,
+        skipped_osm_ids: Vec<i64>,
+        region_id: Option<String>,
+    ) -> AppResult<SyncRun>;
+    async fn get_run(&self, run_id: &str) -> AppResult<SyncRun>;
+    async fn get_run_conflicts(&self, run_id: &str) -> AppResult<Vec<SyncConflict>>;
+    async fn list_runs(
+        &self,
+        status: Option<SyncStatus>,
+        region_id: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<(Vec<SyncRun>, i64)>;
+}
+
+#[async_trait]
+pub trait RegionService: Send + Sync {
+    /// Stations inside the boundary move into the new region
+    async fn create_region(&self, data: CreateRegionData) -> AppResult<Region>;
+    async fn get_region(&self, region_id: &str) -> AppResult<Region>;
+    async fn list_regions(&self) -> AppResult<Vec<Region>>;
+    /// Its stations move to the next region containing them, if any
+    async fn delete_region(&self, region_id: &str) -> AppResult<()>;
+    async fn region_statistics(&self) -> AppResult<Vec<RegionStatistics>>;
+}
+
+#[async_trait]
+pub trait StationMergeService: Send + Sync {
+    /// Likely duplicate pairs for review, best first. Defaults to pairs
+    /// within 50 m scoring at least 0.5.
+    async fn list_candidates(
+        &self,
+        max_distance_meters: Option<f64>,
+        min_score: Option<f64>,
+        region_id: Option<&str>,
+        limit: i64,
+    ) -> AppResult<Vec<DuplicateCandidate>>;
+    /// Marks the pair as distinct stations
+    async fn dismiss_candidate(
+        &self,
+        station_id_a: &str,
+        station_id_b: &str,
+        dismissed_by: Option<&str>,
+    ) -> AppResult<()>;
+    /// Moves connectors, reviews and everything else referencing the merged
+    /// station onto the surviving one and deletes the merged station
+    async fn merge_stations(
+        &self,
+        surviving_station_id: &str,
+        merged_station_id: &str,
+        merged_by: Option<&str>,
+    ) -> AppResult<StationMerge>;
+    async fn list_merges(
+        &self,
+        station_id: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<StationMerge>>;
+}
+
+#[async_trait]
+pub trait DeletedRecordService: Send + Sync {
+    /// Soft-deleted stations, connectors and networks, most recent first
+    async fn list_deleted(
+        &self,
+        record_type: Option<DeletedRecordType>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<(Vec<DeletedRecord>, i64)>;
+    /// When a record deleted at `deleted_at` is purged
+    fn purge_after(&self, deleted_at: DateTime<Utc>) -> DateTime<Utc>;
+    /// Removes the records deleted longer than the retention period ago
+    async fn purge_expired(&self) -> AppResult<PurgeCounts>;
+}
+
+#[async_trait]
+pub trait OcpiService: Send + Sync {
+    /// A party to hand the returned token A over to
+    async fn create_party(&self, name: &str, created_by: &str) -> AppResult<OcpiParty>;
+    async fn list_parties(&self) -> AppResult<Vec<OcpiParty>>;
+    /// Revokes the party's tokens
+    async fn unregister_party(&self, party_uid: &str) -> AppResult<OcpiParty>;
+
+    /// The party calling with `token`, and which token it is
+    async fn authenticate(&self, token: &str) -> AppResult<(OcpiParty, OcpiToken)>;
+    fn identity(&self) -> &OcpiIdentity;
+    /// Registers a party calling with token A from its credentials: fetches
+    /// its endpoints and returns our credentials with a new token C
+    async fn register(&self, party: &OcpiParty, credentials: Credentials)
+    -> AppResult<Credentials>;
+    /// Same for a registered party calling with token C, whose token C is
+    /// replaced
+    async fn update_registration(
+        &self,
+        party: &OcpiParty,
+        credentials: Credentials,
+    ) -> AppResult<Credentials>;
+    /// The party unregistering itself
+    async fn unregister(&self, party: &OcpiParty) -> AppResult<()>;
+
+    async fn list_locations(
+        &self,
+        date_from: Option<DateTime<Utc>>,
+        date_to: Option<DateTime<Utc>>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<(Vec<Location>, i64)>;
+    async fn get_location(&self, location_id: &str) -> AppResult<Location>;
+    async fn get_evse(&self, location_id: &str, evse_uid: &str) -> AppResult<Evse>;
+    async fn get_connector(
+        &self,
+        location_id: &str,
+        evse_uid: &str,
+        connector_id: &str,
+    ) -> AppResult<OcpiConnector>;
+    async fn list_tariffs(
+        &self,
+        date_from: Option<DateTime<Utc>>,
+        date_to: Option<DateTime<Utc>>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<(Vec<Tariff>, i64)>;
+    /// Pushes the locations updated since the last push to every registered
+    /// party with a Locations receiver; returns how many were accepted
+    async fn push_locations(&self) -> AppResult<usize>;
+}
+
+/// Admin review of the verification requests partners submit through
+/// locate-service
+#[async_trait]
+pub trait NetworkVerificationService: Send + Sync {
+    async fn list_requests(
+        &self,
+        network_id: Option<&str>,
+        status: Option<VerificationStatus>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<(Vec<NetworkVerificationRequest>, i64)>;
+    async fn get_request(&self, request_id: &str) -> AppResult<NetworkVerificationDetail>;
+
+    /// Verifies the network; the request needs at least one document
+    async fn approve_request(
+        &self,
+        request_id: &str,
+        admin_id: &str,
+        comment: Option<String>,
+    ) -> AppResult<NetworkVerificationRequest>;
+
+    /// The comment tells the partner what to fix before submitting again
+    async fn reject_request(
+        &self,
+        request_id: &str,
+        admin_id: &str,
+        comment: String,
+    ) -> AppResult<NetworkVerificationRequest>;
+
+    /// Unverifies the network and deactivates its tariffs
+    async fn revoke_verification(
+        &self,
+        network_id: &str,
+        admin_id: &str,
+        comment: String,
+    ) -> AppResult<()>;
+
+    /// Oldest first
+    async fn list_network_events(&self, network_id: &str) -> AppResult<Vec<VerificationEvent>>;
+}
+
+/// Admin decisions on the reviews users write through locate-service
+#[async_trait]
+pub trait ReviewModerationService: Send + Sync {
+    async fn list_queue(
+        &self,
+        status: Option<ModerationStatus>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<(Vec<ModerationQueueEntry>, i64)>;
+
+    /// Hides or restores the review and resolves its open flags
+    async fn moderate_review(
+        &self,
+        review_id: &str,
+        status: ModerationStatus,
+        reason: String,
+        admin_id: &str,
+    ) -> AppResult<UserReview>;
 }
@@ -0,0 +1,315 @@
+use super::entities::{OsmRemovedStation, OsmStagedStation, OsmStationConnector, SyncConflict};
+use crate::core::errors::AppError;
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+
+const EARTH_RADIUS_METERS: f64 = 6_371_000.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncTrigger {
+    Manual,
+    Scheduled,
+}
+
+impl SyncTrigger {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Manual => "manual",
+            Self::Scheduled => "scheduled",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncStatus {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+impl SyncStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Running => "running",
+            Self::Succeeded => "succeeded",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+impl FromStr for SyncStatus {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "running" => Ok(Self::Running),
+            "succeeded" => Ok(Self::Succeeded),
+            "failed" => Ok(Self::Failed),
+            other => Err(AppError::ValidationError(format!(
+                "Unknown sync status: {}, expected running, succeeded or failed",
+                other
+            ))),
+        }
+    }
+}
+
+/// What `sync_osm_charging_stations()` changed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, sqlx::FromRow)]
+pub struct SyncCounts {
+    pub inserted_count: i32,
+    pub updated_count: i32,
+    pub deactivated_count: i32,
+    /// Staged values left unapplied on protected fields and connectors
+    pub conflict_count: i32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: &'static str,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagChange {
+    pub key: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectorSpec {
+    pub connector_type: String,
+    pub current_type: String,
+    pub power_kw: Option<f64>,
+    pub voltage: Option<i32>,
+    pub amperage: Option<i32>,
+    pub count_total: Option<i32>,
+}
+
+impl ConnectorSpec {
+    fn same_as(&self, other: &Self) -> bool {
+        let same_power = match (self.power_kw, other.power_kw) {
+            (Some(a), Some(b)) => (a - b).abs() < 0.005,
+            (a, b) => a.is_none() && b.is_none(),
+        };
+        same_power
+            && self.voltage == other.voltage
+            && self.amperage == other.amperage
+            && self.count_total == other.count_total
+    }
+}
+
+impl From<OsmStationConnector> for ConnectorSpec {
+    fn from(connector: OsmStationConnector) -> Self {
+        Self {
+            connector_type: connector.connector_type,
+            current_type: connector.current_type,
+            power_kw: connector.power_kw,
+            voltage: connector.voltage,
+            amperage: connector.amperage,
+            count_total: connector.count_total,
+        }
+    }
+}
+
+/// A connector added, removed or changed, by connector and current type
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectorChange {
+    pub connector_type: String,
+    pub current_type: String,
+    pub old: Option<ConnectorSpec>,
+    pub new: Option<ConnectorSpec>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewStationPreview {
+    pub osm_id: i64,
+    pub name: String,
+    pub address: Option<String>,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub connectors: Vec<ConnectorSpec>,
+}
+
+#[derive(Debug, Clone)]
+pub struct StationChangePreview {
+    pub station_id: String,
+    pub osm_id: i64,
+    pub name: String,
+    /// The station had been deactivated and is back in the extract
+    pub reactivated: bool,
+    /// name, address and location (as `lat,lon`)
+    pub fields: Vec<FieldChange>,
+    pub moved_meters: Option<f64>,
+    pub tags: Vec<TagChange>,
+    /// The sync rebuilds the station's connectors from its tags
+    pub connectors: Vec<ConnectorChange>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SyncPreview {
+    pub new_stations: Vec<NewStationPreview>,
+    pub changed_stations: Vec<StationChangePreview>,
+    pub removed_stations: Vec<OsmRemovedStation>,
+    pub unchanged_count: usize,
+    /// Staged values the sync would not apply
+    pub conflicts: Vec<SyncConflict>,
+}
+
+/// Compares the staged OSM data with the stations the same way
+/// `sync_osm_charging_stations()` decides what to write
+pub fn build_preview(
+    staged: Vec<OsmStagedStation>,
+    staged_connectors: Vec<OsmStationConnector>,
+    current_connectors: Vec<OsmStationConnector>,
+    removed_stations: Vec<OsmRemovedStation>,
+) -> SyncPreview {
+    let mut proposed = group_connectors(staged_connectors);
+    let mut current = group_connectors(current_connectors);
+    let mut preview = SyncPreview {
+        removed_stations,
+        ..Default::default()
+    };
+
+    for row in staged {
+        let connectors = proposed.remove(&row.osm_id).unwrap_or_default();
+        let Some(station_id) = row.station_id.clone() else {
+            preview.new_stations.push(NewStationPreview {
+                osm_id: row.osm_id,
+                name: row.name,
+                address: row.address,
+                latitude: row.latitude,
+                longitude: row.longitude,
+                connectors,
+            });
+            continue;
+        };
+
+        let mut fields = Vec::new();
+        if row.current_name.as_deref() != Some(row.name.as_str()) {
+            fields.push(FieldChange {
+                field: "name",
+                old: row.current_name.clone(),
+                new: Some(row.name.clone()),
+            });
+        }
+        if row.current_address != row.address {
+            fields.push(FieldChange {
+                field: "address",
+                old: row.current_address.clone(),
+                new: row.address.clone(),
+            });
+        }
+        let mut moved_meters = None;
+        if let (Some(lat), Some(lon)) = (row.current_latitude, row.current_longitude)
+            && (lat != row.latitude || lon != row.longitude)
+        {
+            fields.push(FieldChange {
+                field: "location",
+                old: Some(format!("{},{}", lat, lon)),
+                new: Some(format!("{},{}", row.latitude, row.longitude)),
+            });
+            moved_meters = Some(distance_meters(lat, lon, row.latitude, row.longitude));
+        }
+
+        let tags = diff_tags(&row.current_tags, &row.tags);
+        if !row.deactivated && fields.is_empty() && tags.is_empty() {
+            preview.unchanged_count += 1;
+            continue;
+        }
+
+        let connectors =
+            diff_connectors(current.remove(&row.osm_id).unwrap_or_default(), connectors);
+        preview.changed_stations.push(StationChangePreview {
+            station_id,
+            osm_id: row.osm_id,
+            name: row.current_name.unwrap_or(row.name),
+            reactivated: row.deactivated,
+            fields,
+            moved_meters,
+            tags,
+            connectors,
+        });
+    }
+
+    preview
+}
+
+fn group_connectors(connectors: Vec<OsmStationConnector>) -> HashMap<i64, Vec<ConnectorSpec>> {
+    let mut grouped: HashMap<i64, Vec<ConnectorSpec>> = HashMap::new();
+    for connector in connectors {
+        grouped
+            .entry(connector.osm_id)
+            .or_default()
+            .push(connector.into());
+    }
+    grouped
+}
+
+/// Tags with a value; a NULL value counts as no tag, as in
+/// `hstore_strip_nulls()`
+pub fn tag_map(pairs: &[Option<String>]) -> BTreeMap<&str, &str> {
+    pairs
+        .chunks_exact(2)
+        .filter_map(|pair| Some((pair[0].as_deref()?, pair[1].as_deref()?)))
+        .collect()
+}
+
+fn diff_tags(old: &[Option<String>], new: &[Option<String>]) -> Vec<TagChange> {
+    let old = tag_map(old);
+    let new = tag_map(new);
+    let mut keys: Vec<&str> = old.keys().chain(new.keys()).copied().collect();
+    keys.sort_unstable();
+    keys.dedup();
+
+    keys.into_iter()
+        .filter_map(|key| {
+            let before = old.get(key);
+            let after = new.get(key);
+            (before != after).then(|| TagChange {
+                key: key.to_string(),
+                old: before.map(|v| v.to_string()),
+                new: after.map(|v| v.to_string()),
+            })
+        })
+        .collect()
+}
+
+fn diff_connectors(old: Vec<ConnectorSpec>, new: Vec<ConnectorSpec>) -> Vec<ConnectorChange> {
+    let key = |c: &ConnectorSpec| (c.connector_type.clone(), c.current_type.clone());
+    let mut pairs: BTreeMap<(String, String), (Option<ConnectorSpec>, Option<ConnectorSpec>)> =
+        BTreeMap::new();
+    for connector in old {
+        let slot = &mut pairs.entry(key(&connector)).or_default().0;
+        *slot = Some(connector);
+    }
+    for connector in new {
+        let slot = &mut pairs.entry(key(&connector)).or_default().1;
+        *slot = Some(connector);
+    }
+
+    pairs
+        .into_iter()
+        .filter(|(_, (old, new))| match (old, new) {
+            (Some(old), Some(new)) => !old.same_as(new),
+            _ => true,
+        })
+        .map(
+            |((connector_type, current_type), (old, new))| ConnectorChange {
+                connector_type,
+                current_type,
+                old,
+                new,
+            },
+        )
+        .collect()
+}
+
+fn distance_meters(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let (lat1, lat2) = (lat1.to_radians(), lat2.to_radians());
+    let dlat = lat2 - lat1;
+    let dlon = (lon2 - lon1).to_radians();
+    let a = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_METERS * a.sqrt().asin()
+}
@@ -15,7 +15,6 @@ pub struct UpdateNetworkData {
     pub network_type: Option<String>,
     pub support_phone: Option<String>,
     pub support_email: Option<String>,
-    pub is_verified: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -63,3 +62,33 @@ pub struct UpdateConnectorData {
     pub count_available: Option<i32>,
     pub count_total: Option<i32>,
 }
+
+/// Where the boundary of a region comes from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum RegionBoundary {
+    /// The imported OSM administrative boundary with this ISO 3166-1 or
+    /// ISO 3166-2 code
+    OsmIsoCode(String),
+    /// A GeoJSON Polygon or MultiPolygon geometry
+    GeoJson(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateRegionData {
+    pub region_id: String,
+    pub name: String,
+    pub country_code: Option<String>,
+    pub boundary: RegionBoundary,
+}
+
+/// What a party's credentials and endpoints tell us when it registers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OcpiRegistrationData {
+    pub country_code: String,
+    pub party_id: String,
+    pub role: String,
+    pub token_b: String,
+    pub token_c: String,
+    pub versions_url: String,
+    pub locations_url: Option<String>,
+}
@@ -0,0 +1,144 @@
+use crate::core::errors::AppError;
+use std::str::FromStr;
+
+/// State of a network verification request. Partners submit requests and
+/// their documents through locate-service; admins review them here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerificationStatus {
+    Pending,
+    Approved,
+    Rejected,
+    Revoked,
+}
+
+impl VerificationStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Approved => "approved",
+            Self::Rejected => "rejected",
+            Self::Revoked => "revoked",
+        }
+    }
+
+    /// Admins decide on pending requests and may later revoke an approval.
+    /// A rejected or revoked network submits a new request.
+    pub fn can_transition_to(&self, next: VerificationStatus) -> bool {
+        matches!(
+            (self, next),
+            (Self::Pending, Self::Approved)
+                | (Self::Pending, Self::Rejected)
+                | (Self::Approved, Self::Revoked)
+        )
+    }
+}
+
+impl FromStr for VerificationStatus {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(Self::Pending),
+            "approved" => Ok(Self::Approved),
+            "rejected" => Ok(Self::Rejected),
+            "revoked" => Ok(Self::Revoked),
+            other => Err(AppError::ValidationError(format!(
+                "Unknown verification status: {}, expected pending, approved, rejected or revoked",
+                other
+            ))),
+        }
+    }
+}
+
+/// What the partner who submitted a request is told about its outcome
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificationNotification {
+    pub kind: &'static str,
+    pub message: String,
+}
+
+impl VerificationNotification {
+    /// None for `Pending`, which no admin decision leads to
+    pub fn for_decision(status: VerificationStatus, comment: Option<&str>) -> Option<Self> {
+        let comment = comment.unwrap_or_default();
+        let (kind, message) = match status {
+            VerificationStatus::Approved => (
+                "network_verification_approved",
+                "Your network has been verified".to_string(),
+            ),
+            VerificationStatus::Rejected => (
+                "network_verification_rejected",
+                format!(
+                    "Your network verification request was rejected: {}",
+                    comment
+                ),
+            ),
+            VerificationStatus::Revoked => (
+                "network_verification_revoked",
+                format!("The verification of your network was revoked: {}", comment),
+            ),
+            VerificationStatus::Pending => return None,
+        };
+        Some(Self { kind, message })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [VerificationStatus; 4] = [
+        VerificationStatus::Pending,
+        VerificationStatus::Approved,
+        VerificationStatus::Rejected,
+        VerificationStatus::Revoked,
+    ];
+
+    #[test]
+    fn parses_what_it_prints() {
+        for status in ALL {
+            assert_eq!(
+                VerificationStatus::from_str(status.as_str()).unwrap(),
+                status
+            );
+        }
+        assert!(VerificationStatus::from_str("verified").is_err());
+    }
+
+    #[test]
+    fn only_admin_decisions_are_transitions() {
+        let allowed: Vec<_> = ALL
+            .iter()
+            .flat_map(|from| ALL.iter().map(move |to| (*from, *to)))
+            .filter(|(from, to)| from.can_transition_to(*to))
+            .collect();
+
+        assert_eq!(
+            allowed,
+            vec![
+                (VerificationStatus::Pending, VerificationStatus::Approved),
+                (VerificationStatus::Pending, VerificationStatus::Rejected),
+                (VerificationStatus::Approved, VerificationStatus::Revoked),
+            ]
+        );
+    }
+
+    #[test]
+    fn tells_the_partner_why_it_was_rejected() {
+        let notification = VerificationNotification::for_decision(
+            VerificationStatus::Rejected,
+            Some("Registration extract is unreadable"),
+        )
+        .unwrap();
+
+        assert_eq!(notification.kind, "network_verification_rejected");
+        assert!(
+            notification
+                .message
+                .ends_with("Registration extract is unreadable")
+        );
+        assert!(
+            VerificationNotification::for_decision(VerificationStatus::Pending, None).is_none()
+        );
+    }
+}
@@ -1 +1,4 @@
+pub mod ocpi_client;
+pub mod osm_change;
+pub mod osm_pbf;
 pub mod repositories;
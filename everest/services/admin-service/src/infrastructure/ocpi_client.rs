@@ -0,0 +1,114 @@
+use crate::core::constants::OCPI_REQUEST_TIMEOUT_SECONDS;
+use crate::core::errors::{AppError, AppResult};
+use crate::domain::entities::OcpiParty;
+use crate::domain::ocpi::{
+    Location, OcpiIdentity, OcpiPartyApi, OcpiResponse, Version, VersionDetails,
+};
+use async_trait::async_trait;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+
+/// OCPI 2.2.1 calls to a party over HTTP
+pub struct HttpOcpiPartyApi {
+    identity: OcpiIdentity,
+    http_client: reqwest::Client,
+}
+
+impl HttpOcpiPartyApi {
+    pub fn new(identity: OcpiIdentity) -> Self {
+        Self {
+            identity,
+            http_client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(OCPI_REQUEST_TIMEOUT_SECONDS))
+                .build()
+                .expect("HTTP client configuration is valid"),
+        }
+    }
+
+    /// Adds the token, base64 encoded as of OCPI 2.2, and the request ids
+    fn request(&self, method: reqwest::Method, url: &str, token: &str) -> reqwest::RequestBuilder {
+        let request_id = nanoid::nanoid!();
+        self.http_client
+            .request(method, url)
+            .header("Authorization", format!("Token {}", BASE64.encode(token)))
+            .header("X-Request-ID", &request_id)
+            .header("X-Correlation-ID", &request_id)
+    }
+
+    /// Sends the request and unwraps the OCPI envelope
+    async fn send<T: DeserializeOwned>(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> AppResult<Option<T>> {
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AppError::InternalError(format!("OCPI request failed: {}", e)))?;
+        let status = response.status();
+        let body: OcpiResponse<T> = response.json().await.map_err(|e| {
+            AppError::InternalError(format!("Invalid OCPI response (HTTP {}): {}", status, e))
+        })?;
+
+        if !status.is_success() || !(1000..2000).contains(&body.status_code) {
+            return Err(AppError::InternalError(format!(
+                "OCPI request refused (HTTP {}, status {}): {}",
+                status,
+                body.status_code,
+                body.status_message.unwrap_or_default()
+            )));
+        }
+
+        Ok(body.data)
+    }
+}
+
+#[async_trait]
+impl OcpiPartyApi for HttpOcpiPartyApi {
+    async fn versions(&self, url: &str, token: &str) -> AppResult<Vec<Version>> {
+        let versions = self
+            .send(self.request(reqwest::Method::GET, url, token))
+            .await?;
+        Ok(versions.unwrap_or_default())
+    }
+
+    async fn version_details(&self, url: &str, token: &str) -> AppResult<VersionDetails> {
+        self.send(self.request(reqwest::Method::GET, url, token))
+            .await?
+            .ok_or_else(|| AppError::InternalError("OCPI version details missing".to_string()))
+    }
+
+    async fn put_location(&self, party: &OcpiParty, location: &Location) -> AppResult<()> {
+        let (Some(locations_url), Some(token)) = (&party.locations_url, &party.token_b) else {
+            return Err(AppError::InternalError(format!(
+                "OCPI party {} takes no location pushes",
+                party.party_uid
+            )));
+        };
+        let url = format!(
+            "{}/{}/{}/{}",
+            locations_url.trim_end_matches('/'),
+            location.country_code,
+            location.party_id,
+            location.id
+        );
+
+        let request = self
+            .request(reqwest::Method::PUT, &url, token)
+            .header("OCPI-from-country-code", &self.identity.country_code)
+            .header("OCPI-from-party-id", &self.identity.party_id)
+            .header(
+                "OCPI-to-country-code",
+                party.country_code.as_deref().unwrap_or_default(),
+            )
+            .header(
+                "OCPI-to-party-id",
+                party.party_id.as_deref().unwrap_or_default(),
+            )
+            .json(location);
+        self.send::<serde_json::Value>(request).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,165 @@
+//! Reader for OpenStreetMap osmChange files (`.osc`, or `.osc.gz` as
+//! published by the replication servers): the created, modified and deleted
+//! nodes and ways with their tags. Relations are skipped.
+
+use crate::core::errors::{AppError, AppResult};
+use flate2::read::MultiGzDecoder;
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeAction {
+    Create,
+    Modify,
+    Delete,
+}
+
+#[derive(Debug, Clone)]
+pub enum ChangedElement {
+    /// Deleted nodes may come without a location
+    Node {
+        id: i64,
+        location: Option<(f64, f64)>,
+        tags: Vec<(String, String)>,
+    },
+    Way {
+        id: i64,
+        node_ids: Vec<i64>,
+        tags: Vec<(String, String)>,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct OsmChange {
+    pub action: ChangeAction,
+    pub element: ChangedElement,
+}
+
+/// Every node and way change of the file, in file order. Files ending in
+/// `.gz` are decompressed.
+pub fn read_changes(path: &Path) -> AppResult<Vec<OsmChange>> {
+    let file = File::open(path)
+        .map_err(|e| AppError::ValidationError(format!("Cannot open {}: {}", path.display(), e)))?;
+
+    if path.extension().is_some_and(|ext| ext == "gz") {
+        parse(BufReader::new(MultiGzDecoder::new(file)))
+    } else {
+        parse(BufReader::new(file))
+    }
+}
+
+fn invalid(e: impl std::fmt::Display) -> AppError {
+    AppError::ValidationError(format!("Not a valid osmChange file: {}", e))
+}
+
+fn parse(input: impl BufRead) -> AppResult<Vec<OsmChange>> {
+    let mut reader = Reader::from_reader(input);
+    let mut buffer = Vec::new();
+    let mut changes = Vec::new();
+    let mut seen_root = false;
+    let mut action: Option<ChangeAction> = None;
+    // The node or way being read, until its end tag
+    let mut current: Option<ChangedElement> = None;
+
+    loop {
+        // Nodes and ways end at their end tag, or right away when empty
+        let mut completed = false;
+        match reader.read_event_into(&mut buffer).map_err(invalid)? {
+            Event::Start(e) => start_element(&e, &mut seen_root, &mut action, &mut current)?,
+            Event::Empty(e) => {
+                start_element(&e, &mut seen_root, &mut action, &mut current)?;
+                completed = matches!(e.name().as_ref(), b"node" | b"way");
+            }
+            Event::End(e) => match e.name().as_ref() {
+                b"create" | b"modify" | b"delete" => action = None,
+                b"node" | b"way" => completed = true,
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+
+        if completed && let Some(element) = current.take() {
+            let action =
+                action.ok_or_else(|| invalid("element outside create, modify or delete"))?;
+            changes.push(OsmChange { action, element });
+        }
+        buffer.clear();
+    }
+
+    if !seen_root {
+        return Err(invalid("no osmChange root element"));
+    }
+    Ok(changes)
+}
+
+fn start_element(
+    e: &BytesStart<'_>,
+    seen_root: &mut bool,
+    action: &mut Option<ChangeAction>,
+    current: &mut Option<ChangedElement>,
+) -> AppResult<()> {
+    match e.name().as_ref() {
+        b"osmChange" => *seen_root = true,
+        b"create" => *action = Some(ChangeAction::Create),
+        b"modify" => *action = Some(ChangeAction::Modify),
+        b"delete" => *action = Some(ChangeAction::Delete),
+        b"node" => {
+            let location = match (attribute(e, b"lon")?, attribute(e, b"lat")?) {
+                (Some(lon), Some(lat)) => Some((number(&lon)?, number(&lat)?)),
+                _ => None,
+            };
+            *current = Some(ChangedElement::Node {
+                id: element_id(e)?,
+                location,
+                tags: Vec::new(),
+            });
+        }
+        b"way" => {
+            *current = Some(ChangedElement::Way {
+                id: element_id(e)?,
+                node_ids: Vec::new(),
+                tags: Vec::new(),
+            });
+        }
+        b"tag" => {
+            if let Some(ChangedElement::Node { tags, .. } | ChangedElement::Way { tags, .. }) =
+                current.as_mut()
+                && let (Some(key), Some(value)) = (attribute(e, b"k")?, attribute(e, b"v")?)
+            {
+                tags.push((key, value));
+            }
+        }
+        b"nd" => {
+            if let Some(ChangedElement::Way { node_ids, .. }) = current.as_mut() {
+                let node_ref = attribute(e, b"ref")?.ok_or_else(|| invalid("nd without ref"))?;
+                node_ids.push(number(&node_ref)?);
+            }
+        }
+        // Relations and their members are not read
+        _ => {}
+    }
+    Ok(())
+}
+
+fn attribute(e: &BytesStart<'_>, name: &[u8]) -> AppResult<Option<String>> {
+    match e.try_get_attribute(name).map_err(invalid)? {
+        Some(attr) => Ok(Some(attr.unescape_value().map_err(invalid)?.into_owned())),
+        None => Ok(None),
+    }
+}
+
+fn element_id(e: &BytesStart<'_>) -> AppResult<i64> {
+    let id = attribute(e, b"id")?.ok_or_else(|| invalid("element without id"))?;
+    number(&id)
+}
+
+fn number<T: std::str::FromStr>(value: &str) -> AppResult<T>
+where
+    T::Err: std::fmt::Display,
+{
+    value.trim().parse().map_err(invalid)
+}
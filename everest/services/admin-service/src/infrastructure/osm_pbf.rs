@@ -0,0 +1,371 @@
+//! Minimal reader for OpenStreetMap `.osm.pbf` files: nodes and ways with
+//! their tags, from raw or zlib-compressed blocks. Relations are skipped.
+
+use crate::core::errors::{AppError, AppResult};
+use flate2::read::ZlibDecoder;
+use prost::Message;
+use std::fs::File;
+use std::io::{BufReader, ErrorKind, Read};
+use std::path::Path;
+
+/// Upper bounds from the PBF specification
+const MAX_BLOB_HEADER_BYTES: usize = 64 * 1024;
+const MAX_BLOB_BYTES: usize = 32 * 1024 * 1024;
+
+const SUPPORTED_FEATURES: &[&str] = &["OsmSchema-V0.6", "DenseNodes"];
+
+#[derive(Clone, PartialEq, Message)]
+struct BlobHeader {
+    #[prost(string, required, tag = "1")]
+    r#type: String,
+    #[prost(int32, required, tag = "3")]
+    datasize: i32,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct Blob {
+    #[prost(bytes = "vec", optional, tag = "1")]
+    raw: Option<Vec<u8>>,
+    #[prost(int32, optional, tag = "2")]
+    raw_size: Option<i32>,
+    #[prost(bytes = "vec", optional, tag = "3")]
+    zlib_data: Option<Vec<u8>>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct HeaderBlock {
+    #[prost(string, repeated, tag = "4")]
+    required_features: Vec<String>,
+    #[prost(int64, optional, tag = "33")]
+    osmosis_replication_sequence_number: Option<i64>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct PrimitiveBlock {
+    #[prost(message, required, tag = "1")]
+    stringtable: StringTable,
+    #[prost(message, repeated, tag = "2")]
+    primitivegroup: Vec<PrimitiveGroup>,
+    #[prost(int32, optional, tag = "17", default = "100")]
+    granularity: Option<i32>,
+    #[prost(int64, optional, tag = "19", default = "0")]
+    lat_offset: Option<i64>,
+    #[prost(int64, optional, tag = "20", default = "0")]
+    lon_offset: Option<i64>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct StringTable {
+    #[prost(bytes = "vec", repeated, tag = "1")]
+    s: Vec<Vec<u8>>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct PrimitiveGroup {
+    #[prost(message, repeated, tag = "1")]
+    nodes: Vec<Node>,
+    #[prost(message, optional, tag = "2")]
+    dense: Option<DenseNodes>,
+    #[prost(message, repeated, tag = "3")]
+    ways: Vec<Way>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct Node {
+    #[prost(sint64, required, tag = "1")]
+    id: i64,
+    #[prost(uint32, repeated, packed = "true", tag = "2")]
+    keys: Vec<u32>,
+    #[prost(uint32, repeated, packed = "true", tag = "3")]
+    vals: Vec<u32>,
+    #[prost(sint64, required, tag = "8")]
+    lat: i64,
+    #[prost(sint64, required, tag = "9")]
+    lon: i64,
+}
+
+/// Ids and coordinates are delta coded; `keys_vals` holds key/value string
+/// indexes per node, each node's list ending with 0
+#[derive(Clone, PartialEq, Message)]
+struct DenseNodes {
+    #[prost(sint64, repeated, packed = "true", tag = "1")]
+    id: Vec<i64>,
+    #[prost(sint64, repeated, packed = "true", tag = "8")]
+    lat: Vec<i64>,
+    #[prost(sint64, repeated, packed = "true", tag = "9")]
+    lon: Vec<i64>,
+    #[prost(int32, repeated, packed = "true", tag = "10")]
+    keys_vals: Vec<i32>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct Way {
+    #[prost(int64, required, tag = "1")]
+    id: i64,
+    #[prost(uint32, repeated, packed = "true", tag = "2")]
+    keys: Vec<u32>,
+    #[prost(uint32, repeated, packed = "true", tag = "3")]
+    vals: Vec<u32>,
+    /// Delta coded node ids
+    #[prost(sint64, repeated, packed = "true", tag = "8")]
+    refs: Vec<i64>,
+}
+
+/// A node or way as read from the file, tags borrowed from its block
+#[derive(Debug)]
+pub enum OsmElement<'a> {
+    Node {
+        id: i64,
+        longitude: f64,
+        latitude: f64,
+        tags: Vec<(&'a str, &'a str)>,
+    },
+    Way {
+        id: i64,
+        node_ids: Vec<i64>,
+        tags: Vec<(&'a str, &'a str)>,
+    },
+}
+
+/// Calls `visit` with every node and way of the file, in file order.
+/// Returns the replication sequence number of the file, when its header has
+/// one.
+pub fn for_each_element(
+    path: &Path,
+    mut visit: impl FnMut(OsmElement<'_>),
+) -> AppResult<Option<i64>> {
+    let file = File::open(path)
+        .map_err(|e| AppError::ValidationError(format!("Cannot open {}: {}", path.display(), e)))?;
+    let mut reader = BufReader::new(file);
+    let mut sequence_number = None;
+
+    while let Some((header, data)) = read_blob(&mut reader)? {
+        match header.r#type.as_str() {
+            "OSMHeader" => {
+                let header = HeaderBlock::decode(data.as_slice()).map_err(corrupt)?;
+                check_features(&header)?;
+                sequence_number = header.osmosis_replication_sequence_number;
+            }
+            "OSMData" => {
+                let block = PrimitiveBlock::decode(data.as_slice()).map_err(corrupt)?;
+                visit_block(&block, &mut visit);
+            }
+            // Unknown blob types are to be skipped
+            _ => {}
+        }
+    }
+
+    Ok(sequence_number)
+}
+
+fn corrupt(e: impl std::fmt::Display) -> AppError {
+    AppError::ValidationError(format!("Not a valid OSM PBF file: {}", e))
+}
+
+/// Reads the next blob and returns its header and uncompressed content, or
+/// None at the end of the file
+fn read_blob(reader: &mut impl Read) -> AppResult<Option<(BlobHeader, Vec<u8>)>> {
+    let mut length = [0u8; 4];
+    match reader.read_exact(&mut length) {
+        Ok(()) => {}
+        Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(corrupt(e)),
+    }
+
+    let header_len = u32::from_be_bytes(length) as usize;
+    if header_len > MAX_BLOB_HEADER_BYTES {
+        return Err(corrupt(format!("blob header of {} bytes", header_len)));
+    }
+    let header = BlobHeader::decode(read_bytes(reader, header_len)?.as_slice()).map_err(corrupt)?;
+
+    let blob_len = usize::try_from(header.datasize).map_err(corrupt)?;
+    if blob_len > MAX_BLOB_BYTES {
+        return Err(corrupt(format!("blob of {} bytes", blob_len)));
+    }
+    let blob = Blob::decode(read_bytes(reader, blob_len)?.as_slice()).map_err(corrupt)?;
+
+    let data = match (blob.raw, blob.zlib_data) {
+        (Some(raw), _) => raw,
+        (None, Some(compressed)) => {
+            let raw_size = blob
+                .raw_size
+                .and_then(|size| usize::try_from(size).ok())
+                .ok_or_else(|| corrupt("zlib blob without a valid raw_size"))?;
+            if raw_size > MAX_BLOB_BYTES {
+                return Err(corrupt(format!("blob of {} bytes once inflated", raw_size)));
+            }
+            // One byte more than announced, to catch blobs inflating past it
+            let mut data = Vec::with_capacity(raw_size);
+            ZlibDecoder::new(compressed.as_slice())
+                .take(raw_size as u64 + 1)
+                .read_to_end(&mut data)
+                .map_err(corrupt)?;
+            if data.len() != raw_size {
+                return Err(corrupt(format!(
+                    "blob inflated to {} bytes instead of {}",
+                    data.len(),
+                    raw_size
+                )));
+            }
+            data
+        }
+        (None, None) => {
+            return Err(AppError::ValidationError(
+                "Unsupported PBF compression, only raw and zlib blocks can be read".to_string(),
+            ));
+        }
+    };
+
+    Ok(Some((header, data)))
+}
+
+fn read_bytes(reader: &mut impl Read, len: usize) -> AppResult<Vec<u8>> {
+    let mut buffer = vec![0u8; len];
+    reader.read_exact(&mut buffer).map_err(corrupt)?;
+    Ok(buffer)
+}
+
+fn check_features(header: &HeaderBlock) -> AppResult<()> {
+    match header
+        .required_features
+        .iter()
+        .find(|f| !SUPPORTED_FEATURES.contains(&f.as_str()))
+    {
+        Some(feature) => Err(AppError::ValidationError(format!(
+            "Unsupported PBF feature: {}",
+            feature
+        ))),
+        None => Ok(()),
+    }
+}
+
+fn visit_block(block: &PrimitiveBlock, visit: &mut impl FnMut(OsmElement<'_>)) {
+    let strings: Vec<&str> = block
+        .stringtable
+        .s
+        .iter()
+        .map(|s| std::str::from_utf8(s).unwrap_or(""))
+        .collect();
+    let string = |index: u32| strings.get(index as usize).copied().unwrap_or("");
+    let tags = |keys: &[u32], vals: &[u32]| -> Vec<(&str, &str)> {
+        keys.iter()
+            .zip(vals)
+            .map(|(k, v)| (string(*k), string(*v)))
+            .collect()
+    };
+
+    let granularity = block.granularity.unwrap_or(100) as f64;
+    let lat_offset = block.lat_offset.unwrap_or(0) as f64;
+    let lon_offset = block.lon_offset.unwrap_or(0) as f64;
+    let degrees = |offset: f64, value: i64| (offset + granularity * value as f64) * 1e-9;
+
+    for group in &block.primitivegroup {
+        for node in &group.nodes {
+            visit(OsmElement::Node {
+                id: node.id,
+                longitude: degrees(lon_offset, node.lon),
+                latitude: degrees(lat_offset, node.lat),
+                tags: tags(&node.keys, &node.vals),
+            });
+        }
+
+        if let Some(dense) = &group.dense {
+            let mut keys_vals = dense.keys_vals.iter();
+            let (mut id, mut lat, mut lon) = (0i64, 0i64, 0i64);
+            for i in 0..dense.id.len() {
+                id += dense.id[i];
+                lat += dense.lat.get(i).copied().unwrap_or(0);
+                lon += dense.lon.get(i).copied().unwrap_or(0);
+
+                let mut node_tags = Vec::new();
+                while let Some(&key) = keys_vals.next() {
+                    if key == 0 {
+                        break;
+                    }
+                    let value = keys_vals.next().copied().unwrap_or(0);
+                    node_tags.push((string(key as u32), string(value as u32)));
+                }
+
+                visit(OsmElement::Node {
+                    id,
+                    longitude: degrees(lon_offset, lon),
+                    latitude: degrees(lat_offset, lat),
+                    tags: node_tags,
+                });
+            }
+        }
+
+        for way in &group.ways {
+            let mut node_id = 0i64;
+            visit(OsmElement::Way {
+                id: way.id,
+                node_ids: way
+                    .refs
+                    .iter()
+                    .map(|delta| {
+                        node_id += delta;
+                        node_id
+                    })
+                    .collect(),
+                tags: tags(&way.keys, &way.vals),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::Compression;
+    use flate2::write::ZlibEncoder;
+    use std::io::Write;
+
+    fn zlib_blob(data: &[u8], raw_size: Option<i32>) -> Vec<u8> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data).unwrap();
+        let blob = Blob {
+            raw: None,
+            raw_size,
+            zlib_data: Some(encoder.finish().unwrap()),
+        }
+        .encode_to_vec();
+        let header = BlobHeader {
+            r#type: "OSMData".to_string(),
+            datasize: blob.len() as i32,
+        }
+        .encode_to_vec();
+
+        let mut file = (header.len() as u32).to_be_bytes().to_vec();
+        file.extend(header);
+        file.extend(blob);
+        file
+    }
+
+    #[test]
+    fn inflates_blobs_of_their_raw_size() {
+        let file = zlib_blob(&[7; 1000], Some(1000));
+        let (header, data) = read_blob(&mut file.as_slice()).unwrap().unwrap();
+        assert_eq!(header.r#type, "OSMData");
+        assert_eq!(data, vec![7; 1000]);
+    }
+
+    #[test]
+    fn rejects_blobs_inflating_past_their_raw_size() {
+        let file = zlib_blob(&[0; 100_000], Some(1000));
+        assert!(read_blob(&mut file.as_slice()).is_err());
+    }
+
+    #[test]
+    fn rejects_blobs_shorter_than_their_raw_size() {
+        let file = zlib_blob(&[0; 100], Some(1000));
+        assert!(read_blob(&mut file.as_slice()).is_err());
+    }
+
+    #[test]
+    fn rejects_zlib_blobs_without_a_bounded_raw_size() {
+        for raw_size in [None, Some(-1), Some(MAX_BLOB_BYTES as i32 + 1)] {
+            let file = zlib_blob(&[0; 100], raw_size);
+            assert!(read_blob(&mut file.as_slice()).is_err(), "{:?}", raw_size);
+        }
+    }
+}
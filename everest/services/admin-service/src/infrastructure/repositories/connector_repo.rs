@@ -1,6 +1,7 @@
-use crate::core::errors::AppResult;
+use crate::core::errors::{AppError, AppResult};
 use crate::domain::entities::Connector;
 use crate::domain::repositories::ConnectorRepository;
+use crate::infrastructure::repositories::refresh_station_views;
 use async_trait::async_trait;
 use chrono::Utc;
 use sqlx::PgPool;
@@ -22,9 +23,11 @@ impl ConnectorRepository for PgConnectorRepository {
             r#"
             INSERT INTO connectors (
                 connector_id, station_id, connector_type_id, status_id, current_type_id,
-                power_kw, voltage, amperage, count_available, count_total,
+                power_kw, voltage, amperage, count_available, count_total, data_source,
                 created_by, created_at, updated_by, updated_at
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+            )
+            SELECT $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15
+            WHERE EXISTS (SELECT 1 FROM stations WHERE station_id = $2 AND deleted_at IS NULL)
             RETURNING *
             "#,
         )
@@ -33,34 +36,38 @@ impl ConnectorRepository for PgConnectorRepository {
         .bind(connector.connector_type_id)
         .bind(connector.status_id)
         .bind(connector.current_type_id)
-        .bind(&connector.power_kw)
+        .bind(connector.power_kw)
         .bind(connector.voltage)
         .bind(connector.amperage)
         .bind(connector.count_available)
         .bind(connector.count_total)
+        .bind(&connector.data_source)
         .bind(&connector.created_by)
-        .bind(&connector.created_at)
+        .bind(connector.created_at)
         .bind(&connector.updated_by)
-        .bind(&connector.updated_at)
-        .fetch_one(&self.pool)
+        .bind(connector.updated_at)
+        .fetch_optional(&self.pool)
         .await?;
 
-        Ok(result)
+        result.ok_or_else(|| {
+            AppError::ValidationError(format!("Station {} not found", connector.station_id))
+        })
     }
 
     async fn find_by_id(&self, connector_id: &str) -> AppResult<Option<Connector>> {
-        let result =
-            sqlx::query_as::<_, Connector>("SELECT * FROM connectors WHERE connector_id = $1")
-                .bind(connector_id)
-                .fetch_optional(&self.pool)
-                .await?;
+        let result = sqlx::query_as::<_, Connector>(
+            "SELECT * FROM connectors WHERE connector_id = $1 AND deleted_at IS NULL",
+        )
+        .bind(connector_id)
+        .fetch_optional(&self.pool)
+        .await?;
 
         Ok(result)
     }
 
     async fn find_by_station(&self, station_id: &str) -> AppResult<Vec<Connector>> {
         let results = sqlx::query_as::<_, Connector>(
-            "SELECT * FROM connectors WHERE station_id = $1 ORDER BY created_at DESC",
+            "SELECT * FROM connectors WHERE station_id = $1 AND deleted_at IS NULL ORDER BY created_at DESC",
         )
         .bind(station_id)
         .fetch_all(&self.pool)
@@ -71,7 +78,7 @@ impl ConnectorRepository for PgConnectorRepository {
 
     async fn find_all(&self, limit: i64, offset: i64) -> AppResult<Vec<Connector>> {
         let results = sqlx::query_as::<_, Connector>(
-            "SELECT * FROM connectors ORDER BY created_at DESC LIMIT $1 OFFSET $2",
+            "SELECT * FROM connectors WHERE deleted_at IS NULL ORDER BY created_at DESC LIMIT $1 OFFSET $2",
         )
         .bind(limit)
         .bind(offset)
@@ -93,9 +100,10 @@ impl ConnectorRepository for PgConnectorRepository {
                 amperage = $7,
                 count_available = $8,
                 count_total = $9,
-                updated_by = $10,
-                updated_at = $11
-            WHERE connector_id = $1
+                data_source = $10,
+                updated_by = $11,
+                updated_at = $12
+            WHERE connector_id = $1 AND deleted_at IS NULL
             RETURNING *
             "#,
         )
@@ -103,11 +111,12 @@ impl ConnectorRepository for PgConnectorRepository {
         .bind(connector.connector_type_id)
         .bind(connector.status_id)
         .bind(connector.current_type_id)
-        .bind(&connector.power_kw)
+        .bind(connector.power_kw)
         .bind(connector.voltage)
         .bind(connector.amperage)
         .bind(connector.count_available)
         .bind(connector.count_total)
+        .bind(&connector.data_source)
         .bind(&connector.updated_by)
         .bind(Utc::now())
         .fetch_one(&self.pool)
@@ -116,19 +125,77 @@ impl ConnectorRepository for PgConnectorRepository {
         Ok(result)
     }
 
-    async fn delete(&self, connector_id: &str) -> AppResult<()> {
-        sqlx::query("DELETE FROM connectors WHERE connector_id = $1")
-            .bind(connector_id)
-            .execute(&self.pool)
-            .await?;
+    async fn delete(&self, connector_id: &str, deleted_by: &str) -> AppResult<()> {
+        sqlx::query(
+            r#"
+            UPDATE connectors SET deleted_at = NOW(), deleted_by = $2
+            WHERE connector_id = $1 AND deleted_at IS NULL
+            "#,
+        )
+        .bind(connector_id)
+        .bind(deleted_by)
+        .execute(&self.pool)
+        .await?;
+
+        refresh_station_views(&self.pool).await
+    }
+
+    async fn restore(&self, connector_id: &str, restored_by: &str) -> AppResult<Option<Connector>> {
+        let mut tx = self.pool.begin().await?;
 
-        Ok(())
+        let station_deleted: Option<(bool,)> = sqlx::query_as(
+            r#"
+            SELECT s.deleted_at IS NOT NULL
+            FROM connectors c
+            JOIN stations s ON s.station_id = c.station_id
+            WHERE c.connector_id = $1 AND c.deleted_at IS NOT NULL
+            FOR UPDATE OF c
+            "#,
+        )
+        .bind(connector_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        match station_deleted {
+            None => return Ok(None),
+            Some((true,)) => {
+                return Err(AppError::Conflict(format!(
+                    "The station of connector {} is deleted, restore it first",
+                    connector_id
+                )));
+            }
+            Some((false,)) => {}
+        }
+
+        let connector = sqlx::query_as::<_, Connector>(
+            r#"
+            UPDATE connectors
+            SET deleted_at = NULL, deleted_by = NULL, updated_by = $2, updated_at = NOW()
+            WHERE connector_id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(connector_id)
+        .bind(restored_by)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => AppError::Conflict(
+                "The station has a connector of this type again, delete it first".to_string(),
+            ),
+            e => e.into(),
+        })?;
+
+        tx.commit().await?;
+        refresh_station_views(&self.pool).await?;
+
+        Ok(Some(connector))
     }
 
     async fn count(&self) -> AppResult<i64> {
-        let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM connectors")
-            .fetch_one(&self.pool)
-            .await?;
+        let count: (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM connectors WHERE deleted_at IS NULL")
+                .fetch_one(&self.pool)
+                .await?;
 
         Ok(count.0)
     }
@@ -0,0 +1,91 @@
+use crate::core::errors::AppResult;
+use crate::domain::deleted::{DeletedRecordType, PurgeCounts};
+use crate::domain::entities::DeletedRecord;
+use crate::domain::repositories::DeletedRecordRepository;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+/// Deleted stations, connectors and networks as `d`. A connector deleted
+/// along with its station has the station's deletion time.
+const DELETED_RECORDS: &str = r#"
+    (
+        SELECT
+            'station' AS record_type, s.station_id AS record_id, s.name,
+            NULL::VARCHAR AS station_id, s.deleted_at, s.deleted_by
+        FROM stations s
+        WHERE s.deleted_at IS NOT NULL
+        UNION ALL
+        SELECT
+            'connector', c.connector_id, ct.name, c.station_id, c.deleted_at, c.deleted_by
+        FROM connectors c
+        JOIN connector_types ct ON ct.id = c.connector_type_id
+        JOIN stations s ON s.station_id = c.station_id
+        WHERE c.deleted_at IS NOT NULL
+          AND s.deleted_at IS DISTINCT FROM c.deleted_at
+        UNION ALL
+        SELECT
+            'network', n.network_id, n.name, NULL, n.deleted_at, n.deleted_by
+        FROM networks n
+        WHERE n.deleted_at IS NOT NULL
+    ) d
+"#;
+
+pub struct PgDeletedRecordRepository {
+    pool: PgPool,
+}
+
+impl PgDeletedRecordRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl DeletedRecordRepository for PgDeletedRecordRepository {
+    async fn find_all(
+        &self,
+        record_type: Option<DeletedRecordType>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<DeletedRecord>> {
+        let records = sqlx::query_as::<_, DeletedRecord>(&format!(
+            r#"
+            SELECT d.record_type, d.record_id, d.name, d.station_id, d.deleted_at, d.deleted_by
+            FROM {}
+            WHERE ($1::TEXT IS NULL OR d.record_type = $1)
+            ORDER BY d.deleted_at DESC, d.record_id
+            LIMIT $2 OFFSET $3
+            "#,
+            DELETED_RECORDS
+        ))
+        .bind(record_type.map(|t| t.as_str()))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    async fn count(&self, record_type: Option<DeletedRecordType>) -> AppResult<i64> {
+        let count: (i64,) = sqlx::query_as(&format!(
+            "SELECT COUNT(*) FROM {} WHERE ($1::TEXT IS NULL OR d.record_type = $1)",
+            DELETED_RECORDS
+        ))
+        .bind(record_type.map(|t| t.as_str()))
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count.0)
+    }
+
+    async fn purge(&self, deleted_before: DateTime<Utc>) -> AppResult<PurgeCounts> {
+        let counts = sqlx::query_as::<_, PurgeCounts>("SELECT * FROM purge_deleted_records($1)")
+            .bind(deleted_before)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(counts)
+    }
+}
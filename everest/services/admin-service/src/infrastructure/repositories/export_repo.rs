@@ -0,0 +1,81 @@
+use crate::core::constants::EXPORT_PREFETCH_ROWS;
+use crate::core::errors::AppResult;
+use crate::domain::entities::StationExportRow;
+use crate::domain::export::ExportFilter;
+use crate::domain::repositories::ExportRepository;
+use futures_util::StreamExt;
+use futures_util::stream::{self, BoxStream};
+use sqlx::PgPool;
+use tokio::sync::mpsc;
+
+pub struct PgExportRepository {
+    pool: PgPool,
+}
+
+impl PgExportRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl ExportRepository for PgExportRepository {
+    fn stream_stations(
+        &self,
+        filter: ExportFilter,
+    ) -> BoxStream<'static, AppResult<StationExportRow>> {
+        // The row stream borrows the pool, so it is driven by its own task and
+        // handed over through a bounded channel; the query stops when the
+        // client goes away and the channel closes
+        let pool = self.pool.clone();
+        let (tx, rx) = mpsc::channel(EXPORT_PREFETCH_ROWS);
+
+        tokio::spawn(async move {
+            let bbox = filter.bbox;
+            let mut rows = sqlx::query_as::<_, StationExportRow>(
+                r#"
+                SELECT
+                    s.station_id, s.external_ref, s.osm_id, s.name, s.address,
+                    ST_Y(s.location::geometry) AS latitude,
+                    ST_X(s.location::geometry) AS longitude,
+                    s.network_id, n.name AS network_name,
+                    c.connector_id, ct.name AS connector_type, cur.name AS current_type,
+                    cs.name AS status, c.power_kw::FLOAT8 AS power_kw, c.voltage, c.amperage,
+                    c.count_total, c.count_available,
+                    COALESCE(s.updated_at, s.created_at) AS updated_at
+                FROM stations s
+                LEFT JOIN networks n ON n.network_id = s.network_id
+                LEFT JOIN connectors c ON c.station_id = s.station_id AND c.deleted_at IS NULL
+                LEFT JOIN connector_types ct ON ct.id = c.connector_type_id
+                LEFT JOIN current_types cur ON cur.id = c.current_type_id
+                LEFT JOIN connector_statuses cs ON cs.id = c.status_id
+                WHERE s.deleted_at IS NULL
+                  AND ($1::VARCHAR IS NULL OR s.network_id = $1)
+                  AND ($2::FLOAT8 IS NULL OR ST_Intersects(
+                      s.location,
+                      ST_MakeEnvelope($2, $3, $4, $5, 4326)::geography
+                  ))
+                ORDER BY s.station_id, c.connector_id
+                "#,
+            )
+            .bind(&filter.network_id)
+            .bind(bbox.map(|b| b.min_lon))
+            .bind(bbox.map(|b| b.min_lat))
+            .bind(bbox.map(|b| b.max_lon))
+            .bind(bbox.map(|b| b.max_lat))
+            .fetch(&pool);
+
+            while let Some(row) = rows.next().await {
+                let failed = row.is_err();
+                if tx.send(row.map_err(Into::into)).await.is_err() || failed {
+                    break;
+                }
+            }
+        });
+
+        stream::unfold(
+            rx,
+            |mut rx| async move { rx.recv().await.map(|row| (row, rx)) },
+        )
+        .boxed()
+    }
+}
@@ -0,0 +1,163 @@
+use crate::core::errors::AppResult;
+use crate::domain::entities::{ConnectorType, CurrentType};
+use crate::domain::import::PlannedStation;
+use crate::domain::provenance::StationField;
+use crate::domain::repositories::ImportRepository;
+use crate::infrastructure::repositories::refresh_station_views;
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+pub struct PgImportRepository {
+    pool: PgPool,
+}
+
+impl PgImportRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ImportRepository for PgImportRepository {
+    async fn find_connector_types(&self) -> AppResult<Vec<ConnectorType>> {
+        let types = sqlx::query_as::<_, ConnectorType>(
+            "SELECT id::BIGINT AS id, name, description FROM connector_types",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(types)
+    }
+
+    async fn find_current_types(&self) -> AppResult<Vec<CurrentType>> {
+        let types = sqlx::query_as::<_, CurrentType>(
+            "SELECT id::BIGINT AS id, name, description FROM current_types",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(types)
+    }
+
+    async fn find_existing_networks(&self, network_ids: &[String]) -> AppResult<Vec<String>> {
+        let rows: Vec<(String,)> = sqlx::query_as(
+            "SELECT network_id FROM networks WHERE network_id = ANY($1) AND deleted_at IS NULL",
+        )
+        .bind(network_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|r| r.0).collect())
+    }
+
+    async fn find_existing_osm_ids(&self, osm_ids: &[i64]) -> AppResult<Vec<i64>> {
+        let rows: Vec<(i64,)> =
+            sqlx::query_as("SELECT osm_id FROM stations WHERE osm_id = ANY($1)")
+                .bind(osm_ids)
+                .fetch_all(&self.pool)
+                .await?;
+
+        Ok(rows.into_iter().map(|r| r.0).collect())
+    }
+
+    async fn find_existing_refs(
+        &self,
+        external_refs: &[String],
+    ) -> AppResult<Vec<(String, String)>> {
+        let rows: Vec<(String, String)> = sqlx::query_as(
+            r#"
+            SELECT network_id, external_ref FROM stations
+            WHERE external_ref = ANY($1) AND network_id IS NOT NULL
+            "#,
+        )
+        .bind(external_refs)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    async fn import_stations(
+        &self,
+        stations: &[PlannedStation],
+        created_by: &str,
+    ) -> AppResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        for station in stations {
+            sqlx::query(
+                r#"
+                INSERT INTO stations (
+                    station_id, osm_id, name, address, location, network_id, external_ref, created_by
+                ) VALUES (
+                    $1, COALESCE($2, nextval('imported_station_osm_id_seq')), $3, $4,
+                    ST_SetSRID(ST_MakePoint($5, $6), 4326)::geography, $7, $8, $9
+                )
+                "#,
+            )
+            .bind(&station.station_id)
+            .bind(station.osm_id)
+            .bind(&station.name)
+            .bind(&station.address)
+            .bind(station.longitude)
+            .bind(station.latitude)
+            .bind(&station.network_id)
+            .bind(&station.external_ref)
+            .bind(created_by)
+            .execute(&mut *tx)
+            .await?;
+
+            // Partner data: the OSM sync leaves these stations as imported
+            sqlx::query(
+                r#"
+                INSERT INTO station_field_sources (station_id, field, data_source, updated_by)
+                SELECT $1, field, 'commercial', $2
+                FROM UNNEST($3::VARCHAR[]) AS field
+                "#,
+            )
+            .bind(&station.station_id)
+            .bind(created_by)
+            .bind(StationField::ALL.map(|f| f.as_str()).as_slice())
+            .execute(&mut *tx)
+            .await?;
+
+            for connector in &station.connectors {
+                sqlx::query(
+                    r#"
+                    INSERT INTO connectors (
+                        connector_id, station_id, connector_type_id, status_id, current_type_id,
+                        power_kw, voltage, amperage, count_available, count_total, data_source,
+                        created_by
+                    ) VALUES (
+                        $1, $2, $3, (SELECT id FROM connector_statuses WHERE name = 'available'), $4,
+                        $5, $6, $7, $8, $9, 'commercial', $10
+                    )
+                    "#,
+                )
+                .bind(&connector.connector_id)
+                .bind(&station.station_id)
+                .bind(connector.connector_type_id)
+                .bind(connector.current_type_id)
+                .bind(connector.power_kw)
+                .bind(connector.voltage)
+                .bind(connector.amperage)
+                .bind(connector.count_available)
+                .bind(connector.count_total)
+                .bind(created_by)
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+
+        tx.commit().await?;
+
+        // Make the new stations searchable right away, without blocking
+        // searches while the views are rebuilt. The import is committed
+        // either way; a failed refresh is caught up by the next one.
+        if let Err(e) = refresh_station_views(&self.pool).await {
+            tracing::warn!("Failed to refresh the station views after an import: {}", e);
+        }
+
+        Ok(())
+    }
+}
@@ -1,3 +1,46 @@
 pub mod connector_repo;
+pub mod deleted_record_repo;
+pub mod export_repo;
+pub mod import_repo;
 pub mod network_repo;
+pub mod network_verification_repo;
+pub mod ocpi_party_repo;
+pub mod ocpi_repo;
+pub mod osm_staging_repo;
+pub mod provenance_repo;
+pub mod region_repo;
+pub mod review_moderation_repo;
+pub mod station_merge_repo;
 pub mod station_repo;
+pub mod sync_run_repo;
+
+use crate::core::errors::AppResult;
+use sqlx::PgPool;
+
+/// Views built from stations and connectors. Each has a unique index, so
+/// they refresh concurrently without blocking searches.
+const STATION_VIEWS: [&str; 4] = [
+    "mv_stations_geo",
+    "mv_stations_summary",
+    "mv_connector_type_stats",
+    "mv_stations_reviews",
+];
+
+/// Refreshes the station views, reviews included, after stations or
+/// connectors were imported, deleted, restored or merged
+pub(crate) async fn refresh_station_views(pool: &PgPool) -> AppResult<()> {
+    for view in STATION_VIEWS {
+        sqlx::query(&format!("REFRESH MATERIALIZED VIEW CONCURRENTLY {}", view))
+            .execute(pool)
+            .await?;
+    }
+    Ok(())
+}
+
+/// Refreshes the review aggregates after a review was hidden or restored
+pub(crate) async fn refresh_review_view(pool: &PgPool) -> AppResult<()> {
+    sqlx::query("REFRESH MATERIALIZED VIEW CONCURRENTLY mv_stations_reviews")
+        .execute(pool)
+        .await?;
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use crate::core::errors::AppResult;
+use crate::core::errors::{AppError, AppResult};
 use crate::domain::entities::Network;
 use crate::domain::repositories::NetworkRepository;
 use async_trait::async_trait;
@@ -32,9 +32,9 @@ impl NetworkRepository for PgNetworkRepository {
         .bind(&network.network_type)
         .bind(&network.support_phone)
         .bind(&network.support_email)
-        .bind(&network.is_verified)
-        .bind(&network.created_at)
-        .bind(&network.updated_at)
+        .bind(network.is_verified)
+        .bind(network.created_at)
+        .bind(network.updated_at)
         .bind(&network.created_by)
         .bind(&network.updated_by)
         .fetch_one(&self.pool)
@@ -44,17 +44,19 @@ impl NetworkRepository for PgNetworkRepository {
     }
 
     async fn find_by_id(&self, network_id: &str) -> AppResult<Option<Network>> {
-        let result = sqlx::query_as::<_, Network>("SELECT * FROM networks WHERE network_id = $1")
-            .bind(network_id)
-            .fetch_optional(&self.pool)
-            .await?;
+        let result = sqlx::query_as::<_, Network>(
+            "SELECT * FROM networks WHERE network_id = $1 AND deleted_at IS NULL",
+        )
+        .bind(network_id)
+        .fetch_optional(&self.pool)
+        .await?;
 
         Ok(result)
     }
 
     async fn find_all(&self, limit: i64, offset: i64) -> AppResult<Vec<Network>> {
         let results = sqlx::query_as::<_, Network>(
-            "SELECT * FROM networks ORDER BY created_at DESC LIMIT $1 OFFSET $2",
+            "SELECT * FROM networks WHERE deleted_at IS NULL ORDER BY created_at DESC LIMIT $1 OFFSET $2",
         )
         .bind(limit)
         .bind(offset)
@@ -72,10 +74,9 @@ impl NetworkRepository for PgNetworkRepository {
                 network_type = $3,
                 support_phone = $4,
                 support_email = $5,
-                is_verified = $6,
-                updated_at = $7,
-                updated_by = $8
-            WHERE network_id = $1
+                updated_at = $6,
+                updated_by = $7
+            WHERE network_id = $1 AND deleted_at IS NULL
             RETURNING *
             "#,
         )
@@ -84,7 +85,6 @@ impl NetworkRepository for PgNetworkRepository {
         .bind(&network.network_type)
         .bind(&network.support_phone)
         .bind(&network.support_email)
-        .bind(&network.is_verified)
         .bind(Utc::now())
         .bind(&network.updated_by)
         .fetch_one(&self.pool)
@@ -93,19 +93,67 @@ impl NetworkRepository for PgNetworkRepository {
         Ok(result)
     }
 
-    async fn delete(&self, network_id: &str) -> AppResult<()> {
-        sqlx::query("DELETE FROM networks WHERE network_id = $1")
-            .bind(network_id)
-            .execute(&self.pool)
-            .await?;
+    async fn delete(&self, network_id: &str, deleted_by: &str) -> AppResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        // Locked so that no station is put on it meanwhile
+        let network = sqlx::query(
+            "SELECT 1 FROM networks WHERE network_id = $1 AND deleted_at IS NULL FOR UPDATE",
+        )
+        .bind(network_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        if network.is_none() {
+            return Ok(());
+        }
+
+        let has_stations: (bool,) = sqlx::query_as(
+            "SELECT EXISTS(SELECT 1 FROM stations WHERE network_id = $1 AND deleted_at IS NULL)",
+        )
+        .bind(network_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        if has_stations.0 {
+            return Err(AppError::Conflict(format!(
+                "Network {} still has stations, delete or move them first",
+                network_id
+            )));
+        }
+
+        sqlx::query(
+            "UPDATE networks SET deleted_at = NOW(), deleted_by = $2 WHERE network_id = $1",
+        )
+        .bind(network_id)
+        .bind(deleted_by)
+        .execute(&mut *tx)
+        .await?;
 
+        tx.commit().await?;
         Ok(())
     }
 
+    async fn restore(&self, network_id: &str, restored_by: &str) -> AppResult<Option<Network>> {
+        let result = sqlx::query_as::<_, Network>(
+            r#"
+            UPDATE networks
+            SET deleted_at = NULL, deleted_by = NULL, updated_by = $2, updated_at = NOW()
+            WHERE network_id = $1 AND deleted_at IS NOT NULL
+            RETURNING *
+            "#,
+        )
+        .bind(network_id)
+        .bind(restored_by)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result)
+    }
+
     async fn count(&self) -> AppResult<i64> {
-        let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM networks")
-            .fetch_one(&self.pool)
-            .await?;
+        let count: (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM networks WHERE deleted_at IS NULL")
+                .fetch_one(&self.pool)
+                .await?;
 
         Ok(count.0)
     }
@@ -0,0 +1,342 @@
+use crate::core::constants::NOTIFICATION_ID_PREFIX;
+use crate::core::errors::AppResult;
+use crate::core::utils::generate_id;
+use crate::domain::entities::{
+    NetworkVerificationRequest, VerificationDocument, VerificationEvent,
+};
+use crate::domain::repositories::NetworkVerificationRepository;
+use crate::domain::verification::{VerificationNotification, VerificationStatus};
+use async_trait::async_trait;
+use sqlx::{PgPool, Postgres, Transaction};
+
+pub struct PgNetworkVerificationRepository {
+    pool: PgPool,
+}
+
+impl PgNetworkVerificationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+async fn record_event(
+    tx: &mut Transaction<'_, Postgres>,
+    network_id: &str,
+    request_id: Option<&str>,
+    from: VerificationStatus,
+    to: VerificationStatus,
+    actor_id: &str,
+    comment: Option<&str>,
+) -> AppResult<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO network_verification_events (
+            network_id, request_id, from_status, to_status, actor_id, comment
+        )
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+    )
+    .bind(network_id)
+    .bind(request_id)
+    .bind(from.as_str())
+    .bind(to.as_str())
+    .bind(actor_id)
+    .bind(comment)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// Sets `networks.is_verified`; an unverified network loses its active tariff
+async fn set_network_verified(
+    tx: &mut Transaction<'_, Postgres>,
+    network_id: &str,
+    verified: bool,
+    actor_id: &str,
+) -> AppResult<()> {
+    sqlx::query(
+        r#"
+        UPDATE networks
+        SET is_verified = $2, updated_by = $3, updated_at = NOW()
+        WHERE network_id = $1
+        "#,
+    )
+    .bind(network_id)
+    .bind(verified)
+    .bind(actor_id)
+    .execute(&mut **tx)
+    .await?;
+
+    if !verified {
+        sqlx::query(
+            r#"
+            UPDATE tariffs
+            SET is_active = FALSE, updated_by = $2, updated_at = NOW()
+            WHERE network_id = $1 AND is_active
+            "#,
+        )
+        .bind(network_id)
+        .bind(actor_id)
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    Ok(())
+}
+
+#[async_trait]
+impl NetworkVerificationRepository for PgNetworkVerificationRepository {
+    async fn find_by_id(&self, request_id: &str) -> AppResult<Option<NetworkVerificationRequest>> {
+        let request = sqlx::query_as::<_, NetworkVerificationRequest>(
+            "SELECT * FROM network_verification_requests WHERE request_id = $1",
+        )
+        .bind(request_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(request)
+    }
+
+    async fn find_all<'a>(
+        &self,
+        network_id: Option<&'a str>,
+        status: Option<&'a str>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<NetworkVerificationRequest>> {
+        let requests = sqlx::query_as::<_, NetworkVerificationRequest>(
+            r#"
+            SELECT * FROM network_verification_requests
+            WHERE ($1::VARCHAR IS NULL OR network_id = $1)
+              AND ($2::VARCHAR IS NULL OR status = $2)
+            ORDER BY submitted_at DESC
+            LIMIT $3 OFFSET $4
+            "#,
+        )
+        .bind(network_id)
+        .bind(status)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(requests)
+    }
+
+    async fn count<'a>(
+        &self,
+        network_id: Option<&'a str>,
+        status: Option<&'a str>,
+    ) -> AppResult<i64> {
+        let count: (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*) FROM network_verification_requests
+            WHERE ($1::VARCHAR IS NULL OR network_id = $1)
+              AND ($2::VARCHAR IS NULL OR status = $2)
+            "#,
+        )
+        .bind(network_id)
+        .bind(status)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count.0)
+    }
+
+    async fn find_approved(
+        &self,
+        network_id: &str,
+    ) -> AppResult<Option<NetworkVerificationRequest>> {
+        let request = sqlx::query_as::<_, NetworkVerificationRequest>(
+            r#"
+            SELECT * FROM network_verification_requests
+            WHERE network_id = $1 AND status = 'approved'
+            ORDER BY reviewed_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(network_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(request)
+    }
+
+    async fn transition<'a>(
+        &self,
+        request_id: &str,
+        from: VerificationStatus,
+        to: VerificationStatus,
+        actor_id: &str,
+        comment: Option<&'a str>,
+        notification: &VerificationNotification,
+    ) -> AppResult<Option<NetworkVerificationRequest>> {
+        let mut tx = self.pool.begin().await?;
+
+        let request = sqlx::query_as::<_, NetworkVerificationRequest>(
+            r#"
+            UPDATE network_verification_requests
+            SET status = $3, reviewed_by = $4, reviewed_at = NOW(), review_comment = $5
+            WHERE request_id = $1 AND status = $2
+            RETURNING *
+            "#,
+        )
+        .bind(request_id)
+        .bind(from.as_str())
+        .bind(to.as_str())
+        .bind(actor_id)
+        .bind(comment)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(request) = request else {
+            return Ok(None);
+        };
+
+        record_event(
+            &mut tx,
+            &request.network_id,
+            Some(request_id),
+            from,
+            to,
+            actor_id,
+            comment,
+        )
+        .await?;
+
+        match to {
+            VerificationStatus::Approved => {
+                set_network_verified(&mut tx, &request.network_id, true, actor_id).await?
+            }
+            VerificationStatus::Revoked => {
+                set_network_verified(&mut tx, &request.network_id, false, actor_id).await?
+            }
+            VerificationStatus::Pending | VerificationStatus::Rejected => {}
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO user_notifications (notification_id, user_id, kind, message)
+            VALUES ($1, $2, $3, $4)
+            "#,
+        )
+        .bind(generate_id(NOTIFICATION_ID_PREFIX))
+        .bind(&request.submitted_by)
+        .bind(notification.kind)
+        .bind(&notification.message)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(request))
+    }
+
+    async fn revoke_network<'a>(
+        &self,
+        network_id: &str,
+        actor_id: &str,
+        comment: Option<&'a str>,
+    ) -> AppResult<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        let verified = sqlx::query(
+            r#"
+            SELECT 1 FROM networks
+            WHERE network_id = $1 AND is_verified AND deleted_at IS NULL
+            FOR UPDATE
+            "#,
+        )
+        .bind(network_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        if verified.is_none() {
+            return Ok(false);
+        }
+
+        set_network_verified(&mut tx, network_id, false, actor_id).await?;
+        record_event(
+            &mut tx,
+            network_id,
+            None,
+            VerificationStatus::Approved,
+            VerificationStatus::Revoked,
+            actor_id,
+            comment,
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn find_network_verified(&self, network_id: &str) -> AppResult<Option<bool>> {
+        let verified: Option<(bool,)> = sqlx::query_as(
+            r#"
+            SELECT COALESCE(is_verified, FALSE)
+            FROM networks
+            WHERE network_id = $1 AND deleted_at IS NULL
+            "#,
+        )
+        .bind(network_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(verified.map(|v| v.0))
+    }
+
+    async fn find_documents(&self, request_id: &str) -> AppResult<Vec<VerificationDocument>> {
+        let documents = sqlx::query_as::<_, VerificationDocument>(
+            r#"
+            SELECT * FROM network_verification_documents
+            WHERE request_id = $1
+            ORDER BY uploaded_at
+            "#,
+        )
+        .bind(request_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(documents)
+    }
+
+    async fn count_documents(&self, request_id: &str) -> AppResult<i64> {
+        let count: (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM network_verification_documents WHERE request_id = $1",
+        )
+        .bind(request_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count.0)
+    }
+
+    async fn find_events_by_request(&self, request_id: &str) -> AppResult<Vec<VerificationEvent>> {
+        let events = sqlx::query_as::<_, VerificationEvent>(
+            r#"
+            SELECT * FROM network_verification_events
+            WHERE request_id = $1
+            ORDER BY created_at, event_id
+            "#,
+        )
+        .bind(request_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(events)
+    }
+
+    async fn find_events_by_network(&self, network_id: &str) -> AppResult<Vec<VerificationEvent>> {
+        let events = sqlx::query_as::<_, VerificationEvent>(
+            r#"
+            SELECT * FROM network_verification_events
+            WHERE network_id = $1
+            ORDER BY created_at, event_id
+            "#,
+        )
+        .bind(network_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(events)
+    }
+}
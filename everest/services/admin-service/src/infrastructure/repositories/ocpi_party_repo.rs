@@ -0,0 +1,166 @@
+use crate::core::errors::AppResult;
+use crate::domain::entities::OcpiParty;
+use crate::domain::repositories::OcpiPartyRepository;
+use crate::domain::value_objects::OcpiRegistrationData;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+pub struct PgOcpiPartyRepository {
+    pool: PgPool,
+}
+
+impl PgOcpiPartyRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl OcpiPartyRepository for PgOcpiPartyRepository {
+    async fn create(&self, party: &OcpiParty) -> AppResult<OcpiParty> {
+        let created = sqlx::query_as::<_, OcpiParty>(
+            r#"
+            INSERT INTO ocpi_parties (party_uid, name, status, token_a, created_by)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *
+            "#,
+        )
+        .bind(&party.party_uid)
+        .bind(&party.name)
+        .bind(&party.status)
+        .bind(&party.token_a)
+        .bind(&party.created_by)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(created)
+    }
+
+    async fn find_all(&self) -> AppResult<Vec<OcpiParty>> {
+        let parties =
+            sqlx::query_as::<_, OcpiParty>("SELECT * FROM ocpi_parties ORDER BY created_at DESC")
+                .fetch_all(&self.pool)
+                .await?;
+
+        Ok(parties)
+    }
+
+    async fn find_by_id(&self, party_uid: &str) -> AppResult<Option<OcpiParty>> {
+        let party =
+            sqlx::query_as::<_, OcpiParty>("SELECT * FROM ocpi_parties WHERE party_uid = $1")
+                .bind(party_uid)
+                .fetch_optional(&self.pool)
+                .await?;
+
+        Ok(party)
+    }
+
+    async fn find_by_token(&self, token: &str) -> AppResult<Option<OcpiParty>> {
+        let party = sqlx::query_as::<_, OcpiParty>(
+            r#"
+            SELECT * FROM ocpi_parties
+            WHERE (status = 'pending' AND token_a = $1)
+               OR (status = 'registered' AND token_c = $1)
+            "#,
+        )
+        .bind(token)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(party)
+    }
+
+    async fn find_registered(&self) -> AppResult<Vec<OcpiParty>> {
+        let parties = sqlx::query_as::<_, OcpiParty>(
+            "SELECT * FROM ocpi_parties WHERE status = 'registered' ORDER BY registered_at",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(parties)
+    }
+
+    async fn register(
+        &self,
+        party_uid: &str,
+        registration: &OcpiRegistrationData,
+    ) -> AppResult<Option<OcpiParty>> {
+        let party = sqlx::query_as::<_, OcpiParty>(
+            r#"
+            UPDATE ocpi_parties
+            SET country_code = $2,
+                party_id = $3,
+                role = $4,
+                token_a = NULL,
+                token_b = $5,
+                token_c = $6,
+                versions_url = $7,
+                locations_url = $8,
+                locations_pushed_until = CASE WHEN status = 'pending' THEN NULL ELSE locations_pushed_until END,
+                locations_pushed_until_id = CASE WHEN status = 'pending' THEN NULL ELSE locations_pushed_until_id END,
+                last_push_error = NULL,
+                registered_at = CASE WHEN status = 'pending' THEN NOW() ELSE registered_at END,
+                status = 'registered',
+                updated_at = NOW()
+            WHERE party_uid = $1 AND status IN ('pending', 'registered')
+            RETURNING *
+            "#,
+        )
+        .bind(party_uid)
+        .bind(&registration.country_code)
+        .bind(&registration.party_id)
+        .bind(&registration.role)
+        .bind(&registration.token_b)
+        .bind(&registration.token_c)
+        .bind(&registration.versions_url)
+        .bind(&registration.locations_url)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(party)
+    }
+
+    async fn unregister(&self, party_uid: &str) -> AppResult<Option<OcpiParty>> {
+        let party = sqlx::query_as::<_, OcpiParty>(
+            r#"
+            UPDATE ocpi_parties
+            SET status = 'unregistered', token_a = NULL, token_c = NULL, updated_at = NOW()
+            WHERE party_uid = $1 AND status IN ('pending', 'registered')
+            RETURNING *
+            "#,
+        )
+        .bind(party_uid)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(party)
+    }
+
+    async fn record_push(
+        &self,
+        party_uid: &str,
+        cursor: Option<(DateTime<Utc>, String)>,
+        error: Option<&str>,
+    ) -> AppResult<()> {
+        let (pushed_until, pushed_until_id) = cursor.unzip();
+        sqlx::query(
+            r#"
+            UPDATE ocpi_parties
+            SET locations_pushed_until = $2,
+                locations_pushed_until_id = $3,
+                last_push_error = $4,
+                last_push_at = NOW()
+            WHERE party_uid = $1 AND status = 'registered'
+            "#,
+        )
+        .bind(party_uid)
+        .bind(pushed_until)
+        .bind(pushed_until_id)
+        .bind(error)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
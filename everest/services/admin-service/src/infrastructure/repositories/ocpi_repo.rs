@@ -0,0 +1,278 @@
+use crate::core::errors::AppResult;
+use crate::domain::entities::{
+    OcpiConnectorRow, OcpiLocationRow, OcpiTariffComponentRow, OcpiTariffRow,
+};
+use crate::domain::repositories::OcpiRepository;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+/// Stations of an operator network. A location is removed once the station
+/// is deleted or deactivated, or its network loses its verification; the
+/// tariffs lateral covers deactivated tariffs in last_updated.
+const LOCATIONS: &str = r#"
+    SELECT * FROM (
+        SELECT
+            s.station_id,
+            s.name,
+            s.address,
+            s.tags -> 'addr:city' AS city,
+            s.tags -> 'addr:postcode' AS postal_code,
+            ST_Y(s.location::GEOMETRY) AS latitude,
+            ST_X(s.location::GEOMETRY) AS longitude,
+            nw.name AS operator_name,
+            t.tariff_id,
+            (
+                s.deleted_at IS NOT NULL
+                OR s.deactivated_at IS NOT NULL
+                OR nw.deleted_at IS NOT NULL
+                OR NOT COALESCE(nw.is_verified, FALSE)
+            ) AS removed,
+            GREATEST(
+                s.created_at, s.updated_at, s.deactivated_at, s.deleted_at,
+                nw.updated_at, nw.deleted_at, t.last_updated, c.last_updated
+            ) AS last_updated
+        FROM stations s
+        JOIN networks nw ON nw.network_id = s.network_id
+        LEFT JOIN LATERAL (
+            SELECT
+                MAX(tariff_id) FILTER (WHERE is_active) AS tariff_id,
+                MAX(GREATEST(created_at, updated_at)) AS last_updated
+            FROM tariffs
+            WHERE network_id = nw.network_id
+        ) t ON TRUE
+        LEFT JOIN LATERAL (
+            SELECT MAX(GREATEST(created_at, updated_at, deleted_at)) AS last_updated
+            FROM connectors
+            WHERE station_id = s.station_id
+        ) c ON TRUE
+    ) l
+"#;
+
+const LOCATIONS_WINDOW: &str = r#"
+    WHERE (($1::TIMESTAMPTZ IS NULL AND NOT l.removed) OR l.last_updated >= $1)
+      AND ($2::TIMESTAMPTZ IS NULL OR l.last_updated < $2)
+"#;
+
+const TARIFFS: &str = r#"
+    SELECT * FROM (
+        SELECT
+            t.tariff_id,
+            t.currency,
+            t.vat_percent::FLOAT8 AS vat_percent,
+            t.min_price::FLOAT8 AS min_price,
+            t.max_price::FLOAT8 AS max_price,
+            GREATEST(t.created_at, t.updated_at) AS last_updated
+        FROM tariffs t
+        JOIN networks nw ON nw.network_id = t.network_id
+        WHERE t.is_active AND nw.is_verified AND nw.deleted_at IS NULL
+    ) t
+    WHERE ($1::TIMESTAMPTZ IS NULL OR t.last_updated >= $1)
+      AND ($2::TIMESTAMPTZ IS NULL OR t.last_updated < $2)
+"#;
+
+pub struct PgOcpiRepository {
+    pool: PgPool,
+}
+
+impl PgOcpiRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Fills in the connectors, deleted ones included
+    async fn with_connectors(
+        &self,
+        mut locations: Vec<OcpiLocationRow>,
+    ) -> AppResult<Vec<OcpiLocationRow>> {
+        if locations.is_empty() {
+            return Ok(locations);
+        }
+
+        let station_ids: Vec<String> = locations.iter().map(|l| l.station_id.clone()).collect();
+        let connectors = sqlx::query_as::<_, OcpiConnectorRow>(
+            r#"
+            SELECT
+                c.station_id,
+                c.connector_id,
+                ct.name AS connector_type,
+                cu.name AS current_type,
+                cs.name AS status,
+                c.power_kw::FLOAT8 AS power_kw,
+                c.voltage,
+                c.amperage,
+                COALESCE(c.count_available, 0) AS count_available,
+                COALESCE(c.count_total, 1) AS count_total,
+                c.deleted_at IS NOT NULL AS removed,
+                GREATEST(c.created_at, c.updated_at, c.deleted_at) AS last_updated
+            FROM connectors c
+            JOIN connector_types ct ON ct.id = c.connector_type_id
+            JOIN current_types cu ON cu.id = c.current_type_id
+            JOIN connector_statuses cs ON cs.id = c.status_id
+            WHERE c.station_id = ANY($1)
+            ORDER BY c.station_id, c.created_at, c.connector_id
+            "#,
+        )
+        .bind(&station_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        for connector in connectors {
+            if let Some(location) = locations
+                .iter_mut()
+                .find(|l| l.station_id == connector.station_id)
+            {
+                location.connectors.push(connector);
+            }
+        }
+
+        Ok(locations)
+    }
+}
+
+#[async_trait]
+impl OcpiRepository for PgOcpiRepository {
+    async fn find_locations(
+        &self,
+        date_from: Option<DateTime<Utc>>,
+        date_to: Option<DateTime<Utc>>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<OcpiLocationRow>> {
+        let query = format!(
+            "{} {} ORDER BY l.last_updated, l.station_id LIMIT $3 OFFSET $4",
+            LOCATIONS, LOCATIONS_WINDOW
+        );
+        let locations = sqlx::query_as::<_, OcpiLocationRow>(&query)
+            .bind(date_from)
+            .bind(date_to)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&self.pool)
+            .await?;
+
+        self.with_connectors(locations).await
+    }
+
+    async fn count_locations(
+        &self,
+        date_from: Option<DateTime<Utc>>,
+        date_to: Option<DateTime<Utc>>,
+    ) -> AppResult<i64> {
+        let query = format!(
+            "SELECT COUNT(*) FROM ({} {}) counted",
+            LOCATIONS, LOCATIONS_WINDOW
+        );
+        let count: (i64,) = sqlx::query_as(&query)
+            .bind(date_from)
+            .bind(date_to)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(count.0)
+    }
+
+    async fn find_location(&self, location_id: &str) -> AppResult<Option<OcpiLocationRow>> {
+        let query = format!("{} WHERE l.station_id = $1", LOCATIONS);
+        let location = sqlx::query_as::<_, OcpiLocationRow>(&query)
+            .bind(location_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(self
+            .with_connectors(location.into_iter().collect())
+            .await?
+            .pop())
+    }
+
+    async fn find_locations_after(
+        &self,
+        cursor: Option<(DateTime<Utc>, String)>,
+        settle_seconds: i64,
+        limit: i64,
+    ) -> AppResult<Vec<OcpiLocationRow>> {
+        let (updated, location_id) = cursor.unzip();
+        let query = format!(
+            r#"
+            {}
+            WHERE (($1::TIMESTAMPTZ IS NULL AND NOT l.removed)
+                   OR (l.last_updated, l.station_id) > ($1, $2))
+              AND l.last_updated < NOW() - make_interval(secs => $3)
+            ORDER BY l.last_updated, l.station_id
+            LIMIT $4
+            "#,
+            LOCATIONS
+        );
+        let locations = sqlx::query_as::<_, OcpiLocationRow>(&query)
+            .bind(updated)
+            .bind(location_id)
+            .bind(settle_seconds as f64)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        self.with_connectors(locations).await
+    }
+
+    async fn find_tariffs(
+        &self,
+        date_from: Option<DateTime<Utc>>,
+        date_to: Option<DateTime<Utc>>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<OcpiTariffRow>> {
+        let query = format!(
+            "{} ORDER BY t.last_updated, t.tariff_id LIMIT $3 OFFSET $4",
+            TARIFFS
+        );
+        let mut tariffs = sqlx::query_as::<_, OcpiTariffRow>(&query)
+            .bind(date_from)
+            .bind(date_to)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&self.pool)
+            .await?;
+        if tariffs.is_empty() {
+            return Ok(tariffs);
+        }
+
+        let tariff_ids: Vec<String> = tariffs.iter().map(|t| t.tariff_id.clone()).collect();
+        let components = sqlx::query_as::<_, OcpiTariffComponentRow>(
+            r#"
+            SELECT tariff_id, component_type, price::FLOAT8 AS price, step_size
+            FROM tariff_components
+            WHERE tariff_id = ANY($1)
+            ORDER BY tariff_id, id
+            "#,
+        )
+        .bind(&tariff_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        for component in components {
+            if let Some(tariff) = tariffs
+                .iter_mut()
+                .find(|t| t.tariff_id == component.tariff_id)
+            {
+                tariff.components.push(component);
+            }
+        }
+
+        Ok(tariffs)
+    }
+
+    async fn count_tariffs(
+        &self,
+        date_from: Option<DateTime<Utc>>,
+        date_to: Option<DateTime<Utc>>,
+    ) -> AppResult<i64> {
+        let query = format!("SELECT COUNT(*) FROM ({}) counted", TARIFFS);
+        let count: (i64,) = sqlx::query_as(&query)
+            .bind(date_from)
+            .bind(date_to)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(count.0)
+    }
+}
@@ -0,0 +1,209 @@
+use crate::core::constants::OSM_STAGING_BATCH_ROWS;
+use crate::core::errors::AppResult;
+use crate::domain::osm_import::{OsmStagingChanges, StagedOsmStation};
+use crate::domain::repositories::OsmStagingRepository;
+use async_trait::async_trait;
+use sqlx::{PgConnection, PgPool};
+
+pub struct PgOsmStagingRepository {
+    pool: PgPool,
+}
+
+impl PgOsmStagingRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl OsmStagingRepository for PgOsmStagingRepository {
+    async fn replace_staged_stations(
+        &self,
+        stations: &[StagedOsmStation],
+        sequence_number: Option<i64>,
+    ) -> AppResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM osm_charging_stations_temp")
+            .execute(&mut *tx)
+            .await?;
+        upsert_staged_stations(&mut tx, stations).await?;
+        set_replication_sequence(&mut tx, sequence_number).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn has_staged_stations(&self) -> AppResult<bool> {
+        let exists: bool =
+            sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM osm_charging_stations_temp)")
+                .fetch_one(&self.pool)
+                .await?;
+
+        Ok(exists)
+    }
+
+    async fn find_replication_sequence(&self) -> AppResult<Option<i64>> {
+        let sequence_number: Option<Option<i64>> =
+            sqlx::query_scalar("SELECT sequence_number FROM osm_replication_state")
+                .fetch_optional(&self.pool)
+                .await?;
+
+        Ok(sequence_number.flatten())
+    }
+
+    async fn find_staged_locations(&self, osm_ids: &[i64]) -> AppResult<Vec<(i64, f64, f64)>> {
+        let locations = sqlx::query_as::<_, (i64, f64, f64)>(
+            r#"
+            SELECT osm_id, ST_X(geom), ST_Y(geom)
+            FROM osm_charging_stations_temp
+            WHERE osm_id = ANY($1) AND geom IS NOT NULL
+            "#,
+        )
+        .bind(osm_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(locations)
+    }
+
+    async fn apply_staged_changes(
+        &self,
+        changes: &OsmStagingChanges,
+        sequence_number: i64,
+    ) -> AppResult<(usize, i32)> {
+        let mut tx = self.pool.begin().await?;
+
+        let removed: Vec<i64> = sqlx::query_scalar(
+            "DELETE FROM osm_charging_stations_temp WHERE osm_id = ANY($1) RETURNING osm_id",
+        )
+        .bind(&changes.removed_osm_ids)
+        .fetch_all(&mut *tx)
+        .await?;
+        upsert_staged_stations(&mut tx, &changes.upserts).await?;
+
+        let deactivated: i32 = sqlx::query_scalar("SELECT deactivate_deleted_osm_stations($1)")
+            .bind(&removed)
+            .fetch_one(&mut *tx)
+            .await?;
+        set_replication_sequence(&mut tx, Some(sequence_number)).await?;
+
+        tx.commit().await?;
+
+        if deactivated > 0 {
+            sqlx::query("SELECT refresh_charging_station_views()")
+                .execute(&self.pool)
+                .await?;
+        }
+
+        Ok((removed.len(), deactivated))
+    }
+}
+
+async fn set_replication_sequence(
+    conn: &mut PgConnection,
+    sequence_number: Option<i64>,
+) -> AppResult<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO osm_replication_state (id, sequence_number, applied_at)
+        VALUES (TRUE, $1, NOW())
+        ON CONFLICT (id) DO UPDATE SET
+            sequence_number = EXCLUDED.sequence_number,
+            applied_at = EXCLUDED.applied_at
+        "#,
+    )
+    .bind(sequence_number)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Inserts the stations, replacing the staged rows with the same osm_id
+async fn upsert_staged_stations(
+    conn: &mut PgConnection,
+    stations: &[StagedOsmStation],
+) -> AppResult<()> {
+    for batch in stations.chunks(OSM_STAGING_BATCH_ROWS) {
+        let column =
+            |f: fn(&StagedOsmStation) -> Option<String>| batch.iter().map(f).collect::<Vec<_>>();
+        let tags: Vec<String> = batch
+            .iter()
+            .map(|s| serde_json::to_string(&s.tags).unwrap_or_default())
+            .collect();
+
+        // Tags travel as JSON objects, one per row, and become hstore here
+        sqlx::query(
+            r#"
+            INSERT INTO osm_charging_stations_temp (
+                osm_id, name, address, longitude, latitude, operator, opening_hours,
+                capacity, fee, parking_fee, access,
+                socket_type2, socket_ccs, socket_chademo,
+                socket_type2_output, socket_ccs_output, socket_chademo_output,
+                tags, geom
+            )
+            SELECT
+                r.osm_id, r.name, r.address, r.longitude, r.latitude, r.operator,
+                r.opening_hours, r.capacity, r.fee, r.parking_fee, r.access,
+                r.socket_type2, r.socket_ccs, r.socket_chademo,
+                r.socket_type2_output, r.socket_ccs_output, r.socket_chademo_output,
+                (SELECT hstore(array_agg(key), array_agg(value)) FROM jsonb_each_text(r.tags::JSONB)),
+                ST_SetSRID(ST_MakePoint(r.longitude, r.latitude), 4326)
+            FROM UNNEST(
+                $1::BIGINT[], $2::VARCHAR[], $3::TEXT[], $4::FLOAT8[], $5::FLOAT8[],
+                $6::VARCHAR[], $7::TEXT[], $8::INT[], $9::TEXT[], $10::TEXT[], $11::TEXT[],
+                $12::INT[], $13::INT[], $14::INT[],
+                $15::FLOAT8[], $16::FLOAT8[], $17::FLOAT8[], $18::TEXT[]
+            ) AS r(
+                osm_id, name, address, longitude, latitude, operator, opening_hours,
+                capacity, fee, parking_fee, access,
+                socket_type2, socket_ccs, socket_chademo,
+                socket_type2_output, socket_ccs_output, socket_chademo_output, tags
+            )
+            ON CONFLICT (osm_id) DO UPDATE SET
+                name = EXCLUDED.name,
+                address = EXCLUDED.address,
+                longitude = EXCLUDED.longitude,
+                latitude = EXCLUDED.latitude,
+                operator = EXCLUDED.operator,
+                opening_hours = EXCLUDED.opening_hours,
+                capacity = EXCLUDED.capacity,
+                fee = EXCLUDED.fee,
+                parking_fee = EXCLUDED.parking_fee,
+                access = EXCLUDED.access,
+                socket_type2 = EXCLUDED.socket_type2,
+                socket_ccs = EXCLUDED.socket_ccs,
+                socket_chademo = EXCLUDED.socket_chademo,
+                socket_type2_output = EXCLUDED.socket_type2_output,
+                socket_ccs_output = EXCLUDED.socket_ccs_output,
+                socket_chademo_output = EXCLUDED.socket_chademo_output,
+                tags = EXCLUDED.tags,
+                geom = EXCLUDED.geom,
+                imported_at = NOW()
+            "#,
+        )
+        .bind(batch.iter().map(|s| s.osm_id).collect::<Vec<_>>())
+        .bind(column(|s| s.name.clone()))
+        .bind(column(|s| s.address.clone()))
+        .bind(batch.iter().map(|s| s.longitude).collect::<Vec<_>>())
+        .bind(batch.iter().map(|s| s.latitude).collect::<Vec<_>>())
+        .bind(column(|s| s.operator.clone()))
+        .bind(column(|s| s.opening_hours.clone()))
+        .bind(batch.iter().map(|s| s.capacity).collect::<Vec<_>>())
+        .bind(column(|s| s.fee.clone()))
+        .bind(column(|s| s.parking_fee.clone()))
+        .bind(column(|s| s.access.clone()))
+        .bind(batch.iter().map(|s| s.socket_type2).collect::<Vec<_>>())
+        .bind(batch.iter().map(|s| s.socket_ccs).collect::<Vec<_>>())
+        .bind(batch.iter().map(|s| s.socket_chademo).collect::<Vec<_>>())
+        .bind(batch.iter().map(|s| s.socket_type2_output).collect::<Vec<_>>())
+        .bind(batch.iter().map(|s| s.socket_ccs_output).collect::<Vec<_>>())
+        .bind(batch.iter().map(|s| s.socket_chademo_output).collect::<Vec<_>>())
+        .bind(tags)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
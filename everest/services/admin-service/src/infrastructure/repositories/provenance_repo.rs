@@ -0,0 +1,67 @@
+use crate::core::errors::AppResult;
+use crate::domain::entities::StationFieldSource;
+use crate::domain::provenance::{DataSource, StationField};
+use crate::domain::repositories::ProvenanceRepository;
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+pub struct PgProvenanceRepository {
+    pool: PgPool,
+}
+
+impl PgProvenanceRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ProvenanceRepository for PgProvenanceRepository {
+    async fn find_by_station(&self, station_id: &str) -> AppResult<Vec<StationFieldSource>> {
+        let sources = sqlx::query_as::<_, StationFieldSource>(
+            r#"
+            SELECT station_id, field, data_source, locked, updated_by, updated_at
+            FROM station_field_sources
+            WHERE station_id = $1
+            "#,
+        )
+        .bind(station_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(sources)
+    }
+
+    async fn set_sources(
+        &self,
+        station_id: &str,
+        fields: &[StationField],
+        source: DataSource,
+        locked: Option<bool>,
+        updated_by: Option<&str>,
+    ) -> AppResult<()> {
+        let fields: Vec<&str> = fields.iter().map(|f| f.as_str()).collect();
+
+        sqlx::query(
+            r#"
+            INSERT INTO station_field_sources (station_id, field, data_source, locked, updated_by)
+            SELECT $1, field, $3, COALESCE($4, FALSE), $5
+            FROM UNNEST($2::VARCHAR[]) AS field
+            ON CONFLICT (station_id, field) DO UPDATE SET
+                data_source = EXCLUDED.data_source,
+                locked = COALESCE($4, station_field_sources.locked),
+                updated_by = EXCLUDED.updated_by,
+                updated_at = NOW()
+            "#,
+        )
+        .bind(station_id)
+        .bind(&fields)
+        .bind(source.as_str())
+        .bind(locked)
+        .bind(updated_by)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,146 @@
+use crate::core::errors::{AppError, AppResult};
+use crate::domain::entities::{Region, RegionStatistics};
+use crate::domain::repositories::RegionRepository;
+use crate::domain::value_objects::RegionBoundary;
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+const REGION_COLUMNS: &str = "region_id, name, country_code, boundary_source, created_at";
+
+pub struct PgRegionRepository {
+    pool: PgPool,
+}
+
+impl PgRegionRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl RegionRepository for PgRegionRepository {
+    async fn create(&self, region: &Region, boundary: &RegionBoundary) -> AppResult<Region> {
+        let (boundary_sql, boundary_value) = match boundary {
+            RegionBoundary::OsmIsoCode(iso_code) => ("osm_admin_boundary($5)", iso_code),
+            RegionBoundary::GeoJson(geojson) => (
+                "ST_Multi(ST_SetSRID(ST_GeomFromGeoJSON($5), 4326))",
+                geojson,
+            ),
+        };
+
+        // No row when the boundary is not in the OSM import
+        let query = format!(
+            r#"
+            INSERT INTO regions (region_id, name, country_code, boundary_source, boundary, created_at)
+            SELECT $1, $2, $3, $4, b.boundary, $6
+            FROM (SELECT {boundary_sql} AS boundary) b
+            WHERE b.boundary IS NOT NULL AND NOT ST_IsEmpty(b.boundary)
+            RETURNING {REGION_COLUMNS}
+            "#
+        );
+
+        sqlx::query_as::<_, Region>(&query)
+            .bind(&region.region_id)
+            .bind(&region.name)
+            .bind(&region.country_code)
+            .bind(&region.boundary_source)
+            .bind(boundary_value)
+            .bind(region.created_at)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| match boundary {
+                RegionBoundary::OsmIsoCode(iso_code) => AppError::ValidationError(format!(
+                    "No administrative boundary with ISO code {} in the OSM import",
+                    iso_code
+                )),
+                RegionBoundary::GeoJson(_) => {
+                    AppError::ValidationError("The boundary is empty".to_string())
+                }
+            })
+    }
+
+    async fn find_by_id(&self, region_id: &str) -> AppResult<Option<Region>> {
+        let query = format!("SELECT {REGION_COLUMNS} FROM regions WHERE region_id = $1");
+        let result = sqlx::query_as::<_, Region>(&query)
+            .bind(region_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn find_all(&self) -> AppResult<Vec<Region>> {
+        let query = format!("SELECT {REGION_COLUMNS} FROM regions ORDER BY region_id");
+        let results = sqlx::query_as::<_, Region>(&query)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(results)
+    }
+
+    async fn delete(&self, region_id: &str) -> AppResult<()> {
+        sqlx::query("DELETE FROM regions WHERE region_id = $1")
+            .bind(region_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn assign_station_regions(&self) -> AppResult<i32> {
+        let moved: (i32,) = sqlx::query_as("SELECT assign_station_regions()")
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(moved.0)
+    }
+
+    async fn statistics(&self) -> AppResult<Vec<RegionStatistics>> {
+        let results = sqlx::query_as::<_, RegionStatistics>(
+            r#"
+            SELECT
+                r.region_id,
+                r.name,
+                COALESCE(st.station_count, 0) AS station_count,
+                COALESCE(st.active_station_count, 0) AS active_station_count,
+                COALESCE(st.osm_station_count, 0) AS osm_station_count,
+                COALESCE(co.connector_count, 0) AS connector_count,
+                COALESCE(co.available_connector_count, 0) AS available_connector_count,
+                (
+                    SELECT MAX(sr.finished_at)
+                    FROM sync_runs sr
+                    WHERE sr.status = 'succeeded'
+                      AND (sr.region_id IS NULL OR sr.region_id = r.region_id)
+                ) AS last_synced_at
+            FROM regions r
+            LEFT JOIN (
+                SELECT
+                    s.region_id,
+                    COUNT(*) AS station_count,
+                    COUNT(*) FILTER (WHERE s.deactivated_at IS NULL) AS active_station_count,
+                    COUNT(*) FILTER (WHERE s.osm_id > 0) AS osm_station_count
+                FROM stations s
+                WHERE s.deleted_at IS NULL
+                GROUP BY s.region_id
+            ) st ON st.region_id = r.region_id
+            LEFT JOIN (
+                SELECT
+                    s.region_id,
+                    SUM(COALESCE(c.count_total, 1))::BIGINT AS connector_count,
+                    SUM(COALESCE(c.count_available, 0))::BIGINT AS available_connector_count
+                FROM connectors c
+                JOIN stations s ON s.station_id = c.station_id
+                WHERE s.deactivated_at IS NULL
+                  AND s.deleted_at IS NULL
+                  AND c.deleted_at IS NULL
+                GROUP BY s.region_id
+            ) co ON co.region_id = r.region_id
+            ORDER BY r.region_id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results)
+    }
+}
@@ -0,0 +1,129 @@
+use crate::core::errors::AppResult;
+use crate::domain::entities::{ModerationQueueEntry, UserReview};
+use crate::domain::moderation::ModerationStatus;
+use crate::domain::repositories::ReviewModerationRepository;
+use crate::infrastructure::repositories::refresh_review_view;
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+pub struct PgReviewModerationRepository {
+    pool: PgPool,
+}
+
+impl PgReviewModerationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ReviewModerationRepository for PgReviewModerationRepository {
+    async fn find_queue<'a>(
+        &self,
+        status: Option<&'a str>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<ModerationQueueEntry>> {
+        let entries = sqlx::query_as::<_, ModerationQueueEntry>(
+            r#"
+            SELECT
+                r.review_id, r.user_id, r.station_id, r.rating, r.review_text,
+                r.created_at, r.updated_at, r.moderation_status, r.moderation_reason,
+                r.moderated_by, r.moderated_at,
+                COUNT(f.flag_id) AS open_flags,
+                COALESCE(
+                    ARRAY_AGG(DISTINCT f.reason) FILTER (WHERE f.flag_id IS NOT NULL),
+                    '{}'
+                )::TEXT[] AS flag_reasons
+            FROM user_reviews r
+            LEFT JOIN review_flags f
+                ON f.review_id = r.review_id AND f.resolved_at IS NULL
+            WHERE ($1::TEXT IS NULL AND (r.moderation_status = 'pending' OR f.flag_id IS NOT NULL))
+               OR r.moderation_status = $1
+            GROUP BY r.review_id
+            ORDER BY COUNT(f.flag_id) DESC, r.updated_at ASC
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(status)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entries)
+    }
+
+    async fn count_queue<'a>(&self, status: Option<&'a str>) -> AppResult<i64> {
+        let count: (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*) FROM user_reviews r
+            WHERE ($1::TEXT IS NULL AND (
+                    r.moderation_status = 'pending'
+                    OR EXISTS (
+                        SELECT 1 FROM review_flags f
+                        WHERE f.review_id = r.review_id AND f.resolved_at IS NULL
+                    )
+                ))
+               OR r.moderation_status = $1
+            "#,
+        )
+        .bind(status)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count.0)
+    }
+
+    async fn moderate(
+        &self,
+        review_id: &str,
+        status: ModerationStatus,
+        reason: &str,
+        admin_id: &str,
+    ) -> AppResult<Option<UserReview>> {
+        let mut tx = self.pool.begin().await?;
+
+        let review = sqlx::query_as::<_, UserReview>(
+            r#"
+            UPDATE user_reviews
+            SET
+                moderation_status = $2,
+                moderation_reason = $3,
+                moderated_by = $4,
+                moderated_at = NOW()
+            WHERE review_id = $1
+            RETURNING
+                review_id, user_id, station_id, rating, review_text, created_at, updated_at,
+                moderation_status, moderation_reason, moderated_by, moderated_at
+            "#,
+        )
+        .bind(review_id)
+        .bind(status.as_str())
+        .bind(reason)
+        .bind(admin_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(review) = review else {
+            return Ok(None);
+        };
+
+        sqlx::query(
+            r#"
+            UPDATE review_flags
+            SET resolved_at = NOW(), resolved_by = $2
+            WHERE review_id = $1 AND resolved_at IS NULL
+            "#,
+        )
+        .bind(review_id)
+        .bind(admin_id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        // Hidden reviews drop out of the ratings and ranking right away
+        refresh_review_view(&self.pool).await?;
+        Ok(Some(review))
+    }
+}
@@ -0,0 +1,228 @@
+use crate::core::errors::AppResult;
+use crate::domain::entities::{DuplicateCandidate, DuplicateStation, StationMerge};
+use crate::domain::repositories::StationMergeRepository;
+use crate::infrastructure::repositories::refresh_station_views;
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+const STATION_MERGE_COLUMNS: &str = r#"
+    merge_id, surviving_station_id, merged_station_id, merged_osm_id, merged_station,
+    moved_connectors, dropped_connectors, moved_reviews, dropped_reviews, moved_checkins,
+    moved_media, moved_issue_reports, merged_by, merged_at
+"#;
+
+/// Columns of one side of a pair, `a` or `b`
+fn duplicate_station_columns(side: &str) -> String {
+    format!(
+        r#"
+        {side}.station_id AS {side}_station_id,
+        {side}.osm_id AS {side}_osm_id,
+        {side}.name AS {side}_name,
+        {side}.address AS {side}_address,
+        ST_Y({side}.location::GEOMETRY) AS {side}_latitude,
+        ST_X({side}.location::GEOMETRY) AS {side}_longitude,
+        {side}.network_id AS {side}_network_id,
+        {side}.external_ref AS {side}_external_ref,
+        ARRAY(
+            SELECT DISTINCT ct.name::TEXT
+            FROM connectors c
+            JOIN connector_types ct ON ct.id = c.connector_type_id
+            WHERE c.station_id = {side}.station_id AND c.deleted_at IS NULL
+            ORDER BY 1
+        ) AS {side}_connector_types
+        "#
+    )
+}
+
+#[derive(sqlx::FromRow)]
+struct CandidateRow {
+    a_station_id: String,
+    a_osm_id: i64,
+    a_name: String,
+    a_address: Option<String>,
+    a_latitude: f64,
+    a_longitude: f64,
+    a_network_id: Option<String>,
+    a_external_ref: Option<String>,
+    a_connector_types: Vec<String>,
+    b_station_id: String,
+    b_osm_id: i64,
+    b_name: String,
+    b_address: Option<String>,
+    b_latitude: f64,
+    b_longitude: f64,
+    b_network_id: Option<String>,
+    b_external_ref: Option<String>,
+    b_connector_types: Vec<String>,
+    distance_meters: f64,
+    name_similarity: f64,
+    connector_overlap: Option<f64>,
+    score: f64,
+}
+
+impl From<CandidateRow> for DuplicateCandidate {
+    fn from(r: CandidateRow) -> Self {
+        Self {
+            station_a: DuplicateStation {
+                station_id: r.a_station_id,
+                osm_id: r.a_osm_id,
+                name: r.a_name,
+                address: r.a_address,
+                latitude: r.a_latitude,
+                longitude: r.a_longitude,
+                network_id: r.a_network_id,
+                external_ref: r.a_external_ref,
+                connector_types: r.a_connector_types,
+            },
+            station_b: DuplicateStation {
+                station_id: r.b_station_id,
+                osm_id: r.b_osm_id,
+                name: r.b_name,
+                address: r.b_address,
+                latitude: r.b_latitude,
+                longitude: r.b_longitude,
+                network_id: r.b_network_id,
+                external_ref: r.b_external_ref,
+                connector_types: r.b_connector_types,
+            },
+            distance_meters: r.distance_meters,
+            name_similarity: r.name_similarity,
+            connector_overlap: r.connector_overlap,
+            score: r.score,
+        }
+    }
+}
+
+pub struct PgStationMergeRepository {
+    pool: PgPool,
+}
+
+impl PgStationMergeRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl StationMergeRepository for PgStationMergeRepository {
+    async fn find_candidates(
+        &self,
+        max_distance_meters: f64,
+        min_score: f64,
+        region_id: Option<&str>,
+        limit: i64,
+    ) -> AppResult<Vec<DuplicateCandidate>> {
+        let rows = sqlx::query_as::<_, CandidateRow>(&format!(
+            r#"
+            SELECT
+                {},
+                {},
+                d.distance_meters,
+                d.name_similarity,
+                d.connector_overlap,
+                d.score
+            FROM find_duplicate_station_candidates($1, $2, $3) d
+            JOIN stations a ON a.station_id = d.station_id_a
+            JOIN stations b ON b.station_id = d.station_id_b
+            ORDER BY d.score DESC, d.distance_meters
+            LIMIT $4
+            "#,
+            duplicate_station_columns("a"),
+            duplicate_station_columns("b")
+        ))
+        .bind(max_distance_meters)
+        .bind(min_score)
+        .bind(region_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(DuplicateCandidate::from).collect())
+    }
+
+    async fn dismiss(
+        &self,
+        station_id_a: &str,
+        station_id_b: &str,
+        user_id: Option<&str>,
+    ) -> AppResult<()> {
+        // Stored in id order, as the candidates list them
+        let (first, second) = if station_id_a < station_id_b {
+            (station_id_a, station_id_b)
+        } else {
+            (station_id_b, station_id_a)
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO station_duplicate_dismissals (station_id_a, station_id_b, dismissed_by)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (station_id_a, station_id_b) DO NOTHING
+            "#,
+        )
+        .bind(first)
+        .bind(second)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn merge(
+        &self,
+        merge_id: &str,
+        surviving_station_id: &str,
+        merged_station_id: &str,
+        user_id: Option<&str>,
+    ) -> AppResult<StationMerge> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("SELECT merge_stations($1, $2, $3, $4)")
+            .bind(surviving_station_id)
+            .bind(merged_station_id)
+            .bind(merge_id)
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        let merge = sqlx::query_as::<_, StationMerge>(&format!(
+            "SELECT {} FROM station_merges WHERE merge_id = $1",
+            STATION_MERGE_COLUMNS
+        ))
+        .bind(merge_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        refresh_station_views(&self.pool).await?;
+
+        Ok(merge)
+    }
+
+    async fn find_merges(
+        &self,
+        station_id: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<StationMerge>> {
+        let merges = sqlx::query_as::<_, StationMerge>(&format!(
+            r#"
+            SELECT {}
+            FROM station_merges
+            WHERE ($1::VARCHAR IS NULL OR surviving_station_id = $1)
+            ORDER BY merged_at DESC, merge_id
+            LIMIT $2 OFFSET $3
+            "#,
+            STATION_MERGE_COLUMNS
+        ))
+        .bind(station_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(merges)
+    }
+}
@@ -1,6 +1,7 @@
-use crate::core::errors::AppResult;
+use crate::core::errors::{AppError, AppResult};
 use crate::domain::entities::Station;
 use crate::domain::repositories::StationRepository;
+use crate::infrastructure::repositories::refresh_station_views;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use serde_json::Value;
@@ -14,6 +15,24 @@ impl PgStationRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
+
+    /// Stations cannot be put on a deleted network
+    async fn ensure_network_exists(&self, network_id: Option<&str>) -> AppResult<()> {
+        let Some(network_id) = network_id else {
+            return Ok(());
+        };
+        let exists: (bool,) = sqlx::query_as(
+            "SELECT EXISTS(SELECT 1 FROM networks WHERE network_id = $1 AND deleted_at IS NULL)",
+        )
+        .bind(network_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        if !exists.0 {
+            return Err(AppError::ValidationError(format!("Network {} not found", network_id)));
+        }
+        Ok(())
+    }
 }
 
 type StationRow = (
@@ -26,6 +45,7 @@ type StationRow = (
     Option<Value>,
     Option<String>,
     Option<String>,
+    Option<String>,
     DateTime<Utc>,
     Option<String>,
     Option<DateTime<Utc>>,
@@ -34,6 +54,7 @@ type StationRow = (
 #[async_trait]
 impl StationRepository for PgStationRepository {
     async fn create(&self, station: &Station) -> AppResult<Station> {
+        self.ensure_network_exists(station.network_id.as_deref()).await?;
         sqlx::query(
             r#"INSERT INTO stations (station_id, osm_id, name, address, location, tags, network_id, created_by, created_at) 
                VALUES ($1, $2, $3, $4, ST_SetSRID(ST_MakePoint($5, $6), 4326)::geography, $7::jsonb::hstore, $8, $9, $10)"#
@@ -42,54 +63,135 @@ impl StationRepository for PgStationRepository {
         .bind(station.longitude).bind(station.latitude).bind(&station.tags).bind(&station.network_id)
         .bind(&station.created_by).bind(station.created_at)
         .execute(&self.pool).await?;
-        Ok(station.clone())
+        // Read back for the region the database assigned
+        self.find_by_id(&station.station_id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound.into())
     }
 
     async fn find_by_id(&self, id: &str) -> AppResult<Option<Station>> {
         let row = sqlx::query_as::<_, StationRow>(
             r#"SELECT station_id, osm_id, name, address, ST_Y(location::geometry), ST_X(location::geometry), 
-               tags::jsonb, network_id, created_by, created_at, updated_by, updated_at FROM stations WHERE station_id = $1"#
+               tags::jsonb, network_id, region_id, created_by, created_at, updated_by, updated_at FROM stations
+               WHERE station_id = $1 AND deleted_at IS NULL"#
         ).bind(id).fetch_optional(&self.pool).await?;
         Ok(row.map(map_row))
     }
 
     async fn update(&self, s: &Station) -> AppResult<Station> {
+        self.ensure_network_exists(s.network_id.as_deref()).await?;
         sqlx::query(r#"UPDATE stations SET name=$2, address=$3, location=ST_SetSRID(ST_MakePoint($4, $5), 4326)::geography, 
-                       tags=$6::jsonb::hstore, network_id=$7, updated_at=$8 WHERE station_id=$1"#)
+                       tags=$6::jsonb::hstore, network_id=$7, updated_by=$8, updated_at=$9 WHERE station_id=$1 AND deleted_at IS NULL"#)
         .bind(&s.station_id).bind(&s.name).bind(&s.address).bind(s.longitude).bind(s.latitude)
-        .bind(&s.tags).bind(&s.network_id).bind(Utc::now())
+        .bind(&s.tags).bind(&s.network_id).bind(&s.updated_by).bind(Utc::now())
         .execute(&self.pool).await?;
-        Ok(s.clone())
+        self.find_by_id(&s.station_id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound.into())
     }
 
-    async fn find_all(&self, limit: i64, offset: i64) -> AppResult<Vec<Station>> {
+    async fn find_all(
+        &self,
+        network_id: Option<&str>,
+        region_id: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<Station>> {
         let rows = sqlx::query_as::<_, StationRow>(
             r#"SELECT station_id, osm_id, name, address, ST_Y(location::geometry), ST_X(location::geometry), 
-               tags::jsonb, network_id, created_by, created_at, updated_by, updated_at FROM stations LIMIT $1 OFFSET $2"#
-        ).bind(limit).bind(offset).fetch_all(&self.pool).await?;
+               tags::jsonb, network_id, region_id, created_by, created_at, updated_by, updated_at FROM stations
+               WHERE deleted_at IS NULL
+                 AND ($1::VARCHAR IS NULL OR network_id = $1) AND ($2::VARCHAR IS NULL OR region_id = $2)
+               ORDER BY created_at, station_id LIMIT $3 OFFSET $4"#
+        ).bind(network_id).bind(region_id).bind(limit).bind(offset).fetch_all(&self.pool).await?;
         Ok(rows.into_iter().map(map_row).collect())
     }
 
-    async fn find_by_network(&self, id: &str, limit: i64, offset: i64) -> AppResult<Vec<Station>> {
-        let rows = sqlx::query_as::<_, StationRow>(
-            r#"SELECT station_id, osm_id, name, address, ST_Y(location::geometry), ST_X(location::geometry), 
-               tags::jsonb, network_id, created_by, created_at, updated_by, updated_at FROM stations WHERE network_id = $1 LIMIT $2 OFFSET $3"#
-        ).bind(id).bind(limit).bind(offset).fetch_all(&self.pool).await?;
-        Ok(rows.into_iter().map(map_row).collect())
+    async fn delete(&self, id: &str, deleted_by: &str) -> AppResult<()> {
+        // The connectors get the station's deletion time, to come back with it
+        sqlx::query(
+            r#"
+            WITH station AS (
+                UPDATE stations SET deleted_at = NOW(), deleted_by = $2
+                WHERE station_id = $1 AND deleted_at IS NULL
+                RETURNING station_id, deleted_at
+            )
+            UPDATE connectors c SET deleted_at = station.deleted_at, deleted_by = $2
+            FROM station
+            WHERE c.station_id = station.station_id AND c.deleted_at IS NULL
+            "#,
+        )
+        .bind(id)
+        .bind(deleted_by)
+        .execute(&self.pool)
+        .await?;
+
+        refresh_station_views(&self.pool).await
     }
 
-    async fn delete(&self, id: &str) -> AppResult<()> {
-        sqlx::query("DELETE FROM stations WHERE station_id = $1")
-            .bind(id)
-            .execute(&self.pool)
-            .await?;
-        Ok(())
+    async fn restore(&self, id: &str, restored_by: &str) -> AppResult<Option<Station>> {
+        let mut tx = self.pool.begin().await?;
+
+        let deleted: Option<(DateTime<Utc>, bool)> = sqlx::query_as(
+            r#"
+            SELECT s.deleted_at, n.deleted_at IS NOT NULL
+            FROM stations s
+            LEFT JOIN networks n ON n.network_id = s.network_id
+            WHERE s.station_id = $1 AND s.deleted_at IS NOT NULL
+            FOR UPDATE OF s
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some((deleted_at, network_deleted)) = deleted else {
+            return Ok(None);
+        };
+        if network_deleted {
+            return Err(AppError::Conflict(format!(
+                "The network of station {} is deleted, restore it first",
+                id
+            )));
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE connectors SET deleted_at = NULL, deleted_by = NULL
+            WHERE station_id = $1 AND deleted_at = $2
+            "#,
+        )
+        .bind(id)
+        .bind(deleted_at)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            UPDATE stations
+            SET deleted_at = NULL, deleted_by = NULL, updated_by = $2, updated_at = NOW()
+            WHERE station_id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(restored_by)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        refresh_station_views(&self.pool).await?;
+
+        self.find_by_id(id).await
     }
 
-    async fn count(&self) -> AppResult<i64> {
-        let res: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM stations")
-            .fetch_one(&self.pool)
-            .await?;
+    async fn count(&self, network_id: Option<&str>, region_id: Option<&str>) -> AppResult<i64> {
+        let res: (i64,) = sqlx::query_as(
+            r#"SELECT COUNT(*) FROM stations
+               WHERE deleted_at IS NULL AND ($1::VARCHAR IS NULL OR network_id = $1) AND ($2::VARCHAR IS NULL OR region_id = $2)"#,
+        )
+        .bind(network_id)
+        .bind(region_id)
+        .fetch_one(&self.pool)
+        .await?;
         Ok(res.0)
     }
 }
@@ -104,9 +206,10 @@ fn map_row(r: StationRow) -> Station {
         longitude: r.5,
         tags: r.6,
         network_id: r.7,
-        created_by: r.8,
-        created_at: r.9,
-        updated_by: r.10,
-        updated_at: r.11,
+        region_id: r.8,
+        created_by: r.9,
+        created_at: r.10,
+        updated_by: r.11,
+        updated_at: r.12,
     }
 }
@@ -0,0 +1,423 @@
+use crate::core::errors::{AppError, AppResult};
+use crate::domain::entities::{
+    OsmRemovedStation, OsmStagedStation, OsmStagedTags, OsmStationConnector, SyncConflict, SyncRun,
+};
+use crate::domain::repositories::SyncRunRepository;
+use crate::domain::sync::{SyncCounts, SyncStatus, SyncTrigger};
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+const SYNC_RUN_COLUMNS: &str = r#"
+    run_id, trigger_type, status, triggered_by, started_at, finished_at,
+    inserted_count, updated_count, deactivated_count, error, skipped_osm_ids,
+    conflict_count, region_id
+"#;
+
+pub struct PgSyncRunRepository {
+    pool: PgPool,
+}
+
+impl PgSyncRunRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SyncRunRepository for PgSyncRunRepository {
+    async fn start_run(
+        &self,
+        run_id: &str,
+        trigger: SyncTrigger,
+        triggered_by: Option<&str>,
+        skipped_osm_ids: &[i64],
+        region_id: Option<&str>,
+    ) -> AppResult<SyncRun> {
+        sqlx::query_as::<_, SyncRun>(&format!(
+            r#"
+            INSERT INTO sync_runs (
+                run_id, trigger_type, status, triggered_by, skipped_osm_ids, region_id
+            )
+            VALUES ($1, $2, 'running', $3, $4, $5)
+            RETURNING {}
+            "#,
+            SYNC_RUN_COLUMNS
+        ))
+        .bind(run_id)
+        .bind(trigger.as_str())
+        .bind(triggered_by)
+        .bind(skipped_osm_ids)
+        .bind(region_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => {
+                AppError::Conflict("An OSM sync is already running".to_string())
+            }
+            e => e.into(),
+        })
+    }
+
+    async fn finish_run(
+        &self,
+        run_id: &str,
+        status: SyncStatus,
+        counts: Option<SyncCounts>,
+        error: Option<&str>,
+    ) -> AppResult<SyncRun> {
+        let run = sqlx::query_as::<_, SyncRun>(&format!(
+            r#"
+            UPDATE sync_runs
+            SET status = $2, finished_at = NOW(),
+                inserted_count = $3, updated_count = $4, deactivated_count = $5,
+                conflict_count = $6, error = $7
+            WHERE run_id = $1
+            RETURNING {}
+            "#,
+            SYNC_RUN_COLUMNS
+        ))
+        .bind(run_id)
+        .bind(status.as_str())
+        .bind(counts.map(|c| c.inserted_count))
+        .bind(counts.map(|c| c.updated_count))
+        .bind(counts.map(|c| c.deactivated_count))
+        .bind(counts.map(|c| c.conflict_count))
+        .bind(error)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(run)
+    }
+
+    async fn touch_run(&self, run_id: &str) -> AppResult<()> {
+        sqlx::query(
+            "UPDATE sync_runs SET heartbeat_at = NOW() WHERE run_id = $1 AND status = 'running'",
+        )
+        .bind(run_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fail_interrupted_runs(&self, stale_seconds: i64) -> AppResult<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE sync_runs
+            SET status = 'failed', finished_at = NOW(),
+                error = 'Interrupted, the admin-service running it stopped'
+            WHERE status = 'running'
+              AND heartbeat_at < NOW() - make_interval(secs => $1)
+            "#,
+        )
+        .bind(stale_seconds as f64)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn find_by_id(&self, run_id: &str) -> AppResult<Option<SyncRun>> {
+        let run = sqlx::query_as::<_, SyncRun>(&format!(
+            "SELECT {} FROM sync_runs WHERE run_id = $1",
+            SYNC_RUN_COLUMNS
+        ))
+        .bind(run_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(run)
+    }
+
+    async fn find_all(
+        &self,
+        status: Option<SyncStatus>,
+        region_id: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<SyncRun>> {
+        let runs = sqlx::query_as::<_, SyncRun>(&format!(
+            r#"
+            SELECT {}
+            FROM sync_runs
+            WHERE ($1::VARCHAR IS NULL OR status = $1)
+              AND ($2::VARCHAR IS NULL OR region_id = $2)
+            ORDER BY started_at DESC
+            LIMIT $3 OFFSET $4
+            "#,
+            SYNC_RUN_COLUMNS
+        ))
+        .bind(status.map(|s| s.as_str()))
+        .bind(region_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(runs)
+    }
+
+    async fn count(&self, status: Option<SyncStatus>, region_id: Option<&str>) -> AppResult<i64> {
+        let count: (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*) FROM sync_runs
+            WHERE ($1::VARCHAR IS NULL OR status = $1)
+              AND ($2::VARCHAR IS NULL OR region_id = $2)
+            "#,
+        )
+        .bind(status.map(|s| s.as_str()))
+        .bind(region_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count.0)
+    }
+
+    async fn sync_osm_stations(
+        &self,
+        run_id: &str,
+        user_id: Option<&str>,
+        skipped_osm_ids: &[i64],
+        region_id: Option<&str>,
+    ) -> AppResult<SyncCounts> {
+        let counts = sqlx::query_as::<_, SyncCounts>(
+            r#"
+            SELECT inserted_count, updated_count, deactivated_count, conflict_count
+            FROM sync_osm_charging_stations($1, $2, $3, $4)
+            "#,
+        )
+        .bind(user_id)
+        .bind(skipped_osm_ids)
+        .bind(run_id)
+        .bind(region_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(counts)
+    }
+
+    async fn find_osm_ids_outside_region(&self, region_id: &str) -> AppResult<Vec<i64>> {
+        let osm_ids: Vec<i64> = sqlx::query_scalar("SELECT osm_ids_outside_region($1)")
+            .bind(region_id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(osm_ids)
+    }
+
+    async fn find_conflicts(&self, run_id: &str) -> AppResult<Vec<SyncConflict>> {
+        let conflicts = sqlx::query_as::<_, SyncConflict>(
+            r#"
+            SELECT station_id, osm_id, field, data_source, locked, current_value, osm_value
+            FROM sync_conflicts
+            WHERE run_id = $1
+            ORDER BY osm_id, field
+            "#,
+        )
+        .bind(run_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(conflicts)
+    }
+
+    async fn find_pending_conflicts(&self) -> AppResult<Vec<SyncConflict>> {
+        let conflicts = sqlx::query_as::<_, SyncConflict>(
+            r#"
+            SELECT station_id, osm_id, field, data_source, locked, current_value, osm_value
+            FROM osm_sync_conflicts()
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(conflicts)
+    }
+
+    async fn find_staged_stations(&self) -> AppResult<Vec<OsmStagedStation>> {
+        let stations = sqlx::query_as::<_, OsmStagedStation>(
+            r#"
+            SELECT
+                v.osm_id,
+                v.station_id,
+                s.deactivated_at IS NOT NULL AS deactivated,
+                s.name AS current_name,
+                s.address AS current_address,
+                ST_Y(s.location::geometry) AS current_latitude,
+                ST_X(s.location::geometry) AS current_longitude,
+                hstore_to_array(COALESCE(s.tags, ''::HSTORE)) AS current_tags,
+                v.name,
+                v.address,
+                ST_Y(v.location::geometry) AS latitude,
+                ST_X(v.location::geometry) AS longitude,
+                hstore_to_array(COALESCE(v.tags, ''::HSTORE)) AS tags
+            FROM osm_staged_station_values() v
+            LEFT JOIN stations s ON s.station_id = v.station_id
+            WHERE v.location IS NOT NULL
+            ORDER BY v.osm_id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(stations)
+    }
+
+    async fn find_staged_tags(&self) -> AppResult<Vec<OsmStagedTags>> {
+        let rows = sqlx::query_as::<_, OsmStagedTags>(
+            r#"
+            SELECT osm_id, hstore_to_array(COALESCE(tags, ''::HSTORE)) AS tags
+            FROM osm_staged_station_values()
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    async fn stage_connectors(&self, connectors: &[OsmStationConnector]) -> AppResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM osm_staged_connectors")
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO osm_staged_connectors (
+                osm_id, connector_type, current_type, power_kw, voltage, amperage, count_total
+            )
+            SELECT * FROM UNNEST(
+                $1::BIGINT[], $2::VARCHAR[], $3::VARCHAR[], $4::FLOAT8[],
+                $5::INT[], $6::INT[], $7::INT[]
+            )
+            "#,
+        )
+        .bind(connectors.iter().map(|c| c.osm_id).collect::<Vec<_>>())
+        .bind(
+            connectors
+                .iter()
+                .map(|c| c.connector_type.as_str())
+                .collect::<Vec<_>>(),
+        )
+        .bind(
+            connectors
+                .iter()
+                .map(|c| c.current_type.as_str())
+                .collect::<Vec<_>>(),
+        )
+        .bind(connectors.iter().map(|c| c.power_kw).collect::<Vec<_>>())
+        .bind(connectors.iter().map(|c| c.voltage).collect::<Vec<_>>())
+        .bind(connectors.iter().map(|c| c.amperage).collect::<Vec<_>>())
+        .bind(connectors.iter().map(|c| c.count_total).collect::<Vec<_>>())
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn filter_uncovered_connectors(
+        &self,
+        connectors: &[OsmStationConnector],
+    ) -> AppResult<Vec<OsmStationConnector>> {
+        let connectors = sqlx::query_as::<_, OsmStationConnector>(
+            r#"
+            SELECT
+                w.osm_id, w.connector_type, w.current_type,
+                w.power_kw, w.voltage, w.amperage, w.count_total
+            FROM UNNEST(
+                $1::BIGINT[], $2::VARCHAR[], $3::VARCHAR[], $4::FLOAT8[],
+                $5::INT[], $6::INT[], $7::INT[]
+            ) AS w(osm_id, connector_type, current_type, power_kw, voltage, amperage, count_total)
+            JOIN connector_types ct ON ct.name = w.connector_type
+            JOIN current_types cur ON cur.name = w.current_type
+            LEFT JOIN stations s ON s.osm_id = w.osm_id
+            WHERE NOT EXISTS (
+                SELECT 1
+                FROM connectors c
+                WHERE c.station_id = s.station_id
+                  AND c.connector_type_id = ct.id
+                  AND c.current_type_id = cur.id
+                  AND c.data_source <> 'osm'
+            )
+            "#,
+        )
+        .bind(connectors.iter().map(|c| c.osm_id).collect::<Vec<_>>())
+        .bind(
+            connectors
+                .iter()
+                .map(|c| c.connector_type.as_str())
+                .collect::<Vec<_>>(),
+        )
+        .bind(
+            connectors
+                .iter()
+                .map(|c| c.current_type.as_str())
+                .collect::<Vec<_>>(),
+        )
+        .bind(connectors.iter().map(|c| c.power_kw).collect::<Vec<_>>())
+        .bind(connectors.iter().map(|c| c.voltage).collect::<Vec<_>>())
+        .bind(connectors.iter().map(|c| c.amperage).collect::<Vec<_>>())
+        .bind(connectors.iter().map(|c| c.count_total).collect::<Vec<_>>())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(connectors)
+    }
+
+    async fn find_matched_connectors(&self) -> AppResult<Vec<OsmStationConnector>> {
+        let connectors = sqlx::query_as::<_, OsmStationConnector>(
+            r#"
+            SELECT
+                s.osm_id, ct.name AS connector_type, cur.name AS current_type,
+                c.power_kw::FLOAT8 AS power_kw, c.voltage, c.amperage, c.count_total
+            FROM stations s
+            JOIN osm_charging_stations_temp osm ON osm.osm_id = s.osm_id
+            JOIN connectors c ON c.station_id = s.station_id
+            JOIN connector_types ct ON ct.id = c.connector_type_id
+            JOIN current_types cur ON cur.id = c.current_type_id
+            WHERE c.data_source = 'osm'
+              AND s.deleted_at IS NULL
+              AND c.deleted_at IS NULL
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(connectors)
+    }
+
+    async fn find_missing_stations(&self) -> AppResult<Vec<OsmRemovedStation>> {
+        let stations = sqlx::query_as::<_, OsmRemovedStation>(
+            r#"
+            SELECT
+                s.station_id, s.osm_id, s.name, s.address,
+                ST_Y(s.location::geometry) AS latitude,
+                ST_X(s.location::geometry) AS longitude
+            FROM stations s
+            WHERE s.osm_id > 0
+              AND s.external_ref IS NULL
+              AND s.deactivated_at IS NULL
+              AND s.deleted_at IS NULL
+              AND NOT EXISTS (
+                  SELECT 1 FROM osm_charging_stations_temp osm WHERE osm.osm_id = s.osm_id
+              )
+            ORDER BY s.osm_id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(stations)
+    }
+
+    async fn refresh_station_views(&self) -> AppResult<()> {
+        sqlx::query("SELECT refresh_charging_station_views()")
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -5,19 +5,42 @@ pub mod infrastructure;
 pub mod presentation;
 
 use crate::application::connector_service::ConnectorServiceImpl;
+use crate::application::deleted_record_service::DeletedRecordServiceImpl;
+use crate::application::export_service::StationExportServiceImpl;
 use crate::application::health_service::HealthService;
+use crate::application::import_service::StationImportServiceImpl;
 use crate::application::network_service::NetworkServiceImpl;
+use crate::application::network_verification_service::NetworkVerificationServiceImpl;
+use crate::application::review_moderation_service::ReviewModerationServiceImpl;
+use crate::application::ocpi_service::OcpiServiceImpl;
+use crate::application::region_service::RegionServiceImpl;
+use crate::application::station_merge_service::StationMergeServiceImpl;
 use crate::application::station_service::StationServiceImpl;
+use crate::application::sync_service::OsmSyncServiceImpl;
 use crate::core::auth::JwtValidator;
 use crate::core::config::Config;
 use crate::core::database::create_pool;
+use crate::domain::ocpi::OcpiIdentity;
+use crate::infrastructure::ocpi_client::HttpOcpiPartyApi;
 use crate::infrastructure::repositories::connector_repo::PgConnectorRepository;
+use crate::infrastructure::repositories::deleted_record_repo::PgDeletedRecordRepository;
+use crate::infrastructure::repositories::export_repo::PgExportRepository;
+use crate::infrastructure::repositories::import_repo::PgImportRepository;
 use crate::infrastructure::repositories::network_repo::PgNetworkRepository;
+use crate::infrastructure::repositories::network_verification_repo::PgNetworkVerificationRepository;
+use crate::infrastructure::repositories::review_moderation_repo::PgReviewModerationRepository;
+use crate::infrastructure::repositories::ocpi_party_repo::PgOcpiPartyRepository;
+use crate::infrastructure::repositories::ocpi_repo::PgOcpiRepository;
+use crate::infrastructure::repositories::provenance_repo::PgProvenanceRepository;
+use crate::infrastructure::repositories::region_repo::PgRegionRepository;
+use crate::infrastructure::repositories::station_merge_repo::PgStationMergeRepository;
 use crate::infrastructure::repositories::station_repo::PgStationRepository;
+use crate::infrastructure::repositories::sync_run_repo::PgSyncRunRepository;
 use crate::presentation::openapi::ApiDoc;
 use actix_cors::Cors;
 use actix_web::{App, HttpServer, middleware, web};
 use std::sync::Arc;
+use std::time::Duration;
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
@@ -41,14 +64,104 @@ pub async fn run() -> anyhow::Result<()> {
         as Arc<dyn crate::domain::repositories::NetworkRepository>;
     let station_repo = Arc::new(PgStationRepository::new(db_pool.clone()))
         as Arc<dyn crate::domain::repositories::StationRepository>;
+    let provenance_repo = Arc::new(PgProvenanceRepository::new(db_pool.clone()))
+        as Arc<dyn crate::domain::repositories::ProvenanceRepository>;
     let connector_repo = Arc::new(PgConnectorRepository::new(db_pool.clone()))
         as Arc<dyn crate::domain::repositories::ConnectorRepository>;
+    let import_repo = Arc::new(PgImportRepository::new(db_pool.clone()))
+        as Arc<dyn crate::domain::repositories::ImportRepository>;
+    let export_repo = Arc::new(PgExportRepository::new(db_pool.clone()))
+        as Arc<dyn crate::domain::repositories::ExportRepository>;
+    let sync_run_repo = Arc::new(PgSyncRunRepository::new(db_pool.clone()))
+        as Arc<dyn crate::domain::repositories::SyncRunRepository>;
+    let region_repo = Arc::new(PgRegionRepository::new(db_pool.clone()))
+        as Arc<dyn crate::domain::repositories::RegionRepository>;
+    let station_merge_repo = Arc::new(PgStationMergeRepository::new(db_pool.clone()))
+        as Arc<dyn crate::domain::repositories::StationMergeRepository>;
+    let deleted_record_repo = Arc::new(PgDeletedRecordRepository::new(db_pool.clone()))
+        as Arc<dyn crate::domain::repositories::DeletedRecordRepository>;
+    let ocpi_repo = Arc::new(PgOcpiRepository::new(db_pool.clone()))
+        as Arc<dyn crate::domain::repositories::OcpiRepository>;
+    let ocpi_party_repo = Arc::new(PgOcpiPartyRepository::new(db_pool.clone()))
+        as Arc<dyn crate::domain::repositories::OcpiPartyRepository>;
+    let verification_repo = Arc::new(PgNetworkVerificationRepository::new(db_pool.clone()))
+        as Arc<dyn crate::domain::repositories::NetworkVerificationRepository>;
+    let review_moderation_repo = Arc::new(PgReviewModerationRepository::new(db_pool.clone()))
+        as Arc<dyn crate::domain::repositories::ReviewModerationRepository>;
+
+    // OCPI
+    let ocpi_identity = OcpiIdentity {
+        base_url: config.ocpi_base_url.clone(),
+        country_code: config.ocpi_country_code.clone(),
+        party_id: config.ocpi_party_id.clone(),
+        business_name: config.ocpi_business_name.clone(),
+        location_country: config.ocpi_location_country.clone(),
+        time_zone: config.ocpi_time_zone.clone(),
+    };
+    let ocpi_party_api = Arc::new(HttpOcpiPartyApi::new(ocpi_identity.clone()))
+        as Arc<dyn crate::domain::ocpi::OcpiPartyApi>;
 
     // Services
     let health_service = Arc::new(HealthService::new(db_pool.clone()));
     let network_service = Arc::new(NetworkServiceImpl::new(network_repo));
-    let station_service = Arc::new(StationServiceImpl::new(station_repo));
+    let station_service = Arc::new(StationServiceImpl::new(
+        station_repo.clone(),
+        provenance_repo,
+    ));
+    let station_merge_service = Arc::new(StationMergeServiceImpl::new(
+        station_merge_repo,
+        station_repo,
+    ));
     let connector_service = Arc::new(ConnectorServiceImpl::new(connector_repo));
+    let import_service = Arc::new(StationImportServiceImpl::new(import_repo));
+    let export_service = Arc::new(StationExportServiceImpl::new(export_repo));
+    let sync_service = Arc::new(OsmSyncServiceImpl::new(sync_run_repo, region_repo.clone()));
+    let region_service = Arc::new(RegionServiceImpl::new(region_repo));
+    let deleted_record_service = Arc::new(DeletedRecordServiceImpl::new(
+        deleted_record_repo,
+        config.deleted_retention_days,
+    ));
+    let ocpi_service = Arc::new(OcpiServiceImpl::new(
+        ocpi_repo,
+        ocpi_party_repo,
+        ocpi_party_api,
+        ocpi_identity,
+    ));
+    let verification_service = Arc::new(NetworkVerificationServiceImpl::new(verification_repo));
+    let review_moderation_service =
+        Arc::new(ReviewModerationServiceImpl::new(review_moderation_repo));
+
+    sync_service.recover_interrupted_runs().await?;
+    if config.osm_sync_interval_minutes > 0 {
+        sync_service
+            .clone()
+            .schedule(Duration::from_secs(config.osm_sync_interval_minutes * 60));
+        tracing::info!(
+            "OSM sync scheduled every {} minutes",
+            config.osm_sync_interval_minutes
+        );
+    }
+
+    if config.deleted_purge_interval_minutes > 0 {
+        deleted_record_service.clone().schedule(Duration::from_secs(
+            config.deleted_purge_interval_minutes * 60,
+        ));
+        tracing::info!(
+            "Records deleted over {} days ago purged every {} minutes",
+            config.deleted_retention_days,
+            config.deleted_purge_interval_minutes
+        );
+    }
+
+    if config.ocpi_push_interval_minutes > 0 {
+        ocpi_service
+            .clone()
+            .schedule(Duration::from_secs(config.ocpi_push_interval_minutes * 60));
+        tracing::info!(
+            "OCPI location updates pushed every {} minutes",
+            config.ocpi_push_interval_minutes
+        );
+    }
 
     tracing::info!("Services initialized");
 
@@ -69,6 +182,15 @@ pub async fn run() -> anyhow::Result<()> {
             .app_data(web::Data::new(network_service.clone()))
             .app_data(web::Data::new(station_service.clone()))
             .app_data(web::Data::new(connector_service.clone()))
+            .app_data(web::Data::new(import_service.clone()))
+            .app_data(web::Data::new(export_service.clone()))
+            .app_data(web::Data::new(sync_service.clone()))
+            .app_data(web::Data::new(region_service.clone()))
+            .app_data(web::Data::new(station_merge_service.clone()))
+            .app_data(web::Data::new(deleted_record_service.clone()))
+            .app_data(web::Data::new(ocpi_service.clone()))
+            .app_data(web::Data::new(verification_service.clone()))
+            .app_data(web::Data::new(review_moderation_service.clone()))
             .configure(presentation::configure_routes)
             .service(
                 SwaggerUi::new("/swagger-ui/{_:.*}")
@@ -163,7 +163,7 @@ pub async fn update_connector(
     ),
     security(("bearer_auth" = [])),
     responses(
-        (status = 204, description = "Connector deleted"),
+        (status = 204, description = "Connector deleted until restored or purged"),
         (status = 404, description = "Connector not found"),
         (status = 401, description = "Unauthorized"),
         (status = 403, description = "Forbidden - Admin only")
@@ -176,17 +176,52 @@ pub async fn delete_connector(
     service: web::Data<Arc<ConnectorServiceImpl>>,
     validator: web::Data<Arc<JwtValidator>>,
 ) -> Result<HttpResponse, AppError> {
-    require_admin_auth(&req, &validator).await?;
+    let claims = require_admin_auth(&req, &validator).await?;
 
-    service.delete_connector(&path.into_inner()).await?;
+    service
+        .delete_connector(&path.into_inner(), &claims.sub)
+        .await?;
 
     Ok(HttpResponse::NoContent().finish())
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/connectors/{id}/restore",
+    tag = "Connectors",
+    params(
+        ("id" = String, Path, description = "Connector ID")
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Connector restored", body = ConnectorResponse),
+        (status = 404, description = "Deleted connector not found"),
+        (status = 409, description = "Its station is deleted or has a connector of the same type"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only")
+    )
+)]
+#[post("/connectors/{id}/restore")]
+pub async fn restore_connector(
+    req: HttpRequest,
+    path: web::Path<String>,
+    service: web::Data<Arc<ConnectorServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    let claims = require_admin_auth(&req, &validator).await?;
+
+    let connector = service
+        .restore_connector(&path.into_inner(), &claims.sub)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(ConnectorResponse::from(connector)))
+}
+
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(create_connector)
         .service(list_connectors)
         .service(get_connector)
         .service(update_connector)
-        .service(delete_connector);
+        .service(delete_connector)
+        .service(restore_connector);
 }
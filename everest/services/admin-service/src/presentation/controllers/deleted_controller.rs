@@ -0,0 +1,97 @@
+use crate::application::deleted_record_service::DeletedRecordServiceImpl;
+use crate::application::dtos::deleted::{
+    DeletedRecordListResponse, DeletedRecordResponse, PurgeResponse,
+};
+use crate::core::auth::{JwtValidator, require_admin_auth};
+use crate::core::constants::MAX_DELETED_RECORDS_PAGE;
+use crate::core::errors::AppError;
+use crate::domain::deleted::DeletedRecordType;
+use crate::domain::services::DeletedRecordService;
+use actix_web::{HttpRequest, HttpResponse, get, post, web};
+use std::str::FromStr;
+use std::sync::Arc;
+
+#[derive(serde::Deserialize)]
+pub struct DeletedRecordsQuery {
+    #[serde(rename = "type")]
+    pub record_type: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/deleted",
+    tag = "Deleted records",
+    params(
+        ("type" = Option<String>, Query, description = "station, connector or network"),
+        ("limit" = Option<i64>, Query, description = "Items per page (max 100)"),
+        ("offset" = Option<i64>, Query, description = "Offset")
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Deleted records, most recent first", body = DeletedRecordListResponse),
+        (status = 400, description = "Unknown record type"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only")
+    )
+)]
+#[get("/deleted")]
+pub async fn list_deleted_records(
+    req: HttpRequest,
+    query: web::Query<DeletedRecordsQuery>,
+    service: web::Data<Arc<DeletedRecordServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    require_admin_auth(&req, &validator).await?;
+
+    let record_type = query
+        .record_type
+        .as_deref()
+        .map(DeletedRecordType::from_str)
+        .transpose()?;
+    let limit = query.limit.unwrap_or(50).clamp(1, MAX_DELETED_RECORDS_PAGE);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    let (records, total) = service.list_deleted(record_type, limit, offset).await?;
+
+    Ok(HttpResponse::Ok().json(DeletedRecordListResponse {
+        records: records
+            .into_iter()
+            .map(|record| {
+                let purge_after = service.purge_after(record.deleted_at);
+                DeletedRecordResponse::new(record, purge_after)
+            })
+            .collect(),
+        total,
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/deleted/purge",
+    tag = "Deleted records",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Records past the retention period removed for good", body = PurgeResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only")
+    )
+)]
+#[post("/deleted/purge")]
+pub async fn purge_deleted_records(
+    req: HttpRequest,
+    service: web::Data<Arc<DeletedRecordServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    require_admin_auth(&req, &validator).await?;
+
+    let counts = service.purge_expired().await?;
+
+    Ok(HttpResponse::Ok().json(PurgeResponse::from(counts)))
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(list_deleted_records)
+        .service(purge_deleted_records);
+}
@@ -0,0 +1,180 @@
+use crate::application::dtos::duplicate::{
+    DismissDuplicateRequest, DuplicateCandidateResponse, MergeStationsRequest, StationMergeResponse,
+};
+use crate::application::station_merge_service::StationMergeServiceImpl;
+use crate::core::auth::{JwtValidator, require_admin_auth};
+use crate::core::constants::MAX_DUPLICATES_PAGE;
+use crate::core::errors::AppError;
+use crate::domain::services::StationMergeService;
+use actix_web::{HttpRequest, HttpResponse, get, post, web};
+use std::sync::Arc;
+
+#[derive(serde::Deserialize)]
+pub struct DuplicateCandidatesQuery {
+    pub max_distance_meters: Option<f64>,
+    pub min_score: Option<f64>,
+    pub region: Option<String>,
+    pub limit: Option<i64>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/duplicates/candidates",
+    tag = "Duplicates",
+    params(
+        ("max_distance_meters" = Option<f64>, Query, description = "Maximum distance between the stations (default 50, max 500)"),
+        ("min_score" = Option<f64>, Query, description = "Minimum score from 0 to 1 (default 0.5)"),
+        ("region" = Option<String>, Query, description = "Only pairs with a station in this region"),
+        ("limit" = Option<i64>, Query, description = "Items (max 100)")
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Likely duplicate pairs, best first", body = Vec<DuplicateCandidateResponse>),
+        (status = 400, description = "Invalid distance or score"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only")
+    )
+)]
+#[get("/duplicates/candidates")]
+pub async fn list_duplicate_candidates(
+    req: HttpRequest,
+    query: web::Query<DuplicateCandidatesQuery>,
+    service: web::Data<Arc<StationMergeServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    require_admin_auth(&req, &validator).await?;
+
+    let limit = query.limit.unwrap_or(50).clamp(1, MAX_DUPLICATES_PAGE);
+    let candidates = service
+        .list_candidates(
+            query.max_distance_meters,
+            query.min_score,
+            query.region.as_deref(),
+            limit,
+        )
+        .await?;
+
+    Ok(HttpResponse::Ok().json(
+        candidates
+            .into_iter()
+            .map(DuplicateCandidateResponse::from)
+            .collect::<Vec<_>>(),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/duplicates/dismissals",
+    tag = "Duplicates",
+    request_body = DismissDuplicateRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 204, description = "Pair no longer listed as a candidate"),
+        (status = 400, description = "Same station twice"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only"),
+        (status = 404, description = "Station not found")
+    )
+)]
+#[post("/duplicates/dismissals")]
+pub async fn dismiss_duplicate(
+    req: HttpRequest,
+    body: web::Json<DismissDuplicateRequest>,
+    service: web::Data<Arc<StationMergeServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    let claims = require_admin_auth(&req, &validator).await?;
+
+    service
+        .dismiss_candidate(&body.station_id_a, &body.station_id_b, Some(&claims.sub))
+        .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/duplicates/merges",
+    tag = "Duplicates",
+    request_body = MergeStationsRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 201, description = "Stations merged", body = StationMergeResponse),
+        (status = 400, description = "Same station twice, or both stations come from OSM"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only"),
+        (status = 404, description = "Station not found")
+    )
+)]
+#[post("/duplicates/merges")]
+pub async fn merge_stations(
+    req: HttpRequest,
+    body: web::Json<MergeStationsRequest>,
+    service: web::Data<Arc<StationMergeServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    let claims = require_admin_auth(&req, &validator).await?;
+
+    let merge = service
+        .merge_stations(
+            &body.surviving_station_id,
+            &body.merged_station_id,
+            Some(&claims.sub),
+        )
+        .await?;
+
+    Ok(HttpResponse::Created().json(StationMergeResponse::from(merge)))
+}
+
+#[derive(serde::Deserialize)]
+pub struct StationMergesQuery {
+    pub station_id: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/duplicates/merges",
+    tag = "Duplicates",
+    params(
+        ("station_id" = Option<String>, Query, description = "Only merges into this station"),
+        ("limit" = Option<i64>, Query, description = "Items per page (max 100)"),
+        ("offset" = Option<i64>, Query, description = "Offset")
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Merge history, most recent first", body = Vec<StationMergeResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only")
+    )
+)]
+#[get("/duplicates/merges")]
+pub async fn list_station_merges(
+    req: HttpRequest,
+    query: web::Query<StationMergesQuery>,
+    service: web::Data<Arc<StationMergeServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    require_admin_auth(&req, &validator).await?;
+
+    let limit = query.limit.unwrap_or(20).clamp(1, MAX_DUPLICATES_PAGE);
+    let offset = query.offset.unwrap_or(0).max(0);
+    let merges = service
+        .list_merges(query.station_id.as_deref(), limit, offset)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(
+        merges
+            .into_iter()
+            .map(StationMergeResponse::from)
+            .collect::<Vec<_>>(),
+    ))
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(list_duplicate_candidates)
+        .service(dismiss_duplicate)
+        .service(merge_stations)
+        .service(list_station_merges);
+}
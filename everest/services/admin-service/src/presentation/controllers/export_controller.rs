@@ -0,0 +1,77 @@
+use crate::application::export_service::StationExportServiceImpl;
+use crate::core::auth::{JwtValidator, require_admin_auth};
+use crate::core::errors::AppError;
+use crate::domain::export::{BoundingBox, ExportFilter, ExportFormat};
+use crate::domain::services::StationExportService;
+use actix_web::{HttpRequest, HttpResponse, get, web};
+use futures_util::StreamExt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+#[derive(serde::Deserialize)]
+pub struct ExportQuery {
+    pub format: Option<String>,
+    pub network_id: Option<String>,
+    pub bbox: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/stations/export",
+    tag = "Import",
+    params(
+        ("format" = Option<String>, Query, description = "csv (one row per connector, default) or geojson (one feature per station)"),
+        ("network_id" = Option<String>, Query, description = "Only stations of this network"),
+        ("bbox" = Option<String>, Query, description = "Only stations within min_lon,min_lat,max_lon,max_lat")
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Stations with their connectors, streamed", content_type = "text/csv"),
+        (status = 400, description = "Invalid format or bbox"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only")
+    )
+)]
+#[get("/stations/export")]
+pub async fn export_stations(
+    req: HttpRequest,
+    query: web::Query<ExportQuery>,
+    service: web::Data<Arc<StationExportServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    require_admin_auth(&req, &validator).await?;
+
+    let format = query
+        .format
+        .as_deref()
+        .map(ExportFormat::from_str)
+        .transpose()?
+        .unwrap_or(ExportFormat::Csv);
+    let filter = ExportFilter {
+        network_id: query.network_id.clone(),
+        bbox: query
+            .bbox
+            .as_deref()
+            .map(BoundingBox::from_str)
+            .transpose()?,
+    };
+
+    let body = service
+        .export_stations(format, filter)
+        .map(|chunk| chunk.map(web::Bytes::from));
+
+    Ok(HttpResponse::Ok()
+        .content_type(format.content_type())
+        .insert_header((
+            "Content-Disposition",
+            format!(
+                "attachment; filename=\"stations.{}\"",
+                format.file_extension()
+            ),
+        ))
+        .streaming(body))
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(export_stations);
+}
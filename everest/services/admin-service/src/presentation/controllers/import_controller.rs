@@ -0,0 +1,84 @@
+use crate::application::dtos::import::ImportReportResponse;
+use crate::application::import_service::StationImportServiceImpl;
+use crate::core::auth::{JwtValidator, require_admin_auth};
+use crate::core::constants::MAX_IMPORT_BYTES;
+use crate::core::errors::AppError;
+use crate::domain::import::ImportFormat;
+use crate::domain::services::StationImportService;
+use actix_web::{HttpMessage, HttpRequest, HttpResponse, web};
+use std::str::FromStr;
+use std::sync::Arc;
+
+#[derive(serde::Deserialize)]
+pub struct ImportQuery {
+    pub format: Option<String>,
+    pub dry_run: Option<bool>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/stations/import",
+    tag = "Import",
+    params(
+        ("format" = Option<String>, Query, description = "csv or geojson (default: from the Content-Type)"),
+        ("dry_run" = Option<bool>, Query, description = "Only validate and report (default: true)")
+    ),
+    request_body(
+        content = String,
+        description = "CSV with a header row (name, latitude, longitude, and optionally station_ref, osm_id, address, network_id, connector_type, current_type, power_kw, voltage, amperage, count_total, count_available), one connector per line, lines sharing a station_ref forming one station; or a GeoJSON FeatureCollection of Points with the same properties and a connectors array",
+        content_type = "text/csv"
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Dry-run report", body = ImportReportResponse),
+        (status = 201, description = "All stations and connectors created", body = ImportReportResponse),
+        (status = 400, description = "Unreadable file or unknown format"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only"),
+        (status = 422, description = "Some rows are invalid, nothing was created", body = ImportReportResponse)
+    )
+)]
+pub async fn import_stations(
+    req: HttpRequest,
+    query: web::Query<ImportQuery>,
+    body: web::Bytes,
+    service: web::Data<Arc<StationImportServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    let claims = require_admin_auth(&req, &validator).await?;
+
+    let format = match query.format.as_deref() {
+        Some(format) => ImportFormat::from_str(format)?,
+        None => ImportFormat::from_content_type(req.content_type()).ok_or_else(|| {
+            AppError::ValidationError(
+                "Send text/csv or application/geo+json, or set format=csv|geojson".to_string(),
+            )
+        })?,
+    };
+    let text = std::str::from_utf8(&body)
+        .map_err(|_| AppError::ValidationError("The file must be UTF-8 encoded".to_string()))?;
+    let dry_run = query.dry_run.unwrap_or(true);
+
+    let report = service
+        .import_stations(format, text, dry_run, &claims.sub)
+        .await?;
+    let response = ImportReportResponse::from(report);
+
+    Ok(if response.committed {
+        HttpResponse::Created().json(response)
+    } else if !dry_run {
+        HttpResponse::UnprocessableEntity().json(response)
+    } else {
+        HttpResponse::Ok().json(response)
+    })
+}
+
+/// Imported files may be larger than the default payload limit, which
+/// still applies to every other endpoint
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("/stations/import")
+            .app_data(web::PayloadConfig::new(MAX_IMPORT_BYTES))
+            .route(web::post().to(import_stations)),
+    );
+}
@@ -1,4 +1,14 @@
 pub mod connector_controller;
+pub mod deleted_controller;
+pub mod duplicate_controller;
+pub mod export_controller;
 pub mod health_controller;
+pub mod import_controller;
 pub mod network_controller;
+pub mod ocpi_controller;
+pub mod ocpi_party_controller;
+pub mod region_controller;
+pub mod review_moderation_controller;
 pub mod station_controller;
+pub mod sync_controller;
+pub mod verification_controller;
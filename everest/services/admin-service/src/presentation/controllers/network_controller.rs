@@ -131,7 +131,6 @@ pub async fn update_network(
                 network_type: body.network_type.clone(),
                 support_phone: body.support_phone.clone(),
                 support_email: body.support_email.clone(),
-                is_verified: body.is_verified,
             },
         )
         .await?;
@@ -148,8 +147,9 @@ pub async fn update_network(
     ),
     security(("bearer_auth" = [])),
     responses(
-        (status = 204, description = "Network deleted"),
+        (status = 204, description = "Network deleted until restored or purged"),
         (status = 404, description = "Network not found"),
+        (status = 409, description = "Stations still use the network"),
         (status = 401, description = "Unauthorized"),
         (status = 403, description = "Forbidden - Admin only")
     )
@@ -161,17 +161,51 @@ pub async fn delete_network(
     service: web::Data<Arc<NetworkServiceImpl>>,
     validator: web::Data<Arc<JwtValidator>>,
 ) -> Result<HttpResponse, AppError> {
-    require_admin_auth(&req, &validator).await?;
+    let claims = require_admin_auth(&req, &validator).await?;
 
-    service.delete_network(&path.into_inner()).await?;
+    service
+        .delete_network(&path.into_inner(), &claims.sub)
+        .await?;
 
     Ok(HttpResponse::NoContent().finish())
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/networks/{id}/restore",
+    tag = "Networks",
+    params(
+        ("id" = String, Path, description = "Network ID")
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Network restored", body = NetworkResponse),
+        (status = 404, description = "Deleted network not found"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only")
+    )
+)]
+#[post("/networks/{id}/restore")]
+pub async fn restore_network(
+    req: HttpRequest,
+    path: web::Path<String>,
+    service: web::Data<Arc<NetworkServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    let claims = require_admin_auth(&req, &validator).await?;
+
+    let network = service
+        .restore_network(&path.into_inner(), &claims.sub)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(NetworkResponse::from(network)))
+}
+
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(create_network)
         .service(list_networks)
         .service(get_network)
         .service(update_network)
-        .service(delete_network);
+        .service(delete_network)
+        .service(restore_network);
 }
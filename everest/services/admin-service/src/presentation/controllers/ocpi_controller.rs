@@ -0,0 +1,525 @@
+//! OCPI 2.2.1 CPO endpoints, authenticated with the party's token rather
+//! than a JWT and answering in the OCPI envelope.
+
+use crate::application::ocpi_service::OcpiServiceImpl;
+use crate::core::constants::{MAX_OCPI_PAGE, OCPI_VERSION};
+use crate::core::errors::AppError;
+use crate::domain::entities::OcpiParty;
+use crate::domain::ocpi::{
+    self, Credentials, OcpiResponse, OcpiToken, STATUS_CLIENT_API_ERROR, STATUS_CLIENT_ERROR,
+    STATUS_INVALID_PARAMETERS, STATUS_SERVER_ERROR, STATUS_UNKNOWN_LOCATION, Version,
+};
+use crate::domain::services::OcpiService;
+use actix_web::http::StatusCode;
+use actix_web::{
+    HttpRequest, HttpResponse, HttpResponseBuilder, ResponseError, delete, get, post, put, web,
+};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::fmt;
+use std::sync::Arc;
+
+/// An error in the OCPI envelope
+#[derive(Debug)]
+pub struct OcpiError {
+    status: StatusCode,
+    status_code: u16,
+    message: String,
+}
+
+impl OcpiError {
+    /// Registration fails with 3001 when the party's own endpoints did
+    pub fn registration(error: AppError) -> Self {
+        match error {
+            AppError::InternalError(message) => Self {
+                status: StatusCode::INTERNAL_SERVER_ERROR,
+                status_code: STATUS_CLIENT_API_ERROR,
+                message,
+            },
+            other => other.into(),
+        }
+    }
+}
+
+impl From<AppError> for OcpiError {
+    fn from(error: AppError) -> Self {
+        let (status, status_code) = match &error {
+            AppError::NotFound(_) => (StatusCode::NOT_FOUND, STATUS_UNKNOWN_LOCATION),
+            AppError::ValidationError(_) => (StatusCode::BAD_REQUEST, STATUS_INVALID_PARAMETERS),
+            AppError::Unauthorized(_) => (StatusCode::UNAUTHORIZED, STATUS_CLIENT_ERROR),
+            AppError::Forbidden(_) => (StatusCode::FORBIDDEN, STATUS_CLIENT_ERROR),
+            // Credentials calls not matching the registration state
+            AppError::Conflict(_) => (StatusCode::METHOD_NOT_ALLOWED, STATUS_CLIENT_ERROR),
+            AppError::DatabaseError(_) | AppError::InternalError(_) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, STATUS_SERVER_ERROR)
+            }
+        };
+        Self {
+            status,
+            status_code,
+            message: error.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for OcpiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "OCPI status {}: {}", self.status_code, self.message)
+    }
+}
+
+impl ResponseError for OcpiError {
+    fn status_code(&self) -> StatusCode {
+        self.status
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status).json(OcpiResponse::<()>::error(
+            self.status_code,
+            self.message.clone(),
+        ))
+    }
+}
+
+#[derive(serde::Deserialize)]
+pub struct OcpiPageQuery {
+    pub date_from: Option<String>,
+    pub date_to: Option<String>,
+    pub offset: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+/// The party calling with `Authorization: Token <token>`. OCPI 2.2 sends
+/// the token base64 encoded; older clients send it as is.
+async fn authenticate(
+    req: &HttpRequest,
+    service: &OcpiServiceImpl,
+) -> Result<(OcpiParty, OcpiToken), OcpiError> {
+    let token = req
+        .headers()
+        .get("Authorization")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Token "))
+        .map(str::trim)
+        .ok_or_else(|| AppError::Unauthorized("Missing OCPI token".to_string()))?;
+
+    let decoded = BASE64
+        .decode(token)
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok());
+    if let Some(decoded) = decoded
+        && let Ok(found) = service.authenticate(&decoded).await
+    {
+        return Ok(found);
+    }
+    Ok(service.authenticate(token).await?)
+}
+
+/// Token A only opens the versions and credentials endpoints
+async fn authenticate_access(
+    req: &HttpRequest,
+    service: &OcpiServiceImpl,
+) -> Result<OcpiParty, OcpiError> {
+    match authenticate(req, service).await? {
+        (party, OcpiToken::Access) => Ok(party),
+        (_, OcpiToken::Registration) => {
+            Err(AppError::Unauthorized("Register before using this endpoint".to_string()).into())
+        }
+    }
+}
+
+/// A response echoing the request and correlation ids
+fn ocpi_response(req: &HttpRequest) -> HttpResponseBuilder {
+    let mut response = HttpResponse::Ok();
+    for header in ["X-Request-ID", "X-Correlation-ID"] {
+        if let Some(value) = req.headers().get(header) {
+            response.insert_header((header, value.clone()));
+        }
+    }
+    response
+}
+
+fn ocpi_ok<T: Serialize>(req: &HttpRequest, data: Option<T>) -> HttpResponse {
+    ocpi_response(req).json(OcpiResponse::success(data))
+}
+
+/// Date window, limit and offset of a paginated GET
+struct Page {
+    date_from: Option<DateTime<Utc>>,
+    date_to: Option<DateTime<Utc>>,
+    limit: i64,
+    offset: i64,
+}
+
+fn page(query: &OcpiPageQuery) -> Result<Page, OcpiError> {
+    Ok(Page {
+        date_from: query
+            .date_from
+            .as_deref()
+            .map(ocpi::parse_timestamp)
+            .transpose()?,
+        date_to: query
+            .date_to
+            .as_deref()
+            .map(ocpi::parse_timestamp)
+            .transpose()?,
+        limit: query.limit.unwrap_or(MAX_OCPI_PAGE).clamp(1, MAX_OCPI_PAGE),
+        offset: query.offset.unwrap_or(0).max(0),
+    })
+}
+
+/// A page with the X-Total-Count and X-Limit headers, and a Link to the
+/// next page if any
+fn paginated<T: Serialize>(
+    req: &HttpRequest,
+    service: &OcpiServiceImpl,
+    page: &Page,
+    items: Vec<T>,
+    total: i64,
+) -> HttpResponse {
+    let mut response = ocpi_response(req);
+    response
+        .insert_header(("X-Total-Count", total.to_string()))
+        .insert_header(("X-Limit", page.limit.to_string()));
+
+    let next_offset = page.offset + items.len() as i64;
+    if !items.is_empty() && next_offset < total {
+        let mut next = format!(
+            "{}{}?offset={}&limit={}",
+            service.identity().base_url,
+            req.path(),
+            next_offset,
+            page.limit
+        );
+        if let Some(date_from) = page.date_from {
+            next.push_str(&format!("&date_from={}", ocpi::format_timestamp(date_from)));
+        }
+        if let Some(date_to) = page.date_to {
+            next.push_str(&format!("&date_to={}", ocpi::format_timestamp(date_to)));
+        }
+        response.insert_header(("Link", format!("<{}>; rel=\"next\"", next)));
+    }
+
+    response.json(OcpiResponse::success(Some(items)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/ocpi/versions",
+    tag = "OCPI",
+    security(("ocpi_token" = [])),
+    responses(
+        (status = 200, description = "Supported OCPI versions"),
+        (status = 401, description = "Unknown token")
+    )
+)]
+#[get("/versions")]
+pub async fn get_versions(
+    req: HttpRequest,
+    service: web::Data<Arc<OcpiServiceImpl>>,
+) -> Result<HttpResponse, OcpiError> {
+    authenticate(&req, &service).await?;
+
+    let identity = service.identity();
+    Ok(ocpi_ok(
+        &req,
+        Some(vec![Version {
+            version: OCPI_VERSION.to_string(),
+            url: format!("{}/ocpi/{}", identity.base_url, OCPI_VERSION),
+        }]),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/ocpi/2.2.1",
+    tag = "OCPI",
+    security(("ocpi_token" = [])),
+    responses(
+        (status = 200, description = "Modules we implement, as sender"),
+        (status = 401, description = "Unknown token")
+    )
+)]
+#[get("/2.2.1")]
+pub async fn get_version_details(
+    req: HttpRequest,
+    service: web::Data<Arc<OcpiServiceImpl>>,
+) -> Result<HttpResponse, OcpiError> {
+    authenticate(&req, &service).await?;
+
+    Ok(ocpi_ok(&req, Some(service.identity().version_details())))
+}
+
+#[utoipa::path(
+    get,
+    path = "/ocpi/2.2.1/credentials",
+    tag = "OCPI",
+    security(("ocpi_token" = [])),
+    responses(
+        (status = 200, description = "Our credentials for the calling token"),
+        (status = 401, description = "Unknown token")
+    )
+)]
+#[get("/2.2.1/credentials")]
+pub async fn get_credentials(
+    req: HttpRequest,
+    service: web::Data<Arc<OcpiServiceImpl>>,
+) -> Result<HttpResponse, OcpiError> {
+    let (party, kind) = authenticate(&req, &service).await?;
+
+    let token = match kind {
+        OcpiToken::Registration => party.token_a,
+        OcpiToken::Access => party.token_c,
+    }
+    .unwrap_or_default();
+    Ok(ocpi_ok(&req, Some(service.identity().credentials(&token))))
+}
+
+#[utoipa::path(
+    post,
+    path = "/ocpi/2.2.1/credentials",
+    tag = "OCPI",
+    request_body(content = Object, description = "The party's OCPI credentials, with token B"),
+    security(("ocpi_token" = [])),
+    responses(
+        (status = 200, description = "Registered with token A; our credentials with token C"),
+        (status = 401, description = "Unknown token"),
+        (status = 405, description = "Already registered"),
+        (status = 500, description = "The party's versions could not be read (status 3001)")
+    )
+)]
+#[post("/2.2.1/credentials")]
+pub async fn register_credentials(
+    req: HttpRequest,
+    body: web::Json<Credentials>,
+    service: web::Data<Arc<OcpiServiceImpl>>,
+) -> Result<HttpResponse, OcpiError> {
+    let (party, _) = authenticate(&req, &service).await?;
+
+    let credentials = service
+        .register(&party, body.into_inner())
+        .await
+        .map_err(OcpiError::registration)?;
+
+    Ok(ocpi_ok(&req, Some(credentials)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/ocpi/2.2.1/credentials",
+    tag = "OCPI",
+    request_body(content = Object, description = "The party's OCPI credentials, with token B"),
+    security(("ocpi_token" = [])),
+    responses(
+        (status = 200, description = "Registration updated; our credentials with a new token C"),
+        (status = 401, description = "Unknown token"),
+        (status = 405, description = "Not registered yet"),
+        (status = 500, description = "The party's versions could not be read (status 3001)")
+    )
+)]
+#[put("/2.2.1/credentials")]
+pub async fn update_credentials(
+    req: HttpRequest,
+    body: web::Json<Credentials>,
+    service: web::Data<Arc<OcpiServiceImpl>>,
+) -> Result<HttpResponse, OcpiError> {
+    let (party, _) = authenticate(&req, &service).await?;
+
+    let credentials = service
+        .update_registration(&party, body.into_inner())
+        .await
+        .map_err(OcpiError::registration)?;
+
+    Ok(ocpi_ok(&req, Some(credentials)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/ocpi/2.2.1/credentials",
+    tag = "OCPI",
+    security(("ocpi_token" = [])),
+    responses(
+        (status = 200, description = "Unregistered, token C revoked"),
+        (status = 401, description = "Unknown token"),
+        (status = 405, description = "Not registered")
+    )
+)]
+#[delete("/2.2.1/credentials")]
+pub async fn delete_credentials(
+    req: HttpRequest,
+    service: web::Data<Arc<OcpiServiceImpl>>,
+) -> Result<HttpResponse, OcpiError> {
+    let (party, _) = authenticate(&req, &service).await?;
+
+    service.unregister(&party).await?;
+
+    Ok(ocpi_ok::<()>(&req, None))
+}
+
+#[utoipa::path(
+    get,
+    path = "/ocpi/2.2.1/locations",
+    tag = "OCPI",
+    params(
+        ("date_from" = Option<String>, Query, description = "Only locations last updated at or after; includes removed ones"),
+        ("date_to" = Option<String>, Query, description = "Only locations last updated before"),
+        ("offset" = Option<i64>, Query, description = "Offset"),
+        ("limit" = Option<i64>, Query, description = "Items per page (max 100)")
+    ),
+    security(("ocpi_token" = [])),
+    responses(
+        (status = 200, description = "Locations by last update, with X-Total-Count, X-Limit and Link headers"),
+        (status = 400, description = "Invalid date (status 2001)"),
+        (status = 401, description = "Unknown token or not registered")
+    )
+)]
+#[get("/2.2.1/locations")]
+pub async fn list_locations(
+    req: HttpRequest,
+    query: web::Query<OcpiPageQuery>,
+    service: web::Data<Arc<OcpiServiceImpl>>,
+) -> Result<HttpResponse, OcpiError> {
+    authenticate_access(&req, &service).await?;
+
+    let page = page(&query)?;
+    let (locations, total) = service
+        .list_locations(page.date_from, page.date_to, page.limit, page.offset)
+        .await?;
+
+    Ok(paginated(&req, &service, &page, locations, total))
+}
+
+#[utoipa::path(
+    get,
+    path = "/ocpi/2.2.1/locations/{location_id}",
+    tag = "OCPI",
+    params(("location_id" = String, Path, description = "Station ID")),
+    security(("ocpi_token" = [])),
+    responses(
+        (status = 200, description = "The location"),
+        (status = 401, description = "Unknown token or not registered"),
+        (status = 404, description = "Unknown location (status 2003)")
+    )
+)]
+#[get("/2.2.1/locations/{location_id}")]
+pub async fn get_location(
+    req: HttpRequest,
+    path: web::Path<String>,
+    service: web::Data<Arc<OcpiServiceImpl>>,
+) -> Result<HttpResponse, OcpiError> {
+    authenticate_access(&req, &service).await?;
+
+    let location = service.get_location(&path.into_inner()).await?;
+
+    Ok(ocpi_ok(&req, Some(location)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/ocpi/2.2.1/locations/{location_id}/{evse_uid}",
+    tag = "OCPI",
+    params(
+        ("location_id" = String, Path, description = "Station ID"),
+        ("evse_uid" = String, Path, description = "EVSE uid, {connector_id}-{n}")
+    ),
+    security(("ocpi_token" = [])),
+    responses(
+        (status = 200, description = "The EVSE"),
+        (status = 401, description = "Unknown token or not registered"),
+        (status = 404, description = "Unknown location or EVSE (status 2003)")
+    )
+)]
+#[get("/2.2.1/locations/{location_id}/{evse_uid}")]
+pub async fn get_evse(
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+    service: web::Data<Arc<OcpiServiceImpl>>,
+) -> Result<HttpResponse, OcpiError> {
+    authenticate_access(&req, &service).await?;
+
+    let (location_id, evse_uid) = path.into_inner();
+    let evse = service.get_evse(&location_id, &evse_uid).await?;
+
+    Ok(ocpi_ok(&req, Some(evse)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/ocpi/2.2.1/locations/{location_id}/{evse_uid}/{connector_id}",
+    tag = "OCPI",
+    params(
+        ("location_id" = String, Path, description = "Station ID"),
+        ("evse_uid" = String, Path, description = "EVSE uid, {connector_id}-{n}"),
+        ("connector_id" = String, Path, description = "Connector of the EVSE, always 1")
+    ),
+    security(("ocpi_token" = [])),
+    responses(
+        (status = 200, description = "The connector"),
+        (status = 401, description = "Unknown token or not registered"),
+        (status = 404, description = "Unknown location, EVSE or connector (status 2003)")
+    )
+)]
+#[get("/2.2.1/locations/{location_id}/{evse_uid}/{connector_id}")]
+pub async fn get_connector(
+    req: HttpRequest,
+    path: web::Path<(String, String, String)>,
+    service: web::Data<Arc<OcpiServiceImpl>>,
+) -> Result<HttpResponse, OcpiError> {
+    authenticate_access(&req, &service).await?;
+
+    let (location_id, evse_uid, connector_id) = path.into_inner();
+    let connector = service
+        .get_connector(&location_id, &evse_uid, &connector_id)
+        .await?;
+
+    Ok(ocpi_ok(&req, Some(connector)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/ocpi/2.2.1/tariffs",
+    tag = "OCPI",
+    params(
+        ("date_from" = Option<String>, Query, description = "Only tariffs last updated at or after"),
+        ("date_to" = Option<String>, Query, description = "Only tariffs last updated before"),
+        ("offset" = Option<i64>, Query, description = "Offset"),
+        ("limit" = Option<i64>, Query, description = "Items per page (max 100)")
+    ),
+    security(("ocpi_token" = [])),
+    responses(
+        (status = 200, description = "Active tariffs of verified networks, with X-Total-Count, X-Limit and Link headers"),
+        (status = 400, description = "Invalid date (status 2001)"),
+        (status = 401, description = "Unknown token or not registered")
+    )
+)]
+#[get("/2.2.1/tariffs")]
+pub async fn list_tariffs(
+    req: HttpRequest,
+    query: web::Query<OcpiPageQuery>,
+    service: web::Data<Arc<OcpiServiceImpl>>,
+) -> Result<HttpResponse, OcpiError> {
+    authenticate_access(&req, &service).await?;
+
+    let page = page(&query)?;
+    let (tariffs, total) = service
+        .list_tariffs(page.date_from, page.date_to, page.limit, page.offset)
+        .await?;
+
+    Ok(paginated(&req, &service, &page, tariffs, total))
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_versions)
+        .service(get_version_details)
+        .service(get_credentials)
+        .service(register_credentials)
+        .service(update_credentials)
+        .service(delete_credentials)
+        .service(list_locations)
+        .service(get_location)
+        .service(get_evse)
+        .service(get_connector)
+        .service(list_tariffs);
+}
@@ -0,0 +1,127 @@
+use crate::application::dtos::ocpi::{
+    CreateOcpiPartyRequest, OcpiPartyCreatedResponse, OcpiPartyResponse, OcpiPushResponse,
+};
+use crate::application::ocpi_service::OcpiServiceImpl;
+use crate::core::auth::{JwtValidator, require_admin_auth};
+use crate::core::errors::AppError;
+use crate::domain::services::OcpiService;
+use actix_web::{HttpRequest, HttpResponse, delete, get, post, web};
+use std::sync::Arc;
+
+#[utoipa::path(
+    post,
+    path = "/api/ocpi/parties",
+    tag = "OCPI parties",
+    request_body = CreateOcpiPartyRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 201, description = "Party created with its registration token", body = OcpiPartyCreatedResponse),
+        (status = 400, description = "Validation error"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only")
+    )
+)]
+#[post("/ocpi/parties")]
+pub async fn create_ocpi_party(
+    req: HttpRequest,
+    body: web::Json<CreateOcpiPartyRequest>,
+    service: web::Data<Arc<OcpiServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    let claims = require_admin_auth(&req, &validator).await?;
+
+    let party = service.create_party(&body.name, &claims.sub).await?;
+
+    Ok(HttpResponse::Created().json(OcpiPartyCreatedResponse {
+        party: OcpiPartyResponse::from(party),
+        our_versions_url: service.identity().versions_url(),
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/ocpi/parties",
+    tag = "OCPI parties",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Parties, most recent first", body = Vec<OcpiPartyResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only")
+    )
+)]
+#[get("/ocpi/parties")]
+pub async fn list_ocpi_parties(
+    req: HttpRequest,
+    service: web::Data<Arc<OcpiServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    require_admin_auth(&req, &validator).await?;
+
+    let parties = service.list_parties().await?;
+
+    Ok(HttpResponse::Ok().json(
+        parties
+            .into_iter()
+            .map(OcpiPartyResponse::from)
+            .collect::<Vec<_>>(),
+    ))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/ocpi/parties/{party_uid}",
+    tag = "OCPI parties",
+    params(("party_uid" = String, Path, description = "Party UID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Party unregistered, its tokens revoked", body = OcpiPartyResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only"),
+        (status = 404, description = "Party not found"),
+        (status = 409, description = "Party already unregistered")
+    )
+)]
+#[delete("/ocpi/parties/{party_uid}")]
+pub async fn unregister_ocpi_party(
+    req: HttpRequest,
+    path: web::Path<String>,
+    service: web::Data<Arc<OcpiServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    require_admin_auth(&req, &validator).await?;
+
+    let party = service.unregister_party(&path.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(OcpiPartyResponse::from(party)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/ocpi/push",
+    tag = "OCPI parties",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Location updates pushed to the registered parties", body = OcpiPushResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only")
+    )
+)]
+#[post("/ocpi/push")]
+pub async fn push_ocpi_locations(
+    req: HttpRequest,
+    service: web::Data<Arc<OcpiServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    require_admin_auth(&req, &validator).await?;
+
+    let pushed = service.push_locations().await?;
+
+    Ok(HttpResponse::Ok().json(OcpiPushResponse { pushed }))
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(create_ocpi_party)
+        .service(list_ocpi_parties)
+        .service(unregister_ocpi_party)
+        .service(push_ocpi_locations);
+}
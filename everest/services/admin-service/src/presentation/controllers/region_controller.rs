@@ -0,0 +1,190 @@
+use crate::application::dtos::region::{
+    CreateOsmRegionRequest, CreateRegionRequest, RegionResponse, RegionStatisticsResponse,
+};
+use crate::application::region_service::RegionServiceImpl;
+use crate::core::auth::{JwtValidator, require_admin_auth};
+use crate::core::errors::AppError;
+use crate::domain::services::RegionService;
+use crate::domain::value_objects::{CreateRegionData, RegionBoundary};
+use actix_web::{HttpRequest, HttpResponse, delete, get, post, web};
+use std::sync::Arc;
+
+#[utoipa::path(
+    post,
+    path = "/api/regions",
+    tag = "Regions",
+    request_body = CreateRegionRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 201, description = "Region created and stations assigned", body = RegionResponse),
+        (status = 400, description = "Validation error"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only"),
+        (status = 409, description = "Region already exists")
+    )
+)]
+#[post("/regions")]
+pub async fn create_region(
+    req: HttpRequest,
+    body: web::Json<CreateRegionRequest>,
+    service: web::Data<Arc<RegionServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    require_admin_auth(&req, &validator).await?;
+
+    let body = body.into_inner();
+    let region = service
+        .create_region(CreateRegionData {
+            region_id: body.region_id,
+            name: body.name,
+            country_code: body.country_code,
+            boundary: RegionBoundary::GeoJson(body.boundary.to_string()),
+        })
+        .await?;
+
+    Ok(HttpResponse::Created().json(RegionResponse::from(region)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/regions/from-osm",
+    tag = "Regions",
+    request_body = CreateOsmRegionRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 201, description = "Region created and stations assigned", body = RegionResponse),
+        (status = 400, description = "Validation error or boundary not in the OSM import"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only"),
+        (status = 409, description = "Region already exists")
+    )
+)]
+#[post("/regions/from-osm")]
+pub async fn create_region_from_osm(
+    req: HttpRequest,
+    body: web::Json<CreateOsmRegionRequest>,
+    service: web::Data<Arc<RegionServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    require_admin_auth(&req, &validator).await?;
+
+    let body = body.into_inner();
+    let region = service
+        .create_region(CreateRegionData {
+            region_id: body.region_id,
+            name: body.name,
+            country_code: None,
+            boundary: RegionBoundary::OsmIsoCode(body.iso_code.trim().to_uppercase()),
+        })
+        .await?;
+
+    Ok(HttpResponse::Created().json(RegionResponse::from(region)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/regions",
+    tag = "Regions",
+    responses(
+        (status = 200, description = "Regions", body = Vec<RegionResponse>)
+    )
+)]
+#[get("/regions")]
+pub async fn list_regions(
+    service: web::Data<Arc<RegionServiceImpl>>,
+) -> Result<HttpResponse, AppError> {
+    let regions = service.list_regions().await?;
+    let response: Vec<RegionResponse> = regions.into_iter().map(RegionResponse::from).collect();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/regions/statistics",
+    tag = "Regions",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Station, connector and sync figures per region", body = Vec<RegionStatisticsResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only")
+    )
+)]
+#[get("/regions/statistics")]
+pub async fn region_statistics(
+    req: HttpRequest,
+    service: web::Data<Arc<RegionServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    require_admin_auth(&req, &validator).await?;
+
+    let statistics = service.region_statistics().await?;
+
+    Ok(HttpResponse::Ok().json(
+        statistics
+            .into_iter()
+            .map(RegionStatisticsResponse::from)
+            .collect::<Vec<_>>(),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/regions/{id}",
+    tag = "Regions",
+    params(
+        ("id" = String, Path, description = "Region ID")
+    ),
+    responses(
+        (status = 200, description = "Region details", body = RegionResponse),
+        (status = 404, description = "Region not found")
+    )
+)]
+#[get("/regions/{id}")]
+pub async fn get_region(
+    path: web::Path<String>,
+    service: web::Data<Arc<RegionServiceImpl>>,
+) -> Result<HttpResponse, AppError> {
+    let region = service.get_region(&path.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(RegionResponse::from(region)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/regions/{id}",
+    tag = "Regions",
+    params(
+        ("id" = String, Path, description = "Region ID")
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 204, description = "Region deleted, its stations reassigned"),
+        (status = 404, description = "Region not found"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only")
+    )
+)]
+#[delete("/regions/{id}")]
+pub async fn delete_region(
+    req: HttpRequest,
+    path: web::Path<String>,
+    service: web::Data<Arc<RegionServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    require_admin_auth(&req, &validator).await?;
+
+    service.delete_region(&path.into_inner()).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    // Before /regions/{id}, which would match "statistics"
+    cfg.service(region_statistics)
+        .service(list_regions)
+        .service(get_region)
+        .service(create_region)
+        .service(create_region_from_osm)
+        .service(delete_region);
+}
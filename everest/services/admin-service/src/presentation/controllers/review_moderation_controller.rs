@@ -0,0 +1,165 @@
+use crate::application::dtos::review::{
+    ModerationActionRequest, ModerationQueueItemResponse, ModerationQueueResponse, ReviewResponse,
+};
+use crate::application::review_moderation_service::ReviewModerationServiceImpl;
+use crate::core::auth::{JwtValidator, require_admin_auth};
+use crate::core::constants::MAX_MODERATION_QUEUE_PAGE;
+use crate::core::errors::AppError;
+use crate::domain::moderation::ModerationStatus;
+use crate::domain::services::ReviewModerationService;
+use actix_web::{HttpRequest, HttpResponse, get, post, web};
+use std::str::FromStr;
+use std::sync::Arc;
+use validator::Validate;
+
+#[derive(serde::Deserialize)]
+pub struct ModerationQueueQuery {
+    pub status: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/reviews/moderation/queue",
+    tag = "Review moderation",
+    params(
+        ("status" = Option<String>, Query, description = "visible, pending or hidden (default: pending and flagged reviews)"),
+        ("limit" = Option<i64>, Query, description = "Items per page (max 100)"),
+        ("offset" = Option<i64>, Query, description = "Offset")
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Reviews awaiting moderation, most flagged first", body = ModerationQueueResponse),
+        (status = 400, description = "Unknown status"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only")
+    )
+)]
+#[get("/reviews/moderation/queue")]
+pub async fn get_moderation_queue(
+    req: HttpRequest,
+    query: web::Query<ModerationQueueQuery>,
+    service: web::Data<Arc<ReviewModerationServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    require_admin_auth(&req, &validator).await?;
+
+    let status = query
+        .status
+        .as_deref()
+        .map(ModerationStatus::from_str)
+        .transpose()?;
+    let limit = query
+        .limit
+        .unwrap_or(20)
+        .clamp(1, MAX_MODERATION_QUEUE_PAGE);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    let (entries, total) = service.list_queue(status, limit, offset).await?;
+
+    Ok(HttpResponse::Ok().json(ModerationQueueResponse {
+        reviews: entries
+            .into_iter()
+            .map(ModerationQueueItemResponse::from)
+            .collect(),
+        total,
+    }))
+}
+
+async fn moderate(
+    req: HttpRequest,
+    review_id: String,
+    status: ModerationStatus,
+    body: ModerationActionRequest,
+    service: &ReviewModerationServiceImpl,
+    validator: &JwtValidator,
+) -> Result<HttpResponse, AppError> {
+    let claims = require_admin_auth(&req, validator).await?;
+    body.validate()
+        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+
+    let review = service
+        .moderate_review(&review_id, status, body.reason, &claims.sub)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(ReviewResponse::from(review)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/reviews/{id}/hide",
+    tag = "Review moderation",
+    params(
+        ("id" = String, Path, description = "Review ID")
+    ),
+    request_body = ModerationActionRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Review hidden and its open flags resolved", body = ReviewResponse),
+        (status = 400, description = "Missing reason"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only"),
+        (status = 404, description = "Review not found")
+    )
+)]
+#[post("/reviews/{id}/hide")]
+pub async fn hide_review(
+    req: HttpRequest,
+    path: web::Path<String>,
+    body: web::Json<ModerationActionRequest>,
+    service: web::Data<Arc<ReviewModerationServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    moderate(
+        req,
+        path.into_inner(),
+        ModerationStatus::Hidden,
+        body.into_inner(),
+        &service,
+        &validator,
+    )
+    .await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/reviews/{id}/restore",
+    tag = "Review moderation",
+    params(
+        ("id" = String, Path, description = "Review ID")
+    ),
+    request_body = ModerationActionRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Review visible again and its open flags resolved", body = ReviewResponse),
+        (status = 400, description = "Missing reason"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only"),
+        (status = 404, description = "Review not found")
+    )
+)]
+#[post("/reviews/{id}/restore")]
+pub async fn restore_review(
+    req: HttpRequest,
+    path: web::Path<String>,
+    body: web::Json<ModerationActionRequest>,
+    service: web::Data<Arc<ReviewModerationServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    moderate(
+        req,
+        path.into_inner(),
+        ModerationStatus::Visible,
+        body.into_inner(),
+        &service,
+        &validator,
+    )
+    .await
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_moderation_queue)
+        .service(hide_review)
+        .service(restore_review);
+}
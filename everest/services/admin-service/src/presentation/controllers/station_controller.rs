@@ -1,12 +1,15 @@
 use crate::application::dtos::station::{
-    CreateStationRequest, StationResponse, UpdateStationRequest,
+    CreateStationRequest, FieldSourceResponse, StationResponse, UpdateFieldSourceRequest,
+    UpdateStationRequest,
 };
 use crate::application::station_service::StationServiceImpl;
 use crate::core::auth::{JwtValidator, require_admin_auth};
 use crate::core::errors::AppError;
+use crate::domain::provenance::{DataSource, StationField};
 use crate::domain::services::StationService;
 use crate::domain::value_objects::{CreateStationData, UpdateStationData};
 use actix_web::{HttpRequest, HttpResponse, delete, get, post, put, web};
+use std::str::FromStr;
 use std::sync::Arc;
 
 #[utoipa::path(
@@ -56,7 +59,8 @@ pub async fn create_station(
     params(
         ("limit" = Option<i64>, Query, description = "Items per page"),
         ("offset" = Option<i64>, Query, description = "Offset"),
-        ("network_id" = Option<String>, Query, description = "Filter by network ID")
+        ("network_id" = Option<String>, Query, description = "Filter by network ID"),
+        ("region" = Option<String>, Query, description = "Filter by region ID, e.g. tunisia")
     ),
     responses(
         (status = 200, description = "Stations list", body = Vec<StationResponse>)
@@ -71,7 +75,12 @@ pub async fn list_stations(
     let offset = query.offset.unwrap_or(0);
 
     let (stations, _total) = service
-        .list_stations(query.network_id.clone(), limit, offset)
+        .list_stations(
+            query.network_id.clone(),
+            query.region.clone(),
+            limit,
+            offset,
+        )
         .await?;
     let response: Vec<StationResponse> = stations.into_iter().map(StationResponse::from).collect();
 
@@ -83,6 +92,7 @@ pub struct StationListQuery {
     pub limit: Option<i64>,
     pub offset: Option<i64>,
     pub network_id: Option<String>,
+    pub region: Option<String>,
 }
 
 #[utoipa::path(
@@ -131,7 +141,7 @@ pub async fn update_station(
     service: web::Data<Arc<StationServiceImpl>>,
     validator: web::Data<Arc<JwtValidator>>,
 ) -> Result<HttpResponse, AppError> {
-    require_admin_auth(&req, &validator).await?;
+    let claims = require_admin_auth(&req, &validator).await?;
 
     let station = service
         .update_station(
@@ -147,6 +157,7 @@ pub async fn update_station(
                     .map(|t| serde_json::to_value(t).unwrap_or_default()),
                 network_id: body.network_id.clone(),
             },
+            &claims.sub,
         )
         .await?;
 
@@ -162,7 +173,7 @@ pub async fn update_station(
     ),
     security(("bearer_auth" = [])),
     responses(
-        (status = 204, description = "Station deleted"),
+        (status = 204, description = "Station and its connectors deleted until restored or purged"),
         (status = 404, description = "Station not found"),
         (status = 401, description = "Unauthorized"),
         (status = 403, description = "Forbidden - Admin only")
@@ -175,17 +186,130 @@ pub async fn delete_station(
     service: web::Data<Arc<StationServiceImpl>>,
     validator: web::Data<Arc<JwtValidator>>,
 ) -> Result<HttpResponse, AppError> {
-    require_admin_auth(&req, &validator).await?;
+    let claims = require_admin_auth(&req, &validator).await?;
 
-    service.delete_station(&path.into_inner()).await?;
+    service
+        .delete_station(&path.into_inner(), &claims.sub)
+        .await?;
 
     Ok(HttpResponse::NoContent().finish())
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/stations/{id}/restore",
+    tag = "Stations",
+    params(
+        ("id" = String, Path, description = "Station ID")
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Station restored with the connectors deleted along with it", body = StationResponse),
+        (status = 404, description = "Deleted station not found"),
+        (status = 409, description = "Its network is deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only")
+    )
+)]
+#[post("/stations/{id}/restore")]
+pub async fn restore_station(
+    req: HttpRequest,
+    path: web::Path<String>,
+    service: web::Data<Arc<StationServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    let claims = require_admin_auth(&req, &validator).await?;
+
+    let station = service
+        .restore_station(&path.into_inner(), &claims.sub)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(StationResponse::from(station)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/stations/{id}/provenance",
+    tag = "Stations",
+    params(
+        ("id" = String, Path, description = "Station ID")
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Source and lock of each station field", body = Vec<FieldSourceResponse>),
+        (status = 404, description = "Station not found"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only")
+    )
+)]
+#[get("/stations/{id}/provenance")]
+pub async fn get_station_provenance(
+    req: HttpRequest,
+    path: web::Path<String>,
+    service: web::Data<Arc<StationServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    require_admin_auth(&req, &validator).await?;
+
+    let sources = service.get_station_provenance(&path.into_inner()).await?;
+    let response: Vec<FieldSourceResponse> =
+        sources.into_iter().map(FieldSourceResponse::from).collect();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/stations/{id}/provenance/{field}",
+    tag = "Stations",
+    params(
+        ("id" = String, Path, description = "Station ID"),
+        ("field" = String, Path, description = "name, address, location or tags")
+    ),
+    request_body = UpdateFieldSourceRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Provenance updated", body = Vec<FieldSourceResponse>),
+        (status = 400, description = "Validation error"),
+        (status = 404, description = "Station not found"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only")
+    )
+)]
+#[put("/stations/{id}/provenance/{field}")]
+pub async fn update_field_provenance(
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+    body: web::Json<UpdateFieldSourceRequest>,
+    service: web::Data<Arc<StationServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    let claims = require_admin_auth(&req, &validator).await?;
+
+    let (station_id, field) = path.into_inner();
+    let field = StationField::from_str(&field)?;
+    let source = body
+        .data_source
+        .as_deref()
+        .map(DataSource::from_str)
+        .transpose()?;
+
+    let sources = service
+        .update_field_provenance(&station_id, field, source, body.locked, &claims.sub)
+        .await?;
+    let response: Vec<FieldSourceResponse> =
+        sources.into_iter().map(FieldSourceResponse::from).collect();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(create_station)
         .service(list_stations)
         .service(get_station)
         .service(update_station)
-        .service(delete_station);
+        .service(delete_station)
+        .service(restore_station)
+        .service(get_station_provenance)
+        .service(update_field_provenance);
 }
@@ -0,0 +1,207 @@
+use crate::application::dtos::sync::{
+    StartSyncRequest, SyncConflictResponse, SyncPreviewResponse, SyncRunListResponse,
+    SyncRunResponse,
+};
+use crate::application::sync_service::OsmSyncServiceImpl;
+use crate::core::auth::{JwtValidator, require_admin_auth};
+use crate::core::constants::MAX_SYNC_RUNS_PAGE;
+use crate::core::errors::AppError;
+use crate::domain::services::OsmSyncService;
+use crate::domain::sync::{SyncStatus, SyncTrigger};
+use actix_web::{HttpRequest, HttpResponse, get, post, web};
+use std::str::FromStr;
+use std::sync::Arc;
+
+#[derive(serde::Deserialize)]
+pub struct SyncPreviewQuery {
+    pub region: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/sync/osm/preview",
+    tag = "Sync",
+    params(
+        ("region" = Option<String>, Query, description = "Only preview the changes to this region")
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "What a sync would change now", body = SyncPreviewResponse),
+        (status = 400, description = "Nothing has been imported into the staging table"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only"),
+        (status = 404, description = "Region not found")
+    )
+)]
+#[get("/sync/osm/preview")]
+pub async fn preview_osm_sync(
+    req: HttpRequest,
+    query: web::Query<SyncPreviewQuery>,
+    service: web::Data<Arc<OsmSyncServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    require_admin_auth(&req, &validator).await?;
+
+    let preview = service.preview_sync(query.region.as_deref()).await?;
+
+    Ok(HttpResponse::Ok().json(SyncPreviewResponse::from(preview)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/sync/osm",
+    tag = "Sync",
+    request_body(content = Option<StartSyncRequest>, description = "Changes from the preview to skip, and the region to limit the sync to"),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 202, description = "Sync started, poll the run for its outcome", body = SyncRunResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only"),
+        (status = 404, description = "Region not found"),
+        (status = 409, description = "A sync is already running")
+    )
+)]
+#[post("/sync/osm")]
+pub async fn start_osm_sync(
+    req: HttpRequest,
+    body: Option<web::Json<StartSyncRequest>>,
+    service: web::Data<Arc<OsmSyncServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    let claims = require_admin_auth(&req, &validator).await?;
+
+    let body = body.map(web::Json::into_inner).unwrap_or_default();
+    let run = service
+        .start_sync(
+            SyncTrigger::Manual,
+            Some(&claims.sub),
+            body.skip_osm_ids,
+            body.region_id,
+        )
+        .await?;
+
+    Ok(HttpResponse::Accepted().json(SyncRunResponse::from(run)))
+}
+
+#[derive(serde::Deserialize)]
+pub struct SyncRunsQuery {
+    pub status: Option<String>,
+    pub region: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/sync/runs",
+    tag = "Sync",
+    params(
+        ("status" = Option<String>, Query, description = "running, succeeded or failed"),
+        ("region" = Option<String>, Query, description = "Only runs limited to this region"),
+        ("limit" = Option<i64>, Query, description = "Items per page (max 100)"),
+        ("offset" = Option<i64>, Query, description = "Offset")
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Sync runs, most recent first", body = SyncRunListResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only")
+    )
+)]
+#[get("/sync/runs")]
+pub async fn list_sync_runs(
+    req: HttpRequest,
+    query: web::Query<SyncRunsQuery>,
+    service: web::Data<Arc<OsmSyncServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    require_admin_auth(&req, &validator).await?;
+
+    let status = query
+        .status
+        .as_deref()
+        .map(SyncStatus::from_str)
+        .transpose()?;
+    let limit = query.limit.unwrap_or(20).clamp(1, MAX_SYNC_RUNS_PAGE);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    let (runs, total) = service
+        .list_runs(status, query.region.as_deref(), limit, offset)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(SyncRunListResponse {
+        runs: runs.into_iter().map(SyncRunResponse::from).collect(),
+        total,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/sync/runs/{id}",
+    tag = "Sync",
+    params(
+        ("id" = String, Path, description = "Sync run ID")
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Sync run", body = SyncRunResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only"),
+        (status = 404, description = "Sync run not found")
+    )
+)]
+#[get("/sync/runs/{id}")]
+pub async fn get_sync_run(
+    req: HttpRequest,
+    path: web::Path<String>,
+    service: web::Data<Arc<OsmSyncServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    require_admin_auth(&req, &validator).await?;
+
+    let run = service.get_run(&path.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(SyncRunResponse::from(run)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/sync/runs/{id}/conflicts",
+    tag = "Sync",
+    params(
+        ("id" = String, Path, description = "Sync run ID")
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Values the run left unapplied", body = Vec<SyncConflictResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only"),
+        (status = 404, description = "Sync run not found")
+    )
+)]
+#[get("/sync/runs/{id}/conflicts")]
+pub async fn list_sync_run_conflicts(
+    req: HttpRequest,
+    path: web::Path<String>,
+    service: web::Data<Arc<OsmSyncServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    require_admin_auth(&req, &validator).await?;
+
+    let conflicts = service.get_run_conflicts(&path.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(
+        conflicts
+            .into_iter()
+            .map(SyncConflictResponse::from)
+            .collect::<Vec<_>>(),
+    ))
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(preview_osm_sync)
+        .service(start_osm_sync)
+        .service(list_sync_runs)
+        .service(get_sync_run)
+        .service(list_sync_run_conflicts);
+}
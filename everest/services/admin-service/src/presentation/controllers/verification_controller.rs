@@ -0,0 +1,264 @@
+use crate::application::dtos::verification::{
+    VerificationDecisionRequest, VerificationEventResponse, VerificationRequestDetailResponse,
+    VerificationRequestListResponse, VerificationRequestResponse,
+};
+use crate::application::network_verification_service::NetworkVerificationServiceImpl;
+use crate::core::auth::{JwtValidator, require_admin_auth};
+use crate::core::constants::MAX_VERIFICATION_REQUESTS_PAGE;
+use crate::core::errors::AppError;
+use crate::domain::services::NetworkVerificationService;
+use crate::domain::verification::VerificationStatus;
+use actix_web::{HttpRequest, HttpResponse, get, post, web};
+use std::str::FromStr;
+use std::sync::Arc;
+use validator::Validate;
+
+#[derive(serde::Deserialize)]
+pub struct VerificationRequestsQuery {
+    pub network_id: Option<String>,
+    pub status: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/network-verifications",
+    tag = "Network verification",
+    params(
+        ("network_id" = Option<String>, Query, description = "Only requests of this network"),
+        ("status" = Option<String>, Query, description = "pending, approved, rejected or revoked"),
+        ("limit" = Option<i64>, Query, description = "Items per page (max 100)"),
+        ("offset" = Option<i64>, Query, description = "Offset")
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Verification requests, newest first", body = VerificationRequestListResponse),
+        (status = 400, description = "Unknown status"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only")
+    )
+)]
+#[get("/network-verifications")]
+pub async fn list_network_verifications(
+    req: HttpRequest,
+    query: web::Query<VerificationRequestsQuery>,
+    service: web::Data<Arc<NetworkVerificationServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    require_admin_auth(&req, &validator).await?;
+
+    let status = query
+        .status
+        .as_deref()
+        .map(VerificationStatus::from_str)
+        .transpose()?;
+    let limit = query
+        .limit
+        .unwrap_or(20)
+        .clamp(1, MAX_VERIFICATION_REQUESTS_PAGE);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    let (requests, total) = service
+        .list_requests(query.network_id.as_deref(), status, limit, offset)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(VerificationRequestListResponse {
+        requests: requests
+            .into_iter()
+            .map(VerificationRequestResponse::from)
+            .collect(),
+        total,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/network-verifications/{id}",
+    tag = "Network verification",
+    params(
+        ("id" = String, Path, description = "Verification request ID")
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "The request with its documents and state changes", body = VerificationRequestDetailResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only"),
+        (status = 404, description = "Request not found")
+    )
+)]
+#[get("/network-verifications/{id}")]
+pub async fn get_network_verification(
+    req: HttpRequest,
+    path: web::Path<String>,
+    service: web::Data<Arc<NetworkVerificationServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    require_admin_auth(&req, &validator).await?;
+
+    let detail = service.get_request(&path.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(VerificationRequestDetailResponse::from(detail)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/network-verifications/{id}/approve",
+    tag = "Network verification",
+    params(
+        ("id" = String, Path, description = "Verification request ID")
+    ),
+    request_body = VerificationDecisionRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Network verified and partner notified", body = VerificationRequestResponse),
+        (status = 400, description = "Validation error or no documents uploaded"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only"),
+        (status = 404, description = "Request not found"),
+        (status = 409, description = "Request is not pending")
+    )
+)]
+#[post("/network-verifications/{id}/approve")]
+pub async fn approve_network_verification(
+    req: HttpRequest,
+    path: web::Path<String>,
+    body: web::Json<VerificationDecisionRequest>,
+    service: web::Data<Arc<NetworkVerificationServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    let claims = require_admin_auth(&req, &validator).await?;
+    body.validate()
+        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+
+    let request = service
+        .approve_request(&path.into_inner(), &claims.sub, body.into_inner().comment)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(VerificationRequestResponse::from(request)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/network-verifications/{id}/reject",
+    tag = "Network verification",
+    params(
+        ("id" = String, Path, description = "Verification request ID")
+    ),
+    request_body = VerificationDecisionRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Request rejected and partner notified", body = VerificationRequestResponse),
+        (status = 400, description = "Validation error or missing comment"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only"),
+        (status = 404, description = "Request not found"),
+        (status = 409, description = "Request is not pending")
+    )
+)]
+#[post("/network-verifications/{id}/reject")]
+pub async fn reject_network_verification(
+    req: HttpRequest,
+    path: web::Path<String>,
+    body: web::Json<VerificationDecisionRequest>,
+    service: web::Data<Arc<NetworkVerificationServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    let claims = require_admin_auth(&req, &validator).await?;
+    body.validate()
+        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+
+    let request = service
+        .reject_request(
+            &path.into_inner(),
+            &claims.sub,
+            body.into_inner().comment.unwrap_or_default(),
+        )
+        .await?;
+
+    Ok(HttpResponse::Ok().json(VerificationRequestResponse::from(request)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/networks/{id}/verification/revoke",
+    tag = "Network verification",
+    params(
+        ("id" = String, Path, description = "Network ID")
+    ),
+    request_body = VerificationDecisionRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 204, description = "Network unverified and its tariffs deactivated"),
+        (status = 400, description = "Validation error or missing comment"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only"),
+        (status = 404, description = "Network not found"),
+        (status = 409, description = "Network is not verified")
+    )
+)]
+#[post("/networks/{id}/verification/revoke")]
+pub async fn revoke_network_verification(
+    req: HttpRequest,
+    path: web::Path<String>,
+    body: web::Json<VerificationDecisionRequest>,
+    service: web::Data<Arc<NetworkVerificationServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    let claims = require_admin_auth(&req, &validator).await?;
+    body.validate()
+        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+
+    service
+        .revoke_verification(
+            &path.into_inner(),
+            &claims.sub,
+            body.into_inner().comment.unwrap_or_default(),
+        )
+        .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/networks/{id}/verification/events",
+    tag = "Network verification",
+    params(
+        ("id" = String, Path, description = "Network ID")
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Verification state changes of the network, oldest first", body = Vec<VerificationEventResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - Admin only"),
+        (status = 404, description = "Network not found")
+    )
+)]
+#[get("/networks/{id}/verification/events")]
+pub async fn list_network_verification_events(
+    req: HttpRequest,
+    path: web::Path<String>,
+    service: web::Data<Arc<NetworkVerificationServiceImpl>>,
+    validator: web::Data<Arc<JwtValidator>>,
+) -> Result<HttpResponse, AppError> {
+    require_admin_auth(&req, &validator).await?;
+
+    let events = service.list_network_events(&path.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(
+        events
+            .into_iter()
+            .map(VerificationEventResponse::from)
+            .collect::<Vec<_>>(),
+    ))
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(list_network_verifications)
+        .service(get_network_verification)
+        .service(approve_network_verification)
+        .service(reject_network_verification)
+        .service(revoke_network_verification)
+        .service(list_network_verification_events);
+}
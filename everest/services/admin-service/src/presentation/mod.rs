@@ -8,7 +8,17 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
         web::scope("/api")
             .configure(controllers::health_controller::configure)
             .configure(controllers::network_controller::configure)
+            .configure(controllers::import_controller::configure)
+            .configure(controllers::export_controller::configure)
             .configure(controllers::station_controller::configure)
-            .configure(controllers::connector_controller::configure),
-    );
+            .configure(controllers::connector_controller::configure)
+            .configure(controllers::sync_controller::configure)
+            .configure(controllers::region_controller::configure)
+            .configure(controllers::duplicate_controller::configure)
+            .configure(controllers::deleted_controller::configure)
+            .configure(controllers::ocpi_party_controller::configure)
+            .configure(controllers::verification_controller::configure)
+            .configure(controllers::review_moderation_controller::configure),
+    )
+    .service(web::scope("/ocpi").configure(controllers::ocpi_controller::configure));
 }
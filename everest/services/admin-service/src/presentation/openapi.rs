@@ -1,6 +1,6 @@
 use utoipa::Modify;
 use utoipa::OpenApi;
-use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme};
 
 #[derive(OpenApi)]
 #[openapi(
@@ -11,16 +11,64 @@ use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
         crate::presentation::controllers::network_controller::create_network,
         crate::presentation::controllers::network_controller::update_network,
         crate::presentation::controllers::network_controller::delete_network,
+        crate::presentation::controllers::network_controller::restore_network,
         crate::presentation::controllers::station_controller::list_stations,
         crate::presentation::controllers::station_controller::get_station,
         crate::presentation::controllers::station_controller::create_station,
         crate::presentation::controllers::station_controller::update_station,
         crate::presentation::controllers::station_controller::delete_station,
+        crate::presentation::controllers::station_controller::restore_station,
+        crate::presentation::controllers::station_controller::get_station_provenance,
+        crate::presentation::controllers::station_controller::update_field_provenance,
+        crate::presentation::controllers::import_controller::import_stations,
+        crate::presentation::controllers::export_controller::export_stations,
         crate::presentation::controllers::connector_controller::list_connectors,
         crate::presentation::controllers::connector_controller::get_connector,
         crate::presentation::controllers::connector_controller::create_connector,
         crate::presentation::controllers::connector_controller::update_connector,
         crate::presentation::controllers::connector_controller::delete_connector,
+        crate::presentation::controllers::connector_controller::restore_connector,
+        crate::presentation::controllers::sync_controller::preview_osm_sync,
+        crate::presentation::controllers::sync_controller::start_osm_sync,
+        crate::presentation::controllers::sync_controller::list_sync_runs,
+        crate::presentation::controllers::sync_controller::get_sync_run,
+        crate::presentation::controllers::sync_controller::list_sync_run_conflicts,
+        crate::presentation::controllers::region_controller::create_region,
+        crate::presentation::controllers::region_controller::create_region_from_osm,
+        crate::presentation::controllers::region_controller::list_regions,
+        crate::presentation::controllers::region_controller::region_statistics,
+        crate::presentation::controllers::region_controller::get_region,
+        crate::presentation::controllers::region_controller::delete_region,
+        crate::presentation::controllers::duplicate_controller::list_duplicate_candidates,
+        crate::presentation::controllers::duplicate_controller::dismiss_duplicate,
+        crate::presentation::controllers::duplicate_controller::merge_stations,
+        crate::presentation::controllers::duplicate_controller::list_station_merges,
+        crate::presentation::controllers::deleted_controller::list_deleted_records,
+        crate::presentation::controllers::deleted_controller::purge_deleted_records,
+        crate::presentation::controllers::ocpi_party_controller::create_ocpi_party,
+        crate::presentation::controllers::ocpi_party_controller::list_ocpi_parties,
+        crate::presentation::controllers::ocpi_party_controller::unregister_ocpi_party,
+        crate::presentation::controllers::ocpi_party_controller::push_ocpi_locations,
+        crate::presentation::controllers::verification_controller::list_network_verifications,
+        crate::presentation::controllers::verification_controller::get_network_verification,
+        crate::presentation::controllers::verification_controller::approve_network_verification,
+        crate::presentation::controllers::verification_controller::reject_network_verification,
+        crate::presentation::controllers::verification_controller::revoke_network_verification,
+        crate::presentation::controllers::verification_controller::list_network_verification_events,
+        crate::presentation::controllers::review_moderation_controller::get_moderation_queue,
+        crate::presentation::controllers::review_moderation_controller::hide_review,
+        crate::presentation::controllers::review_moderation_controller::restore_review,
+        crate::presentation::controllers::ocpi_controller::get_versions,
+        crate::presentation::controllers::ocpi_controller::get_version_details,
+        crate::presentation::controllers::ocpi_controller::get_credentials,
+        crate::presentation::controllers::ocpi_controller::register_credentials,
+        crate::presentation::controllers::ocpi_controller::update_credentials,
+        crate::presentation::controllers::ocpi_controller::delete_credentials,
+        crate::presentation::controllers::ocpi_controller::list_locations,
+        crate::presentation::controllers::ocpi_controller::get_location,
+        crate::presentation::controllers::ocpi_controller::get_evse,
+        crate::presentation::controllers::ocpi_controller::get_connector,
+        crate::presentation::controllers::ocpi_controller::list_tariffs,
     ),
     components(schemas(
                 crate::application::dtos::health::HealthResponse,
@@ -30,9 +78,52 @@ use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
         crate::application::dtos::station::CreateStationRequest,
         crate::application::dtos::station::UpdateStationRequest,
         crate::application::dtos::station::StationResponse,
+        crate::application::dtos::station::FieldSourceResponse,
+        crate::application::dtos::station::UpdateFieldSourceRequest,
+        crate::application::dtos::import::ImportReportResponse,
+        crate::application::dtos::import::ImportRowResponse,
         crate::application::dtos::connector::CreateConnectorRequest,
         crate::application::dtos::connector::UpdateConnectorRequest,
         crate::application::dtos::connector::ConnectorResponse,
+        crate::application::dtos::sync::SyncRunResponse,
+        crate::application::dtos::sync::SyncRunListResponse,
+        crate::application::dtos::sync::StartSyncRequest,
+        crate::application::dtos::sync::SyncPreviewResponse,
+        crate::application::dtos::sync::NewStationPreviewResponse,
+        crate::application::dtos::sync::NewConnectorResponse,
+        crate::application::dtos::sync::StationChangePreviewResponse,
+        crate::application::dtos::sync::FieldChangeResponse,
+        crate::application::dtos::sync::TagChangeResponse,
+        crate::application::dtos::sync::ConnectorChangeResponse,
+        crate::application::dtos::sync::ConnectorSpecResponse,
+        crate::application::dtos::sync::RemovedStationPreviewResponse,
+        crate::application::dtos::sync::SyncConflictResponse,
+        crate::application::dtos::region::CreateRegionRequest,
+        crate::application::dtos::region::CreateOsmRegionRequest,
+        crate::application::dtos::region::RegionResponse,
+        crate::application::dtos::region::RegionStatisticsResponse,
+        crate::application::dtos::duplicate::DuplicateStationResponse,
+        crate::application::dtos::duplicate::DuplicateCandidateResponse,
+        crate::application::dtos::duplicate::DismissDuplicateRequest,
+        crate::application::dtos::duplicate::MergeStationsRequest,
+        crate::application::dtos::duplicate::StationMergeResponse,
+        crate::application::dtos::deleted::DeletedRecordResponse,
+        crate::application::dtos::deleted::DeletedRecordListResponse,
+        crate::application::dtos::deleted::PurgeResponse,
+        crate::application::dtos::ocpi::CreateOcpiPartyRequest,
+        crate::application::dtos::ocpi::OcpiPartyResponse,
+        crate::application::dtos::ocpi::OcpiPartyCreatedResponse,
+        crate::application::dtos::ocpi::OcpiPushResponse,
+        crate::application::dtos::verification::VerificationDecisionRequest,
+        crate::application::dtos::verification::VerificationRequestResponse,
+        crate::application::dtos::verification::VerificationRequestListResponse,
+        crate::application::dtos::verification::VerificationDocumentResponse,
+        crate::application::dtos::verification::VerificationEventResponse,
+        crate::application::dtos::verification::VerificationRequestDetailResponse,
+        crate::application::dtos::review::ModerationActionRequest,
+        crate::application::dtos::review::ReviewResponse,
+        crate::application::dtos::review::ModerationQueueItemResponse,
+        crate::application::dtos::review::ModerationQueueResponse,
     )),
     modifiers(&SecurityAddon),
     tags(
@@ -40,6 +131,15 @@ use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
         (name = "Networks", description = "Networks endpoints"),
         (name = "Stations", description = "Stations endpoints"),
         (name = "Connectors", description = "Connectors endpoints"),
+        (name = "Import", description = "Bulk station import and export"),
+        (name = "Sync", description = "OpenStreetMap sync runs"),
+        (name = "Regions", description = "Regions partitioning the stations"),
+        (name = "Duplicates", description = "Duplicate station review and merges"),
+        (name = "Deleted records", description = "Deleted stations, connectors and networks awaiting purge"),
+        (name = "OCPI parties", description = "eMSPs roaming with our network over OCPI"),
+        (name = "Network verification", description = "Admin review of partner network verification requests"),
+        (name = "Review moderation", description = "Flagged and held user reviews awaiting an admin decision"),
+        (name = "OCPI", description = "OCPI 2.2.1 CPO interface, authenticated with the party's token"),
     ),
     info(
         title = "Admin Service API",
@@ -65,5 +165,12 @@ impl Modify for SecurityAddon {
                     .build(),
             ),
         );
+        components.add_security_scheme(
+            "ocpi_token",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::with_description(
+                "Authorization",
+                "Token <base64 of the OCPI token>",
+            ))),
+        );
     }
 }
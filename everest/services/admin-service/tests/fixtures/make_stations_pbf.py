@@ -0,0 +1,173 @@
+#!/usr/bin/env python3
+"""Writes stations.osm.pbf, the extract read by tests/osm_pbf_fixture.rs.
+
+The protobuf messages are encoded by hand so that the fixture does not come
+from the reader under test. Coordinates are in units of 100 nanodegrees, the
+default granularity.
+
+  OSMHeader  replication sequence 4321
+  OSMData    zlib: node 1 (station), dense nodes 10-14 and 20 (14 a
+             station), ways 100 (closed square station), 101 (open
+             station), 102 (service road) and 103 (station whose only
+             node is missing)
+  OSMData    raw: node 30 (station)
+"""
+
+import struct
+import zlib
+from pathlib import Path
+
+
+def varint(value):
+    out = bytearray()
+    while True:
+        byte = value & 0x7F
+        value >>= 7
+        if value:
+            out.append(byte | 0x80)
+        else:
+            out.append(byte)
+            return bytes(out)
+
+
+def zigzag(value):
+    return (value << 1) ^ (value >> 63)
+
+
+def key(tag, wire_type):
+    return varint(tag << 3 | wire_type)
+
+
+def uint(tag, value):
+    return key(tag, 0) + varint(value & 0xFFFFFFFFFFFFFFFF)
+
+
+def sint(tag, value):
+    return key(tag, 0) + varint(zigzag(value))
+
+
+def length_delimited(tag, payload):
+    return key(tag, 2) + varint(len(payload)) + payload
+
+
+def packed_uint(tag, values):
+    return length_delimited(tag, b"".join(varint(v) for v in values))
+
+
+def packed_sint(tag, values):
+    return length_delimited(tag, b"".join(varint(zigzag(v)) for v in values))
+
+
+def deltas(values):
+    return [value - previous for previous, value in zip([0] + values, values)]
+
+
+STRINGS = [
+    "",
+    "amenity",
+    "charging_station",
+    "socket:type2",
+    "2",
+    "highway",
+    "service",
+    "name",
+    "Hub",
+]
+INDEX = {s: i for i, s in enumerate(STRINGS)}
+
+
+def tag_indexes(tags):
+    return [INDEX[k] for k, _ in tags], [INDEX[v] for _, v in tags]
+
+
+def node(node_id, lon, lat, tags):
+    keys, vals = tag_indexes(tags)
+    return (
+        sint(1, node_id)
+        + packed_uint(2, keys)
+        + packed_uint(3, vals)
+        + sint(8, lat)
+        + sint(9, lon)
+    )
+
+
+def dense(nodes):
+    keys_vals = []
+    for _, _, _, tags in nodes:
+        for k, v in tags:
+            keys_vals += [INDEX[k], INDEX[v]]
+        keys_vals.append(0)
+    return (
+        packed_sint(1, deltas([n[0] for n in nodes]))
+        + packed_sint(8, deltas([n[2] for n in nodes]))
+        + packed_sint(9, deltas([n[1] for n in nodes]))
+        + packed_uint(10, keys_vals)
+    )
+
+
+def way(way_id, refs, tags):
+    keys, vals = tag_indexes(tags)
+    return (
+        uint(1, way_id)
+        + packed_uint(2, keys)
+        + packed_uint(3, vals)
+        + packed_sint(8, deltas(refs))
+    )
+
+
+def block(groups):
+    table = b"".join(length_delimited(1, s.encode()) for s in STRINGS)
+    return length_delimited(1, table) + b"".join(
+        length_delimited(2, group) for group in groups
+    )
+
+
+def blob(kind, data, compress):
+    if compress:
+        body = uint(2, len(data)) + length_delimited(3, zlib.compress(data))
+    else:
+        body = length_delimited(1, data)
+    header = length_delimited(1, kind.encode()) + uint(3, len(body))
+    return struct.pack(">I", len(header)) + header + body
+
+
+STATION = [("amenity", "charging_station")]
+
+header = (
+    length_delimited(4, b"OsmSchema-V0.6")
+    + length_delimited(4, b"DenseNodes")
+    + uint(33, 4321)
+)
+
+compressed = block(
+    [
+        length_delimited(
+            1, node(1, 101_800_000, 368_000_000, STATION + [("socket:type2", "2")])
+        ),
+        length_delimited(
+            2,
+            dense(
+                [
+                    (10, 100_000_000, 368_000_000, []),
+                    (11, 100_010_000, 368_000_000, []),
+                    (12, 100_010_000, 368_010_000, []),
+                    (13, 100_000_000, 368_010_000, []),
+                    (14, 100_020_000, 368_020_000, STATION + [("name", "Hub")]),
+                    (20, 100_030_000, 368_030_000, []),
+                ]
+            ),
+        ),
+        length_delimited(3, way(100, [10, 11, 12, 13, 10], STATION))
+        + length_delimited(3, way(101, [10, 14], STATION))
+        + length_delimited(3, way(102, [10, 11], [("highway", "service")]))
+        + length_delimited(3, way(103, [999], STATION)),
+    ]
+)
+
+raw = block([length_delimited(1, node(30, 102_000_000, 369_000_000, STATION))])
+
+Path(__file__).with_name("stations.osm.pbf").write_bytes(
+    blob("OSMHeader", header, compress=False)
+    + blob("OSMData", compressed, compress=True)
+    + blob("OSMData", raw, compress=False)
+)
@@ -0,0 +1,442 @@
+//! Runs the OCPI credentials handshake and a location push between the CPO
+//! endpoints, backed by in-memory repositories, and the ocpi-mock-emsp binary.
+
+use actix_web::{App, HttpServer, web};
+use admin_service::application::ocpi_service::OcpiServiceImpl;
+use admin_service::core::errors::AppResult;
+use admin_service::domain::entities::{
+    OcpiConnectorRow, OcpiLocationRow, OcpiParty, OcpiTariffComponentRow, OcpiTariffRow,
+};
+use admin_service::domain::ocpi::{OcpiIdentity, OcpiPartyStatus};
+use admin_service::domain::repositories::{OcpiPartyRepository, OcpiRepository};
+use admin_service::domain::services::OcpiService;
+use admin_service::domain::value_objects::OcpiRegistrationData;
+use admin_service::infrastructure::ocpi_client::HttpOcpiPartyApi;
+use admin_service::presentation::controllers::ocpi_controller;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use std::net::TcpListener;
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, BufReader, Lines};
+use tokio::process::{ChildStdout, Command};
+
+const WAIT: Duration = Duration::from_secs(30);
+
+/// Locations are kept in (last_updated, station id) order
+struct MemoryOcpiRepository {
+    locations: Mutex<Vec<OcpiLocationRow>>,
+    tariffs: Vec<OcpiTariffRow>,
+}
+
+impl MemoryOcpiRepository {
+    fn touch(&self, station_id: &str, last_updated: DateTime<Utc>) {
+        let mut locations = self.locations.lock().unwrap();
+        let location = locations
+            .iter_mut()
+            .find(|l| l.station_id == station_id)
+            .unwrap();
+        location.last_updated = last_updated;
+        locations
+            .sort_by(|a, b| (a.last_updated, &a.station_id).cmp(&(b.last_updated, &b.station_id)));
+    }
+
+    fn window(
+        &self,
+        date_from: Option<DateTime<Utc>>,
+        date_to: Option<DateTime<Utc>>,
+    ) -> Vec<OcpiLocationRow> {
+        self.locations
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|l| match date_from {
+                Some(from) => l.last_updated >= from,
+                None => !l.removed,
+            })
+            .filter(|l| date_to.is_none_or(|to| l.last_updated < to))
+            .cloned()
+            .collect()
+    }
+}
+
+#[async_trait]
+impl OcpiRepository for MemoryOcpiRepository {
+    async fn find_locations(
+        &self,
+        date_from: Option<DateTime<Utc>>,
+        date_to: Option<DateTime<Utc>>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<OcpiLocationRow>> {
+        Ok(self
+            .window(date_from, date_to)
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect())
+    }
+
+    async fn count_locations(
+        &self,
+        date_from: Option<DateTime<Utc>>,
+        date_to: Option<DateTime<Utc>>,
+    ) -> AppResult<i64> {
+        Ok(self.window(date_from, date_to).len() as i64)
+    }
+
+    async fn find_location(&self, location_id: &str) -> AppResult<Option<OcpiLocationRow>> {
+        Ok(self
+            .locations
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|l| l.station_id == location_id)
+            .cloned())
+    }
+
+    async fn find_locations_after(
+        &self,
+        cursor: Option<(DateTime<Utc>, String)>,
+        settle_seconds: i64,
+        limit: i64,
+    ) -> AppResult<Vec<OcpiLocationRow>> {
+        let settled = Utc::now() - ChronoDuration::seconds(settle_seconds);
+        Ok(self
+            .locations
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|l| match &cursor {
+                Some((updated, id)) => (l.last_updated, &l.station_id) > (*updated, id),
+                None => !l.removed,
+            })
+            .filter(|l| l.last_updated < settled)
+            .take(limit as usize)
+            .cloned()
+            .collect())
+    }
+
+    async fn find_tariffs(
+        &self,
+        _date_from: Option<DateTime<Utc>>,
+        _date_to: Option<DateTime<Utc>>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<OcpiTariffRow>> {
+        Ok(self
+            .tariffs
+            .iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .cloned()
+            .collect())
+    }
+
+    async fn count_tariffs(
+        &self,
+        _date_from: Option<DateTime<Utc>>,
+        _date_to: Option<DateTime<Utc>>,
+    ) -> AppResult<i64> {
+        Ok(self.tariffs.len() as i64)
+    }
+}
+
+#[derive(Default)]
+struct MemoryOcpiPartyRepository {
+    parties: Mutex<Vec<OcpiParty>>,
+}
+
+impl MemoryOcpiPartyRepository {
+    fn update(
+        &self,
+        party_uid: &str,
+        from: &[OcpiPartyStatus],
+        change: impl FnOnce(&mut OcpiParty),
+    ) -> Option<OcpiParty> {
+        let mut parties = self.parties.lock().unwrap();
+        let party = parties
+            .iter_mut()
+            .find(|p| p.party_uid == party_uid && from.iter().any(|s| s.as_str() == p.status))?;
+        change(party);
+        Some(party.clone())
+    }
+}
+
+#[async_trait]
+impl OcpiPartyRepository for MemoryOcpiPartyRepository {
+    async fn create(&self, party: &OcpiParty) -> AppResult<OcpiParty> {
+        self.parties.lock().unwrap().push(party.clone());
+        Ok(party.clone())
+    }
+
+    async fn find_all(&self) -> AppResult<Vec<OcpiParty>> {
+        Ok(self.parties.lock().unwrap().clone())
+    }
+
+    async fn find_by_id(&self, party_uid: &str) -> AppResult<Option<OcpiParty>> {
+        Ok(self
+            .parties
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|p| p.party_uid == party_uid)
+            .cloned())
+    }
+
+    async fn find_by_token(&self, token: &str) -> AppResult<Option<OcpiParty>> {
+        Ok(self
+            .parties
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|p| match p.status.as_str() {
+                "pending" => p.token_a.as_deref() == Some(token),
+                "registered" => p.token_c.as_deref() == Some(token),
+                _ => false,
+            })
+            .cloned())
+    }
+
+    async fn find_registered(&self) -> AppResult<Vec<OcpiParty>> {
+        Ok(self
+            .parties
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|p| p.status == OcpiPartyStatus::Registered.as_str())
+            .cloned()
+            .collect())
+    }
+
+    async fn register(
+        &self,
+        party_uid: &str,
+        registration: &OcpiRegistrationData,
+    ) -> AppResult<Option<OcpiParty>> {
+        let registration = registration.clone();
+        Ok(self.update(
+            party_uid,
+            &[OcpiPartyStatus::Pending, OcpiPartyStatus::Registered],
+            |p| {
+                p.status = OcpiPartyStatus::Registered.as_str().to_string();
+                p.country_code = Some(registration.country_code);
+                p.party_id = Some(registration.party_id);
+                p.role = Some(registration.role);
+                p.token_a = None;
+                p.token_b = Some(registration.token_b);
+                p.token_c = Some(registration.token_c);
+                p.versions_url = Some(registration.versions_url);
+                p.locations_url = registration.locations_url;
+                p.registered_at = Some(Utc::now());
+            },
+        ))
+    }
+
+    async fn unregister(&self, party_uid: &str) -> AppResult<Option<OcpiParty>> {
+        Ok(self.update(
+            party_uid,
+            &[OcpiPartyStatus::Pending, OcpiPartyStatus::Registered],
+            |p| {
+                p.status = OcpiPartyStatus::Unregistered.as_str().to_string();
+                p.token_a = None;
+                p.token_c = None;
+            },
+        ))
+    }
+
+    async fn record_push(
+        &self,
+        party_uid: &str,
+        cursor: Option<(DateTime<Utc>, String)>,
+        error: Option<&str>,
+    ) -> AppResult<()> {
+        self.update(party_uid, &[OcpiPartyStatus::Registered], |p| {
+            (p.locations_pushed_until, p.locations_pushed_until_id) = cursor.unzip();
+            p.last_push_error = error.map(str::to_string);
+            p.last_push_at = Some(Utc::now());
+        });
+        Ok(())
+    }
+}
+
+fn location(station_id: &str, last_updated: DateTime<Utc>, removed: bool) -> OcpiLocationRow {
+    OcpiLocationRow {
+        station_id: station_id.to_string(),
+        name: format!("Station {}", station_id),
+        address: Some("Avenue Habib Bourguiba".to_string()),
+        city: Some("Tunis".to_string()),
+        postal_code: Some("1000".to_string()),
+        latitude: 36.8,
+        longitude: 10.18,
+        operator_name: "Everest Charging".to_string(),
+        tariff_id: Some("TAR-1".to_string()),
+        removed,
+        last_updated,
+        connectors: vec![OcpiConnectorRow {
+            station_id: station_id.to_string(),
+            connector_id: format!("CON-{}", station_id),
+            connector_type: "ccs".to_string(),
+            current_type: "DC".to_string(),
+            status: "available".to_string(),
+            power_kw: Some(50.0),
+            voltage: None,
+            amperage: None,
+            count_available: 2,
+            count_total: 2,
+            removed: false,
+            last_updated,
+        }],
+    }
+}
+
+fn free_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+/// Reads the mock's log until a line contains `needle`
+async fn wait_for(lines: &mut Lines<BufReader<ChildStdout>>, needle: &str) -> String {
+    tokio::time::timeout(WAIT, async {
+        while let Some(line) = lines.next_line().await.unwrap() {
+            if line.contains(needle) {
+                return line;
+            }
+        }
+        panic!("ocpi-mock-emsp exited before logging {:?}", needle);
+    })
+    .await
+    .unwrap_or_else(|_| panic!("ocpi-mock-emsp did not log {:?}", needle))
+}
+
+#[actix_web::test]
+async fn mock_emsp_registers_and_receives_location_pushes() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let identity = OcpiIdentity {
+        base_url: format!("http://{}", listener.local_addr().unwrap()),
+        country_code: "TN".to_string(),
+        party_id: "EVR".to_string(),
+        business_name: "Everest".to_string(),
+        location_country: "TUN".to_string(),
+        time_zone: "Africa/Tunis".to_string(),
+    };
+
+    let an_hour_ago = Utc::now() - ChronoDuration::hours(1);
+    let ocpi_repo = Arc::new(MemoryOcpiRepository {
+        locations: Mutex::new(vec![
+            location("STA-1", an_hour_ago, false),
+            location("STA-2", an_hour_ago + ChronoDuration::seconds(1), false),
+            location("STA-3", an_hour_ago + ChronoDuration::seconds(2), true),
+        ]),
+        tariffs: vec![OcpiTariffRow {
+            tariff_id: "TAR-1".to_string(),
+            currency: "TND".to_string(),
+            vat_percent: 19.0,
+            min_price: None,
+            max_price: None,
+            last_updated: an_hour_ago,
+            components: vec![OcpiTariffComponentRow {
+                tariff_id: "TAR-1".to_string(),
+                component_type: "ENERGY".to_string(),
+                price: 0.8,
+                step_size: 1,
+            }],
+        }],
+    });
+    let party_repo = Arc::new(MemoryOcpiPartyRepository::default());
+    let service = Arc::new(OcpiServiceImpl::new(
+        ocpi_repo.clone(),
+        party_repo.clone(),
+        Arc::new(HttpOcpiPartyApi::new(identity.clone())),
+        identity.clone(),
+    ));
+
+    let server_service = service.clone();
+    let server = HttpServer::new(move || {
+        App::new()
+            .app_data(web::Data::new(server_service.clone()))
+            .service(web::scope("/ocpi").configure(ocpi_controller::configure))
+    })
+    .listen(listener)
+    .unwrap()
+    .workers(1)
+    .run();
+    let server_handle = server.handle();
+    tokio::spawn(server);
+
+    let party = service.create_party("Mock eMSP", "admin").await.unwrap();
+    let mock_address = format!("127.0.0.1:{}", free_port());
+    let mut mock = Command::new(env!("CARGO_BIN_EXE_ocpi-mock-emsp"))
+        .arg(identity.versions_url())
+        .arg(party.token_a.as_deref().unwrap())
+        .arg(&mock_address)
+        .env("RUST_LOG", "info")
+        .stdout(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .unwrap();
+    let mut log = BufReader::new(mock.stdout.take().unwrap()).lines();
+
+    // Handshake: token A for the mock's token B, then pulls with token C
+    wait_for(&mut log, "Registered with Everest (TN*EVR)").await;
+    let pulled = wait_for(&mut log, "Pulled").await;
+    assert!(
+        pulled.contains("Pulled 2 locations and 1 tariffs"),
+        "{}",
+        pulled
+    );
+    wait_for(&mut log, "Waiting for location pushes").await;
+
+    let registered = party_repo
+        .find_by_id(&party.party_uid)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(registered.status, "registered");
+    assert_eq!(registered.country_code.as_deref(), Some("TN"));
+    assert_eq!(registered.party_id.as_deref(), Some("MSP"));
+    assert_eq!(
+        registered.locations_url,
+        Some(format!("http://{}/ocpi/2.2.1/locations", mock_address))
+    );
+    assert!(registered.token_a.is_none());
+
+    // The first push sends the published locations and skips the removed one
+    assert_eq!(service.push_locations().await.unwrap(), 2);
+    wait_for(
+        &mut log,
+        "TN*EVR location STA-1: Station STA-1 (publish true, 2 EVSEs",
+    )
+    .await;
+    wait_for(
+        &mut log,
+        "TN*EVR location STA-2: Station STA-2 (publish true, 2 EVSEs",
+    )
+    .await;
+    assert_eq!(service.push_locations().await.unwrap(), 0);
+
+    let pushed = party_repo
+        .find_by_id(&party.party_uid)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(pushed.locations_pushed_until_id.as_deref(), Some("STA-3"));
+    assert!(pushed.last_push_error.is_none());
+
+    // Updates are pushed once settled, removals as unpublished
+    ocpi_repo.touch("STA-2", Utc::now());
+    ocpi_repo.touch("STA-3", Utc::now() - ChronoDuration::minutes(10));
+    assert_eq!(service.push_locations().await.unwrap(), 1);
+    wait_for(
+        &mut log,
+        "TN*EVR location STA-3: Station STA-3 (publish false",
+    )
+    .await;
+
+    mock.kill().await.unwrap();
+    server_handle.stop(true).await;
+}
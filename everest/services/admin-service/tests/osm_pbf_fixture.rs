@@ -0,0 +1,102 @@
+//! Reads `tests/fixtures/stations.osm.pbf`, written by
+//! `tests/fixtures/make_stations_pbf.py`, which lists its elements.
+
+use admin_service::application::osm_import_service::read_charging_stations;
+use admin_service::domain::osm_import::WAY_OSM_ID_OFFSET;
+use admin_service::infrastructure::osm_pbf::{OsmElement, for_each_element};
+use std::path::{Path, PathBuf};
+
+fn fixture() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/stations.osm.pbf")
+}
+
+fn assert_near(actual: (f64, f64), expected: (f64, f64)) {
+    assert!(
+        (actual.0 - expected.0).abs() < 1e-9 && (actual.1 - expected.1).abs() < 1e-9,
+        "{:?} is not {:?}",
+        actual,
+        expected
+    );
+}
+
+#[test]
+fn reads_every_node_and_way_of_raw_and_zlib_blocks() {
+    let mut node_ids = Vec::new();
+    let mut ways = Vec::new();
+    let sequence_number = for_each_element(&fixture(), |element| match element {
+        OsmElement::Node { id, .. } => node_ids.push(id),
+        OsmElement::Way { id, node_ids, .. } => ways.push((id, node_ids)),
+    })
+    .unwrap();
+
+    assert_eq!(sequence_number, Some(4321));
+    assert_eq!(node_ids, vec![1, 10, 11, 12, 13, 14, 20, 30]);
+    assert_eq!(
+        ways,
+        vec![
+            (100, vec![10, 11, 12, 13, 10]),
+            (101, vec![10, 14]),
+            (102, vec![10, 11]),
+            (103, vec![999]),
+        ]
+    );
+}
+
+#[test]
+fn decodes_coordinates_and_tags_of_plain_and_dense_nodes() {
+    let mut nodes = Vec::new();
+    for_each_element(&fixture(), |element| {
+        if let OsmElement::Node {
+            id,
+            longitude,
+            latitude,
+            tags,
+        } = element
+            && !tags.is_empty()
+        {
+            let tags: Vec<(String, String)> = tags
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+            nodes.push((id, (longitude, latitude), tags));
+        }
+    })
+    .unwrap();
+
+    let ids: Vec<i64> = nodes.iter().map(|(id, _, _)| *id).collect();
+    assert_eq!(ids, vec![1, 14, 30]);
+    assert_near(nodes[0].1, (10.18, 36.8));
+    assert_near(nodes[1].1, (10.002, 36.802));
+    assert_near(nodes[2].1, (10.2, 36.9));
+    assert!(
+        nodes[1]
+            .2
+            .contains(&("name".to_string(), "Hub".to_string()))
+    );
+}
+
+#[test]
+fn stages_station_nodes_and_ways_at_their_centroids() {
+    let (stations, report) = read_charging_stations(&fixture()).unwrap();
+
+    assert_eq!(report.station_nodes, 3);
+    assert_eq!(report.station_ways, 3);
+    assert_eq!(report.skipped_ways, 1);
+    assert_eq!(report.staged, 5);
+    assert_eq!(report.sequence_number, Some(4321));
+
+    let location = |osm_id: i64| {
+        let station = stations
+            .iter()
+            .find(|s| s.osm_id == osm_id)
+            .unwrap_or_else(|| panic!("{} is not staged", osm_id));
+        (station.longitude, station.latitude)
+    };
+    assert_near(location(1), (10.18, 36.8));
+    // Area centroid of the closed square
+    assert_near(location(WAY_OSM_ID_OFFSET + 100), (10.0005, 36.8005));
+    // Mean of the nodes of the open way
+    assert_near(location(WAY_OSM_ID_OFFSET + 101), (10.001, 36.801));
+    assert!(stations.iter().all(|s| s.osm_id != WAY_OSM_ID_OFFSET + 102));
+    assert!(stations.iter().all(|s| s.osm_id != WAY_OSM_ID_OFFSET + 103));
+}
@@ -0,0 +1,114 @@
+use crate::core::constants::*;
+use crate::core::errors::{AppError, AppResult};
+use crate::core::utils::generate_id;
+use crate::domain::checkins::{AvailabilityPolicy, CheckinOutcome, StationAvailability};
+use crate::domain::entities::StationCheckin;
+use crate::domain::repositories::{CheckinRepository, StationRepository};
+use crate::domain::services::CheckinService;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use std::str::FromStr;
+use std::sync::Arc;
+
+pub struct CheckinServiceImpl {
+    checkin_repo: Arc<dyn CheckinRepository>,
+    station_repo: Arc<dyn StationRepository>,
+    policy: AvailabilityPolicy,
+}
+
+impl CheckinServiceImpl {
+    pub fn new(
+        checkin_repo: Arc<dyn CheckinRepository>,
+        station_repo: Arc<dyn StationRepository>,
+        policy: AvailabilityPolicy,
+    ) -> Self {
+        Self {
+            checkin_repo,
+            station_repo,
+            policy,
+        }
+    }
+}
+
+#[async_trait]
+impl CheckinService for CheckinServiceImpl {
+    async fn check_in(
+        &self,
+        user_id: String,
+        station_id: &str,
+        outcome: &str,
+        connector_id: Option<String>,
+        comment: Option<String>,
+    ) -> AppResult<StationCheckin> {
+        let outcome = CheckinOutcome::from_str(outcome)?;
+
+        if !self.station_repo.exists(station_id).await? {
+            return Err(AppError::NotFound(format!(
+                "Station with id {} not found",
+                station_id
+            )));
+        }
+        if let Some(ref connector_id) = connector_id {
+            let connector = self.station_repo.find_connector(connector_id).await?;
+            if connector.is_none_or(|c| c.station_id != station_id) {
+                return Err(AppError::ValidationError(format!(
+                    "Connector {} does not belong to station {}",
+                    connector_id, station_id
+                )));
+            }
+        }
+
+        if let Some(last) = self
+            .checkin_repo
+            .find_latest_by_user(&user_id, station_id)
+            .await?
+            && Utc::now() - last.created_at < Duration::minutes(CHECKIN_COOLDOWN_MINUTES)
+        {
+            return Err(AppError::Conflict(format!(
+                "You already checked in at this station less than {} minutes ago",
+                CHECKIN_COOLDOWN_MINUTES
+            )));
+        }
+
+        self.checkin_repo
+            .create(&StationCheckin {
+                checkin_id: generate_id(CHECKIN_ID_PREFIX),
+                station_id: station_id.to_string(),
+                connector_id,
+                user_id,
+                outcome: outcome.as_str().to_string(),
+                comment: comment.filter(|c| !c.trim().is_empty()),
+                created_at: Utc::now(),
+            })
+            .await
+    }
+
+    async fn get_station_availability(&self, station_id: &str) -> AppResult<StationAvailability> {
+        if !self.station_repo.exists(station_id).await? {
+            return Err(AppError::NotFound(format!(
+                "Station with id {} not found",
+                station_id
+            )));
+        }
+
+        let now = Utc::now();
+        let station_ids = [station_id.to_string()];
+        let recent = self
+            .checkin_repo
+            .find_recent(&station_ids, now - Duration::hours(CHECKIN_LOOKBACK_HOURS))
+            .await?;
+        let last_successful = self
+            .checkin_repo
+            .find_last_successful(&station_ids)
+            .await?
+            .into_iter()
+            .next()
+            .map(|c| c.created_at);
+
+        Ok(StationAvailability {
+            station_id: station_id.to_string(),
+            estimate: self.policy.estimate(&recent, last_successful, now),
+            recent_checkins: recent.into_iter().take(RECENT_CHECKINS_LIMIT).collect(),
+        })
+    }
+}
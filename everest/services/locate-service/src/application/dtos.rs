@@ -1,4 +1,13 @@
-use crate::domain::entities::{Station, UserReview};
+use crate::domain::charging::ChargeEstimate;
+use crate::domain::checkins::StationAvailability;
+use crate::domain::entities::{
+    Amenity, ChargeCurvePoint, Connector, IssueReport, Media, NetworkVerificationDetail,
+    NetworkVerificationRequest, Notification, ReviewFlag, ReviewReply, ReviewSummary, Station,
+    StationCheckin, StationDetail, StationReview, StationReviewPage, UserReview, Vehicle,
+    VehicleModel, VerificationDocument, VerificationEvent,
+};
+use crate::domain::pricing::{CostBreakdown, CostLine};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use validator::Validate;
@@ -9,6 +18,49 @@ pub struct NearbyStationsQuery {
     pub longitude: f64,
     pub radius_meters: Option<i32>,
     pub limit: Option<i32>,
+    pub vehicle_id: Option<String>,
+    /// relevance (default), distance, power or rating
+    pub sort: Option<String>,
+    /// Comma-separated amenity categories that must all be within walking distance
+    pub amenities: Option<String>,
+    /// Only stations of this region, e.g. tunisia
+    pub region: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct StationAmenitiesQuery {
+    /// Comma-separated categories: cafe, restaurant, toilets, supermarket, hotel
+    pub categories: Option<String>,
+    pub radius_meters: Option<i32>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AmenityResponse {
+    pub osm_id: i64,
+    pub osm_type: String,
+    pub category: String,
+    pub kind: String,
+    pub name: Option<String>,
+    pub opening_hours: Option<String>,
+    pub distance_meters: f64,
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl From<Amenity> for AmenityResponse {
+    fn from(amenity: Amenity) -> Self {
+        Self {
+            osm_id: amenity.osm_id,
+            osm_type: amenity.osm_type,
+            category: amenity.category,
+            kind: amenity.kind,
+            name: amenity.name,
+            opening_hours: amenity.opening_hours,
+            distance_meters: amenity.distance_meters.round(),
+            latitude: amenity.latitude,
+            longitude: amenity.longitude,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -22,8 +74,25 @@ pub struct StationResponse {
     pub max_power_kw: Option<f64>,
     pub power_tier: Option<String>,
     pub operator: Option<String>,
+    /// The operating network passed verification
+    pub network_verified: bool,
     pub latitude: Option<f64>,
     pub longitude: Option<f64>,
+    pub effective_power_kw: Option<f64>,
+    pub average_rating: Option<f64>,
+    pub review_count: i64,
+    /// Average rating smoothed towards the mean of all stations
+    pub bayesian_rating: Option<f64>,
+    /// Energy price per kWh of the network's active tariff, VAT included
+    pub energy_price_per_kwh: Option<f64>,
+    /// Relevance score between 0 and 1
+    pub score: Option<f64>,
+    /// Share of recent driver check-ins that could charge, freshest counting most
+    pub reported_availability: Option<f64>,
+    /// How much recent check-ins can be trusted, between 0 and 1
+    pub availability_confidence: Option<f64>,
+    pub last_successful_charge_at: Option<String>,
+    pub hours_since_last_successful_charge: Option<f64>,
 }
 
 impl From<Station> for StationResponse {
@@ -38,8 +107,197 @@ impl From<Station> for StationResponse {
             max_power_kw: station.max_power_kw,
             power_tier: station.power_tier,
             operator: station.operator,
+            network_verified: station.network_verified,
             latitude: station.latitude,
             longitude: station.longitude,
+            effective_power_kw: station.effective_power_kw,
+            average_rating: station
+                .average_rating
+                .map(|avg| (avg * 100.0).round() / 100.0),
+            review_count: station.review_count,
+            bayesian_rating: station.bayesian_rating,
+            energy_price_per_kwh: station.energy_price_per_kwh,
+            score: station.score,
+            reported_availability: station.reported_availability,
+            availability_confidence: station.availability_confidence,
+            last_successful_charge_at: station.last_successful_charge_at.map(|t| t.to_rfc3339()),
+            hours_since_last_successful_charge: station.last_successful_charge_at.map(hours_since),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ConnectorResponse {
+    pub connector_id: String,
+    pub connector_type: String,
+    pub current_type: String,
+    pub power_kw: Option<f64>,
+    pub voltage: Option<i32>,
+    pub amperage: Option<i32>,
+    pub count_available: Option<i32>,
+    pub count_total: Option<i32>,
+}
+
+impl From<Connector> for ConnectorResponse {
+    fn from(connector: Connector) -> Self {
+        Self {
+            connector_id: connector.connector_id,
+            connector_type: connector.connector_type,
+            current_type: connector.current_type,
+            power_kw: connector.power_kw,
+            voltage: connector.voltage,
+            amperage: connector.amperage,
+            count_available: connector.count_available,
+            count_total: connector.count_total,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StationDetailResponse {
+    #[serde(flatten)]
+    pub station: StationResponse,
+    pub connectors: Vec<ConnectorResponse>,
+    /// Photos of the station and of its visible reviews, newest first
+    pub photos: Vec<MediaResponse>,
+    /// Charging time per compatible connector, fastest first. Only present
+    /// when a `vehicle_id` is given.
+    pub charging_estimates: Option<Vec<ChargingEstimateResponse>>,
+}
+
+impl StationDetailResponse {
+    pub fn new(
+        detail: StationDetail,
+        photos: Vec<Media>,
+        charging_estimates: Option<Vec<ChargeEstimate>>,
+    ) -> Self {
+        Self {
+            station: StationResponse::from(detail.station),
+            connectors: detail
+                .connectors
+                .into_iter()
+                .map(ConnectorResponse::from)
+                .collect(),
+            photos: photos.into_iter().map(MediaResponse::from).collect(),
+            charging_estimates: charging_estimates.map(|estimates| {
+                estimates
+                    .into_iter()
+                    .map(ChargingEstimateResponse::from)
+                    .collect()
+            }),
+        }
+    }
+}
+
+/// Multipart form with a single `file` part (JPEG, PNG or WebP)
+#[derive(Debug, ToSchema)]
+#[allow(dead_code)]
+pub struct PhotoUploadForm {
+    #[schema(value_type = String, format = Binary)]
+    pub file: Vec<u8>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MediaResponse {
+    pub media_id: String,
+    pub station_id: String,
+    pub review_id: Option<String>,
+    pub uploaded_by: String,
+    pub content_type: String,
+    pub width: i32,
+    pub height: i32,
+    pub size_bytes: i64,
+    pub url: String,
+    pub thumbnail_url: String,
+    pub created_at: String,
+}
+
+impl From<Media> for MediaResponse {
+    fn from(media: Media) -> Self {
+        Self {
+            media_id: media.media_id,
+            station_id: media.station_id,
+            review_id: media.review_id,
+            uploaded_by: media.uploaded_by,
+            content_type: media.content_type,
+            width: media.width,
+            height: media.height,
+            size_bytes: media.size_bytes,
+            url: media.url,
+            thumbnail_url: media.thumbnail_url,
+            created_at: media.created_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Rounded to a tenth of an hour, for "last successful charge N hours ago"
+fn hours_since(time: DateTime<Utc>) -> f64 {
+    let hours = (Utc::now() - time).num_minutes().max(0) as f64 / 60.0;
+    (hours * 10.0).round() / 10.0
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CheckinRequest {
+    /// charged_ok, all_occupied or not_working
+    pub outcome: String,
+    pub connector_id: Option<String>,
+    #[validate(length(max = 500))]
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CheckinResponse {
+    pub checkin_id: String,
+    pub station_id: String,
+    pub connector_id: Option<String>,
+    pub user_id: String,
+    pub outcome: String,
+    pub comment: Option<String>,
+    pub created_at: String,
+}
+
+impl From<StationCheckin> for CheckinResponse {
+    fn from(checkin: StationCheckin) -> Self {
+        Self {
+            checkin_id: checkin.checkin_id,
+            station_id: checkin.station_id,
+            connector_id: checkin.connector_id,
+            user_id: checkin.user_id,
+            outcome: checkin.outcome,
+            comment: checkin.comment,
+            created_at: checkin.created_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StationAvailabilityResponse {
+    pub station_id: String,
+    /// Share of recent check-ins that could charge, freshest counting most
+    pub reported_availability: Option<f64>,
+    /// Between 0 (no recent check-ins) and 1
+    pub confidence: f64,
+    pub checkins_considered: usize,
+    pub last_successful_charge_at: Option<String>,
+    pub hours_since_last_successful_charge: Option<f64>,
+    pub recent_checkins: Vec<CheckinResponse>,
+}
+
+impl From<StationAvailability> for StationAvailabilityResponse {
+    fn from(availability: StationAvailability) -> Self {
+        let estimate = availability.estimate;
+        Self {
+            station_id: availability.station_id,
+            reported_availability: estimate.probability,
+            confidence: estimate.confidence,
+            checkins_considered: estimate.checkins_considered,
+            last_successful_charge_at: estimate.last_successful_charge_at.map(|t| t.to_rfc3339()),
+            hours_since_last_successful_charge: estimate.last_successful_charge_at.map(hours_since),
+            recent_checkins: availability
+                .recent_checkins
+                .into_iter()
+                .map(CheckinResponse::from)
+                .collect(),
         }
     }
 }
@@ -65,8 +323,13 @@ pub struct ReviewResponse {
     pub station_id: String,
     pub rating: i32,
     pub review_text: Option<String>,
+    /// visible, pending or hidden
+    pub moderation_status: String,
     pub created_at: String,
     pub updated_at: String,
+    pub helpful_votes: i64,
+    /// Public answer from the station's network
+    pub reply: Option<ReviewReplyResponse>,
 }
 
 impl From<UserReview> for ReviewResponse {
@@ -77,8 +340,598 @@ impl From<UserReview> for ReviewResponse {
             station_id: review.station_id,
             rating: review.rating,
             review_text: review.review_text,
+            moderation_status: review.moderation_status,
             created_at: review.created_at.to_rfc3339(),
             updated_at: review.updated_at.to_rfc3339(),
+            helpful_votes: review.helpful_votes,
+            reply: None,
+        }
+    }
+}
+
+impl From<StationReview> for ReviewResponse {
+    fn from(station_review: StationReview) -> Self {
+        Self {
+            reply: station_review.reply.map(ReviewReplyResponse::from),
+            ..Self::from(station_review.review)
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct StationReviewsQuery {
+    /// newest (default), highest, lowest or most_helpful
+    pub sort: Option<String>,
+    /// Only reviews with this many stars
+    pub rating: Option<i32>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReviewSummaryResponse {
+    pub average_rating: Option<f64>,
+    pub total_reviews: i64,
+    /// Number of 1 to 5 star reviews, in that order
+    pub histogram: Vec<i64>,
+}
+
+impl From<ReviewSummary> for ReviewSummaryResponse {
+    fn from(summary: ReviewSummary) -> Self {
+        Self {
+            average_rating: summary
+                .average_rating
+                .map(|avg| (avg * 100.0).round() / 100.0),
+            total_reviews: summary.total_reviews,
+            histogram: summary.histogram,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StationReviewsResponse {
+    /// Covers all visible reviews, regardless of the rating filter
+    pub summary: ReviewSummaryResponse,
+    pub reviews: Vec<ReviewResponse>,
+    /// Reviews matching the filter, across all pages
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+impl From<StationReviewPage> for StationReviewsResponse {
+    fn from(page: StationReviewPage) -> Self {
+        Self {
+            summary: ReviewSummaryResponse::from(page.summary),
+            reviews: page.reviews.into_iter().map(ReviewResponse::from).collect(),
+            total: page.total,
+            limit: page.limit,
+            offset: page.offset,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HelpfulVoteResponse {
+    pub review_id: String,
+    pub helpful_votes: i64,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ReviewReplyRequest {
+    #[validate(length(min = 1, max = 2000))]
+    pub reply_text: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReviewReplyResponse {
+    pub reply_id: String,
+    pub review_id: String,
+    pub network_id: String,
+    pub reply_text: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<ReviewReply> for ReviewReplyResponse {
+    fn from(reply: ReviewReply) -> Self {
+        Self {
+            reply_id: reply.reply_id,
+            review_id: reply.review_id,
+            network_id: reply.network_id,
+            reply_text: reply.reply_text,
+            created_at: reply.created_at.to_rfc3339(),
+            updated_at: reply.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct NotificationsQuery {
+    pub unread_only: Option<bool>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NotificationResponse {
+    pub notification_id: String,
+    pub kind: String,
+    pub message: String,
+    pub review_id: Option<String>,
+    pub created_at: String,
+    pub read_at: Option<String>,
+}
+
+impl From<Notification> for NotificationResponse {
+    fn from(notification: Notification) -> Self {
+        Self {
+            notification_id: notification.notification_id,
+            kind: notification.kind,
+            message: notification.message,
+            review_id: notification.review_id,
+            created_at: notification.created_at.to_rfc3339(),
+            read_at: notification.read_at.map(|t| t.to_rfc3339()),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct FlagReviewRequest {
+    /// spam, offensive, off_topic, false_information or other
+    pub reason: String,
+    #[validate(length(max = 1000))]
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReviewFlagResponse {
+    pub flag_id: String,
+    pub review_id: String,
+    pub reason: String,
+    pub comment: Option<String>,
+    pub created_at: String,
+}
+
+impl From<ReviewFlag> for ReviewFlagResponse {
+    fn from(flag: ReviewFlag) -> Self {
+        Self {
+            flag_id: flag.flag_id,
+            review_id: flag.review_id,
+            reason: flag.reason,
+            comment: flag.comment,
+            created_at: flag.created_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ReportIssueRequest {
+    pub connector_id: String,
+    /// wont_start, damaged_cable, blocked_by_ice, payment_failed, slow_charging or other
+    pub category: String,
+    #[validate(length(max = 2000))]
+    pub description: Option<String>,
+    /// Photo of the station uploaded by the reporter with
+    /// POST /api/stations/{station_id}/photos
+    pub photo_media_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct IssuesQuery {
+    /// Admins only; operators always see their own network
+    pub network_id: Option<String>,
+    /// open, acknowledged or resolved
+    pub status: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateIssueStatusRequest {
+    /// acknowledged or resolved
+    pub status: String,
+    #[validate(length(max = 2000))]
+    pub resolution_note: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct IssueResponse {
+    pub issue_id: String,
+    pub station_id: String,
+    pub connector_id: String,
+    pub network_id: Option<String>,
+    pub reporter_id: String,
+    pub category: String,
+    pub description: Option<String>,
+    /// Served by GET /api/media/{media_id}
+    pub photo_media_id: Option<String>,
+    /// open, acknowledged or resolved
+    pub status: String,
+    pub resolution_note: Option<String>,
+    pub acknowledged_at: Option<String>,
+    pub resolved_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<IssueReport> for IssueResponse {
+    fn from(issue: IssueReport) -> Self {
+        Self {
+            issue_id: issue.issue_id,
+            station_id: issue.station_id,
+            connector_id: issue.connector_id,
+            network_id: issue.network_id,
+            reporter_id: issue.reporter_id,
+            category: issue.category,
+            description: issue.description,
+            photo_media_id: issue.photo_media_id,
+            status: issue.status,
+            resolution_note: issue.resolution_note,
+            acknowledged_at: issue.acknowledged_at.map(|t| t.to_rfc3339()),
+            resolved_at: issue.resolved_at.map(|t| t.to_rfc3339()),
+            created_at: issue.created_at.to_rfc3339(),
+            updated_at: issue.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct SubmitVerificationRequest {
+    /// Legal name as registered
+    #[validate(length(min = 1, max = 255))]
+    pub company_name: String,
+    /// Trade register number
+    #[validate(length(min = 1, max = 100))]
+    pub registration_number: String,
+    #[validate(length(max = 100))]
+    pub vat_number: Option<String>,
+    #[validate(length(min = 1, max = 2000))]
+    pub registered_address: String,
+    #[validate(length(min = 1, max = 255))]
+    pub contact_name: String,
+    #[validate(email, length(max = 255))]
+    pub contact_email: String,
+    #[validate(length(max = 50))]
+    pub contact_phone: Option<String>,
+}
+
+/// Partners always see their own network; admins list every network in
+/// admin-service
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct VerificationRequestsQuery {
+    /// pending, approved, rejected or revoked
+    pub status: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// Multipart form with a single `file` part (PDF, JPEG or PNG)
+#[derive(Debug, ToSchema)]
+#[allow(dead_code)]
+pub struct DocumentUploadForm {
+    #[schema(value_type = String, format = Binary)]
+    pub file: Vec<u8>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct VerificationRequestResponse {
+    pub request_id: String,
+    pub network_id: String,
+    /// pending, approved, rejected or revoked
+    pub status: String,
+    pub company_name: String,
+    pub registration_number: String,
+    pub vat_number: Option<String>,
+    pub registered_address: String,
+    pub contact_name: String,
+    pub contact_email: String,
+    pub contact_phone: Option<String>,
+    pub submitted_by: String,
+    pub submitted_at: String,
+    pub reviewed_by: Option<String>,
+    pub reviewed_at: Option<String>,
+    pub review_comment: Option<String>,
+}
+
+impl From<NetworkVerificationRequest> for VerificationRequestResponse {
+    fn from(request: NetworkVerificationRequest) -> Self {
+        Self {
+            request_id: request.request_id,
+            network_id: request.network_id,
+            status: request.status,
+            company_name: request.company_name,
+            registration_number: request.registration_number,
+            vat_number: request.vat_number,
+            registered_address: request.registered_address,
+            contact_name: request.contact_name,
+            contact_email: request.contact_email,
+            contact_phone: request.contact_phone,
+            submitted_by: request.submitted_by,
+            submitted_at: request.submitted_at.to_rfc3339(),
+            reviewed_by: request.reviewed_by,
+            reviewed_at: request.reviewed_at.map(|t| t.to_rfc3339()),
+            review_comment: request.review_comment,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct VerificationDocumentResponse {
+    pub document_id: String,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    /// Requires the same authorization as the request, or an admin token
+    pub url: String,
+    pub uploaded_by: String,
+    pub uploaded_at: String,
+}
+
+impl From<VerificationDocument> for VerificationDocumentResponse {
+    fn from(document: VerificationDocument) -> Self {
+        Self {
+            url: format!(
+                "/api/network-verifications/{}/documents/{}",
+                document.request_id, document.document_id
+            ),
+            document_id: document.document_id,
+            file_name: document.file_name,
+            content_type: document.content_type,
+            size_bytes: document.size_bytes,
+            uploaded_by: document.uploaded_by,
+            uploaded_at: document.uploaded_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct VerificationEventResponse {
+    pub network_id: String,
+    pub request_id: Option<String>,
+    pub from_status: Option<String>,
+    pub to_status: String,
+    pub actor_id: String,
+    pub comment: Option<String>,
+    pub created_at: String,
+}
+
+impl From<VerificationEvent> for VerificationEventResponse {
+    fn from(event: VerificationEvent) -> Self {
+        Self {
+            network_id: event.network_id,
+            request_id: event.request_id,
+            from_status: event.from_status,
+            to_status: event.to_status,
+            actor_id: event.actor_id,
+            comment: event.comment,
+            created_at: event.created_at.to_rfc3339(),
         }
     }
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct VerificationRequestDetailResponse {
+    #[serde(flatten)]
+    pub request: VerificationRequestResponse,
+    pub documents: Vec<VerificationDocumentResponse>,
+    /// Oldest first
+    pub events: Vec<VerificationEventResponse>,
+}
+
+impl From<NetworkVerificationDetail> for VerificationRequestDetailResponse {
+    fn from(detail: NetworkVerificationDetail) -> Self {
+        Self {
+            request: VerificationRequestResponse::from(detail.request),
+            documents: detail
+                .documents
+                .into_iter()
+                .map(VerificationDocumentResponse::from)
+                .collect(),
+            events: detail
+                .events
+                .into_iter()
+                .map(VerificationEventResponse::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CostEstimateQuery {
+    pub energy_kwh: f64,
+    pub charging_minutes: f64,
+    pub idle_minutes: Option<f64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CostLineResponse {
+    pub component_type: String,
+    pub quantity: f64,
+    pub unit: String,
+    pub unit_price: f64,
+    pub amount: f64,
+}
+
+impl From<CostLine> for CostLineResponse {
+    fn from(line: CostLine) -> Self {
+        Self {
+            component_type: line.component_type.as_str().to_string(),
+            quantity: line.quantity,
+            unit: line.unit,
+            unit_price: line.unit_price,
+            amount: line.amount,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CostEstimateResponse {
+    pub tariff_id: String,
+    pub currency: String,
+    pub lines: Vec<CostLineResponse>,
+    pub subtotal_excl_vat: f64,
+    pub cap_adjustment: f64,
+    pub total_excl_vat: f64,
+    pub vat_percent: f64,
+    pub vat_amount: f64,
+    pub total_incl_vat: f64,
+}
+
+impl From<CostBreakdown> for CostEstimateResponse {
+    fn from(cost: CostBreakdown) -> Self {
+        Self {
+            tariff_id: cost.tariff_id,
+            currency: cost.currency,
+            lines: cost.lines.into_iter().map(CostLineResponse::from).collect(),
+            subtotal_excl_vat: cost.subtotal_excl_vat,
+            cap_adjustment: cost.cap_adjustment,
+            total_excl_vat: cost.total_excl_vat,
+            vat_percent: cost.vat_percent,
+            vat_amount: cost.vat_amount,
+            total_incl_vat: cost.total_incl_vat,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct VehicleModelResponse {
+    pub id: i32,
+    pub make: String,
+    pub model: String,
+    pub battery_kwh: f64,
+    pub connector_types: Vec<String>,
+    pub max_ac_kw: Option<f64>,
+    pub max_dc_kw: Option<f64>,
+    pub ac_phases: i16,
+    pub charge_curve: Option<Vec<ChargeCurvePoint>>,
+}
+
+impl From<VehicleModel> for VehicleModelResponse {
+    fn from(model: VehicleModel) -> Self {
+        Self {
+            id: model.id,
+            make: model.make,
+            model: model.model,
+            battery_kwh: model.battery_kwh,
+            connector_types: model.connector_types,
+            max_ac_kw: model.max_ac_kw,
+            max_dc_kw: model.max_dc_kw,
+            ac_phases: model.ac_phases,
+            charge_curve: model.charge_curve.map(|c| c.0),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateVehicleRequest {
+    pub model_id: Option<i32>,
+    #[validate(length(max = 100))]
+    pub nickname: Option<String>,
+    #[validate(length(min = 1, max = 100))]
+    pub make: Option<String>,
+    #[validate(length(min = 1, max = 100))]
+    pub model: Option<String>,
+    pub battery_kwh: Option<f64>,
+    pub connector_types: Option<Vec<String>>,
+    pub max_ac_kw: Option<f64>,
+    pub max_dc_kw: Option<f64>,
+    /// Phases the on-board charger can use on AC (1 or 3)
+    pub ac_phases: Option<i16>,
+    /// DC charging curve, sorted by soc_percent
+    pub charge_curve: Option<Vec<ChargeCurvePoint>>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateVehicleRequest {
+    pub nickname: Option<String>,
+    pub battery_kwh: Option<f64>,
+    pub connector_types: Option<Vec<String>>,
+    pub max_ac_kw: Option<f64>,
+    pub max_dc_kw: Option<f64>,
+    pub ac_phases: Option<i16>,
+    pub charge_curve: Option<Vec<ChargeCurvePoint>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct VehicleResponse {
+    pub vehicle_id: String,
+    pub model_id: Option<i32>,
+    pub nickname: Option<String>,
+    pub make: String,
+    pub model: String,
+    pub battery_kwh: f64,
+    pub connector_types: Vec<String>,
+    pub max_ac_kw: Option<f64>,
+    pub max_dc_kw: Option<f64>,
+    pub ac_phases: i16,
+    pub charge_curve: Option<Vec<ChargeCurvePoint>>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<Vehicle> for VehicleResponse {
+    fn from(vehicle: Vehicle) -> Self {
+        Self {
+            vehicle_id: vehicle.vehicle_id,
+            model_id: vehicle.model_id,
+            nickname: vehicle.nickname,
+            make: vehicle.make,
+            model: vehicle.model,
+            battery_kwh: vehicle.battery_kwh,
+            connector_types: vehicle.connector_types,
+            max_ac_kw: vehicle.max_ac_kw,
+            max_dc_kw: vehicle.max_dc_kw,
+            ac_phases: vehicle.ac_phases,
+            charge_curve: vehicle.charge_curve.map(|c| c.0),
+            created_at: vehicle.created_at.to_rfc3339(),
+            updated_at: vehicle.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct StationDetailQuery {
+    /// Adds charging estimates for this vehicle of the authenticated user
+    pub vehicle_id: Option<String>,
+    /// Defaults to 10
+    pub from_soc: Option<f64>,
+    /// Defaults to 80
+    pub to_soc: Option<f64>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ChargingEstimateQuery {
+    pub vehicle_id: String,
+    /// Defaults to 10
+    pub from_soc: Option<f64>,
+    /// Defaults to 80
+    pub to_soc: Option<f64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ChargingEstimateResponse {
+    pub connector_id: String,
+    pub connector_type: String,
+    pub current_type: String,
+    pub from_soc_percent: f64,
+    pub to_soc_percent: f64,
+    pub energy_kwh: f64,
+    pub minutes: f64,
+    pub average_power_kw: f64,
+    pub max_power_kw: f64,
+}
+
+impl From<ChargeEstimate> for ChargingEstimateResponse {
+    fn from(estimate: ChargeEstimate) -> Self {
+        Self {
+            connector_id: estimate.connector_id,
+            connector_type: estimate.connector_type,
+            current_type: estimate.current_type,
+            from_soc_percent: estimate.from_soc_percent,
+            to_soc_percent: estimate.to_soc_percent,
+            energy_kwh: estimate.energy_kwh,
+            minutes: estimate.minutes,
+            average_power_kw: estimate.average_power_kw,
+            max_power_kw: estimate.max_power_kw,
+        }
+    }
+}
@@ -0,0 +1,352 @@
+use crate::core::constants::*;
+use crate::core::errors::{AppError, AppResult};
+use crate::core::utils::generate_id;
+use crate::domain::entities::IssueReport;
+use crate::domain::issues::{IssueCategory, IssueStatus};
+use crate::domain::repositories::{IssueRepository, MediaRepository, StationRepository};
+use crate::domain::services::IssueService;
+use async_trait::async_trait;
+use chrono::Utc;
+use std::str::FromStr;
+use std::sync::Arc;
+
+pub struct IssueServiceImpl {
+    issue_repo: Arc<dyn IssueRepository>,
+    station_repo: Arc<dyn StationRepository>,
+    media_repo: Arc<dyn MediaRepository>,
+}
+
+impl IssueServiceImpl {
+    pub fn new(
+        issue_repo: Arc<dyn IssueRepository>,
+        station_repo: Arc<dyn StationRepository>,
+        media_repo: Arc<dyn MediaRepository>,
+    ) -> Self {
+        Self {
+            issue_repo,
+            station_repo,
+            media_repo,
+        }
+    }
+
+    /// Checks that the photo is one the reporter uploaded for the station
+    async fn check_issue_photo(
+        &self,
+        media_id: &str,
+        station_id: &str,
+        reporter_id: &str,
+    ) -> AppResult<()> {
+        let media = self
+            .media_repo
+            .find_by_id(media_id)
+            .await?
+            .ok_or(AppError::NotFound(format!(
+                "Media with id {} not found",
+                media_id
+            )))?;
+
+        if media.station_id != station_id || media.uploaded_by != reporter_id {
+            return Err(AppError::ValidationError(
+                "The photo must be one you uploaded for the connector's station".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// The ticket, provided it is assigned to `network_id` or the caller is an admin
+    async fn find_network_issue(
+        &self,
+        issue_id: &str,
+        network_id: Option<&str>,
+        is_admin: bool,
+    ) -> AppResult<IssueReport> {
+        let issue = self
+            .issue_repo
+            .find_by_id(issue_id)
+            .await?
+            .ok_or(AppError::NotFound(format!(
+                "Issue with id {} not found",
+                issue_id
+            )))?;
+
+        if !is_admin && (network_id.is_none() || network_id != issue.network_id.as_deref()) {
+            return Err(AppError::Forbidden(
+                "Issue is assigned to another network".to_string(),
+            ));
+        }
+
+        Ok(issue)
+    }
+}
+
+#[async_trait]
+impl IssueService for IssueServiceImpl {
+    async fn report_issue(
+        &self,
+        reporter_id: String,
+        connector_id: &str,
+        category: &str,
+        description: Option<String>,
+        photo_media_id: Option<String>,
+    ) -> AppResult<IssueReport> {
+        let category = IssueCategory::from_str(category)?;
+        let connector = self
+            .station_repo
+            .find_connector(connector_id)
+            .await?
+            .ok_or(AppError::NotFound(format!(
+                "Connector with id {} not found",
+                connector_id
+            )))?;
+        if let Some(media_id) = &photo_media_id {
+            self.check_issue_photo(media_id, &connector.station_id, &reporter_id)
+                .await?;
+        }
+        let network_id = self
+            .station_repo
+            .find_network_id(&connector.station_id)
+            .await?;
+
+        self.issue_repo
+            .create(&IssueReport {
+                issue_id: generate_id(ISSUE_ID_PREFIX),
+                station_id: connector.station_id,
+                connector_id: connector.connector_id,
+                network_id,
+                reporter_id: reporter_id.clone(),
+                category: category.as_str().to_string(),
+                description: description.filter(|d| !d.trim().is_empty()),
+                photo_media_id,
+                status: IssueStatus::Open.as_str().to_string(),
+                resolution_note: None,
+                acknowledged_by: None,
+                acknowledged_at: None,
+                resolved_by: None,
+                resolved_at: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                updated_by: Some(reporter_id),
+            })
+            .await
+    }
+
+    async fn list_my_issues(&self, reporter_id: &str) -> AppResult<Vec<IssueReport>> {
+        self.issue_repo.find_by_reporter(reporter_id).await
+    }
+
+    async fn list_issues(
+        &self,
+        network_id: Option<String>,
+        is_admin: bool,
+        status: Option<String>,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> AppResult<Vec<IssueReport>> {
+        if !is_admin && network_id.is_none() {
+            return Err(AppError::Forbidden(
+                "Your account is not attached to a network".to_string(),
+            ));
+        }
+
+        let status = status.as_deref().map(IssueStatus::from_str).transpose()?;
+
+        let limit = limit.unwrap_or(DEFAULT_ISSUE_LIST_LIMIT);
+        if limit <= 0 || limit > MAX_ISSUE_LIST_LIMIT {
+            return Err(AppError::ValidationError(format!(
+                "Limit must be between 1 and {}",
+                MAX_ISSUE_LIST_LIMIT
+            )));
+        }
+        let offset = offset.unwrap_or(0);
+        if offset < 0 {
+            return Err(AppError::ValidationError(
+                "Offset cannot be negative".to_string(),
+            ));
+        }
+
+        self.issue_repo
+            .find_by_network(
+                network_id.as_deref(),
+                status.as_ref().map(|s| s.as_str()),
+                limit,
+                offset,
+            )
+            .await
+    }
+
+    async fn update_issue_status(
+        &self,
+        issue_id: &str,
+        network_id: Option<String>,
+        is_admin: bool,
+        status: &str,
+        resolution_note: Option<String>,
+        user_id: &str,
+    ) -> AppResult<IssueReport> {
+        let next = IssueStatus::from_str(status)?;
+        let issue = self
+            .find_network_issue(issue_id, network_id.as_deref(), is_admin)
+            .await?;
+
+        let current = IssueStatus::from_str(&issue.status)?;
+        if !current.can_transition_to(next) {
+            return Err(AppError::Conflict(format!(
+                "Issue {} is {} and cannot move to {}",
+                issue_id,
+                current.as_str(),
+                next.as_str()
+            )));
+        }
+
+        self.issue_repo
+            .update_status(
+                issue_id,
+                next.as_str(),
+                resolution_note.filter(|n| !n.trim().is_empty()),
+                user_id,
+            )
+            .await
+    }
+
+    async fn mark_connector_faulty(
+        &self,
+        issue_id: &str,
+        network_id: Option<String>,
+        is_admin: bool,
+        user_id: &str,
+    ) -> AppResult<IssueReport> {
+        let issue = self
+            .find_network_issue(issue_id, network_id.as_deref(), is_admin)
+            .await?;
+        if issue.status == IssueStatus::Resolved.as_str() {
+            return Err(AppError::Conflict(format!(
+                "Issue {} is already resolved",
+                issue_id
+            )));
+        }
+
+        self.station_repo
+            .mark_connector_faulty(&issue.connector_id, user_id)
+            .await?;
+
+        // Taking the connector out of service acknowledges the report
+        if issue.status == IssueStatus::Open.as_str() {
+            return self
+                .issue_repo
+                .update_status(issue_id, IssueStatus::Acknowledged.as_str(), None, user_id)
+                .await;
+        }
+        Ok(issue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::{Connector, Media};
+    use crate::domain::repositories::{
+        MockIssueRepository, MockMediaRepository, MockStationRepository,
+    };
+
+    fn media(station_id: &str, uploaded_by: &str) -> Media {
+        Media {
+            media_id: "MED1".to_string(),
+            station_id: station_id.to_string(),
+            review_id: None,
+            uploaded_by: uploaded_by.to_string(),
+            content_type: "image/jpeg".to_string(),
+            width: 800,
+            height: 600,
+            size_bytes: 1024,
+            storage_key: "stations/STA1/MED1.jpg".to_string(),
+            thumbnail_key: "stations/STA1/MED1_thumb.jpg".to_string(),
+            created_at: Utc::now(),
+            url: String::new(),
+            thumbnail_url: String::new(),
+        }
+    }
+
+    fn service(photo: Option<Media>) -> IssueServiceImpl {
+        let mut station_repo = MockStationRepository::new();
+        station_repo.expect_find_connector().returning(|_| {
+            Ok(Some(Connector {
+                connector_id: "CON1".to_string(),
+                station_id: "STA1".to_string(),
+                connector_type: "CCS".to_string(),
+                current_type: "DC".to_string(),
+                power_kw: Some(50.0),
+                voltage: None,
+                amperage: None,
+                count_available: Some(1),
+                count_total: Some(1),
+            }))
+        });
+        station_repo
+            .expect_find_network_id()
+            .returning(|_| Ok(Some("NET1".to_string())));
+
+        let mut media_repo = MockMediaRepository::new();
+        media_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(photo.clone()));
+
+        let mut issue_repo = MockIssueRepository::new();
+        issue_repo
+            .expect_create()
+            .returning(|issue| Ok(issue.clone()));
+
+        IssueServiceImpl::new(
+            Arc::new(issue_repo),
+            Arc::new(station_repo),
+            Arc::new(media_repo),
+        )
+    }
+
+    #[tokio::test]
+    async fn attaches_a_photo_the_reporter_uploaded_for_the_station() {
+        let issue = service(Some(media("STA1", "driver")))
+            .report_issue(
+                "driver".to_string(),
+                "CON1",
+                "damaged_cable",
+                None,
+                Some("MED1".to_string()),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(issue.photo_media_id.as_deref(), Some("MED1"));
+        assert_eq!(issue.network_id.as_deref(), Some("NET1"));
+    }
+
+    #[tokio::test]
+    async fn rejects_photos_of_other_stations_or_uploaders() {
+        for photo in [media("STA2", "driver"), media("STA1", "someone-else")] {
+            let result = service(Some(photo))
+                .report_issue(
+                    "driver".to_string(),
+                    "CON1",
+                    "damaged_cable",
+                    None,
+                    Some("MED1".to_string()),
+                )
+                .await;
+            assert!(matches!(result, Err(AppError::ValidationError(_))));
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_photos() {
+        let result = service(None)
+            .report_issue(
+                "driver".to_string(),
+                "CON1",
+                "wont_start",
+                None,
+                Some("MED1".to_string()),
+            )
+            .await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+}
@@ -0,0 +1,217 @@
+use crate::core::constants::*;
+use crate::core::errors::{AppError, AppResult};
+use crate::core::utils::generate_id;
+use crate::domain::entities::Media;
+use crate::domain::media::process_image;
+use crate::domain::repositories::{MediaRepository, ReviewRepository, StationRepository};
+use crate::domain::services::MediaService;
+use crate::domain::storage::ObjectStorage;
+use async_trait::async_trait;
+use chrono::Utc;
+use std::sync::Arc;
+
+pub struct MediaServiceImpl {
+    media_repo: Arc<dyn MediaRepository>,
+    station_repo: Arc<dyn StationRepository>,
+    review_repo: Arc<dyn ReviewRepository>,
+    storage: Arc<dyn ObjectStorage>,
+}
+
+impl MediaServiceImpl {
+    pub fn new(
+        media_repo: Arc<dyn MediaRepository>,
+        station_repo: Arc<dyn StationRepository>,
+        review_repo: Arc<dyn ReviewRepository>,
+        storage: Arc<dyn ObjectStorage>,
+    ) -> Self {
+        Self {
+            media_repo,
+            station_repo,
+            review_repo,
+            storage,
+        }
+    }
+
+    fn with_urls(&self, mut media: Media) -> Media {
+        media.url = self
+            .storage
+            .public_url(&media.storage_key)
+            .unwrap_or_else(|| format!("/api/media/{}", media.media_id));
+        media.thumbnail_url = self
+            .storage
+            .public_url(&media.thumbnail_key)
+            .unwrap_or_else(|| format!("/api/media/{}/thumbnail", media.media_id));
+        media
+    }
+
+    async fn find_media(&self, media_id: &str) -> AppResult<Media> {
+        self.media_repo
+            .find_by_id(media_id)
+            .await?
+            .ok_or(AppError::NotFound(format!(
+                "Media with id {} not found",
+                media_id
+            )))
+    }
+
+    /// Processes the image, writes it and its thumbnail to storage, then
+    /// records it. Stored objects are cleaned up if recording fails.
+    async fn store(
+        &self,
+        station_id: String,
+        review_id: Option<String>,
+        uploaded_by: String,
+        bytes: &[u8],
+        content_type: &str,
+    ) -> AppResult<Media> {
+        let (bytes, content_type) = (bytes.to_vec(), content_type.to_string());
+        let image = tokio::task::spawn_blocking(move || process_image(&bytes, &content_type))
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))??;
+
+        let media_id = generate_id(MEDIA_ID_PREFIX);
+        let storage_key = format!("stations/{}/{}.{}", station_id, media_id, image.extension);
+        let thumbnail_key = format!("stations/{}/{}_thumb.jpg", station_id, media_id);
+        let size_bytes = image.bytes.len() as i64;
+
+        self.storage
+            .put(&storage_key, image.bytes, image.content_type)
+            .await?;
+        if let Err(e) = self
+            .storage
+            .put(&thumbnail_key, image.thumbnail, "image/jpeg")
+            .await
+        {
+            self.remove_objects(&[&storage_key]).await;
+            return Err(e);
+        }
+
+        let created = self
+            .media_repo
+            .create(&Media {
+                media_id,
+                station_id,
+                review_id,
+                uploaded_by,
+                content_type: image.content_type.to_string(),
+                width: image.width as i32,
+                height: image.height as i32,
+                size_bytes,
+                storage_key: storage_key.clone(),
+                thumbnail_key: thumbnail_key.clone(),
+                created_at: Utc::now(),
+                url: String::new(),
+                thumbnail_url: String::new(),
+            })
+            .await;
+
+        match created {
+            Ok(media) => Ok(self.with_urls(media)),
+            Err(e) => {
+                self.remove_objects(&[&storage_key, &thumbnail_key]).await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Best effort: a leftover object only wastes space
+    async fn remove_objects(&self, keys: &[&str]) {
+        for key in keys {
+            if let Err(e) = self.storage.delete(key).await {
+                tracing::warn!("Failed to delete stored object {}: {}", key, e);
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl MediaService for MediaServiceImpl {
+    async fn upload_station_photo(
+        &self,
+        station_id: &str,
+        user_id: String,
+        bytes: &[u8],
+        content_type: &str,
+    ) -> AppResult<Media> {
+        if !self.station_repo.exists(station_id).await? {
+            return Err(AppError::NotFound(format!(
+                "Station with id {} not found",
+                station_id
+            )));
+        }
+
+        self.store(station_id.to_string(), None, user_id, bytes, content_type)
+            .await
+    }
+
+    async fn upload_review_photo(
+        &self,
+        review_id: &str,
+        user_id: String,
+        bytes: &[u8],
+        content_type: &str,
+    ) -> AppResult<Media> {
+        let review = self
+            .review_repo
+            .find_by_id(review_id)
+            .await?
+            .ok_or(AppError::NotFound(format!(
+                "Review with id {} not found",
+                review_id
+            )))?;
+        if review.user_id != user_id {
+            return Err(AppError::Forbidden(
+                "Only the author can add photos to a review".to_string(),
+            ));
+        }
+        if self.media_repo.count_by_review(review_id).await? >= MAX_PHOTOS_PER_REVIEW {
+            return Err(AppError::ValidationError(format!(
+                "A review can have at most {} photos",
+                MAX_PHOTOS_PER_REVIEW
+            )));
+        }
+
+        self.store(
+            review.station_id,
+            Some(review.review_id),
+            user_id,
+            bytes,
+            content_type,
+        )
+        .await
+    }
+
+    async fn list_station_media(&self, station_id: &str) -> AppResult<Vec<Media>> {
+        let media = self.media_repo.find_by_station(station_id).await?;
+        Ok(media.into_iter().map(|m| self.with_urls(m)).collect())
+    }
+
+    async fn get_media_content(
+        &self,
+        media_id: &str,
+        thumbnail: bool,
+    ) -> AppResult<(Vec<u8>, String)> {
+        let media = self.find_media(media_id).await?;
+        if thumbnail {
+            let bytes = self.storage.get(&media.thumbnail_key).await?;
+            Ok((bytes, "image/jpeg".to_string()))
+        } else {
+            let bytes = self.storage.get(&media.storage_key).await?;
+            Ok((bytes, media.content_type))
+        }
+    }
+
+    async fn delete_media(&self, media_id: &str, user_id: &str, is_admin: bool) -> AppResult<()> {
+        let media = self.find_media(media_id).await?;
+        if !is_admin && media.uploaded_by != user_id {
+            return Err(AppError::Forbidden(
+                "Only the uploader or an admin can delete this photo".to_string(),
+            ));
+        }
+
+        self.media_repo.delete(media_id).await?;
+        self.remove_objects(&[&media.storage_key, &media.thumbnail_key])
+            .await;
+        Ok(())
+    }
+}
@@ -1,3 +1,10 @@
+pub mod checkin_service;
 pub mod dtos;
+pub mod issue_service;
+pub mod media_service;
+pub mod network_verification_service;
+pub mod notification_service;
 pub mod review_service;
 pub mod station_service;
+pub mod tariff_service;
+pub mod vehicle_service;
@@ -0,0 +1,309 @@
+use crate::core::constants::*;
+use crate::core::errors::{AppError, AppResult};
+use crate::core::utils::generate_id;
+use crate::domain::entities::{
+    NetworkVerificationDetail, NetworkVerificationRequest, VerificationDocument, VerificationEvent,
+};
+use crate::domain::repositories::NetworkVerificationRepository;
+use crate::domain::services::NetworkVerificationService;
+use crate::domain::storage::ObjectStorage;
+use crate::domain::value_objects::SubmitVerificationData;
+use crate::domain::verification::{VerificationStatus, document_extension};
+use async_trait::async_trait;
+use chrono::Utc;
+use std::str::FromStr;
+use std::sync::Arc;
+
+const MAX_FILE_NAME_CHARS: usize = 255;
+
+pub struct NetworkVerificationServiceImpl {
+    verification_repo: Arc<dyn NetworkVerificationRepository>,
+    storage: Arc<dyn ObjectStorage>,
+}
+
+impl NetworkVerificationServiceImpl {
+    pub fn new(
+        verification_repo: Arc<dyn NetworkVerificationRepository>,
+        storage: Arc<dyn ObjectStorage>,
+    ) -> Self {
+        Self {
+            verification_repo,
+            storage,
+        }
+    }
+
+    /// The request, provided it belongs to `network_id` or the caller is an admin
+    async fn find_network_request(
+        &self,
+        request_id: &str,
+        network_id: Option<&str>,
+        is_admin: bool,
+    ) -> AppResult<NetworkVerificationRequest> {
+        let request =
+            self.verification_repo
+                .find_by_id(request_id)
+                .await?
+                .ok_or(AppError::NotFound(format!(
+                    "Verification request with id {} not found",
+                    request_id
+                )))?;
+
+        if !is_admin && (network_id.is_none() || network_id != Some(request.network_id.as_str())) {
+            return Err(AppError::Forbidden(
+                "Verification request belongs to another network".to_string(),
+            ));
+        }
+
+        Ok(request)
+    }
+
+    /// Best effort: a leftover object only wastes space
+    async fn remove_object(&self, key: &str) {
+        if let Err(e) = self.storage.delete(key).await {
+            tracing::warn!("Failed to delete stored object {}: {}", key, e);
+        }
+    }
+}
+
+/// The last path segment of an uploaded file name, without control characters
+fn clean_file_name(file_name: Option<String>) -> Option<String> {
+    let name: String = file_name?
+        .rsplit(['/', '\\'])
+        .next()?
+        .chars()
+        .filter(|c| !c.is_control())
+        .take(MAX_FILE_NAME_CHARS)
+        .collect();
+    let name = name.trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+#[async_trait]
+impl NetworkVerificationService for NetworkVerificationServiceImpl {
+    async fn submit_request(
+        &self,
+        network_id: Option<String>,
+        user_id: String,
+        data: SubmitVerificationData,
+    ) -> AppResult<NetworkVerificationRequest> {
+        let network_id = network_id.ok_or(AppError::Forbidden(
+            "Your account is not attached to a network".to_string(),
+        ))?;
+
+        match self
+            .verification_repo
+            .find_network_verified(&network_id)
+            .await?
+        {
+            None => {
+                return Err(AppError::NotFound(format!(
+                    "Network with id {} not found",
+                    network_id
+                )));
+            }
+            Some(true) => {
+                return Err(AppError::Conflict(format!(
+                    "Network {} is already verified",
+                    network_id
+                )));
+            }
+            Some(false) => {}
+        }
+
+        let optional = |value: Option<String>| {
+            value
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+
+        self.verification_repo
+            .create(&NetworkVerificationRequest {
+                request_id: generate_id(VERIFICATION_REQUEST_ID_PREFIX),
+                network_id,
+                status: VerificationStatus::Pending.as_str().to_string(),
+                company_name: data.company_name.trim().to_string(),
+                registration_number: data.registration_number.trim().to_string(),
+                vat_number: optional(data.vat_number),
+                registered_address: data.registered_address.trim().to_string(),
+                contact_name: data.contact_name.trim().to_string(),
+                contact_email: data.contact_email.trim().to_string(),
+                contact_phone: optional(data.contact_phone),
+                submitted_by: user_id,
+                submitted_at: Utc::now(),
+                reviewed_by: None,
+                reviewed_at: None,
+                review_comment: None,
+            })
+            .await
+    }
+
+    async fn list_requests(
+        &self,
+        network_id: Option<String>,
+        status: Option<String>,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> AppResult<Vec<NetworkVerificationRequest>> {
+        let network_id = network_id.ok_or(AppError::Forbidden(
+            "Your account is not attached to a network".to_string(),
+        ))?;
+
+        let status = status
+            .as_deref()
+            .map(VerificationStatus::from_str)
+            .transpose()?;
+
+        let limit = limit.unwrap_or(DEFAULT_VERIFICATION_LIST_LIMIT);
+        if limit <= 0 || limit > MAX_VERIFICATION_LIST_LIMIT {
+            return Err(AppError::ValidationError(format!(
+                "Limit must be between 1 and {}",
+                MAX_VERIFICATION_LIST_LIMIT
+            )));
+        }
+        let offset = offset.unwrap_or(0);
+        if offset < 0 {
+            return Err(AppError::ValidationError(
+                "Offset cannot be negative".to_string(),
+            ));
+        }
+
+        self.verification_repo
+            .find_all(
+                &network_id,
+                status.as_ref().map(|s| s.as_str()),
+                limit,
+                offset,
+            )
+            .await
+    }
+
+    async fn get_request(
+        &self,
+        request_id: &str,
+        network_id: Option<String>,
+    ) -> AppResult<NetworkVerificationDetail> {
+        let request = self
+            .find_network_request(request_id, network_id.as_deref(), false)
+            .await?;
+        let documents = self.verification_repo.find_documents(request_id).await?;
+        let events = self
+            .verification_repo
+            .find_events_by_request(request_id)
+            .await?;
+
+        Ok(NetworkVerificationDetail {
+            request,
+            documents,
+            events,
+        })
+    }
+
+    async fn upload_document(
+        &self,
+        request_id: &str,
+        network_id: Option<String>,
+        user_id: String,
+        file_name: Option<String>,
+        bytes: Vec<u8>,
+        content_type: &str,
+    ) -> AppResult<VerificationDocument> {
+        let request = self
+            .find_network_request(request_id, network_id.as_deref(), false)
+            .await?;
+        if request.status != VerificationStatus::Pending.as_str() {
+            return Err(AppError::Conflict(format!(
+                "Verification request {} is {} and no longer takes documents",
+                request_id, request.status
+            )));
+        }
+        if self.verification_repo.count_documents(request_id).await?
+            >= MAX_DOCUMENTS_PER_VERIFICATION
+        {
+            return Err(AppError::ValidationError(format!(
+                "A verification request can have at most {} documents",
+                MAX_DOCUMENTS_PER_VERIFICATION
+            )));
+        }
+
+        let extension = document_extension(&bytes, content_type)?;
+        let document_id = generate_id(VERIFICATION_DOCUMENT_ID_PREFIX);
+        // Kept apart from station media and never handed out through a public URL
+        let storage_key = format!(
+            "verifications/{}/{}/{}.{}",
+            request.network_id, request_id, document_id, extension
+        );
+        let file_name =
+            clean_file_name(file_name).unwrap_or_else(|| format!("{}.{}", document_id, extension));
+        let size_bytes = bytes.len() as i64;
+
+        self.storage.put(&storage_key, bytes, content_type).await?;
+
+        let created = self
+            .verification_repo
+            .add_document(&VerificationDocument {
+                document_id,
+                request_id: request_id.to_string(),
+                file_name,
+                content_type: content_type.to_string(),
+                size_bytes,
+                storage_key: storage_key.clone(),
+                uploaded_by: user_id,
+                uploaded_at: Utc::now(),
+            })
+            .await;
+
+        if created.is_err() {
+            self.remove_object(&storage_key).await;
+        }
+        created
+    }
+
+    async fn get_document_content(
+        &self,
+        request_id: &str,
+        document_id: &str,
+        network_id: Option<String>,
+        is_admin: bool,
+    ) -> AppResult<(VerificationDocument, Vec<u8>)> {
+        self.find_network_request(request_id, network_id.as_deref(), is_admin)
+            .await?;
+        let document = self
+            .verification_repo
+            .find_document(request_id, document_id)
+            .await?
+            .ok_or(AppError::NotFound(format!(
+                "Document with id {} not found",
+                document_id
+            )))?;
+
+        let bytes = self.storage.get(&document.storage_key).await?;
+        Ok((document, bytes))
+    }
+
+    async fn list_network_events(
+        &self,
+        target_network_id: &str,
+        network_id: Option<String>,
+    ) -> AppResult<Vec<VerificationEvent>> {
+        if network_id.as_deref() != Some(target_network_id) {
+            return Err(AppError::Forbidden(
+                "Only staff of the network can see its verification history".to_string(),
+            ));
+        }
+        if self
+            .verification_repo
+            .find_network_verified(target_network_id)
+            .await?
+            .is_none()
+        {
+            return Err(AppError::NotFound(format!(
+                "Network with id {} not found",
+                target_network_id
+            )));
+        }
+
+        self.verification_repo
+            .find_events_by_network(target_network_id)
+            .await
+    }
+}
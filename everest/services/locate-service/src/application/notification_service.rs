@@ -0,0 +1,39 @@
+use crate::core::errors::AppResult;
+use crate::domain::entities::Notification;
+use crate::domain::repositories::NotificationRepository;
+use crate::domain::services::NotificationService;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+pub struct NotificationServiceImpl {
+    notification_repo: Arc<dyn NotificationRepository>,
+}
+
+impl NotificationServiceImpl {
+    pub fn new(notification_repo: Arc<dyn NotificationRepository>) -> Self {
+        Self { notification_repo }
+    }
+}
+
+#[async_trait]
+impl NotificationService for NotificationServiceImpl {
+    async fn list_notifications(
+        &self,
+        user_id: &str,
+        unread_only: bool,
+    ) -> AppResult<Vec<Notification>> {
+        self.notification_repo
+            .find_by_user(user_id, unread_only)
+            .await
+    }
+
+    async fn mark_notification_read(
+        &self,
+        notification_id: &str,
+        user_id: &str,
+    ) -> AppResult<Notification> {
+        self.notification_repo
+            .mark_read(notification_id, user_id)
+            .await
+    }
+}
@@ -1,19 +1,153 @@
 use crate::core::constants::*;
 use crate::core::errors::{AppError, AppResult};
 use crate::core::utils::generate_id;
-use crate::domain::entities::UserReview;
-use crate::domain::repositories::ReviewRepository;
+use crate::domain::entities::{
+    Notification, ReviewFlag, ReviewReply, StationReview, StationReviewPage, UserReview,
+};
+use crate::domain::moderation::{FlagReason, ModerationPolicy, ModerationStatus};
+use crate::domain::repositories::{
+    NetworkVerificationRepository, NotificationRepository, ReviewRepository, StationRepository,
+};
 use crate::domain::services::ReviewService;
+use crate::domain::value_objects::ReviewListFilter;
 use async_trait::async_trait;
+use chrono::Utc;
+use std::collections::HashMap;
+use std::str::FromStr;
 use std::sync::Arc;
 
 pub struct ReviewServiceImpl {
     review_repo: Arc<dyn ReviewRepository>,
+    station_repo: Arc<dyn StationRepository>,
+    notification_repo: Arc<dyn NotificationRepository>,
+    verification_repo: Arc<dyn NetworkVerificationRepository>,
+    policy: ModerationPolicy,
 }
 
 impl ReviewServiceImpl {
-    pub fn new(review_repo: Arc<dyn ReviewRepository>) -> Self {
-        Self { review_repo }
+    pub fn new(
+        review_repo: Arc<dyn ReviewRepository>,
+        station_repo: Arc<dyn StationRepository>,
+        notification_repo: Arc<dyn NotificationRepository>,
+        verification_repo: Arc<dyn NetworkVerificationRepository>,
+        policy: ModerationPolicy,
+    ) -> Self {
+        Self {
+            review_repo,
+            station_repo,
+            notification_repo,
+            verification_repo,
+            policy,
+        }
+    }
+
+    async fn find_review(&self, review_id: &str) -> AppResult<UserReview> {
+        self.review_repo
+            .find_by_id(review_id)
+            .await?
+            .ok_or(AppError::NotFound(format!(
+                "Review with id {} not found",
+                review_id
+            )))
+    }
+
+    async fn find_network_reply(
+        &self,
+        reply_id: &str,
+        network_id: Option<&str>,
+    ) -> AppResult<ReviewReply> {
+        let reply = self
+            .review_repo
+            .find_reply(reply_id)
+            .await?
+            .ok_or(AppError::NotFound(format!(
+                "Reply with id {} not found",
+                reply_id
+            )))?;
+
+        if network_id != Some(reply.network_id.as_str()) {
+            return Err(AppError::Forbidden(
+                "Reply belongs to another network".to_string(),
+            ));
+        }
+
+        Ok(reply)
+    }
+
+    /// Unverified networks cannot answer reviews publicly
+    async fn ensure_network_verified(&self, network_id: &str) -> AppResult<()> {
+        let verified = self
+            .verification_repo
+            .find_network_verified(network_id)
+            .await?
+            .unwrap_or(false);
+        if !verified {
+            return Err(AppError::Forbidden(
+                "Only verified networks can reply to reviews".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn notify_reviewer(
+        &self,
+        review: &UserReview,
+        kind: &str,
+        message: &str,
+    ) -> AppResult<()> {
+        self.notification_repo
+            .create(&Notification {
+                notification_id: generate_id(NOTIFICATION_ID_PREFIX),
+                user_id: review.user_id.clone(),
+                kind: kind.to_string(),
+                message: message.to_string(),
+                review_id: Some(review.review_id.clone()),
+                created_at: Utc::now(),
+                read_at: None,
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// Holds a freshly written review for an admin when its text hits the
+    /// blocklist, or when it is edited while hidden.
+    async fn apply_automatic_hold(&self, review: UserReview) -> AppResult<UserReview> {
+        let blocked_term = review
+            .review_text
+            .as_deref()
+            .and_then(|text| self.policy.blocklist_match(text));
+
+        let reason = match (blocked_term, review.moderation_status.as_str()) {
+            (Some(term), _) => format!("Matched blocklist term \"{}\"", term),
+            (None, "hidden") => "Edited after being hidden".to_string(),
+            _ => return Ok(review),
+        };
+
+        self.review_repo
+            .set_moderation(
+                &review.review_id,
+                ModerationStatus::Pending.as_str(),
+                Some(reason),
+                None,
+            )
+            .await
+    }
+
+    async fn find_owned_review(
+        &self,
+        review_id: &str,
+        user_id: &str,
+        is_admin: bool,
+    ) -> AppResult<UserReview> {
+        let review = self.find_review(review_id).await?;
+
+        if !is_admin && review.user_id != user_id {
+            return Err(AppError::Forbidden(
+                "Only the author of a review can modify it".to_string(),
+            ));
+        }
+
+        Ok(review)
     }
 }
 
@@ -26,17 +160,25 @@ impl ReviewService for ReviewServiceImpl {
         rating: i32,
         review_text: Option<String>,
         created_by: String,
-    ) -> AppResult<UserReview> {
-        if rating < 1 || rating > 5 {
+    ) -> AppResult<(UserReview, bool)> {
+        if !(1..=5).contains(&rating) {
             return Err(AppError::ValidationError(
                 "Rating must be between 1 and 5".to_string(),
             ));
         }
 
+        if !self.station_repo.exists(&station_id).await? {
+            return Err(AppError::NotFound(format!(
+                "Station with id {} not found",
+                station_id
+            )));
+        }
+
         let review_id = generate_id(REVIEW_ID_PREFIX);
 
-        self.review_repo
-            .create(
+        let (review, created) = self
+            .review_repo
+            .upsert(
                 review_id,
                 user_id,
                 station_id,
@@ -44,11 +186,93 @@ impl ReviewService for ReviewServiceImpl {
                 review_text,
                 created_by,
             )
-            .await
+            .await?;
+
+        Ok((self.apply_automatic_hold(review).await?, created))
     }
 
-    async fn get_reviews_by_station(&self, station_id: &str) -> AppResult<Vec<UserReview>> {
-        self.review_repo.find_by_station(station_id).await
+    async fn get_reviews_by_station(
+        &self,
+        station_id: &str,
+        filter: ReviewListFilter,
+    ) -> AppResult<StationReviewPage> {
+        if let Some(r) = filter.rating
+            && !(1..=5).contains(&r)
+        {
+            return Err(AppError::ValidationError(
+                "Rating must be between 1 and 5".to_string(),
+            ));
+        }
+        if filter.limit <= 0 || filter.limit > MAX_REVIEW_PAGE_SIZE {
+            return Err(AppError::ValidationError(format!(
+                "Limit must be between 1 and {}",
+                MAX_REVIEW_PAGE_SIZE
+            )));
+        }
+        if filter.offset < 0 {
+            return Err(AppError::ValidationError(
+                "Offset cannot be negative".to_string(),
+            ));
+        }
+
+        let reviews = self
+            .review_repo
+            .find_by_station(station_id, &filter)
+            .await?;
+        let review_ids: Vec<String> = reviews.iter().map(|r| r.review_id.clone()).collect();
+        let mut replies: HashMap<String, ReviewReply> = self
+            .review_repo
+            .find_replies_for_reviews(&review_ids)
+            .await?
+            .into_iter()
+            .map(|r| (r.review_id.clone(), r))
+            .collect();
+
+        Ok(StationReviewPage {
+            summary: self.review_repo.summarize_station(station_id).await?,
+            total: self
+                .review_repo
+                .count_by_station(station_id, filter.rating)
+                .await?,
+            reviews: reviews
+                .into_iter()
+                .map(|review| StationReview {
+                    reply: replies.remove(&review.review_id),
+                    review,
+                })
+                .collect(),
+            limit: filter.limit,
+            offset: filter.offset,
+        })
+    }
+
+    async fn vote_helpful(&self, review_id: &str, user_id: &str) -> AppResult<i64> {
+        let review = self.find_review(review_id).await?;
+        if review.user_id == user_id {
+            return Err(AppError::ValidationError(
+                "You cannot vote for your own review".to_string(),
+            ));
+        }
+        if review.moderation_status != ModerationStatus::Visible.as_str() {
+            return Err(AppError::NotFound(format!(
+                "Review with id {} not found",
+                review_id
+            )));
+        }
+
+        self.review_repo
+            .add_helpful_vote(review_id, user_id)
+            .await?;
+        self.review_repo.count_helpful_votes(review_id).await
+    }
+
+    async fn unvote_helpful(&self, review_id: &str, user_id: &str) -> AppResult<i64> {
+        self.find_review(review_id).await?;
+
+        self.review_repo
+            .remove_helpful_vote(review_id, user_id)
+            .await?;
+        self.review_repo.count_helpful_votes(review_id).await
     }
 
     async fn update_review(
@@ -57,21 +281,386 @@ impl ReviewService for ReviewServiceImpl {
         rating: Option<i32>,
         review_text: Option<String>,
         updated_by: String,
+        is_admin: bool,
     ) -> AppResult<UserReview> {
-        if let Some(r) = rating {
-            if r < 1 || r > 5 {
-                return Err(AppError::ValidationError(
-                    "Rating must be between 1 and 5".to_string(),
+        if let Some(r) = rating
+            && !(1..=5).contains(&r)
+        {
+            return Err(AppError::ValidationError(
+                "Rating must be between 1 and 5".to_string(),
+            ));
+        }
+
+        self.find_owned_review(review_id, &updated_by, is_admin)
+            .await?;
+
+        let review = self
+            .review_repo
+            .update(review_id, rating, review_text, updated_by)
+            .await?;
+
+        self.apply_automatic_hold(review).await
+    }
+
+    async fn delete_review(&self, review_id: &str, user_id: &str, is_admin: bool) -> AppResult<()> {
+        self.find_owned_review(review_id, user_id, is_admin).await?;
+        self.review_repo.delete(review_id).await
+    }
+
+    async fn flag_review(
+        &self,
+        review_id: &str,
+        user_id: String,
+        reason: &str,
+        comment: Option<String>,
+    ) -> AppResult<ReviewFlag> {
+        let reason = FlagReason::from_str(reason)?;
+
+        let review = self.find_review(review_id).await?;
+        if review.user_id == user_id {
+            return Err(AppError::ValidationError(
+                "You cannot flag your own review".to_string(),
+            ));
+        }
+
+        let flag = self
+            .review_repo
+            .create_flag(&ReviewFlag {
+                flag_id: generate_id(REVIEW_FLAG_ID_PREFIX),
+                review_id: review_id.to_string(),
+                user_id,
+                reason: reason.as_str().to_string(),
+                comment,
+                created_at: Utc::now(),
+                resolved_at: None,
+                resolved_by: None,
+            })
+            .await?;
+
+        // Enough independent flags take the review offline until an admin decides
+        let open_flags = self.review_repo.count_open_flags(review_id).await?;
+        if review.moderation_status == ModerationStatus::Visible.as_str()
+            && open_flags >= self.policy.flag_threshold()
+        {
+            self.review_repo
+                .set_moderation(
+                    review_id,
+                    ModerationStatus::Pending.as_str(),
+                    Some(format!("Flagged by {} users", open_flags)),
+                    None,
+                )
+                .await?;
+        }
+
+        Ok(flag)
+    }
+
+    async fn reply_to_review(
+        &self,
+        review_id: &str,
+        author_id: String,
+        network_id: Option<String>,
+        reply_text: String,
+    ) -> AppResult<ReviewReply> {
+        if reply_text.trim().is_empty() {
+            return Err(AppError::ValidationError(
+                "Reply text cannot be empty".to_string(),
+            ));
+        }
+
+        let review = self.find_review(review_id).await?;
+        let station_network = self
+            .station_repo
+            .find_network_id(&review.station_id)
+            .await?;
+        let network_id = match (network_id, station_network) {
+            (Some(own), Some(station)) if own == station => own,
+            _ => {
+                return Err(AppError::Forbidden(
+                    "Only the network operating the station can reply".to_string(),
                 ));
             }
+        };
+        self.ensure_network_verified(&network_id).await?;
+
+        let reply = self
+            .review_repo
+            .create_reply(&ReviewReply {
+                reply_id: generate_id(REVIEW_REPLY_ID_PREFIX),
+                review_id: review_id.to_string(),
+                network_id,
+                author_id: author_id.clone(),
+                reply_text,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                created_by: Some(author_id.clone()),
+                updated_by: Some(author_id),
+            })
+            .await?;
+
+        self.notify_reviewer(
+            &review,
+            "review_reply",
+            "The station operator replied to your review",
+        )
+        .await?;
+
+        Ok(reply)
+    }
+
+    async fn update_reply(
+        &self,
+        reply_id: &str,
+        user_id: String,
+        network_id: Option<String>,
+        reply_text: String,
+    ) -> AppResult<ReviewReply> {
+        if reply_text.trim().is_empty() {
+            return Err(AppError::ValidationError(
+                "Reply text cannot be empty".to_string(),
+            ));
         }
 
-        self.review_repo
-            .update(review_id, rating, review_text, updated_by)
+        let existing = self
+            .find_network_reply(reply_id, network_id.as_deref())
+            .await?;
+        self.ensure_network_verified(&existing.network_id).await?;
+        let reply = self
+            .review_repo
+            .update_reply(reply_id, &reply_text, &user_id)
+            .await?;
+
+        let review = self.find_review(&existing.review_id).await?;
+        self.notify_reviewer(
+            &review,
+            "review_reply_updated",
+            "The station operator updated its reply to your review",
+        )
+        .await?;
+
+        Ok(reply)
+    }
+
+    async fn delete_reply(
+        &self,
+        reply_id: &str,
+        network_id: Option<String>,
+        is_admin: bool,
+    ) -> AppResult<()> {
+        if !is_admin {
+            self.find_network_reply(reply_id, network_id.as_deref())
+                .await?;
+        }
+        self.review_repo.delete_reply(reply_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repositories::{
+        MockNetworkVerificationRepository, MockNotificationRepository, MockReviewRepository,
+        MockStationRepository,
+    };
+    use actix_web::ResponseError;
+    use actix_web::http::StatusCode;
+    use mockall::predicate::eq;
+
+    const AUTHOR: &str = "user-author";
+    const OTHER: &str = "user-other";
+
+    fn review(review_id: &str, user_id: &str) -> UserReview {
+        UserReview {
+            review_id: review_id.to_string(),
+            user_id: user_id.to_string(),
+            station_id: "STA-1".to_string(),
+            rating: 4,
+            review_text: Some("Fast and clean".to_string()),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            created_by: user_id.to_string(),
+            updated_by: user_id.to_string(),
+            moderation_status: ModerationStatus::Visible.as_str().to_string(),
+            moderation_reason: None,
+            moderated_by: None,
+            moderated_at: None,
+            helpful_votes: 0,
+        }
+    }
+
+    fn service(
+        review_repo: MockReviewRepository,
+        station_repo: MockStationRepository,
+    ) -> ReviewServiceImpl {
+        ReviewServiceImpl::new(
+            Arc::new(review_repo),
+            Arc::new(station_repo),
+            Arc::new(MockNotificationRepository::new()),
+            Arc::new(MockNetworkVerificationRepository::new()),
+            ModerationPolicy::new(&[], 3),
+        )
+    }
+
+    /// A review repository holding one review by `AUTHOR`
+    fn review_repo_with_review() -> MockReviewRepository {
+        let mut repo = MockReviewRepository::new();
+        repo.expect_find_by_id()
+            .with(eq("REV-1"))
+            .returning(|_| Ok(Some(review("REV-1", AUTHOR))));
+        repo.expect_find_by_id().returning(|_| Ok(None));
+        repo
+    }
+
+    fn status(err: AppError) -> StatusCode {
+        err.status_code()
+    }
+
+    #[tokio::test]
+    async fn create_review_reports_insert_then_update() {
+        for created in [true, false] {
+            let mut station_repo = MockStationRepository::new();
+            station_repo
+                .expect_exists()
+                .with(eq("STA-1"))
+                .returning(|_| Ok(true));
+            let mut review_repo = MockReviewRepository::new();
+            review_repo
+                .expect_upsert()
+                .times(1)
+                .returning(move |_, user_id, _, _, _, _| Ok((review("REV-1", &user_id), created)));
+
+            let (review, was_created) = service(review_repo, station_repo)
+                .create_review(
+                    AUTHOR.to_string(),
+                    "STA-1".to_string(),
+                    4,
+                    None,
+                    AUTHOR.to_string(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(was_created, created);
+            assert_eq!(review.user_id, AUTHOR);
+        }
+    }
+
+    #[tokio::test]
+    async fn create_review_for_an_unknown_station_is_not_found() {
+        let mut station_repo = MockStationRepository::new();
+        station_repo.expect_exists().returning(|_| Ok(false));
+        let mut review_repo = MockReviewRepository::new();
+        review_repo.expect_upsert().never();
+
+        let err = service(review_repo, station_repo)
+            .create_review(
+                AUTHOR.to_string(),
+                "STA-404".to_string(),
+                4,
+                None,
+                AUTHOR.to_string(),
+            )
             .await
+            .unwrap_err();
+
+        assert_eq!(status(err), StatusCode::NOT_FOUND);
     }
 
-    async fn delete_review(&self, review_id: &str) -> AppResult<()> {
-        self.review_repo.delete(review_id).await
+    #[tokio::test]
+    async fn update_by_another_user_is_forbidden() {
+        let mut review_repo = review_repo_with_review();
+        review_repo.expect_update().never();
+
+        let err = service(review_repo, MockStationRepository::new())
+            .update_review("REV-1", Some(1), None, OTHER.to_string(), false)
+            .await
+            .unwrap_err();
+
+        assert_eq!(status(err), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn delete_by_another_user_is_forbidden() {
+        let mut review_repo = review_repo_with_review();
+        review_repo.expect_delete().never();
+
+        let err = service(review_repo, MockStationRepository::new())
+            .delete_review("REV-1", OTHER, false)
+            .await
+            .unwrap_err();
+
+        assert_eq!(status(err), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn author_can_update_and_delete() {
+        let mut review_repo = review_repo_with_review();
+        review_repo
+            .expect_update()
+            .times(1)
+            .returning(|review_id, rating, _, updated_by| {
+                let mut r = review(review_id, AUTHOR);
+                r.rating = rating.unwrap();
+                r.updated_by = updated_by;
+                Ok(r)
+            });
+        review_repo
+            .expect_delete()
+            .with(eq("REV-1"))
+            .times(1)
+            .returning(|_| Ok(()));
+        let service = service(review_repo, MockStationRepository::new());
+
+        let updated = service
+            .update_review("REV-1", Some(2), None, AUTHOR.to_string(), false)
+            .await
+            .unwrap();
+        assert_eq!(updated.rating, 2);
+
+        service.delete_review("REV-1", AUTHOR, false).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn admin_can_update_and_delete_any_review() {
+        let mut review_repo = review_repo_with_review();
+        review_repo
+            .expect_update()
+            .times(1)
+            .returning(|review_id, _, _, updated_by| {
+                let mut r = review(review_id, AUTHOR);
+                r.updated_by = updated_by;
+                Ok(r)
+            });
+        review_repo
+            .expect_delete()
+            .with(eq("REV-1"))
+            .times(1)
+            .returning(|_| Ok(()));
+        let service = service(review_repo, MockStationRepository::new());
+
+        let updated = service
+            .update_review("REV-1", None, None, "admin".to_string(), true)
+            .await
+            .unwrap();
+        assert_eq!(updated.updated_by, "admin");
+
+        service.delete_review("REV-1", "admin", true).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn unknown_review_is_not_found() {
+        let service = service(review_repo_with_review(), MockStationRepository::new());
+
+        let err = service
+            .update_review("REV-404", None, None, AUTHOR.to_string(), false)
+            .await
+            .unwrap_err();
+        assert_eq!(status(err), StatusCode::NOT_FOUND);
+
+        let err = service
+            .delete_review("REV-404", AUTHOR, true)
+            .await
+            .unwrap_err();
+        assert_eq!(status(err), StatusCode::NOT_FOUND);
     }
 }
@@ -1,18 +1,66 @@
 use crate::core::constants::*;
 use crate::core::errors::{AppError, AppResult};
-use crate::domain::entities::Station;
-use crate::domain::repositories::StationRepository;
+use crate::domain::amenities::AmenityCategory;
+use crate::domain::charging::{ChargeEstimate, estimate_compatible_connectors, validate_soc_range};
+use crate::domain::checkins::AvailabilityPolicy;
+use crate::domain::entities::{Amenity, Station, StationDetail, Vehicle};
+use crate::domain::ranking::{RankingPolicy, StationSort};
+use crate::domain::repositories::{AmenityRepository, CheckinRepository, StationRepository};
 use crate::domain::services::StationService;
 use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use std::collections::HashSet;
 use std::sync::Arc;
 
 pub struct StationServiceImpl {
     station_repo: Arc<dyn StationRepository>,
+    checkin_repo: Arc<dyn CheckinRepository>,
+    amenity_repo: Arc<dyn AmenityRepository>,
+    ranking_policy: RankingPolicy,
+    availability_policy: AvailabilityPolicy,
+    walking_distance_meters: i32,
 }
 
 impl StationServiceImpl {
-    pub fn new(station_repo: Arc<dyn StationRepository>) -> Self {
-        Self { station_repo }
+    pub fn new(
+        station_repo: Arc<dyn StationRepository>,
+        checkin_repo: Arc<dyn CheckinRepository>,
+        amenity_repo: Arc<dyn AmenityRepository>,
+        ranking_policy: RankingPolicy,
+        availability_policy: AvailabilityPolicy,
+        walking_distance_meters: i32,
+    ) -> Self {
+        Self {
+            station_repo,
+            checkin_repo,
+            amenity_repo,
+            ranking_policy,
+            availability_policy,
+            walking_distance_meters,
+        }
+    }
+
+    /// Fills in what drivers recently reported about each station
+    async fn attach_checkins(&self, stations: &mut [Station]) -> AppResult<()> {
+        let now = Utc::now();
+        let station_ids: Vec<String> = stations.iter().map(|s| s.station_id.clone()).collect();
+        let recent = self
+            .checkin_repo
+            .find_recent(&station_ids, now - Duration::hours(CHECKIN_LOOKBACK_HOURS))
+            .await?;
+        let last_successful = self.checkin_repo.find_last_successful(&station_ids).await?;
+
+        let mut estimates =
+            self.availability_policy
+                .estimate_by_station(recent, last_successful, now);
+        for station in stations.iter_mut() {
+            if let Some(estimate) = estimates.remove(&station.station_id) {
+                station.reported_availability = estimate.probability;
+                station.availability_confidence = Some(estimate.confidence);
+                station.last_successful_charge_at = estimate.last_successful_charge_at;
+            }
+        }
+        Ok(())
     }
 }
 
@@ -24,14 +72,18 @@ impl StationService for StationServiceImpl {
         longitude: f64,
         radius_meters: Option<i32>,
         limit: Option<i32>,
+        vehicle: Option<Vehicle>,
+        sort: StationSort,
+        amenities: Vec<AmenityCategory>,
+        region_id: Option<&str>,
     ) -> AppResult<Vec<Station>> {
         // Validate coordinates
-        if latitude < -90.0 || latitude > 90.0 {
+        if !(-90.0..=90.0).contains(&latitude) {
             return Err(AppError::ValidationError(
                 "Latitude must be between -90 and 90".to_string(),
             ));
         }
-        if longitude < -180.0 || longitude > 180.0 {
+        if !(-180.0..=180.0).contains(&longitude) {
             return Err(AppError::ValidationError(
                 "Longitude must be between -180 and 180".to_string(),
             ));
@@ -53,8 +105,238 @@ impl StationService for StationServiceImpl {
             )));
         }
 
-        self.station_repo
-            .find_nearby(latitude, longitude, radius, limit_val)
+        // Ranking needs a wider pool than the page, since the best stations
+        // are not necessarily the closest ones, and the amenity filter may
+        // drop many of them
+        let candidates = if sort == StationSort::Distance && amenities.is_empty() {
+            limit_val
+        } else {
+            RANKING_CANDIDATE_LIMIT
+        };
+
+        let mut stations = match vehicle {
+            Some(v) => {
+                self.station_repo
+                    .find_nearby_compatible(
+                        latitude,
+                        longitude,
+                        radius,
+                        candidates,
+                        &v.connector_types,
+                        v.max_ac_kw,
+                        v.max_dc_kw,
+                        region_id,
+                    )
+                    .await?
+            }
+            None => {
+                self.station_repo
+                    .find_nearby(latitude, longitude, radius, candidates, region_id)
+                    .await?
+            }
+        };
+
+        if !amenities.is_empty() && !stations.is_empty() {
+            let station_ids: Vec<String> = stations.iter().map(|s| s.station_id.clone()).collect();
+            let categories: Vec<String> =
+                amenities.iter().map(|c| c.as_str().to_string()).collect();
+            let matching: HashSet<String> = self
+                .amenity_repo
+                .find_stations_with_all(&station_ids, self.walking_distance_meters, &categories)
+                .await?
+                .into_iter()
+                .collect();
+            stations.retain(|s| matching.contains(&s.station_id));
+        }
+
+        self.attach_checkins(&mut stations).await?;
+        let prior_mean = self.station_repo.average_rating().await?;
+        self.ranking_policy
+            .rank(&mut stations, sort, radius, prior_mean);
+        stations.truncate(limit_val as usize);
+
+        Ok(stations)
+    }
+
+    async fn get_station_detail(&self, station_id: &str) -> AppResult<StationDetail> {
+        let station = self
+            .station_repo
+            .find_by_id(station_id)
+            .await?
+            .ok_or(AppError::NotFound(format!(
+                "Station with id {} not found",
+                station_id
+            )))?;
+
+        let mut stations = [station];
+        self.attach_checkins(&mut stations).await?;
+        let [mut station] = stations;
+        let prior_mean = self.station_repo.average_rating().await?;
+        self.ranking_policy.rate(&mut station, prior_mean);
+
+        Ok(StationDetail {
+            connectors: self.station_repo.find_connectors(station_id).await?,
+            station,
+        })
+    }
+
+    async fn list_station_amenities(
+        &self,
+        station_id: &str,
+        categories: Vec<AmenityCategory>,
+        radius_meters: Option<i32>,
+    ) -> AppResult<Vec<Amenity>> {
+        let radius = radius_meters.unwrap_or(self.walking_distance_meters);
+        if radius <= 0 || radius > MAX_AMENITY_RADIUS_METERS {
+            return Err(AppError::ValidationError(format!(
+                "Radius must be between 1 and {} meters",
+                MAX_AMENITY_RADIUS_METERS
+            )));
+        }
+        if !self.station_repo.exists(station_id).await? {
+            return Err(AppError::NotFound(format!(
+                "Station with id {} not found",
+                station_id
+            )));
+        }
+
+        let categories = if categories.is_empty() {
+            AmenityCategory::ALL.to_vec()
+        } else {
+            categories
+        };
+        let categories: Vec<String> = categories.iter().map(|c| c.as_str().to_string()).collect();
+
+        self.amenity_repo
+            .find_near_station(station_id, radius, &categories, MAX_AMENITIES)
             .await
     }
+
+    async fn estimate_charging_time(
+        &self,
+        station_id: &str,
+        vehicle: &Vehicle,
+        from_soc: f64,
+        to_soc: f64,
+    ) -> AppResult<Vec<ChargeEstimate>> {
+        validate_soc_range(from_soc, to_soc)?;
+        let connectors = self.station_repo.find_connectors(station_id).await?;
+        if connectors.is_empty() {
+            return Err(AppError::NotFound(format!(
+                "No connectors found for station {}",
+                station_id
+            )));
+        }
+
+        let estimates = estimate_compatible_connectors(vehicle, &connectors, from_soc, to_soc);
+        if estimates.is_empty() {
+            return Err(AppError::ValidationError(format!(
+                "Station {} has no usable connector compatible with vehicle {}",
+                station_id, vehicle.vehicle_id
+            )));
+        }
+
+        Ok(estimates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::Connector;
+    use crate::domain::ranking::RankingWeights;
+    use crate::domain::repositories::{
+        MockAmenityRepository, MockCheckinRepository, MockStationRepository,
+    };
+
+    fn service(connectors: Vec<Connector>) -> StationServiceImpl {
+        let mut station_repo = MockStationRepository::new();
+        station_repo
+            .expect_find_connectors()
+            .returning(move |_| Ok(connectors.clone()));
+        let weights = RankingWeights {
+            distance: 0.4,
+            availability: 0.2,
+            power: 0.15,
+            rating: 0.15,
+            price: 0.1,
+        };
+        StationServiceImpl::new(
+            Arc::new(station_repo),
+            Arc::new(MockCheckinRepository::new()),
+            Arc::new(MockAmenityRepository::new()),
+            RankingPolicy::new(weights, 5.0),
+            AvailabilityPolicy::new(6.0),
+            400,
+        )
+    }
+
+    fn connector(connector_id: &str, connector_type: &str, power_kw: Option<f64>) -> Connector {
+        Connector {
+            connector_id: connector_id.to_string(),
+            station_id: "STA1".to_string(),
+            connector_type: connector_type.to_string(),
+            current_type: "DC".to_string(),
+            power_kw,
+            voltage: None,
+            amperage: None,
+            count_available: None,
+            count_total: None,
+        }
+    }
+
+    fn vehicle() -> Vehicle {
+        Vehicle {
+            vehicle_id: "VEH1".to_string(),
+            user_id: "user".to_string(),
+            model_id: None,
+            nickname: None,
+            make: "Make".to_string(),
+            model: "Model".to_string(),
+            battery_kwh: 60.0,
+            connector_types: vec!["CCS".to_string()],
+            max_ac_kw: Some(11.0),
+            max_dc_kw: Some(100.0),
+            ac_phases: 3,
+            charge_curve: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            created_by: None,
+            updated_by: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn skips_connectors_without_usable_power() {
+        let service = service(vec![
+            connector("CON1", "CCS", Some(0.0)),
+            connector("CON2", "CCS", None),
+            connector("CON3", "CCS", Some(50.0)),
+            connector("CON4", "CHAdeMO", Some(50.0)),
+        ]);
+        let estimates = service
+            .estimate_charging_time("STA1", &vehicle(), 20.0, 80.0)
+            .await
+            .unwrap();
+        let ids: Vec<&str> = estimates.iter().map(|e| e.connector_id.as_str()).collect();
+        assert_eq!(ids, vec!["CON3"]);
+    }
+
+    #[tokio::test]
+    async fn fails_when_no_connector_is_usable() {
+        let service = service(vec![connector("CON1", "CCS", Some(0.0))]);
+        let result = service
+            .estimate_charging_time("STA1", &vehicle(), 20.0, 80.0)
+            .await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_ranges_instead_of_skipping_connectors() {
+        let service = service(vec![connector("CON1", "CCS", Some(50.0))]);
+        let result = service
+            .estimate_charging_time("STA1", &vehicle(), 80.0, 20.0)
+            .await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
 }
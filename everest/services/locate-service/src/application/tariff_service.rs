@@ -0,0 +1,64 @@
+use crate::core::errors::{AppError, AppResult};
+use crate::domain::entities::Tariff;
+use crate::domain::pricing::{CostBreakdown, SessionProfile, calculate_cost};
+use crate::domain::repositories::TariffRepository;
+use crate::domain::services::TariffService;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+
+pub struct TariffServiceImpl {
+    tariff_repo: Arc<dyn TariffRepository>,
+}
+
+impl TariffServiceImpl {
+    pub fn new(tariff_repo: Arc<dyn TariffRepository>) -> Self {
+        Self { tariff_repo }
+    }
+}
+
+#[async_trait]
+impl TariffService for TariffServiceImpl {
+    async fn get_station_tariff(&self, station_id: &str) -> AppResult<Tariff> {
+        self.tariff_repo
+            .find_active_by_station(station_id)
+            .await?
+            .ok_or(AppError::NotFound(format!(
+                "No active tariff for station {}",
+                station_id
+            )))
+    }
+
+    async fn estimate_session_cost(
+        &self,
+        station_id: &str,
+        energy_kwh: f64,
+        charging_minutes: f64,
+        idle_minutes: Option<f64>,
+    ) -> AppResult<CostBreakdown> {
+        if !charging_minutes.is_finite() || charging_minutes < 0.0 {
+            return Err(AppError::ValidationError(
+                "Charging minutes must be a non-negative number".to_string(),
+            ));
+        }
+        let idle_minutes = idle_minutes.unwrap_or(0.0);
+        if !idle_minutes.is_finite() || idle_minutes < 0.0 {
+            return Err(AppError::ValidationError(
+                "Idle minutes must be a non-negative number".to_string(),
+            ));
+        }
+
+        let tariff = self.get_station_tariff(station_id).await?;
+
+        let start = Utc::now();
+        let duration_seconds = (charging_minutes * 60.0).round() + (idle_minutes * 60.0).round();
+        let session = SessionProfile {
+            start,
+            stop: start + Duration::seconds(duration_seconds as i64),
+            energy_kwh,
+            idle_minutes,
+        };
+
+        calculate_cost(&tariff, &session)
+    }
+}
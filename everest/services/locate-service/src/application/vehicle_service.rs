@@ -0,0 +1,216 @@
+use crate::core::constants::*;
+use crate::core::errors::{AppError, AppResult};
+use crate::core::utils::generate_id;
+use crate::domain::charging::validate_charge_curve;
+use crate::domain::entities::{Vehicle, VehicleModel};
+use crate::domain::repositories::VehicleRepository;
+use crate::domain::services::VehicleService;
+use crate::domain::value_objects::{CreateVehicleData, UpdateVehicleData};
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::types::Json;
+use std::sync::Arc;
+
+pub struct VehicleServiceImpl {
+    vehicle_repo: Arc<dyn VehicleRepository>,
+}
+
+impl VehicleServiceImpl {
+    pub fn new(vehicle_repo: Arc<dyn VehicleRepository>) -> Self {
+        Self { vehicle_repo }
+    }
+
+    async fn validate_connector_types(&self, connector_types: &[String]) -> AppResult<()> {
+        if connector_types.is_empty() {
+            return Err(AppError::ValidationError(
+                "A vehicle must support at least one connector type".to_string(),
+            ));
+        }
+
+        let known = self.vehicle_repo.list_connector_types().await?;
+        let unknown: Vec<&String> = connector_types
+            .iter()
+            .filter(|t| !known.contains(t))
+            .collect();
+        if !unknown.is_empty() {
+            return Err(AppError::ValidationError(format!(
+                "Unknown connector types {:?}, expected any of {:?}",
+                unknown, known
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn validate_specs(
+    battery_kwh: f64,
+    max_ac_kw: Option<f64>,
+    max_dc_kw: Option<f64>,
+    ac_phases: i16,
+) -> AppResult<()> {
+    if battery_kwh <= 0.0 {
+        return Err(AppError::ValidationError(
+            "battery_kwh must be greater than 0".to_string(),
+        ));
+    }
+    if max_ac_kw.is_some_and(|kw| kw <= 0.0) || max_dc_kw.is_some_and(|kw| kw <= 0.0) {
+        return Err(AppError::ValidationError(
+            "Maximum charge rates must be greater than 0".to_string(),
+        ));
+    }
+    if ac_phases != 1 && ac_phases != 3 {
+        return Err(AppError::ValidationError(
+            "ac_phases must be 1 or 3".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl VehicleService for VehicleServiceImpl {
+    async fn list_vehicle_models(&self) -> AppResult<Vec<VehicleModel>> {
+        self.vehicle_repo.list_models().await
+    }
+
+    async fn create_vehicle(&self, user_id: String, data: CreateVehicleData) -> AppResult<Vehicle> {
+        let catalogue = match data.model_id {
+            Some(model_id) => Some(self.vehicle_repo.find_model(model_id).await?.ok_or(
+                AppError::NotFound(format!("Vehicle model {} not found", model_id)),
+            )?),
+            None => None,
+        };
+
+        let missing = |field: &str| {
+            AppError::ValidationError(format!("{} is required when model_id is not set", field))
+        };
+
+        let make = data
+            .make
+            .or_else(|| catalogue.as_ref().map(|m| m.make.clone()))
+            .ok_or_else(|| missing("make"))?;
+        let model = data
+            .model
+            .or_else(|| catalogue.as_ref().map(|m| m.model.clone()))
+            .ok_or_else(|| missing("model"))?;
+        let battery_kwh = data
+            .battery_kwh
+            .or_else(|| catalogue.as_ref().map(|m| m.battery_kwh))
+            .ok_or_else(|| missing("battery_kwh"))?;
+        let connector_types = data
+            .connector_types
+            .or_else(|| catalogue.as_ref().map(|m| m.connector_types.clone()))
+            .ok_or_else(|| missing("connector_types"))?;
+        let max_ac_kw = data
+            .max_ac_kw
+            .or_else(|| catalogue.as_ref().and_then(|m| m.max_ac_kw));
+        let max_dc_kw = data
+            .max_dc_kw
+            .or_else(|| catalogue.as_ref().and_then(|m| m.max_dc_kw));
+        let ac_phases = data
+            .ac_phases
+            .or_else(|| catalogue.as_ref().map(|m| m.ac_phases))
+            .unwrap_or(DEFAULT_AC_PHASES);
+        let charge_curve = data
+            .charge_curve
+            .map(Json)
+            .or_else(|| catalogue.as_ref().and_then(|m| m.charge_curve.clone()));
+
+        validate_specs(battery_kwh, max_ac_kw, max_dc_kw, ac_phases)?;
+        if let Some(curve) = &charge_curve {
+            validate_charge_curve(curve)?;
+        }
+        self.validate_connector_types(&connector_types).await?;
+
+        let vehicle = Vehicle {
+            vehicle_id: generate_id(VEHICLE_ID_PREFIX),
+            user_id: user_id.clone(),
+            model_id: data.model_id,
+            nickname: data.nickname,
+            make,
+            model,
+            battery_kwh,
+            connector_types,
+            max_ac_kw,
+            max_dc_kw,
+            ac_phases,
+            charge_curve,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            created_by: Some(user_id.clone()),
+            updated_by: Some(user_id),
+        };
+
+        self.vehicle_repo.create(&vehicle).await
+    }
+
+    async fn list_user_vehicles(&self, user_id: &str) -> AppResult<Vec<Vehicle>> {
+        self.vehicle_repo.find_by_user(user_id).await
+    }
+
+    async fn get_vehicle(&self, vehicle_id: &str, user_id: &str) -> AppResult<Vehicle> {
+        let vehicle = self
+            .vehicle_repo
+            .find_by_id(vehicle_id)
+            .await?
+            .ok_or(AppError::NotFound(format!(
+                "Vehicle with id {} not found",
+                vehicle_id
+            )))?;
+
+        if vehicle.user_id != user_id {
+            return Err(AppError::Forbidden(
+                "Vehicle belongs to another user".to_string(),
+            ));
+        }
+
+        Ok(vehicle)
+    }
+
+    async fn update_vehicle(
+        &self,
+        vehicle_id: &str,
+        user_id: &str,
+        data: UpdateVehicleData,
+    ) -> AppResult<Vehicle> {
+        let mut vehicle = self.get_vehicle(vehicle_id, user_id).await?;
+
+        if let Some(nickname) = data.nickname {
+            vehicle.nickname = Some(nickname);
+        }
+        if let Some(battery_kwh) = data.battery_kwh {
+            vehicle.battery_kwh = battery_kwh;
+        }
+        if let Some(connector_types) = data.connector_types {
+            self.validate_connector_types(&connector_types).await?;
+            vehicle.connector_types = connector_types;
+        }
+        if let Some(max_ac_kw) = data.max_ac_kw {
+            vehicle.max_ac_kw = Some(max_ac_kw);
+        }
+        if let Some(max_dc_kw) = data.max_dc_kw {
+            vehicle.max_dc_kw = Some(max_dc_kw);
+        }
+        if let Some(ac_phases) = data.ac_phases {
+            vehicle.ac_phases = ac_phases;
+        }
+        if let Some(charge_curve) = data.charge_curve {
+            validate_charge_curve(&charge_curve)?;
+            vehicle.charge_curve = Some(Json(charge_curve));
+        }
+
+        validate_specs(
+            vehicle.battery_kwh,
+            vehicle.max_ac_kw,
+            vehicle.max_dc_kw,
+            vehicle.ac_phases,
+        )?;
+
+        vehicle.updated_by = Some(user_id.to_string());
+        self.vehicle_repo.update(&vehicle).await
+    }
+
+    async fn delete_vehicle(&self, vehicle_id: &str, user_id: &str) -> AppResult<()> {
+        let _ = self.get_vehicle(vehicle_id, user_id).await?;
+        self.vehicle_repo.delete(vehicle_id).await
+    }
+}
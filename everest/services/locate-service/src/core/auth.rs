@@ -31,13 +31,24 @@ pub struct RealmAccess {
 
 impl TokenClaims {
     pub fn get_roles(&self) -> Vec<String> {
-        if let Some(ref realm) = self.realm_access {
-            if !realm.roles.is_empty() {
-                return realm.roles.clone();
-            }
+        if let Some(ref realm) = self.realm_access
+            && !realm.roles.is_empty()
+        {
+            return realm.roles.clone();
         }
         self.roles.clone()
     }
+
+    pub fn is_admin(&self) -> bool {
+        self.get_roles().iter().any(|r| r == "admin")
+    }
+
+    /// Partners and operators act on behalf of their `network_id`
+    pub fn is_network_staff(&self) -> bool {
+        self.get_roles()
+            .iter()
+            .any(|r| r == "partner" || r == "operator")
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -139,6 +150,19 @@ impl JwtValidator {
 
         Ok(claims)
     }
+
+    pub async fn validate_operator_role(&self, token: &str) -> AppResult<TokenClaims> {
+        let claims = self.validate_token(token).await?;
+
+        if !claims.is_network_staff() {
+            return Err(AppError::Forbidden(format!(
+                "Partner or operator role required. Found roles: {:?}",
+                claims.get_roles()
+            )));
+        }
+
+        Ok(claims)
+    }
 }
 
 pub fn extract_bearer_token(req: &HttpRequest) -> AppResult<String> {
@@ -160,6 +184,67 @@ pub async fn require_user_auth(
     validator.validate_user_role(&token).await
 }
 
+pub async fn require_operator_auth(
+    req: &HttpRequest,
+    validator: &JwtValidator,
+) -> AppResult<TokenClaims> {
+    let token = extract_bearer_token(req)?;
+    validator.validate_operator_role(&token).await
+}
+
 pub fn extract_claims(req: &HttpRequest) -> Option<TokenClaims> {
     req.extensions().get::<TokenClaims>().cloned()
 }
+
+/// Tokens signed with a fixed test key, and a validator that trusts it
+#[cfg(test)]
+pub mod testing {
+    use super::*;
+    use jsonwebtoken::{EncodingKey, Header, encode};
+
+    const KID: &str = "test-key";
+    const ISSUER: &str = "everest-test";
+    const PRIVATE_KEY: &str = include_str!("../../tests/fixtures/jwt_test_key.pem");
+    const MODULUS: &str = "yWPOTd-YAg1dSCSB1qACQzJWOGL_0BZ5vKPi9a7-l1HqwWNs0L7RsuBYRNzzZeul-_FU9aOGkN7bPxcA_L_bCvBon6RpSxcwa9Qnuuy9Two-Xy6RwPE2IadkuQ1TRu-YnTCjyZYH0osEONd9lwYlpDkbbzKYqHXy_d6I1bi_1oMQnimUEYrE-7Mr74uaUQWufVaDFpHmJjxfKZu6K-Xrbp4jA5DkN84aD_M6IGHxy2AAPeIHGG_sXtecrW_4ZC_IRnGPB2Ugh1JSm8JwibBwkWwEtHduukPZlBfgWTO_UXuUgO1f-ZNHHs73lR8JcDNLxhvo1uMmZ3LnNgk9UJGZIQ";
+    const EXPONENT: &str = "AQAB";
+
+    /// A validator whose JWKS is preloaded with the test key
+    pub fn validator() -> JwtValidator {
+        let validator =
+            JwtValidator::new("http://127.0.0.1:9/jwks".to_string(), ISSUER.to_string());
+        let jwks = Jwks {
+            keys: vec![JwksKey {
+                kid: KID.to_string(),
+                n: MODULUS.to_string(),
+                e: EXPONENT.to_string(),
+            }],
+        };
+        *validator
+            .jwks_cache
+            .try_write()
+            .expect("cache is not shared yet") = Some(jwks);
+        validator
+    }
+
+    pub fn token(sub: &str, roles: &[&str]) -> String {
+        let now = chrono::Utc::now().timestamp();
+        let claims = TokenClaims {
+            sub: sub.to_string(),
+            exp: now + 300,
+            iat: now,
+            jti: nanoid::nanoid!(),
+            roles: roles.iter().map(|r| r.to_string()).collect(),
+            realm_access: None,
+            email: None,
+            preferred_username: None,
+            iss: Some(ISSUER.to_string()),
+            aud: None,
+            network_id: None,
+            station_id: None,
+        };
+        let mut header = Header::new(Algorithm::RS256);
+        header.kid = Some(KID.to_string());
+        let key = EncodingKey::from_rsa_pem(PRIVATE_KEY.as_bytes()).expect("test key is valid");
+        encode(&header, &claims, &key).expect("test token encodes")
+    }
+}
@@ -1,3 +1,4 @@
+use crate::domain::ranking::RankingWeights;
 use std::env;
 
 #[derive(Clone)]
@@ -8,6 +9,28 @@ pub struct Config {
     pub log_level: String,
     pub jwt_issuer: String,
     pub jwks_url: String,
+    pub review_blocklist: Vec<String>,
+    pub review_flag_threshold: i64,
+    pub ranking_weights: RankingWeights,
+    pub rating_prior_weight: f64,
+    pub checkin_half_life_hours: f64,
+    pub amenity_walking_distance_meters: i32,
+    pub storage: StorageConfig,
+}
+
+/// Where uploaded photos are kept
+#[derive(Clone)]
+pub enum StorageConfig {
+    Local {
+        dir: String,
+        public_base_url: Option<String>,
+    },
+    S3 {
+        bucket: String,
+        region: String,
+        endpoint: Option<String>,
+        public_base_url: Option<String>,
+    },
 }
 
 impl Config {
@@ -24,6 +47,27 @@ impl Config {
             log_level: env::var("RUST_LOG").unwrap_or_else(|_| "info".to_string()),
             jwt_issuer: env::var("JWT_ISSUER").expect("JWT_ISSUER must be set"),
             jwks_url: env::var("JWKS_URL").expect("JWKS_URL must be set"),
+            review_blocklist: env::var("REVIEW_BLOCKLIST")
+                .map(|terms| terms.split(',').map(|t| t.trim().to_string()).collect())
+                .unwrap_or_default(),
+            review_flag_threshold: env::var("REVIEW_FLAG_THRESHOLD")
+                .unwrap_or_else(|_| "3".to_string())
+                .parse()
+                .expect("REVIEW_FLAG_THRESHOLD must be a number"),
+            ranking_weights: RankingWeights {
+                distance: env_f64("RANKING_WEIGHT_DISTANCE", 0.4),
+                availability: env_f64("RANKING_WEIGHT_AVAILABILITY", 0.2),
+                power: env_f64("RANKING_WEIGHT_POWER", 0.15),
+                rating: env_f64("RANKING_WEIGHT_RATING", 0.15),
+                price: env_f64("RANKING_WEIGHT_PRICE", 0.1),
+            },
+            rating_prior_weight: env_f64("RATING_PRIOR_WEIGHT", 5.0),
+            checkin_half_life_hours: env_f64("CHECKIN_HALF_LIFE_HOURS", 6.0),
+            amenity_walking_distance_meters: env::var("AMENITY_WALKING_DISTANCE_METERS")
+                .unwrap_or_else(|_| "400".to_string())
+                .parse()
+                .expect("AMENITY_WALKING_DISTANCE_METERS must be a number"),
+            storage: storage_from_env(),
         }
     }
 
@@ -31,3 +75,29 @@ impl Config {
         format!("{}:{}", self.host, self.port)
     }
 }
+
+fn storage_from_env() -> StorageConfig {
+    let public_base_url = env::var("MEDIA_PUBLIC_BASE_URL").ok();
+    match env::var("STORAGE_BACKEND").as_deref() {
+        Ok("s3") => StorageConfig::S3 {
+            bucket: env::var("S3_BUCKET").expect("S3_BUCKET must be set for the s3 backend"),
+            region: env::var("S3_REGION").unwrap_or_else(|_| "us-east-1".to_string()),
+            endpoint: env::var("S3_ENDPOINT").ok(),
+            public_base_url,
+        },
+        Ok("local") | Err(_) => StorageConfig::Local {
+            dir: env::var("MEDIA_LOCAL_DIR").unwrap_or_else(|_| "./media".to_string()),
+            public_base_url,
+        },
+        Ok(other) => panic!("STORAGE_BACKEND must be local or s3, got {}", other),
+    }
+}
+
+fn env_f64(name: &str, default: f64) -> f64 {
+    env::var(name)
+        .map(|v| {
+            v.parse()
+                .unwrap_or_else(|_| panic!("{} must be a number", name))
+        })
+        .unwrap_or(default)
+}
@@ -3,9 +3,46 @@ pub const STATION_ID_PREFIX: &str = "STA";
 pub const CONNECTOR_ID_PREFIX: &str = "CON";
 
 pub const REVIEW_ID_PREFIX: &str = "REV";
+pub const REVIEW_FLAG_ID_PREFIX: &str = "FLG";
+pub const REVIEW_REPLY_ID_PREFIX: &str = "RPL";
+pub const NOTIFICATION_ID_PREFIX: &str = "NTF";
+pub const ISSUE_ID_PREFIX: &str = "ISS";
+pub const CHECKIN_ID_PREFIX: &str = "CHK";
+pub const MEDIA_ID_PREFIX: &str = "MED";
+pub const VEHICLE_ID_PREFIX: &str = "VEH";
+pub const VERIFICATION_REQUEST_ID_PREFIX: &str = "NVR";
+pub const VERIFICATION_DOCUMENT_ID_PREFIX: &str = "DOC";
 pub const NANOID_LENGTH: usize = 16;
 
 pub const DEFAULT_RADIUS_METERS: i32 = 20000; // 20km
 pub const DEFAULT_LIMIT: i32 = 5;
 pub const MAX_RADIUS_METERS: i32 = 50000; // 50km
 pub const MAX_LIMIT: i32 = 50;
+/// Stations considered before ranking when results are not sorted by distance
+pub const RANKING_CANDIDATE_LIMIT: i32 = 200;
+
+/// Furthest an amenity can be looked up from a station
+pub const MAX_AMENITY_RADIUS_METERS: i32 = 2000;
+pub const MAX_AMENITIES: i64 = 100;
+
+pub const DEFAULT_AC_PHASES: i16 = 3;
+pub const DEFAULT_FROM_SOC_PERCENT: f64 = 10.0;
+pub const DEFAULT_TO_SOC_PERCENT: f64 = 80.0;
+
+pub const DEFAULT_REVIEW_PAGE_SIZE: i64 = 20;
+pub const MAX_REVIEW_PAGE_SIZE: i64 = 100;
+/// Older check-ins no longer weigh on the availability estimate
+pub const CHECKIN_LOOKBACK_HOURS: i64 = 72;
+/// Minimum time between two check-ins of the same user at the same station
+pub const CHECKIN_COOLDOWN_MINUTES: i64 = 15;
+pub const RECENT_CHECKINS_LIMIT: usize = 20;
+
+pub const MAX_UPLOAD_BYTES: usize = 10 * 1024 * 1024;
+pub const MAX_PHOTOS_PER_REVIEW: i64 = 5;
+
+pub const DEFAULT_ISSUE_LIST_LIMIT: i64 = 50;
+pub const MAX_ISSUE_LIST_LIMIT: i64 = 200;
+
+pub const DEFAULT_VERIFICATION_LIST_LIMIT: i64 = 50;
+pub const MAX_VERIFICATION_LIST_LIMIT: i64 = 200;
+pub const MAX_DOCUMENTS_PER_VERIFICATION: i64 = 10;
@@ -0,0 +1,63 @@
+use crate::core::errors::{AppError, AppResult};
+use std::str::FromStr;
+
+/// What drivers look for while charging. Mirrors `osm_amenity_category` in
+/// the database, which maps OSM amenity/shop/tourism tags onto these.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AmenityCategory {
+    Cafe,
+    Restaurant,
+    Toilets,
+    Supermarket,
+    Hotel,
+}
+
+impl AmenityCategory {
+    pub const ALL: [Self; 5] = [
+        Self::Cafe,
+        Self::Restaurant,
+        Self::Toilets,
+        Self::Supermarket,
+        Self::Hotel,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Cafe => "cafe",
+            Self::Restaurant => "restaurant",
+            Self::Toilets => "toilets",
+            Self::Supermarket => "supermarket",
+            Self::Hotel => "hotel",
+        }
+    }
+
+    /// Comma-separated categories, e.g. `cafe,toilets`. Duplicates are dropped.
+    pub fn parse_list(list: &str) -> AppResult<Vec<Self>> {
+        let mut categories = Vec::new();
+        for part in list.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let category = Self::from_str(part)?;
+            if !categories.contains(&category) {
+                categories.push(category);
+            }
+        }
+        Ok(categories)
+    }
+}
+
+impl FromStr for AmenityCategory {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cafe" => Ok(Self::Cafe),
+            "restaurant" => Ok(Self::Restaurant),
+            "toilets" => Ok(Self::Toilets),
+            "supermarket" => Ok(Self::Supermarket),
+            "hotel" => Ok(Self::Hotel),
+            other => Err(AppError::ValidationError(format!(
+                "Unknown amenity category: {}, expected cafe, restaurant, toilets, supermarket or hotel",
+                other
+            ))),
+        }
+    }
+}
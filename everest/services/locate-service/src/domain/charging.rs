@@ -0,0 +1,373 @@
+use super::entities::{ChargeCurvePoint, Connector, Vehicle};
+use crate::core::errors::{AppError, AppResult};
+use serde::Serialize;
+
+/// Resolution of the numerical integration over the state of charge.
+const SOC_STEP_PERCENT: f64 = 0.5;
+
+/// Connectors rated at or above this voltage are three phase (line-to-line voltage).
+const THREE_PHASE_MIN_VOLTAGE: f64 = 380.0;
+
+/// Highest power a single phase can deliver (32 A at 230 V), used when a
+/// connector only advertises `power_kw` and the vehicle charges on one phase.
+const SINGLE_PHASE_MAX_KW: f64 = 7.4;
+
+/// Fallback DC taper, as a fraction of the vehicle's `max_dc_kw`, for
+/// vehicles without a charging curve.
+const DEFAULT_DC_TAPER: [(f64, f64); 4] = [(0.0, 1.0), (50.0, 1.0), (80.0, 0.5), (100.0, 0.1)];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChargeEstimate {
+    pub connector_id: String,
+    pub connector_type: String,
+    pub current_type: String,
+    pub from_soc_percent: f64,
+    pub to_soc_percent: f64,
+    pub energy_kwh: f64,
+    pub minutes: f64,
+    pub average_power_kw: f64,
+    /// Upper bound set by the connector and the vehicle, before any taper
+    pub max_power_kw: f64,
+}
+
+/// Predicts how long `vehicle` takes to charge from `from_soc` to `to_soc`
+/// (percent) on `connector`.
+///
+/// The power drawn at any state of charge is the lowest of what the connector
+/// can supply, what the vehicle accepts for the connector's current type and,
+/// on DC, the vehicle's charging curve. AC supply accounts for the number of
+/// phases both the connector and the on-board charger can use.
+pub fn estimate_charging_time(
+    vehicle: &Vehicle,
+    connector: &Connector,
+    from_soc: f64,
+    to_soc: f64,
+) -> AppResult<ChargeEstimate> {
+    validate_soc_range(from_soc, to_soc)?;
+
+    let is_dc = connector.current_type.eq_ignore_ascii_case("DC");
+    let supply_kw = if is_dc {
+        dc_supply_kw(connector)
+    } else {
+        ac_supply_kw(connector, vehicle.ac_phases)
+    }
+    .ok_or_else(|| {
+        AppError::ValidationError(format!(
+            "Connector {} does not advertise its power",
+            connector.connector_id
+        ))
+    })?;
+
+    let vehicle_limit_kw = if is_dc {
+        vehicle.max_dc_kw
+    } else {
+        vehicle.max_ac_kw
+    };
+    let max_power_kw = vehicle_limit_kw.map_or(supply_kw, |limit| supply_kw.min(limit));
+    if max_power_kw <= 0.0 {
+        return Err(AppError::ValidationError(format!(
+            "Vehicle cannot charge on {} connector {}",
+            connector.current_type, connector.connector_id
+        )));
+    }
+
+    let curve = vehicle.charge_curve.as_ref().map(|c| c.0.as_slice());
+    let mut hours = 0.0;
+    let mut soc = from_soc;
+    while soc < to_soc {
+        let step = SOC_STEP_PERCENT.min(to_soc - soc);
+        let midpoint = soc + step / 2.0;
+
+        let mut power_kw = max_power_kw;
+        if is_dc {
+            power_kw = power_kw.min(dc_curve_kw(curve, vehicle.max_dc_kw, midpoint));
+        }
+        if power_kw <= 0.0 {
+            return Err(AppError::ValidationError(format!(
+                "Charging curve of vehicle {} drops to zero at {:.0}% state of charge",
+                vehicle.vehicle_id, midpoint
+            )));
+        }
+
+        hours += vehicle.battery_kwh * step / 100.0 / power_kw;
+        soc += step;
+    }
+
+    let energy_kwh = vehicle.battery_kwh * (to_soc - from_soc) / 100.0;
+
+    Ok(ChargeEstimate {
+        connector_id: connector.connector_id.clone(),
+        connector_type: connector.connector_type.clone(),
+        current_type: connector.current_type.clone(),
+        from_soc_percent: from_soc,
+        to_soc_percent: to_soc,
+        energy_kwh: round2(energy_kwh),
+        minutes: round2(hours * 60.0),
+        average_power_kw: round2(energy_kwh / hours),
+        max_power_kw: round2(max_power_kw),
+    })
+}
+
+/// Estimates for the `connectors` the vehicle plugs into, fastest first.
+/// Connectors it cannot draw power from are left out.
+pub fn estimate_compatible_connectors(
+    vehicle: &Vehicle,
+    connectors: &[Connector],
+    from_soc: f64,
+    to_soc: f64,
+) -> Vec<ChargeEstimate> {
+    let mut estimates = connectors
+        .iter()
+        .filter(|c| vehicle.connector_types.contains(&c.connector_type))
+        // With the range valid, only connectors the vehicle cannot draw
+        // power from fail, such as ones without a rating or rated 0 kW
+        .filter_map(|c| estimate_charging_time(vehicle, c, from_soc, to_soc).ok())
+        .collect::<Vec<_>>();
+    estimates.sort_by(|a, b| a.minutes.total_cmp(&b.minutes));
+    estimates
+}
+
+/// Checks that charging from `from_soc` to `to_soc` (percent) makes sense
+pub fn validate_soc_range(from_soc: f64, to_soc: f64) -> AppResult<()> {
+    if !(0.0..=100.0).contains(&from_soc) || !(0.0..=100.0).contains(&to_soc) {
+        return Err(AppError::ValidationError(
+            "State of charge must be between 0 and 100".to_string(),
+        ));
+    }
+    if from_soc >= to_soc {
+        return Err(AppError::ValidationError(
+            "Target state of charge must be above the starting one".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that a curve covers a usable range: at least two points, sorted by
+/// state of charge within 0-100 and with a finite positive power. A point at
+/// 0 kW would stall the estimate, so curves end at the trickle power instead.
+pub fn validate_charge_curve(curve: &[ChargeCurvePoint]) -> AppResult<()> {
+    if curve.len() < 2 {
+        return Err(AppError::ValidationError(
+            "A charging curve needs at least two points".to_string(),
+        ));
+    }
+    if curve.iter().any(|p| {
+        !p.soc_percent.is_finite()
+            || !(0.0..=100.0).contains(&p.soc_percent)
+            || !p.power_kw.is_finite()
+            || p.power_kw <= 0.0
+    }) {
+        return Err(AppError::ValidationError(
+            "Charging curve points need a soc_percent between 0 and 100 and a positive power_kw"
+                .to_string(),
+        ));
+    }
+    if curve
+        .windows(2)
+        .any(|w| w[0].soc_percent >= w[1].soc_percent)
+    {
+        return Err(AppError::ValidationError(
+            "Charging curve points must be sorted by increasing soc_percent".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn dc_supply_kw(connector: &Connector) -> Option<f64> {
+    connector.power_kw.or_else(|| {
+        let (voltage, amperage) = (connector.voltage?, connector.amperage?);
+        Some(voltage as f64 * amperage as f64 / 1000.0)
+    })
+}
+
+fn ac_supply_kw(connector: &Connector, vehicle_phases: i16) -> Option<f64> {
+    let vehicle_phases = f64::from(vehicle_phases.max(1));
+
+    let from_rating = match (connector.voltage, connector.amperage) {
+        (Some(voltage), Some(amperage)) => {
+            let voltage = voltage as f64;
+            let (phase_voltage, station_phases) = if voltage >= THREE_PHASE_MIN_VOLTAGE {
+                (voltage / 3f64.sqrt(), 3.0)
+            } else {
+                (voltage, 1.0)
+            };
+            let phases = vehicle_phases.min(station_phases);
+            Some(phase_voltage * amperage as f64 * phases / 1000.0)
+        }
+        _ => None,
+    };
+
+    match (connector.power_kw, from_rating) {
+        (Some(power_kw), Some(rated_kw)) => Some(power_kw.min(rated_kw)),
+        (None, Some(rated_kw)) => Some(rated_kw),
+        (Some(power_kw), None) if vehicle_phases < 3.0 => Some(power_kw.min(SINGLE_PHASE_MAX_KW)),
+        (Some(power_kw), None) => Some(power_kw),
+        (None, None) => None,
+    }
+}
+
+/// Power accepted at `soc` according to the vehicle's curve, or the default
+/// taper scaled to `max_dc_kw`. Unbounded when neither is known.
+fn dc_curve_kw(curve: Option<&[ChargeCurvePoint]>, max_dc_kw: Option<f64>, soc: f64) -> f64 {
+    match (curve, max_dc_kw) {
+        (Some(points), _) if !points.is_empty() => {
+            interpolate(points.iter().map(|p| (p.soc_percent, p.power_kw)), soc)
+        }
+        (_, Some(max_kw)) => max_kw * interpolate(DEFAULT_DC_TAPER.into_iter(), soc),
+        _ => f64::INFINITY,
+    }
+}
+
+/// Linear interpolation over points sorted by x, clamped at both ends.
+fn interpolate(points: impl Iterator<Item = (f64, f64)>, x: f64) -> f64 {
+    let mut previous: Option<(f64, f64)> = None;
+    for (px, py) in points {
+        if x <= px {
+            return match previous {
+                Some((qx, qy)) => qy + (py - qy) * (x - qx) / (px - qx),
+                None => py,
+            };
+        }
+        previous = Some((px, py));
+    }
+    previous.map_or(f64::INFINITY, |(_, py)| py)
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use sqlx::types::Json;
+
+    fn point(soc_percent: f64, power_kw: f64) -> ChargeCurvePoint {
+        ChargeCurvePoint {
+            soc_percent,
+            power_kw,
+        }
+    }
+
+    fn vehicle(curve: Option<Vec<ChargeCurvePoint>>) -> Vehicle {
+        Vehicle {
+            vehicle_id: "VEH1".to_string(),
+            user_id: "user".to_string(),
+            model_id: None,
+            nickname: None,
+            make: "Make".to_string(),
+            model: "Model".to_string(),
+            battery_kwh: 60.0,
+            connector_types: vec!["CCS".to_string()],
+            max_ac_kw: Some(11.0),
+            max_dc_kw: Some(100.0),
+            ac_phases: 3,
+            charge_curve: curve.map(Json),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            created_by: None,
+            updated_by: None,
+        }
+    }
+
+    fn dc_connector(power_kw: Option<f64>) -> Connector {
+        Connector {
+            connector_id: "CON1".to_string(),
+            station_id: "STA1".to_string(),
+            connector_type: "CCS".to_string(),
+            current_type: "DC".to_string(),
+            power_kw,
+            voltage: None,
+            amperage: None,
+            count_available: None,
+            count_total: None,
+        }
+    }
+
+    #[test]
+    fn accepts_sorted_curves_with_positive_power() {
+        assert!(validate_charge_curve(&[point(0.0, 100.0), point(100.0, 5.0)]).is_ok());
+    }
+
+    #[test]
+    fn rejects_curves_reaching_zero_power() {
+        for power_kw in [0.0, -1.0, f64::NAN] {
+            let curve = [point(0.0, 100.0), point(100.0, power_kw)];
+            assert!(validate_charge_curve(&curve).is_err(), "{}", power_kw);
+        }
+    }
+
+    #[test]
+    fn rejects_non_finite_points() {
+        for value in [f64::INFINITY, f64::NEG_INFINITY, f64::NAN] {
+            let curve = [point(0.0, 100.0), point(100.0, value)];
+            assert!(validate_charge_curve(&curve).is_err(), "power {}", value);
+            let curve = [point(0.0, 100.0), point(value, 50.0)];
+            assert!(validate_charge_curve(&curve).is_err(), "soc {}", value);
+        }
+    }
+
+    #[test]
+    fn rejects_short_or_unsorted_curves() {
+        assert!(validate_charge_curve(&[point(0.0, 100.0)]).is_err());
+        assert!(validate_charge_curve(&[point(50.0, 100.0), point(10.0, 50.0)]).is_err());
+        assert!(validate_charge_curve(&[point(0.0, 100.0), point(120.0, 50.0)]).is_err());
+    }
+
+    #[test]
+    fn valid_curves_always_give_an_estimate() {
+        let curve = vec![point(0.0, 150.0), point(80.0, 50.0), point(100.0, 1.0)];
+        validate_charge_curve(&curve).unwrap();
+        let estimate = estimate_charging_time(
+            &vehicle(Some(curve)),
+            &dc_connector(Some(150.0)),
+            0.0,
+            100.0,
+        )
+        .unwrap();
+        assert_eq!(estimate.max_power_kw, 100.0);
+        assert!(estimate.minutes > 0.0);
+    }
+
+    #[test]
+    fn flat_power_gives_energy_over_power() {
+        let estimate =
+            estimate_charging_time(&vehicle(None), &dc_connector(Some(50.0)), 20.0, 50.0).unwrap();
+        // 18 kWh at 50 kW, below the taper of the default curve
+        assert_eq!(estimate.energy_kwh, 18.0);
+        assert_eq!(estimate.minutes, 21.6);
+    }
+
+    #[test]
+    fn rejects_connectors_the_vehicle_cannot_draw_from() {
+        assert!(estimate_charging_time(&vehicle(None), &dc_connector(None), 0.0, 80.0).is_err());
+        assert!(
+            estimate_charging_time(&vehicle(None), &dc_connector(Some(0.0)), 0.0, 80.0).is_err()
+        );
+    }
+
+    #[test]
+    fn estimates_compatible_connectors_fastest_first() {
+        let mut type2 = dc_connector(Some(150.0));
+        type2.connector_type = "Type2".to_string();
+        let connectors = [
+            dc_connector(Some(50.0)),
+            dc_connector(None),
+            type2,
+            dc_connector(Some(100.0)),
+        ];
+
+        let estimates = estimate_compatible_connectors(&vehicle(None), &connectors, 20.0, 50.0);
+        let powers: Vec<f64> = estimates.iter().map(|e| e.max_power_kw).collect();
+        assert_eq!(powers, vec![100.0, 50.0]);
+    }
+
+    #[test]
+    fn rejects_invalid_soc_ranges() {
+        assert!(validate_soc_range(-1.0, 50.0).is_err());
+        assert!(validate_soc_range(20.0, 101.0).is_err());
+        assert!(validate_soc_range(50.0, 50.0).is_err());
+        assert!(validate_soc_range(10.0, 90.0).is_ok());
+    }
+}
@@ -0,0 +1,142 @@
+use super::entities::StationCheckin;
+use crate::core::errors::AppError;
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckinOutcome {
+    ChargedOk,
+    AllOccupied,
+    NotWorking,
+}
+
+impl CheckinOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ChargedOk => "charged_ok",
+            Self::AllOccupied => "all_occupied",
+            Self::NotWorking => "not_working",
+        }
+    }
+}
+
+impl FromStr for CheckinOutcome {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "charged_ok" => Ok(Self::ChargedOk),
+            "all_occupied" => Ok(Self::AllOccupied),
+            "not_working" => Ok(Self::NotWorking),
+            other => Err(AppError::ValidationError(format!(
+                "Unknown check-in outcome: {}, expected charged_ok, all_occupied or not_working",
+                other
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StationAvailability {
+    pub station_id: String,
+    pub estimate: AvailabilityEstimate,
+    pub recent_checkins: Vec<StationCheckin>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AvailabilityEstimate {
+    /// Freshness-weighted share of check-ins where the driver could charge
+    pub probability: Option<f64>,
+    /// 0 without recent check-ins, approaching 1 as fresh reports pile up
+    pub confidence: f64,
+    pub checkins_considered: usize,
+    pub last_successful_charge_at: Option<DateTime<Utc>>,
+}
+
+/// Turns recent check-ins into an availability estimate, each check-in
+/// counting half as much every `half_life_hours`.
+#[derive(Debug, Clone)]
+pub struct AvailabilityPolicy {
+    half_life_hours: f64,
+}
+
+impl AvailabilityPolicy {
+    pub fn new(half_life_hours: f64) -> Self {
+        Self {
+            half_life_hours: if half_life_hours > 0.0 {
+                half_life_hours
+            } else {
+                1.0
+            },
+        }
+    }
+
+    pub fn estimate(
+        &self,
+        checkins: &[StationCheckin],
+        last_successful_charge_at: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> AvailabilityEstimate {
+        let mut total_weight = 0.0;
+        let mut positive_weight = 0.0;
+        for checkin in checkins {
+            let age_hours = (now - checkin.created_at).num_seconds().max(0) as f64 / 3600.0;
+            let weight = 0.5f64.powf(age_hours / self.half_life_hours);
+            total_weight += weight;
+            if checkin.outcome == CheckinOutcome::ChargedOk.as_str() {
+                positive_weight += weight;
+            }
+        }
+
+        AvailabilityEstimate {
+            probability: (total_weight > 0.0).then(|| round2(positive_weight / total_weight)),
+            // A single check-in made just now gives ~0.63, two give ~0.86
+            confidence: round2(1.0 - (-total_weight).exp()),
+            checkins_considered: checkins.len(),
+            last_successful_charge_at,
+        }
+    }
+}
+
+impl AvailabilityPolicy {
+    /// One estimate per station appearing in either list
+    pub fn estimate_by_station(
+        &self,
+        recent: Vec<StationCheckin>,
+        last_successful: Vec<StationCheckin>,
+        now: DateTime<Utc>,
+    ) -> HashMap<String, AvailabilityEstimate> {
+        let mut recent_by_station: HashMap<String, Vec<StationCheckin>> = HashMap::new();
+        for checkin in recent {
+            recent_by_station
+                .entry(checkin.station_id.clone())
+                .or_default()
+                .push(checkin);
+        }
+        let mut last_success_by_station: HashMap<String, DateTime<Utc>> = last_successful
+            .into_iter()
+            .map(|c| (c.station_id, c.created_at))
+            .collect();
+
+        let station_ids: HashSet<String> = recent_by_station
+            .keys()
+            .chain(last_success_by_station.keys())
+            .cloned()
+            .collect();
+
+        station_ids
+            .into_iter()
+            .map(|station_id| {
+                let checkins = recent_by_station.remove(&station_id).unwrap_or_default();
+                let last_success = last_success_by_station.remove(&station_id);
+                let estimate = self.estimate(&checkins, last_success, now);
+                (station_id, estimate)
+            })
+            .collect()
+    }
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
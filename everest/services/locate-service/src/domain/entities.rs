@@ -1,5 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
 use utoipa::ToSchema;
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
@@ -15,6 +16,31 @@ pub struct Station {
     pub operator: Option<String>,
     pub latitude: Option<f64>,
     pub longitude: Option<f64>,
+    /// Only set when searching for a specific vehicle
+    #[sqlx(default)]
+    pub effective_power_kw: Option<f64>,
+    #[sqlx(default)]
+    pub average_rating: Option<f64>,
+    #[sqlx(default)]
+    pub review_count: i64,
+    /// Energy price of the network's active tariff, VAT included
+    #[sqlx(default)]
+    pub energy_price_per_kwh: Option<f64>,
+    /// The operating network passed verification
+    #[sqlx(default)]
+    pub network_verified: bool,
+    /// Set by ranking
+    #[sqlx(skip)]
+    pub bayesian_rating: Option<f64>,
+    #[sqlx(skip)]
+    pub score: Option<f64>,
+    /// Set from recent driver check-ins
+    #[sqlx(skip)]
+    pub reported_availability: Option<f64>,
+    #[sqlx(skip)]
+    pub availability_confidence: Option<f64>,
+    #[sqlx(skip)]
+    pub last_successful_charge_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
@@ -28,4 +54,282 @@ pub struct UserReview {
     pub updated_at: DateTime<Utc>,
     pub created_by: String,
     pub updated_by: String,
+    pub moderation_status: String,
+    pub moderation_reason: Option<String>,
+    pub moderated_by: Option<String>,
+    pub moderated_at: Option<DateTime<Utc>>,
+    /// Only populated by listing queries
+    #[sqlx(default)]
+    pub helpful_votes: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct ReviewSummary {
+    pub average_rating: Option<f64>,
+    pub total_reviews: i64,
+    /// Number of 1 to 5 star reviews, in that order
+    pub histogram: Vec<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct ReviewFlag {
+    pub flag_id: String,
+    pub review_id: String,
+    pub user_id: String,
+    pub reason: String,
+    pub comment: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub resolved_by: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct ReviewReply {
+    pub reply_id: String,
+    pub review_id: String,
+    pub network_id: String,
+    pub author_id: String,
+    pub reply_text: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub created_by: Option<String>,
+    pub updated_by: Option<String>,
+}
+
+/// A review as shown on a station, with the network's reply if any
+#[derive(Debug, Clone)]
+pub struct StationReview {
+    pub review: UserReview,
+    pub reply: Option<ReviewReply>,
+}
+
+#[derive(Debug, Clone)]
+pub struct StationReviewPage {
+    pub summary: ReviewSummary,
+    pub reviews: Vec<StationReview>,
+    /// Reviews matching the filter, across all pages
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct Notification {
+    pub notification_id: String,
+    pub user_id: String,
+    pub kind: String,
+    pub message: String,
+    pub review_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub read_at: Option<DateTime<Utc>>,
+}
+
+/// What a driver found at a station: charged fine, all occupied or not working
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct StationCheckin {
+    pub checkin_id: String,
+    pub station_id: String,
+    pub connector_id: Option<String>,
+    pub user_id: String,
+    pub outcome: String,
+    pub comment: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A point of interest near a station, from the imported OSM extract
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct Amenity {
+    pub osm_id: i64,
+    /// node, way or relation
+    pub osm_type: String,
+    pub category: String,
+    /// The raw OSM tag value, e.g. fast_food for a restaurant
+    pub kind: String,
+    pub name: Option<String>,
+    pub opening_hours: Option<String>,
+    pub distance_meters: f64,
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+/// A photo of a station, uploaded on its own or attached to a review
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct Media {
+    pub media_id: String,
+    pub station_id: String,
+    pub review_id: Option<String>,
+    pub uploaded_by: String,
+    pub content_type: String,
+    pub width: i32,
+    pub height: i32,
+    pub size_bytes: i64,
+    pub storage_key: String,
+    pub thumbnail_key: String,
+    pub created_at: DateTime<Utc>,
+    /// Set by the media service from the storage backend
+    #[sqlx(skip)]
+    pub url: String,
+    #[sqlx(skip)]
+    pub thumbnail_url: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct StationDetail {
+    pub station: Station,
+    pub connectors: Vec<Connector>,
+}
+
+/// A connector problem reported by a driver, handled as a ticket by the
+/// network operating the station
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct IssueReport {
+    pub issue_id: String,
+    pub station_id: String,
+    pub connector_id: String,
+    /// None while the station is not assigned to a network
+    pub network_id: Option<String>,
+    pub reporter_id: String,
+    pub category: String,
+    pub description: Option<String>,
+    /// Station photo uploaded by the reporter
+    pub photo_media_id: Option<String>,
+    pub status: String,
+    pub resolution_note: Option<String>,
+    pub acknowledged_by: Option<String>,
+    pub acknowledged_at: Option<DateTime<Utc>>,
+    pub resolved_by: Option<String>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub updated_by: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct Tariff {
+    pub tariff_id: String,
+    pub network_id: String,
+    pub name: String,
+    pub currency: String,
+    pub vat_percent: f64,
+    pub min_price: Option<f64>,
+    pub max_price: Option<f64>,
+    #[sqlx(skip)]
+    pub components: Vec<TariffComponent>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct TariffComponent {
+    pub component_type: String,
+    pub price: f64,
+    pub step_size: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct VehicleModel {
+    pub id: i32,
+    pub make: String,
+    pub model: String,
+    pub battery_kwh: f64,
+    pub connector_types: Vec<String>,
+    pub max_ac_kw: Option<f64>,
+    pub max_dc_kw: Option<f64>,
+    pub ac_phases: i16,
+    #[schema(value_type = Option<Vec<ChargeCurvePoint>>)]
+    pub charge_curve: Option<Json<Vec<ChargeCurvePoint>>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct Vehicle {
+    pub vehicle_id: String,
+    pub user_id: String,
+    pub model_id: Option<i32>,
+    pub nickname: Option<String>,
+    pub make: String,
+    pub model: String,
+    pub battery_kwh: f64,
+    pub connector_types: Vec<String>,
+    pub max_ac_kw: Option<f64>,
+    pub max_dc_kw: Option<f64>,
+    pub ac_phases: i16,
+    #[schema(value_type = Option<Vec<ChargeCurvePoint>>)]
+    pub charge_curve: Option<Json<Vec<ChargeCurvePoint>>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub created_by: Option<String>,
+    pub updated_by: Option<String>,
+}
+
+/// One point of a DC charging curve: the power the battery accepts at a state of charge
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+pub struct ChargeCurvePoint {
+    pub soc_percent: f64,
+    pub power_kw: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct Connector {
+    pub connector_id: String,
+    pub station_id: String,
+    pub connector_type: String,
+    pub current_type: String,
+    pub power_kw: Option<f64>,
+    pub voltage: Option<i32>,
+    pub amperage: Option<i32>,
+    pub count_available: Option<i32>,
+    pub count_total: Option<i32>,
+}
+
+/// Company details a partner submits to get its network verified
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct NetworkVerificationRequest {
+    pub request_id: String,
+    pub network_id: String,
+    pub status: String,
+    pub company_name: String,
+    pub registration_number: String,
+    pub vat_number: Option<String>,
+    pub registered_address: String,
+    pub contact_name: String,
+    pub contact_email: String,
+    pub contact_phone: Option<String>,
+    pub submitted_by: String,
+    pub submitted_at: DateTime<Utc>,
+    pub reviewed_by: Option<String>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub review_comment: Option<String>,
+}
+
+/// A supporting document of a verification request, such as a company
+/// registration extract
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct VerificationDocument {
+    pub document_id: String,
+    pub request_id: String,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub storage_key: String,
+    pub uploaded_by: String,
+    pub uploaded_at: DateTime<Utc>,
+}
+
+/// A change of a network's verification state
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct VerificationEvent {
+    pub event_id: i64,
+    pub network_id: String,
+    pub request_id: Option<String>,
+    /// None when the request was submitted
+    pub from_status: Option<String>,
+    pub to_status: String,
+    pub actor_id: String,
+    pub comment: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NetworkVerificationDetail {
+    pub request: NetworkVerificationRequest,
+    pub documents: Vec<VerificationDocument>,
+    pub events: Vec<VerificationEvent>,
 }
@@ -0,0 +1,88 @@
+use crate::core::errors::AppError;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueCategory {
+    WontStart,
+    DamagedCable,
+    BlockedByIce,
+    PaymentFailed,
+    SlowCharging,
+    Other,
+}
+
+impl IssueCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::WontStart => "wont_start",
+            Self::DamagedCable => "damaged_cable",
+            Self::BlockedByIce => "blocked_by_ice",
+            Self::PaymentFailed => "payment_failed",
+            Self::SlowCharging => "slow_charging",
+            Self::Other => "other",
+        }
+    }
+}
+
+impl FromStr for IssueCategory {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "wont_start" => Ok(Self::WontStart),
+            "damaged_cable" => Ok(Self::DamagedCable),
+            "blocked_by_ice" => Ok(Self::BlockedByIce),
+            "payment_failed" => Ok(Self::PaymentFailed),
+            "slow_charging" => Ok(Self::SlowCharging),
+            "other" => Ok(Self::Other),
+            other => Err(AppError::ValidationError(format!(
+                "Unknown issue category: {}",
+                other
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueStatus {
+    Open,
+    Acknowledged,
+    Resolved,
+}
+
+impl IssueStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::Acknowledged => "acknowledged",
+            Self::Resolved => "resolved",
+        }
+    }
+
+    /// Tickets only move forward; a resolved ticket is closed for good and a
+    /// recurring problem gets a new report.
+    pub fn can_transition_to(&self, next: IssueStatus) -> bool {
+        matches!(
+            (self, next),
+            (Self::Open, Self::Acknowledged)
+                | (Self::Open, Self::Resolved)
+                | (Self::Acknowledged, Self::Resolved)
+        )
+    }
+}
+
+impl FromStr for IssueStatus {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "open" => Ok(Self::Open),
+            "acknowledged" => Ok(Self::Acknowledged),
+            "resolved" => Ok(Self::Resolved),
+            other => Err(AppError::ValidationError(format!(
+                "Unknown issue status: {}",
+                other
+            ))),
+        }
+    }
+}
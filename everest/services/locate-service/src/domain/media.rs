@@ -0,0 +1,148 @@
+use crate::core::errors::{AppError, AppResult};
+use image::codecs::jpeg::JpegEncoder;
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader, Limits};
+use std::io::Cursor;
+
+/// Longest side of a stored photo; larger uploads are scaled down.
+const MAX_DIMENSION: u32 = 2048;
+const THUMBNAIL_DIMENSION: u32 = 320;
+const JPEG_QUALITY: u8 = 85;
+/// Bounds on what an upload may decode to, as a small compressed file can
+/// describe a huge image
+const MAX_DECODED_DIMENSION: u32 = 8192;
+const MAX_DECODED_BYTES: u64 = 256 * 1024 * 1024;
+
+pub const ALLOWED_CONTENT_TYPES: [&str; 3] = ["image/jpeg", "image/png", "image/webp"];
+
+#[derive(Debug)]
+pub struct ProcessedImage {
+    pub bytes: Vec<u8>,
+    pub content_type: &'static str,
+    pub extension: &'static str,
+    pub width: u32,
+    pub height: u32,
+    pub thumbnail: Vec<u8>,
+}
+
+/// Checks that `bytes` really is an image of the declared type, then
+/// re-encodes it. Re-encoding drops all metadata, EXIF location included;
+/// the EXIF orientation is applied to the pixels first so photos stay upright.
+///
+/// PNGs stay PNG to keep transparency, everything else becomes JPEG.
+/// Thumbnails are always JPEG. Decoding and resizing are CPU bound, so
+/// async callers run this on a blocking thread.
+pub fn process_image(bytes: &[u8], declared_content_type: &str) -> AppResult<ProcessedImage> {
+    if !ALLOWED_CONTENT_TYPES.contains(&declared_content_type) {
+        return Err(AppError::ValidationError(format!(
+            "Unsupported content type {}, expected one of {}",
+            declared_content_type,
+            ALLOWED_CONTENT_TYPES.join(", ")
+        )));
+    }
+
+    let format = image::guess_format(bytes)
+        .map_err(|_| AppError::ValidationError("File is not a recognised image".to_string()))?;
+    if format.to_mime_type() != declared_content_type {
+        return Err(AppError::ValidationError(format!(
+            "File content is {} but was uploaded as {}",
+            format.to_mime_type(),
+            declared_content_type
+        )));
+    }
+
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_DECODED_DIMENSION);
+    limits.max_image_height = Some(MAX_DECODED_DIMENSION);
+    limits.max_alloc = Some(MAX_DECODED_BYTES);
+    let mut reader = ImageReader::with_format(Cursor::new(bytes), format);
+    reader.limits(limits);
+    let mut decoder = reader.into_decoder().map_err(invalid_image)?;
+    let orientation = decoder.orientation().map_err(invalid_image)?;
+    let mut image = DynamicImage::from_decoder(decoder).map_err(invalid_image)?;
+    image.apply_orientation(orientation);
+
+    if image.width() > MAX_DIMENSION || image.height() > MAX_DIMENSION {
+        image = image.resize(
+            MAX_DIMENSION,
+            MAX_DIMENSION,
+            image::imageops::FilterType::Lanczos3,
+        );
+    }
+
+    let (bytes, content_type, extension) = if format == ImageFormat::Png {
+        (encode_png(&image)?, "image/png", "png")
+    } else {
+        (encode_jpeg(&image)?, "image/jpeg", "jpg")
+    };
+    let thumbnail = encode_jpeg(&image.thumbnail(THUMBNAIL_DIMENSION, THUMBNAIL_DIMENSION))?;
+
+    Ok(ProcessedImage {
+        bytes,
+        content_type,
+        extension,
+        width: image.width(),
+        height: image.height(),
+        thumbnail,
+    })
+}
+
+fn encode_jpeg(image: &DynamicImage) -> AppResult<Vec<u8>> {
+    let mut out = Vec::new();
+    // JPEG has no alpha channel
+    image
+        .to_rgb8()
+        .write_with_encoder(JpegEncoder::new_with_quality(&mut out, JPEG_QUALITY))
+        .map_err(encoding_failed)?;
+    Ok(out)
+}
+
+fn encode_png(image: &DynamicImage) -> AppResult<Vec<u8>> {
+    let mut out = Cursor::new(Vec::new());
+    image
+        .write_to(&mut out, ImageFormat::Png)
+        .map_err(encoding_failed)?;
+    Ok(out.into_inner())
+}
+
+fn invalid_image(err: image::ImageError) -> AppError {
+    AppError::ValidationError(format!("Invalid image: {}", err))
+}
+
+fn encoding_failed(err: image::ImageError) -> AppError {
+    AppError::InternalError(format!("Failed to encode image: {}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::RgbImage;
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        encode_png(&DynamicImage::ImageRgb8(RgbImage::new(width, height))).unwrap()
+    }
+
+    #[test]
+    fn keeps_small_pngs_as_png() {
+        let image = process_image(&png(40, 30), "image/png").unwrap();
+        assert_eq!(image.content_type, "image/png");
+        assert_eq!((image.width, image.height), (40, 30));
+    }
+
+    #[test]
+    fn scales_down_large_images() {
+        let image = process_image(&png(4096, 16), "image/png").unwrap();
+        assert_eq!((image.width, image.height), (MAX_DIMENSION, 8));
+    }
+
+    #[test]
+    fn rejects_images_past_the_decoding_limits() {
+        let result = process_image(&png(MAX_DECODED_DIMENSION + 1, 1), "image/png");
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+
+    #[test]
+    fn rejects_content_of_another_type() {
+        let result = process_image(&png(4, 4), "image/jpeg");
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+}
@@ -1,3 +1,14 @@
+pub mod amenities;
+pub mod charging;
+pub mod checkins;
 pub mod entities;
+pub mod issues;
+pub mod media;
+pub mod moderation;
+pub mod pricing;
+pub mod ranking;
 pub mod repositories;
 pub mod services;
+pub mod storage;
+pub mod value_objects;
+pub mod verification;
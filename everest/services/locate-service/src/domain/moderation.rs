@@ -0,0 +1,119 @@
+use crate::core::errors::AppError;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModerationStatus {
+    Visible,
+    Pending,
+    Hidden,
+}
+
+impl ModerationStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Visible => "visible",
+            Self::Pending => "pending",
+            Self::Hidden => "hidden",
+        }
+    }
+}
+
+impl FromStr for ModerationStatus {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "visible" => Ok(Self::Visible),
+            "pending" => Ok(Self::Pending),
+            "hidden" => Ok(Self::Hidden),
+            other => Err(AppError::ValidationError(format!(
+                "Unknown moderation status: {}",
+                other
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlagReason {
+    Spam,
+    Offensive,
+    OffTopic,
+    FalseInformation,
+    Other,
+}
+
+impl FlagReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Spam => "spam",
+            Self::Offensive => "offensive",
+            Self::OffTopic => "off_topic",
+            Self::FalseInformation => "false_information",
+            Self::Other => "other",
+        }
+    }
+}
+
+impl FromStr for FlagReason {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "spam" => Ok(Self::Spam),
+            "offensive" => Ok(Self::Offensive),
+            "off_topic" => Ok(Self::OffTopic),
+            "false_information" => Ok(Self::FalseInformation),
+            "other" => Ok(Self::Other),
+            other => Err(AppError::ValidationError(format!(
+                "Unknown flag reason: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Rules deciding when a review is held for an admin instead of going live.
+#[derive(Debug, Clone)]
+pub struct ModerationPolicy {
+    blocklist: Vec<String>,
+    flag_threshold: i64,
+}
+
+impl ModerationPolicy {
+    pub fn new(blocklist: &[String], flag_threshold: i64) -> Self {
+        Self {
+            blocklist: blocklist
+                .iter()
+                .map(|term| normalize(term))
+                .filter(|term| !term.trim().is_empty())
+                .collect(),
+            flag_threshold: flag_threshold.max(1),
+        }
+    }
+
+    /// First blocklisted term found in `text`, matched case-insensitively on
+    /// whole words so that e.g. "ass" does not match "class".
+    pub fn blocklist_match(&self, text: &str) -> Option<&str> {
+        let text = normalize(text);
+        self.blocklist
+            .iter()
+            .find(|term| text.contains(term.as_str()))
+            .map(|term| term.trim())
+    }
+
+    pub fn flag_threshold(&self) -> i64 {
+        self.flag_threshold
+    }
+}
+
+/// Lowercases and collapses anything that is not a letter or digit into
+/// single spaces, padded on both ends so terms can be matched as " word ".
+fn normalize(text: &str) -> String {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+        .collect();
+    format!(" {} ", words.join(" "))
+}
@@ -0,0 +1,501 @@
+use super::entities::Tariff;
+use crate::core::errors::{AppError, AppResult};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::str::FromStr;
+
+const WH_PER_KWH: f64 = 1000.0;
+const SECONDS_PER_HOUR: f64 = 3600.0;
+
+/// Absorbs floating point noise (e.g. 12.3 kWh -> 12300.000000000002 Wh)
+/// before rounding up to a step or to the currency's minor unit.
+const EPSILON: f64 = 1e-7;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PriceComponentType {
+    Energy,
+    Time,
+    ParkingTime,
+    Flat,
+}
+
+impl PriceComponentType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Energy => "ENERGY",
+            Self::Time => "TIME",
+            Self::ParkingTime => "PARKING_TIME",
+            Self::Flat => "FLAT",
+        }
+    }
+
+    pub fn unit(&self) -> &'static str {
+        match self {
+            Self::Energy => "kWh",
+            Self::Time | Self::ParkingTime => "h",
+            Self::Flat => "session",
+        }
+    }
+}
+
+impl FromStr for PriceComponentType {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ENERGY" => Ok(Self::Energy),
+            "TIME" => Ok(Self::Time),
+            "PARKING_TIME" => Ok(Self::ParkingTime),
+            "FLAT" => Ok(Self::Flat),
+            other => Err(AppError::ValidationError(format!(
+                "Unknown tariff component type: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// What happened (or is expected to happen) during a charging session.
+/// Idle minutes are the part of the session spent plugged in without charging.
+#[derive(Debug, Clone)]
+pub struct SessionProfile {
+    pub start: DateTime<Utc>,
+    pub stop: DateTime<Utc>,
+    pub energy_kwh: f64,
+    pub idle_minutes: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CostLine {
+    pub component_type: PriceComponentType,
+    pub quantity: f64,
+    pub unit: String,
+    pub unit_price: f64,
+    pub amount: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CostBreakdown {
+    pub tariff_id: String,
+    pub currency: String,
+    pub lines: Vec<CostLine>,
+    pub subtotal_excl_vat: f64,
+    /// Positive when `min_price` lifted the total, negative when `max_price` capped it.
+    pub cap_adjustment: f64,
+    pub total_excl_vat: f64,
+    pub vat_percent: f64,
+    pub vat_amount: f64,
+    pub total_incl_vat: f64,
+}
+
+/// Prices a session against a tariff.
+///
+/// Quantities are rounded up to the component's step size (Wh for energy,
+/// seconds for time based components) before pricing, each line is rounded
+/// to the currency's minor unit, price caps apply to the total excluding VAT,
+/// and VAT is computed on the capped total.
+///
+/// Only the station cost estimate uses it for now. Charging sessions are not
+/// recorded yet, so there is no final amount to bill; session billing is left
+/// to a follow-up and should price the recorded session with this function.
+pub fn calculate_cost(tariff: &Tariff, session: &SessionProfile) -> AppResult<CostBreakdown> {
+    validate_tariff(tariff)?;
+
+    let total_seconds = (session.stop - session.start).num_seconds() as f64;
+    if total_seconds < 0.0 {
+        return Err(AppError::ValidationError(
+            "Session stop time must not be before its start time".to_string(),
+        ));
+    }
+    if !session.energy_kwh.is_finite() || session.energy_kwh < 0.0 {
+        return Err(AppError::ValidationError(
+            "Delivered energy must be a non-negative number".to_string(),
+        ));
+    }
+    if !session.idle_minutes.is_finite() || session.idle_minutes < 0.0 {
+        return Err(AppError::ValidationError(
+            "Idle minutes must be a non-negative number".to_string(),
+        ));
+    }
+
+    // Sessions are metered to the second
+    let idle_seconds = (session.idle_minutes * 60.0).round();
+    if idle_seconds > total_seconds {
+        return Err(AppError::ValidationError(
+            "Idle time cannot exceed the session duration".to_string(),
+        ));
+    }
+    let charging_seconds = total_seconds - idle_seconds;
+
+    let decimals = currency_decimals(&tariff.currency);
+    let mut lines = Vec::with_capacity(tariff.components.len());
+
+    for component in &tariff.components {
+        let component_type = PriceComponentType::from_str(&component.component_type)?;
+        let step = component.step_size as f64;
+
+        let quantity = match component_type {
+            PriceComponentType::Energy => {
+                round_up_to_step(session.energy_kwh * WH_PER_KWH, step) / WH_PER_KWH
+            }
+            PriceComponentType::Time => round_up_to_step(charging_seconds, step) / SECONDS_PER_HOUR,
+            PriceComponentType::ParkingTime => {
+                round_up_to_step(idle_seconds, step) / SECONDS_PER_HOUR
+            }
+            PriceComponentType::Flat => 1.0,
+        };
+
+        if quantity <= 0.0 {
+            continue;
+        }
+
+        lines.push(CostLine {
+            component_type,
+            quantity,
+            unit: component_type.unit().to_string(),
+            unit_price: component.price,
+            amount: round_to_currency(quantity * component.price, decimals),
+        });
+    }
+
+    let subtotal = round_to_currency(lines.iter().map(|l| l.amount).sum::<f64>(), decimals);
+    let total_excl_vat = apply_price_caps(subtotal, tariff.min_price, tariff.max_price);
+    let vat_amount = round_to_currency(total_excl_vat * tariff.vat_percent / 100.0, decimals);
+
+    Ok(CostBreakdown {
+        tariff_id: tariff.tariff_id.clone(),
+        currency: tariff.currency.clone(),
+        lines,
+        subtotal_excl_vat: subtotal,
+        cap_adjustment: round_to_currency(total_excl_vat - subtotal, decimals),
+        total_excl_vat,
+        vat_percent: tariff.vat_percent,
+        vat_amount,
+        total_incl_vat: round_to_currency(total_excl_vat + vat_amount, decimals),
+    })
+}
+
+fn validate_tariff(tariff: &Tariff) -> AppResult<()> {
+    if !tariff.vat_percent.is_finite() || tariff.vat_percent < 0.0 {
+        return Err(AppError::ValidationError(
+            "Tariff VAT percentage must be a non-negative number".to_string(),
+        ));
+    }
+    if [tariff.min_price, tariff.max_price]
+        .into_iter()
+        .flatten()
+        .any(|p| !p.is_finite() || p < 0.0)
+    {
+        return Err(AppError::ValidationError(
+            "Tariff price caps must be non-negative numbers".to_string(),
+        ));
+    }
+    if let (Some(min), Some(max)) = (tariff.min_price, tariff.max_price)
+        && min > max
+    {
+        return Err(AppError::ValidationError(
+            "Tariff min_price cannot exceed max_price".to_string(),
+        ));
+    }
+    if tariff
+        .components
+        .iter()
+        .any(|c| !c.price.is_finite() || c.price < 0.0 || c.step_size < 1)
+    {
+        return Err(AppError::ValidationError(
+            "Tariff components need a non-negative price and a step size of at least 1".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn apply_price_caps(amount: f64, min_price: Option<f64>, max_price: Option<f64>) -> f64 {
+    let mut capped = amount;
+    if let Some(min) = min_price {
+        capped = capped.max(min);
+    }
+    if let Some(max) = max_price {
+        capped = capped.min(max);
+    }
+    capped
+}
+
+fn round_up_to_step(value: f64, step: f64) -> f64 {
+    if value <= 0.0 {
+        return 0.0;
+    }
+    (value / step - EPSILON).ceil() * step
+}
+
+/// Rounds half up to the currency's minor unit. Amounts are never negative.
+fn round_to_currency(amount: f64, decimals: i32) -> f64 {
+    let factor = 10f64.powi(decimals);
+    (amount * factor + EPSILON).round() / factor
+}
+
+/// ISO 4217 minor units for the currencies we are likely to bill in.
+pub fn currency_decimals(currency: &str) -> i32 {
+    match currency.to_ascii_uppercase().as_str() {
+        "TND" | "LYD" | "KWD" | "BHD" | "OMR" | "JOD" | "IQD" => 3,
+        "JPY" | "KRW" | "XOF" | "XAF" => 0,
+        _ => 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::TariffComponent;
+    use chrono::{Duration, TimeZone};
+
+    fn component(component_type: &str, price: f64, step_size: i32) -> TariffComponent {
+        TariffComponent {
+            component_type: component_type.to_string(),
+            price,
+            step_size,
+        }
+    }
+
+    fn tariff(currency: &str, components: Vec<TariffComponent>) -> Tariff {
+        Tariff {
+            tariff_id: "TAR-1".to_string(),
+            network_id: "NET-1".to_string(),
+            name: "Test tariff".to_string(),
+            currency: currency.to_string(),
+            vat_percent: 0.0,
+            min_price: None,
+            max_price: None,
+            components,
+        }
+    }
+
+    fn session(minutes: i64, energy_kwh: f64, idle_minutes: f64) -> SessionProfile {
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 10, 0, 0).unwrap();
+        SessionProfile {
+            start,
+            stop: start + Duration::minutes(minutes),
+            energy_kwh,
+            idle_minutes,
+        }
+    }
+
+    fn line(breakdown: &CostBreakdown, component_type: PriceComponentType) -> &CostLine {
+        breakdown
+            .lines
+            .iter()
+            .find(|l| l.component_type == component_type)
+            .expect("line is present")
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn rounds_energy_up_to_the_step_in_wh() {
+        let t = tariff("EUR", vec![component("ENERGY", 0.5, 100)]);
+
+        let cost = calculate_cost(&t, &session(60, 12.301, 0.0)).unwrap();
+        assert_close(line(&cost, PriceComponentType::Energy).quantity, 12.4);
+
+        // Exact multiples are not pushed to the next step by float noise
+        let cost = calculate_cost(&t, &session(60, 12.3, 0.0)).unwrap();
+        assert_close(line(&cost, PriceComponentType::Energy).quantity, 12.3);
+    }
+
+    #[test]
+    fn rounds_time_up_to_the_step_in_seconds() {
+        let t = tariff(
+            "EUR",
+            vec![
+                component("TIME", 6.0, 900),
+                component("PARKING_TIME", 3.0, 60),
+            ],
+        );
+
+        // 61 minutes total, 0.5 idle minutes = 30s idle, 3630s charging
+        let cost = calculate_cost(&t, &session(61, 10.0, 0.5)).unwrap();
+        assert_close(line(&cost, PriceComponentType::Time).quantity, 1.25);
+        assert_close(
+            line(&cost, PriceComponentType::ParkingTime).quantity,
+            60.0 / 3600.0,
+        );
+    }
+
+    #[test]
+    fn rounds_each_line_to_the_currency_minor_unit() {
+        let components = vec![component("ENERGY", 0.3333, 1), component("TIME", 1.0, 1)];
+        let s = session(10, 10.0, 0.0);
+
+        let eur = calculate_cost(&tariff("EUR", components.clone()), &s).unwrap();
+        assert_close(line(&eur, PriceComponentType::Energy).amount, 3.33);
+        assert_close(line(&eur, PriceComponentType::Time).amount, 0.17);
+        assert_close(eur.subtotal_excl_vat, 3.5);
+
+        let tnd = calculate_cost(&tariff("TND", components.clone()), &s).unwrap();
+        assert_close(line(&tnd, PriceComponentType::Energy).amount, 3.333);
+        assert_close(line(&tnd, PriceComponentType::Time).amount, 0.167);
+        assert_close(tnd.subtotal_excl_vat, 3.5);
+
+        let jpy = calculate_cost(&tariff("JPY", components), &s).unwrap();
+        assert_close(line(&jpy, PriceComponentType::Energy).amount, 3.0);
+        assert_close(line(&jpy, PriceComponentType::Time).amount, 0.0);
+        assert_close(jpy.subtotal_excl_vat, 3.0);
+    }
+
+    #[test]
+    fn rounds_half_up() {
+        let t = tariff("EUR", vec![component("ENERGY", 0.125, 1)]);
+        let cost = calculate_cost(&t, &session(10, 1.0, 0.0)).unwrap();
+        assert_close(cost.total_excl_vat, 0.13);
+    }
+
+    #[test]
+    fn min_price_lifts_the_total() {
+        let mut t = tariff("EUR", vec![component("ENERGY", 0.5, 1)]);
+        t.min_price = Some(5.0);
+
+        let cost = calculate_cost(&t, &session(30, 4.0, 0.0)).unwrap();
+        assert_close(cost.subtotal_excl_vat, 2.0);
+        assert_close(cost.total_excl_vat, 5.0);
+        assert_close(cost.cap_adjustment, 3.0);
+    }
+
+    #[test]
+    fn max_price_caps_the_total() {
+        let mut t = tariff("EUR", vec![component("ENERGY", 0.5, 1)]);
+        t.max_price = Some(10.0);
+
+        let cost = calculate_cost(&t, &session(30, 40.0, 0.0)).unwrap();
+        assert_close(cost.subtotal_excl_vat, 20.0);
+        assert_close(cost.total_excl_vat, 10.0);
+        assert_close(cost.cap_adjustment, -10.0);
+    }
+
+    #[test]
+    fn totals_within_the_caps_are_untouched() {
+        let mut t = tariff("EUR", vec![component("ENERGY", 0.5, 1)]);
+        t.min_price = Some(1.0);
+        t.max_price = Some(100.0);
+
+        let cost = calculate_cost(&t, &session(30, 10.0, 0.0)).unwrap();
+        assert_close(cost.total_excl_vat, 5.0);
+        assert_close(cost.cap_adjustment, 0.0);
+    }
+
+    #[test]
+    fn applies_vat_to_the_capped_total() {
+        let mut t = tariff("EUR", vec![component("ENERGY", 0.5, 1)]);
+        t.vat_percent = 19.0;
+        t.max_price = Some(10.0);
+
+        let cost = calculate_cost(&t, &session(30, 40.0, 0.0)).unwrap();
+        assert_close(cost.vat_amount, 1.9);
+        assert_close(cost.total_incl_vat, 11.9);
+    }
+
+    #[test]
+    fn rejects_idle_time_longer_than_the_session() {
+        let t = tariff("EUR", vec![component("PARKING_TIME", 1.0, 60)]);
+        let err = calculate_cost(&t, &session(30, 0.0, 31.0)).unwrap_err();
+        assert!(matches!(err, AppError::ValidationError(_)));
+    }
+
+    #[test]
+    fn prices_a_flat_only_tariff() {
+        let t = tariff("EUR", vec![component("FLAT", 2.5, 1)]);
+
+        let cost = calculate_cost(&t, &session(0, 0.0, 0.0)).unwrap();
+        assert_eq!(cost.lines.len(), 1);
+        assert_close(line(&cost, PriceComponentType::Flat).quantity, 1.0);
+        assert_close(cost.total_excl_vat, 2.5);
+    }
+
+    #[test]
+    fn skips_zero_quantity_lines() {
+        let t = tariff(
+            "EUR",
+            vec![
+                component("ENERGY", 0.5, 1),
+                component("TIME", 1.0, 60),
+                component("PARKING_TIME", 2.0, 60),
+            ],
+        );
+
+        let cost = calculate_cost(&t, &session(30, 0.0, 0.0)).unwrap();
+        let types: Vec<_> = cost.lines.iter().map(|l| l.component_type).collect();
+        assert_eq!(types, vec![PriceComponentType::Time]);
+    }
+
+    #[test]
+    fn rejects_negative_and_nan_session_inputs() {
+        let t = tariff("EUR", vec![component("ENERGY", 0.5, 1)]);
+
+        for s in [
+            session(30, -1.0, 0.0),
+            session(30, f64::NAN, 0.0),
+            session(30, f64::INFINITY, 0.0),
+            session(30, 1.0, -1.0),
+            session(30, 1.0, f64::NAN),
+            session(-30, 1.0, 0.0),
+        ] {
+            let err = calculate_cost(&t, &s).unwrap_err();
+            assert!(matches!(err, AppError::ValidationError(_)), "{:?}", s);
+        }
+    }
+
+    #[test]
+    fn rejects_negative_and_nan_tariff_inputs() {
+        let s = session(30, 1.0, 0.0);
+        let valid = tariff("EUR", vec![component("ENERGY", 0.5, 1)]);
+        let with = |f: fn(&mut Tariff)| {
+            let mut t = valid.clone();
+            f(&mut t);
+            t
+        };
+
+        let invalid = [
+            tariff("EUR", vec![component("ENERGY", -0.5, 1)]),
+            tariff("EUR", vec![component("ENERGY", f64::NAN, 1)]),
+            tariff("EUR", vec![component("ENERGY", 0.5, 0)]),
+            tariff("EUR", vec![component("SOMETHING", 0.5, 1)]),
+            with(|t| t.vat_percent = -1.0),
+            with(|t| t.vat_percent = f64::NAN),
+            with(|t| t.min_price = Some(-1.0)),
+            with(|t| t.max_price = Some(f64::NAN)),
+            with(|t| {
+                t.min_price = Some(10.0);
+                t.max_price = Some(5.0);
+            }),
+        ];
+
+        for t in invalid {
+            let err = calculate_cost(&t, &s).unwrap_err();
+            assert!(matches!(err, AppError::ValidationError(_)), "{:?}", t);
+        }
+    }
+
+    #[test]
+    fn round_up_to_step_handles_edges() {
+        assert_close(round_up_to_step(0.0, 100.0), 0.0);
+        assert_close(round_up_to_step(-5.0, 100.0), 0.0);
+        assert_close(round_up_to_step(1.0, 100.0), 100.0);
+        assert_close(round_up_to_step(100.0, 100.0), 100.0);
+        assert_close(round_up_to_step(12300.000000000002, 1.0), 12300.0);
+    }
+
+    #[test]
+    fn currency_decimals_follow_iso_4217() {
+        assert_eq!(currency_decimals("TND"), 3);
+        assert_eq!(currency_decimals("tnd"), 3);
+        assert_eq!(currency_decimals("JPY"), 0);
+        assert_eq!(currency_decimals("EUR"), 2);
+        assert_eq!(currency_decimals("XYZ"), 2);
+    }
+}
@@ -0,0 +1,216 @@
+use super::entities::Station;
+use crate::core::errors::AppError;
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+/// Charging power at which the power factor saturates; anything from this
+/// rating up counts as equally fast.
+const REFERENCE_POWER_KW: f64 = 150.0;
+
+/// Prior mean used when no station has been reviewed yet (midpoint of 1-5).
+const FALLBACK_PRIOR_RATING: f64 = 3.0;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StationSort {
+    #[default]
+    Relevance,
+    Distance,
+    Power,
+    Rating,
+}
+
+impl FromStr for StationSort {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "relevance" => Ok(Self::Relevance),
+            "distance" => Ok(Self::Distance),
+            "power" => Ok(Self::Power),
+            "rating" => Ok(Self::Rating),
+            other => Err(AppError::ValidationError(format!(
+                "Unknown sort order: {}, expected relevance, distance, power or rating",
+                other
+            ))),
+        }
+    }
+}
+
+/// Relative importance of each factor in the relevance score. Weights need
+/// not sum to one; the score is normalised by the weights that apply.
+#[derive(Debug, Clone, Copy)]
+pub struct RankingWeights {
+    pub distance: f64,
+    pub availability: f64,
+    pub power: f64,
+    pub rating: f64,
+    pub price: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct RankingPolicy {
+    weights: RankingWeights,
+    rating_prior_weight: f64,
+}
+
+impl RankingPolicy {
+    pub fn new(weights: RankingWeights, rating_prior_weight: f64) -> Self {
+        Self {
+            weights: RankingWeights {
+                distance: weights.distance.max(0.0),
+                availability: weights.availability.max(0.0),
+                power: weights.power.max(0.0),
+                rating: weights.rating.max(0.0),
+                price: weights.price.max(0.0),
+            },
+            rating_prior_weight: rating_prior_weight.max(0.0),
+        }
+    }
+
+    /// Average rating pulled towards `prior_mean` as if the station had
+    /// `rating_prior_weight` extra reviews at that mean, so that a single
+    /// 5-star review does not outrank hundreds of 4.8 averages.
+    pub fn bayesian_rating(
+        &self,
+        average_rating: Option<f64>,
+        review_count: i64,
+        prior_mean: Option<f64>,
+    ) -> f64 {
+        let prior_mean = prior_mean.unwrap_or(FALLBACK_PRIOR_RATING);
+        let n = review_count.max(0) as f64;
+        let total = self.rating_prior_weight + n;
+        if total == 0.0 {
+            return prior_mean;
+        }
+        (self.rating_prior_weight * prior_mean + average_rating.unwrap_or(prior_mean) * n) / total
+    }
+
+    /// Sets the station's `bayesian_rating` and returns it unrounded
+    pub fn rate(&self, station: &mut Station, prior_mean: Option<f64>) -> f64 {
+        let bayesian =
+            self.bayesian_rating(station.average_rating, station.review_count, prior_mean);
+        station.bayesian_rating = Some(round2(bayesian));
+        bayesian
+    }
+
+    /// Fills in `bayesian_rating` and `score` on every station, then orders
+    /// them according to `sort`. Ties are broken by distance.
+    pub fn rank(
+        &self,
+        stations: &mut [Station],
+        sort: StationSort,
+        radius_meters: i32,
+        prior_mean: Option<f64>,
+    ) {
+        let prices: Vec<f64> = stations
+            .iter()
+            .filter_map(|s| s.energy_price_per_kwh)
+            .collect();
+        let cheapest = prices.iter().copied().fold(f64::INFINITY, f64::min);
+        let dearest = prices.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+        for station in stations.iter_mut() {
+            let bayesian = self.rate(station, prior_mean);
+
+            let mut factors = vec![
+                (
+                    self.weights.distance,
+                    distance_factor(station, radius_meters),
+                ),
+                (self.weights.availability, availability_factor(station)),
+                (self.weights.power, power_factor(station)),
+                (self.weights.rating, (bayesian - 1.0) / 4.0),
+            ];
+            // Stations without a published price are ranked on the other factors only
+            if let Some(price) = station.energy_price_per_kwh {
+                let price_factor = if dearest > cheapest {
+                    (dearest - price) / (dearest - cheapest)
+                } else {
+                    1.0
+                };
+                factors.push((self.weights.price, price_factor));
+            }
+
+            let total_weight: f64 = factors.iter().map(|(w, _)| w).sum();
+            let score = if total_weight > 0.0 {
+                factors.iter().map(|(w, f)| w * f).sum::<f64>() / total_weight
+            } else {
+                0.0
+            };
+            station.score = Some((score * 1000.0).round() / 1000.0);
+        }
+
+        stations.sort_by(|a, b| {
+            let primary = match sort {
+                StationSort::Relevance => desc(a.score, b.score),
+                StationSort::Distance => Ordering::Equal,
+                StationSort::Power => desc(station_power_kw(a), station_power_kw(b)),
+                StationSort::Rating => desc(a.bayesian_rating, b.bayesian_rating)
+                    .then(b.review_count.cmp(&a.review_count)),
+            };
+            primary.then(asc(a.distance_meters, b.distance_meters))
+        });
+    }
+}
+
+/// 1 at the search point, 0 at the edge of the search radius
+fn distance_factor(station: &Station, radius_meters: i32) -> f64 {
+    match station.distance_meters {
+        Some(distance) if radius_meters > 0 => {
+            (1.0 - distance / f64::from(radius_meters)).clamp(0.0, 1.0)
+        }
+        _ => 0.0,
+    }
+}
+
+/// Telemetry blended with driver check-ins in proportion to how much the
+/// check-ins can be trusted; most stations have no live telemetry at all.
+fn availability_factor(station: &Station) -> f64 {
+    let telemetry = if station.has_available_connectors.unwrap_or(false) {
+        1.0
+    } else {
+        0.0
+    };
+    match (
+        station.reported_availability,
+        station.availability_confidence,
+    ) {
+        (Some(reported), Some(confidence)) => {
+            confidence * reported + (1.0 - confidence) * telemetry
+        }
+        _ => telemetry,
+    }
+}
+
+fn power_factor(station: &Station) -> f64 {
+    station_power_kw(station).map_or(0.0, |kw| (kw / REFERENCE_POWER_KW).clamp(0.0, 1.0))
+}
+
+/// Power the searched vehicle can draw when known, otherwise the station's best
+fn station_power_kw(station: &Station) -> Option<f64> {
+    station.effective_power_kw.or(station.max_power_kw)
+}
+
+/// Known values first, highest first
+fn desc(a: Option<f64>, b: Option<f64>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => b.total_cmp(&a),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// Known values first, lowest first
+fn asc(a: Option<f64>, b: Option<f64>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.total_cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
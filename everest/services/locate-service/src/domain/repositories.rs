@@ -1,21 +1,79 @@
-use super::entities::{Station, UserReview};
+use super::entities::{
+    Amenity, Connector, IssueReport, Media, NetworkVerificationRequest, Notification, ReviewFlag,
+    ReviewReply, ReviewSummary, Station, StationCheckin, Tariff, UserReview, Vehicle, VehicleModel,
+    VerificationDocument, VerificationEvent,
+};
+use super::value_objects::ReviewListFilter;
 use crate::core::errors::AppResult;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait AmenityRepository: Send + Sync {
+    /// Amenities of the given categories within `radius_meters` of the
+    /// station, closest first
+    async fn find_near_station(
+        &self,
+        station_id: &str,
+        radius_meters: i32,
+        categories: &[String],
+        limit: i64,
+    ) -> AppResult<Vec<Amenity>>;
+
+    /// The stations among `station_ids` with at least one amenity of every
+    /// one of `categories` within `radius_meters`
+    async fn find_stations_with_all(
+        &self,
+        station_ids: &[String],
+        radius_meters: i32,
+        categories: &[String],
+    ) -> AppResult<Vec<String>>;
+}
+
+#[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait StationRepository: Send + Sync {
-    async fn find_nearby(
+    async fn find_nearby<'a>(
         &self,
         latitude: f64,
         longitude: f64,
         radius_meters: i32,
         limit: i32,
+        region_id: Option<&'a str>,
     ) -> AppResult<Vec<Station>>;
+
+    #[allow(clippy::too_many_arguments)]
+    async fn find_nearby_compatible<'a>(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        radius_meters: i32,
+        limit: i32,
+        connector_types: &[String],
+        max_ac_kw: Option<f64>,
+        max_dc_kw: Option<f64>,
+        region_id: Option<&'a str>,
+    ) -> AppResult<Vec<Station>>;
+
+    async fn find_by_id(&self, station_id: &str) -> AppResult<Option<Station>>;
+    async fn find_connectors(&self, station_id: &str) -> AppResult<Vec<Connector>>;
+    async fn find_connector(&self, connector_id: &str) -> AppResult<Option<Connector>>;
+    /// Takes the connector out of service: status `faulty`, nothing available
+    async fn mark_connector_faulty(&self, connector_id: &str, updated_by: &str) -> AppResult<()>;
+    async fn exists(&self, station_id: &str) -> AppResult<bool>;
+    async fn find_network_id(&self, station_id: &str) -> AppResult<Option<String>>;
+
+    /// Mean rating over all reviews of all stations, None before the first review
+    async fn average_rating(&self) -> AppResult<Option<f64>>;
 }
 
+#[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait ReviewRepository: Send + Sync {
-    async fn create(
+    /// Creates the review, or replaces the user's existing review of the station.
+    /// The flag is true when a new review was inserted.
+    async fn upsert(
         &self,
         review_id: String,
         user_id: String,
@@ -23,9 +81,21 @@ pub trait ReviewRepository: Send + Sync {
         rating: i32,
         review_text: Option<String>,
         created_by: String,
-    ) -> AppResult<UserReview>;
+    ) -> AppResult<(UserReview, bool)>;
 
-    async fn find_by_station(&self, station_id: &str) -> AppResult<Vec<UserReview>>;
+    /// Visible reviews of the station, one page at a time
+    async fn find_by_station(
+        &self,
+        station_id: &str,
+        filter: &ReviewListFilter,
+    ) -> AppResult<Vec<UserReview>>;
+    async fn count_by_station(&self, station_id: &str, rating: Option<i32>) -> AppResult<i64>;
+    async fn summarize_station(&self, station_id: &str) -> AppResult<ReviewSummary>;
+
+    /// Returns false when the user had already voted
+    async fn add_helpful_vote(&self, review_id: &str, user_id: &str) -> AppResult<bool>;
+    async fn remove_helpful_vote(&self, review_id: &str, user_id: &str) -> AppResult<()>;
+    async fn count_helpful_votes(&self, review_id: &str) -> AppResult<i64>;
     async fn find_by_id(&self, review_id: &str) -> AppResult<Option<UserReview>>;
 
     async fn update(
@@ -37,4 +107,148 @@ pub trait ReviewRepository: Send + Sync {
     ) -> AppResult<UserReview>;
 
     async fn delete(&self, review_id: &str) -> AppResult<()>;
+
+    async fn set_moderation(
+        &self,
+        review_id: &str,
+        status: &str,
+        reason: Option<String>,
+        moderated_by: Option<String>,
+    ) -> AppResult<UserReview>;
+
+    async fn create_flag(&self, flag: &ReviewFlag) -> AppResult<ReviewFlag>;
+    async fn count_open_flags(&self, review_id: &str) -> AppResult<i64>;
+
+    async fn create_reply(&self, reply: &ReviewReply) -> AppResult<ReviewReply>;
+    async fn find_reply(&self, reply_id: &str) -> AppResult<Option<ReviewReply>>;
+    async fn find_replies_for_reviews(&self, review_ids: &[String]) -> AppResult<Vec<ReviewReply>>;
+    async fn update_reply(
+        &self,
+        reply_id: &str,
+        reply_text: &str,
+        updated_by: &str,
+    ) -> AppResult<ReviewReply>;
+    async fn delete_reply(&self, reply_id: &str) -> AppResult<()>;
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait NotificationRepository: Send + Sync {
+    async fn create(&self, notification: &Notification) -> AppResult<Notification>;
+    async fn find_by_user(&self, user_id: &str, unread_only: bool) -> AppResult<Vec<Notification>>;
+    async fn mark_read(&self, notification_id: &str, user_id: &str) -> AppResult<Notification>;
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait CheckinRepository: Send + Sync {
+    async fn create(&self, checkin: &StationCheckin) -> AppResult<StationCheckin>;
+    async fn find_latest_by_user(
+        &self,
+        user_id: &str,
+        station_id: &str,
+    ) -> AppResult<Option<StationCheckin>>;
+
+    /// Check-ins of any of the stations made after `since`, newest first
+    async fn find_recent(
+        &self,
+        station_ids: &[String],
+        since: DateTime<Utc>,
+    ) -> AppResult<Vec<StationCheckin>>;
+
+    /// The latest `charged_ok` check-in of each station that has one
+    async fn find_last_successful(&self, station_ids: &[String]) -> AppResult<Vec<StationCheckin>>;
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait MediaRepository: Send + Sync {
+    async fn create(&self, media: &Media) -> AppResult<Media>;
+    async fn find_by_id(&self, media_id: &str) -> AppResult<Option<Media>>;
+
+    /// Photos of the station and of its visible reviews, newest first
+    async fn find_by_station(&self, station_id: &str) -> AppResult<Vec<Media>>;
+    async fn count_by_review(&self, review_id: &str) -> AppResult<i64>;
+    async fn delete(&self, media_id: &str) -> AppResult<()>;
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait IssueRepository: Send + Sync {
+    async fn create(&self, issue: &IssueReport) -> AppResult<IssueReport>;
+    async fn find_by_id(&self, issue_id: &str) -> AppResult<Option<IssueReport>>;
+    async fn find_by_reporter(&self, reporter_id: &str) -> AppResult<Vec<IssueReport>>;
+
+    /// Newest first; all networks when `network_id` is None
+    async fn find_by_network<'a>(
+        &self,
+        network_id: Option<&'a str>,
+        status: Option<&'a str>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<IssueReport>>;
+
+    /// Records who moved the ticket into `status` and when
+    async fn update_status(
+        &self,
+        issue_id: &str,
+        status: &str,
+        resolution_note: Option<String>,
+        updated_by: &str,
+    ) -> AppResult<IssueReport>;
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait NetworkVerificationRepository: Send + Sync {
+    /// Records the request along with its submission event
+    async fn create(
+        &self,
+        request: &NetworkVerificationRequest,
+    ) -> AppResult<NetworkVerificationRequest>;
+    async fn find_by_id(&self, request_id: &str) -> AppResult<Option<NetworkVerificationRequest>>;
+    async fn find_all<'a>(
+        &self,
+        network_id: &str,
+        status: Option<&'a str>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<NetworkVerificationRequest>>;
+
+    /// `is_verified` of the network, None when it does not exist
+    async fn find_network_verified(&self, network_id: &str) -> AppResult<Option<bool>>;
+
+    async fn add_document(
+        &self,
+        document: &VerificationDocument,
+    ) -> AppResult<VerificationDocument>;
+    async fn find_documents(&self, request_id: &str) -> AppResult<Vec<VerificationDocument>>;
+    async fn find_document(
+        &self,
+        request_id: &str,
+        document_id: &str,
+    ) -> AppResult<Option<VerificationDocument>>;
+    async fn count_documents(&self, request_id: &str) -> AppResult<i64>;
+
+    /// Oldest first
+    async fn find_events_by_request(&self, request_id: &str) -> AppResult<Vec<VerificationEvent>>;
+    async fn find_events_by_network(&self, network_id: &str) -> AppResult<Vec<VerificationEvent>>;
+}
+
+#[async_trait]
+pub trait TariffRepository: Send + Sync {
+    async fn find_active_by_station(&self, station_id: &str) -> AppResult<Option<Tariff>>;
+}
+
+#[async_trait]
+pub trait VehicleRepository: Send + Sync {
+    async fn list_models(&self) -> AppResult<Vec<VehicleModel>>;
+    async fn find_model(&self, model_id: i32) -> AppResult<Option<VehicleModel>>;
+    async fn list_connector_types(&self) -> AppResult<Vec<String>>;
+
+    async fn create(&self, vehicle: &Vehicle) -> AppResult<Vehicle>;
+    async fn find_by_id(&self, vehicle_id: &str) -> AppResult<Option<Vehicle>>;
+    async fn find_by_user(&self, user_id: &str) -> AppResult<Vec<Vehicle>>;
+    async fn update(&self, vehicle: &Vehicle) -> AppResult<Vehicle>;
+    async fn delete(&self, vehicle_id: &str) -> AppResult<()>;
 }
@@ -1,20 +1,63 @@
-use super::entities::{Station, UserReview};
+use super::amenities::AmenityCategory;
+use super::charging::ChargeEstimate;
+use super::checkins::StationAvailability;
+use super::entities::{
+    Amenity, IssueReport, Media, NetworkVerificationDetail, NetworkVerificationRequest,
+    Notification, ReviewFlag, ReviewReply, Station, StationCheckin, StationDetail,
+    StationReviewPage, Tariff, UserReview, Vehicle, VehicleModel, VerificationDocument,
+    VerificationEvent,
+};
+use super::pricing::CostBreakdown;
+use super::ranking::StationSort;
+use super::value_objects::{
+    CreateVehicleData, ReviewListFilter, SubmitVerificationData, UpdateVehicleData,
+};
 use crate::core::errors::AppResult;
 use async_trait::async_trait;
 
 #[async_trait]
 pub trait StationService: Send + Sync {
+    /// With `amenities`, only stations with each of them within walking
+    /// distance; with `region_id`, only stations of that region
+    #[allow(clippy::too_many_arguments)]
     async fn find_nearby_stations(
         &self,
         latitude: f64,
         longitude: f64,
         radius_meters: Option<i32>,
         limit: Option<i32>,
+        vehicle: Option<Vehicle>,
+        sort: StationSort,
+        amenities: Vec<AmenityCategory>,
+        region_id: Option<&str>,
     ) -> AppResult<Vec<Station>>;
+
+    /// The station with its connectors and what drivers recently reported
+    async fn get_station_detail(&self, station_id: &str) -> AppResult<StationDetail>;
+
+    /// Amenities around the station, closest first. Every category when
+    /// `categories` is empty; walking distance when no radius is given.
+    async fn list_station_amenities(
+        &self,
+        station_id: &str,
+        categories: Vec<AmenityCategory>,
+        radius_meters: Option<i32>,
+    ) -> AppResult<Vec<Amenity>>;
+
+    /// One estimate per connector of the station the vehicle can plug into, fastest first
+    async fn estimate_charging_time(
+        &self,
+        station_id: &str,
+        vehicle: &Vehicle,
+        from_soc: f64,
+        to_soc: f64,
+    ) -> AppResult<Vec<ChargeEstimate>>;
 }
 
 #[async_trait]
 pub trait ReviewService: Send + Sync {
+    /// A user has at most one review per station; posting again replaces it.
+    /// The flag is true when a new review was created.
     async fn create_review(
         &self,
         user_id: String,
@@ -22,17 +65,259 @@ pub trait ReviewService: Send + Sync {
         rating: i32,
         review_text: Option<String>,
         created_by: String,
-    ) -> AppResult<UserReview>;
+    ) -> AppResult<(UserReview, bool)>;
+
+    /// One page of visible reviews of the station, each with the network's
+    /// reply if any, plus the rating summary of all visible reviews
+    async fn get_reviews_by_station(
+        &self,
+        station_id: &str,
+        filter: ReviewListFilter,
+    ) -> AppResult<StationReviewPage>;
 
-    async fn get_reviews_by_station(&self, station_id: &str) -> AppResult<Vec<UserReview>>;
+    /// One helpful vote per user; voting again is a no-op. Returns the new vote count.
+    async fn vote_helpful(&self, review_id: &str, user_id: &str) -> AppResult<i64>;
+    async fn unvote_helpful(&self, review_id: &str, user_id: &str) -> AppResult<i64>;
 
+    /// Only the author of the review, or an admin, may update it
     async fn update_review(
         &self,
         review_id: &str,
         rating: Option<i32>,
         review_text: Option<String>,
         updated_by: String,
+        is_admin: bool,
     ) -> AppResult<UserReview>;
 
-    async fn delete_review(&self, review_id: &str) -> AppResult<()>;
+    /// Only the author of the review, or an admin, may delete it
+    async fn delete_review(&self, review_id: &str, user_id: &str, is_admin: bool) -> AppResult<()>;
+
+    async fn flag_review(
+        &self,
+        review_id: &str,
+        user_id: String,
+        reason: &str,
+        comment: Option<String>,
+    ) -> AppResult<ReviewFlag>;
+
+    /// Reply on behalf of `network_id`, which must operate the reviewed station
+    /// and be verified. The reviewer is notified.
+    async fn reply_to_review(
+        &self,
+        review_id: &str,
+        author_id: String,
+        network_id: Option<String>,
+        reply_text: String,
+    ) -> AppResult<ReviewReply>;
+
+    /// Any staff member of the replying network may edit its reply, as long
+    /// as the network stays verified
+    async fn update_reply(
+        &self,
+        reply_id: &str,
+        user_id: String,
+        network_id: Option<String>,
+        reply_text: String,
+    ) -> AppResult<ReviewReply>;
+
+    async fn delete_reply(
+        &self,
+        reply_id: &str,
+        network_id: Option<String>,
+        is_admin: bool,
+    ) -> AppResult<()>;
+}
+
+#[async_trait]
+pub trait CheckinService: Send + Sync {
+    /// One check-in per user and station every few minutes
+    async fn check_in(
+        &self,
+        user_id: String,
+        station_id: &str,
+        outcome: &str,
+        connector_id: Option<String>,
+        comment: Option<String>,
+    ) -> AppResult<StationCheckin>;
+
+    async fn get_station_availability(&self, station_id: &str) -> AppResult<StationAvailability>;
+}
+
+#[async_trait]
+pub trait MediaService: Send + Sync {
+    async fn upload_station_photo(
+        &self,
+        station_id: &str,
+        user_id: String,
+        bytes: &[u8],
+        content_type: &str,
+    ) -> AppResult<Media>;
+
+    /// Only the author of the review may attach photos to it
+    async fn upload_review_photo(
+        &self,
+        review_id: &str,
+        user_id: String,
+        bytes: &[u8],
+        content_type: &str,
+    ) -> AppResult<Media>;
+
+    async fn list_station_media(&self, station_id: &str) -> AppResult<Vec<Media>>;
+
+    /// The stored bytes and their content type
+    async fn get_media_content(
+        &self,
+        media_id: &str,
+        thumbnail: bool,
+    ) -> AppResult<(Vec<u8>, String)>;
+
+    /// Only the uploader, or an admin, may delete a photo
+    async fn delete_media(&self, media_id: &str, user_id: &str, is_admin: bool) -> AppResult<()>;
+}
+
+#[async_trait]
+pub trait IssueService: Send + Sync {
+    /// Opens a ticket assigned to the network operating the connector's station
+    async fn report_issue(
+        &self,
+        reporter_id: String,
+        connector_id: &str,
+        category: &str,
+        description: Option<String>,
+        photo_media_id: Option<String>,
+    ) -> AppResult<IssueReport>;
+
+    async fn list_my_issues(&self, reporter_id: &str) -> AppResult<Vec<IssueReport>>;
+
+    /// Tickets of `network_id`; admins may leave it out to see every network
+    async fn list_issues(
+        &self,
+        network_id: Option<String>,
+        is_admin: bool,
+        status: Option<String>,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> AppResult<Vec<IssueReport>>;
+
+    async fn update_issue_status(
+        &self,
+        issue_id: &str,
+        network_id: Option<String>,
+        is_admin: bool,
+        status: &str,
+        resolution_note: Option<String>,
+        user_id: &str,
+    ) -> AppResult<IssueReport>;
+
+    /// Sets the reported connector to `faulty` and acknowledges an open ticket
+    async fn mark_connector_faulty(
+        &self,
+        issue_id: &str,
+        network_id: Option<String>,
+        is_admin: bool,
+        user_id: &str,
+    ) -> AppResult<IssueReport>;
+}
+
+/// What partners do about the verification of their own network; admins
+/// review the requests in admin-service
+#[async_trait]
+pub trait NetworkVerificationService: Send + Sync {
+    /// Opens a pending request for the partner's own network
+    async fn submit_request(
+        &self,
+        network_id: Option<String>,
+        user_id: String,
+        data: SubmitVerificationData,
+    ) -> AppResult<NetworkVerificationRequest>;
+
+    /// Requests of the partner's own network
+    async fn list_requests(
+        &self,
+        network_id: Option<String>,
+        status: Option<String>,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> AppResult<Vec<NetworkVerificationRequest>>;
+
+    async fn get_request(
+        &self,
+        request_id: &str,
+        network_id: Option<String>,
+    ) -> AppResult<NetworkVerificationDetail>;
+
+    /// Only while the request is pending
+    async fn upload_document(
+        &self,
+        request_id: &str,
+        network_id: Option<String>,
+        user_id: String,
+        file_name: Option<String>,
+        bytes: Vec<u8>,
+        content_type: &str,
+    ) -> AppResult<VerificationDocument>;
+
+    /// The document and its stored bytes. Admins reviewing the request in
+    /// admin-service download the documents here, where they are stored.
+    async fn get_document_content(
+        &self,
+        request_id: &str,
+        document_id: &str,
+        network_id: Option<String>,
+        is_admin: bool,
+    ) -> AppResult<(VerificationDocument, Vec<u8>)>;
+
+    async fn list_network_events(
+        &self,
+        target_network_id: &str,
+        network_id: Option<String>,
+    ) -> AppResult<Vec<VerificationEvent>>;
+}
+
+#[async_trait]
+pub trait NotificationService: Send + Sync {
+    async fn list_notifications(
+        &self,
+        user_id: &str,
+        unread_only: bool,
+    ) -> AppResult<Vec<Notification>>;
+
+    async fn mark_notification_read(
+        &self,
+        notification_id: &str,
+        user_id: &str,
+    ) -> AppResult<Notification>;
+}
+
+#[async_trait]
+pub trait TariffService: Send + Sync {
+    async fn get_station_tariff(&self, station_id: &str) -> AppResult<Tariff>;
+
+    async fn estimate_session_cost(
+        &self,
+        station_id: &str,
+        energy_kwh: f64,
+        charging_minutes: f64,
+        idle_minutes: Option<f64>,
+    ) -> AppResult<CostBreakdown>;
+}
+
+#[async_trait]
+pub trait VehicleService: Send + Sync {
+    async fn list_vehicle_models(&self) -> AppResult<Vec<VehicleModel>>;
+
+    async fn create_vehicle(&self, user_id: String, data: CreateVehicleData) -> AppResult<Vehicle>;
+
+    async fn list_user_vehicles(&self, user_id: &str) -> AppResult<Vec<Vehicle>>;
+
+    async fn get_vehicle(&self, vehicle_id: &str, user_id: &str) -> AppResult<Vehicle>;
+
+    async fn update_vehicle(
+        &self,
+        vehicle_id: &str,
+        user_id: &str,
+        data: UpdateVehicleData,
+    ) -> AppResult<Vehicle>;
+
+    async fn delete_vehicle(&self, vehicle_id: &str, user_id: &str) -> AppResult<()>;
 }
@@ -0,0 +1,15 @@
+use crate::core::errors::AppResult;
+use async_trait::async_trait;
+
+/// Where uploaded files are kept. Keys are relative paths such as
+/// `stations/STA123/MED456.jpg`.
+#[async_trait]
+pub trait ObjectStorage: Send + Sync {
+    async fn put(&self, key: &str, bytes: Vec<u8>, content_type: &str) -> AppResult<()>;
+    async fn get(&self, key: &str) -> AppResult<Vec<u8>>;
+    async fn delete(&self, key: &str) -> AppResult<()>;
+
+    /// URL clients can fetch the object from directly, if the storage is
+    /// exposed publicly (CDN, public bucket, static file server)
+    fn public_url(&self, key: &str) -> Option<String>;
+}
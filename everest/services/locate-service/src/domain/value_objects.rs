@@ -0,0 +1,77 @@
+use super::entities::ChargeCurvePoint;
+use crate::core::errors::AppError;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateVehicleData {
+    /// Catalogue entry to start from; explicit fields below override it
+    pub model_id: Option<i32>,
+    pub nickname: Option<String>,
+    pub make: Option<String>,
+    pub model: Option<String>,
+    pub battery_kwh: Option<f64>,
+    pub connector_types: Option<Vec<String>>,
+    pub max_ac_kw: Option<f64>,
+    pub max_dc_kw: Option<f64>,
+    pub ac_phases: Option<i16>,
+    pub charge_curve: Option<Vec<ChargeCurvePoint>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateVehicleData {
+    pub nickname: Option<String>,
+    pub battery_kwh: Option<f64>,
+    pub connector_types: Option<Vec<String>>,
+    pub max_ac_kw: Option<f64>,
+    pub max_dc_kw: Option<f64>,
+    pub ac_phases: Option<i16>,
+    pub charge_curve: Option<Vec<ChargeCurvePoint>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewSort {
+    #[default]
+    Newest,
+    Highest,
+    Lowest,
+    MostHelpful,
+}
+
+impl FromStr for ReviewSort {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "newest" => Ok(Self::Newest),
+            "highest" => Ok(Self::Highest),
+            "lowest" => Ok(Self::Lowest),
+            "most_helpful" => Ok(Self::MostHelpful),
+            other => Err(AppError::ValidationError(format!(
+                "Unknown sort order: {}, expected newest, highest, lowest or most_helpful",
+                other
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewListFilter {
+    pub sort: ReviewSort,
+    /// Only reviews with exactly this many stars
+    pub rating: Option<i32>,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmitVerificationData {
+    pub company_name: String,
+    pub registration_number: String,
+    pub vat_number: Option<String>,
+    pub registered_address: String,
+    pub contact_name: String,
+    pub contact_email: String,
+    pub contact_phone: Option<String>,
+}
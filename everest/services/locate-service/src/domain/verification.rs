@@ -0,0 +1,66 @@
+use crate::core::errors::{AppError, AppResult};
+use std::str::FromStr;
+
+pub const ALLOWED_DOCUMENT_TYPES: [&str; 3] = ["application/pdf", "image/jpeg", "image/png"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerificationStatus {
+    Pending,
+    Approved,
+    Rejected,
+    Revoked,
+}
+
+impl VerificationStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Approved => "approved",
+            Self::Rejected => "rejected",
+            Self::Revoked => "revoked",
+        }
+    }
+}
+
+impl FromStr for VerificationStatus {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(Self::Pending),
+            "approved" => Ok(Self::Approved),
+            "rejected" => Ok(Self::Rejected),
+            "revoked" => Ok(Self::Revoked),
+            other => Err(AppError::ValidationError(format!(
+                "Unknown verification status: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Checks that `bytes` starts like a file of the declared type and returns
+/// the extension to store it under. Documents are kept as uploaded.
+pub fn document_extension(bytes: &[u8], declared_content_type: &str) -> AppResult<&'static str> {
+    let (signature, extension): (&[u8], _) = match declared_content_type {
+        "application/pdf" => (b"%PDF-", "pdf"),
+        "image/jpeg" => (&[0xFF, 0xD8, 0xFF], "jpg"),
+        "image/png" => (&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A], "png"),
+        other => {
+            return Err(AppError::ValidationError(format!(
+                "Unsupported content type {}, expected one of {}",
+                other,
+                ALLOWED_DOCUMENT_TYPES.join(", ")
+            )));
+        }
+    };
+
+    if !bytes.starts_with(signature) {
+        return Err(AppError::ValidationError(format!(
+            "File content is not {}",
+            declared_content_type
+        )));
+    }
+
+    Ok(extension)
+}
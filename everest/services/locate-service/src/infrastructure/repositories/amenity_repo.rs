@@ -0,0 +1,69 @@
+use crate::core::errors::AppResult;
+use crate::domain::entities::Amenity;
+use crate::domain::repositories::AmenityRepository;
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+pub struct PgAmenityRepository {
+    pool: PgPool,
+}
+
+impl PgAmenityRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl AmenityRepository for PgAmenityRepository {
+    async fn find_near_station(
+        &self,
+        station_id: &str,
+        radius_meters: i32,
+        categories: &[String],
+        limit: i64,
+    ) -> AppResult<Vec<Amenity>> {
+        let amenities = sqlx::query_as::<_, Amenity>(
+            r#"
+            SELECT * FROM find_station_amenities($1, $2)
+            WHERE category = ANY($3)
+            ORDER BY distance_meters
+            LIMIT $4
+            "#,
+        )
+        .bind(station_id)
+        .bind(radius_meters)
+        .bind(categories)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(amenities)
+    }
+
+    async fn find_stations_with_all(
+        &self,
+        station_ids: &[String],
+        radius_meters: i32,
+        categories: &[String],
+    ) -> AppResult<Vec<String>> {
+        let rows: Vec<(String,)> = sqlx::query_as(
+            r#"
+            SELECT s.station_id
+            FROM UNNEST($1::VARCHAR[]) AS s(station_id)
+            WHERE (
+                SELECT COUNT(DISTINCT a.category)
+                FROM find_station_amenities(s.station_id, $2) a
+                WHERE a.category = ANY($3)
+            ) = CARDINALITY($3)
+            "#,
+        )
+        .bind(station_ids)
+        .bind(radius_meters)
+        .bind(categories)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(|r| r.0).collect())
+    }
+}
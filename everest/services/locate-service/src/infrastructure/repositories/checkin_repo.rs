@@ -0,0 +1,96 @@
+use crate::core::errors::AppResult;
+use crate::domain::entities::StationCheckin;
+use crate::domain::repositories::CheckinRepository;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+pub struct PgCheckinRepository {
+    pool: PgPool,
+}
+
+impl PgCheckinRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl CheckinRepository for PgCheckinRepository {
+    async fn create(&self, checkin: &StationCheckin) -> AppResult<StationCheckin> {
+        let created = sqlx::query_as::<_, StationCheckin>(
+            r#"
+            INSERT INTO station_checkins (checkin_id, station_id, connector_id, user_id, outcome, comment)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *
+            "#,
+        )
+        .bind(&checkin.checkin_id)
+        .bind(&checkin.station_id)
+        .bind(&checkin.connector_id)
+        .bind(&checkin.user_id)
+        .bind(&checkin.outcome)
+        .bind(&checkin.comment)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(created)
+    }
+
+    async fn find_latest_by_user(
+        &self,
+        user_id: &str,
+        station_id: &str,
+    ) -> AppResult<Option<StationCheckin>> {
+        let checkin = sqlx::query_as::<_, StationCheckin>(
+            r#"
+            SELECT * FROM station_checkins
+            WHERE user_id = $1 AND station_id = $2
+            ORDER BY created_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(user_id)
+        .bind(station_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(checkin)
+    }
+
+    async fn find_recent(
+        &self,
+        station_ids: &[String],
+        since: DateTime<Utc>,
+    ) -> AppResult<Vec<StationCheckin>> {
+        let checkins = sqlx::query_as::<_, StationCheckin>(
+            r#"
+            SELECT * FROM station_checkins
+            WHERE station_id = ANY($1) AND created_at > $2
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(station_ids)
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(checkins)
+    }
+
+    async fn find_last_successful(&self, station_ids: &[String]) -> AppResult<Vec<StationCheckin>> {
+        let checkins = sqlx::query_as::<_, StationCheckin>(
+            r#"
+            SELECT DISTINCT ON (station_id) *
+            FROM station_checkins
+            WHERE station_id = ANY($1) AND outcome = 'charged_ok'
+            ORDER BY station_id, created_at DESC
+            "#,
+        )
+        .bind(station_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(checkins)
+    }
+}
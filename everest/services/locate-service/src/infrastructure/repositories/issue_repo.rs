@@ -0,0 +1,128 @@
+use crate::core::errors::{AppError, AppResult};
+use crate::domain::entities::IssueReport;
+use crate::domain::repositories::IssueRepository;
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+pub struct PgIssueRepository {
+    pool: PgPool,
+}
+
+impl PgIssueRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl IssueRepository for PgIssueRepository {
+    async fn create(&self, issue: &IssueReport) -> AppResult<IssueReport> {
+        let created = sqlx::query_as::<_, IssueReport>(
+            r#"
+            INSERT INTO issue_reports (
+                issue_id, station_id, connector_id, network_id, reporter_id,
+                category, description, photo_media_id, status, updated_by
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            RETURNING *
+            "#,
+        )
+        .bind(&issue.issue_id)
+        .bind(&issue.station_id)
+        .bind(&issue.connector_id)
+        .bind(&issue.network_id)
+        .bind(&issue.reporter_id)
+        .bind(&issue.category)
+        .bind(&issue.description)
+        .bind(&issue.photo_media_id)
+        .bind(&issue.status)
+        .bind(&issue.updated_by)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(created)
+    }
+
+    async fn find_by_id(&self, issue_id: &str) -> AppResult<Option<IssueReport>> {
+        let issue =
+            sqlx::query_as::<_, IssueReport>("SELECT * FROM issue_reports WHERE issue_id = $1")
+                .bind(issue_id)
+                .fetch_optional(&self.pool)
+                .await?;
+
+        Ok(issue)
+    }
+
+    async fn find_by_reporter(&self, reporter_id: &str) -> AppResult<Vec<IssueReport>> {
+        let issues = sqlx::query_as::<_, IssueReport>(
+            "SELECT * FROM issue_reports WHERE reporter_id = $1 ORDER BY created_at DESC",
+        )
+        .bind(reporter_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(issues)
+    }
+
+    async fn find_by_network<'a>(
+        &self,
+        network_id: Option<&'a str>,
+        status: Option<&'a str>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<IssueReport>> {
+        let issues = sqlx::query_as::<_, IssueReport>(
+            r#"
+            SELECT * FROM issue_reports
+            WHERE ($1::VARCHAR IS NULL OR network_id = $1)
+              AND ($2::VARCHAR IS NULL OR status = $2)
+            ORDER BY created_at DESC
+            LIMIT $3 OFFSET $4
+            "#,
+        )
+        .bind(network_id)
+        .bind(status)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(issues)
+    }
+
+    async fn update_status(
+        &self,
+        issue_id: &str,
+        status: &str,
+        resolution_note: Option<String>,
+        updated_by: &str,
+    ) -> AppResult<IssueReport> {
+        let issue = sqlx::query_as::<_, IssueReport>(
+            r#"
+            UPDATE issue_reports
+            SET status = $2,
+                resolution_note = COALESCE($3, resolution_note),
+                acknowledged_by = CASE WHEN $2 = 'acknowledged' THEN $4 ELSE acknowledged_by END,
+                acknowledged_at = CASE WHEN $2 = 'acknowledged' THEN NOW() ELSE acknowledged_at END,
+                resolved_by = CASE WHEN $2 = 'resolved' THEN $4 ELSE resolved_by END,
+                resolved_at = CASE WHEN $2 = 'resolved' THEN NOW() ELSE resolved_at END,
+                updated_by = $4,
+                updated_at = NOW()
+            WHERE issue_id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(issue_id)
+        .bind(status)
+        .bind(resolution_note)
+        .bind(updated_by)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(AppError::NotFound(format!(
+            "Issue with id {} not found",
+            issue_id
+        )))?;
+
+        Ok(issue)
+    }
+}
@@ -0,0 +1,90 @@
+use crate::core::errors::AppResult;
+use crate::domain::entities::Media;
+use crate::domain::repositories::MediaRepository;
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+pub struct PgMediaRepository {
+    pool: PgPool,
+}
+
+impl PgMediaRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl MediaRepository for PgMediaRepository {
+    async fn create(&self, media: &Media) -> AppResult<Media> {
+        let created = sqlx::query_as::<_, Media>(
+            r#"
+            INSERT INTO media (
+                media_id, station_id, review_id, uploaded_by, content_type,
+                width, height, size_bytes, storage_key, thumbnail_key
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            RETURNING *
+            "#,
+        )
+        .bind(&media.media_id)
+        .bind(&media.station_id)
+        .bind(&media.review_id)
+        .bind(&media.uploaded_by)
+        .bind(&media.content_type)
+        .bind(media.width)
+        .bind(media.height)
+        .bind(media.size_bytes)
+        .bind(&media.storage_key)
+        .bind(&media.thumbnail_key)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(created)
+    }
+
+    async fn find_by_id(&self, media_id: &str) -> AppResult<Option<Media>> {
+        let media = sqlx::query_as::<_, Media>("SELECT * FROM media WHERE media_id = $1")
+            .bind(media_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(media)
+    }
+
+    async fn find_by_station(&self, station_id: &str) -> AppResult<Vec<Media>> {
+        let media = sqlx::query_as::<_, Media>(
+            r#"
+            SELECT m.*
+            FROM media m
+            LEFT JOIN user_reviews r ON r.review_id = m.review_id
+            WHERE m.station_id = $1
+              AND (m.review_id IS NULL OR r.moderation_status = 'visible')
+            ORDER BY m.created_at DESC
+            "#,
+        )
+        .bind(station_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(media)
+    }
+
+    async fn count_by_review(&self, review_id: &str) -> AppResult<i64> {
+        let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM media WHERE review_id = $1")
+            .bind(review_id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(count.0)
+    }
+
+    async fn delete(&self, media_id: &str) -> AppResult<()> {
+        sqlx::query("DELETE FROM media WHERE media_id = $1")
+            .bind(media_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
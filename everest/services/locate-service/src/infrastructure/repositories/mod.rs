@@ -1,2 +1,32 @@
+pub mod amenity_repo;
+pub mod checkin_repo;
+pub mod issue_repo;
+pub mod media_repo;
+pub mod network_verification_repo;
+pub mod notification_repo;
 pub mod review_repo;
 pub mod station_repo;
+pub mod tariff_repo;
+pub mod vehicle_repo;
+
+use crate::core::errors::AppResult;
+use sqlx::PgPool;
+
+/// Views built from stations and connectors. Each has a unique index, so
+/// they refresh concurrently without blocking searches.
+const STATION_VIEWS: [&str; 4] = [
+    "mv_stations_geo",
+    "mv_stations_summary",
+    "mv_connector_type_stats",
+    "mv_stations_reviews",
+];
+
+/// Refreshes the station views after a connector status changed
+pub(crate) async fn refresh_station_views(pool: &PgPool) -> AppResult<()> {
+    for view in STATION_VIEWS {
+        sqlx::query(&format!("REFRESH MATERIALIZED VIEW CONCURRENTLY {}", view))
+            .execute(pool)
+            .await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,256 @@
+use crate::core::errors::{AppError, AppResult};
+use crate::domain::entities::{
+    NetworkVerificationRequest, VerificationDocument, VerificationEvent,
+};
+use crate::domain::repositories::NetworkVerificationRepository;
+use crate::domain::verification::VerificationStatus;
+use async_trait::async_trait;
+use sqlx::{PgPool, Postgres, Transaction};
+
+pub struct PgNetworkVerificationRepository {
+    pool: PgPool,
+}
+
+impl PgNetworkVerificationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+async fn record_event(
+    tx: &mut Transaction<'_, Postgres>,
+    network_id: &str,
+    request_id: Option<&str>,
+    from: Option<VerificationStatus>,
+    to: VerificationStatus,
+    actor_id: &str,
+    comment: Option<&str>,
+) -> AppResult<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO network_verification_events (
+            network_id, request_id, from_status, to_status, actor_id, comment
+        )
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+    )
+    .bind(network_id)
+    .bind(request_id)
+    .bind(from.map(|s| s.as_str()))
+    .bind(to.as_str())
+    .bind(actor_id)
+    .bind(comment)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+#[async_trait]
+impl NetworkVerificationRepository for PgNetworkVerificationRepository {
+    async fn create(
+        &self,
+        request: &NetworkVerificationRequest,
+    ) -> AppResult<NetworkVerificationRequest> {
+        let mut tx = self.pool.begin().await?;
+
+        let created = sqlx::query_as::<_, NetworkVerificationRequest>(
+            r#"
+            INSERT INTO network_verification_requests (
+                request_id, network_id, status, company_name, registration_number,
+                vat_number, registered_address, contact_name, contact_email,
+                contact_phone, submitted_by
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            RETURNING *
+            "#,
+        )
+        .bind(&request.request_id)
+        .bind(&request.network_id)
+        .bind(&request.status)
+        .bind(&request.company_name)
+        .bind(&request.registration_number)
+        .bind(&request.vat_number)
+        .bind(&request.registered_address)
+        .bind(&request.contact_name)
+        .bind(&request.contact_email)
+        .bind(&request.contact_phone)
+        .bind(&request.submitted_by)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(ref db) if db.is_unique_violation() => AppError::Conflict(
+                "A verification request of this network is already under review".to_string(),
+            ),
+            other => other.into(),
+        })?;
+
+        record_event(
+            &mut tx,
+            &created.network_id,
+            Some(&created.request_id),
+            None,
+            VerificationStatus::Pending,
+            &created.submitted_by,
+            None,
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(created)
+    }
+
+    async fn find_by_id(&self, request_id: &str) -> AppResult<Option<NetworkVerificationRequest>> {
+        let request = sqlx::query_as::<_, NetworkVerificationRequest>(
+            "SELECT * FROM network_verification_requests WHERE request_id = $1",
+        )
+        .bind(request_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(request)
+    }
+
+    async fn find_all<'a>(
+        &self,
+        network_id: &str,
+        status: Option<&'a str>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<NetworkVerificationRequest>> {
+        let requests = sqlx::query_as::<_, NetworkVerificationRequest>(
+            r#"
+            SELECT * FROM network_verification_requests
+            WHERE network_id = $1
+              AND ($2::VARCHAR IS NULL OR status = $2)
+            ORDER BY submitted_at DESC
+            LIMIT $3 OFFSET $4
+            "#,
+        )
+        .bind(network_id)
+        .bind(status)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(requests)
+    }
+
+    async fn find_network_verified(&self, network_id: &str) -> AppResult<Option<bool>> {
+        let verified: Option<(bool,)> = sqlx::query_as(
+            r#"
+            SELECT COALESCE(is_verified, FALSE)
+            FROM networks
+            WHERE network_id = $1 AND deleted_at IS NULL
+            "#,
+        )
+        .bind(network_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(verified.map(|v| v.0))
+    }
+
+    async fn add_document(
+        &self,
+        document: &VerificationDocument,
+    ) -> AppResult<VerificationDocument> {
+        let created = sqlx::query_as::<_, VerificationDocument>(
+            r#"
+            INSERT INTO network_verification_documents (
+                document_id, request_id, file_name, content_type, size_bytes,
+                storage_key, uploaded_by
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING *
+            "#,
+        )
+        .bind(&document.document_id)
+        .bind(&document.request_id)
+        .bind(&document.file_name)
+        .bind(&document.content_type)
+        .bind(document.size_bytes)
+        .bind(&document.storage_key)
+        .bind(&document.uploaded_by)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(created)
+    }
+
+    async fn find_documents(&self, request_id: &str) -> AppResult<Vec<VerificationDocument>> {
+        let documents = sqlx::query_as::<_, VerificationDocument>(
+            r#"
+            SELECT * FROM network_verification_documents
+            WHERE request_id = $1
+            ORDER BY uploaded_at
+            "#,
+        )
+        .bind(request_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(documents)
+    }
+
+    async fn find_document(
+        &self,
+        request_id: &str,
+        document_id: &str,
+    ) -> AppResult<Option<VerificationDocument>> {
+        let document = sqlx::query_as::<_, VerificationDocument>(
+            r#"
+            SELECT * FROM network_verification_documents
+            WHERE request_id = $1 AND document_id = $2
+            "#,
+        )
+        .bind(request_id)
+        .bind(document_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(document)
+    }
+
+    async fn count_documents(&self, request_id: &str) -> AppResult<i64> {
+        let count: (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM network_verification_documents WHERE request_id = $1",
+        )
+        .bind(request_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count.0)
+    }
+
+    async fn find_events_by_request(&self, request_id: &str) -> AppResult<Vec<VerificationEvent>> {
+        let events = sqlx::query_as::<_, VerificationEvent>(
+            r#"
+            SELECT * FROM network_verification_events
+            WHERE request_id = $1
+            ORDER BY created_at, event_id
+            "#,
+        )
+        .bind(request_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(events)
+    }
+
+    async fn find_events_by_network(&self, network_id: &str) -> AppResult<Vec<VerificationEvent>> {
+        let events = sqlx::query_as::<_, VerificationEvent>(
+            r#"
+            SELECT * FROM network_verification_events
+            WHERE network_id = $1
+            ORDER BY created_at, event_id
+            "#,
+        )
+        .bind(network_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(events)
+    }
+}
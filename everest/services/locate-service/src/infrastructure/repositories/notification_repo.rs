@@ -0,0 +1,74 @@
+use crate::core::errors::{AppError, AppResult};
+use crate::domain::entities::Notification;
+use crate::domain::repositories::NotificationRepository;
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+pub struct PgNotificationRepository {
+    pool: PgPool,
+}
+
+impl PgNotificationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl NotificationRepository for PgNotificationRepository {
+    async fn create(&self, notification: &Notification) -> AppResult<Notification> {
+        let created = sqlx::query_as::<_, Notification>(
+            r#"
+            INSERT INTO user_notifications (notification_id, user_id, kind, message, review_id)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *
+            "#,
+        )
+        .bind(&notification.notification_id)
+        .bind(&notification.user_id)
+        .bind(&notification.kind)
+        .bind(&notification.message)
+        .bind(&notification.review_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(created)
+    }
+
+    async fn find_by_user(&self, user_id: &str, unread_only: bool) -> AppResult<Vec<Notification>> {
+        let notifications = sqlx::query_as::<_, Notification>(
+            r#"
+            SELECT * FROM user_notifications
+            WHERE user_id = $1 AND (NOT $2 OR read_at IS NULL)
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(user_id)
+        .bind(unread_only)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(notifications)
+    }
+
+    async fn mark_read(&self, notification_id: &str, user_id: &str) -> AppResult<Notification> {
+        let notification = sqlx::query_as::<_, Notification>(
+            r#"
+            UPDATE user_notifications
+            SET read_at = COALESCE(read_at, NOW())
+            WHERE notification_id = $1 AND user_id = $2
+            RETURNING *
+            "#,
+        )
+        .bind(notification_id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(AppError::NotFound(format!(
+            "Notification with id {} not found",
+            notification_id
+        )))?;
+
+        Ok(notification)
+    }
+}
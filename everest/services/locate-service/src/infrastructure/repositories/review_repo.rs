@@ -1,8 +1,9 @@
 use crate::core::errors::{AppError, AppResult};
-use crate::domain::entities::UserReview;
+use crate::domain::entities::{ReviewFlag, ReviewReply, ReviewSummary, UserReview};
 use crate::domain::repositories::ReviewRepository;
+use crate::domain::value_objects::{ReviewListFilter, ReviewSort};
 use async_trait::async_trait;
-use sqlx::PgPool;
+use sqlx::{FromRow, PgPool, Row};
 
 pub struct PgReviewRepository {
     pool: PgPool,
@@ -16,7 +17,7 @@ impl PgReviewRepository {
 
 #[async_trait]
 impl ReviewRepository for PgReviewRepository {
-    async fn create(
+    async fn upsert(
         &self,
         review_id: String,
         user_id: String,
@@ -24,12 +25,19 @@ impl ReviewRepository for PgReviewRepository {
         rating: i32,
         review_text: Option<String>,
         created_by: String,
-    ) -> AppResult<UserReview> {
-        let review = sqlx::query_as::<_, UserReview>(
+    ) -> AppResult<(UserReview, bool)> {
+        // xmax is only zero for a freshly inserted row
+        let row = sqlx::query(
             r#"
             INSERT INTO user_reviews (review_id, user_id, station_id, rating, review_text, created_by, updated_by)
             VALUES ($1, $2, $3, $4, $5, $6, $6)
-            RETURNING *
+            ON CONFLICT (user_id, station_id) DO UPDATE
+            SET
+                rating = EXCLUDED.rating,
+                review_text = EXCLUDED.review_text,
+                updated_by = EXCLUDED.updated_by,
+                updated_at = NOW()
+            RETURNING *, (xmax = 0) AS inserted
             "#,
         )
         .bind(&review_id)
@@ -41,24 +49,125 @@ impl ReviewRepository for PgReviewRepository {
         .fetch_one(&self.pool)
         .await?;
 
-        Ok(review)
+        let review = UserReview::from_row(&row)?;
+        let inserted: bool = row.try_get("inserted")?;
+
+        Ok((review, inserted))
     }
 
-    async fn find_by_station(&self, station_id: &str) -> AppResult<Vec<UserReview>> {
-        let reviews = sqlx::query_as::<_, UserReview>(
+    async fn find_by_station(
+        &self,
+        station_id: &str,
+        filter: &ReviewListFilter,
+    ) -> AppResult<Vec<UserReview>> {
+        let order_by = match filter.sort {
+            ReviewSort::Newest => "r.created_at DESC",
+            ReviewSort::Highest => "r.rating DESC, r.created_at DESC",
+            ReviewSort::Lowest => "r.rating ASC, r.created_at DESC",
+            ReviewSort::MostHelpful => "helpful_votes DESC, r.created_at DESC",
+        };
+
+        let reviews = sqlx::query_as::<_, UserReview>(&format!(
             r#"
-            SELECT * FROM user_reviews
-            WHERE station_id = $1
-            ORDER BY created_at DESC
+            SELECT
+                r.*,
+                (SELECT COUNT(*) FROM review_votes v WHERE v.review_id = r.review_id) AS helpful_votes
+            FROM user_reviews r
+            WHERE r.station_id = $1
+              AND r.moderation_status = 'visible'
+              AND ($2::INT IS NULL OR r.rating = $2)
+            ORDER BY {}, r.review_id
+            LIMIT $3 OFFSET $4
             "#,
-        )
+            order_by
+        ))
         .bind(station_id)
+        .bind(filter.rating)
+        .bind(filter.limit)
+        .bind(filter.offset)
         .fetch_all(&self.pool)
         .await?;
 
         Ok(reviews)
     }
 
+    async fn count_by_station(&self, station_id: &str, rating: Option<i32>) -> AppResult<i64> {
+        let count: (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*) FROM user_reviews
+            WHERE station_id = $1
+              AND moderation_status = 'visible'
+              AND ($2::INT IS NULL OR rating = $2)
+            "#,
+        )
+        .bind(station_id)
+        .bind(rating)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count.0)
+    }
+
+    async fn summarize_station(&self, station_id: &str) -> AppResult<ReviewSummary> {
+        let summary = sqlx::query_as::<_, ReviewSummary>(
+            r#"
+            SELECT
+                AVG(rating)::FLOAT8 AS average_rating,
+                COUNT(*) AS total_reviews,
+                ARRAY[
+                    COUNT(*) FILTER (WHERE rating = 1),
+                    COUNT(*) FILTER (WHERE rating = 2),
+                    COUNT(*) FILTER (WHERE rating = 3),
+                    COUNT(*) FILTER (WHERE rating = 4),
+                    COUNT(*) FILTER (WHERE rating = 5)
+                ] AS histogram
+            FROM user_reviews
+            WHERE station_id = $1 AND moderation_status = 'visible'
+            "#,
+        )
+        .bind(station_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(summary)
+    }
+
+    async fn add_helpful_vote(&self, review_id: &str, user_id: &str) -> AppResult<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO review_votes (review_id, user_id)
+            VALUES ($1, $2)
+            ON CONFLICT (review_id, user_id) DO NOTHING
+            "#,
+        )
+        .bind(review_id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn remove_helpful_vote(&self, review_id: &str, user_id: &str) -> AppResult<()> {
+        sqlx::query("DELETE FROM review_votes WHERE review_id = $1 AND user_id = $2")
+            .bind(review_id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn count_helpful_votes(&self, review_id: &str) -> AppResult<i64> {
+        let count: (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM review_votes WHERE review_id = $1")
+                .bind(review_id)
+                .fetch_one(&self.pool)
+                .await?;
+
+        Ok(count.0)
+    }
+
     async fn find_by_id(&self, review_id: &str) -> AppResult<Option<UserReview>> {
         let review = sqlx::query_as::<_, UserReview>(
             r#"
@@ -130,4 +239,168 @@ impl ReviewRepository for PgReviewRepository {
 
         Ok(())
     }
+
+    async fn set_moderation(
+        &self,
+        review_id: &str,
+        status: &str,
+        reason: Option<String>,
+        moderated_by: Option<String>,
+    ) -> AppResult<UserReview> {
+        let review = sqlx::query_as::<_, UserReview>(
+            r#"
+            UPDATE user_reviews
+            SET
+                moderation_status = $2,
+                moderation_reason = $3,
+                moderated_by = $4,
+                moderated_at = NOW()
+            WHERE review_id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(review_id)
+        .bind(status)
+        .bind(&reason)
+        .bind(&moderated_by)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(AppError::NotFound(format!(
+            "Review with id {} not found",
+            review_id
+        )))?;
+
+        // Held reviews leave the ratings and ranking right away
+        sqlx::query("REFRESH MATERIALIZED VIEW CONCURRENTLY mv_stations_reviews")
+            .execute(&self.pool)
+            .await?;
+
+        Ok(review)
+    }
+
+    async fn create_flag(&self, flag: &ReviewFlag) -> AppResult<ReviewFlag> {
+        let created = sqlx::query_as::<_, ReviewFlag>(
+            r#"
+            INSERT INTO review_flags (flag_id, review_id, user_id, reason, comment)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *
+            "#,
+        )
+        .bind(&flag.flag_id)
+        .bind(&flag.review_id)
+        .bind(&flag.user_id)
+        .bind(&flag.reason)
+        .bind(&flag.comment)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(ref db) if db.is_unique_violation() => AppError::Conflict(
+                format!("Review {} was already flagged by this user", flag.review_id),
+            ),
+            other => other.into(),
+        })?;
+
+        Ok(created)
+    }
+
+    async fn count_open_flags(&self, review_id: &str) -> AppResult<i64> {
+        let count: (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM review_flags WHERE review_id = $1 AND resolved_at IS NULL",
+        )
+        .bind(review_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count.0)
+    }
+
+    async fn create_reply(&self, reply: &ReviewReply) -> AppResult<ReviewReply> {
+        let created = sqlx::query_as::<_, ReviewReply>(
+            r#"
+            INSERT INTO review_replies (
+                reply_id, review_id, network_id, author_id, reply_text, created_by, updated_by
+            ) VALUES ($1, $2, $3, $4, $5, $4, $4)
+            RETURNING *
+            "#,
+        )
+        .bind(&reply.reply_id)
+        .bind(&reply.review_id)
+        .bind(&reply.network_id)
+        .bind(&reply.author_id)
+        .bind(&reply.reply_text)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(ref db) if db.is_unique_violation() => {
+                AppError::Conflict(format!("Review {} already has a reply", reply.review_id))
+            }
+            other => other.into(),
+        })?;
+
+        Ok(created)
+    }
+
+    async fn find_reply(&self, reply_id: &str) -> AppResult<Option<ReviewReply>> {
+        let reply =
+            sqlx::query_as::<_, ReviewReply>("SELECT * FROM review_replies WHERE reply_id = $1")
+                .bind(reply_id)
+                .fetch_optional(&self.pool)
+                .await?;
+
+        Ok(reply)
+    }
+
+    async fn find_replies_for_reviews(&self, review_ids: &[String]) -> AppResult<Vec<ReviewReply>> {
+        let replies = sqlx::query_as::<_, ReviewReply>(
+            "SELECT * FROM review_replies WHERE review_id = ANY($1)",
+        )
+        .bind(review_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(replies)
+    }
+
+    async fn update_reply(
+        &self,
+        reply_id: &str,
+        reply_text: &str,
+        updated_by: &str,
+    ) -> AppResult<ReviewReply> {
+        let reply = sqlx::query_as::<_, ReviewReply>(
+            r#"
+            UPDATE review_replies
+            SET reply_text = $2, updated_by = $3, updated_at = NOW()
+            WHERE reply_id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(reply_id)
+        .bind(reply_text)
+        .bind(updated_by)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or(AppError::NotFound(format!(
+            "Reply with id {} not found",
+            reply_id
+        )))?;
+
+        Ok(reply)
+    }
+
+    async fn delete_reply(&self, reply_id: &str) -> AppResult<()> {
+        let result = sqlx::query("DELETE FROM review_replies WHERE reply_id = $1")
+            .bind(reply_id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!(
+                "Reply with id {} not found",
+                reply_id
+            )));
+        }
+
+        Ok(())
+    }
 }
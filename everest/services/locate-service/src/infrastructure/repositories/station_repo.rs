@@ -1,9 +1,38 @@
-use crate::core::errors::AppResult;
-use crate::domain::entities::Station;
+use crate::core::errors::{AppError, AppResult};
+use crate::domain::entities::{Connector, Station};
 use crate::domain::repositories::StationRepository;
+use crate::infrastructure::repositories::refresh_station_views;
 use async_trait::async_trait;
 use sqlx::PgPool;
 
+/// Review and price columns used to rank nearby search results, and the
+/// verification of the operating network, selected from the `n` alias of a
+/// nearby search with `RANKING_JOINS`.
+const RANKING_COLUMNS: &str = r#"
+                rv.avg_rating::FLOAT8 AS average_rating,
+                COALESCE(rv.total_reviews, 0) AS review_count,
+                price.energy_price_per_kwh,
+                COALESCE(operator_network.is_verified, FALSE) AS network_verified
+"#;
+
+const RANKING_JOINS: &str = r#"
+            LEFT JOIN mv_stations_reviews rv ON rv.station_id = n.station_id
+            LEFT JOIN LATERAL (
+                SELECT (tc.price * (1 + t.vat_percent / 100))::FLOAT8 AS energy_price_per_kwh
+                FROM stations s
+                JOIN tariffs t ON t.network_id = s.network_id AND t.is_active
+                JOIN tariff_components tc
+                    ON tc.tariff_id = t.tariff_id AND tc.component_type = 'ENERGY'
+                WHERE s.station_id = n.station_id AND s.deleted_at IS NULL
+            ) price ON TRUE
+            LEFT JOIN LATERAL (
+                SELECT nw.is_verified
+                FROM stations s
+                JOIN networks nw ON nw.network_id = s.network_id AND nw.deleted_at IS NULL
+                WHERE s.station_id = n.station_id AND s.deleted_at IS NULL
+            ) operator_network ON TRUE
+"#;
+
 pub struct PgStationRepository {
     pool: PgPool,
 }
@@ -16,37 +45,235 @@ impl PgStationRepository {
 
 #[async_trait]
 impl StationRepository for PgStationRepository {
-    async fn find_nearby(
+    async fn find_nearby<'a>(
         &self,
         latitude: f64,
         longitude: f64,
         radius_meters: i32,
         limit: i32,
+        region_id: Option<&'a str>,
     ) -> AppResult<Vec<Station>> {
-        let stations = sqlx::query_as::<_, Station>(
+        let stations = sqlx::query_as::<_, Station>(&format!(
             r#"
             SELECT 
-                station_id,
-                name,
-                address,
-                distance_meters,
-                has_available_connectors,
-                total_available_connectors,
-                max_power_kw,
-                power_tier,
-                operator,
-                latitude,
-                longitude
-            FROM find_nearby_stations($1, $2, $3, $4)
+                n.station_id,
+                n.name,
+                n.address,
+                n.distance_meters,
+                n.has_available_connectors,
+                n.total_available_connectors,
+                n.max_power_kw,
+                n.power_tier,
+                n.operator,
+                n.latitude,
+                n.longitude,
+                {}
+            FROM find_nearby_stations($1, $2, $3, $4, $5) n
+            {}
             "#,
-        )
+            RANKING_COLUMNS, RANKING_JOINS
+        ))
         .bind(latitude)
         .bind(longitude)
         .bind(radius_meters)
         .bind(limit)
+        .bind(region_id)
         .fetch_all(&self.pool)
         .await?;
 
         Ok(stations)
     }
-}
\ No newline at end of file
+
+    async fn find_nearby_compatible<'a>(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        radius_meters: i32,
+        limit: i32,
+        connector_types: &[String],
+        max_ac_kw: Option<f64>,
+        max_dc_kw: Option<f64>,
+        region_id: Option<&'a str>,
+    ) -> AppResult<Vec<Station>> {
+        let stations = sqlx::query_as::<_, Station>(&format!(
+            r#"
+            SELECT 
+                n.station_id,
+                n.name,
+                n.address,
+                n.distance_meters,
+                n.has_available_connectors,
+                n.total_available_connectors,
+                n.max_power_kw,
+                n.power_tier,
+                n.operator,
+                n.latitude,
+                n.longitude,
+                n.effective_power_kw,
+                {}
+            FROM find_nearby_compatible_stations($1, $2, $3, $4, $5, $6, $7, $8) n
+            {}
+            "#,
+            RANKING_COLUMNS, RANKING_JOINS
+        ))
+        .bind(latitude)
+        .bind(longitude)
+        .bind(radius_meters)
+        .bind(limit)
+        .bind(connector_types)
+        .bind(max_ac_kw)
+        .bind(max_dc_kw)
+        .bind(region_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(stations)
+    }
+
+    async fn find_by_id(&self, station_id: &str) -> AppResult<Option<Station>> {
+        let station = sqlx::query_as::<_, Station>(&format!(
+            r#"
+            SELECT
+                n.station_id,
+                n.name,
+                n.address,
+                NULL::FLOAT8 AS distance_meters,
+                n.has_available_connectors,
+                n.total_available_connectors,
+                n.max_power_kw::FLOAT8 AS max_power_kw,
+                n.power_tier,
+                n.operator,
+                n.latitude::FLOAT8 AS latitude,
+                n.longitude::FLOAT8 AS longitude,
+                {}
+            FROM mv_stations_geo n
+            {}
+            WHERE n.station_id = $1
+            "#,
+            RANKING_COLUMNS, RANKING_JOINS
+        ))
+        .bind(station_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(station)
+    }
+
+    async fn find_connectors(&self, station_id: &str) -> AppResult<Vec<Connector>> {
+        let connectors = sqlx::query_as::<_, Connector>(
+            r#"
+            SELECT
+                c.connector_id,
+                c.station_id,
+                ct.name AS connector_type,
+                cu.name AS current_type,
+                c.power_kw::FLOAT8 AS power_kw,
+                c.voltage,
+                c.amperage,
+                c.count_available,
+                c.count_total
+            FROM connectors c
+            JOIN connector_types ct ON ct.id = c.connector_type_id
+            JOIN current_types cu ON cu.id = c.current_type_id
+            WHERE c.station_id = $1 AND c.deleted_at IS NULL
+            ORDER BY c.power_kw DESC NULLS LAST
+            "#,
+        )
+        .bind(station_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(connectors)
+    }
+
+    async fn find_connector(&self, connector_id: &str) -> AppResult<Option<Connector>> {
+        let connector = sqlx::query_as::<_, Connector>(
+            r#"
+            SELECT
+                c.connector_id,
+                c.station_id,
+                ct.name AS connector_type,
+                cu.name AS current_type,
+                c.power_kw::FLOAT8 AS power_kw,
+                c.voltage,
+                c.amperage,
+                c.count_available,
+                c.count_total
+            FROM connectors c
+            JOIN connector_types ct ON ct.id = c.connector_type_id
+            JOIN current_types cu ON cu.id = c.current_type_id
+            WHERE c.connector_id = $1 AND c.deleted_at IS NULL
+            "#,
+        )
+        .bind(connector_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(connector)
+    }
+
+    async fn mark_connector_faulty(&self, connector_id: &str, updated_by: &str) -> AppResult<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE connectors
+            SET status_id = (SELECT id FROM connector_statuses WHERE name = 'faulty'),
+                count_available = 0,
+                updated_by = $2,
+                updated_at = NOW()
+            WHERE connector_id = $1 AND deleted_at IS NULL
+            "#,
+        )
+        .bind(connector_id)
+        .bind(updated_by)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!(
+                "Connector with id {} not found",
+                connector_id
+            )));
+        }
+
+        // Searches stop offering the connector as available right away
+        refresh_station_views(&self.pool).await
+    }
+
+    async fn exists(&self, station_id: &str) -> AppResult<bool> {
+        let exists: (bool,) = sqlx::query_as(
+            "SELECT EXISTS(SELECT 1 FROM stations WHERE station_id = $1 AND deleted_at IS NULL)",
+        )
+        .bind(station_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(exists.0)
+    }
+
+    async fn find_network_id(&self, station_id: &str) -> AppResult<Option<String>> {
+        let row: Option<(Option<String>,)> = sqlx::query_as(
+            "SELECT network_id FROM stations WHERE station_id = $1 AND deleted_at IS NULL",
+        )
+        .bind(station_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|r| r.0).ok_or(AppError::NotFound(format!(
+            "Station with id {} not found",
+            station_id
+        )))
+    }
+
+    async fn average_rating(&self) -> AppResult<Option<f64>> {
+        let average: (Option<f64>,) = sqlx::query_as(
+            r#"
+            SELECT (SUM(avg_rating * total_reviews) / NULLIF(SUM(total_reviews), 0))::FLOAT8
+            FROM mv_stations_reviews
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(average.0)
+    }
+}
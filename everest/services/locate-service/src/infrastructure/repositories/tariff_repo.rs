@@ -0,0 +1,57 @@
+use crate::core::errors::AppResult;
+use crate::domain::entities::{Tariff, TariffComponent};
+use crate::domain::repositories::TariffRepository;
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+pub struct PgTariffRepository {
+    pool: PgPool,
+}
+
+impl PgTariffRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TariffRepository for PgTariffRepository {
+    async fn find_active_by_station(&self, station_id: &str) -> AppResult<Option<Tariff>> {
+        let tariff = sqlx::query_as::<_, Tariff>(
+            r#"
+            SELECT
+                t.tariff_id,
+                t.network_id,
+                t.name,
+                t.currency::TEXT AS currency,
+                t.vat_percent::FLOAT8 AS vat_percent,
+                t.min_price::FLOAT8 AS min_price,
+                t.max_price::FLOAT8 AS max_price
+            FROM tariffs t
+            JOIN stations s ON s.network_id = t.network_id
+            WHERE s.station_id = $1 AND s.deleted_at IS NULL AND t.is_active
+            "#,
+        )
+        .bind(station_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let Some(mut tariff) = tariff else {
+            return Ok(None);
+        };
+
+        tariff.components = sqlx::query_as::<_, TariffComponent>(
+            r#"
+            SELECT component_type, price::FLOAT8 AS price, step_size
+            FROM tariff_components
+            WHERE tariff_id = $1
+            ORDER BY id
+            "#,
+        )
+        .bind(&tariff.tariff_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(Some(tariff))
+    }
+}
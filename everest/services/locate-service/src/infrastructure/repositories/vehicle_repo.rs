@@ -0,0 +1,173 @@
+use crate::core::errors::{AppError, AppResult};
+use crate::domain::entities::{Vehicle, VehicleModel};
+use crate::domain::repositories::VehicleRepository;
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+const VEHICLE_COLUMNS: &str = r#"
+    vehicle_id, user_id, model_id, nickname, make, model,
+    battery_kwh::FLOAT8 AS battery_kwh,
+    connector_types,
+    max_ac_kw::FLOAT8 AS max_ac_kw,
+    max_dc_kw::FLOAT8 AS max_dc_kw,
+    ac_phases, charge_curve,
+    created_at, updated_at, created_by, updated_by
+"#;
+
+const VEHICLE_MODEL_COLUMNS: &str = r#"
+    id, make, model,
+    battery_kwh::FLOAT8 AS battery_kwh,
+    connector_types,
+    max_ac_kw::FLOAT8 AS max_ac_kw,
+    max_dc_kw::FLOAT8 AS max_dc_kw,
+    ac_phases, charge_curve
+"#;
+
+pub struct PgVehicleRepository {
+    pool: PgPool,
+}
+
+impl PgVehicleRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl VehicleRepository for PgVehicleRepository {
+    async fn list_models(&self) -> AppResult<Vec<VehicleModel>> {
+        let models = sqlx::query_as::<_, VehicleModel>(&format!(
+            "SELECT {} FROM vehicle_models ORDER BY make, model",
+            VEHICLE_MODEL_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(models)
+    }
+
+    async fn find_model(&self, model_id: i32) -> AppResult<Option<VehicleModel>> {
+        let model = sqlx::query_as::<_, VehicleModel>(&format!(
+            "SELECT {} FROM vehicle_models WHERE id = $1",
+            VEHICLE_MODEL_COLUMNS
+        ))
+        .bind(model_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(model)
+    }
+
+    async fn list_connector_types(&self) -> AppResult<Vec<String>> {
+        let names: Vec<(String,)> = sqlx::query_as("SELECT name FROM connector_types ORDER BY id")
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(names.into_iter().map(|n| n.0).collect())
+    }
+
+    async fn create(&self, vehicle: &Vehicle) -> AppResult<Vehicle> {
+        let created = sqlx::query_as::<_, Vehicle>(&format!(
+            r#"
+            INSERT INTO user_vehicles (
+                vehicle_id, user_id, model_id, nickname, make, model, battery_kwh,
+                connector_types, max_ac_kw, max_dc_kw, ac_phases, charge_curve,
+                created_by, updated_by
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $13)
+            RETURNING {}
+            "#,
+            VEHICLE_COLUMNS
+        ))
+        .bind(&vehicle.vehicle_id)
+        .bind(&vehicle.user_id)
+        .bind(vehicle.model_id)
+        .bind(&vehicle.nickname)
+        .bind(&vehicle.make)
+        .bind(&vehicle.model)
+        .bind(vehicle.battery_kwh)
+        .bind(&vehicle.connector_types)
+        .bind(vehicle.max_ac_kw)
+        .bind(vehicle.max_dc_kw)
+        .bind(vehicle.ac_phases)
+        .bind(&vehicle.charge_curve)
+        .bind(&vehicle.created_by)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(created)
+    }
+
+    async fn find_by_id(&self, vehicle_id: &str) -> AppResult<Option<Vehicle>> {
+        let vehicle = sqlx::query_as::<_, Vehicle>(&format!(
+            "SELECT {} FROM user_vehicles WHERE vehicle_id = $1",
+            VEHICLE_COLUMNS
+        ))
+        .bind(vehicle_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(vehicle)
+    }
+
+    async fn find_by_user(&self, user_id: &str) -> AppResult<Vec<Vehicle>> {
+        let vehicles = sqlx::query_as::<_, Vehicle>(&format!(
+            "SELECT {} FROM user_vehicles WHERE user_id = $1 ORDER BY created_at DESC",
+            VEHICLE_COLUMNS
+        ))
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(vehicles)
+    }
+
+    async fn update(&self, vehicle: &Vehicle) -> AppResult<Vehicle> {
+        let updated = sqlx::query_as::<_, Vehicle>(&format!(
+            r#"
+            UPDATE user_vehicles
+            SET
+                nickname = $2,
+                battery_kwh = $3,
+                connector_types = $4,
+                max_ac_kw = $5,
+                max_dc_kw = $6,
+                ac_phases = $7,
+                charge_curve = $8,
+                updated_by = $9,
+                updated_at = NOW()
+            WHERE vehicle_id = $1
+            RETURNING {}
+            "#,
+            VEHICLE_COLUMNS
+        ))
+        .bind(&vehicle.vehicle_id)
+        .bind(&vehicle.nickname)
+        .bind(vehicle.battery_kwh)
+        .bind(&vehicle.connector_types)
+        .bind(vehicle.max_ac_kw)
+        .bind(vehicle.max_dc_kw)
+        .bind(vehicle.ac_phases)
+        .bind(&vehicle.charge_curve)
+        .bind(&vehicle.updated_by)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(updated)
+    }
+
+    async fn delete(&self, vehicle_id: &str) -> AppResult<()> {
+        let result = sqlx::query("DELETE FROM user_vehicles WHERE vehicle_id = $1")
+            .bind(vehicle_id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!(
+                "Vehicle with id {} not found",
+                vehicle_id
+            )));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+use crate::core::errors::{AppError, AppResult};
+use crate::domain::storage::ObjectStorage;
+use async_trait::async_trait;
+use std::io::ErrorKind;
+use std::path::{Component, Path, PathBuf};
+
+/// Keeps objects as files under `base_dir`, one file per key.
+pub struct LocalStorage {
+    base_dir: PathBuf,
+    public_base_url: Option<String>,
+}
+
+impl LocalStorage {
+    pub fn new(base_dir: impl Into<PathBuf>, public_base_url: Option<String>) -> Self {
+        Self {
+            base_dir: base_dir.into(),
+            public_base_url,
+        }
+    }
+
+    /// Rejects keys that would escape `base_dir`
+    fn path_for(&self, key: &str) -> AppResult<PathBuf> {
+        let relative = Path::new(key);
+        if relative
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_)))
+        {
+            return Err(AppError::ValidationError(format!(
+                "Invalid storage key: {}",
+                key
+            )));
+        }
+        Ok(self.base_dir.join(relative))
+    }
+}
+
+#[async_trait]
+impl ObjectStorage for LocalStorage {
+    async fn put(&self, key: &str, bytes: Vec<u8>, _content_type: &str) -> AppResult<()> {
+        let path = self.path_for(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| AppError::InternalError(format!("Failed to store {}: {}", key, e)))?;
+        }
+        tokio::fs::write(&path, bytes)
+            .await
+            .map_err(|e| AppError::InternalError(format!("Failed to store {}: {}", key, e)))
+    }
+
+    async fn get(&self, key: &str) -> AppResult<Vec<u8>> {
+        let path = self.path_for(key)?;
+        tokio::fs::read(&path).await.map_err(|e| match e.kind() {
+            ErrorKind::NotFound => AppError::NotFound(format!("Object {} not found", key)),
+            _ => AppError::InternalError(format!("Failed to read {}: {}", key, e)),
+        })
+    }
+
+    async fn delete(&self, key: &str) -> AppResult<()> {
+        let path = self.path_for(key)?;
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(AppError::InternalError(format!(
+                "Failed to delete {}: {}",
+                key, e
+            ))),
+        }
+    }
+
+    fn public_url(&self, key: &str) -> Option<String> {
+        self.public_base_url
+            .as_ref()
+            .map(|base| format!("{}/{}", base.trim_end_matches('/'), key))
+    }
+}
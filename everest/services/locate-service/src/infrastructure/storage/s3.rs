@@ -0,0 +1,99 @@
+use crate::core::errors::{AppError, AppResult};
+use crate::domain::storage::ObjectStorage;
+use async_trait::async_trait;
+use aws_config::{BehaviorVersion, Region};
+use aws_sdk_s3::Client;
+use aws_sdk_s3::primitives::ByteStream;
+
+/// Any S3-compatible bucket: AWS, MinIO, Ceph, ... Credentials come from the
+/// usual AWS environment variables (`AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY`).
+pub struct S3Storage {
+    client: Client,
+    bucket: String,
+    public_base_url: Option<String>,
+}
+
+impl S3Storage {
+    /// `endpoint` points at a non-AWS server such as a local MinIO; path-style
+    /// addressing is used so that no bucket DNS is needed.
+    pub async fn new(
+        bucket: String,
+        region: String,
+        endpoint: Option<String>,
+        public_base_url: Option<String>,
+    ) -> Self {
+        let mut loader =
+            aws_config::defaults(BehaviorVersion::latest()).region(Region::new(region));
+        if let Some(endpoint) = endpoint {
+            loader = loader.endpoint_url(endpoint);
+        }
+        let shared_config = loader.load().await;
+        let config = aws_sdk_s3::config::Builder::from(&shared_config)
+            .force_path_style(true)
+            .build();
+
+        Self {
+            client: Client::from_conf(config),
+            bucket,
+            public_base_url,
+        }
+    }
+}
+
+#[async_trait]
+impl ObjectStorage for S3Storage {
+    async fn put(&self, key: &str, bytes: Vec<u8>, content_type: &str) -> AppResult<()> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .content_type(content_type)
+            .body(ByteStream::from(bytes))
+            .send()
+            .await
+            .map_err(|e| AppError::InternalError(format!("Failed to store {}: {}", key, e)))?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> AppResult<Vec<u8>> {
+        let object = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|e| {
+                let err = e.into_service_error();
+                if err.is_no_such_key() {
+                    AppError::NotFound(format!("Object {} not found", key))
+                } else {
+                    AppError::InternalError(format!("Failed to read {}: {}", key, err))
+                }
+            })?;
+
+        let bytes = object
+            .body
+            .collect()
+            .await
+            .map_err(|e| AppError::InternalError(format!("Failed to read {}: {}", key, e)))?;
+        Ok(bytes.into_bytes().to_vec())
+    }
+
+    async fn delete(&self, key: &str) -> AppResult<()> {
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|e| AppError::InternalError(format!("Failed to delete {}: {}", key, e)))?;
+        Ok(())
+    }
+
+    fn public_url(&self, key: &str) -> Option<String> {
+        self.public_base_url
+            .as_ref()
+            .map(|base| format!("{}/{}", base.trim_end_matches('/'), key))
+    }
+}
@@ -4,13 +4,34 @@ pub mod domain;
 pub mod infrastructure;
 pub mod presentation;
 
+use crate::application::checkin_service::CheckinServiceImpl;
+use crate::application::issue_service::IssueServiceImpl;
+use crate::application::media_service::MediaServiceImpl;
+use crate::application::network_verification_service::NetworkVerificationServiceImpl;
+use crate::application::notification_service::NotificationServiceImpl;
 use crate::application::review_service::ReviewServiceImpl;
 use crate::application::station_service::StationServiceImpl;
+use crate::application::tariff_service::TariffServiceImpl;
+use crate::application::vehicle_service::VehicleServiceImpl;
 use crate::core::auth::JwtValidator;
-use crate::core::config::Config;
+use crate::core::config::{Config, StorageConfig};
 use crate::core::database::create_pool;
+use crate::domain::checkins::AvailabilityPolicy;
+use crate::domain::moderation::ModerationPolicy;
+use crate::domain::ranking::RankingPolicy;
+use crate::domain::storage::ObjectStorage;
+use crate::infrastructure::repositories::amenity_repo::PgAmenityRepository;
+use crate::infrastructure::repositories::checkin_repo::PgCheckinRepository;
+use crate::infrastructure::repositories::issue_repo::PgIssueRepository;
+use crate::infrastructure::repositories::media_repo::PgMediaRepository;
+use crate::infrastructure::repositories::network_verification_repo::PgNetworkVerificationRepository;
+use crate::infrastructure::repositories::notification_repo::PgNotificationRepository;
 use crate::infrastructure::repositories::review_repo::PgReviewRepository;
 use crate::infrastructure::repositories::station_repo::PgStationRepository;
+use crate::infrastructure::repositories::tariff_repo::PgTariffRepository;
+use crate::infrastructure::repositories::vehicle_repo::PgVehicleRepository;
+use crate::infrastructure::storage::local::LocalStorage;
+use crate::infrastructure::storage::s3::S3Storage;
 use crate::presentation::openapi::ApiDoc;
 use actix_cors::Cors;
 use actix_web::{App, HttpServer, middleware, web};
@@ -38,8 +59,48 @@ pub async fn run() -> anyhow::Result<()> {
         as Arc<dyn crate::domain::repositories::StationRepository>;
     let review_repo = Arc::new(PgReviewRepository::new(db_pool.clone()))
         as Arc<dyn crate::domain::repositories::ReviewRepository>;
+    let tariff_repo = Arc::new(PgTariffRepository::new(db_pool.clone()))
+        as Arc<dyn crate::domain::repositories::TariffRepository>;
+    let vehicle_repo = Arc::new(PgVehicleRepository::new(db_pool.clone()))
+        as Arc<dyn crate::domain::repositories::VehicleRepository>;
+    let notification_repo = Arc::new(PgNotificationRepository::new(db_pool.clone()))
+        as Arc<dyn crate::domain::repositories::NotificationRepository>;
+    let issue_repo = Arc::new(PgIssueRepository::new(db_pool.clone()))
+        as Arc<dyn crate::domain::repositories::IssueRepository>;
+    let checkin_repo = Arc::new(PgCheckinRepository::new(db_pool.clone()))
+        as Arc<dyn crate::domain::repositories::CheckinRepository>;
+    let amenity_repo = Arc::new(PgAmenityRepository::new(db_pool.clone()))
+        as Arc<dyn crate::domain::repositories::AmenityRepository>;
+    let media_repo = Arc::new(PgMediaRepository::new(db_pool.clone()))
+        as Arc<dyn crate::domain::repositories::MediaRepository>;
+    let verification_repo = Arc::new(PgNetworkVerificationRepository::new(db_pool.clone()))
+        as Arc<dyn crate::domain::repositories::NetworkVerificationRepository>;
+
+    // Object storage
+    let storage: Arc<dyn ObjectStorage> = match config.storage.clone() {
+        StorageConfig::Local {
+            dir,
+            public_base_url,
+        } => {
+            tracing::info!("Storing media under {}", dir);
+            Arc::new(LocalStorage::new(dir, public_base_url))
+        }
+        StorageConfig::S3 {
+            bucket,
+            region,
+            endpoint,
+            public_base_url,
+        } => {
+            tracing::info!("Storing media in S3 bucket {}", bucket);
+            Arc::new(S3Storage::new(bucket, region, endpoint, public_base_url).await)
+        }
+    };
 
     // Services
+    let moderation_policy =
+        ModerationPolicy::new(&config.review_blocklist, config.review_flag_threshold);
+    let ranking_policy = RankingPolicy::new(config.ranking_weights, config.rating_prior_weight);
+    let availability_policy = AvailabilityPolicy::new(config.checkin_half_life_hours);
     //    let station_service = Arc::new(StationServiceImpl::new(station_repo));
     //    let review_service = Arc::new(ReviewServiceImpl::new(review_repo));
 
@@ -57,11 +118,49 @@ pub async fn run() -> anyhow::Result<()> {
             .wrap(cors)
             .wrap(middleware::Logger::default())
             .wrap(tracing_actix_web::TracingLogger::default())
-            .app_data(web::Data::new(jwt_validator.clone()))
+            .app_data(web::Data::from(jwt_validator.clone()))
             .app_data(web::Data::new(StationServiceImpl::new(
                 station_repo.clone(),
+                checkin_repo.clone(),
+                amenity_repo.clone(),
+                ranking_policy.clone(),
+                availability_policy.clone(),
+                config.amenity_walking_distance_meters,
+            )))
+            .app_data(web::Data::new(CheckinServiceImpl::new(
+                checkin_repo.clone(),
+                station_repo.clone(),
+                availability_policy.clone(),
+            )))
+            .app_data(web::Data::new(ReviewServiceImpl::new(
+                review_repo.clone(),
+                station_repo.clone(),
+                notification_repo.clone(),
+                verification_repo.clone(),
+                moderation_policy.clone(),
+            )))
+            .app_data(web::Data::new(NotificationServiceImpl::new(
+                notification_repo.clone(),
+            )))
+            .app_data(web::Data::new(IssueServiceImpl::new(
+                issue_repo.clone(),
+                station_repo.clone(),
+                media_repo.clone(),
+            )))
+            .app_data(web::Data::new(MediaServiceImpl::new(
+                media_repo.clone(),
+                station_repo.clone(),
+                review_repo.clone(),
+                storage.clone(),
+            )))
+            .app_data(web::Data::new(NetworkVerificationServiceImpl::new(
+                verification_repo.clone(),
+                storage.clone(),
+            )))
+            .app_data(web::Data::new(TariffServiceImpl::new(tariff_repo.clone())))
+            .app_data(web::Data::new(VehicleServiceImpl::new(
+                vehicle_repo.clone(),
             )))
-            .app_data(web::Data::new(ReviewServiceImpl::new(review_repo.clone())))
             .configure(presentation::configure_routes)
             .service(
                 SwaggerUi::new("/swagger-ui/{_:.*}")
@@ -1,10 +1,36 @@
+use crate::application::checkin_service::CheckinServiceImpl;
 use crate::application::dtos::*;
+use crate::application::issue_service::IssueServiceImpl;
+use crate::application::media_service::MediaServiceImpl;
+use crate::application::network_verification_service::NetworkVerificationServiceImpl;
+use crate::application::notification_service::NotificationServiceImpl;
 use crate::application::review_service::ReviewServiceImpl;
 use crate::application::station_service::StationServiceImpl;
-use crate::core::auth::{JwtValidator, TokenClaims, extract_bearer_token};
+use crate::application::tariff_service::TariffServiceImpl;
+use crate::application::vehicle_service::VehicleServiceImpl;
+use crate::core::auth::{
+    JwtValidator, extract_bearer_token, require_operator_auth, require_user_auth,
+};
+use crate::core::constants::{
+    DEFAULT_FROM_SOC_PERCENT, DEFAULT_REVIEW_PAGE_SIZE, DEFAULT_TO_SOC_PERCENT, MAX_UPLOAD_BYTES,
+};
 use crate::core::errors::{AppError, AppResult};
-use crate::domain::services::{ReviewService, StationService};
+use crate::domain::amenities::AmenityCategory;
+use crate::domain::charging::{estimate_compatible_connectors, validate_soc_range};
+use crate::domain::ranking::StationSort;
+use crate::domain::services::{
+    CheckinService, IssueService, MediaService, NetworkVerificationService, NotificationService,
+    ReviewService, StationService, TariffService, VehicleService,
+};
+use crate::domain::value_objects::{
+    CreateVehicleData, ReviewListFilter, ReviewSort, SubmitVerificationData, UpdateVehicleData,
+};
+use actix_multipart::Multipart;
+use actix_web::http::header::ContentDisposition;
 use actix_web::{HttpRequest, HttpResponse, web};
+use futures_util::TryStreamExt;
+use std::str::FromStr;
+use validator::Validate;
 
 #[utoipa::path(
     get,
@@ -13,165 +39,1567 @@ use actix_web::{HttpRequest, HttpResponse, web};
         ("latitude" = f64, Query, description = "Latitude coordinate"),
         ("longitude" = f64, Query, description = "Longitude coordinate"),
         ("radius_meters" = Option<i32>, Query, description = "Search radius in meters (default: 20000)"),
-        ("limit" = Option<i32>, Query, description = "Maximum number of results (default: 5)")
+        ("limit" = Option<i32>, Query, description = "Maximum number of results (default: 5)"),
+        ("vehicle_id" = Option<String>, Query, description = "Only return stations compatible with this vehicle (requires authentication)"),
+        ("sort" = Option<String>, Query, description = "relevance (default), distance, power or rating"),
+        ("amenities" = Option<String>, Query, description = "Only stations with all of these amenities within walking distance, e.g. cafe,toilets"),
+        ("region" = Option<String>, Query, description = "Only stations of this region, e.g. tunisia")
     ),
     responses(
         (status = 200, description = "List of nearby stations", body = Vec<StationResponse>),
+        (status = 400, description = "Invalid query"),
+        (status = 401, description = "Unauthorized - vehicle_id requires a token"),
+        (status = 403, description = "Vehicle belongs to another user"),
+        (status = 404, description = "Vehicle not found"),
         (status = 500, description = "Internal server error")
     ),
     tag = "stations"
 )]
 pub async fn get_nearby_stations(
+    req: HttpRequest,
     query: web::Query<NearbyStationsQuery>,
     station_service: web::Data<StationServiceImpl>,
+    vehicle_service: web::Data<VehicleServiceImpl>,
+    validator: web::Data<JwtValidator>,
+) -> AppResult<HttpResponse> {
+    let vehicle = match &query.vehicle_id {
+        Some(vehicle_id) => {
+            let claims = require_user_auth(&req, &validator).await?;
+            Some(vehicle_service.get_vehicle(vehicle_id, &claims.sub).await?)
+        }
+        None => None,
+    };
+    let sort = query
+        .sort
+        .as_deref()
+        .map(StationSort::from_str)
+        .transpose()?
+        .unwrap_or_default();
+    let amenities = query
+        .amenities
+        .as_deref()
+        .map(AmenityCategory::parse_list)
+        .transpose()?
+        .unwrap_or_default();
+
+    let stations = station_service
+        .find_nearby_stations(
+            query.latitude,
+            query.longitude,
+            query.radius_meters,
+            query.limit,
+            vehicle,
+            sort,
+            amenities,
+            query.region.as_deref(),
+        )
+        .await?;
+
+    let response: Vec<StationResponse> = stations.into_iter().map(StationResponse::from).collect();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/stations/{station_id}/cost-estimate",
+    params(
+        ("station_id" = String, Path, description = "Station ID"),
+        ("energy_kwh" = f64, Query, description = "Energy to deliver in kWh"),
+        ("charging_minutes" = f64, Query, description = "Expected charging time in minutes"),
+        ("idle_minutes" = Option<f64>, Query, description = "Expected time plugged in without charging (default: 0)")
+    ),
+    responses(
+        (status = 200, description = "Itemized cost estimate for the session", body = CostEstimateResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 404, description = "Station has no active tariff"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "stations"
+)]
+pub async fn get_station_cost_estimate(
+    station_id: web::Path<String>,
+    query: web::Query<CostEstimateQuery>,
+    tariff_service: web::Data<TariffServiceImpl>,
+) -> AppResult<HttpResponse> {
+    let cost = tariff_service
+        .estimate_session_cost(
+            &station_id,
+            query.energy_kwh,
+            query.charging_minutes,
+            query.idle_minutes,
+        )
+        .await?;
+
+    Ok(HttpResponse::Ok().json(CostEstimateResponse::from(cost)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/stations/{station_id}/charging-estimate",
+    params(
+        ("station_id" = String, Path, description = "Station ID"),
+        ("vehicle_id" = String, Query, description = "Vehicle to estimate for"),
+        ("from_soc" = Option<f64>, Query, description = "Starting state of charge in percent (default: 10)"),
+        ("to_soc" = Option<f64>, Query, description = "Target state of charge in percent (default: 80)")
+    ),
+    responses(
+        (status = 200, description = "Charging time per compatible connector, fastest first", body = Vec<ChargingEstimateResponse>),
+        (status = 400, description = "Invalid state of charge or no compatible connector"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Vehicle belongs to another user"),
+        (status = 404, description = "Station or vehicle not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "stations"
+)]
+pub async fn get_station_charging_estimate(
+    req: HttpRequest,
+    station_id: web::Path<String>,
+    query: web::Query<ChargingEstimateQuery>,
+    station_service: web::Data<StationServiceImpl>,
+    vehicle_service: web::Data<VehicleServiceImpl>,
+    validator: web::Data<JwtValidator>,
+) -> AppResult<HttpResponse> {
+    let claims = require_user_auth(&req, &validator).await?;
+
+    let vehicle = vehicle_service
+        .get_vehicle(&query.vehicle_id, &claims.sub)
+        .await?;
+    let estimates = station_service
+        .estimate_charging_time(
+            &station_id,
+            &vehicle,
+            query.from_soc.unwrap_or(DEFAULT_FROM_SOC_PERCENT),
+            query.to_soc.unwrap_or(DEFAULT_TO_SOC_PERCENT),
+        )
+        .await?;
+    let response: Vec<ChargingEstimateResponse> = estimates
+        .into_iter()
+        .map(ChargingEstimateResponse::from)
+        .collect();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/stations/{station_id}",
+    params(
+        ("station_id" = String, Path, description = "Station ID"),
+        ("vehicle_id" = Option<String>, Query, description = "Vehicle to estimate charging times for (requires authentication)"),
+        ("from_soc" = Option<f64>, Query, description = "Starting state of charge in percent (default: 10)"),
+        ("to_soc" = Option<f64>, Query, description = "Target state of charge in percent (default: 80)")
+    ),
+    responses(
+        (status = 200, description = "Station with its connectors, photos and, given a vehicle, charging estimates", body = StationDetailResponse),
+        (status = 400, description = "Invalid state of charge"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Vehicle belongs to another user"),
+        (status = 404, description = "Station or vehicle not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "stations"
+)]
+pub async fn get_station_detail(
+    req: HttpRequest,
+    station_id: web::Path<String>,
+    query: web::Query<StationDetailQuery>,
+    station_service: web::Data<StationServiceImpl>,
+    media_service: web::Data<MediaServiceImpl>,
+    vehicle_service: web::Data<VehicleServiceImpl>,
+    validator: web::Data<JwtValidator>,
+) -> AppResult<HttpResponse> {
+    let detail = station_service.get_station_detail(&station_id).await?;
+    let photos = media_service.list_station_media(&station_id).await?;
+
+    let charging_estimates = match &query.vehicle_id {
+        Some(vehicle_id) => {
+            let claims = require_user_auth(&req, &validator).await?;
+            let vehicle = vehicle_service.get_vehicle(vehicle_id, &claims.sub).await?;
+            let from_soc = query.from_soc.unwrap_or(DEFAULT_FROM_SOC_PERCENT);
+            let to_soc = query.to_soc.unwrap_or(DEFAULT_TO_SOC_PERCENT);
+            validate_soc_range(from_soc, to_soc)?;
+            Some(estimate_compatible_connectors(
+                &vehicle,
+                &detail.connectors,
+                from_soc,
+                to_soc,
+            ))
+        }
+        None => None,
+    };
+
+    Ok(HttpResponse::Ok().json(StationDetailResponse::new(
+        detail,
+        photos,
+        charging_estimates,
+    )))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/stations/{station_id}/amenities",
+    params(
+        ("station_id" = String, Path, description = "Station ID"),
+        ("categories" = Option<String>, Query, description = "Comma-separated categories: cafe, restaurant, toilets, supermarket, hotel (default: all)"),
+        ("radius_meters" = Option<i32>, Query, description = "Search radius in meters (default: walking distance, max 2000)")
+    ),
+    responses(
+        (status = 200, description = "Amenities around the station from OpenStreetMap, closest first", body = Vec<AmenityResponse>),
+        (status = 400, description = "Invalid query"),
+        (status = 404, description = "Station not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "stations"
+)]
+pub async fn get_station_amenities(
+    station_id: web::Path<String>,
+    query: web::Query<StationAmenitiesQuery>,
+    station_service: web::Data<StationServiceImpl>,
+) -> AppResult<HttpResponse> {
+    let categories = query
+        .categories
+        .as_deref()
+        .map(AmenityCategory::parse_list)
+        .transpose()?
+        .unwrap_or_default();
+
+    let amenities = station_service
+        .list_station_amenities(&station_id, categories, query.radius_meters)
+        .await?;
+    let response: Vec<AmenityResponse> = amenities.into_iter().map(AmenityResponse::from).collect();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Reads the `file` part of a multipart upload, refusing anything larger
+/// than `MAX_UPLOAD_BYTES`. Returns the bytes, the declared content type and
+/// the file name, if the client sent one.
+async fn read_upload(mut payload: Multipart) -> AppResult<(Vec<u8>, String, Option<String>)> {
+    let invalid = |e: actix_multipart::MultipartError| {
+        AppError::ValidationError(format!("Invalid multipart upload: {}", e))
+    };
+
+    while let Some(mut field) = payload.try_next().await.map_err(invalid)? {
+        if field.name() != Some("file") {
+            continue;
+        }
+        let content_type = field
+            .content_type()
+            .map(|mime| mime.essence_str().to_string())
+            .ok_or(AppError::ValidationError(
+                "The file part needs a Content-Type".to_string(),
+            ))?;
+        let file_name = field
+            .content_disposition()
+            .and_then(|cd| cd.get_filename())
+            .map(str::to_string);
+
+        let mut bytes = Vec::new();
+        while let Some(chunk) = field.try_next().await.map_err(invalid)? {
+            if bytes.len() + chunk.len() > MAX_UPLOAD_BYTES {
+                return Err(AppError::ValidationError(format!(
+                    "File is larger than {} MB",
+                    MAX_UPLOAD_BYTES / (1024 * 1024)
+                )));
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        if bytes.is_empty() {
+            return Err(AppError::ValidationError("File is empty".to_string()));
+        }
+        return Ok((bytes, content_type, file_name));
+    }
+
+    Err(AppError::ValidationError(
+        "Missing file part in multipart upload".to_string(),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/stations/{station_id}/photos",
+    params(
+        ("station_id" = String, Path, description = "Station ID")
+    ),
+    request_body(content = PhotoUploadForm, content_type = "multipart/form-data"),
+    responses(
+        (status = 201, description = "Photo stored with its thumbnail", body = MediaResponse),
+        (status = 400, description = "Missing, oversized or unsupported image"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Station not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "media"
+)]
+pub async fn upload_station_photo(
+    req: HttpRequest,
+    station_id: web::Path<String>,
+    payload: Multipart,
+    media_service: web::Data<MediaServiceImpl>,
+    validator: web::Data<JwtValidator>,
+) -> AppResult<HttpResponse> {
+    let claims = require_user_auth(&req, &validator).await?;
+
+    let (bytes, content_type, _) = read_upload(payload).await?;
+    let media = media_service
+        .upload_station_photo(&station_id, claims.sub, &bytes, &content_type)
+        .await?;
+
+    Ok(HttpResponse::Created().json(MediaResponse::from(media)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/stations/{station_id}/photos",
+    params(
+        ("station_id" = String, Path, description = "Station ID")
+    ),
+    responses(
+        (status = 200, description = "Photos of the station and of its visible reviews, newest first", body = Vec<MediaResponse>),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "media"
+)]
+pub async fn list_station_photos(
+    station_id: web::Path<String>,
+    media_service: web::Data<MediaServiceImpl>,
+) -> AppResult<HttpResponse> {
+    let media = media_service.list_station_media(&station_id).await?;
+    let response: Vec<MediaResponse> = media.into_iter().map(MediaResponse::from).collect();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/reviews/{review_id}/photos",
+    params(
+        ("review_id" = String, Path, description = "Review ID")
+    ),
+    request_body(content = PhotoUploadForm, content_type = "multipart/form-data"),
+    responses(
+        (status = 201, description = "Photo attached to the review", body = MediaResponse),
+        (status = 400, description = "Missing, oversized or unsupported image, or too many photos"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Review belongs to another user"),
+        (status = 404, description = "Review not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "media"
+)]
+pub async fn upload_review_photo(
+    req: HttpRequest,
+    review_id: web::Path<String>,
+    payload: Multipart,
+    media_service: web::Data<MediaServiceImpl>,
+    validator: web::Data<JwtValidator>,
+) -> AppResult<HttpResponse> {
+    let claims = require_user_auth(&req, &validator).await?;
+
+    let (bytes, content_type, _) = read_upload(payload).await?;
+    let media = media_service
+        .upload_review_photo(&review_id, claims.sub, &bytes, &content_type)
+        .await?;
+
+    Ok(HttpResponse::Created().json(MediaResponse::from(media)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/media/{media_id}",
+    params(
+        ("media_id" = String, Path, description = "Media ID")
+    ),
+    responses(
+        (status = 200, description = "The image", content_type = "image/*"),
+        (status = 404, description = "Media not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "media"
+)]
+pub async fn get_media(
+    media_id: web::Path<String>,
+    media_service: web::Data<MediaServiceImpl>,
+) -> AppResult<HttpResponse> {
+    let (bytes, content_type) = media_service.get_media_content(&media_id, false).await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(content_type)
+        .insert_header(("Cache-Control", "public, max-age=86400, immutable"))
+        .body(bytes))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/media/{media_id}/thumbnail",
+    params(
+        ("media_id" = String, Path, description = "Media ID")
+    ),
+    responses(
+        (status = 200, description = "JPEG thumbnail", content_type = "image/jpeg"),
+        (status = 404, description = "Media not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "media"
+)]
+pub async fn get_media_thumbnail(
+    media_id: web::Path<String>,
+    media_service: web::Data<MediaServiceImpl>,
+) -> AppResult<HttpResponse> {
+    let (bytes, content_type) = media_service.get_media_content(&media_id, true).await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(content_type)
+        .insert_header(("Cache-Control", "public, max-age=86400, immutable"))
+        .body(bytes))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/media/{media_id}",
+    params(
+        ("media_id" = String, Path, description = "Media ID")
+    ),
+    responses(
+        (status = 204, description = "Photo deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Photo was uploaded by another user"),
+        (status = 404, description = "Media not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "media"
+)]
+pub async fn delete_media(
+    req: HttpRequest,
+    media_id: web::Path<String>,
+    media_service: web::Data<MediaServiceImpl>,
+    validator: web::Data<JwtValidator>,
+) -> AppResult<HttpResponse> {
+    let claims = require_user_auth(&req, &validator).await?;
+
+    media_service
+        .delete_media(&media_id, &claims.sub, claims.is_admin())
+        .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/stations/{station_id}/checkins",
+    params(
+        ("station_id" = String, Path, description = "Station ID")
+    ),
+    request_body = CheckinRequest,
+    responses(
+        (status = 201, description = "Check-in recorded", body = CheckinResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Station not found"),
+        (status = 409, description = "Checked in at this station too recently"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "stations"
+)]
+pub async fn create_checkin(
+    req: HttpRequest,
+    station_id: web::Path<String>,
+    payload: web::Json<CheckinRequest>,
+    checkin_service: web::Data<CheckinServiceImpl>,
+    validator: web::Data<JwtValidator>,
+) -> AppResult<HttpResponse> {
+    let claims = require_user_auth(&req, &validator).await?;
+
+    payload
+        .validate()
+        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+
+    let payload = payload.into_inner();
+    let checkin = checkin_service
+        .check_in(
+            claims.sub,
+            &station_id,
+            &payload.outcome,
+            payload.connector_id,
+            payload.comment,
+        )
+        .await?;
+
+    Ok(HttpResponse::Created().json(CheckinResponse::from(checkin)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/stations/{station_id}/availability",
+    params(
+        ("station_id" = String, Path, description = "Station ID")
+    ),
+    responses(
+        (status = 200, description = "Availability inferred from recent driver check-ins", body = StationAvailabilityResponse),
+        (status = 404, description = "Station not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "stations"
+)]
+pub async fn get_station_availability(
+    station_id: web::Path<String>,
+    checkin_service: web::Data<CheckinServiceImpl>,
+) -> AppResult<HttpResponse> {
+    let availability = checkin_service
+        .get_station_availability(&station_id)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(StationAvailabilityResponse::from(availability)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/reviews",
+    request_body = CreateReviewRequest,
+    responses(
+        (status = 201, description = "Review created successfully", body = ReviewResponse),
+        (status = 200, description = "Existing review of the station replaced", body = ReviewResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - user role required"),
+        (status = 404, description = "Station not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "reviews"
+)]
+pub async fn create_review(
+    req: HttpRequest,
+    payload: web::Json<CreateReviewRequest>,
+    review_service: web::Data<ReviewServiceImpl>,
+    validator: web::Data<JwtValidator>,
+) -> AppResult<HttpResponse> {
+    let token = extract_bearer_token(&req)?;
+    let claims = validator.validate_user_role(&token).await?;
+
+    let user_id = claims.sub.clone();
+
+    let (review, created) = review_service
+        .create_review(
+            user_id.clone(),
+            payload.station_id.clone(),
+            payload.rating,
+            payload.review_text.clone(),
+            user_id,
+        )
+        .await?;
+
+    if created {
+        Ok(HttpResponse::Created().json(ReviewResponse::from(review)))
+    } else {
+        Ok(HttpResponse::Ok().json(ReviewResponse::from(review)))
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/reviews/station/{station_id}",
+    params(
+        ("station_id" = String, Path, description = "Station ID"),
+        ("sort" = Option<String>, Query, description = "newest (default), highest, lowest or most_helpful"),
+        ("rating" = Option<i32>, Query, description = "Only reviews with this many stars (1-5)"),
+        ("limit" = Option<i64>, Query, description = "Page size (default: 20, max: 100)"),
+        ("offset" = Option<i64>, Query, description = "Number of reviews to skip")
+    ),
+    responses(
+        (status = 200, description = "One page of reviews for the station with its rating summary", body = StationReviewsResponse),
+        (status = 400, description = "Invalid query"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "reviews"
+)]
+pub async fn get_station_reviews(
+    req: HttpRequest,
+    station_id: web::Path<String>,
+    query: web::Query<StationReviewsQuery>,
+    review_service: web::Data<ReviewServiceImpl>,
+    validator: web::Data<JwtValidator>,
+) -> AppResult<HttpResponse> {
+    let token = extract_bearer_token(&req)?;
+    let _ = validator.validate_user_role(&token).await?;
+
+    let query = query.into_inner();
+    let filter = ReviewListFilter {
+        sort: query
+            .sort
+            .as_deref()
+            .map(ReviewSort::from_str)
+            .transpose()?
+            .unwrap_or_default(),
+        rating: query.rating,
+        limit: query.limit.unwrap_or(DEFAULT_REVIEW_PAGE_SIZE),
+        offset: query.offset.unwrap_or(0),
+    };
+    let page = review_service
+        .get_reviews_by_station(&station_id, filter)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(StationReviewsResponse::from(page)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/reviews/{review_id}/helpful",
+    params(
+        ("review_id" = String, Path, description = "Review ID")
+    ),
+    responses(
+        (status = 200, description = "Vote recorded; voting twice has no further effect", body = HelpfulVoteResponse),
+        (status = 400, description = "Cannot vote for your own review"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Review not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "reviews"
+)]
+pub async fn vote_review_helpful(
+    req: HttpRequest,
+    review_id: web::Path<String>,
+    review_service: web::Data<ReviewServiceImpl>,
+    validator: web::Data<JwtValidator>,
+) -> AppResult<HttpResponse> {
+    let claims = require_user_auth(&req, &validator).await?;
+
+    let helpful_votes = review_service.vote_helpful(&review_id, &claims.sub).await?;
+
+    Ok(HttpResponse::Ok().json(HelpfulVoteResponse {
+        review_id: review_id.into_inner(),
+        helpful_votes,
+    }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/reviews/{review_id}/helpful",
+    params(
+        ("review_id" = String, Path, description = "Review ID")
+    ),
+    responses(
+        (status = 200, description = "Vote withdrawn", body = HelpfulVoteResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Review not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "reviews"
+)]
+pub async fn unvote_review_helpful(
+    req: HttpRequest,
+    review_id: web::Path<String>,
+    review_service: web::Data<ReviewServiceImpl>,
+    validator: web::Data<JwtValidator>,
+) -> AppResult<HttpResponse> {
+    let claims = require_user_auth(&req, &validator).await?;
+
+    let helpful_votes = review_service
+        .unvote_helpful(&review_id, &claims.sub)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(HelpfulVoteResponse {
+        review_id: review_id.into_inner(),
+        helpful_votes,
+    }))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/reviews/{review_id}",
+    params(
+        ("review_id" = String, Path, description = "Review ID")
+    ),
+    request_body = UpdateReviewRequest,
+    responses(
+        (status = 200, description = "Review updated successfully", body = ReviewResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Review belongs to another user"),
+        (status = 404, description = "Review not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "reviews"
+)]
+pub async fn update_review(
+    req: HttpRequest,
+    review_id: web::Path<String>,
+    payload: web::Json<UpdateReviewRequest>,
+    review_service: web::Data<ReviewServiceImpl>,
+    validator: web::Data<JwtValidator>,
+) -> AppResult<HttpResponse> {
+    let token = extract_bearer_token(&req)?;
+    let claims = validator.validate_user_role(&token).await?;
+
+    let updated_by = claims.sub.clone();
+
+    let review = review_service
+        .update_review(
+            &review_id,
+            payload.rating,
+            payload.review_text.clone(),
+            updated_by,
+            claims.is_admin(),
+        )
+        .await?;
+
+    Ok(HttpResponse::Ok().json(ReviewResponse::from(review)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/reviews/{review_id}",
+    params(
+        ("review_id" = String, Path, description = "Review ID")
+    ),
+    responses(
+        (status = 204, description = "Review deleted successfully"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Review belongs to another user"),
+        (status = 404, description = "Review not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "reviews"
+)]
+pub async fn delete_review(
+    req: HttpRequest,
+    review_id: web::Path<String>,
+    review_service: web::Data<ReviewServiceImpl>,
+    validator: web::Data<JwtValidator>,
+) -> AppResult<HttpResponse> {
+    let token = extract_bearer_token(&req)?;
+    let claims = validator.validate_user_role(&token).await?;
+
+    review_service
+        .delete_review(&review_id, &claims.sub, claims.is_admin())
+        .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/reviews/{review_id}/flags",
+    params(
+        ("review_id" = String, Path, description = "Review ID")
+    ),
+    request_body = FlagReviewRequest,
+    responses(
+        (status = 201, description = "Review flagged for moderation", body = ReviewFlagResponse),
+        (status = 400, description = "Invalid reason or own review"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Review not found"),
+        (status = 409, description = "Review already flagged by this user"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "reviews"
+)]
+pub async fn flag_review(
+    req: HttpRequest,
+    review_id: web::Path<String>,
+    payload: web::Json<FlagReviewRequest>,
+    review_service: web::Data<ReviewServiceImpl>,
+    validator: web::Data<JwtValidator>,
+) -> AppResult<HttpResponse> {
+    let claims = require_user_auth(&req, &validator).await?;
+    payload
+        .validate()
+        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+
+    let payload = payload.into_inner();
+    let flag = review_service
+        .flag_review(&review_id, claims.sub, &payload.reason, payload.comment)
+        .await?;
+
+    Ok(HttpResponse::Created().json(ReviewFlagResponse::from(flag)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/reviews/{review_id}/reply",
+    params(
+        ("review_id" = String, Path, description = "Review ID")
+    ),
+    request_body = ReviewReplyRequest,
+    responses(
+        (status = 201, description = "Reply published and reviewer notified", body = ReviewReplyResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Station is not operated by the caller's network, or the network is not verified"),
+        (status = 404, description = "Review not found"),
+        (status = 409, description = "Review already has a reply"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "reviews"
+)]
+pub async fn reply_to_review(
+    req: HttpRequest,
+    review_id: web::Path<String>,
+    payload: web::Json<ReviewReplyRequest>,
+    review_service: web::Data<ReviewServiceImpl>,
+    validator: web::Data<JwtValidator>,
+) -> AppResult<HttpResponse> {
+    let claims = require_operator_auth(&req, &validator).await?;
+    payload
+        .validate()
+        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+
+    let reply = review_service
+        .reply_to_review(
+            &review_id,
+            claims.sub,
+            claims.network_id,
+            payload.into_inner().reply_text,
+        )
+        .await?;
+
+    Ok(HttpResponse::Created().json(ReviewReplyResponse::from(reply)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/reviews/replies/{reply_id}",
+    params(
+        ("reply_id" = String, Path, description = "Reply ID")
+    ),
+    request_body = ReviewReplyRequest,
+    responses(
+        (status = 200, description = "Reply updated and reviewer notified", body = ReviewReplyResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Reply belongs to another network, or the network is not verified"),
+        (status = 404, description = "Reply not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "reviews"
+)]
+pub async fn update_reply(
+    req: HttpRequest,
+    reply_id: web::Path<String>,
+    payload: web::Json<ReviewReplyRequest>,
+    review_service: web::Data<ReviewServiceImpl>,
+    validator: web::Data<JwtValidator>,
+) -> AppResult<HttpResponse> {
+    let claims = require_operator_auth(&req, &validator).await?;
+    payload
+        .validate()
+        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+
+    let reply = review_service
+        .update_reply(
+            &reply_id,
+            claims.sub,
+            claims.network_id,
+            payload.into_inner().reply_text,
+        )
+        .await?;
+
+    Ok(HttpResponse::Ok().json(ReviewReplyResponse::from(reply)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/reviews/replies/{reply_id}",
+    params(
+        ("reply_id" = String, Path, description = "Reply ID")
+    ),
+    responses(
+        (status = 204, description = "Reply deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Reply belongs to another network"),
+        (status = 404, description = "Reply not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "reviews"
+)]
+pub async fn delete_reply(
+    req: HttpRequest,
+    reply_id: web::Path<String>,
+    review_service: web::Data<ReviewServiceImpl>,
+    validator: web::Data<JwtValidator>,
+) -> AppResult<HttpResponse> {
+    let token = extract_bearer_token(&req)?;
+    let claims = validator.validate_token(&token).await?;
+    if !claims.is_admin() && !claims.is_network_staff() {
+        return Err(AppError::Forbidden(
+            "Partner, operator or admin role required".to_string(),
+        ));
+    }
+
+    review_service
+        .delete_reply(&reply_id, claims.network_id.clone(), claims.is_admin())
+        .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/issues",
+    request_body = ReportIssueRequest,
+    responses(
+        (status = 201, description = "Issue reported to the station's network", body = IssueResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Connector or photo not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "issues"
+)]
+pub async fn report_issue(
+    req: HttpRequest,
+    payload: web::Json<ReportIssueRequest>,
+    issue_service: web::Data<IssueServiceImpl>,
+    validator: web::Data<JwtValidator>,
+) -> AppResult<HttpResponse> {
+    let claims = require_user_auth(&req, &validator).await?;
+
+    payload
+        .validate()
+        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+
+    let payload = payload.into_inner();
+    let issue = issue_service
+        .report_issue(
+            claims.sub,
+            &payload.connector_id,
+            &payload.category,
+            payload.description,
+            payload.photo_media_id,
+        )
+        .await?;
+
+    Ok(HttpResponse::Created().json(IssueResponse::from(issue)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/issues/mine",
+    responses(
+        (status = 200, description = "Issues reported by the current user, newest first", body = Vec<IssueResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "issues"
+)]
+pub async fn list_my_issues(
+    req: HttpRequest,
+    issue_service: web::Data<IssueServiceImpl>,
+    validator: web::Data<JwtValidator>,
+) -> AppResult<HttpResponse> {
+    let claims = require_user_auth(&req, &validator).await?;
+
+    let issues = issue_service.list_my_issues(&claims.sub).await?;
+    let response: Vec<IssueResponse> = issues.into_iter().map(IssueResponse::from).collect();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/issues",
+    params(
+        ("status" = Option<String>, Query, description = "open, acknowledged or resolved"),
+        ("limit" = Option<i64>, Query, description = "Maximum number of results (default: 50)"),
+        ("offset" = Option<i64>, Query, description = "Number of results to skip")
+    ),
+    responses(
+        (status = 200, description = "Tickets of the caller's network, newest first", body = Vec<IssueResponse>),
+        (status = 400, description = "Invalid query"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - partner or operator role on a network required"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "issues"
+)]
+pub async fn list_issues(
+    req: HttpRequest,
+    query: web::Query<IssuesQuery>,
+    issue_service: web::Data<IssueServiceImpl>,
+    validator: web::Data<JwtValidator>,
+) -> AppResult<HttpResponse> {
+    let token = extract_bearer_token(&req)?;
+    let claims = validator.validate_token(&token).await?;
+    if !claims.is_admin() && !claims.is_network_staff() {
+        return Err(AppError::Forbidden(
+            "Partner, operator or admin role required".to_string(),
+        ));
+    }
+
+    let query = query.into_inner();
+    let network_id = if claims.is_admin() {
+        query.network_id
+    } else {
+        claims.network_id.clone()
+    };
+    let issues = issue_service
+        .list_issues(
+            network_id,
+            claims.is_admin(),
+            query.status,
+            query.limit,
+            query.offset,
+        )
+        .await?;
+    let response: Vec<IssueResponse> = issues.into_iter().map(IssueResponse::from).collect();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/issues/{issue_id}/status",
+    params(
+        ("issue_id" = String, Path, description = "Issue ID")
+    ),
+    request_body = UpdateIssueStatusRequest,
+    responses(
+        (status = 200, description = "Ticket status updated", body = IssueResponse),
+        (status = 400, description = "Invalid status"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Issue is assigned to another network"),
+        (status = 404, description = "Issue not found"),
+        (status = 409, description = "Status change not allowed from the current status"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "issues"
+)]
+pub async fn update_issue_status(
+    req: HttpRequest,
+    issue_id: web::Path<String>,
+    payload: web::Json<UpdateIssueStatusRequest>,
+    issue_service: web::Data<IssueServiceImpl>,
+    validator: web::Data<JwtValidator>,
+) -> AppResult<HttpResponse> {
+    let token = extract_bearer_token(&req)?;
+    let claims = validator.validate_token(&token).await?;
+    if !claims.is_admin() && !claims.is_network_staff() {
+        return Err(AppError::Forbidden(
+            "Partner, operator or admin role required".to_string(),
+        ));
+    }
+
+    payload
+        .validate()
+        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+
+    let payload = payload.into_inner();
+    let issue = issue_service
+        .update_issue_status(
+            &issue_id,
+            claims.network_id.clone(),
+            claims.is_admin(),
+            &payload.status,
+            payload.resolution_note,
+            &claims.sub,
+        )
+        .await?;
+
+    Ok(HttpResponse::Ok().json(IssueResponse::from(issue)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/issues/{issue_id}/mark-faulty",
+    params(
+        ("issue_id" = String, Path, description = "Issue ID")
+    ),
+    responses(
+        (status = 200, description = "Connector set to faulty; an open ticket is acknowledged", body = IssueResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Issue is assigned to another network"),
+        (status = 404, description = "Issue or connector not found"),
+        (status = 409, description = "Issue already resolved"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "issues"
+)]
+pub async fn mark_issue_connector_faulty(
+    req: HttpRequest,
+    issue_id: web::Path<String>,
+    issue_service: web::Data<IssueServiceImpl>,
+    validator: web::Data<JwtValidator>,
+) -> AppResult<HttpResponse> {
+    let token = extract_bearer_token(&req)?;
+    let claims = validator.validate_token(&token).await?;
+    if !claims.is_admin() && !claims.is_network_staff() {
+        return Err(AppError::Forbidden(
+            "Partner, operator or admin role required".to_string(),
+        ));
+    }
+
+    let issue = issue_service
+        .mark_connector_faulty(
+            &issue_id,
+            claims.network_id.clone(),
+            claims.is_admin(),
+            &claims.sub,
+        )
+        .await?;
+
+    Ok(HttpResponse::Ok().json(IssueResponse::from(issue)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/network-verifications",
+    request_body = SubmitVerificationRequest,
+    responses(
+        (status = 201, description = "Request submitted for admin review; documents can now be uploaded", body = VerificationRequestResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - partner or operator role on a network required"),
+        (status = 404, description = "Network not found"),
+        (status = 409, description = "Network already verified or a request is already under review"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "verification"
+)]
+pub async fn submit_network_verification(
+    req: HttpRequest,
+    payload: web::Json<SubmitVerificationRequest>,
+    verification_service: web::Data<NetworkVerificationServiceImpl>,
+    validator: web::Data<JwtValidator>,
 ) -> AppResult<HttpResponse> {
-    let stations = station_service
-        .find_nearby_stations(
-            query.latitude,
-            query.longitude,
-            query.radius_meters,
-            query.limit,
+    let claims = require_operator_auth(&req, &validator).await?;
+    payload
+        .validate()
+        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+
+    let payload = payload.into_inner();
+    let request = verification_service
+        .submit_request(
+            claims.network_id,
+            claims.sub,
+            SubmitVerificationData {
+                company_name: payload.company_name,
+                registration_number: payload.registration_number,
+                vat_number: payload.vat_number,
+                registered_address: payload.registered_address,
+                contact_name: payload.contact_name,
+                contact_email: payload.contact_email,
+                contact_phone: payload.contact_phone,
+            },
         )
         .await?;
 
-    let response: Vec<StationResponse> = stations.into_iter().map(StationResponse::from).collect();
+    Ok(HttpResponse::Created().json(VerificationRequestResponse::from(request)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/network-verifications",
+    params(
+        ("status" = Option<String>, Query, description = "pending, approved, rejected or revoked"),
+        ("limit" = Option<i64>, Query, description = "Maximum number of results (default: 50)"),
+        ("offset" = Option<i64>, Query, description = "Number of results to skip")
+    ),
+    responses(
+        (status = 200, description = "Requests of the caller's network, newest first", body = Vec<VerificationRequestResponse>),
+        (status = 400, description = "Invalid query"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - partner or operator role on a network required"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "verification"
+)]
+pub async fn list_network_verifications(
+    req: HttpRequest,
+    query: web::Query<VerificationRequestsQuery>,
+    verification_service: web::Data<NetworkVerificationServiceImpl>,
+    validator: web::Data<JwtValidator>,
+) -> AppResult<HttpResponse> {
+    let claims = require_operator_auth(&req, &validator).await?;
+
+    let query = query.into_inner();
+    let requests = verification_service
+        .list_requests(claims.network_id, query.status, query.limit, query.offset)
+        .await?;
+    let response: Vec<VerificationRequestResponse> = requests
+        .into_iter()
+        .map(VerificationRequestResponse::from)
+        .collect();
 
     Ok(HttpResponse::Ok().json(response))
 }
 
 #[utoipa::path(
-    post,
-    path = "/api/reviews",
-    request_body = CreateReviewRequest,
+    get,
+    path = "/api/network-verifications/{request_id}",
+    params(
+        ("request_id" = String, Path, description = "Verification request ID")
+    ),
     responses(
-        (status = 201, description = "Review created successfully", body = ReviewResponse),
-        (status = 400, description = "Invalid request"),
+        (status = 200, description = "The request with its documents and state changes", body = VerificationRequestDetailResponse),
         (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - user role required"),
+        (status = 403, description = "Request belongs to another network"),
+        (status = 404, description = "Request not found"),
         (status = 500, description = "Internal server error")
     ),
     security(("bearer_auth" = [])),
-    tag = "reviews"
+    tag = "verification"
 )]
-pub async fn create_review(
+pub async fn get_network_verification(
     req: HttpRequest,
-    payload: web::Json<CreateReviewRequest>,
-    review_service: web::Data<ReviewServiceImpl>,
+    request_id: web::Path<String>,
+    verification_service: web::Data<NetworkVerificationServiceImpl>,
     validator: web::Data<JwtValidator>,
 ) -> AppResult<HttpResponse> {
-    let token = extract_bearer_token(&req)?;
-    let claims = validator.validate_user_role(&token).await?;
+    let claims = require_operator_auth(&req, &validator).await?;
 
-    let user_id = claims.sub.clone();
+    let detail = verification_service
+        .get_request(&request_id, claims.network_id)
+        .await?;
 
-    let review = review_service
-        .create_review(
-            user_id.clone(),
-            payload.station_id.clone(),
-            payload.rating,
-            payload.review_text.clone(),
-            user_id,
+    Ok(HttpResponse::Ok().json(VerificationRequestDetailResponse::from(detail)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/network-verifications/{request_id}/documents",
+    params(
+        ("request_id" = String, Path, description = "Verification request ID")
+    ),
+    request_body(content = DocumentUploadForm, content_type = "multipart/form-data"),
+    responses(
+        (status = 201, description = "Document attached to the request", body = VerificationDocumentResponse),
+        (status = 400, description = "Missing, oversized or unsupported file, or too many documents"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Request belongs to another network"),
+        (status = 404, description = "Request not found"),
+        (status = 409, description = "Request is no longer pending"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "verification"
+)]
+pub async fn upload_verification_document(
+    req: HttpRequest,
+    request_id: web::Path<String>,
+    payload: Multipart,
+    verification_service: web::Data<NetworkVerificationServiceImpl>,
+    validator: web::Data<JwtValidator>,
+) -> AppResult<HttpResponse> {
+    let claims = require_operator_auth(&req, &validator).await?;
+
+    let (bytes, content_type, file_name) = read_upload(payload).await?;
+    let document = verification_service
+        .upload_document(
+            &request_id,
+            claims.network_id,
+            claims.sub,
+            file_name,
+            bytes,
+            &content_type,
         )
         .await?;
 
-    Ok(HttpResponse::Created().json(ReviewResponse::from(review)))
+    Ok(HttpResponse::Created().json(VerificationDocumentResponse::from(document)))
 }
 
 #[utoipa::path(
     get,
-    path = "/api/reviews/station/{station_id}",
+    path = "/api/network-verifications/{request_id}/documents/{document_id}",
     params(
-        ("station_id" = String, Path, description = "Station ID")
+        ("request_id" = String, Path, description = "Verification request ID"),
+        ("document_id" = String, Path, description = "Document ID")
     ),
     responses(
-        (status = 200, description = "List of reviews for the station", body = Vec<ReviewResponse>),
+        (status = 200, description = "The document as uploaded", content_type = "application/octet-stream"),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Request belongs to another network"),
+        (status = 404, description = "Request or document not found"),
         (status = 500, description = "Internal server error")
     ),
     security(("bearer_auth" = [])),
-    tag = "reviews"
+    tag = "verification"
 )]
-pub async fn get_station_reviews(
+pub async fn get_verification_document(
     req: HttpRequest,
-    station_id: web::Path<String>,
-    review_service: web::Data<ReviewServiceImpl>,
+    path: web::Path<(String, String)>,
+    verification_service: web::Data<NetworkVerificationServiceImpl>,
     validator: web::Data<JwtValidator>,
 ) -> AppResult<HttpResponse> {
     let token = extract_bearer_token(&req)?;
-    let _ = validator.validate_user_role(&token).await?;
+    let claims = validator.validate_token(&token).await?;
+    if !claims.is_admin() && !claims.is_network_staff() {
+        return Err(AppError::Forbidden(
+            "Partner, operator or admin role required".to_string(),
+        ));
+    }
+
+    let (request_id, document_id) = path.into_inner();
+    let (document, bytes) = verification_service
+        .get_document_content(
+            &request_id,
+            &document_id,
+            claims.network_id.clone(),
+            claims.is_admin(),
+        )
+        .await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(document.content_type)
+        .insert_header(ContentDisposition::attachment(document.file_name))
+        .insert_header(("Cache-Control", "private, no-store"))
+        .body(bytes))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/networks/{network_id}/verification/events",
+    params(
+        ("network_id" = String, Path, description = "Network ID")
+    ),
+    responses(
+        (status = 200, description = "Verification state changes of the network, oldest first", body = Vec<VerificationEventResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Network is not the caller's"),
+        (status = 404, description = "Network not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "verification"
+)]
+pub async fn list_network_verification_events(
+    req: HttpRequest,
+    network_id: web::Path<String>,
+    verification_service: web::Data<NetworkVerificationServiceImpl>,
+    validator: web::Data<JwtValidator>,
+) -> AppResult<HttpResponse> {
+    let claims = require_operator_auth(&req, &validator).await?;
+
+    let events = verification_service
+        .list_network_events(&network_id, claims.network_id)
+        .await?;
+    let response: Vec<VerificationEventResponse> = events
+        .into_iter()
+        .map(VerificationEventResponse::from)
+        .collect();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/vehicles/models",
+    responses(
+        (status = 200, description = "Reference catalogue of vehicle models", body = Vec<VehicleModelResponse>),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "vehicles"
+)]
+pub async fn list_vehicle_models(
+    vehicle_service: web::Data<VehicleServiceImpl>,
+) -> AppResult<HttpResponse> {
+    let models = vehicle_service.list_vehicle_models().await?;
+    let response: Vec<VehicleModelResponse> =
+        models.into_iter().map(VehicleModelResponse::from).collect();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/vehicles",
+    request_body = CreateVehicleRequest,
+    responses(
+        (status = 201, description = "Vehicle profile created", body = VehicleResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Vehicle model not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "vehicles"
+)]
+pub async fn create_vehicle(
+    req: HttpRequest,
+    payload: web::Json<CreateVehicleRequest>,
+    vehicle_service: web::Data<VehicleServiceImpl>,
+    validator: web::Data<JwtValidator>,
+) -> AppResult<HttpResponse> {
+    let claims = require_user_auth(&req, &validator).await?;
+    payload
+        .validate()
+        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+
+    let payload = payload.into_inner();
+    let vehicle = vehicle_service
+        .create_vehicle(
+            claims.sub,
+            CreateVehicleData {
+                model_id: payload.model_id,
+                nickname: payload.nickname,
+                make: payload.make,
+                model: payload.model,
+                battery_kwh: payload.battery_kwh,
+                connector_types: payload.connector_types,
+                max_ac_kw: payload.max_ac_kw,
+                max_dc_kw: payload.max_dc_kw,
+                ac_phases: payload.ac_phases,
+                charge_curve: payload.charge_curve,
+            },
+        )
+        .await?;
+
+    Ok(HttpResponse::Created().json(VehicleResponse::from(vehicle)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/vehicles",
+    responses(
+        (status = 200, description = "Vehicles of the current user", body = Vec<VehicleResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "vehicles"
+)]
+pub async fn list_my_vehicles(
+    req: HttpRequest,
+    vehicle_service: web::Data<VehicleServiceImpl>,
+    validator: web::Data<JwtValidator>,
+) -> AppResult<HttpResponse> {
+    let claims = require_user_auth(&req, &validator).await?;
 
-    let reviews = review_service.get_reviews_by_station(&station_id).await?;
-    let response: Vec<ReviewResponse> = reviews.into_iter().map(ReviewResponse::from).collect();
+    let vehicles = vehicle_service.list_user_vehicles(&claims.sub).await?;
+    let response: Vec<VehicleResponse> = vehicles.into_iter().map(VehicleResponse::from).collect();
 
     Ok(HttpResponse::Ok().json(response))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/vehicles/{vehicle_id}",
+    params(
+        ("vehicle_id" = String, Path, description = "Vehicle ID")
+    ),
+    responses(
+        (status = 200, description = "Vehicle profile", body = VehicleResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Vehicle belongs to another user"),
+        (status = 404, description = "Vehicle not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "vehicles"
+)]
+pub async fn get_vehicle(
+    req: HttpRequest,
+    vehicle_id: web::Path<String>,
+    vehicle_service: web::Data<VehicleServiceImpl>,
+    validator: web::Data<JwtValidator>,
+) -> AppResult<HttpResponse> {
+    let claims = require_user_auth(&req, &validator).await?;
+
+    let vehicle = vehicle_service
+        .get_vehicle(&vehicle_id, &claims.sub)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(VehicleResponse::from(vehicle)))
+}
+
 #[utoipa::path(
     put,
-    path = "/api/reviews/{review_id}",
+    path = "/api/vehicles/{vehicle_id}",
     params(
-        ("review_id" = String, Path, description = "Review ID")
+        ("vehicle_id" = String, Path, description = "Vehicle ID")
     ),
-    request_body = UpdateReviewRequest,
+    request_body = UpdateVehicleRequest,
     responses(
-        (status = 200, description = "Review updated successfully", body = ReviewResponse),
+        (status = 200, description = "Vehicle profile updated", body = VehicleResponse),
         (status = 400, description = "Invalid request"),
         (status = 401, description = "Unauthorized"),
-        (status = 404, description = "Review not found"),
+        (status = 403, description = "Vehicle belongs to another user"),
+        (status = 404, description = "Vehicle not found"),
         (status = 500, description = "Internal server error")
     ),
     security(("bearer_auth" = [])),
-    tag = "reviews"
+    tag = "vehicles"
 )]
-pub async fn update_review(
+pub async fn update_vehicle(
     req: HttpRequest,
-    review_id: web::Path<String>,
-    payload: web::Json<UpdateReviewRequest>,
-    review_service: web::Data<ReviewServiceImpl>,
+    vehicle_id: web::Path<String>,
+    payload: web::Json<UpdateVehicleRequest>,
+    vehicle_service: web::Data<VehicleServiceImpl>,
     validator: web::Data<JwtValidator>,
 ) -> AppResult<HttpResponse> {
-    let token = extract_bearer_token(&req)?;
-    let claims = validator.validate_user_role(&token).await?;
-
-    let updated_by = claims.sub.clone();
+    let claims = require_user_auth(&req, &validator).await?;
 
-    let review = review_service
-        .update_review(
-            &review_id,
-            payload.rating,
-            payload.review_text.clone(),
-            updated_by,
+    let payload = payload.into_inner();
+    let vehicle = vehicle_service
+        .update_vehicle(
+            &vehicle_id,
+            &claims.sub,
+            UpdateVehicleData {
+                nickname: payload.nickname,
+                battery_kwh: payload.battery_kwh,
+                connector_types: payload.connector_types,
+                max_ac_kw: payload.max_ac_kw,
+                max_dc_kw: payload.max_dc_kw,
+                ac_phases: payload.ac_phases,
+                charge_curve: payload.charge_curve,
+            },
         )
         .await?;
 
-    Ok(HttpResponse::Ok().json(ReviewResponse::from(review)))
+    Ok(HttpResponse::Ok().json(VehicleResponse::from(vehicle)))
 }
 
 #[utoipa::path(
     delete,
-    path = "/api/reviews/{review_id}",
+    path = "/api/vehicles/{vehicle_id}",
     params(
-        ("review_id" = String, Path, description = "Review ID")
+        ("vehicle_id" = String, Path, description = "Vehicle ID")
     ),
     responses(
-        (status = 204, description = "Review deleted successfully"),
+        (status = 204, description = "Vehicle profile deleted"),
         (status = 401, description = "Unauthorized"),
-        (status = 404, description = "Review not found"),
+        (status = 403, description = "Vehicle belongs to another user"),
+        (status = 404, description = "Vehicle not found"),
         (status = 500, description = "Internal server error")
     ),
     security(("bearer_auth" = [])),
-    tag = "reviews"
+    tag = "vehicles"
 )]
-pub async fn delete_review(
+pub async fn delete_vehicle(
     req: HttpRequest,
-    review_id: web::Path<String>,
-    review_service: web::Data<ReviewServiceImpl>,
+    vehicle_id: web::Path<String>,
+    vehicle_service: web::Data<VehicleServiceImpl>,
     validator: web::Data<JwtValidator>,
 ) -> AppResult<HttpResponse> {
-    let token = extract_bearer_token(&req)?;
-    let _ = validator.validate_user_role(&token).await?;
+    let claims = require_user_auth(&req, &validator).await?;
 
-    review_service.delete_review(&review_id).await?;
+    vehicle_service
+        .delete_vehicle(&vehicle_id, &claims.sub)
+        .await?;
 
     Ok(HttpResponse::NoContent().finish())
 }
@@ -218,3 +1646,149 @@ pub async fn get_user_info(
         "username": claims.preferred_username,
     })))
 }
+
+#[utoipa::path(
+    get,
+    path = "/api/user/notifications",
+    params(
+        ("unread_only" = Option<bool>, Query, description = "Only return unread notifications")
+    ),
+    responses(
+        (status = 200, description = "Notifications of the current user, newest first", body = Vec<NotificationResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "user"
+)]
+pub async fn list_notifications(
+    req: HttpRequest,
+    query: web::Query<NotificationsQuery>,
+    notification_service: web::Data<NotificationServiceImpl>,
+    validator: web::Data<JwtValidator>,
+) -> AppResult<HttpResponse> {
+    let claims = require_user_auth(&req, &validator).await?;
+
+    let notifications = notification_service
+        .list_notifications(&claims.sub, query.unread_only.unwrap_or(false))
+        .await?;
+    let response: Vec<NotificationResponse> = notifications
+        .into_iter()
+        .map(NotificationResponse::from)
+        .collect();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/user/notifications/{notification_id}/read",
+    params(
+        ("notification_id" = String, Path, description = "Notification ID")
+    ),
+    responses(
+        (status = 200, description = "Notification marked as read", body = NotificationResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Notification not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "user"
+)]
+pub async fn mark_notification_read(
+    req: HttpRequest,
+    notification_id: web::Path<String>,
+    notification_service: web::Data<NotificationServiceImpl>,
+    validator: web::Data<JwtValidator>,
+) -> AppResult<HttpResponse> {
+    let claims = require_user_auth(&req, &validator).await?;
+
+    let notification = notification_service
+        .mark_notification_read(&notification_id, &claims.sub)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(NotificationResponse::from(notification)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::auth::testing;
+    use crate::domain::entities::UserReview;
+    use crate::domain::moderation::{ModerationPolicy, ModerationStatus};
+    use crate::domain::repositories::{
+        MockNetworkVerificationRepository, MockNotificationRepository, MockReviewRepository,
+        MockStationRepository,
+    };
+    use actix_web::http::StatusCode;
+    use actix_web::{App, test};
+    use chrono::Utc;
+    use std::sync::Arc;
+
+    fn review_service(created: bool) -> ReviewServiceImpl {
+        let mut station_repo = MockStationRepository::new();
+        station_repo.expect_exists().returning(|_| Ok(true));
+        let mut review_repo = MockReviewRepository::new();
+        review_repo.expect_upsert().returning(
+            move |review_id, user_id, station_id, rating, review_text, _| {
+                Ok((
+                    UserReview {
+                        review_id,
+                        user_id: user_id.clone(),
+                        station_id,
+                        rating,
+                        review_text,
+                        created_at: Utc::now(),
+                        updated_at: Utc::now(),
+                        created_by: user_id.clone(),
+                        updated_by: user_id,
+                        moderation_status: ModerationStatus::Visible.as_str().to_string(),
+                        moderation_reason: None,
+                        moderated_by: None,
+                        moderated_at: None,
+                        helpful_votes: 0,
+                    },
+                    created,
+                ))
+            },
+        );
+
+        ReviewServiceImpl::new(
+            Arc::new(review_repo),
+            Arc::new(station_repo),
+            Arc::new(MockNotificationRepository::new()),
+            Arc::new(MockNetworkVerificationRepository::new()),
+            ModerationPolicy::new(&[], 3),
+        )
+    }
+
+    async fn post_review(created: bool) -> StatusCode {
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(review_service(created)))
+                .app_data(web::Data::new(testing::validator()))
+                .route("/api/reviews", web::post().to(create_review)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/api/reviews")
+            .insert_header((
+                "Authorization",
+                format!("Bearer {}", testing::token("user-1", &["user"])),
+            ))
+            .set_json(serde_json::json!({ "station_id": "STA-1", "rating": 5 }))
+            .to_request();
+        test::call_service(&app, req).await.status()
+    }
+
+    #[actix_web::test]
+    async fn create_review_is_created_on_first_insert() {
+        assert_eq!(post_review(true).await, StatusCode::CREATED);
+    }
+
+    #[actix_web::test]
+    async fn create_review_is_ok_when_replacing_the_users_review() {
+        assert_eq!(post_review(false).await, StatusCode::OK);
+    }
+}
@@ -8,19 +8,146 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
         web::scope("/api")
             .service(
                 web::scope("/stations")
-                    .route("/nearby", web::get().to(controllers::get_nearby_stations)),
+                    .route("/nearby", web::get().to(controllers::get_nearby_stations))
+                    .route(
+                        "/{station_id}",
+                        web::get().to(controllers::get_station_detail),
+                    )
+                    .route(
+                        "/{station_id}/amenities",
+                        web::get().to(controllers::get_station_amenities),
+                    )
+                    .route(
+                        "/{station_id}/photos",
+                        web::get().to(controllers::list_station_photos),
+                    )
+                    .route(
+                        "/{station_id}/photos",
+                        web::post().to(controllers::upload_station_photo),
+                    )
+                    .route(
+                        "/{station_id}/cost-estimate",
+                        web::get().to(controllers::get_station_cost_estimate),
+                    )
+                    .route(
+                        "/{station_id}/charging-estimate",
+                        web::get().to(controllers::get_station_charging_estimate),
+                    )
+                    .route(
+                        "/{station_id}/checkins",
+                        web::post().to(controllers::create_checkin),
+                    )
+                    .route(
+                        "/{station_id}/availability",
+                        web::get().to(controllers::get_station_availability),
+                    ),
             )
             .service(
                 web::scope("/reviews")
                     .route("", web::post().to(controllers::create_review))
+                    .route(
+                        "/replies/{reply_id}",
+                        web::put().to(controllers::update_reply),
+                    )
+                    .route(
+                        "/replies/{reply_id}",
+                        web::delete().to(controllers::delete_reply),
+                    )
                     .route(
                         "/station/{station_id}",
                         web::get().to(controllers::get_station_reviews),
                     )
                     .route("/{review_id}", web::put().to(controllers::update_review))
-                    .route("/{review_id}", web::delete().to(controllers::delete_review)),
+                    .route("/{review_id}", web::delete().to(controllers::delete_review))
+                    .route(
+                        "/{review_id}/flags",
+                        web::post().to(controllers::flag_review),
+                    )
+                    .route(
+                        "/{review_id}/helpful",
+                        web::post().to(controllers::vote_review_helpful),
+                    )
+                    .route(
+                        "/{review_id}/helpful",
+                        web::delete().to(controllers::unvote_review_helpful),
+                    )
+                    .route(
+                        "/{review_id}/photos",
+                        web::post().to(controllers::upload_review_photo),
+                    )
+                    .route(
+                        "/{review_id}/reply",
+                        web::post().to(controllers::reply_to_review),
+                    ),
             )
-            .service(web::scope("/user").route("/info", web::get().to(controllers::get_user_info))),
+            .service(
+                web::scope("/media")
+                    .route("/{media_id}", web::get().to(controllers::get_media))
+                    .route("/{media_id}", web::delete().to(controllers::delete_media))
+                    .route(
+                        "/{media_id}/thumbnail",
+                        web::get().to(controllers::get_media_thumbnail),
+                    ),
+            )
+            .service(
+                web::scope("/issues")
+                    .route("", web::post().to(controllers::report_issue))
+                    .route("", web::get().to(controllers::list_issues))
+                    .route("/mine", web::get().to(controllers::list_my_issues))
+                    .route(
+                        "/{issue_id}/status",
+                        web::put().to(controllers::update_issue_status),
+                    )
+                    .route(
+                        "/{issue_id}/mark-faulty",
+                        web::post().to(controllers::mark_issue_connector_faulty),
+                    ),
+            )
+            .service(
+                web::scope("/network-verifications")
+                    .route("", web::post().to(controllers::submit_network_verification))
+                    .route("", web::get().to(controllers::list_network_verifications))
+                    .route(
+                        "/{request_id}",
+                        web::get().to(controllers::get_network_verification),
+                    )
+                    .route(
+                        "/{request_id}/documents",
+                        web::post().to(controllers::upload_verification_document),
+                    )
+                    .route(
+                        "/{request_id}/documents/{document_id}",
+                        web::get().to(controllers::get_verification_document),
+                    ),
+            )
+            .service(web::scope("/networks").route(
+                "/{network_id}/verification/events",
+                web::get().to(controllers::list_network_verification_events),
+            ))
+            .service(
+                web::scope("/vehicles")
+                    .route("", web::get().to(controllers::list_my_vehicles))
+                    .route("", web::post().to(controllers::create_vehicle))
+                    .route("/models", web::get().to(controllers::list_vehicle_models))
+                    .route("/{vehicle_id}", web::get().to(controllers::get_vehicle))
+                    .route("/{vehicle_id}", web::put().to(controllers::update_vehicle))
+                    .route(
+                        "/{vehicle_id}",
+                        web::delete().to(controllers::delete_vehicle),
+                    ),
+            )
+            .service(
+                web::scope("/user")
+                    .route("/info", web::get().to(controllers::get_user_info))
+                    .route(
+                        "/notifications",
+                        web::get().to(controllers::list_notifications),
+                    )
+                    .route(
+                        "/notifications/{notification_id}/read",
+                        web::post().to(controllers::mark_notification_read),
+                    ),
+            ),
     )
     .route("/health", web::get().to(controllers::health_check));
 }
@@ -1,4 +1,5 @@
 use crate::application::dtos::*;
+use crate::domain::entities::ChargeCurvePoint;
 use utoipa::{
     Modify, OpenApi,
     openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
@@ -10,20 +11,94 @@ use crate::presentation::controllers;
 #[openapi(
     paths(
         controllers::get_nearby_stations,
+        controllers::get_station_detail,
+        controllers::get_station_amenities,
+        controllers::get_station_cost_estimate,
+        controllers::get_station_charging_estimate,
+        controllers::create_checkin,
+        controllers::get_station_availability,
         controllers::create_review,
         controllers::get_station_reviews,
+        controllers::vote_review_helpful,
+        controllers::unvote_review_helpful,
         controllers::update_review,
         controllers::delete_review,
+        controllers::flag_review,
+        controllers::reply_to_review,
+        controllers::update_reply,
+        controllers::delete_reply,
+        controllers::upload_station_photo,
+        controllers::list_station_photos,
+        controllers::upload_review_photo,
+        controllers::get_media,
+        controllers::get_media_thumbnail,
+        controllers::delete_media,
+        controllers::report_issue,
+        controllers::list_my_issues,
+        controllers::list_issues,
+        controllers::update_issue_status,
+        controllers::mark_issue_connector_faulty,
+        controllers::submit_network_verification,
+        controllers::list_network_verifications,
+        controllers::get_network_verification,
+        controllers::upload_verification_document,
+        controllers::get_verification_document,
+        controllers::list_network_verification_events,
+        controllers::list_vehicle_models,
+        controllers::create_vehicle,
+        controllers::list_my_vehicles,
+        controllers::get_vehicle,
+        controllers::update_vehicle,
+        controllers::delete_vehicle,
         controllers::get_user_info,
+        controllers::list_notifications,
+        controllers::mark_notification_read,
         controllers::health_check,
     ),
     components(
         schemas(
             NearbyStationsQuery,
             StationResponse,
+            StationDetailResponse,
+            AmenityResponse,
+            ConnectorResponse,
+            PhotoUploadForm,
+            MediaResponse,
+            CostEstimateQuery,
+            CostLineResponse,
+            CostEstimateResponse,
+            ChargingEstimateResponse,
+            ChargeCurvePoint,
+            CheckinRequest,
+            CheckinResponse,
+            StationAvailabilityResponse,
             CreateReviewRequest,
             UpdateReviewRequest,
             ReviewResponse,
+            StationReviewsQuery,
+            ReviewSummaryResponse,
+            StationReviewsResponse,
+            HelpfulVoteResponse,
+            FlagReviewRequest,
+            ReviewFlagResponse,
+            ReviewReplyRequest,
+            ReviewReplyResponse,
+            NotificationResponse,
+            ReportIssueRequest,
+            IssuesQuery,
+            UpdateIssueStatusRequest,
+            IssueResponse,
+            SubmitVerificationRequest,
+            VerificationRequestsQuery,
+            DocumentUploadForm,
+            VerificationRequestResponse,
+            VerificationDocumentResponse,
+            VerificationEventResponse,
+            VerificationRequestDetailResponse,
+            VehicleModelResponse,
+            CreateVehicleRequest,
+            UpdateVehicleRequest,
+            VehicleResponse,
             // Ensure any nested structs within these DTOs are also added here
         )
     ),
@@ -31,6 +106,10 @@ use crate::presentation::controllers;
     tags(
         (name = "stations", description = "Station management and discovery endpoints"),
         (name = "reviews", description = "User reviews and ratings operations"),
+        (name = "media", description = "Station and review photos"),
+        (name = "issues", description = "Connector issue reports and operator tickets"),
+        (name = "verification", description = "Network verification requests of partners, reviewed in admin-service"),
+        (name = "vehicles", description = "User vehicle profiles and reference catalogue"),
         (name = "user", description = "User profile and token information"),
         (name = "health", description = "Service health monitoring")
     )
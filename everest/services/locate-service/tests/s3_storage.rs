@@ -0,0 +1,49 @@
+//! Runs the S3 backend against a real bucket, by default the MinIO of
+//! `deployment/docker-compose.yml`:
+//!
+//!   docker compose -f deployment/docker-compose.yml up -d minio minio-init
+//!   AWS_ACCESS_KEY_ID=minio AWS_SECRET_ACCESS_KEY=password \
+//!     cargo test --test s3_storage -- --ignored
+//!
+//! `S3_TEST_ENDPOINT` and `S3_TEST_BUCKET` point it elsewhere.
+
+use locate_service::core::errors::AppError;
+use locate_service::domain::storage::ObjectStorage;
+use locate_service::infrastructure::storage::s3::S3Storage;
+use std::env;
+
+async fn storage() -> S3Storage {
+    S3Storage::new(
+        env::var("S3_TEST_BUCKET").unwrap_or_else(|_| "everest-media".to_string()),
+        "us-east-1".to_string(),
+        Some(env::var("S3_TEST_ENDPOINT").unwrap_or_else(|_| "http://localhost:9000".to_string())),
+        Some("https://cdn.example.com/media/".to_string()),
+    )
+    .await
+}
+
+#[tokio::test]
+#[ignore = "needs an S3 server, see the module documentation"]
+async fn puts_gets_and_deletes_objects() {
+    let storage = storage().await;
+    let key = format!("tests/{}.jpg", nanoid::nanoid!());
+    let bytes = b"not really a jpeg".to_vec();
+
+    storage
+        .put(&key, bytes.clone(), "image/jpeg")
+        .await
+        .unwrap();
+    assert_eq!(storage.get(&key).await.unwrap(), bytes);
+    assert_eq!(
+        storage.public_url(&key).unwrap(),
+        format!("https://cdn.example.com/media/{}", key)
+    );
+
+    storage.delete(&key).await.unwrap();
+    assert!(matches!(
+        storage.get(&key).await,
+        Err(AppError::NotFound(_))
+    ));
+    // Deleting is idempotent, as S3 answers 204 for missing keys
+    storage.delete(&key).await.unwrap();
+}